@@ -0,0 +1,15 @@
+// build.rs
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    // Generate the gRPC server and client from proto/tfidf.proto, without needing protoc
+    #[cfg(feature = "grpc")]
+    {
+        println!("cargo:rerun-if-changed=proto/tfidf.proto");
+        let descriptors = protox::compile(["tfidf.proto"], ["proto"]).expect("proto/tfidf.proto is valid");
+        tonic_prost_build::configure()
+            .compile_fds(descriptors)
+            .expect("gRPC code generation succeeds");
+    }
+}
@@ -0,0 +1,184 @@
+// src/application/classification/knn.rs
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use crate::application::{ApplicationError, ApplicationResult, DocumentService};
+use crate::domain::{Corpus, Document, DocumentId, DomainResult, SimilarDocument, TfIdf, VectorIndex};
+
+use super::Prediction;
+
+/// The corpus a k-NN classifier was trained on
+struct TrainedCorpus {
+    corpus: Corpus,
+    index: VectorIndex,
+    labels: HashMap<DocumentId, String>,
+}
+
+/// k-nearest-neighbor classifier over TF-IDF vectors
+///
+/// A text is weighted against the training corpus and labeled by majority
+/// vote of the `k` labeled documents with the highest cosine similarity to it.
+/// Ties between labels go to the one with the larger summed similarity.
+/// Documents sharing no term with the text never count as neighbors.
+pub struct KnnClassifier<DS: DocumentService> {
+    document_service: Arc<DS>,
+    label_key: String,
+    k: usize,
+    tfidf: TfIdf,
+    trained: Option<TrainedCorpus>,
+}
+
+impl<DS: DocumentService> KnnClassifier<DS> {
+    /// Create an untrained classifier voting among `k` neighbors, with labels from the given metadata key
+    pub fn new(document_service: Arc<DS>, label_key: impl Into<String>, k: usize) -> Self {
+        Self::with_tfidf(document_service, label_key, k, TfIdf::default())
+    }
+
+    /// Create an untrained classifier weighting vectors with a custom TF-IDF calculator
+    pub fn with_tfidf(document_service: Arc<DS>, label_key: impl Into<String>, k: usize, tfidf: TfIdf) -> Self {
+        Self {
+            document_service,
+            label_key: label_key.into(),
+            k: k.max(1),
+            tfidf,
+            trained: None,
+        }
+    }
+
+    /// Get the metadata key labels are read from
+    pub fn label_key(&self) -> &str {
+        &self.label_key
+    }
+
+    /// Get the number of voting neighbors
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Check if the classifier has been trained
+    pub fn is_trained(&self) -> bool {
+        self.trained.is_some()
+    }
+
+    /// Vectorize an indexed corpus and remember its labeled documents, replacing an earlier one
+    ///
+    /// Returns the number of labeled documents; unlabeled ones still shape the IDF weights.
+    pub fn train(&mut self, corpus: &Corpus) -> ApplicationResult<usize> {
+        let labels: HashMap<DocumentId, String> = corpus.documents()
+            .filter_map(|document| document.map(|document| {
+                document.metadata().get(&self.label_key).map(|label| (document.id().clone(), label.clone()))
+            }).transpose())
+            .collect::<DomainResult<_>>()?;
+
+        if labels.is_empty() {
+            return Err(ApplicationError::InvalidInput(
+                format!("No training document has a '{}' label", self.label_key)
+            ));
+        }
+
+        let index = self.tfidf.build_vector_index(corpus)?;
+        let count = labels.len();
+        self.trained = Some(TrainedCorpus { corpus: corpus.clone(), index, labels });
+        Ok(count)
+    }
+
+    /// Predict the majority label among a text's nearest neighbors
+    pub fn predict(&self, text: &str) -> ApplicationResult<Prediction> {
+        self.predict_all(text)?.into_iter().next().ok_or_else(|| {
+            ApplicationError::NotFound("No training document shares a term with the text".to_string())
+        })
+    }
+
+    /// Get the vote share of every label among a text's nearest neighbors, most voted first
+    pub fn predict_all(&self, text: &str) -> ApplicationResult<Vec<Prediction>> {
+        let neighbors = self.neighbors(text)?;
+        let trained = self.trained()?;
+
+        let mut votes: BTreeMap<&str, (usize, f64)> = BTreeMap::new();
+        for neighbor in &neighbors {
+            let label = trained.labels[neighbor.document_id()].as_str();
+            let entry = votes.entry(label).or_default();
+            entry.0 += 1;
+            entry.1 += neighbor.similarity();
+        }
+
+        let mut ranked: Vec<(&str, usize, f64)> = votes.into_iter()
+            .map(|(label, (count, similarity))| (label, count, similarity))
+            .collect();
+        ranked.sort_by(|a, b| {
+            b.1.cmp(&a.1).then_with(|| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal))
+        });
+
+        Ok(ranked.into_iter()
+            .map(|(label, count, _)| Prediction::new(label, count as f64 / neighbors.len() as f64))
+            .collect())
+    }
+
+    /// Find the `k` labeled training documents most similar to a text, most similar first
+    pub fn neighbors(&self, text: &str) -> ApplicationResult<Vec<SimilarDocument>> {
+        let trained = self.trained()?;
+        let document = self.document_service.analyze_document(&Document::new("query", text))?;
+        let vector = self.tfidf.document_vector(&document, &trained.corpus)?;
+
+        Ok(trained.index.nearest(&vector, trained.index.len())
+            .into_iter()
+            .filter(|neighbor| trained.labels.contains_key(neighbor.document_id()))
+            .take(self.k)
+            .collect())
+    }
+
+    fn trained(&self) -> ApplicationResult<&TrainedCorpus> {
+        self.trained.as_ref().ok_or_else(|| {
+            ApplicationError::NotPermitted("Classifier must be trained before predicting".to_string())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::DocumentServiceImpl;
+    use crate::infrastructure::repository::InMemoryDocumentRepository;
+    use crate::infrastructure::tokenizer::SimpleTokenizer;
+
+    #[test]
+    fn test_train_and_predict() {
+        let service = Arc::new(DocumentServiceImpl::new(
+            Arc::new(InMemoryDocumentRepository::new()),
+            Arc::new(SimpleTokenizer::new()),
+        ));
+
+        let mut corpus = Corpus::new("news", "News");
+        let texts = [
+            ("1", "striker scores goal in final match", Some("sports")),
+            ("2", "keeper saves penalty in the match", Some("sports")),
+            ("3", "compiler adds faster borrow checking", Some("tech")),
+            ("4", "new compiler release for faster builds", Some("tech")),
+            ("5", "weather report for the weekend", None),
+        ];
+        for (id, text, label) in texts {
+            let mut document = service.analyze_document(&Document::new(id, text)).unwrap();
+            if let Some(label) = label {
+                document.set_metadata("topic", label);
+            }
+            corpus.add_document(document).unwrap();
+        }
+        corpus.build_index().unwrap();
+
+        let mut classifier = KnnClassifier::new(service, "topic", 2);
+        assert!(classifier.predict("goal").is_err());
+        assert_eq!(classifier.train(&corpus).unwrap(), 4);
+
+        let neighbors = classifier.neighbors("late goal wins the match").unwrap();
+        assert_eq!(neighbors.len(), 2);
+        assert!(neighbors.iter().all(|n| ["1", "2"].contains(&n.document_id().value())));
+
+        let prediction = classifier.predict("late goal wins the match").unwrap();
+        assert_eq!(prediction.label(), "sports");
+        assert_eq!(prediction.score(), 1.0);
+
+        assert_eq!(classifier.predict("faster compiler").unwrap().label(), "tech");
+        assert!(classifier.predict("zebra").is_err());
+    }
+}
@@ -0,0 +1,48 @@
+// src/application/classification/mod.rs
+
+//! Text classifiers trained on labeled documents.
+//!
+//! Labels are read from a document metadata key, and texts to classify are
+//! analyzed with the same document service pipeline as the training corpus.
+
+mod naive_bayes;
+mod knn;
+
+pub use naive_bayes::NaiveBayesClassifier;
+pub use knn::KnnClassifier;
+
+use serde::{Serialize, Deserialize};
+
+/// A candidate label for a classified text
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Prediction {
+    label: String,
+
+    /// Confidence in the label, between 0 and 1
+    score: f64,
+}
+
+impl Prediction {
+    /// Create a new prediction
+    pub fn new(label: impl Into<String>, score: f64) -> Self {
+        Self { label: label.into(), score }
+    }
+
+    /// Get the predicted label
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Get the confidence in the label
+    pub fn score(&self) -> f64 {
+        self.score
+    }
+}
+
+/// Sort predictions by descending score, breaking ties by label
+fn rank(predictions: &mut [Prediction]) {
+    predictions.sort_by(|a, b| {
+        b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.label.cmp(&b.label))
+    });
+}
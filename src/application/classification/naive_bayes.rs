@@ -0,0 +1,221 @@
+// src/application/classification/naive_bayes.rs
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::application::{ApplicationError, ApplicationResult, DocumentService};
+use crate::domain::Document;
+
+use super::{rank, Prediction};
+
+/// Term counts of the training documents sharing one label
+#[derive(Debug, Clone, Default)]
+struct LabelStats {
+    documents: usize,
+    term_counts: HashMap<String, f64>,
+    total_terms: f64,
+}
+
+/// Multinomial Naive Bayes classifier over document term frequencies
+///
+/// Training documents are expected to be analyzed already, as stored corpus
+/// documents are; their label is read from the `label_key` metadata entry and
+/// unlabeled documents are ignored. Term probabilities use additive (Laplace)
+/// smoothing, and terms never seen in training do not affect a prediction.
+pub struct NaiveBayesClassifier<DS: DocumentService> {
+    document_service: Arc<DS>,
+    label_key: String,
+    smoothing: f64,
+    labels: BTreeMap<String, LabelStats>,
+    vocabulary: HashSet<String>,
+}
+
+impl<DS: DocumentService> NaiveBayesClassifier<DS> {
+    /// Create an untrained classifier reading labels from the given metadata key
+    pub fn new(document_service: Arc<DS>, label_key: impl Into<String>) -> Self {
+        Self {
+            document_service,
+            label_key: label_key.into(),
+            smoothing: 1.0,
+            labels: BTreeMap::new(),
+            vocabulary: HashSet::new(),
+        }
+    }
+
+    /// Get the metadata key labels are read from
+    pub fn label_key(&self) -> &str {
+        &self.label_key
+    }
+
+    /// Get the additive smoothing applied to term counts
+    pub fn smoothing(&self) -> f64 {
+        self.smoothing
+    }
+
+    /// Set the additive smoothing applied to term counts (1.0 is Laplace smoothing)
+    pub fn set_smoothing(&mut self, smoothing: f64) -> ApplicationResult<()> {
+        if !smoothing.is_finite() || smoothing <= 0.0 {
+            return Err(ApplicationError::InvalidInput(
+                format!("Smoothing must be positive, got {}", smoothing)
+            ));
+        }
+        self.smoothing = smoothing;
+        Ok(())
+    }
+
+    /// Check if the classifier has been trained
+    pub fn is_trained(&self) -> bool {
+        !self.labels.is_empty()
+    }
+
+    /// Get the labels seen in training, sorted
+    pub fn labels(&self) -> Vec<&str> {
+        self.labels.keys().map(String::as_str).collect()
+    }
+
+    /// Learn label and term statistics from labeled documents, replacing earlier ones
+    ///
+    /// Returns the number of labeled documents used.
+    pub fn train<'a>(&mut self, documents: impl IntoIterator<Item = &'a Document>) -> ApplicationResult<usize> {
+        let mut labels: BTreeMap<String, LabelStats> = BTreeMap::new();
+        let mut vocabulary = HashSet::new();
+        let mut trained = 0;
+
+        for document in documents {
+            let Some(label) = document.metadata().get(&self.label_key) else {
+                continue;
+            };
+
+            let stats = labels.entry(label.clone()).or_default();
+            stats.documents += 1;
+            for (term, frequency) in document.term_frequencies() {
+                let count = frequency.value() as f64;
+                *stats.term_counts.entry(term.text().to_string()).or_default() += count;
+                stats.total_terms += count;
+                vocabulary.insert(term.text().to_string());
+            }
+            trained += 1;
+        }
+
+        if trained == 0 {
+            return Err(ApplicationError::InvalidInput(
+                format!("No training document has a '{}' label", self.label_key)
+            ));
+        }
+
+        self.labels = labels;
+        self.vocabulary = vocabulary;
+        Ok(trained)
+    }
+
+    /// Predict the most likely label of a text
+    pub fn predict(&self, text: &str) -> ApplicationResult<Prediction> {
+        let mut predictions = self.predict_all(text)?;
+        Ok(predictions.remove(0))
+    }
+
+    /// Get the posterior probability of every label for a text, most likely first
+    pub fn predict_all(&self, text: &str) -> ApplicationResult<Vec<Prediction>> {
+        let document = self.document_service.analyze_document(&Document::new("query", text))?;
+        self.predict_document(&document)
+    }
+
+    /// Get the posterior probability of every label for an analyzed document, most likely first
+    pub fn predict_document(&self, document: &Document) -> ApplicationResult<Vec<Prediction>> {
+        if !self.is_trained() {
+            return Err(ApplicationError::NotPermitted(
+                "Classifier must be trained before predicting".to_string()
+            ));
+        }
+
+        let total_documents: usize = self.labels.values().map(|stats| stats.documents).sum();
+        let vocabulary_size = self.vocabulary.len() as f64;
+
+        let log_likelihoods: Vec<(&String, f64)> = self.labels.iter()
+            .map(|(label, stats)| {
+                let denominator = stats.total_terms + self.smoothing * vocabulary_size;
+                let mut log_likelihood = (stats.documents as f64 / total_documents as f64).ln();
+                for (term, frequency) in document.term_frequencies() {
+                    if !self.vocabulary.contains(term.text()) {
+                        continue;
+                    }
+                    let count = stats.term_counts.get(term.text()).copied().unwrap_or(0.0);
+                    log_likelihood += frequency.value() as f64 * ((count + self.smoothing) / denominator).ln();
+                }
+                (label, log_likelihood)
+            })
+            .collect();
+
+        // Normalize in log space so long texts do not underflow
+        let max = log_likelihoods.iter().map(|(_, value)| *value).fold(f64::NEG_INFINITY, f64::max);
+        let normalizer: f64 = log_likelihoods.iter().map(|(_, value)| (value - max).exp()).sum();
+
+        let mut predictions: Vec<Prediction> = log_likelihoods.into_iter()
+            .map(|(label, value)| Prediction::new(label.clone(), (value - max).exp() / normalizer))
+            .collect();
+        rank(&mut predictions);
+        Ok(predictions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::DocumentServiceImpl;
+    use crate::infrastructure::repository::InMemoryDocumentRepository;
+    use crate::infrastructure::tokenizer::SimpleTokenizer;
+
+    type Service = DocumentServiceImpl<InMemoryDocumentRepository, SimpleTokenizer>;
+
+    fn create_service() -> Arc<Service> {
+        Arc::new(DocumentServiceImpl::new(
+            Arc::new(InMemoryDocumentRepository::new()),
+            Arc::new(SimpleTokenizer::new()),
+        ))
+    }
+
+    fn labeled(service: &Service, id: &str, text: &str, label: Option<&str>) -> Document {
+        let mut document = service.analyze_document(&Document::new(id, text)).unwrap();
+        if let Some(label) = label {
+            document.set_metadata("topic", label);
+        }
+        document
+    }
+
+    #[test]
+    fn test_train_and_predict() {
+        let service = create_service();
+        let documents = vec![
+            labeled(&service, "1", "the striker scored a late goal in the match", Some("sports")),
+            labeled(&service, "2", "the team won the match after extra time", Some("sports")),
+            labeled(&service, "3", "the compiler rejects the borrow of the value", Some("tech")),
+            labeled(&service, "4", "the new compiler release improves build times", Some("tech")),
+            labeled(&service, "5", "an unlabeled note about nothing", None),
+        ];
+
+        let mut classifier = NaiveBayesClassifier::new(service, "topic");
+        assert!(classifier.predict("goal").is_err());
+        assert_eq!(classifier.train(&documents).unwrap(), 4);
+        assert_eq!(classifier.labels(), vec!["sports", "tech"]);
+
+        let prediction = classifier.predict("a goal decided the match").unwrap();
+        assert_eq!(prediction.label(), "sports");
+        assert!(prediction.score() > 0.5);
+
+        let predictions = classifier.predict_all("compiler build").unwrap();
+        assert_eq!(predictions[0].label(), "tech");
+        let total: f64 = predictions.iter().map(Prediction::score).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_train_requires_labels() {
+        let service = create_service();
+        let documents = vec![labeled(&service, "1", "no label here", None)];
+
+        let mut classifier = NaiveBayesClassifier::new(service, "topic");
+        assert!(classifier.train(&documents).is_err());
+        assert!(classifier.set_smoothing(0.0).is_err());
+        assert!(!classifier.is_trained());
+    }
+}
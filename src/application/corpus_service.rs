@@ -1,11 +1,21 @@
 // src/application/corpus_service.rs
 
+use std::collections::HashMap;
+use std::io::{BufRead, Read, Write};
 use std::sync::Arc;
+use std::time::Instant;
 
-use crate::domain::{Corpus, CorpusId, Document, DocumentId};
+use serde::{Serialize, Deserialize};
+use tracing::field::Empty;
+
+use crate::domain::{Corpus, CorpusId, CorpusStats, Document, DocumentId, DomainResult, DuplicatePolicy, MergeReport, Page, PageRequest, TfIdfOptions};
+use crate::infrastructure::export::{self, VocabularyFormat};
+use crate::infrastructure::metrics::{self, Metrics, NoopMetrics};
 use crate::infrastructure::repository::{CorpusRepository, DocumentRepository};
+use crate::infrastructure::telemetry;
 
-use super::{ApplicationError, ApplicationResult, DocumentService};
+use super::events::{self, DomainEvent, EventBus};
+use super::{ApplicationError, ApplicationResult, DocumentService, FieldMapping};
 
 /// Service interface for managing Corpora
 pub trait CorpusService {
@@ -29,6 +39,18 @@ pub trait CorpusService {
     /// Update a corpus's description
     fn update_description(&self, id: &str, new_description: &str) -> ApplicationResult<Corpus>;
     
+    /// Set the TF-IDF options a corpus is scored with (None = the scoring service's defaults)
+    fn update_options(&self, id: &str, options: Option<TfIdfOptions>) -> ApplicationResult<Corpus>;
+    
+    /// Turn automatic indexing on or off, building the index right away when turning it on
+    fn update_auto_index(&self, id: &str, auto_index: bool) -> ApplicationResult<Corpus>;
+    
+    /// Set how a corpus handles documents duplicating one it already holds
+    ///
+    /// Under `DuplicatePolicy::Reject`, `add_document` fails with `DomainError::Duplicate`
+    /// naming the existing document.
+    fn update_duplicate_policy(&self, id: &str, policy: DuplicatePolicy) -> ApplicationResult<Corpus>;
+    
     /// Delete a corpus
     fn delete_corpus(&self, id: &str) -> ApplicationResult<()>;
     
@@ -38,6 +60,24 @@ pub trait CorpusService {
     /// Remove a document from a corpus
     fn remove_document(&self, corpus_id: &str, document_id: &str) -> ApplicationResult<Corpus>;
     
+    /// Remove a document from every corpus holding it, returning the IDs of those corpora
+    ///
+    /// Each corpus's document frequencies are updated; the stored document is kept.
+    fn purge_document(&self, document_id: &str) -> ApplicationResult<Vec<CorpusId>>;
+    
+    /// Delete a document and purge it from every corpus, returning the IDs of the corpora it left
+    fn delete_document(&self, document_id: &str) -> ApplicationResult<Vec<CorpusId>>;
+    
+    /// Check whether any document in a corpus changed or disappeared since it was added
+    ///
+    /// Copies are compared with the stored documents by revision.
+    fn needs_reindex(&self, corpus_id: &str) -> ApplicationResult<bool>;
+    
+    /// Replace outdated copies of a document in every corpus, returning the IDs of the corpora refreshed
+    ///
+    /// If any corpus's duplicate policy rejects the new copy, no corpus is changed.
+    fn refresh_document(&self, document_id: &str) -> ApplicationResult<Vec<CorpusId>>;
+    
     /// Add a stopword to a corpus
     fn add_stopword(&self, corpus_id: &str, word: &str) -> ApplicationResult<Corpus>;
     
@@ -50,14 +90,186 @@ pub trait CorpusService {
     /// List all corpora
     fn list_corpora(&self) -> ApplicationResult<Vec<Corpus>>;
     
+    /// List one page of corpora, ordered by ID
+    fn list_corpora_page(&self, request: PageRequest) -> ApplicationResult<Page<Corpus>>;
+    
     /// Count all corpora
     fn count_corpora(&self) -> ApplicationResult<usize>;
     
     /// Get documents in a corpus
-    fn get_corpus_documents(&self, corpus_id: &str) -> ApplicationResult<Vec<Document>>;
+    fn get_corpus_documents(&self, corpus_id: &str) -> ApplicationResult<Vec<Arc<Document>>>;
     
     /// Count documents in a corpus
     fn count_corpus_documents(&self, corpus_id: &str) -> ApplicationResult<usize>;
+    
+    /// Get document counts, average lengths and vocabularies of a corpus
+    ///
+    /// With a metadata key such as "lang" or "source", the statistics are also
+    /// broken down by the key's values, so vocabulary overlap between groups can be compared.
+    fn corpus_stats(&self, corpus_id: &str, key: Option<&str>) -> ApplicationResult<CorpusStats>;
+    
+    /// Check a corpus for missing documents, stale term statistics and stale indexes
+    fn health_check(&self, corpus_id: &str) -> ApplicationResult<CorpusHealthReport>;
+    
+    /// Check a corpus and fix what is broken by reprocessing and reindexing
+    fn repair_corpus(&self, corpus_id: &str) -> ApplicationResult<CorpusHealthReport>;
+    
+    /// Combine two corpora into a new one, leaving both sources untouched
+    ///
+    /// Where both corpora hold a document ID or metadata key, the first corpus's
+    /// entry wins; the report lists every collision. The new corpus takes the
+    /// first corpus's TF-IDF options (or the second's if it has none), auto-index
+    /// setting, duplicate policy and flagged duplicates.
+    fn merge_corpora(
+        &self,
+        first_id: &str,
+        second_id: &str,
+        new_id: &str
+    ) -> ApplicationResult<(Corpus, MergeReport)>;
+    
+    /// Write a corpus with its metadata, stopwords, options, duplicate policy and documents as a single JSON archive
+    ///
+    /// Fails if any of its documents is missing from the document repository.
+    fn export_corpus(&self, corpus_id: &str, writer: &mut dyn Write) -> ApplicationResult<()>;
+    
+    /// Recreate a corpus and its documents from an archive written by `export_corpus`
+    ///
+    /// Fails if a corpus with the archived ID already exists, or if a stored
+    /// document shares an archived document's ID but not its content, title,
+    /// fields or metadata. Nothing is saved until the whole archive is checked.
+    fn import_corpus(&self, reader: &mut dyn Read) -> ApplicationResult<Corpus>;
+    
+    /// Write a corpus's vocabulary with each term's document frequency, collection frequency and IDF
+    ///
+    /// The corpus must be indexed, as the vocabulary is kept by the index.
+    fn export_vocabulary(&self, corpus_id: &str, format: VocabularyFormat, writer: &mut dyn Write) -> ApplicationResult<()>;
+    
+    /// Restrict scoring of a corpus to the terms of a vocabulary file, e.g. one written by `export_vocabulary`
+    ///
+    /// The terms become the `fixed_vocabulary` of the corpus's options, starting
+    /// from the default options if it has none.
+    fn import_vocabulary(&self, corpus_id: &str, reader: &mut dyn BufRead, format: VocabularyFormat) -> ApplicationResult<Corpus>;
+    
+    /// Create documents from newline-delimited JSON and add them all to a corpus, saving it once
+    ///
+    /// Nothing is saved unless every record is valid and accepted by the
+    /// corpus. A repository error while saving can still leave the documents
+    /// of earlier records created.
+    fn import_jsonl(
+        &self,
+        corpus_id: &str,
+        reader: &mut dyn BufRead,
+        mapping: &FieldMapping
+    ) -> ApplicationResult<Corpus>;
+}
+
+/// Self-contained, portable form of a corpus
+#[derive(Debug, Serialize, Deserialize)]
+struct CorpusArchive {
+    /// Archive layout version, checked on import
+    format_version: u32,
+    id: CorpusId,
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    metadata: HashMap<String, String>,
+    #[serde(default)]
+    stopwords: Vec<String>,
+    #[serde(default)]
+    indexed: bool,
+    #[serde(default)]
+    auto_index: bool,
+    #[serde(default)]
+    options: Option<TfIdfOptions>,
+    #[serde(default)]
+    duplicate_policy: DuplicatePolicy,
+    #[serde(default)]
+    documents: Vec<Arc<Document>>,
+}
+
+impl CorpusArchive {
+    /// Archive layout version written by this crate version
+    const FORMAT_VERSION: u32 = 1;
+    
+    fn of(corpus: &Corpus) -> DomainResult<Self> {
+        let mut stopwords: Vec<_> = corpus.stopwords().cloned().collect();
+        stopwords.sort();
+        let mut documents: Vec<Arc<Document>> = corpus.shared_documents()
+            .map(|document| document.cloned())
+            .collect::<DomainResult<_>>()?;
+        documents.sort_by(|a, b| a.id().value().cmp(b.id().value()));
+        
+        Ok(Self {
+            format_version: Self::FORMAT_VERSION,
+            id: corpus.id().clone(),
+            name: corpus.name().to_string(),
+            description: corpus.description().map(str::to_string),
+            metadata: corpus.metadata().clone(),
+            stopwords,
+            indexed: corpus.is_indexed(),
+            auto_index: corpus.auto_index(),
+            options: corpus.options().cloned(),
+            duplicate_policy: corpus.duplicate_policy(),
+            documents,
+        })
+    }
+}
+
+/// Check whether two documents were created from the same text, title, fields and metadata
+fn same_source(a: &Document, b: &Document) -> bool {
+    a.content() == b.content() && a.title() == b.title() && a.fields() == b.fields() && a.metadata() == b.metadata()
+}
+
+/// A problem found by a corpus health check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HealthIssue {
+    /// The corpus has never been indexed
+    NotIndexed,
+    
+    /// The corpus references a document missing from the document repository
+    MissingDocument(DocumentId),
+    
+    /// A document's stored term statistics disagree with re-analysis of its content
+    StaleDocument(DocumentId),
+    
+    /// The document frequency index disagrees with the corpus documents
+    StaleIndex,
+}
+
+/// Result of a corpus health check
+#[derive(Debug, Clone)]
+pub struct CorpusHealthReport {
+    /// The corpus that was checked
+    corpus_id: CorpusId,
+    
+    /// Problems found
+    issues: Vec<HealthIssue>,
+    
+    /// Whether the issues were repaired
+    repaired: bool,
+}
+
+impl CorpusHealthReport {
+    /// Get the ID of the checked corpus
+    pub fn corpus_id(&self) -> &CorpusId {
+        &self.corpus_id
+    }
+    
+    /// Get the problems found
+    pub fn issues(&self) -> &[HealthIssue] {
+        &self.issues
+    }
+    
+    /// Check whether the issues were repaired
+    pub fn is_repaired(&self) -> bool {
+        self.repaired
+    }
+    
+    /// Check whether no problems were found
+    pub fn is_healthy(&self) -> bool {
+        self.issues.is_empty()
+    }
 }
 
 /// Implementation of the CorpusService
@@ -70,6 +282,9 @@ where
     corpus_repository: Arc<CR>,
     document_repository: Arc<DR>,
     document_service: Arc<DS>,
+    metrics: Arc<dyn Metrics>,
+    events: Option<Arc<EventBus>>,
+    reanalyze_documents: bool,
 }
 
 impl<CR, DR, DS> CorpusServiceImpl<CR, DR, DS>
@@ -88,8 +303,72 @@ where
             corpus_repository,
             document_repository,
             document_service,
+            metrics: Arc::new(NoopMetrics),
+            events: None,
+            reanalyze_documents: true,
         }
     }
+
+    /// Set whether health checks re-analyze documents to find stale term statistics (on by default)
+    ///
+    /// Turn it off when the document service may not analyze documents the way
+    /// the corpora were indexed; repairs then fail instead of overwriting
+    /// documents with differently analyzed terms.
+    pub fn set_reanalyze_documents(&mut self, reanalyze_documents: bool) {
+        self.reanalyze_documents = reanalyze_documents;
+    }
+
+    /// Record index build counts and durations
+    pub fn set_metrics(&mut self, metrics: Arc<dyn Metrics>) {
+        self.metrics = metrics;
+    }
+
+    /// Publish corpus events after each saved change (None = publish nothing)
+    pub fn set_event_bus(&mut self, events: Option<Arc<EventBus>>) {
+        self.events = events;
+    }
+    
+    /// Inspect a corpus without modifying anything
+    fn diagnose(&self, corpus: &Corpus) -> ApplicationResult<Vec<HealthIssue>> {
+        let mut issues = Vec::new();
+        
+        if !corpus.is_indexed() {
+            issues.push(HealthIssue::NotIndexed);
+        }
+        
+        // Load documents held by ID up front, so lookup failures are reported rather than read as missing documents
+        corpus.load_documents()?;
+        let mut document_ids: Vec<&DocumentId> = corpus.document_ids().collect();
+        document_ids.sort_by(|a, b| a.value().cmp(b.value()));
+        for document_id in document_ids {
+            let stored = self.document_repository.find(document_id).map_err(|e| {
+                ApplicationError::RepositoryError(format!("Error retrieving document: {}", e))
+            })?;
+            
+            let (Some(stored), Some(document)) = (stored, corpus.get_document(document_id)?) else {
+                issues.push(HealthIssue::MissingDocument(document_id.clone()));
+                continue;
+            };
+            
+            if !self.reanalyze_documents {
+                continue;
+            }
+            
+            // Compare both the corpus copy and the repository copy against a fresh analysis
+            let fresh = self.document_service.analyze_document(&stored)?;
+            if fresh.term_frequencies() != document.term_frequencies()
+                || fresh.term_frequencies() != stored.term_frequencies()
+            {
+                issues.push(HealthIssue::StaleDocument(document_id.clone()));
+            }
+        }
+        
+        if corpus.has_stale_index()? {
+            issues.push(HealthIssue::StaleIndex);
+        }
+        
+        Ok(issues)
+    }
 }
 
 impl<CR, DR, DS> CorpusService for CorpusServiceImpl<CR, DR, DS>
@@ -146,13 +425,7 @@ where
     }
     
     fn get_corpus(&self, id: &str) -> ApplicationResult<Corpus> {
-        let corpus_id = CorpusId::new(id);
-        
-        self.corpus_repository.find(&corpus_id).map_err(|e| {
-            ApplicationError::RepositoryError(format!("Error retrieving corpus: {}", e))
-        })?.ok_or_else(|| {
-            ApplicationError::NotFound(format!("Corpus with ID '{}' not found", id))
-        })
+        Ok(super::find_corpus(&*self.corpus_repository, &CorpusId::new(id))?.corpus().clone())
     }
     
     fn update_name(&self, id: &str, new_name: &str) -> ApplicationResult<Corpus> {
@@ -197,6 +470,56 @@ where
         Ok(corpus)
     }
     
+    fn update_options(&self, id: &str, options: Option<TfIdfOptions>) -> ApplicationResult<Corpus> {
+        let corpus_id = CorpusId::new(id);
+        
+        // Get existing corpus
+        let mut corpus = self.corpus_repository.find(&corpus_id).map_err(|e| {
+            ApplicationError::RepositoryError(format!("Error retrieving corpus: {}", e))
+        })?.ok_or_else(|| {
+            ApplicationError::NotFound(format!("Corpus with ID '{}' not found", id))
+        })?;
+        
+        corpus.set_options(options);
+        
+        // Save updated corpus
+        self.corpus_repository.save(&corpus).map_err(|e| {
+            ApplicationError::RepositoryError(format!("Error saving corpus: {}", e))
+        })?;
+        
+        Ok(corpus)
+    }
+    
+    fn update_auto_index(&self, id: &str, auto_index: bool) -> ApplicationResult<Corpus> {
+        let mut corpus = self.get_corpus(id)?;
+        let was_indexed = corpus.is_indexed();
+        corpus.set_auto_index(auto_index)?;
+        
+        self.corpus_repository.save(&corpus).map_err(|e| {
+            ApplicationError::RepositoryError(format!("Error saving corpus: {}", e))
+        })?;
+        if !was_indexed && corpus.is_indexed() {
+            events::publish(&self.events, DomainEvent::CorpusIndexed {
+                corpus_id: corpus.id().clone(),
+                documents: corpus.document_count(),
+                terms: corpus.vocabulary().len(),
+            });
+        }
+        
+        Ok(corpus)
+    }
+    
+    fn update_duplicate_policy(&self, id: &str, policy: DuplicatePolicy) -> ApplicationResult<Corpus> {
+        let mut corpus = self.get_corpus(id)?;
+        corpus.set_duplicate_policy(policy)?;
+        
+        self.corpus_repository.save(&corpus).map_err(|e| {
+            ApplicationError::RepositoryError(format!("Error saving corpus: {}", e))
+        })?;
+        
+        Ok(corpus)
+    }
+    
     fn delete_corpus(&self, id: &str) -> ApplicationResult<()> {
         let corpus_id = CorpusId::new(id);
         
@@ -213,6 +536,7 @@ where
         self.corpus_repository.delete(&corpus_id).map_err(|e| {
             ApplicationError::RepositoryError(format!("Error deleting corpus: {}", e))
         })?;
+        events::publish(&self.events, DomainEvent::CorpusDeleted { corpus_id });
         
         Ok(())
     }
@@ -222,11 +546,7 @@ where
         let document_id_obj = DocumentId::new(document_id);
         
         // Get existing corpus
-        let mut corpus = self.corpus_repository.find(&corpus_id).map_err(|e| {
-            ApplicationError::RepositoryError(format!("Error retrieving corpus: {}", e))
-        })?.ok_or_else(|| {
-            ApplicationError::NotFound(format!("Corpus with ID '{}' not found", corpus_id.value()))
-        })?;
+        let mut corpus = super::find_corpus(&*self.corpus_repository, &corpus_id)?.corpus().clone();
         
         // Check if document exists
         let document = self.document_repository.find(&document_id_obj).map_err(|e| {
@@ -243,19 +563,19 @@ where
         }
         
         // Add document to corpus
+        // An indexed corpus updates its index in place
         corpus.add_document(document).map_err(|e| {
             ApplicationError::DomainError(e)
         })?;
         
-        // Rebuild index if corpus was already indexed
-        if corpus.is_indexed() {
-            corpus.build_index();
-        }
-        
         // Save updated corpus
         self.corpus_repository.save(&corpus).map_err(|e| {
             ApplicationError::RepositoryError(format!("Error saving corpus: {}", e))
         })?;
+        events::publish(&self.events, DomainEvent::DocumentAddedToCorpus {
+            corpus_id: corpus_id.clone(),
+            document_id: document_id_obj,
+        });
         
         Ok(corpus)
     }
@@ -265,11 +585,7 @@ where
         let document_id_obj = DocumentId::new(document_id);
         
         // Get existing corpus
-        let mut corpus = self.corpus_repository.find(&corpus_id).map_err(|e| {
-            ApplicationError::RepositoryError(format!("Error retrieving corpus: {}", e))
-        })?.ok_or_else(|| {
-            ApplicationError::NotFound(format!("Corpus with ID '{}' not found", corpus_id.value()))
-        })?;
+        let mut corpus = super::find_corpus(&*self.corpus_repository, &corpus_id)?.corpus().clone();
         
         // Remove document from corpus
         corpus.remove_document(&document_id_obj).map_err(|e| {
@@ -280,19 +596,102 @@ where
         self.corpus_repository.save(&corpus).map_err(|e| {
             ApplicationError::RepositoryError(format!("Error saving corpus: {}", e))
         })?;
+        events::publish(&self.events, DomainEvent::DocumentRemovedFromCorpus {
+            corpus_id: corpus_id.clone(),
+            document_id: document_id_obj,
+        });
         
         Ok(corpus)
     }
     
+    fn purge_document(&self, document_id: &str) -> ApplicationResult<Vec<CorpusId>> {
+        let document_id = DocumentId::new(document_id);
+        
+        // Collect the affected corpora first, so saving does not disturb the iteration
+        let mut holders = Vec::new();
+        for corpus in self.corpus_repository.iter_corpora() {
+            let corpus = corpus.map_err(|e| {
+                ApplicationError::RepositoryError(format!("Error retrieving corpus: {}", e))
+            })?;
+            if corpus.contains_document(&document_id) {
+                holders.push(corpus);
+            }
+        }
+        
+        let mut purged = Vec::with_capacity(holders.len());
+        for mut corpus in holders {
+            corpus.remove_document(&document_id)?;
+            self.corpus_repository.save(&corpus).map_err(|e| {
+                ApplicationError::RepositoryError(format!("Error saving corpus: {}", e))
+            })?;
+            events::publish(&self.events, DomainEvent::DocumentRemovedFromCorpus {
+                corpus_id: corpus.id().clone(),
+                document_id: document_id.clone(),
+            });
+            purged.push(corpus.id().clone());
+        }
+        
+        Ok(purged)
+    }
+    
+    fn needs_reindex(&self, corpus_id: &str) -> ApplicationResult<bool> {
+        let corpus = self.get_corpus(corpus_id)?;
+        
+        for document_id in corpus.document_ids() {
+            let stored = self.document_repository.find(document_id).map_err(|e| {
+                ApplicationError::RepositoryError(format!("Error retrieving document: {}", e))
+            })?;
+            match stored {
+                Some(stored) if !corpus.has_outdated_copy(&stored) => {},
+                _ => return Ok(true),
+            }
+        }
+        
+        Ok(false)
+    }
+    
+    fn refresh_document(&self, document_id: &str) -> ApplicationResult<Vec<CorpusId>> {
+        let document = self.document_service.get_document(document_id)?;
+        
+        let mut outdated = Vec::new();
+        for corpus in self.corpus_repository.iter_corpora() {
+            let mut corpus = corpus.map_err(|e| {
+                ApplicationError::RepositoryError(format!("Error retrieving corpus: {}", e))
+            })?;
+            if corpus.has_outdated_copy(&document) {
+                // Replace in every corpus before saving any, so a rejected duplicate leaves all of them unchanged
+                corpus.replace_document(document.clone())?;
+                outdated.push(corpus);
+            }
+        }
+        
+        let mut refreshed = Vec::with_capacity(outdated.len());
+        for corpus in outdated {
+            self.corpus_repository.save(&corpus).map_err(|e| {
+                ApplicationError::RepositoryError(format!("Error saving corpus: {}", e))
+            })?;
+            events::publish(&self.events, DomainEvent::DocumentRefreshedInCorpus {
+                corpus_id: corpus.id().clone(),
+                document_id: document.id().clone(),
+            });
+            refreshed.push(corpus.id().clone());
+        }
+        
+        Ok(refreshed)
+    }
+    
+    fn delete_document(&self, document_id: &str) -> ApplicationResult<Vec<CorpusId>> {
+        let purged = self.purge_document(document_id)?;
+        self.document_service.delete_document(document_id)?;
+        
+        Ok(purged)
+    }
+    
     fn add_stopword(&self, corpus_id: &str, word: &str) -> ApplicationResult<Corpus> {
         let corpus_id = CorpusId::new(corpus_id);
         
         // Get existing corpus
-        let mut corpus = self.corpus_repository.find(&corpus_id).map_err(|e| {
-            ApplicationError::RepositoryError(format!("Error retrieving corpus: {}", e))
-        })?.ok_or_else(|| {
-            ApplicationError::NotFound(format!("Corpus with ID '{}' not found", corpus_id.value()))
-        })?;
+        let mut corpus = super::find_corpus(&*self.corpus_repository, &corpus_id)?.corpus().clone();
         
         // Add stopword
         corpus.add_stopword(word.to_lowercase());
@@ -327,6 +726,7 @@ where
     }
     
     fn build_index(&self, corpus_id: &str) -> ApplicationResult<Corpus> {
+        let span = tracing::info_span!(target: telemetry::INDEX, "build_index", corpus_id, documents = Empty, terms = Empty).entered();
         let corpus_id = CorpusId::new(corpus_id);
         
         // Get existing corpus
@@ -337,12 +737,22 @@ where
         })?;
         
         // Build index
-        corpus.build_index();
+        let started = Instant::now();
+        corpus.build_index()?;
+        self.metrics.increment_counter(metrics::INDEX_BUILDS, 1);
+        self.metrics.record_duration(metrics::INDEX_BUILD_SECONDS, started);
+        span.record("documents", corpus.document_count());
+        span.record("terms", corpus.vocabulary().len());
         
         // Save updated corpus
         self.corpus_repository.save(&corpus).map_err(|e| {
             ApplicationError::RepositoryError(format!("Error saving corpus: {}", e))
         })?;
+        events::publish(&self.events, DomainEvent::CorpusIndexed {
+            corpus_id,
+            documents: corpus.document_count(),
+            terms: corpus.vocabulary().len(),
+        });
         
         Ok(corpus)
     }
@@ -353,13 +763,19 @@ where
         })
     }
     
+    fn list_corpora_page(&self, request: PageRequest) -> ApplicationResult<Page<Corpus>> {
+        self.corpus_repository.find_page(request).map_err(|e| {
+            ApplicationError::RepositoryError(format!("Error listing corpora: {}", e))
+        })
+    }
+    
     fn count_corpora(&self) -> ApplicationResult<usize> {
         self.corpus_repository.count().map_err(|e| {
             ApplicationError::RepositoryError(format!("Error counting corpora: {}", e))
         })
     }
     
-    fn get_corpus_documents(&self, corpus_id: &str) -> ApplicationResult<Vec<Document>> {
+    fn get_corpus_documents(&self, corpus_id: &str) -> ApplicationResult<Vec<Arc<Document>>> {
         let corpus = self.get_corpus(corpus_id)?;
         
         let document_ids: Vec<_> = corpus.document_ids()
@@ -377,14 +793,248 @@ where
     }
     
     fn count_corpus_documents(&self, corpus_id: &str) -> ApplicationResult<usize> {
-        let corpus = self.get_corpus(corpus_id)?;
+        // Counting needs no documents, so skip loading them
+        let corpus = self.corpus_repository.find(&CorpusId::new(corpus_id)).map_err(|e| {
+            ApplicationError::RepositoryError(format!("Error retrieving corpus: {}", e))
+        })?.ok_or_else(|| {
+            ApplicationError::NotFound(format!("Corpus with ID '{}' not found", corpus_id))
+        })?;
         Ok(corpus.document_count())
     }
+    
+    fn corpus_stats(&self, corpus_id: &str, key: Option<&str>) -> ApplicationResult<CorpusStats> {
+        let corpus = self.get_corpus(corpus_id)?;
+        Ok(match key {
+            Some(key) => CorpusStats::by_metadata(&corpus, key)?,
+            None => CorpusStats::from_corpus(&corpus)?
+        })
+    }
+    
+    fn health_check(&self, corpus_id: &str) -> ApplicationResult<CorpusHealthReport> {
+        let corpus = self.get_corpus(corpus_id)?;
+        let issues = self.diagnose(&corpus)?;
+        
+        Ok(CorpusHealthReport {
+            corpus_id: corpus.id().clone(),
+            issues,
+            repaired: false,
+        })
+    }
+    
+    fn repair_corpus(&self, corpus_id: &str) -> ApplicationResult<CorpusHealthReport> {
+        if !self.reanalyze_documents {
+            return Err(ApplicationError::InvalidInput(format!(
+                "Cannot repair corpus '{}' without the analyzer its documents were indexed with", corpus_id
+            )));
+        }
+        
+        let mut corpus = self.get_corpus(corpus_id)?;
+        let issues = self.diagnose(&corpus)?;
+        
+        if issues.is_empty() {
+            return Ok(CorpusHealthReport {
+                corpus_id: corpus.id().clone(),
+                issues,
+                repaired: false,
+            });
+        }
+        
+        for issue in &issues {
+            match issue {
+                HealthIssue::MissingDocument(document_id) => {
+                    corpus.remove_document(document_id)?;
+                },
+                HealthIssue::StaleDocument(document_id) => {
+                    let fresh = self.document_service.process_document(document_id.value())?;
+                    corpus.remove_document(document_id)?;
+                    corpus.add_document(fresh)?;
+                },
+                HealthIssue::NotIndexed | HealthIssue::StaleIndex => {}
+            }
+        }
+        
+        // Reindex from scratch so document frequencies match the repaired documents
+        corpus.build_index()?;
+        
+        self.corpus_repository.save(&corpus).map_err(|e| {
+            ApplicationError::RepositoryError(format!("Error saving corpus: {}", e))
+        })?;
+        
+        Ok(CorpusHealthReport {
+            corpus_id: corpus.id().clone(),
+            issues,
+            repaired: true,
+        })
+    }
+    
+    fn merge_corpora(
+        &self,
+        first_id: &str,
+        second_id: &str,
+        new_id: &str
+    ) -> ApplicationResult<(Corpus, MergeReport)> {
+        if self.corpus_repository.exists(&CorpusId::new(new_id)).map_err(|e| {
+            ApplicationError::RepositoryError(format!("Error checking existence: {}", e))
+        })? {
+            return Err(ApplicationError::InvalidInput(
+                format!("Corpus with ID '{}' already exists", new_id)
+            ));
+        }
+        
+        let first = self.get_corpus(first_id)?;
+        let second = self.get_corpus(second_id)?;
+        
+        let mut merged = match first.description() {
+            Some(description) => Corpus::with_description(new_id, first.name(), description),
+            None => Corpus::new(new_id, first.name()),
+        };
+        // The first corpus's settings win, so its policy also checks the second corpus's documents
+        merged.set_options(first.options().or(second.options()).cloned());
+        merged.set_auto_index(first.auto_index())?;
+        let duplicate_policy = first.duplicate_policy();
+        merged.merge(first)?;
+        merged.set_duplicate_policy(duplicate_policy)?;
+        let report = merged.merge(second)?;
+        
+        self.corpus_repository.save(&merged).map_err(|e| {
+            ApplicationError::RepositoryError(format!("Error saving corpus: {}", e))
+        })?;
+        
+        Ok((merged, report))
+    }
+    
+    fn export_corpus(&self, corpus_id: &str, writer: &mut dyn Write) -> ApplicationResult<()> {
+        let corpus = self.get_corpus(corpus_id)?;
+        if let Some(document_id) = corpus.load_documents()?.first() {
+            return Err(ApplicationError::NotFound(format!(
+                "Document '{}' of corpus '{}' is missing from the document repository", document_id.value(), corpus_id
+            )));
+        }
+        
+        serde_json::to_writer(writer, &CorpusArchive::of(&corpus)?).map_err(|e| {
+            ApplicationError::Other(format!("Error writing corpus archive: {}", e))
+        })
+    }
+    
+    fn export_vocabulary(&self, corpus_id: &str, format: VocabularyFormat, writer: &mut dyn Write) -> ApplicationResult<()> {
+        let corpus = self.get_corpus(corpus_id)?;
+        if !corpus.is_indexed() {
+            return Err(ApplicationError::NotPermitted(format!("Corpus '{}' must be indexed first", corpus_id)));
+        }
+        
+        export::export_vocabulary(&corpus.vocabulary_entries(), format, writer).map_err(|e| {
+            ApplicationError::Other(format!("Error writing vocabulary: {}", e))
+        })
+    }
+    
+    fn import_vocabulary(&self, corpus_id: &str, reader: &mut dyn BufRead, format: VocabularyFormat) -> ApplicationResult<Corpus> {
+        let corpus = self.get_corpus(corpus_id)?;
+        let terms = export::import_vocabulary(reader, format).map_err(|e| {
+            ApplicationError::InvalidInput(format!("Invalid vocabulary: {}", e))
+        })?;
+        
+        let options = corpus.options().cloned().unwrap_or_default().with_fixed_vocabulary(terms);
+        self.update_options(corpus_id, Some(options))
+    }
+    
+    fn import_corpus(&self, reader: &mut dyn Read) -> ApplicationResult<Corpus> {
+        let archive: CorpusArchive = serde_json::from_reader(reader).map_err(|e| {
+            ApplicationError::InvalidInput(format!("Invalid corpus archive: {}", e))
+        })?;
+        
+        if archive.format_version > CorpusArchive::FORMAT_VERSION {
+            return Err(ApplicationError::InvalidInput(
+                format!("Unsupported corpus archive version {}", archive.format_version)
+            ));
+        }
+        
+        if self.corpus_repository.exists(&archive.id).map_err(|e| {
+            ApplicationError::RepositoryError(format!("Error checking existence: {}", e))
+        })? {
+            return Err(ApplicationError::InvalidInput(
+                format!("Corpus with ID '{}' already exists", archive.id.value())
+            ));
+        }
+        
+        let mut corpus = match archive.description {
+            Some(description) => Corpus::with_description(archive.id.value(), archive.name, description),
+            None => Corpus::new(archive.id.value(), archive.name),
+        };
+        corpus.metadata_mut().extend(archive.metadata);
+        corpus.add_stopwords(archive.stopwords);
+        
+        // Build the whole corpus before storing anything, so an invalid archive leaves the repositories untouched
+        let mut new_documents = Vec::new();
+        for document in &archive.documents {
+            match self.document_repository.find(document.id()).map_err(|e| {
+                ApplicationError::RepositoryError(format!("Error finding document: {}", e))
+            })? {
+                Some(stored) if !same_source(&stored, document) => {
+                    return Err(ApplicationError::InvalidInput(
+                        format!("Document with ID '{}' already exists with different content", document.id().value())
+                    ));
+                },
+                Some(_) => {},
+                None => new_documents.push(Arc::clone(document)),
+            }
+            corpus.add_document(Arc::clone(document))?;
+        }
+        
+        // The archived documents were already checked against the policy when they were added
+        corpus.set_options(archive.options);
+        corpus.set_duplicate_policy(archive.duplicate_policy)?;
+        
+        if archive.indexed {
+            corpus.build_index()?;
+        }
+        corpus.set_auto_index(archive.auto_index)?;
+        
+        for document in &new_documents {
+            self.document_repository.save(document).map_err(|e| {
+                ApplicationError::RepositoryError(format!("Error saving document: {}", e))
+            })?;
+        }
+        self.corpus_repository.save(&corpus).map_err(|e| {
+            ApplicationError::RepositoryError(format!("Error saving corpus: {}", e))
+        })?;
+        
+        Ok(corpus)
+    }
+    
+    fn import_jsonl(
+        &self,
+        corpus_id: &str,
+        reader: &mut dyn BufRead,
+        mapping: &FieldMapping
+    ) -> ApplicationResult<Corpus> {
+        let mut corpus = self.get_corpus(corpus_id)?;
+        
+        // Add every record to the corpus before storing anything, so a rejected one leaves the repositories untouched
+        let mut documents = Vec::new();
+        for document in self.document_service.read_jsonl(reader, mapping) {
+            let document = Arc::new(document?);
+            corpus.add_document(Arc::clone(&document))?;
+            documents.push(document);
+        }
+        
+        for document in &documents {
+            self.document_repository.save(document).map_err(|e| {
+                ApplicationError::RepositoryError(format!("Error saving document: {}", e))
+            })?;
+            events::publish(&self.events, DomainEvent::DocumentCreated { document_id: document.id().clone() });
+        }
+        self.corpus_repository.save(&corpus).map_err(|e| {
+            ApplicationError::RepositoryError(format!("Error saving corpus: {}", e))
+        })?;
+        
+        Ok(corpus)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::DomainError;
     use crate::infrastructure::repository::{InMemoryCorpusRepository, InMemoryDocumentRepository};
     use crate::infrastructure::tokenizer::SimpleTokenizer;
     use crate::application::document_service::DocumentServiceImpl;
@@ -455,6 +1105,135 @@ mod tests {
         assert_eq!(corpus.document_count(), 0);
     }
     
+    #[test]
+    fn test_delete_document_from_corpora() {
+        let (doc_service, corpus_service) = create_service();
+        
+        doc_service.create_document("doc1", "Rust is fast").unwrap();
+        doc_service.create_document("doc2", "Go is simple").unwrap();
+        for id in ["corpus1", "corpus2", "corpus3"] {
+            corpus_service.create_corpus(id, "Test Corpus").unwrap();
+            corpus_service.add_document(id, "doc2").unwrap();
+        }
+        corpus_service.add_document("corpus1", "doc1").unwrap();
+        corpus_service.add_document("corpus3", "doc1").unwrap();
+        corpus_service.build_index("corpus1").unwrap();
+        
+        let mut purged = corpus_service.delete_document("doc1").unwrap();
+        purged.sort_by(|a, b| a.value().cmp(b.value()));
+        assert_eq!(purged, vec![CorpusId::new("corpus1"), CorpusId::new("corpus3")]);
+        assert!(doc_service.get_document("doc1").is_err());
+        
+        let corpus = corpus_service.get_corpus("corpus1").unwrap();
+        assert_eq!(corpus.document_count(), 1);
+        assert_eq!(corpus.document_frequency(&crate::domain::Term::new("rust")), 0);
+        assert_eq!(corpus.document_frequency(&crate::domain::Term::new("is")), 1);
+        
+        // Nothing holds the document any more
+        assert!(corpus_service.purge_document("doc1").unwrap().is_empty());
+    }
+    
+    #[test]
+    fn test_refresh_document() {
+        let (doc_service, corpus_service) = create_service();
+        
+        doc_service.create_document("doc1", "Rust is fast").unwrap();
+        corpus_service.create_corpus("corpus1", "Test Corpus").unwrap();
+        corpus_service.add_document("corpus1", "doc1").unwrap();
+        corpus_service.build_index("corpus1").unwrap();
+        assert!(!corpus_service.needs_reindex("corpus1").unwrap());
+        
+        doc_service.update_content("doc1", "Go is simple").unwrap();
+        assert!(corpus_service.needs_reindex("corpus1").unwrap());
+        
+        assert_eq!(corpus_service.refresh_document("doc1").unwrap(), vec![CorpusId::new("corpus1")]);
+        assert!(!corpus_service.needs_reindex("corpus1").unwrap());
+        let corpus = corpus_service.get_corpus("corpus1").unwrap();
+        assert_eq!(corpus.document_frequency(&crate::domain::Term::new("go")), 1);
+        assert_eq!(corpus.document_frequency(&crate::domain::Term::new("rust")), 0);
+        
+        // Already current
+        assert!(corpus_service.refresh_document("doc1").unwrap().is_empty());
+        
+        // A copy one corpus rejects as a duplicate refreshes none of them
+        doc_service.create_document("doc2", "Rust is fast").unwrap();
+        corpus_service.create_corpus("corpus2", "Strict Corpus").unwrap();
+        corpus_service.update_duplicate_policy("corpus2", DuplicatePolicy::Reject { similarity_threshold: None }).unwrap();
+        corpus_service.add_document("corpus2", "doc1").unwrap();
+        corpus_service.add_document("corpus2", "doc2").unwrap();
+        doc_service.update_content("doc1", "Rust is fast").unwrap();
+        assert!(matches!(corpus_service.refresh_document("doc1"), Err(ApplicationError::DomainError(DomainError::Duplicate(_)))));
+        assert!(corpus_service.needs_reindex("corpus1").unwrap());
+        assert!(corpus_service.needs_reindex("corpus2").unwrap());
+    }
+    
+    #[test]
+    fn test_auto_index() {
+        let (doc_service, corpus_service) = create_service();
+        
+        corpus_service.create_corpus("corpus1", "Test Corpus").unwrap();
+        assert!(corpus_service.update_auto_index("corpus1", true).unwrap().is_indexed());
+        
+        // No build_index call is needed after adding documents
+        doc_service.create_document("doc1", "Rust is fast").unwrap();
+        let corpus = corpus_service.add_document("corpus1", "doc1").unwrap();
+        assert!(corpus.is_indexed());
+        assert_eq!(corpus.document_frequency(&crate::domain::Term::new("rust")), 1);
+        
+        let mut archive = Vec::new();
+        corpus_service.export_corpus("corpus1", &mut archive).unwrap();
+        corpus_service.delete_corpus("corpus1").unwrap();
+        assert!(corpus_service.import_corpus(&mut archive.as_slice()).unwrap().auto_index());
+    }
+    
+    #[test]
+    fn test_add_updates_index_in_place() {
+        let (doc_service, corpus_service) = create_service();
+        
+        doc_service.create_document("doc1", "Rust is fast").unwrap();
+        doc_service.create_document("doc2", "Go is simple").unwrap();
+        doc_service.create_document("doc3", "Rust is safe").unwrap();
+        corpus_service.create_corpus("corpus1", "Test Corpus").unwrap();
+        corpus_service.add_document("corpus1", "doc1").unwrap();
+        corpus_service.add_document("corpus1", "doc2").unwrap();
+        let corpus = corpus_service.build_index("corpus1").unwrap();
+        
+        let tfidf = crate::domain::TfIdf::new(crate::domain::TfIdfOptions::default());
+        let mut index = tfidf.build_vector_index(&corpus).unwrap();
+        let before = index.vector(&DocumentId::new("doc1")).cloned();
+        
+        // One add is one revision, so the vectors are updated rather than rebuilt
+        let corpus = corpus_service.add_document("corpus1", "doc3").unwrap();
+        assert_eq!(corpus.revision(), index.revision() + 1);
+        assert_eq!(corpus.document_frequency(&crate::domain::Term::new("rust")), 2);
+        assert!(tfidf.update_vector_index(&mut index, &corpus).unwrap());
+        assert!(index.vector(&DocumentId::new("doc3")).is_some());
+        
+        // A rebuild would reweight doc1 with the new document count; an update keeps its vector
+        assert_eq!(index.vector(&DocumentId::new("doc1")).cloned(), before);
+        assert_ne!(tfidf.build_vector_index(&corpus).unwrap().vector(&DocumentId::new("doc1")).cloned(), before);
+    }
+    
+    #[test]
+    fn test_reject_duplicates() {
+        let (doc_service, corpus_service) = create_service();
+        
+        corpus_service.create_corpus("corpus1", "Test Corpus").unwrap();
+        corpus_service.update_duplicate_policy("corpus1", DuplicatePolicy::Reject { similarity_threshold: None }).unwrap();
+        doc_service.create_document("doc1", "Rust is fast").unwrap();
+        doc_service.create_document("doc2", "Rust is fast").unwrap();
+        corpus_service.add_document("corpus1", "doc1").unwrap();
+        
+        match corpus_service.add_document("corpus1", "doc2") {
+            Err(ApplicationError::DomainError(DomainError::Duplicate(duplicate))) => {
+                assert_eq!(duplicate.existing_id().value(), "doc1");
+                assert!(duplicate.is_exact());
+            },
+            other => panic!("expected a duplicate error, got {:?}", other),
+        }
+        assert_eq!(corpus_service.count_corpus_documents("corpus1").unwrap(), 1);
+    }
+    
     #[test]
     fn test_build_index() {
         let (doc_service, corpus_service) = create_service();
@@ -478,6 +1257,45 @@ mod tests {
         assert_eq!(corpus.document_frequency(&crate::domain::Term::new("this")), 2);
     }
     
+    #[test]
+    fn test_update_options() {
+        let (_, corpus_service) = create_service();
+        corpus_service.create_corpus("corpus1", "Test Corpus").unwrap();
+        
+        let options = TfIdfOptions::with_scheme(crate::domain::Scheme::ltc());
+        corpus_service.update_options("corpus1", Some(options)).unwrap();
+        let corpus = corpus_service.get_corpus("corpus1").unwrap();
+        assert_eq!(corpus.options().unwrap().scheme, Some(crate::domain::Scheme::ltc()));
+        
+        assert!(corpus_service.update_options("corpus1", None).unwrap().options().is_none());
+        assert!(matches!(corpus_service.update_options("missing", None), Err(ApplicationError::NotFound(_))));
+    }
+    
+    #[test]
+    fn test_export_and_import_vocabulary() {
+        let (document_service, corpus_service) = create_service();
+        document_service.create_document("doc1", "Rust borrow checker").unwrap();
+        document_service.create_document("doc2", "Rust cargo").unwrap();
+        corpus_service.create_corpus("corpus1", "Training").unwrap();
+        corpus_service.create_corpus("corpus2", "Evaluation").unwrap();
+        corpus_service.add_document("corpus1", "doc1").unwrap();
+        corpus_service.add_document("corpus1", "doc2").unwrap();
+        
+        let mut output = Vec::new();
+        assert!(matches!(
+            corpus_service.export_vocabulary("corpus1", VocabularyFormat::Csv, &mut output),
+            Err(ApplicationError::NotPermitted(_))
+        ));
+        corpus_service.build_index("corpus1").unwrap();
+        corpus_service.export_vocabulary("corpus1", VocabularyFormat::Csv, &mut output).unwrap();
+        assert!(String::from_utf8(output.clone()).unwrap().starts_with("term,df,cf,idf\nborrow,1,1,"));
+        
+        let corpus = corpus_service.import_vocabulary("corpus2", &mut output.as_slice(), VocabularyFormat::Csv).unwrap();
+        let vocabulary = corpus.options().unwrap().fixed_vocabulary.clone().unwrap();
+        assert_eq!(vocabulary.len(), 4);
+        assert!(vocabulary.contains("cargo"));
+    }
+    
     #[test]
     fn test_stopwords() {
         let (_, corpus_service) = create_service();
@@ -517,4 +1335,177 @@ mod tests {
         let count = corpus_service.count_corpus_documents("corpus1").unwrap();
         assert_eq!(count, 2);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_health_check_and_repair() {
+        let (doc_service, corpus_service) = create_service();
+        
+        doc_service.create_document("doc1", "Document one").unwrap();
+        doc_service.create_document("doc2", "Document two").unwrap();
+        corpus_service.create_corpus("corpus1", "Test Corpus").unwrap();
+        corpus_service.add_document("corpus1", "doc1").unwrap();
+        corpus_service.add_document("corpus1", "doc2").unwrap();
+        
+        let report = corpus_service.health_check("corpus1").unwrap();
+        assert_eq!(report.issues(), &[HealthIssue::NotIndexed]);
+        
+        corpus_service.build_index("corpus1").unwrap();
+        assert!(corpus_service.health_check("corpus1").unwrap().is_healthy());
+        
+        // Change one document and delete the other behind the corpus's back
+        doc_service.update_content("doc1", "Changed content").unwrap();
+        doc_service.delete_document("doc2").unwrap();
+        
+        let report = corpus_service.health_check("corpus1").unwrap();
+        assert!(report.issues().contains(&HealthIssue::StaleDocument(DocumentId::new("doc1"))));
+        assert!(report.issues().contains(&HealthIssue::MissingDocument(DocumentId::new("doc2"))));
+        
+        let report = corpus_service.repair_corpus("corpus1").unwrap();
+        assert!(report.is_repaired());
+        
+        let corpus = corpus_service.get_corpus("corpus1").unwrap();
+        assert_eq!(corpus.document_count(), 1);
+        assert_eq!(corpus.document_frequency(&crate::domain::Term::new("changed")), 1);
+        assert!(corpus_service.health_check("corpus1").unwrap().is_healthy());
+    }
+    
+    #[test]
+    fn test_export_and_import_corpus() {
+        let (doc_service, corpus_service) = create_service();
+        
+        doc_service.create_document("doc1", "Rust is fast").unwrap();
+        corpus_service.create_corpus_with_description("corpus1", "Test Corpus", "Notes").unwrap();
+        corpus_service.add_document("corpus1", "doc1").unwrap();
+        corpus_service.add_stopword("corpus1", "is").unwrap();
+        corpus_service.update_options("corpus1", Some(TfIdfOptions::with_scheme(crate::domain::Scheme::ltc()))).unwrap();
+        corpus_service.update_duplicate_policy("corpus1", DuplicatePolicy::Reject { similarity_threshold: None }).unwrap();
+        corpus_service.build_index("corpus1").unwrap();
+        
+        let mut archive = Vec::new();
+        corpus_service.export_corpus("corpus1", &mut archive).unwrap();
+        
+        // Importing into the same environment collides with the original
+        assert!(corpus_service.import_corpus(&mut archive.as_slice()).is_err());
+        
+        let (other_doc_service, other_corpus_service) = create_service();
+        let corpus = other_corpus_service.import_corpus(&mut archive.as_slice()).unwrap();
+        assert_eq!(corpus.description(), Some("Notes"));
+        assert!(corpus.is_stopword("is"));
+        assert_eq!(corpus.options().unwrap().scheme, Some(crate::domain::Scheme::ltc()));
+        assert_eq!(corpus.duplicate_policy(), DuplicatePolicy::Reject { similarity_threshold: None });
+        assert!(corpus.is_indexed());
+        assert_eq!(corpus.document_frequency(&crate::domain::Term::new("rust")), 1);
+        assert_eq!(other_doc_service.get_document("doc1").unwrap().content(), "Rust is fast");
+        assert!(other_corpus_service.health_check("corpus1").unwrap().is_healthy());
+        
+        // An archive that fails to import stores none of its documents
+        let mut invalid: serde_json::Value = serde_json::from_slice(&archive).unwrap();
+        let documents = invalid["documents"].as_array_mut().unwrap();
+        documents.push(documents[0].clone());
+        let (doc_service, corpus_service) = create_service();
+        doc_service.create_document("doc1", "Original").unwrap();
+        assert!(corpus_service.import_corpus(&mut serde_json::to_vec(&invalid).unwrap().as_slice()).is_err());
+        assert_eq!(doc_service.get_document("doc1").unwrap().content(), "Original");
+        assert!(corpus_service.get_corpus("corpus1").is_err());
+        
+        // A stored document with the same ID but other content is not overwritten
+        assert!(corpus_service.import_corpus(&mut archive.as_slice()).is_err());
+        assert_eq!(doc_service.get_document("doc1").unwrap().content(), "Original");
+        assert!(corpus_service.get_corpus("corpus1").is_err());
+        
+        // An identical stored document is kept, e.g. when re-importing after deleting the corpus
+        let (doc_service, corpus_service) = create_service();
+        doc_service.create_document("doc1", "Rust is fast").unwrap();
+        let corpus = corpus_service.import_corpus(&mut archive.as_slice()).unwrap();
+        assert_eq!(corpus.document_count(), 1);
+    }
+    
+    #[test]
+    fn test_import_jsonl_into_corpus() {
+        let (document_service, corpus_service) = create_service();
+        corpus_service.create_corpus("corpus1", "Test Corpus").unwrap();
+        
+        let input = "{\"id\": \"doc1\", \"content\": \"Rust is fast\"}\n{\"id\": \"doc2\", \"content\": \"Rust is safe\"}\n";
+        let corpus = corpus_service.import_jsonl("corpus1", &mut input.as_bytes(), &FieldMapping::new()).unwrap();
+        assert_eq!(corpus.document_count(), 2);
+        assert_eq!(corpus_service.count_corpus_documents("corpus1").unwrap(), 2);
+        
+        // A record the duplicate policy rejects leaves no document or corpus change behind
+        corpus_service.update_duplicate_policy("corpus1", DuplicatePolicy::Reject { similarity_threshold: None }).unwrap();
+        let input = "{\"id\": \"doc3\", \"content\": \"Go is simple\"}\n{\"id\": \"doc4\", \"content\": \"Rust is fast\"}\n";
+        assert!(corpus_service.import_jsonl("corpus1", &mut input.as_bytes(), &FieldMapping::new()).is_err());
+        assert!(document_service.get_document("doc3").is_err());
+        assert_eq!(corpus_service.count_corpus_documents("corpus1").unwrap(), 2);
+    }
+    
+    #[test]
+    fn test_merge_corpora() {
+        let (doc_service, corpus_service) = create_service();
+        
+        doc_service.create_document("doc1", "Rust is fast").unwrap();
+        doc_service.create_document("doc2", "Rust is safe").unwrap();
+        corpus_service.create_corpus("corpus1", "First").unwrap();
+        corpus_service.create_corpus("corpus2", "Second").unwrap();
+        corpus_service.add_document("corpus1", "doc1").unwrap();
+        corpus_service.add_document("corpus2", "doc1").unwrap();
+        corpus_service.add_document("corpus2", "doc2").unwrap();
+        corpus_service.build_index("corpus2").unwrap();
+        corpus_service.update_options("corpus2", Some(TfIdfOptions::with_scheme(crate::domain::Scheme::ltc()))).unwrap();
+        corpus_service.update_duplicate_policy("corpus1", DuplicatePolicy::Flag { similarity_threshold: None }).unwrap();
+        corpus_service.update_auto_index("corpus1", true).unwrap();
+        doc_service.create_document("doc3", "Rust is fast").unwrap();
+        corpus_service.add_document("corpus1", "doc3").unwrap();
+        
+        let (merged, report) = corpus_service.merge_corpora("corpus1", "corpus2", "merged").unwrap();
+        assert_eq!(merged.document_count(), 3);
+        
+        // The first corpus has no options of its own, so the second's apply; its other settings win
+        assert_eq!(merged.options().unwrap().scheme, Some(crate::domain::Scheme::ltc()));
+        assert_eq!(merged.duplicate_policy(), DuplicatePolicy::Flag { similarity_threshold: None });
+        assert!(merged.auto_index());
+        assert_eq!(merged.flagged_duplicates().len(), 1);
+        assert_eq!(merged.flagged_duplicates()[0].document_id().value(), "doc3");
+        assert_eq!(report.document_collisions(), &[DocumentId::new("doc1")]);
+        assert_eq!(merged.document_frequency(&crate::domain::Term::new("rust")), 3);
+        assert_eq!(corpus_service.get_corpus("corpus1").unwrap().document_count(), 2);
+        
+        assert!(corpus_service.merge_corpora("corpus1", "corpus2", "merged").is_err());
+    }
+    
+    #[test]
+    fn test_publishes_events() {
+        use std::sync::Mutex;
+        
+        let bus = Arc::new(EventBus::new());
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        bus.subscribe(Arc::new(move |event: &DomainEvent| sink.lock().unwrap().push(event.clone())));
+        
+        let document_repository = Arc::new(InMemoryDocumentRepository::new());
+        let mut doc_service = DocumentServiceImpl::new(document_repository.clone(), Arc::new(SimpleTokenizer::new()));
+        doc_service.set_event_bus(Some(bus.clone()));
+        let mut corpus_service = CorpusServiceImpl::new(
+            Arc::new(InMemoryCorpusRepository::new()),
+            document_repository,
+            Arc::new(doc_service),
+        );
+        corpus_service.set_event_bus(Some(bus));
+        
+        corpus_service.document_service.create_document("doc1", "Rust is fast").unwrap();
+        corpus_service.create_corpus("corpus1", "Test Corpus").unwrap();
+        corpus_service.add_document("corpus1", "doc1").unwrap();
+        corpus_service.build_index("corpus1").unwrap();
+        corpus_service.document_service.update_content("doc1", "Go is simple").unwrap();
+        corpus_service.refresh_document("doc1").unwrap();
+        
+        let corpus_id = CorpusId::new("corpus1");
+        let document_id = DocumentId::new("doc1");
+        assert_eq!(*seen.lock().unwrap(), vec![
+            DomainEvent::DocumentCreated { document_id: document_id.clone() },
+            DomainEvent::DocumentAddedToCorpus { corpus_id: corpus_id.clone(), document_id: document_id.clone() },
+            DomainEvent::CorpusIndexed { corpus_id: corpus_id.clone(), documents: 1, terms: 3 },
+            DomainEvent::DocumentUpdated { document_id: document_id.clone() },
+            DomainEvent::DocumentRefreshedInCorpus { corpus_id, document_id },
+        ]);
+    }
+}
@@ -1,12 +1,23 @@
-/// src/application/document_service.rs
+// src/application/document_service.rs
 
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::Entry;
+use std::fs;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Instant, UNIX_EPOCH};
 
-use crate::domain::{Document, DocumentId, Term};
+use crate::domain::{Document, DocumentId, Page, PageRequest, PositionTracking, Term};
+use crate::infrastructure::extract::{self, TextExtractor};
+use crate::infrastructure::metrics::{self, Metrics, NoopMetrics};
 use crate::infrastructure::repository::DocumentRepository;
-use crate::infrastructure::tokenizer::Tokenizer;
+use crate::infrastructure::telemetry;
+use crate::infrastructure::tokenizer::{Analyzer, LanguageDetector, LanguageProfile, Lemmatizer, Preprocessor, Stemmer, Tokenizer};
 
-use super::{ApplicationError, ApplicationResult};
+use super::events::{self, DomainEvent, EventBus};
+use super::{ApplicationError, ApplicationResult, FieldMapping};
 
 /// Service interface for managing Documents
 pub trait DocumentService {
@@ -16,15 +27,51 @@ pub trait DocumentService {
     /// Create a document with title
     fn create_document_with_title(&self, id: &str, title: &str, content: &str) -> ApplicationResult<Document>;
     
+    /// Create a document from the bytes of a file, extracting its text first
+    fn create_document_from_bytes(
+        &self,
+        id: &str,
+        bytes: &[u8],
+        extractor: &dyn TextExtractor
+    ) -> ApplicationResult<Document>;
+    
+    /// Create a document from a file, using its path as the ID
+    ///
+    /// The title comes from the file name, and the path, size in bytes and
+    /// modification time (Unix seconds) are recorded in the metadata under
+    /// "path", "size" and "modified". The extractor is chosen by file extension.
+    fn create_document_from_path(&self, path: &Path) -> ApplicationResult<Document>;
+    
+    /// Create a document from each file, saving none unless every file can be read and analyzed
+    ///
+    /// Fails before saving anything if a path is listed twice or already used
+    /// as a document ID. A repository error while saving can still leave the
+    /// documents of earlier files created.
+    fn create_documents_from_paths(&self, paths: &[PathBuf]) -> ApplicationResult<Vec<Document>>;
+    
     /// Get a document by ID
-    fn get_document(&self, id: &str) -> ApplicationResult<Document>;
+    fn get_document(&self, id: &str) -> ApplicationResult<Arc<Document>>;
     
     /// Update a document's content
     fn update_content(&self, id: &str, new_content: &str) -> ApplicationResult<Document>;
     
+    /// Append text to a document's content as is, analyzing only the appended part
+    ///
+    /// The content's last word is analyzed again with the appended text, so
+    /// appending "ing" to "test" yields the term "testing". The whole document
+    /// is re-analyzed instead when the tokenizer joins words into n-grams, as
+    /// n-grams ending before the appended text can change too, with a
+    /// preprocessor configured, since preprocessing may depend on the
+    /// surrounding text, or when language detection finds a different language
+    /// in the longer content.
+    fn append_content(&self, id: &str, more_text: &str) -> ApplicationResult<Document>;
+    
     /// Update a document's title
     fn update_title(&self, id: &str, new_title: &str) -> ApplicationResult<Document>;
     
+    /// Set a named field such as "abstract" or "tags", re-analyzing the document
+    fn update_field(&self, id: &str, field: &str, text: &str) -> ApplicationResult<Document>;
+    
     /// Delete a document
     fn delete_document(&self, id: &str) -> ApplicationResult<()>;
     
@@ -32,13 +79,52 @@ pub trait DocumentService {
     fn process_document(&self, id: &str) -> ApplicationResult<Document>;
     
     /// List all documents
-    fn list_documents(&self) -> ApplicationResult<Vec<Document>>;
+    fn list_documents(&self) -> ApplicationResult<Vec<Arc<Document>>>;
+    
+    /// List one page of documents, ordered by ID
+    fn list_documents_page(&self, request: PageRequest) -> ApplicationResult<Page<Arc<Document>>>;
     
     /// Count all documents
     fn count_documents(&self) -> ApplicationResult<usize>;
     
     /// Search for documents by term
-    fn search_by_term(&self, term: &str) -> ApplicationResult<Vec<Document>>;
+    fn search_by_term(&self, term: &str) -> ApplicationResult<Vec<Arc<Document>>>;
+    
+    /// Re-analyze a document's content without saving it
+    fn analyze_document(&self, document: &Document) -> ApplicationResult<Document>;
+    
+    /// Read newline-delimited JSON records as analyzed documents, one per non-empty line, without saving them
+    ///
+    /// Records are read as the iterator advances. It ends after the first
+    /// invalid record, or ID already in the repository or on an earlier line,
+    /// yielding an error that reports its line number.
+    fn read_jsonl<'a>(
+        &'a self,
+        reader: &'a mut dyn BufRead,
+        mapping: &'a FieldMapping
+    ) -> Box<dyn Iterator<Item = ApplicationResult<Document>> + 'a>;
+    
+    /// Create documents from newline-delimited JSON records, one per non-empty line
+    ///
+    /// Each record is read, analyzed and saved as the iterator advances. It
+    /// ends after the first invalid record or duplicate ID, yielding an error
+    /// that reports its line number; documents from earlier lines stay created.
+    fn import_jsonl<'a>(
+        &'a self,
+        reader: &'a mut dyn BufRead,
+        mapping: &'a FieldMapping
+    ) -> Box<dyn Iterator<Item = ApplicationResult<Document>> + 'a>;
+    
+    /// Get the stopwords of the tokenizer analyzing documents
+    fn stopwords(&self) -> Vec<String>;
+    
+    /// Add a stopword to the tokenizer
+    ///
+    /// Only documents analyzed afterwards see the change; reprocess existing ones to apply it.
+    fn add_stopword(&self, word: &str);
+    
+    /// Remove a stopword from the tokenizer, returning whether it was present
+    fn remove_stopword(&self, word: &str) -> bool;
 }
 
 pub struct DocumentServiceImpl<R, T>
@@ -47,7 +133,15 @@ where
     T: Tokenizer
 {
     repository: Arc<R>,
-    tokenizer: Arc<T>
+    tokenizer: Arc<T>,
+    stemmer: Option<Arc<dyn Stemmer>>,
+    lemmatizer: Option<Arc<dyn Lemmatizer>>,
+    preprocessor: Option<Arc<dyn Preprocessor>>,
+    position_tracking: PositionTracking,
+    metrics: Arc<dyn Metrics>,
+    events: Option<Arc<EventBus>>,
+    language_detector: Option<Arc<dyn LanguageDetector>>,
+    language_profiles: HashMap<String, LanguageProfile>
 }
 
 impl <R, T> DocumentServiceImpl<R, T> 
@@ -58,25 +152,326 @@ where
     pub fn new(repository: Arc<R>, tokenizer: Arc<T>) -> Self {
         Self {
             repository,
-            tokenizer
+            tokenizer,
+            stemmer: None,
+            lemmatizer: None,
+            preprocessor: None,
+            position_tracking: PositionTracking::default(),
+            metrics: Arc::new(NoopMetrics),
+            events: None,
+            language_detector: None,
+            language_profiles: HashMap::new()
+        }
+    }
+
+    /// Create a service that records the stem of every analyzed term
+    pub fn with_stemmer(repository: Arc<R>, tokenizer: Arc<T>, stemmer: Arc<dyn Stemmer>) -> Self {
+        Self {
+            repository,
+            tokenizer,
+            stemmer: Some(stemmer),
+            lemmatizer: None,
+            preprocessor: None,
+            position_tracking: PositionTracking::default(),
+            metrics: Arc::new(NoopMetrics),
+            events: None,
+            language_detector: None,
+            language_profiles: HashMap::new()
+        }
+    }
+
+    /// Replace each analyzed token with its lemma before stemming (None = no lemmatization)
+    pub fn set_lemmatizer(&mut self, lemmatizer: Option<Arc<dyn Lemmatizer>>) {
+        self.lemmatizer = lemmatizer;
+    }
+
+    /// Rewrite content and fields before tokenization, e.g. to strip HTML (None = tokenize as-is)
+    ///
+    /// The stored document keeps its original text.
+    pub fn set_preprocessor(&mut self, preprocessor: Option<Arc<dyn Preprocessor>>) {
+        self.preprocessor = preprocessor;
+    }
+
+    /// Choose which details analyzed documents record about each content term occurrence
+    ///
+    /// Offsets point into the content after preprocessing.
+    pub fn set_position_tracking(&mut self, position_tracking: PositionTracking) {
+        self.position_tracking = position_tracking;
+    }
+
+    /// Record analysis counts and tokenization time
+    pub fn set_metrics(&mut self, metrics: Arc<dyn Metrics>) {
+        self.metrics = metrics;
+    }
+
+    /// Publish document events after each saved change (None = publish nothing)
+    pub fn set_event_bus(&mut self, events: Option<Arc<EventBus>>) {
+        self.events = events;
+    }
+
+    /// Detect the language of content during analysis and store it in the "lang" metadata (None = no detection)
+    ///
+    /// Documents tagged with "lang" metadata are not detected. A language
+    /// detected earlier is redetected, and dropped when it no longer can be.
+    pub fn set_language_detector(&mut self, language_detector: Option<Arc<dyn LanguageDetector>>) {
+        self.language_detector = language_detector;
+    }
+
+    /// Analyze documents in `language`, by tagged or detected "lang" metadata, with the given profile
+    ///
+    /// The profile's tokenizer and stemmer replace the service's ones and its
+    /// stopwords are dropped from content and fields.
+    pub fn add_language_profile(&mut self, language: impl Into<String>, profile: LanguageProfile) {
+        self.language_profiles.insert(language.into(), profile);
+    }
+
+    /// Get the profile for the language recorded in a document's metadata, if any
+    fn language_profile<'a>(&'a self, document: &Document) -> Option<&'a LanguageProfile> {
+        document.language().and_then(|language| self.language_profiles.get(language))
+    }
+
+    /// Get the tokenizer for a language profile, falling back to the service's
+    fn tokenizer_for<'a>(&'a self, profile: Option<&'a LanguageProfile>) -> &'a dyn Tokenizer {
+        match profile.and_then(LanguageProfile::tokenizer) {
+            Some(tokenizer) => tokenizer.as_ref(),
+            None => self.tokenizer.as_ref()
+        }
+    }
+
+    /// Run text through the preprocessor, if one is configured
+    fn preprocess<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        match &self.preprocessor {
+            Some(preprocessor) => Cow::Owned(preprocessor.preprocess(text)),
+            None => Cow::Borrowed(text)
+        }
+    }
+
+    /// Turn a token into a term, lemmatizing and stemming it if configured
+    ///
+    /// A language profile's stemmer takes precedence over the service's. Fails
+    /// if the term is new and the term interner has no ID left for it.
+    fn make_term(&self, token: String, profile: Option<&LanguageProfile>) -> ApplicationResult<Term> {
+        let token = match &self.lemmatizer {
+            Some(lemmatizer) => lemmatizer.lemmatize(&token),
+            None => token
+        };
+
+        let mut term = Term::try_new(&token)?;
+        if let Some(stemmer) = profile.and_then(LanguageProfile::stemmer).or(self.stemmer.as_ref()) {
+            term.set_stem(stemmer.stem(&token));
         }
+        Ok(term)
     }
 
     /// Tokenize and analyze document content
     fn analyze_content(&self, document: &mut Document) -> ApplicationResult<()> {
+        let _span = tracing::trace_span!(target: telemetry::DOCUMENT, "analyze", document_id = document.id().value()).entered();
+        let started = Instant::now();
         document.clear_terms();
+        document.set_position_tracking(self.position_tracking);
+
+        // One copy of the content lets tokens borrow from it while the document is updated
+        let content = self.preprocess(document.content()).into_owned();
+        if let Some(detector) = &self.language_detector {
+            document.set_detected_language(detector.detect(&content));
+        }
+        self.add_content_terms(document, &content, 0)?;
+
+        // The title and named fields are tracked separately so they can be boosted or targeted
+        let mut fields: Vec<(String, String)> = document.fields()
+            .iter()
+            .map(|(name, text)| (name.clone(), text.clone()))
+            .collect();
+        if let Some(title) = document.title() {
+            fields.push((Document::TITLE_FIELD.to_string(), title.to_string()));
+        }
+
+        let profile = self.language_profile(document);
+        let tokenizer = self.tokenizer_for(profile);
+        for (name, text) in fields {
+            for token in tokenizer.tokenize(&self.preprocess(&text)) {
+                if profile.is_some_and(|profile| profile.is_stopword(&token)) {
+                    continue;
+                }
+                document.add_field_term(&name, self.make_term(token, profile)?);
+            }
+        }
+
+        self.metrics.increment_counter(metrics::DOCUMENTS_ANALYZED, 1);
+        self.metrics.record_duration(metrics::TOKENIZE_SECONDS, started);
+        Ok(())
+    }
+
+    /// Analyze text appended to the content at byte offset `start`
+    ///
+    /// The last word before the appended text may continue into it, so its
+    /// terms are removed and it is analyzed again together with the new text.
+    fn analyze_appended(&self, document: &mut Document, start: usize) -> ApplicationResult<()> {
+        let started = Instant::now();
+
+        let content = document.content();
+        let tail_start = content[..start].char_indices()
+            .rev()
+            .find(|(_, c)| c.is_whitespace())
+            .map_or(0, |(index, c)| index + c.len_utf8());
+        let (old_tail, tail) = (content[tail_start..start].to_string(), content[tail_start..].to_string());
+
+        let profile = self.language_profile(document);
+        let removed: Vec<Term> = self.tokenizer_for(profile).tokenize(&old_tail)
+            .into_iter()
+            .filter(|token| !profile.is_some_and(|profile| profile.is_stopword(token)))
+            .map(|token| self.make_term(token, profile))
+            .collect::<ApplicationResult<_>>()?;
+        for term in removed.iter().rev() {
+            document.remove_last_term(term);
+        }
+        self.add_content_terms(document, &tail, tail_start)?;
+
+        self.metrics.record_duration(metrics::TOKENIZE_SECONDS, started);
+        Ok(())
+    }
+
+    /// Tokenize content found at byte offset `offset` with the document's language tokenizer and add it as terms
+    ///
+    /// Each distinct token is lemmatized and stemmed once, and the document
+    /// copies a term only the first time it sees it.
+    fn add_content_terms(&self, document: &mut Document, text: &str, offset: usize) -> ApplicationResult<()> {
+        let profile = self.language_profile(document);
+        let spans = self.tokenizer_for(profile).tokenize_spans(text);
+        let mut terms: HashMap<&str, Term> = HashMap::new();
+        for span in &spans {
+            if profile.is_some_and(|profile| profile.is_stopword(span.text())) {
+                continue;
+            }
+            let term = match terms.entry(span.text()) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(self.make_term(span.text().to_string(), profile)?),
+            };
+            document.add_term_ref_with_offsets(term, offset + span.start(), offset + span.end());
+        }
+        Ok(())
+    }
+
+    /// Fail if a document already uses a file path as its ID
+    fn check_new_path(&self, path: &Path) -> ApplicationResult<()> {
+        let id = path.to_string_lossy();
+        if self.repository.exists(&DocumentId::new(id.as_ref())).map_err(|e| {
+            ApplicationError::RepositoryError(format!("Error checking existence: {}", e))
+        })? {
+            return Err(ApplicationError::InvalidInput(format!("Document with ID '{}' already exists", id)));
+        }
+        Ok(())
+    }
+
+    /// Read, extract and analyze a file into a document with its path as the ID, without saving it
+    fn document_from_path(&self, path: &Path) -> ApplicationResult<Document> {
+        let id = path.to_string_lossy();
+        let bytes = fs::read(path).map_err(|e| {
+            ApplicationError::Other(format!("Error reading file '{}': {}", path.display(), e))
+        })?;
+        let file = fs::metadata(path).map_err(|e| {
+            ApplicationError::Other(format!("Error reading metadata of '{}': {}", path.display(), e))
+        })?;
+        let content = extract::extractor_for_path(path).extract(&bytes).map_err(|e| {
+            ApplicationError::InvalidInput(format!("Error extracting text from '{}': {}", path.display(), e))
+        })?;
+
+        let mut document = match title_from_path(path) {
+            Some(title) => Document::with_title(id.as_ref(), title, content),
+            None => Document::new(id.as_ref(), content),
+        };
+        document.set_metadata("path", id.as_ref());
+        document.set_metadata("size", file.len().to_string());
+        if let Some(modified) = file.modified().ok().and_then(|time| time.duration_since(UNIX_EPOCH).ok()) {
+            document.set_metadata("modified", modified.as_secs().to_string());
+        }
+        self.analyze_content(&mut document)?;
+        Ok(document)
+    }
+
+    /// Turn one line of newline-delimited JSON into an analyzed document, or nothing for a blank line
+    ///
+    /// `seen` collects the IDs of earlier lines, so a repeated ID fails even before anything is saved.
+    fn read_record(
+        &self,
+        line_number: usize,
+        line: std::io::Result<String>,
+        mapping: &FieldMapping,
+        seen: &mut HashSet<String>
+    ) -> ApplicationResult<Option<Document>> {
+        let line = line.map_err(|e| {
+            ApplicationError::Other(format!("Error reading line {}: {}", line_number, e))
+        })?;
+        if line.trim().is_empty() {
+            return Ok(None);
+        }
+
+        let record = serde_json::from_str(&line).map_err(|e| {
+            ApplicationError::InvalidInput(format!("Invalid JSON on line {}: {}", line_number, e))
+        })?;
+        let mapped = mapping.extract(&record).map_err(|e| {
+            ApplicationError::InvalidInput(format!("Line {}: {}", line_number, e))
+        })?;
 
-        let tokens = self.tokenizer.tokenize(document.content());
+        if seen.contains(&mapped.id) || self.repository.exists(&DocumentId::new(&mapped.id)).map_err(|e| {
+            ApplicationError::RepositoryError(format!("Error checking existence: {}", e))
+        })? {
+            return Err(ApplicationError::InvalidInput(
+                format!("Line {}: document with ID '{}' already exists", line_number, mapped.id)
+            ));
+        }
+        seen.insert(mapped.id.clone());
 
-        for token in tokens {
-            let term = Term::new(token);
-            document.add_term(term);
+        let mut document = match mapped.title {
+            Some(title) => Document::with_title(mapped.id, title, mapped.content),
+            None => Document::new(mapped.id, mapped.content),
+        };
+        for (key, value) in mapped.metadata {
+            document.set_metadata(key, value);
         }
+        self.analyze_content(&mut document)?;
+
+        Ok(Some(document))
+    }
 
+    /// Save a newly created document and announce it
+    fn save_created(&self, document: &Document) -> ApplicationResult<()> {
+        self.repository.save(document).map_err(|e| {
+            ApplicationError::RepositoryError(format!("Error saving document: {}", e))
+        })?;
+        events::publish(&self.events, DomainEvent::DocumentCreated { document_id: document.id().clone() });
         Ok(())
     }
 }
 
+/// Stop an iterator after the first error it yields
+fn until_error<T>(items: impl Iterator<Item = ApplicationResult<T>>) -> impl Iterator<Item = ApplicationResult<T>> {
+    items.scan(false, |failed, item| {
+        if *failed {
+            return None;
+        }
+        *failed = item.is_err();
+        Some(item)
+    })
+}
+
+/// Turn a file name like `release_notes-2024.txt` into a title like `release notes 2024`
+fn title_from_path(path: &Path) -> Option<String> {
+    let stem = path.file_stem()?.to_string_lossy();
+    let title = stem.split(['_', '-']).filter(|word| !word.is_empty()).collect::<Vec<_>>().join(" ");
+    (!title.is_empty()).then_some(title)
+}
+
+impl<R> DocumentServiceImpl<R, Analyzer>
+where
+    R: DocumentRepository
+{
+    /// Create a service that runs content and fields through an analysis pipeline
+    pub fn with_analyzer(repository: Arc<R>, analyzer: Analyzer) -> Self {
+        Self::new(repository, Arc::new(analyzer))
+    }
+}
+
 impl<R, T> DocumentService for DocumentServiceImpl<R, T> 
 where
     R: DocumentRepository,
@@ -96,6 +491,7 @@ where
         self.repository.save(&document).map_err(|e| {
             ApplicationError::RepositoryError(format!("Error saving document: {}", e))
         })?;
+        events::publish(&self.events, DomainEvent::DocumentCreated { document_id: document.id().clone() });
 
         Ok(document)
     }
@@ -113,11 +509,50 @@ where
         self.repository.save(&document).map_err(|e| {
             ApplicationError::RepositoryError(format!("Error saving document: {}", e))
         })?;
+        events::publish(&self.events, DomainEvent::DocumentCreated { document_id: document.id().clone() });
 
         Ok(document)
     }
 
-    fn get_document(&self, id: &str) -> ApplicationResult<Document> {
+    fn create_document_from_bytes(
+        &self,
+        id: &str,
+        bytes: &[u8],
+        extractor: &dyn TextExtractor
+    ) -> ApplicationResult<Document> {
+        let content = extractor.extract(bytes).map_err(|e| {
+            ApplicationError::InvalidInput(format!("Error extracting text: {}", e))
+        })?;
+
+        self.create_document(id, &content)
+    }
+
+    fn create_document_from_path(&self, path: &Path) -> ApplicationResult<Document> {
+        self.check_new_path(path)?;
+        let document = self.document_from_path(path)?;
+        self.save_created(&document)?;
+        Ok(document)
+    }
+
+    fn create_documents_from_paths(&self, paths: &[PathBuf]) -> ApplicationResult<Vec<Document>> {
+        let mut seen = HashSet::new();
+        for path in paths {
+            if !seen.insert(path) {
+                return Err(ApplicationError::InvalidInput(format!("Path '{}' is listed more than once", path.display())));
+            }
+            self.check_new_path(path)?;
+        }
+
+        let documents = paths.iter()
+            .map(|path| self.document_from_path(path))
+            .collect::<ApplicationResult<Vec<Document>>>()?;
+        for document in &documents {
+            self.save_created(document)?;
+        }
+        Ok(documents)
+    }
+
+    fn get_document(&self, id: &str) -> ApplicationResult<Arc<Document>> {
 
         let doc_id = DocumentId::new(id);
     
@@ -134,47 +569,93 @@ where
 
         let mut document = self.repository.find(&doc_id).map_err(|e| {
             ApplicationError::RepositoryError(format!("Error retriveing document: {}", e))
-        })?.ok_or_else(|| ApplicationError::NotFound(format!("Document with ID '{}' not found", id)))?;
-
-        let new_content_bytes = new_content.as_bytes();
-        let old_content_bytes = document.content().as_bytes();
+        })?.map(Arc::unwrap_or_clone).ok_or_else(|| ApplicationError::NotFound(format!("Document with ID '{}' not found", id)))?;
 
-        if new_content_bytes != old_content_bytes {
-            let mut updated_doc = Document::new(id, new_content);
+        if new_content != document.content() {
+            // Title, fields and metadata carry over; analysis bumps the revision
+            document.set_content(new_content);
+            self.analyze_content(&mut document)?;
 
-            if let Some(title) = document.title() {
-                updated_doc.set_title(title);
-            }
-
-            for (k, v) in document.metadata().iter() {
-                updated_doc.set_metadata(k, v);
-            }
-
-            self.analyze_content(&mut updated_doc)?;
-
-            self.repository.save(&updated_doc).map_err(|e|{
+            self.repository.save(&document).map_err(|e|{
                 ApplicationError::RepositoryError(format!("Error saving doc: {}", e))
             })?;
             
-            document = updated_doc;
+            events::publish(&self.events, DomainEvent::DocumentUpdated { document_id: doc_id });
         }
 
         Ok(document)
 
     }
 
+    fn append_content(&self, id: &str, more_text: &str) -> ApplicationResult<Document> {
+        let doc_id = DocumentId::new(id);
+
+        let mut document = self.repository.find(&doc_id).map_err(|e| {
+            ApplicationError::RepositoryError(format!("Error retriveing document: {}", e))
+        })?.map(Arc::unwrap_or_clone).ok_or_else(|| ApplicationError::NotFound(format!("Document with ID '{}' not found", id)))?;
+
+        if more_text.is_empty() {
+            return Ok(document);
+        }
+
+        let start = document.append_content(more_text);
+
+        // A language detected anew from the longer content changes how all of it is analyzed
+        let mut language_changed = false;
+        if let Some(detector) = &self.language_detector {
+            let previous = document.language().map(str::to_string);
+            let detected = detector.detect(&self.preprocess(document.content()));
+            document.set_detected_language(detected);
+            language_changed = document.language() != previous.as_deref();
+        }
+
+        let joins_words = self.tokenizer_for(self.language_profile(&document)).joins_words();
+        if self.preprocessor.is_some() || language_changed || joins_words {
+            self.analyze_content(&mut document)?;
+        } else {
+            self.analyze_appended(&mut document, start)?;
+        }
+
+        self.repository.save(&document).map_err(|e| {
+            ApplicationError::RepositoryError(format!("Error saving doc: {}", e))
+        })?;
+        events::publish(&self.events, DomainEvent::DocumentUpdated { document_id: doc_id });
+
+        Ok(document)
+    }
+
     fn update_title(&self, id: &str, new_title: &str) -> ApplicationResult<Document> {
         let doc_id = DocumentId::new(id);
 
         let mut document = self.repository.find(&doc_id).map_err(|e| {
             ApplicationError::RepositoryError(format!("Error retriveing document: {}", e))
-        })?.ok_or_else(|| ApplicationError::NotFound(format!("Document with ID '{}' not found", id)))?;
+        })?.map(Arc::unwrap_or_clone).ok_or_else(|| ApplicationError::NotFound(format!("Document with ID '{}' not found", id)))?;
 
         document.set_title(new_title);
+        self.analyze_content(&mut document)?;
+
+        self.repository.save(&document).map_err(|e|{
+                ApplicationError::RepositoryError(format!("Error saving doc: {}", e))
+        })?;
+        events::publish(&self.events, DomainEvent::DocumentUpdated { document_id: doc_id });
+
+        Ok(document)
+    }
+
+    fn update_field(&self, id: &str, field: &str, text: &str) -> ApplicationResult<Document> {
+        let doc_id = DocumentId::new(id);
+
+        let mut document = self.repository.find(&doc_id).map_err(|e| {
+            ApplicationError::RepositoryError(format!("Error retriveing document: {}", e))
+        })?.map(Arc::unwrap_or_clone).ok_or_else(|| ApplicationError::NotFound(format!("Document with ID '{}' not found", id)))?;
+
+        document.set_field(field, text);
+        self.analyze_content(&mut document)?;
 
         self.repository.save(&document).map_err(|e|{
                 ApplicationError::RepositoryError(format!("Error saving doc: {}", e))
         })?;
+        events::publish(&self.events, DomainEvent::DocumentUpdated { document_id: doc_id });
 
         Ok(document)
     }
@@ -195,6 +676,7 @@ where
         self.repository.delete(&doc_id).map_err(|e|{
             ApplicationError::RepositoryError(format!("Error deleting document with ID: {}", e))
         })?;
+        events::publish(&self.events, DomainEvent::DocumentDeleted { document_id: doc_id });
 
         Ok(())
     }
@@ -205,7 +687,7 @@ where
         // Get existing document
         let mut document = self.repository.find(&document_id).map_err(|e| {
             ApplicationError::RepositoryError(format!("Error retrieving document: {}", e))
-        })?.ok_or_else(|| {
+        })?.map(Arc::unwrap_or_clone).ok_or_else(|| {
             ApplicationError::NotFound(format!("Document with ID '{}' not found", id))
         })?;
         
@@ -216,11 +698,12 @@ where
         self.repository.save(&document).map_err(|e| {
             ApplicationError::RepositoryError(format!("Error saving document: {}", e))
         })?;
+        events::publish(&self.events, DomainEvent::DocumentUpdated { document_id });
         
         Ok(document)
     }
 
-    fn list_documents(&self) -> ApplicationResult<Vec<Document>> {
+    fn list_documents(&self) -> ApplicationResult<Vec<Arc<Document>>> {
 
         let documents = self.repository.find_all().map_err(|e| {
             ApplicationError::RepositoryError(format!("Error listing  documents: {}", e))
@@ -229,6 +712,12 @@ where
         Ok(documents)
     }
 
+    fn list_documents_page(&self, request: PageRequest) -> ApplicationResult<Page<Arc<Document>>> {
+        self.repository.find_page(request).map_err(|e| {
+            ApplicationError::RepositoryError(format!("Error listing documents: {}", e))
+        })
+    }
+
     fn count_documents(&self) -> ApplicationResult<usize> {
         let doc_count = self.repository.count().map_err(|e|{
             ApplicationError::RepositoryError(format!("Error counting documents {}", e))
@@ -237,20 +726,68 @@ where
         Ok(doc_count)
     }
 
-    fn search_by_term(&self, term: &str) -> ApplicationResult<Vec<Document>> {
+    fn search_by_term(&self, term: &str) -> ApplicationResult<Vec<Arc<Document>>> {
         let term = Term::new(term.to_lowercase());
 
         self.repository.find_by_term(&term).map_err(|e| {
             ApplicationError::RepositoryError(format!("Error searching documents: {}", e))
         })
     }
+
+    fn analyze_document(&self, document: &Document) -> ApplicationResult<Document> {
+        let mut analyzed = document.clone();
+        self.analyze_content(&mut analyzed)?;
+
+        Ok(analyzed)
+    }
+
+    fn read_jsonl<'a>(
+        &'a self,
+        reader: &'a mut dyn BufRead,
+        mapping: &'a FieldMapping
+    ) -> Box<dyn Iterator<Item = ApplicationResult<Document>> + 'a> {
+        let mut seen = HashSet::new();
+        let documents = reader.lines().enumerate().filter_map(move |(index, line)| {
+            self.read_record(index + 1, line, mapping, &mut seen).transpose()
+        });
+        
+        Box::new(until_error(documents))
+    }
+
+    fn import_jsonl<'a>(
+        &'a self,
+        reader: &'a mut dyn BufRead,
+        mapping: &'a FieldMapping
+    ) -> Box<dyn Iterator<Item = ApplicationResult<Document>> + 'a> {
+        let documents = self.read_jsonl(reader, mapping).map(|document| {
+            let document = document?;
+            self.save_created(&document)?;
+            Ok(document)
+        });
+        
+        Box::new(until_error(documents))
+    }
+
+    fn stopwords(&self) -> Vec<String> {
+        self.tokenizer.stopwords()
+    }
+
+    fn add_stopword(&self, word: &str) {
+        self.tokenizer.add_stopword(word);
+    }
+
+    fn remove_stopword(&self, word: &str) -> bool {
+        self.tokenizer.remove_stopword(word)
+    }
 }
 
 #[cfg(test)]
+#[allow(clippy::len_zero)]
 mod tests {
     use super::*;
+    use crate::infrastructure::extract::PlainTextExtractor;
     use crate::infrastructure::repository::InMemoryDocumentRepository;
-    use crate::infrastructure::tokenizer::SimpleTokenizer;
+    use crate::infrastructure::tokenizer::{DictionaryLemmatizer, HtmlStripper, NGramTokenizer, PorterStemmer, SimpleTokenizer, StemmerFilter, StopwordFilter, StopwordLanguageDetector};
     
     fn create_service() -> impl DocumentService {
         let repository = Arc::new(InMemoryDocumentRepository::new());
@@ -275,6 +812,85 @@ mod tests {
         assert_eq!(retrieved.id().value(), "doc1");
     }
     
+    #[test]
+    fn test_stemming() {
+        let service = DocumentServiceImpl::with_stemmer(
+            Arc::new(InMemoryDocumentRepository::new()),
+            Arc::new(SimpleTokenizer::new()),
+            Arc::new(PorterStemmer::new()),
+        );
+        
+        let doc = service.create_document("doc1", "Running runners run").unwrap();
+        assert_eq!(doc.canonical_term_frequency("run").value(), 2);
+        assert_eq!(doc.canonical_term_frequency("runner").value(), 1);
+    }
+    
+    #[test]
+    fn test_lemmatization() {
+        let mut service = DocumentServiceImpl::new(
+            Arc::new(InMemoryDocumentRepository::new()),
+            Arc::new(SimpleTokenizer::new()),
+        );
+        service.set_lemmatizer(Some(Arc::new(DictionaryLemmatizer::with_entries([
+            ("ran", "run"), ("running", "run"), ("runs", "run"),
+        ]))));
+        
+        let doc = service.create_document("doc1", "She ran, he runs, they keep running").unwrap();
+        assert_eq!(doc.term_frequency(&Term::new("run")).value(), 3);
+        assert_eq!(doc.term_frequency(&Term::new("ran")).value(), 0);
+    }
+    
+    #[test]
+    fn test_analyzer_pipeline() {
+        let analyzer = Analyzer::new(SimpleTokenizer::new())
+            .with_filter(StopwordFilter::new(["the"]))
+            .with_filter(StemmerFilter::new(PorterStemmer::new()));
+        let service = DocumentServiceImpl::with_analyzer(Arc::new(InMemoryDocumentRepository::new()), analyzer);
+        
+        let doc = service.create_document("doc1", "The runner runs").unwrap();
+        assert_eq!(doc.term_frequency(&Term::new("run")).value(), 1);
+        assert_eq!(doc.term_frequency(&Term::new("runner")).value(), 1);
+        assert!(!doc.contains_term(&Term::new("the")));
+    }
+    
+    #[test]
+    fn test_html_preprocessing() {
+        let mut service = DocumentServiceImpl::new(
+            Arc::new(InMemoryDocumentRepository::new()),
+            Arc::new(SimpleTokenizer::new()),
+        );
+        service.set_preprocessor(Some(Arc::new(HtmlStripper::new())));
+        
+        let html = "<div class=\"post\"><p>Rust &amp; WebAssembly</p></div>";
+        let doc = service.create_document("doc1", html).unwrap();
+        assert_eq!(doc.content(), html);
+        assert!(doc.contains_term(&Term::new("webassembly")));
+        assert!(!doc.contains_term(&Term::new("div")));
+        assert!(!doc.contains_term(&Term::new("post")));
+        assert!(!doc.contains_term(&Term::new("amp")));
+    }
+    
+    #[test]
+    fn test_position_tracking() {
+        let mut service = DocumentServiceImpl::new(
+            Arc::new(InMemoryDocumentRepository::new()),
+            Arc::new(SimpleTokenizer::new()),
+        );
+        service.set_position_tracking(PositionTracking::PositionsAndOffsets);
+        
+        let content = "Rust, then more Rust";
+        let doc = service.create_document("doc1", content).unwrap();
+        let offsets = doc.term_offsets(&Term::new("rust"));
+        assert_eq!(offsets.len(), 2);
+        assert_eq!(&content[offsets[1].0..offsets[1].1], "Rust");
+        assert_eq!(doc.term_positions(&Term::new("rust")), &[0, 3]);
+        
+        service.set_position_tracking(PositionTracking::None);
+        let doc = service.create_document("doc2", content).unwrap();
+        assert!(doc.term_positions(&Term::new("rust")).is_empty());
+        assert_eq!(doc.term_frequency(&Term::new("rust")).value(), 2);
+    }
+    
     #[test]
     fn test_update_content() {
         let service = create_service();
@@ -291,6 +907,35 @@ mod tests {
         assert!(!updated.term_frequencies().contains_key(&Term::new("initial")));
     }
     
+    #[test]
+    fn test_title_terms() {
+        let service = create_service();
+        
+        let doc = service.create_document_with_title("doc1", "Rust Guide", "Learning the language").unwrap();
+        assert_eq!(doc.field_term_frequency(Document::TITLE_FIELD, &Term::new("rust")).value(), 1);
+        assert_eq!(doc.term_frequency(&Term::new("rust")).value(), 0);
+        
+        // Changing the title re-analyzes it
+        let doc = service.update_title("doc1", "Go Guide").unwrap();
+        assert!(doc.contains_term(&Term::new("go")));
+        assert!(!doc.contains_term(&Term::new("rust")));
+    }
+    
+    #[test]
+    fn test_update_field() {
+        let service = create_service();
+        
+        service.create_document("doc1", "Body text").unwrap();
+        let doc = service.update_field("doc1", "author", "Ada Lovelace").unwrap();
+        assert_eq!(doc.field_term_frequency("author", &Term::new("lovelace")).value(), 1);
+        assert_eq!(doc.term_frequency(&Term::new("lovelace")).value(), 0);
+        
+        // Fields survive content updates
+        let doc = service.update_content("doc1", "New body").unwrap();
+        assert_eq!(doc.field("author"), Some("Ada Lovelace"));
+        assert!(doc.field_term_frequencies("author").is_some());
+    }
+    
     #[test]
     fn test_delete_document() {
         let service = create_service();
@@ -305,6 +950,22 @@ mod tests {
         assert!(service.get_document("doc1").is_err());
     }
     
+    #[test]
+    fn test_list_documents_page() {
+        let service = create_service();
+        
+        for id in ["doc1", "doc2", "doc3"] {
+            service.create_document(id, "Some content").unwrap();
+        }
+        
+        let page = service.list_documents_page(PageRequest::first(2)).unwrap();
+        assert_eq!(page.items().len(), 2);
+        assert_eq!(page.total(), 3);
+        
+        let next = service.list_documents_page(page.next_request().unwrap()).unwrap();
+        assert_eq!(next.items()[0].id().value(), "doc3");
+    }
+    
     #[test]
     fn test_search_by_term() {
         let service = create_service();
@@ -324,4 +985,219 @@ mod tests {
         assert!(ids.contains(&"doc3"));
         assert!(!ids.contains(&"doc2"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_create_document_from_bytes() {
+        let service = create_service();
+
+        let document = service.create_document_from_bytes("doc1", b"Plain text file", &PlainTextExtractor).unwrap();
+        assert_eq!(document.content(), "Plain text file");
+        assert!(service.create_document_from_bytes("doc2", &[0xff, 0xfe], &PlainTextExtractor).is_err());
+    }
+
+    #[test]
+    fn test_create_document_from_path() {
+        let service = create_service();
+        let root = std::env::temp_dir().join(format!("tf-idf-rs-documents-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let path = root.join("release_notes-2024.txt");
+        fs::write(&path, "Faster indexing").unwrap();
+
+        let document = service.create_document_from_path(&path).unwrap();
+        assert_eq!(document.id().value(), path.to_string_lossy());
+        assert_eq!(document.title(), Some("release notes 2024"));
+        assert_eq!(document.metadata().get("size").map(String::as_str), Some("15"));
+        assert!(document.metadata().contains_key("modified"));
+        assert_eq!(document.term_frequency(&Term::new("indexing")).value(), 1);
+
+        // The bulk variant saves nothing when one file is missing or already created
+        let other = root.join("other.txt");
+        fs::write(&other, "More text").unwrap();
+        let error = service.create_documents_from_paths(&[other.clone(), root.join("missing.txt")]).unwrap_err();
+        assert!(error.to_string().contains("missing.txt"));
+        assert!(service.get_document(&other.to_string_lossy()).is_err());
+        let error = service.create_documents_from_paths(&[other.clone(), path.clone()]).unwrap_err();
+        assert!(error.to_string().contains("already exists"));
+        assert!(service.get_document(&other.to_string_lossy()).is_err());
+        assert!(service.create_documents_from_paths(&[other.clone(), other.clone()]).is_err());
+        assert_eq!(service.create_documents_from_paths(std::slice::from_ref(&other)).unwrap().len(), 1);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_append_content() {
+        let mut service = DocumentServiceImpl::new(
+            Arc::new(InMemoryDocumentRepository::new()),
+            Arc::new(SimpleTokenizer::new()),
+        );
+        service.set_position_tracking(PositionTracking::PositionsAndOffsets);
+
+        service.create_document("doc1", "Rust is fast").unwrap();
+        let appended = service.append_content("doc1", " and Rust is safe").unwrap();
+        assert_eq!(appended.content(), "Rust is fast and Rust is safe");
+
+        // Incremental analysis matches analyzing the whole content at once
+        let full = service.analyze_document(&Document::new("doc1", appended.content())).unwrap();
+        assert_eq!(appended.term_frequencies(), full.term_frequencies());
+        assert_eq!(appended.term_count(), full.term_count());
+        let rust = Term::new("rust");
+        assert_eq!(appended.term_positions(&rust), full.term_positions(&rust));
+        assert_eq!(appended.term_offsets(&rust), full.term_offsets(&rust));
+        assert!(appended.contains_phrase(&[Term::new("fast"), Term::new("and")]));
+
+        // Text continuing the last word is appended as is and joins it
+        service.create_document("doc2", "Rust is fast and test").unwrap();
+        let joined = service.append_content("doc2", "ing").unwrap();
+        assert_eq!(joined.content(), "Rust is fast and testing");
+        let full = service.analyze_document(&Document::new("doc2", joined.content())).unwrap();
+        assert_eq!(joined.term_frequencies(), full.term_frequencies());
+        assert_eq!(joined.term_count(), full.term_count());
+        assert_eq!(joined.term_offsets(&Term::new("testing")), &[(17, 24)]);
+        assert_eq!(joined.term_frequency(&Term::new("test")).value(), 0);
+        assert_eq!(joined.max_term_frequency(), full.max_term_frequency());
+
+        assert_eq!(service.get_document("doc1").unwrap().content(), appended.content());
+        assert!(service.append_content("missing", "text").is_err());
+    }
+
+    #[test]
+    fn test_append_content_with_ngrams() {
+        let service = DocumentServiceImpl::new(
+            Arc::new(InMemoryDocumentRepository::new()),
+            Arc::new(NGramTokenizer::new(SimpleTokenizer::with_stopwords(Vec::<String>::new()), 3)),
+        );
+
+        // Trigrams starting two words before the boundary reach into the appended text
+        service.create_document("doc1", "new york city").unwrap();
+        let appended = service.append_content("doc1", " subway map").unwrap();
+        let full = service.analyze_document(&Document::new("doc1", appended.content())).unwrap();
+        assert_eq!(appended.term_frequencies(), full.term_frequencies());
+        assert_eq!(appended.term_frequency(&Term::new("york city subway")).value(), 1);
+
+        // A continued word changes the n-grams that end in it
+        service.create_document("doc2", "big data test").unwrap();
+        let joined = service.append_content("doc2", "ing tools").unwrap();
+        let full = service.analyze_document(&Document::new("doc2", joined.content())).unwrap();
+        assert_eq!(joined.term_frequencies(), full.term_frequencies());
+        assert_eq!(joined.term_frequency(&Term::new("big data test")).value(), 0);
+        assert_eq!(joined.term_frequency(&Term::new("big data testing")).value(), 1);
+    }
+
+    #[test]
+    fn test_import_jsonl() {
+        let service = create_service();
+        let input = concat!(
+            r#"{"id": "doc1", "title": "First", "content": "Rust is fast", "lang": "en"}"#, "\n",
+            "\n",
+            r#"{"id": "doc2", "content": "Go is simple"}"#, "\n",
+        );
+
+        let mapping = FieldMapping::new().with_metadata("language", "lang");
+        let documents: Vec<Document> = service.import_jsonl(&mut input.as_bytes(), &mapping)
+            .collect::<ApplicationResult<_>>()
+            .unwrap();
+        assert_eq!(documents.len(), 2);
+
+        let doc1 = service.get_document("doc1").unwrap();
+        assert_eq!(doc1.title(), Some("First"));
+        assert_eq!(doc1.metadata().get("language").map(String::as_str), Some("en"));
+        assert_eq!(doc1.term_frequency(&Term::new("rust")).value(), 1);
+
+        let duplicate = r#"{"id": "doc3", "content": "new"}
+{"id": "doc1", "content": "again"}"#;
+        let mut reader = duplicate.as_bytes();
+        let mut imported = service.import_jsonl(&mut reader, &mapping);
+        assert_eq!(imported.next().unwrap().unwrap().id().value(), "doc3");
+        assert!(service.get_document("doc3").is_ok());
+        assert!(imported.next().unwrap().unwrap_err().to_string().contains("Line 2"));
+        assert!(imported.next().is_none());
+
+        // Reading saves nothing and catches IDs repeated within the input
+        let repeated = r#"{"id": "doc4", "content": "one"}
+{"id": "doc4", "content": "two"}"#;
+        let read: Vec<_> = service.read_jsonl(&mut repeated.as_bytes(), &mapping).collect();
+        assert_eq!(read.len(), 2);
+        assert!(read[1].as_ref().unwrap_err().to_string().contains("Line 2"));
+        assert!(service.get_document("doc4").is_err());
+    }
+
+    #[test]
+    fn test_manage_stopwords() {
+        let tokenizer = Arc::new(SimpleTokenizer::new());
+        let service = DocumentServiceImpl::new(Arc::new(InMemoryDocumentRepository::new()), tokenizer.clone());
+        assert!(!tokenizer.is_stopword("fast"));
+
+        // The change reaches every service sharing the tokenizer
+        service.add_stopword("Fast");
+        assert!(tokenizer.is_stopword("fast"));
+        assert!(service.stopwords().contains(&"fast".to_string()));
+
+        assert!(service.remove_stopword("fast"));
+        assert!(!service.remove_stopword("fast"));
+        assert!(!tokenizer.is_stopword("fast"));
+    }
+
+    #[test]
+    fn test_language_routing() {
+        let mut service = DocumentServiceImpl::new(Arc::new(InMemoryDocumentRepository::new()), Arc::new(SimpleTokenizer::new()));
+        service.set_language_detector(Some(Arc::new(StopwordLanguageDetector::new())));
+        service.add_language_profile("de", LanguageProfile::new().with_stopwords(["der", "ist", "und"]));
+        service.add_language_profile("en", LanguageProfile::new().with_stemmer(Arc::new(PorterStemmer::new())));
+
+        let doc = service.create_document("doc1", "Der Compiler ist schnell und sicher").unwrap();
+        assert_eq!(doc.language(), Some("de"));
+        assert!(!doc.contains_term(&Term::new("der")));
+        assert!(doc.contains_term(&Term::new("compiler")));
+
+        let doc = service.create_document("doc2", "The compiler is checking the borrows").unwrap();
+        assert_eq!(doc.language(), Some("en"));
+        let term = doc.term_frequencies().keys().find(|term| term.text() == "checking").unwrap();
+        assert_eq!(term.stem(), Some("check"));
+
+        // Undetected content keeps no language and uses the default analysis
+        let doc = service.create_document("doc3", "Compiler").unwrap();
+        assert!(doc.language().is_none());
+
+        // Re-analysis replaces a detected language that no longer applies
+        let doc = service.update_content("doc1", "Compiler").unwrap();
+        assert!(doc.language().is_none());
+        let doc = service.update_content("doc1", "The compiler is fast").unwrap();
+        assert_eq!(doc.language(), Some("en"));
+        assert!(doc.is_language_detected());
+
+        // Appended text is detected too, and a new language re-analyzes the whole content
+        let doc = service.append_content("doc3", " is checking the borrows").unwrap();
+        assert_eq!(doc.language(), Some("en"));
+        let term = doc.term_frequencies().keys().find(|term| term.text() == "compiler").unwrap();
+        assert_eq!(term.stem(), Some("compil"));
+    }
+
+    #[test]
+    fn test_lang_tag_routing() {
+        let english = Analyzer::new(SimpleTokenizer::new()).with_filter(StopwordFilter::new(["on", "the"]));
+        let mut service = DocumentServiceImpl::with_analyzer(Arc::new(InMemoryDocumentRepository::new()), english);
+        service.set_language_detector(Some(Arc::new(StopwordLanguageDetector::new())));
+        let french = Analyzer::new(SimpleTokenizer::new()).with_filter(StopwordFilter::new(["le", "la", "et"]));
+        service.add_language_profile("fr", LanguageProfile::new().with_tokenizer(Arc::new(french)));
+
+        let input = concat!(
+            r#"{"id": "doc1", "content": "On aime le fromage et la baguette", "lang": "fr"}"#, "\n",
+            r#"{"id": "doc2", "content": "On the shelf"}"#, "\n",
+        );
+        let mapping = FieldMapping::new().with_metadata("lang", "lang");
+        service.import_jsonl(&mut input.as_bytes(), &mapping).collect::<ApplicationResult<Vec<_>>>().unwrap();
+
+        // French text keeps "on" and drops French stopwords; the tag skips detection
+        let doc1 = service.get_document("doc1").unwrap();
+        assert_eq!(doc1.language(), Some("fr"));
+        assert!(!doc1.is_language_detected());
+        assert!(doc1.contains_term(&Term::new("on")));
+        assert!(!doc1.contains_term(&Term::new("le")));
+
+        // Untagged text falls back to the default English analyzer
+        let doc2 = service.get_document("doc2").unwrap();
+        assert!(!doc2.contains_term(&Term::new("on")));
+        assert!(doc2.contains_term(&Term::new("shelf")));
+    }
+}
@@ -0,0 +1,246 @@
+// src/application/engine.rs
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::domain::{Corpus, Document, ScoredDocument, Summary, TfIdf, TfIdfOptions};
+use crate::infrastructure::repository::{InMemoryCorpusRepository, InMemoryDocumentRepository};
+use crate::infrastructure::tokenizer::SimpleTokenizer;
+
+use super::{
+    ApplicationResult, CorpusService, CorpusServiceImpl, DocumentService, DocumentServiceImpl, Keyword,
+    SearchService, SearchServiceImpl, TfIdfService, TfIdfServiceImpl,
+};
+
+/// Document service used by the engine
+pub type EngineDocumentService = DocumentServiceImpl<InMemoryDocumentRepository, SimpleTokenizer>;
+
+/// Corpus service used by the engine
+pub type EngineCorpusService = CorpusServiceImpl<InMemoryCorpusRepository, InMemoryDocumentRepository, EngineDocumentService>;
+
+/// Search service used by the engine
+pub type EngineSearchService = SearchServiceImpl<InMemoryCorpusRepository, SimpleTokenizer>;
+
+/// TF-IDF analysis service used by the engine
+pub type EngineTfIdfService = TfIdfServiceImpl<InMemoryCorpusRepository, SimpleTokenizer>;
+
+/// Builder for a `TfIdfEngine`
+#[derive(Debug, Clone)]
+pub struct EngineBuilder {
+    corpus_id: String,
+    corpus_name: String,
+    options: TfIdfOptions,
+    stopwords: Vec<String>,
+}
+
+impl Default for EngineBuilder {
+    fn default() -> Self {
+        Self {
+            corpus_id: "default".to_string(),
+            corpus_name: "Default corpus".to_string(),
+            options: TfIdfOptions::default(),
+            stopwords: Vec::new(),
+        }
+    }
+}
+
+impl EngineBuilder {
+    /// Set the ID and name of the engine's corpus
+    pub fn corpus(mut self, id: impl Into<String>, name: impl Into<String>) -> Self {
+        self.corpus_id = id.into();
+        self.corpus_name = name.into();
+        self
+    }
+
+    /// Set the TF-IDF options used for search and analysis
+    pub fn options(mut self, options: TfIdfOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Add corpus-specific stopwords
+    pub fn stopwords<I, S>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.stopwords.extend(words.into_iter().map(Into::into));
+        self
+    }
+
+    /// Wire the repositories and services and create the corpus
+    pub fn build(self) -> ApplicationResult<TfIdfEngine> {
+        let document_repository = Arc::new(InMemoryDocumentRepository::new());
+        let mut corpus_repository = InMemoryCorpusRepository::new();
+        corpus_repository.set_document_repository(Some(document_repository.clone()));
+        let corpus_repository = Arc::new(corpus_repository);
+        let tokenizer = Arc::new(SimpleTokenizer::new());
+
+        let document_service = Arc::new(DocumentServiceImpl::new(document_repository.clone(), tokenizer.clone()));
+        let corpus_service = CorpusServiceImpl::new(corpus_repository.clone(), document_repository, document_service.clone());
+        let search_service = SearchServiceImpl::new(corpus_repository.clone(), tokenizer.clone(), TfIdf::new(self.options.clone()));
+        let tfidf_service = TfIdfServiceImpl::new(corpus_repository, tokenizer, TfIdf::new(self.options));
+
+        corpus_service.create_corpus(&self.corpus_id, &self.corpus_name)?;
+        for word in &self.stopwords {
+            corpus_service.add_stopword(&self.corpus_id, word)?;
+        }
+
+        Ok(TfIdfEngine {
+            corpus_id: self.corpus_id,
+            document_service,
+            corpus_service,
+            search_service,
+            tfidf_service,
+            needs_index: AtomicBool::new(true),
+        })
+    }
+}
+
+/// One-stop entry point wiring in-memory repositories, a tokenizer and the services around one corpus
+///
+/// `TfIdfEngine::new()?`, a few `add_document` calls and `search("query")` are
+/// all it takes to start; `builder()` picks the corpus, options and stopwords.
+/// The corpus is reindexed lazily, on the first query after documents change.
+/// The underlying services stay available for everything the engine does not cover.
+pub struct TfIdfEngine {
+    corpus_id: String,
+    document_service: Arc<EngineDocumentService>,
+    corpus_service: EngineCorpusService,
+    search_service: EngineSearchService,
+    tfidf_service: EngineTfIdfService,
+
+    /// Whether documents changed since the corpus was last indexed
+    needs_index: AtomicBool,
+}
+
+impl TfIdfEngine {
+    /// Start configuring an engine
+    pub fn builder() -> EngineBuilder {
+        EngineBuilder::default()
+    }
+
+    /// Create an engine with the default corpus and options
+    pub fn new() -> ApplicationResult<Self> {
+        Self::builder().build()
+    }
+
+    /// Get the ID of the engine's corpus
+    pub fn corpus_id(&self) -> &str {
+        &self.corpus_id
+    }
+
+    /// Analyze a text and add it to the corpus as a new document
+    pub fn add_document(&self, id: &str, content: &str) -> ApplicationResult<Document> {
+        let document = self.document_service.create_document(id, content)?;
+        self.corpus_service.add_document(&self.corpus_id, id)?;
+        self.needs_index.store(true, Ordering::SeqCst);
+        Ok(document)
+    }
+
+    /// Analyze a titled text and add it to the corpus as a new document
+    pub fn add_document_with_title(&self, id: &str, title: &str, content: &str) -> ApplicationResult<Document> {
+        let document = self.document_service.create_document_with_title(id, title, content)?;
+        self.corpus_service.add_document(&self.corpus_id, id)?;
+        self.needs_index.store(true, Ordering::SeqCst);
+        Ok(document)
+    }
+
+    /// Remove a document from the corpus and delete it
+    pub fn remove_document(&self, id: &str) -> ApplicationResult<()> {
+        self.corpus_service.remove_document(&self.corpus_id, id)?;
+        self.document_service.delete_document(id)?;
+        self.needs_index.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Get the corpus with its documents
+    pub fn corpus(&self) -> ApplicationResult<Corpus> {
+        self.corpus_service.get_corpus(&self.corpus_id)
+    }
+
+    /// Search the corpus with a raw query string, best match first
+    pub fn search(&self, query: &str) -> ApplicationResult<Vec<ScoredDocument>> {
+        self.ensure_indexed()?;
+        self.search_service.search(&self.corpus_id, query)
+    }
+
+    /// Get the `n` highest-weighted terms of a document
+    pub fn keywords(&self, document_id: &str, n: usize) -> ApplicationResult<Vec<Keyword>> {
+        self.ensure_indexed()?;
+        self.tfidf_service.extract_keywords(document_id, &self.corpus_id, n)
+    }
+
+    /// Calculate the cosine similarity between two documents
+    pub fn similarity(&self, first_id: &str, second_id: &str) -> ApplicationResult<f64> {
+        self.ensure_indexed()?;
+        self.tfidf_service.document_similarity(&self.corpus_id, first_id, second_id)
+    }
+
+    /// Summarize a document by its `k` highest-weighted sentences
+    pub fn summarize(&self, document_id: &str, k: usize) -> ApplicationResult<Summary> {
+        self.ensure_indexed()?;
+        self.tfidf_service.summarize(document_id, &self.corpus_id, k)
+    }
+
+    /// Get the document service
+    pub fn document_service(&self) -> &EngineDocumentService {
+        &self.document_service
+    }
+
+    /// Get the corpus service
+    pub fn corpus_service(&self) -> &EngineCorpusService {
+        &self.corpus_service
+    }
+
+    /// Get the search service
+    pub fn search_service(&self) -> &EngineSearchService {
+        &self.search_service
+    }
+
+    /// Get the TF-IDF analysis service
+    pub fn tfidf_service(&self) -> &EngineTfIdfService {
+        &self.tfidf_service
+    }
+
+    /// Rebuild the corpus index if documents changed since the last query
+    fn ensure_indexed(&self) -> ApplicationResult<()> {
+        if self.needs_index.swap(false, Ordering::SeqCst)
+            && let Err(e) = self.corpus_service.build_index(&self.corpus_id)
+        {
+            self.needs_index.store(true, Ordering::SeqCst);
+            return Err(e);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quick_start() {
+        let engine = TfIdfEngine::builder()
+            .corpus("articles", "Articles")
+            .stopwords(["language"])
+            .build()
+            .unwrap();
+
+        engine.add_document("doc1", "Rust is a fast and safe language").unwrap();
+        engine.add_document("doc2", "Python is a popular language").unwrap();
+        engine.add_document("doc3", "Go is a simple language").unwrap();
+
+        let results = engine.search("safe").unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].document().id().value(), "doc1");
+        assert_eq!(engine.keywords("doc2", 1).unwrap()[0].term(), "popular");
+
+        engine.add_document("doc4", "Rust is safe and popular").unwrap();
+        assert_eq!(engine.search("safe").unwrap().len(), 2);
+
+        engine.remove_document("doc4").unwrap();
+        assert_eq!(engine.search("safe").unwrap().len(), 1);
+        assert!(engine.corpus().unwrap().is_stopword("language"));
+    }
+}
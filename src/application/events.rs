@@ -0,0 +1,153 @@
+// src/application/events.rs
+
+//! Notifications published by the services after a change has been saved.
+//!
+//! Subscribe an `EventListener` (or a closure) to an `EventBus` and hand the
+//! bus to the services with `set_event_bus` to invalidate caches or notify
+//! external systems.
+
+use std::sync::{Arc, RwLock};
+
+use crate::domain::{CorpusId, DocumentId};
+
+/// Something that happened to a document or corpus
+#[derive(Debug, Clone, PartialEq)]
+pub enum DomainEvent {
+    /// A document was created and analyzed
+    DocumentCreated { document_id: DocumentId },
+
+    /// A document's content, title or fields changed
+    DocumentUpdated { document_id: DocumentId },
+
+    /// A document was deleted
+    DocumentDeleted { document_id: DocumentId },
+
+    /// A document was added to a corpus
+    DocumentAddedToCorpus { corpus_id: CorpusId, document_id: DocumentId },
+
+    /// A document was removed from a corpus
+    DocumentRemovedFromCorpus { corpus_id: CorpusId, document_id: DocumentId },
+
+    /// A corpus's outdated copy of a document was replaced with the stored document
+    DocumentRefreshedInCorpus { corpus_id: CorpusId, document_id: DocumentId },
+
+    /// A corpus index was (re)built
+    CorpusIndexed { corpus_id: CorpusId, documents: usize, terms: usize },
+
+    /// A corpus was deleted
+    CorpusDeleted { corpus_id: CorpusId },
+}
+
+impl DomainEvent {
+    /// Get the corpus the event concerns, if any
+    pub fn corpus_id(&self) -> Option<&CorpusId> {
+        match self {
+            DomainEvent::DocumentAddedToCorpus { corpus_id, .. }
+            | DomainEvent::DocumentRemovedFromCorpus { corpus_id, .. }
+            | DomainEvent::DocumentRefreshedInCorpus { corpus_id, .. }
+            | DomainEvent::CorpusIndexed { corpus_id, .. }
+            | DomainEvent::CorpusDeleted { corpus_id } => Some(corpus_id),
+            _ => None,
+        }
+    }
+
+    /// Get the document the event concerns, if any
+    pub fn document_id(&self) -> Option<&DocumentId> {
+        match self {
+            DomainEvent::DocumentCreated { document_id }
+            | DomainEvent::DocumentUpdated { document_id }
+            | DomainEvent::DocumentDeleted { document_id }
+            | DomainEvent::DocumentAddedToCorpus { document_id, .. }
+            | DomainEvent::DocumentRemovedFromCorpus { document_id, .. }
+            | DomainEvent::DocumentRefreshedInCorpus { document_id, .. } => Some(document_id),
+            _ => None,
+        }
+    }
+}
+
+/// Receives published events
+///
+/// Listeners run synchronously on the publishing thread, so they should be quick.
+pub trait EventListener: Send + Sync {
+    /// Handle an event
+    fn on_event(&self, event: &DomainEvent);
+}
+
+impl<F> EventListener for F
+where
+    F: Fn(&DomainEvent) + Send + Sync,
+{
+    fn on_event(&self, event: &DomainEvent) {
+        self(event)
+    }
+}
+
+/// Dispatches events to every subscribed listener, in subscription order
+#[derive(Default)]
+pub struct EventBus {
+    listeners: RwLock<Vec<Arc<dyn EventListener>>>,
+}
+
+impl EventBus {
+    /// Create a bus without listeners
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a listener
+    pub fn subscribe(&self, listener: Arc<dyn EventListener>) {
+        if let Ok(mut listeners) = self.listeners.write() {
+            listeners.push(listener);
+        }
+    }
+
+    /// Deliver an event to all listeners
+    pub fn publish(&self, event: &DomainEvent) {
+        // Clone the list so listeners may subscribe others without deadlocking
+        let listeners = match self.listeners.read() {
+            Ok(listeners) => listeners.clone(),
+            Err(_) => return,
+        };
+        for listener in listeners {
+            listener.on_event(event);
+        }
+    }
+
+    /// Count the subscribed listeners
+    pub fn listener_count(&self) -> usize {
+        self.listeners.read().map(|listeners| listeners.len()).unwrap_or(0)
+    }
+}
+
+/// Publish an event on an optional bus
+pub(crate) fn publish(bus: &Option<Arc<EventBus>>, event: DomainEvent) {
+    if let Some(bus) = bus {
+        bus.publish(&event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_publish_to_listeners() {
+        let bus = EventBus::new();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        bus.subscribe(Arc::new(move |event: &DomainEvent| sink.lock().unwrap().push(event.clone())));
+        assert_eq!(bus.listener_count(), 1);
+
+        let event = DomainEvent::DocumentAddedToCorpus {
+            corpus_id: CorpusId::new("corpus1"),
+            document_id: DocumentId::new("doc1"),
+        };
+        bus.publish(&event);
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(*seen, vec![event]);
+        assert_eq!(seen[0].corpus_id().unwrap().value(), "corpus1");
+        assert_eq!(seen[0].document_id().unwrap().value(), "doc1");
+    }
+}
@@ -0,0 +1,148 @@
+// src/application/field_mapping.rs
+
+use serde_json::Value;
+
+use super::{ApplicationError, ApplicationResult};
+
+/// Where to find document properties in each record of a JSON import
+///
+/// Paths name object keys, with nested keys separated by dots (`"meta.author"`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldMapping {
+    id: String,
+    title: Option<String>,
+    content: String,
+
+    /// Metadata keys and the paths their values come from
+    metadata: Vec<(String, String)>,
+}
+
+impl Default for FieldMapping {
+    fn default() -> Self {
+        Self {
+            id: "id".to_string(),
+            title: Some("title".to_string()),
+            content: "content".to_string(),
+            metadata: Vec::new(),
+        }
+    }
+}
+
+impl FieldMapping {
+    /// Create a mapping reading `id`, `title` and `content`, with no metadata
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read the document ID from another path
+    pub fn with_id(mut self, path: impl Into<String>) -> Self {
+        self.id = path.into();
+        self
+    }
+
+    /// Read the title from another path, or not at all
+    pub fn with_title(mut self, path: Option<&str>) -> Self {
+        self.title = path.map(str::to_string);
+        self
+    }
+
+    /// Read the content from another path
+    pub fn with_content(mut self, path: impl Into<String>) -> Self {
+        self.content = path.into();
+        self
+    }
+
+    /// Copy the value at a path into a metadata key
+    pub fn with_metadata(mut self, key: impl Into<String>, path: impl Into<String>) -> Self {
+        self.metadata.push((key.into(), path.into()));
+        self
+    }
+
+    /// Get the path of the document ID
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Get the path of the title, if titles are read
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Get the path of the content
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// Get the metadata keys and their paths
+    pub fn metadata(&self) -> &[(String, String)] {
+        &self.metadata
+    }
+
+    /// Extract the mapped properties from one record
+    pub(crate) fn extract(&self, record: &Value) -> ApplicationResult<MappedRecord> {
+        let required = |path: &str| {
+            lookup(record, path).ok_or_else(|| {
+                ApplicationError::InvalidInput(format!("Missing field '{}'", path))
+            })
+        };
+
+        Ok(MappedRecord {
+            id: required(&self.id)?,
+            title: self.title.as_deref().and_then(|path| lookup(record, path)),
+            content: required(&self.content)?,
+            metadata: self.metadata
+                .iter()
+                .filter_map(|(key, path)| lookup(record, path).map(|value| (key.clone(), value)))
+                .collect(),
+        })
+    }
+}
+
+/// Document properties extracted from one record
+pub(crate) struct MappedRecord {
+    pub id: String,
+    pub title: Option<String>,
+    pub content: String,
+    pub metadata: Vec<(String, String)>,
+}
+
+/// Find the value at a dotted path as text; strings are taken as-is and other scalars printed
+fn lookup(record: &Value, path: &str) -> Option<String> {
+    let value = path.split('.').try_fold(record, |value, key| value.get(key))?;
+
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Null => None,
+        other => Some(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract() {
+        let record: Value = serde_json::from_str(
+            r#"{"key": 7, "body": "text", "meta": {"author": "ann", "year": 2020}}"#
+        ).unwrap();
+
+        let mapping = FieldMapping::new()
+            .with_id("key")
+            .with_content("body")
+            .with_metadata("author", "meta.author")
+            .with_metadata("year", "meta.year")
+            .with_metadata("missing", "meta.missing");
+        let mapped = mapping.extract(&record).unwrap();
+
+        assert_eq!(mapped.id, "7");
+        assert_eq!(mapped.title, None);
+        assert_eq!(mapped.content, "text");
+        assert_eq!(mapped.metadata, vec![
+            ("author".to_string(), "ann".to_string()),
+            ("year".to_string(), "2020".to_string()),
+        ]);
+
+        assert!(FieldMapping::new().extract(&record).is_err());
+    }
+}
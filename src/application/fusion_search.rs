@@ -0,0 +1,218 @@
+// src/application/fusion_search.rs
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::domain::DocumentId;
+
+use super::{ApplicationError, ApplicationResult, SearchService};
+
+/// A source of ranked results to fuse, such as a sparse scorer or an external dense retriever
+pub trait RankedSource: Send + Sync {
+    /// Rank the documents of a corpus for a raw query, best first
+    fn rank(&self, corpus_id: &str, query: &str) -> ApplicationResult<Vec<(DocumentId, f64)>>;
+}
+
+impl<S: SearchService + Send + Sync> RankedSource for S {
+    fn rank(&self, corpus_id: &str, query: &str) -> ApplicationResult<Vec<(DocumentId, f64)>> {
+        Ok(self.search_hits(corpus_id, query)?
+            .into_iter()
+            .map(|hit| (hit.document_id().clone(), hit.score()))
+            .collect())
+    }
+}
+
+/// How the ranked lists of several sources are combined
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FusionMethod {
+    /// Each list adds `weight / (k + rank)` for the documents it ranks (rank starting at 1)
+    ///
+    /// Only ranks matter, so sources with incomparable scores fuse well; `k`
+    /// (typically 60) damps the lead of the very top ranks.
+    ReciprocalRank { k: f64 },
+
+    /// Each list adds `weight * score`, with scores min-max normalized to 0..1 within the list
+    WeightedSum,
+}
+
+impl Default for FusionMethod {
+    fn default() -> Self {
+        FusionMethod::ReciprocalRank { k: 60.0 }
+    }
+}
+
+/// A document ranked by fusing several sources
+#[derive(Debug, Clone, PartialEq)]
+pub struct FusedDocument {
+    document_id: DocumentId,
+    score: f64,
+
+    /// Rank of the document in each source's list, starting at 1 (None = not ranked by the source)
+    ranks: Vec<Option<usize>>,
+}
+
+impl FusedDocument {
+    /// Get the document ID
+    pub fn document_id(&self) -> &DocumentId {
+        &self.document_id
+    }
+
+    /// Get the fused score
+    pub fn score(&self) -> f64 {
+        self.score
+    }
+
+    /// Get the rank of the document in each source's list, in the order sources were added
+    pub fn ranks(&self) -> &[Option<usize>] {
+        &self.ranks
+    }
+}
+
+/// Searches several ranked sources and fuses their results into one ranked list
+#[derive(Default)]
+pub struct FusionSearch {
+    sources: Vec<(Arc<dyn RankedSource>, f64)>,
+    method: FusionMethod,
+}
+
+impl FusionSearch {
+    /// Create a fusion search without sources
+    pub fn new(method: FusionMethod) -> Self {
+        Self { sources: Vec::new(), method }
+    }
+
+    /// Get the fusion method
+    pub fn method(&self) -> FusionMethod {
+        self.method
+    }
+
+    /// Add a source whose contribution is scaled by `weight`
+    pub fn add_source(&mut self, source: Arc<dyn RankedSource>, weight: f64) -> ApplicationResult<()> {
+        if !weight.is_finite() || weight < 0.0 {
+            return Err(ApplicationError::InvalidInput(format!("Source weight must be finite and non-negative, got {}", weight)));
+        }
+
+        self.sources.push((source, weight));
+        Ok(())
+    }
+
+    /// Get the number of sources
+    pub fn source_count(&self) -> usize {
+        self.sources.len()
+    }
+
+    /// Search every source and fuse their results, best first
+    pub fn search(&self, corpus_id: &str, query: &str) -> ApplicationResult<Vec<FusedDocument>> {
+        let lists = self.sources.iter()
+            .map(|(source, _)| source.rank(corpus_id, query))
+            .collect::<ApplicationResult<Vec<_>>>()?;
+        let weights: Vec<f64> = self.sources.iter().map(|(_, weight)| *weight).collect();
+
+        Ok(fuse(self.method, &lists, &weights))
+    }
+}
+
+impl RankedSource for FusionSearch {
+    fn rank(&self, corpus_id: &str, query: &str) -> ApplicationResult<Vec<(DocumentId, f64)>> {
+        Ok(self.search(corpus_id, query)?
+            .into_iter()
+            .map(|fused| (fused.document_id, fused.score))
+            .collect())
+    }
+}
+
+/// Fuse ranked lists, each best first, into one list, best first
+///
+/// Ties are broken by document ID, so the order is deterministic.
+pub fn fuse(method: FusionMethod, lists: &[Vec<(DocumentId, f64)>], weights: &[f64]) -> Vec<FusedDocument> {
+    let mut fused: HashMap<&DocumentId, FusedDocument> = HashMap::new();
+    for (index, (list, weight)) in lists.iter().zip(weights).enumerate() {
+        let (low, high) = list.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), (_, score)| {
+            (low.min(*score), high.max(*score))
+        });
+
+        for (position, (document_id, score)) in list.iter().enumerate() {
+            let contribution = match method {
+                FusionMethod::ReciprocalRank { k } => weight / (k + (position + 1) as f64),
+                FusionMethod::WeightedSum if high > low => weight * (score - low) / (high - low),
+                FusionMethod::WeightedSum => *weight,
+            };
+
+            let entry = fused.entry(document_id).or_insert_with(|| FusedDocument {
+                document_id: document_id.clone(),
+                score: 0.0,
+                ranks: vec![None; lists.len()],
+            });
+            // A document listed twice by one source counts at its best rank
+            if entry.ranks[index].is_none() {
+                entry.ranks[index] = Some(position + 1);
+                entry.score += contribution;
+            }
+        }
+    }
+
+    let mut results: Vec<FusedDocument> = fused.into_values().collect();
+    results.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.document_id.value().cmp(b.document_id.value())));
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::{CorpusService, CorpusServiceImpl, DocumentService, DocumentServiceImpl, SearchServiceImpl};
+    use crate::domain::{Bm25, RankingModel, Smoothing, TfIdf, TfIdfOptions};
+    use crate::infrastructure::repository::{InMemoryCorpusRepository, InMemoryDocumentRepository};
+    use crate::infrastructure::tokenizer::SimpleTokenizer;
+
+    fn list(ids: &[(&str, f64)]) -> Vec<(DocumentId, f64)> {
+        ids.iter().map(|(id, score)| (DocumentId::new(*id), *score)).collect()
+    }
+
+    #[test]
+    fn test_fuse() {
+        let sparse = list(&[("doc1", 9.0), ("doc2", 5.0), ("doc3", 1.0)]);
+        let dense = list(&[("doc2", 0.9), ("doc3", 0.8)]);
+
+        let rrf = fuse(FusionMethod::ReciprocalRank { k: 60.0 }, &[sparse.clone(), dense.clone()], &[1.0, 1.0]);
+        assert_eq!(rrf[0].document_id(), &DocumentId::new("doc2"));
+        assert_eq!(rrf[0].ranks(), &[Some(2), Some(1)]);
+        assert_eq!(rrf[2].document_id(), &DocumentId::new("doc1"));
+        assert_eq!(rrf[2].ranks(), &[Some(1), None]);
+
+        // Weighted sum follows the heavier source
+        let weighted = fuse(FusionMethod::WeightedSum, &[sparse, dense], &[1.0, 3.0]);
+        assert_eq!(weighted[0].document_id(), &DocumentId::new("doc2"));
+        assert!((weighted[0].score() - 3.5).abs() < 1e-9);
+        assert_eq!(weighted[1].document_id(), &DocumentId::new("doc1"));
+    }
+
+    #[test]
+    fn test_fusion_search() {
+        let document_repository = Arc::new(InMemoryDocumentRepository::new());
+        let corpus_repository = Arc::new(InMemoryCorpusRepository::new());
+        let tokenizer = Arc::new(SimpleTokenizer::new());
+        let document_service = Arc::new(DocumentServiceImpl::new(document_repository.clone(), tokenizer.clone()));
+        let corpus_service = CorpusServiceImpl::new(corpus_repository.clone(), document_repository, document_service.clone());
+
+        document_service.create_document("doc1", "Rust is a systems programming language").unwrap();
+        document_service.create_document("doc2", "Python is a scripting language").unwrap();
+        document_service.create_document("doc3", "Rust makes Rust code memory safe").unwrap();
+        corpus_service.create_corpus("corpus1", "Languages").unwrap();
+        for id in ["doc1", "doc2", "doc3"] {
+            corpus_service.add_document("corpus1", id).unwrap();
+        }
+        corpus_service.build_index("corpus1").unwrap();
+
+        let tfidf = TfIdf::new(TfIdfOptions { smoothing: Smoothing::None, ..Default::default() });
+        let bm25 = TfIdf::new(TfIdfOptions { ranking: RankingModel::Bm25(Bm25::default()), ..Default::default() });
+        let mut fusion = FusionSearch::new(FusionMethod::default());
+        fusion.add_source(Arc::new(SearchServiceImpl::new(corpus_repository.clone(), tokenizer.clone(), tfidf)), 1.0).unwrap();
+        fusion.add_source(Arc::new(SearchServiceImpl::new(corpus_repository, tokenizer, bm25)), 1.0).unwrap();
+        assert!(fusion.add_source(Arc::new(FusionSearch::default()), -1.0).is_err());
+
+        let results = fusion.search("corpus1", "rust").unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|result| result.ranks().iter().all(Option::is_some)));
+        assert!(fusion.search("missing", "rust").is_err());
+    }
+}
@@ -0,0 +1,195 @@
+// src/application/ingestion_service.rs
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::domain::{Corpus, CorpusId, DocumentId};
+use crate::infrastructure::repository::CorpusRepository;
+
+use super::events::{self, DomainEvent, EventBus};
+use super::{ApplicationError, ApplicationResult, DocumentService};
+
+/// A raw input to turn into a document
+#[derive(Debug, Clone, PartialEq)]
+pub enum IngestionSource {
+    /// Text with the ID and optional title of the document to create
+    Text { id: String, title: Option<String>, content: String },
+
+    /// A file, read and identified by its path
+    File(PathBuf),
+}
+
+impl IngestionSource {
+    /// Create a source from untitled text
+    pub fn text(id: impl Into<String>, content: impl Into<String>) -> Self {
+        IngestionSource::Text { id: id.into(), title: None, content: content.into() }
+    }
+
+    /// Create a source from titled text
+    pub fn titled(id: impl Into<String>, title: impl Into<String>, content: impl Into<String>) -> Self {
+        IngestionSource::Text { id: id.into(), title: Some(title.into()), content: content.into() }
+    }
+
+    /// Create a source from a file
+    pub fn file(path: impl Into<PathBuf>) -> Self {
+        IngestionSource::File(path.into())
+    }
+}
+
+/// A step of an ingestion run, reported as it completes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IngestionProgress {
+    /// A document was created and added to the corpus
+    DocumentIngested { document_id: DocumentId, completed: usize, total: usize },
+
+    /// The corpus index is about to be built
+    Indexing { corpus_id: CorpusId, documents: usize },
+}
+
+/// Service interface for turning raw texts and files into an indexed corpus in one operation
+pub trait IngestionService {
+    /// Create a document from each source, add them all to a corpus and build its index
+    ///
+    /// The corpus is saved once, after indexing. Ingestion stops at the first
+    /// source that fails: documents created before it stay created, but the
+    /// corpus is left unchanged.
+    fn ingest(
+        &self,
+        corpus_id: &str,
+        sources: Vec<IngestionSource>,
+        progress: &dyn Fn(&IngestionProgress)
+    ) -> ApplicationResult<Corpus>;
+}
+
+/// Implementation of the IngestionService
+///
+/// Documents are analyzed by the document service, so its preprocessor,
+/// stemmer and tokenizer apply.
+pub struct IngestionServiceImpl<CR, DS>
+where
+    CR: CorpusRepository,
+    DS: DocumentService,
+{
+    corpus_repository: Arc<CR>,
+    document_service: Arc<DS>,
+    events: Option<Arc<EventBus>>,
+}
+
+impl<CR, DS> IngestionServiceImpl<CR, DS>
+where
+    CR: CorpusRepository,
+    DS: DocumentService,
+{
+    /// Create a new IngestionServiceImpl
+    pub fn new(corpus_repository: Arc<CR>, document_service: Arc<DS>) -> Self {
+        Self {
+            corpus_repository,
+            document_service,
+            events: None,
+        }
+    }
+
+    /// Publish corpus events once the ingested corpus is saved (None = publish nothing)
+    pub fn set_event_bus(&mut self, events: Option<Arc<EventBus>>) {
+        self.events = events;
+    }
+}
+
+impl<CR, DS> IngestionService for IngestionServiceImpl<CR, DS>
+where
+    CR: CorpusRepository,
+    DS: DocumentService,
+{
+    fn ingest(
+        &self,
+        corpus_id: &str,
+        sources: Vec<IngestionSource>,
+        progress: &dyn Fn(&IngestionProgress)
+    ) -> ApplicationResult<Corpus> {
+        let mut corpus = super::find_corpus(&*self.corpus_repository, &CorpusId::new(corpus_id))?.corpus().clone();
+
+        let total = sources.len();
+        let mut ingested = Vec::with_capacity(total);
+        for (index, source) in sources.into_iter().enumerate() {
+            let document = match source {
+                IngestionSource::Text { id, title: Some(title), content } => {
+                    self.document_service.create_document_with_title(&id, &title, &content)?
+                },
+                IngestionSource::Text { id, title: None, content } => {
+                    self.document_service.create_document(&id, &content)?
+                },
+                IngestionSource::File(path) => self.document_service.create_document_from_path(&path)?,
+            };
+
+            let document_id = document.id().clone();
+            corpus.add_document(document)?;
+            progress(&IngestionProgress::DocumentIngested { document_id: document_id.clone(), completed: index + 1, total });
+            ingested.push(document_id);
+        }
+
+        progress(&IngestionProgress::Indexing { corpus_id: corpus.id().clone(), documents: corpus.document_count() });
+        corpus.build_index()?;
+
+        self.corpus_repository.save(&corpus).map_err(|e| {
+            ApplicationError::RepositoryError(format!("Error saving corpus: {}", e))
+        })?;
+        for document_id in ingested {
+            events::publish(&self.events, DomainEvent::DocumentAddedToCorpus { corpus_id: corpus.id().clone(), document_id });
+        }
+        events::publish(&self.events, DomainEvent::CorpusIndexed {
+            corpus_id: corpus.id().clone(),
+            documents: corpus.document_count(),
+            terms: corpus.vocabulary().len(),
+        });
+
+        Ok(corpus)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::application::{CorpusService, CorpusServiceImpl, DocumentServiceImpl};
+    use crate::domain::Term;
+    use crate::infrastructure::repository::{InMemoryCorpusRepository, InMemoryDocumentRepository};
+    use crate::infrastructure::tokenizer::SimpleTokenizer;
+
+    #[test]
+    fn test_ingest() {
+        let document_repository = Arc::new(InMemoryDocumentRepository::new());
+        let corpus_repository = Arc::new(InMemoryCorpusRepository::new());
+        let document_service = Arc::new(DocumentServiceImpl::new(document_repository.clone(), Arc::new(SimpleTokenizer::new())));
+        let corpus_service = CorpusServiceImpl::new(corpus_repository.clone(), document_repository, document_service.clone());
+        corpus_service.create_corpus("corpus1", "Ingested").unwrap();
+
+        let root = std::env::temp_dir().join(format!("tf-idf-rs-ingestion-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        let path = root.join("notes.txt");
+        std::fs::write(&path, "Rust ownership notes").unwrap();
+
+        let service = IngestionServiceImpl::new(corpus_repository, document_service);
+        let steps = Mutex::new(Vec::new());
+        let corpus = service.ingest("corpus1", vec![
+            IngestionSource::text("doc1", "Rust is fast"),
+            IngestionSource::titled("doc2", "Go", "Go is simple"),
+            IngestionSource::file(&path),
+        ], &|step| steps.lock().unwrap().push(step.clone())).unwrap();
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert!(corpus.is_indexed());
+        assert_eq!(corpus.document_count(), 3);
+        assert_eq!(corpus.document_frequency(&Term::new("rust")), 2);
+        assert_eq!(corpus_service.get_corpus("corpus1").unwrap().document_count(), 3);
+
+        let steps = steps.into_inner().unwrap();
+        assert_eq!(steps.len(), 4);
+        assert_eq!(steps[1], IngestionProgress::DocumentIngested { document_id: DocumentId::new("doc2"), completed: 2, total: 3 });
+        assert_eq!(steps[3], IngestionProgress::Indexing { corpus_id: CorpusId::new("corpus1"), documents: 3 });
+
+        // A failing source leaves the corpus as it was
+        assert!(service.ingest("corpus1", vec![IngestionSource::text("doc1", "again")], &|_| {}).is_err());
+        assert_eq!(corpus_service.get_corpus("corpus1").unwrap().document_count(), 3);
+    }
+}
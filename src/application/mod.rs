@@ -5,13 +5,100 @@
 //! This module contains service interfaces and implementations that coordinate
 //! domain entities and provide core application functionality.
 
+use std::borrow::Cow;
+
+use crate::domain::{Corpus, CorpusId, CorpusSnapshot, Term, TfIdf};
+use crate::infrastructure::repository::CorpusRepository;
+use crate::infrastructure::tokenizer::{Lemmatizer, Stemmer, Tokenizer};
+
 mod document_service;
 mod corpus_service;
 mod tf_idf_service;
+mod search_service;
+mod field_mapping;
+mod vectorizer;
+mod classification;
+mod recommendation_service;
+mod engine;
+mod similarity_service;
+mod vector_cache;
+mod events;
+mod ingestion_service;
+mod fusion_search;
 
 pub use document_service::{DocumentService, DocumentServiceImpl};
-pub use corpus_service::{CorpusService, CorpusServiceImpl};
-//pub use tf_idf_service::{TfIdfService, TfIdfServiceImpl};
+pub use corpus_service::{CorpusService, CorpusServiceImpl, CorpusHealthReport, HealthIssue};
+pub use search_service::{CorpusScoredDocument, SearchService, SearchServiceImpl};
+pub use field_mapping::FieldMapping;
+pub use vectorizer::TfIdfVectorizer;
+pub use tf_idf_service::{Keyword, TfIdfService, TfIdfServiceImpl};
+pub use engine::{EngineBuilder, TfIdfEngine, EngineDocumentService, EngineCorpusService, EngineSearchService, EngineTfIdfService};
+pub use similarity_service::{SimilarityService, SimilarityServiceImpl};
+pub use recommendation_service::{Recommendation, RecommendationService, RecommendationServiceImpl};
+pub use classification::{NaiveBayesClassifier, KnnClassifier, Prediction};
+pub use events::{DomainEvent, EventBus, EventListener};
+pub use ingestion_service::{IngestionProgress, IngestionService, IngestionServiceImpl, IngestionSource};
+pub use fusion_search::{fuse, FusedDocument, FusionMethod, FusionSearch, RankedSource};
+
+/// Get the calculator for a corpus, using the corpus's own options unless overridden
+///
+/// The configured calculator is borrowed when the corpus has no options of its own.
+fn corpus_calculator<'a>(tfidf: &'a TfIdf, corpus: &Corpus, override_corpus_options: bool) -> Cow<'a, TfIdf> {
+    match corpus.options() {
+        Some(options) if !override_corpus_options => {
+            let mut tfidf = tfidf.clone();
+            tfidf.set_options(options.clone());
+            Cow::Owned(tfidf)
+        },
+        _ => Cow::Borrowed(tfidf),
+    }
+}
+
+/// Look up a corpus by ID, with the documents it holds by ID loaded
+///
+/// Loading them here makes a document lookup error fail the call up front,
+/// even for calls answered from the index alone. Documents that no longer
+/// exist stay left out.
+fn find_corpus<CR: CorpusRepository + ?Sized>(corpus_repository: &CR, corpus_id: &CorpusId) -> ApplicationResult<CorpusSnapshot> {
+    let corpus = corpus_repository.find_snapshot(corpus_id).map_err(|e| {
+        ApplicationError::RepositoryError(format!("Error retrieving corpus: {}", e))
+    })?.ok_or_else(|| {
+        ApplicationError::NotFound(format!("Corpus with ID '{}' not found", corpus_id.value()))
+    })?;
+    corpus.load_documents()?;
+    Ok(corpus)
+}
+
+/// Turn a raw query into terms the way documents were analyzed, lemmatizing and stemming if configured
+///
+/// Stopwords are kept but marked, so the calculator's filter_stopwords option decides.
+fn analyze_query<T: Tokenizer + ?Sized>(
+    tokenizer: &T,
+    lemmatizer: Option<&dyn Lemmatizer>,
+    stemmer: Option<&dyn Stemmer>,
+    query: &str
+) -> Vec<Term> {
+    tokenizer.tokenize(query)
+        .into_iter()
+        .map(|token| {
+            let is_stopword = tokenizer.is_stopword(&token);
+            let token = match lemmatizer {
+                Some(lemmatizer) => lemmatizer.lemmatize(&token),
+                None => token
+            };
+            let stem = stemmer.map(|stemmer| stemmer.stem(&token));
+            let mut term = if is_stopword {
+                Term::stopword(token)
+            } else {
+                Term::new(token)
+            };
+            if let Some(stem) = stem {
+                term.set_stem(stem);
+            }
+            term
+        })
+        .collect()
+}
 
 /// Common error type for application operations
 #[derive(Debug, thiserror::Error)]
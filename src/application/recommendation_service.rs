@@ -0,0 +1,230 @@
+// src/application/recommendation_service.rs
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use serde::{Serialize, Deserialize};
+
+use crate::domain::{CorpusId, CorpusSnapshot, Document, DocumentId, TfIdf};
+use crate::infrastructure::repository::CorpusRepository;
+
+use super::{find_corpus, ApplicationError, ApplicationResult};
+use super::vector_cache::VectorCache;
+
+/// A document recommended as related to the seed documents
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Recommendation {
+    corpus_id: CorpusId,
+    document_id: DocumentId,
+
+    /// Cosine similarity to the seed documents' combined vector
+    score: f64,
+}
+
+impl Recommendation {
+    /// Get the corpus containing the recommended document
+    pub fn corpus_id(&self) -> &CorpusId {
+        &self.corpus_id
+    }
+
+    /// Get the recommended document
+    pub fn document_id(&self) -> &DocumentId {
+        &self.document_id
+    }
+
+    /// Get the similarity to the seed documents
+    pub fn score(&self) -> f64 {
+        self.score
+    }
+}
+
+/// Service interface for recommending related documents across corpora
+pub trait RecommendationService {
+    /// Get the `k` documents most related to one document, best first
+    ///
+    /// The document is looked up in the given corpora, and candidates come from
+    /// all of them. The document itself and the `exclude` IDs are never returned.
+    fn recommend(&self, document_id: &str, corpus_ids: &[&str], k: usize, exclude: &[&str]) -> ApplicationResult<Vec<Recommendation>>;
+    
+    /// Get the `k` documents most related to a set of documents, such as a user's reading history, best first
+    ///
+    /// Candidates are ranked against the sum of the set's normalized vectors, so
+    /// every seed document counts equally.
+    fn recommend_for_set(&self, document_ids: &[&str], corpus_ids: &[&str], k: usize, exclude: &[&str]) -> ApplicationResult<Vec<Recommendation>>;
+    
+    /// Drop all cached document vectors
+    fn clear_cache(&self);
+}
+
+/// Implementation of the RecommendationService
+///
+/// Each corpus's TF-IDF vectors are computed once and reused until the corpus
+/// revision changes. Seed documents are re-weighted against every candidate
+/// corpus, so similarities across corpora use the candidate corpus's IDF.
+pub struct RecommendationServiceImpl<CR>
+where
+    CR: CorpusRepository,
+{
+    corpus_repository: Arc<CR>,
+    tfidf: TfIdf,
+    vector_cache: VectorCache,
+}
+
+impl<CR> RecommendationServiceImpl<CR>
+where
+    CR: CorpusRepository,
+{
+    /// Create a new RecommendationServiceImpl
+    pub fn new(corpus_repository: Arc<CR>, tfidf: TfIdf) -> Self {
+        Self {
+            corpus_repository,
+            tfidf,
+            vector_cache: VectorCache::new(),
+        }
+    }
+
+    /// Get the TF-IDF calculator
+    pub fn tfidf(&self) -> &TfIdf {
+        &self.tfidf
+    }
+}
+
+impl<CR> RecommendationService for RecommendationServiceImpl<CR>
+where
+    CR: CorpusRepository,
+{
+    fn recommend(&self, document_id: &str, corpus_ids: &[&str], k: usize, exclude: &[&str]) -> ApplicationResult<Vec<Recommendation>> {
+        self.recommend_for_set(&[document_id], corpus_ids, k, exclude)
+    }
+    
+    fn recommend_for_set(&self, document_ids: &[&str], corpus_ids: &[&str], k: usize, exclude: &[&str]) -> ApplicationResult<Vec<Recommendation>> {
+        if document_ids.is_empty() {
+            return Err(ApplicationError::InvalidInput("At least one seed document is required".to_string()));
+        }
+        if corpus_ids.is_empty() {
+            return Err(ApplicationError::InvalidInput("At least one corpus is required".to_string()));
+        }
+
+        let corpora = corpus_ids.iter()
+            .map(|id| find_corpus(&*self.corpus_repository, &CorpusId::new(*id)))
+            .collect::<ApplicationResult<Vec<CorpusSnapshot>>>()?;
+
+        let seeds = document_ids.iter()
+            .map(|id| {
+                let id = DocumentId::new(*id);
+                corpora.iter().find_map(|corpus| corpus.get_document(&id).transpose()).transpose()?.ok_or_else(|| {
+                    ApplicationError::NotFound(format!("Document '{}' not found in the given corpora", id.value()))
+                })
+            })
+            .collect::<ApplicationResult<Vec<&Document>>>()?;
+
+        let skipped: HashSet<&str> = document_ids.iter().chain(exclude).copied().collect();
+
+        let mut recommendations = Vec::new();
+        for corpus in &corpora {
+            let index = self.vector_cache.get_or_build(&self.tfidf, corpus)?;
+
+            let mut profile: HashMap<String, f64> = HashMap::new();
+            for seed in &seeds {
+                for (term, weight) in self.tfidf.document_vector(seed, corpus)? {
+                    *profile.entry(term).or_insert(0.0) += weight;
+                }
+            }
+
+            recommendations.extend(index.nearest(&profile, index.len())
+                .into_iter()
+                .filter(|similar| !skipped.contains(similar.document_id().value()))
+                .take(k)
+                .map(|similar| Recommendation {
+                    corpus_id: corpus.id().clone(),
+                    document_id: similar.document_id().clone(),
+                    score: similar.similarity(),
+                }));
+        }
+
+        recommendations.sort_by(|a, b| {
+            b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.corpus_id.value().cmp(b.corpus_id.value()))
+                .then_with(|| a.document_id.value().cmp(b.document_id.value()))
+        });
+        recommendations.truncate(k);
+
+        Ok(recommendations)
+    }
+    
+    fn clear_cache(&self) {
+        self.vector_cache.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Corpus, Term};
+    use crate::infrastructure::repository::InMemoryCorpusRepository;
+
+    fn corpus(id: &str, texts: &[(&str, &str)]) -> Corpus {
+        let mut corpus = Corpus::new(id, id);
+        for (document_id, text) in texts {
+            let mut document = Document::new(*document_id, *text);
+            document.add_terms(text.split(' ').map(Term::new));
+            corpus.add_document(document).unwrap();
+        }
+        corpus.build_index().unwrap();
+        corpus
+    }
+
+    fn create_service() -> (Arc<InMemoryCorpusRepository>, RecommendationServiceImpl<InMemoryCorpusRepository>) {
+        let repository = Arc::new(InMemoryCorpusRepository::new());
+        repository.save(&corpus("news", &[
+            ("n1", "rust compiler release faster builds"),
+            ("n2", "rust compiler borrow checker"),
+            ("n3", "football match ends in a draw"),
+            ("n4", "stock market rally continues"),
+            ("n5", "election results announced tonight"),
+        ])).unwrap();
+        repository.save(&corpus("blogs", &[
+            ("b1", "my favorite rust compiler flags"),
+            ("b2", "baking sourdough bread at home"),
+            ("b3", "travel tips for japan"),
+            ("b4", "gardening in small spaces"),
+        ])).unwrap();
+        let service = RecommendationServiceImpl::new(Arc::clone(&repository), TfIdf::default());
+        (repository, service)
+    }
+
+    #[test]
+    fn test_recommend_across_corpora() {
+        let (_, service) = create_service();
+
+        let recommendations = service.recommend("n1", &["news", "blogs"], 5, &[]).unwrap();
+        let ids: Vec<&str> = recommendations.iter().map(|r| r.document_id().value()).collect();
+        assert!(ids.contains(&"n2"));
+        assert!(ids.contains(&"b1"));
+        assert!(!ids.contains(&"n1"));
+        assert!(!ids.contains(&"n3"));
+        assert!(recommendations.windows(2).all(|pair| pair[0].score() >= pair[1].score()));
+
+        let excluded = service.recommend("n1", &["news", "blogs"], 5, &["n2"]).unwrap();
+        assert!(excluded.iter().all(|r| r.document_id().value() != "n2"));
+
+        assert!(matches!(service.recommend("missing", &["news"], 5, &[]), Err(ApplicationError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_cache_follows_corpus_changes() {
+        let (repository, service) = create_service();
+        assert!(service.recommend_for_set(&["b2"], &["blogs"], 5, &[]).unwrap().is_empty());
+
+        let mut blogs = repository.find(&CorpusId::new("blogs")).unwrap().unwrap();
+        let mut document = Document::new("b5", "sourdough bread starter");
+        document.add_terms("sourdough bread starter".split(' ').map(Term::new));
+        blogs.add_document(document).unwrap();
+        blogs.build_index().unwrap();
+        repository.save(&blogs).unwrap();
+
+        let recommendations = service.recommend_for_set(&["b2"], &["blogs"], 5, &[]).unwrap();
+        assert_eq!(recommendations.len(), 1);
+        assert_eq!(recommendations[0].document_id().value(), "b5");
+    }
+}
@@ -0,0 +1,703 @@
+// src/application/search_service.rs
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use tracing::field::Empty;
+use tracing::Span;
+
+use crate::domain::{CorpusId, CorpusSnapshot, FacetedResults, GlobalStats, HighlightedDocument, Page, PageRequest, Query, ScoredDocument, SearchHit, SearchRequest, SnippetGenerator, Term, TfIdf};
+use crate::infrastructure::metrics::{self, Metrics, NoopMetrics};
+use crate::infrastructure::repository::CorpusRepository;
+use crate::infrastructure::telemetry;
+use crate::infrastructure::tokenizer::{Lemmatizer, Stemmer, Tokenizer};
+
+use super::{corpus_calculator, find_corpus, ApplicationError, ApplicationResult};
+
+/// Service interface for searching corpora with raw text queries
+pub trait SearchService {
+    /// Turn a raw query string into terms, using the same analysis as documents
+    fn analyze_query(&self, query: &str) -> Vec<Term>;
+
+    /// Extract the quoted phrases of a query, e.g. `"machine learning"`
+    fn analyze_phrases(&self, query: &str) -> Vec<Vec<Term>>;
+
+    /// Extract the prefix and wildcard patterns of a query, e.g. `rust*` or `colo?r`
+    ///
+    /// `*` matches any run of characters and `?` a single one. Patterns are
+    /// case-folded the way the tokenizer folds indexed terms.
+    fn analyze_wildcards(&self, query: &str) -> Vec<String>;
+
+    /// Search a corpus with a raw query string
+    ///
+    /// Quoted phrases only match documents where their terms are adjacent, and
+    /// a word suffixed with `^boost`, e.g. `rust^2.0`, weighs `boost` times as much.
+    fn search(&self, corpus_id: &str, query: &str) -> ApplicationResult<Vec<ScoredDocument>>;
+
+    /// Search a corpus with a raw query string, returning lightweight hits
+    fn search_hits(&self, corpus_id: &str, query: &str) -> ApplicationResult<Vec<SearchHit>>;
+
+    /// Search a corpus with a raw query string, returning one page of results
+    fn search_page(&self, corpus_id: &str, query: &str, request: PageRequest) -> ApplicationResult<Page<ScoredDocument>>;
+
+    /// Search a corpus with a raw query string, counting matches per value of each metadata key
+    fn search_faceted(&self, corpus_id: &str, query: &str, facet_keys: &[&str]) -> ApplicationResult<FacetedResults<ScoredDocument>>;
+
+    /// Search a corpus with a raw query string, pairing each result with its best passage
+    fn search_highlighted(&self, corpus_id: &str, query: &str) -> ApplicationResult<Vec<HighlightedDocument>>;
+
+    /// Search a corpus with a structured boolean query
+    fn search_query(&self, corpus_id: &str, query: &Query) -> ApplicationResult<Vec<ScoredDocument>>;
+
+    /// Search a corpus with a raw query string, only matching within the given fields
+    fn search_fields(&self, corpus_id: &str, query: &str, fields: &[&str]) -> ApplicationResult<Vec<ScoredDocument>>;
+    
+    /// Search several corpora with a raw query string and merge their results, best match first
+    ///
+    /// Scores are divided by the best score within each corpus, so corpora of
+    /// different sizes and vocabularies rank on a common 0 to 1 scale.
+    fn search_multi(&self, corpus_ids: &[&str], query: &str) -> ApplicationResult<Vec<CorpusScoredDocument>>;
+}
+
+/// A search result from one of several corpora
+#[derive(Debug, Clone)]
+pub struct CorpusScoredDocument {
+    /// The corpus the document was found in
+    corpus_id: CorpusId,
+
+    /// Score relative to the best match of the same corpus
+    score: f64,
+
+    /// The result with its score within its corpus
+    scored: ScoredDocument,
+}
+
+impl CorpusScoredDocument {
+    /// Get the ID of the corpus the document was found in
+    pub fn corpus_id(&self) -> &CorpusId {
+        &self.corpus_id
+    }
+
+    /// Get the normalized score, 1.0 for the best match of each corpus
+    pub fn score(&self) -> f64 {
+        self.score
+    }
+
+    /// Get the result as scored within its own corpus
+    pub fn scored(&self) -> &ScoredDocument {
+        &self.scored
+    }
+
+    /// Take the result as scored within its own corpus
+    pub fn into_scored(self) -> ScoredDocument {
+        self.scored
+    }
+}
+
+/// Implementation of the SearchService
+pub struct SearchServiceImpl<CR, T>
+where
+    CR: CorpusRepository,
+    T: Tokenizer,
+{
+    corpus_repository: Arc<CR>,
+    tokenizer: Arc<T>,
+    tfidf: TfIdf,
+    snippets: SnippetGenerator,
+    stemmer: Option<Arc<dyn Stemmer>>,
+    lemmatizer: Option<Arc<dyn Lemmatizer>>,
+    metrics: Arc<dyn Metrics>,
+
+    /// Whether `tfidf`'s options win over options stored on a corpus
+    override_corpus_options: bool,
+}
+
+impl<CR, T> SearchServiceImpl<CR, T>
+where
+    CR: CorpusRepository,
+    T: Tokenizer,
+{
+    /// Create a new SearchServiceImpl
+    pub fn new(corpus_repository: Arc<CR>, tokenizer: Arc<T>, tfidf: TfIdf) -> Self {
+        Self {
+            corpus_repository,
+            tokenizer,
+            tfidf,
+            snippets: SnippetGenerator::default(),
+            stemmer: None,
+            lemmatizer: None,
+            metrics: Arc::new(NoopMetrics),
+            override_corpus_options: false,
+        }
+    }
+
+    /// Get the TF-IDF calculator used for ranking
+    pub fn tfidf(&self) -> &TfIdf {
+        &self.tfidf
+    }
+
+    /// Replace the generator used to extract passages for highlighted results
+    pub fn set_snippet_generator(&mut self, snippets: SnippetGenerator) {
+        self.snippets = snippets;
+    }
+
+    /// Stem query terms the same way documents were stemmed (None = no stemming)
+    pub fn set_stemmer(&mut self, stemmer: Option<Arc<dyn Stemmer>>) {
+        self.stemmer = stemmer;
+    }
+
+    /// Lemmatize query terms the same way documents were lemmatized (None = no lemmatization)
+    pub fn set_lemmatizer(&mut self, lemmatizer: Option<Arc<dyn Lemmatizer>>) {
+        self.lemmatizer = lemmatizer;
+    }
+
+    /// Record search counts, durations and hit counts
+    pub fn set_metrics(&mut self, metrics: Arc<dyn Metrics>) {
+        self.metrics = metrics;
+    }
+
+    /// Rank every corpus with this service's options, ignoring options stored on the corpus
+    pub fn set_override_corpus_options(&mut self, override_corpus_options: bool) {
+        self.override_corpus_options = override_corpus_options;
+    }
+
+    /// Compute IDF from statistics shared across corpora, so shards of one collection rank alike (None = per corpus)
+    pub fn set_global_stats(&mut self, global_stats: Option<Arc<GlobalStats>>) {
+        self.tfidf.set_global_stats(global_stats);
+    }
+
+    /// Run a search within its span, recording its hit count on the span and in the metrics
+    ///
+    /// Failed searches are neither counted nor timed.
+    fn observed<R>(
+        &self,
+        span: Span,
+        hits: impl FnOnce(&R) -> usize,
+        search: impl FnOnce() -> ApplicationResult<R>
+    ) -> ApplicationResult<R> {
+        let _entered = span.enter();
+        let started = Instant::now();
+        let results = search()?;
+
+        let hits = hits(&results);
+        span.record("hits", hits);
+        self.metrics.increment_counter(metrics::SEARCHES, 1);
+        self.metrics.record_duration(metrics::SEARCH_SECONDS, started);
+        self.metrics.record_histogram(metrics::SEARCH_HITS, hits as f64);
+        Ok(results)
+    }
+
+    /// Parse a query into weighted terms and look up the corpus it should run against
+    ///
+    /// Wildcard patterns are expanded into the matching corpus terms.
+    fn prepare(&self, corpus_id: &str, query: &str) -> ApplicationResult<PreparedQuery> {
+        let (wildcard_words, plain): (Vec<&str>, Vec<&str>) = query.split_whitespace().partition(|word| word.contains(['*', '?']));
+        let wildcards: Vec<(String, f64)> = wildcard_words.into_iter()
+            .flat_map(|word| {
+                let (text, boost) = split_boost(word);
+                self.analyze_wildcards(text).into_iter().map(move |pattern| (pattern, boost))
+            })
+            .collect();
+
+        // Analyze the words together, as n-gram tokenizers combine neighbours, then apply boosts
+        let words: Vec<(&str, f64)> = plain.into_iter().map(split_boost).collect();
+        let text: Vec<&str> = words.iter().map(|(text, _)| *text).collect();
+        let mut terms: Vec<(Term, f64)> = self.analyze_query(&text.join(" ")).into_iter().map(|term| (term, 1.0)).collect();
+        for (text, boost) in words.iter().filter(|(_, boost)| *boost != 1.0) {
+            for boosted in self.analyze_query(text) {
+                for (_, weight) in terms.iter_mut().filter(|(term, _)| *term == boosted) {
+                    *weight = *boost;
+                }
+            }
+        }
+
+        if terms.is_empty() && wildcards.is_empty() {
+            return Err(ApplicationError::InvalidInput("Query contains no searchable terms".to_string()));
+        }
+        let phrases = self.analyze_phrases(query);
+        let corpus = find_corpus(&*self.corpus_repository, &CorpusId::new(corpus_id))?;
+        Span::current().record("documents", corpus.document_count());
+
+        for (pattern, boost) in &wildcards {
+            for term in corpus.expand_wildcard(pattern) {
+                if !terms.iter().any(|(existing, _)| *existing == term) {
+                    terms.push((term, *boost));
+                }
+            }
+        }
+
+        Span::current().record("terms", terms.len());
+        Ok((corpus, SearchRequest::weighted(terms).with_phrases(phrases)))
+    }
+}
+
+/// A corpus with the search request for a query's weighted terms and phrases
+type PreparedQuery = (CorpusSnapshot, SearchRequest);
+
+/// Split a `word^boost` suffix off a query word, defaulting to a boost of 1.0
+fn split_boost(word: &str) -> (&str, f64) {
+    match word.rsplit_once('^') {
+        Some((text, boost)) => match boost.parse::<f64>() {
+            Ok(boost) if boost.is_finite() && boost >= 0.0 => (text, boost),
+            _ => (word, 1.0),
+        },
+        None => (word, 1.0),
+    }
+}
+
+impl<CR, T> SearchService for SearchServiceImpl<CR, T>
+where
+    CR: CorpusRepository,
+    T: Tokenizer,
+{
+    fn analyze_query(&self, query: &str) -> Vec<Term> {
+        super::analyze_query(&*self.tokenizer, self.lemmatizer.as_deref(), self.stemmer.as_deref(), query)
+    }
+
+    fn analyze_phrases(&self, query: &str) -> Vec<Vec<Term>> {
+        // Every other segment between double quotes is a phrase; boosts have no meaning inside one
+        query.split('"')
+            .skip(1)
+            .step_by(2)
+            .map(|phrase| {
+                let words: Vec<&str> = phrase.split_whitespace().map(|word| split_boost(word).0).collect();
+                self.analyze_query(&words.join(" "))
+            })
+            .filter(|phrase| phrase.len() > 1)
+            .collect()
+    }
+
+    fn analyze_wildcards(&self, query: &str) -> Vec<String> {
+        let case_folding = self.tokenizer.case_folding();
+        query.split_whitespace()
+            .filter(|word| word.contains(['*', '?']))
+            .map(|word| {
+                let pattern: String = word.chars()
+                    .filter(|c| c.is_alphanumeric() || matches!(c, '*' | '?'))
+                    .collect();
+                case_folding.fold(&pattern)
+            })
+            // A bare `*` would expand to the whole dictionary
+            .filter(|pattern| pattern.chars().any(char::is_alphanumeric))
+            .collect()
+    }
+
+    fn search(&self, corpus_id: &str, query: &str) -> ApplicationResult<Vec<ScoredDocument>> {
+        let span = tracing::debug_span!(target: telemetry::SEARCH, "search", corpus_id, terms = Empty, documents = Empty, hits = Empty);
+        self.observed(span, Vec::len, || {
+            let (corpus, request) = self.prepare(corpus_id, query)?;
+            let hits = corpus_calculator(&self.tfidf, &corpus, self.override_corpus_options).search_request(&request, &corpus)?.into_results();
+            Ok(TfIdf::resolve_hits(hits, &corpus)?)
+        })
+    }
+
+    fn search_hits(&self, corpus_id: &str, query: &str) -> ApplicationResult<Vec<SearchHit>> {
+        let span = tracing::debug_span!(target: telemetry::SEARCH, "search_hits", corpus_id, terms = Empty, documents = Empty, hits = Empty);
+        self.observed(span, Vec::len, || {
+            let (corpus, request) = self.prepare(corpus_id, query)?;
+            Ok(corpus_calculator(&self.tfidf, &corpus, self.override_corpus_options).search_request(&request, &corpus)?.into_results())
+        })
+    }
+
+    fn search_page(&self, corpus_id: &str, query: &str, request: PageRequest) -> ApplicationResult<Page<ScoredDocument>> {
+        let span = tracing::debug_span!(target: telemetry::SEARCH, "search_page", corpus_id, terms = Empty, documents = Empty, hits = Empty);
+        self.observed(span, Page::total, || {
+            let (corpus, search) = self.prepare(corpus_id, query)?;
+            let hits = corpus_calculator(&self.tfidf, &corpus, self.override_corpus_options).search_request(&search, &corpus)?.into_results();
+            Ok(TfIdf::resolve_page(Page::from_vec(hits, request), &corpus)?)
+        })
+    }
+
+    fn search_faceted(&self, corpus_id: &str, query: &str, facet_keys: &[&str]) -> ApplicationResult<FacetedResults<ScoredDocument>> {
+        let span = tracing::debug_span!(target: telemetry::SEARCH, "search_faceted", corpus_id, terms = Empty, documents = Empty, hits = Empty);
+        self.observed(span, |results: &FacetedResults<ScoredDocument>| results.results().len(), || {
+            let (corpus, request) = self.prepare(corpus_id, query)?;
+            let request = request.with_facets(facet_keys.iter().copied());
+            let (hits, facets) = corpus_calculator(&self.tfidf, &corpus, self.override_corpus_options).search_request(&request, &corpus)?.into_parts();
+            Ok(FacetedResults::new(TfIdf::resolve_hits(hits, &corpus)?, facets))
+        })
+    }
+
+    fn search_highlighted(&self, corpus_id: &str, query: &str) -> ApplicationResult<Vec<HighlightedDocument>> {
+        let results = self.search(corpus_id, query)?;
+
+        Ok(results.into_iter()
+            .map(|scored| {
+                let tokens = self.tokenizer.tokenize_with_offsets(scored.document().content());
+                self.snippets.highlight(scored, &tokens)
+            })
+            .collect())
+    }
+
+    fn search_query(&self, corpus_id: &str, query: &Query) -> ApplicationResult<Vec<ScoredDocument>> {
+        let span = tracing::debug_span!(target: telemetry::SEARCH, "search_query", corpus_id, documents = Empty, hits = Empty);
+        self.observed(span, Vec::len, || {
+            let corpus = find_corpus(&*self.corpus_repository, &CorpusId::new(corpus_id))?;
+            Span::current().record("documents", corpus.document_count());
+            let hits = corpus_calculator(&self.tfidf, &corpus, self.override_corpus_options).search_request(&SearchRequest::boolean(query.clone()), &corpus)?.into_results();
+            Ok(TfIdf::resolve_hits(hits, &corpus)?)
+        })
+    }
+
+    fn search_fields(&self, corpus_id: &str, query: &str, fields: &[&str]) -> ApplicationResult<Vec<ScoredDocument>> {
+        let span = tracing::debug_span!(target: telemetry::SEARCH, "search_fields", corpus_id, terms = Empty, documents = Empty, hits = Empty);
+        self.observed(span, Vec::len, || {
+            let (corpus, request) = self.prepare(corpus_id, query)?;
+            let hits = corpus_calculator(&self.tfidf, &corpus, self.override_corpus_options).search_request(&request.in_fields(fields.iter().copied()), &corpus)?.into_results();
+            Ok(TfIdf::resolve_hits(hits, &corpus)?)
+        })
+    }
+
+    fn search_multi(&self, corpus_ids: &[&str], query: &str) -> ApplicationResult<Vec<CorpusScoredDocument>> {
+        let span = tracing::debug_span!(target: telemetry::SEARCH, "search_multi", corpora = corpus_ids.len(), hits = Empty);
+        self.observed(span, Vec::len, || {
+            let mut merged = Vec::new();
+            for corpus_id in corpus_ids {
+                // Wildcards expand against each corpus's own dictionary
+                let (corpus, request) = self.prepare(corpus_id, query)?;
+                let hits = corpus_calculator(&self.tfidf, &corpus, self.override_corpus_options).search_request(&request, &corpus)?.into_results();
+                let results = TfIdf::resolve_hits(hits, &corpus)?;
+                let best = results.first().map_or(1.0, ScoredDocument::score);
+                merged.extend(results.into_iter().map(|scored| CorpusScoredDocument {
+                    corpus_id: corpus.id().clone(),
+                    score: scored.score() / best,
+                    scored,
+                }));
+            }
+
+            merged.sort_by(|a, b| {
+                b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| a.corpus_id.value().cmp(b.corpus_id.value()))
+                    .then_with(|| a.scored.document().id().value().cmp(b.scored.document().id().value()))
+            });
+            Ok(merged)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::{CorpusService, CorpusServiceImpl, DocumentService, DocumentServiceImpl};
+    use crate::infrastructure::repository::{InMemoryCorpusRepository, InMemoryDocumentRepository};
+    use crate::domain::{Corpus, Smoothing, TfIdfOptions};
+    use crate::infrastructure::tokenizer::{CaseFolding, PorterStemmer, SimpleTokenizer};
+
+    fn create_service() -> SearchServiceImpl<InMemoryCorpusRepository, SimpleTokenizer> {
+        let document_repository = Arc::new(InMemoryDocumentRepository::new());
+        let corpus_repository = Arc::new(InMemoryCorpusRepository::new());
+        let tokenizer = Arc::new(SimpleTokenizer::new());
+
+        let document_service = Arc::new(DocumentServiceImpl::new(document_repository.clone(), tokenizer.clone()));
+        let corpus_service = CorpusServiceImpl::new(corpus_repository.clone(), document_repository, document_service.clone());
+
+        document_service.create_document("doc1", "Rust is a systems programming language").unwrap();
+        document_service.create_document("doc2", "Python is a scripting language").unwrap();
+        document_service.create_document("doc3", "The borrow checker makes Rust memory safe").unwrap();
+        document_service.create_document("doc4", "Go is a compiled language").unwrap();
+        document_service.update_field("doc4", "tags", "systems").unwrap();
+        corpus_service.create_corpus("corpus1", "Languages").unwrap();
+        for id in ["doc1", "doc2", "doc3", "doc4"] {
+            corpus_service.add_document("corpus1", id).unwrap();
+        }
+        corpus_service.build_index("corpus1").unwrap();
+
+        SearchServiceImpl::new(corpus_repository, tokenizer, TfIdf::default())
+    }
+
+    #[test]
+    fn test_analyze_query() {
+        let service = create_service();
+
+        let terms = service.analyze_query("The Rust language!");
+        assert_eq!(terms.len(), 3);
+        assert!(terms[0].is_stopword());
+        assert_eq!(terms[1].text(), "rust");
+    }
+
+    #[test]
+    fn test_search_raw_query() {
+        let service = create_service();
+
+        let results = service.search("corpus1", "What about RUST!").unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| r.document().id().value() != "doc2"));
+
+        let hits = service.search_hits("corpus1", "scripting").unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].document_id().value(), "doc2");
+    }
+
+    #[test]
+    fn test_search_page() {
+        let service = create_service();
+
+        let all = service.search("corpus1", "rust borrow").unwrap();
+        let page = service.search_page("corpus1", "rust borrow", PageRequest::new(1, 10)).unwrap();
+        assert_eq!(page.total(), all.len());
+        assert_eq!(page.items().len(), all.len() - 1);
+        assert_eq!(page.items()[0].document().id(), all[1].document().id());
+    }
+
+    #[test]
+    fn test_search_highlighted() {
+        let service = create_service();
+
+        let results = service.search_highlighted("corpus1", "scripting").unwrap();
+        assert_eq!(results.len(), 1);
+
+        let snippet = results[0].snippet().unwrap();
+        assert_eq!(snippet.highlight("<em>", "</em>"), "Python is a <em>scripting</em> language");
+    }
+
+    #[test]
+    fn test_phrase_query() {
+        let service = create_service();
+
+        let phrases = service.analyze_phrases("\"programming language\" rust \"single\"");
+        assert_eq!(phrases.len(), 1);
+        assert_eq!(phrases[0].len(), 2);
+
+        // "systems language" occurs in doc1 but not as adjacent words
+        assert_eq!(service.search("corpus1", "systems language").unwrap().len(), 1);
+        assert!(service.search("corpus1", "\"systems language\"").unwrap().is_empty());
+
+        let results = service.search("corpus1", "\"programming language\"").unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].document().id().value(), "doc1");
+    }
+
+    #[test]
+    fn test_wildcard_query() {
+        let service = create_service();
+
+        assert_eq!(service.analyze_wildcards("Script* and \"pro*\" or * alone"), vec!["script*", "pro*"]);
+
+        let results = service.search("corpus1", "script*").unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].document().id().value(), "doc2");
+
+        // Wildcards combine with plain terms
+        assert_eq!(service.search_hits("corpus1", "scrip* borrow").unwrap().len(), 2);
+        assert!(service.search("corpus1", "zzz*").unwrap().is_empty());
+
+        // `?` matches exactly one character
+        assert_eq!(service.analyze_wildcards("Scri?ting"), vec!["scri?ting"]);
+        assert_eq!(service.search_hits("corpus1", "scri?ting").unwrap().len(), 1);
+        assert!(service.search_hits("corpus1", "rust?").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_wildcard_case_folding() {
+        let document_repository = Arc::new(InMemoryDocumentRepository::new());
+        let corpus_repository = Arc::new(InMemoryCorpusRepository::new());
+        let mut tokenizer = SimpleTokenizer::new();
+        tokenizer.set_case_folding(CaseFolding::PreserveAcronyms);
+        let tokenizer = Arc::new(tokenizer);
+
+        let document_service = Arc::new(DocumentServiceImpl::new(document_repository.clone(), tokenizer.clone()));
+        let corpus_service = CorpusServiceImpl::new(corpus_repository.clone(), document_repository, document_service.clone());
+        document_service.create_document("doc1", "NASA launches rockets").unwrap();
+        document_service.create_document("doc2", "Nasal sprays").unwrap();
+        document_service.create_document("doc3", "Unrelated text").unwrap();
+        corpus_service.create_corpus("corpus1", "Acronyms").unwrap();
+        for id in ["doc1", "doc2", "doc3"] {
+            corpus_service.add_document("corpus1", id).unwrap();
+        }
+        corpus_service.build_index("corpus1").unwrap();
+        let service = SearchServiceImpl::new(corpus_repository, tokenizer, TfIdf::default());
+
+        // Patterns fold like indexed terms, so the acronym keeps its case
+        assert_eq!(service.analyze_wildcards("NAS* Nas*"), vec!["NAS*", "nas*"]);
+        let hits = service.search_hits("corpus1", "NAS*").unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].document_id().value(), "doc1");
+        let hits = service.search_hits("corpus1", "Nas*").unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].document_id().value(), "doc2");
+    }
+
+    #[test]
+    fn test_search_multi() {
+        let service = create_service();
+        let corpus_repository = service.corpus_repository.clone();
+
+        // A second, smaller shard where "rust" scores very differently
+        let mut corpus = Corpus::new("corpus2", "Blogs");
+        for (id, content) in [("post1", "rust rust rust"), ("post2", "cooking pasta"), ("post3", "baking bread")] {
+            let mut document = crate::domain::Document::new(id, content);
+            document.add_terms(service.analyze_query(content));
+            corpus.add_document(document).unwrap();
+        }
+        corpus.build_index().unwrap();
+        corpus_repository.save(&corpus).unwrap();
+
+        let results = service.search_multi(&["corpus1", "corpus2"], "rust").unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results.iter().filter(|r| r.corpus_id().value() == "corpus2").count(), 1);
+
+        // The best match of each corpus is scored 1.0, whatever its raw score
+        assert!(results.iter().all(|r| r.score() > 0.0 && r.score() <= 1.0));
+        let post = results.iter().find(|r| r.corpus_id().value() == "corpus2").unwrap();
+        assert_eq!(post.scored().document().id().value(), "post1");
+        assert_eq!(post.score(), 1.0);
+        assert_ne!(post.scored().score(), 1.0);
+        assert_eq!(results[0].score(), 1.0);
+
+        assert!(service.search_multi(&["corpus1", "missing"], "rust").is_err());
+    }
+
+    #[test]
+    fn test_boosted_query() {
+        let service = create_service();
+
+        // Whichever word is boosted ranks its document strictly first
+        for (query, expected) in [("scripting^5 borrow", "doc2"), ("scripting borrow^5", "doc3")] {
+            let hits = service.search_hits("corpus1", query).unwrap();
+            assert_eq!(hits.len(), 2);
+            assert_eq!(hits[0].document_id().value(), expected);
+            assert!(hits[0].score() > hits[1].score());
+        }
+
+        // A malformed boost is kept as part of the word
+        assert_eq!(split_boost("rust^x"), ("rust^x", 1.0));
+        assert_eq!(split_boost("rust^2.5"), ("rust", 2.5));
+
+        // A boost inside a quoted phrase is dropped rather than analyzed as part of it
+        assert_eq!(service.analyze_phrases("\"programming language^3\""), service.analyze_phrases("\"programming language\""));
+        let results = service.search("corpus1", "\"programming language^3\"").unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].document().id().value(), "doc1");
+    }
+
+    #[test]
+    fn test_boolean_query() {
+        let service = create_service();
+
+        let query = Query::and([
+            Query::term(Term::new("rust")),
+            Query::not(Query::term(Term::new("memory"))),
+        ]);
+        let results = service.search_query("corpus1", &query).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].document().id().value(), "doc1");
+    }
+
+    #[test]
+    fn test_search_fields() {
+        let service = create_service();
+
+        let results = service.search_fields("corpus1", "systems", &["tags"]).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].document().id().value(), "doc4");
+
+        // Plain search ignores fields without a boost
+        let results = service.search("corpus1", "systems").unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].document().id().value(), "doc1");
+
+        // Phrases restrict and boosts reweight field searches as they do plain ones
+        let results = service.search_fields("corpus1", "\"programming language\" language", &["content"]).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].document().id().value(), "doc1");
+        let results = service.search_fields("corpus1", "rust^5 python", &["content"]).unwrap();
+        assert_ne!(results[0].document().id().value(), "doc2");
+        let results = service.search_fields("corpus1", "rust python^5", &["content"]).unwrap();
+        assert_eq!(results[0].document().id().value(), "doc2");
+    }
+
+    #[test]
+    fn test_stemmed_search() {
+        let document_repository = Arc::new(InMemoryDocumentRepository::new());
+        let corpus_repository = Arc::new(InMemoryCorpusRepository::new());
+        let tokenizer = Arc::new(SimpleTokenizer::new());
+        let stemmer = Arc::new(PorterStemmer::new());
+
+        let document_service = Arc::new(DocumentServiceImpl::with_stemmer(document_repository.clone(), tokenizer.clone(), stemmer.clone()));
+        let corpus_service = CorpusServiceImpl::new(corpus_repository.clone(), document_repository, document_service.clone());
+
+        document_service.create_document("doc1", "Connected devices").unwrap();
+        document_service.create_document("doc2", "Connection pooling").unwrap();
+        document_service.create_document("doc3", "Unrelated text").unwrap();
+        corpus_service.create_corpus("corpus1", "Stems").unwrap();
+        for id in ["doc1", "doc2", "doc3"] {
+            corpus_service.add_document("corpus1", id).unwrap();
+        }
+        corpus_service.build_index("corpus1").unwrap();
+
+        let tfidf = TfIdf::new(TfIdfOptions {
+            aggregate_stems: true,
+            smoothing: Smoothing::None,
+            ..TfIdfOptions::default()
+        });
+        let mut service = SearchServiceImpl::new(corpus_repository.clone(), tokenizer.clone(), tfidf);
+        service.set_stemmer(Some(stemmer.clone()));
+
+        assert_eq!(service.search("corpus1", "connecting").unwrap().len(), 2);
+
+        // A corpus can opt into stem aggregation through its own options
+        let mut service = SearchServiceImpl::new(corpus_repository, tokenizer, TfIdf::default());
+        service.set_stemmer(Some(stemmer));
+        assert!(service.search("corpus1", "connecting").unwrap().is_empty());
+
+        corpus_service.update_options("corpus1", Some(TfIdfOptions {
+            aggregate_stems: true,
+            smoothing: Smoothing::None,
+            ..TfIdfOptions::default()
+        })).unwrap();
+        assert_eq!(service.search("corpus1", "connecting").unwrap().len(), 2);
+
+        let results = service.search("corpus1", "\"connecting device\"").unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].document().id().value(), "doc1");
+
+        service.set_override_corpus_options(true);
+        assert!(service.search("corpus1", "connecting").unwrap().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "subscriber")]
+    fn test_search_is_traced() {
+        use crate::infrastructure::telemetry::tests::CollectingWriter;
+
+        let service = create_service();
+        let writer = CollectingWriter::default();
+        let subscriber = telemetry::subscriber("debug", writer.clone()).unwrap();
+        tracing::subscriber::with_default(subscriber, || service.search("corpus1", "rust").unwrap());
+
+        let lines = writer.lines();
+        let line = lines.iter().find(|line| line.contains("search{corpus_id=\"corpus1\"")).expect("search span");
+        assert!(line.contains("terms=1") && line.contains("documents=4") && line.contains("hits=2"), "{}", line);
+    }
+
+    #[test]
+    fn test_search_is_metered() {
+        use crate::infrastructure::metrics::InMemoryMetrics;
+
+        let mut service = create_service();
+        let recorder = Arc::new(InMemoryMetrics::new());
+        service.set_metrics(recorder.clone());
+        service.search("corpus1", "rust").unwrap();
+        service.search_hits("corpus1", "scripting").unwrap();
+
+        let snapshot = recorder.snapshot();
+        assert_eq!(snapshot.counter(metrics::SEARCHES), 2);
+        assert_eq!(snapshot.histogram(metrics::SEARCH_SECONDS).unwrap().count, 2);
+        assert_eq!(snapshot.histogram(metrics::SEARCH_HITS).unwrap().sum, 3.0);
+        
+        // Faceted and field searches are counted too
+        service.search_faceted("corpus1", "rust", &[]).unwrap();
+        service.search_fields("corpus1", "rust", &["title"]).unwrap();
+        let snapshot = recorder.snapshot();
+        assert_eq!(snapshot.counter(metrics::SEARCHES), 4);
+        assert_eq!(snapshot.histogram(metrics::SEARCH_SECONDS).unwrap().count, 4);
+    }
+
+    #[test]
+    fn test_search_errors() {
+        let service = create_service();
+
+        assert!(matches!(service.search("corpus1", "?!"), Err(ApplicationError::InvalidInput(_))));
+        assert!(matches!(service.search("missing", "rust"), Err(ApplicationError::NotFound(_))));
+    }
+}
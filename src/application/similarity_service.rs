@@ -0,0 +1,372 @@
+// src/application/similarity_service.rs
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::domain::{Corpus, CorpusId, Document, DocumentEmbedder, DocumentId, DomainError, SimilarDocument, SimilarityMatrix, SimilarityMetric, TfIdf, TfIdfError};
+use crate::infrastructure::repository::CorpusRepository;
+use crate::infrastructure::tokenizer::{Lemmatizer, Stemmer, Tokenizer};
+
+use super::{analyze_query, find_corpus, ApplicationError, ApplicationResult};
+use super::vector_cache::VectorCache;
+
+/// Service interface for TF-IDF cosine similarities within stored corpora
+pub trait SimilarityService {
+    /// Calculate the cosine similarity between two documents of a corpus
+    fn document_similarity(&self, corpus_id: &str, first_id: &str, second_id: &str) -> ApplicationResult<f64>;
+    
+    /// Compare two documents of a corpus under a metric; distance metrics return lower values for closer documents
+    fn document_similarity_with(&self, corpus_id: &str, first_id: &str, second_id: &str, metric: SimilarityMetric) -> ApplicationResult<f64>;
+    
+    /// Calculate the cosine similarity between a document of a corpus and a raw query string
+    fn query_similarity(&self, corpus_id: &str, document_id: &str, query: &str) -> ApplicationResult<f64>;
+    
+    /// Find the `k` documents of a corpus most similar to one of its documents, most similar first
+    fn most_similar(&self, corpus_id: &str, document_id: &str, k: usize) -> ApplicationResult<Vec<SimilarDocument>>;
+    
+    /// Find the `k` documents of a corpus most similar to one of its documents under a metric, most similar first
+    fn most_similar_with(&self, corpus_id: &str, document_id: &str, k: usize, metric: SimilarityMetric) -> ApplicationResult<Vec<SimilarDocument>>;
+    
+    /// Find every document of a corpus whose cosine similarity to one of its documents exceeds a threshold (0 to 1)
+    fn find_documents_above_similarity(&self, corpus_id: &str, document_id: &str, threshold: f64) -> ApplicationResult<Vec<SimilarDocument>>;
+    
+    /// Find the `k` documents of a corpus most similar to a raw query string, most similar first
+    fn most_similar_to_query(&self, corpus_id: &str, query: &str, k: usize) -> ApplicationResult<Vec<SimilarDocument>>;
+    
+    /// Find the `k` documents of a corpus most similar to a raw query string, mixing TF-IDF and dense embeddings
+    ///
+    /// `dense_weight` (0 to 1) is the share of the dense cosine similarity in
+    /// each score. Requires an embedder.
+    fn hybrid_search(&self, corpus_id: &str, query: &str, k: usize, dense_weight: f64) -> ApplicationResult<Vec<SimilarDocument>>;
+    
+    /// Compute the pairwise cosine similarities of all documents in a corpus
+    fn similarity_matrix(&self, corpus_id: &str) -> ApplicationResult<SimilarityMatrix>;
+    
+    /// Drop all cached document vectors
+    fn clear_cache(&self);
+}
+
+/// Implementation of the SimilarityService
+///
+/// Document vectors are cached per corpus and rebuilt when the corpus revision changes.
+pub struct SimilarityServiceImpl<CR, T>
+where
+    CR: CorpusRepository,
+    T: Tokenizer,
+{
+    corpus_repository: Arc<CR>,
+    tokenizer: Arc<T>,
+    tfidf: TfIdf,
+    vector_cache: VectorCache,
+    embedder: Option<Arc<dyn DocumentEmbedder>>,
+    stemmer: Option<Arc<dyn Stemmer>>,
+    lemmatizer: Option<Arc<dyn Lemmatizer>>,
+}
+
+impl<CR, T> SimilarityServiceImpl<CR, T>
+where
+    CR: CorpusRepository,
+    T: Tokenizer,
+{
+    /// Create a new SimilarityServiceImpl
+    pub fn new(corpus_repository: Arc<CR>, tokenizer: Arc<T>, tfidf: TfIdf) -> Self {
+        Self {
+            corpus_repository,
+            tokenizer,
+            tfidf,
+            vector_cache: VectorCache::new(),
+            embedder: None,
+            stemmer: None,
+            lemmatizer: None,
+        }
+    }
+
+    /// Get the TF-IDF calculator
+    pub fn tfidf(&self) -> &TfIdf {
+        &self.tfidf
+    }
+
+    /// Embed documents and queries for hybrid search (None = hybrid search is unavailable)
+    ///
+    /// Document embeddings are cached with the TF-IDF vectors, so the cache is
+    /// cleared to keep embeddings of different models apart.
+    pub fn set_embedder(&mut self, embedder: Option<Arc<dyn DocumentEmbedder>>) {
+        self.embedder = embedder;
+        self.vector_cache.clear();
+    }
+
+    /// Stem query terms the same way documents were stemmed (None = no stemming)
+    pub fn set_stemmer(&mut self, stemmer: Option<Arc<dyn Stemmer>>) {
+        self.stemmer = stemmer;
+    }
+
+    /// Lemmatize query terms the same way documents were lemmatized (None = no lemmatization)
+    pub fn set_lemmatizer(&mut self, lemmatizer: Option<Arc<dyn Lemmatizer>>) {
+        self.lemmatizer = lemmatizer;
+    }
+
+    /// Weight a raw query string against a corpus, like a document of it
+    fn query_vector(&self, query: &str, corpus: &Corpus) -> ApplicationResult<HashMap<String, f64>> {
+        let mut document = Document::new("query", query);
+        document.add_terms(analyze_query(&*self.tokenizer, self.lemmatizer.as_deref(), self.stemmer.as_deref(), query));
+        if document.term_count() == 0 {
+            return Err(ApplicationError::InvalidInput("Query contains no searchable terms".to_string()));
+        }
+
+        Ok(self.tfidf.document_vector(&document, corpus)?)
+    }
+}
+
+/// Report documents missing from the vectors as not found
+fn lookup_error(e: DomainError, corpus_id: &str) -> ApplicationError {
+    match e {
+        DomainError::TfIdfError(TfIdfError::DocumentNotFound(id)) => ApplicationError::NotFound(
+            format!("Document '{}' not found in corpus '{}'", id, corpus_id)
+        ),
+        e => e.into(),
+    }
+}
+
+impl<CR, T> SimilarityService for SimilarityServiceImpl<CR, T>
+where
+    CR: CorpusRepository,
+    T: Tokenizer,
+{
+    fn document_similarity(&self, corpus_id: &str, first_id: &str, second_id: &str) -> ApplicationResult<f64> {
+        let corpus = find_corpus(&*self.corpus_repository, &CorpusId::new(corpus_id))?;
+        let index = self.vector_cache.get_or_build(&self.tfidf, &corpus)?;
+
+        self.vector_cache.similarity(&index, &DocumentId::new(first_id), &DocumentId::new(second_id), SimilarityMetric::Cosine)
+            .map_err(|e| lookup_error(e, corpus_id))
+    }
+    
+    fn document_similarity_with(&self, corpus_id: &str, first_id: &str, second_id: &str, metric: SimilarityMetric) -> ApplicationResult<f64> {
+        let corpus = find_corpus(&*self.corpus_repository, &CorpusId::new(corpus_id))?;
+        let index = self.vector_cache.get_or_build(&self.tfidf, &corpus)?;
+
+        self.vector_cache.similarity(&index, &DocumentId::new(first_id), &DocumentId::new(second_id), metric)
+            .map_err(|e| lookup_error(e, corpus_id))
+    }
+    
+    fn query_similarity(&self, corpus_id: &str, document_id: &str, query: &str) -> ApplicationResult<f64> {
+        let corpus = find_corpus(&*self.corpus_repository, &CorpusId::new(corpus_id))?;
+        let index = self.vector_cache.get_or_build(&self.tfidf, &corpus)?;
+        let vector = self.query_vector(query, &corpus)?;
+
+        index.similarity_to(&DocumentId::new(document_id), &vector).map_err(|e| lookup_error(e, corpus_id))
+    }
+    
+    fn most_similar(&self, corpus_id: &str, document_id: &str, k: usize) -> ApplicationResult<Vec<SimilarDocument>> {
+        let corpus = find_corpus(&*self.corpus_repository, &CorpusId::new(corpus_id))?;
+        let index = self.vector_cache.get_or_build(&self.tfidf, &corpus)?;
+
+        index.most_similar(&DocumentId::new(document_id), k).map_err(|e| lookup_error(e, corpus_id))
+    }
+    
+    fn most_similar_with(&self, corpus_id: &str, document_id: &str, k: usize, metric: SimilarityMetric) -> ApplicationResult<Vec<SimilarDocument>> {
+        let corpus = find_corpus(&*self.corpus_repository, &CorpusId::new(corpus_id))?;
+        let index = self.vector_cache.get_or_build(&self.tfidf, &corpus)?;
+
+        index.most_similar_by(&DocumentId::new(document_id), k, metric).map_err(|e| lookup_error(e, corpus_id))
+    }
+    
+    fn find_documents_above_similarity(&self, corpus_id: &str, document_id: &str, threshold: f64) -> ApplicationResult<Vec<SimilarDocument>> {
+        if !(0.0..=1.0).contains(&threshold) {
+            return Err(ApplicationError::InvalidInput(format!("Similarity threshold must be between 0 and 1, got {}", threshold)));
+        }
+
+        let corpus = find_corpus(&*self.corpus_repository, &CorpusId::new(corpus_id))?;
+        let index = self.vector_cache.get_or_build(&self.tfidf, &corpus)?;
+
+        index.above_similarity(&DocumentId::new(document_id), threshold).map_err(|e| lookup_error(e, corpus_id))
+    }
+    
+    fn most_similar_to_query(&self, corpus_id: &str, query: &str, k: usize) -> ApplicationResult<Vec<SimilarDocument>> {
+        let corpus = find_corpus(&*self.corpus_repository, &CorpusId::new(corpus_id))?;
+        let index = self.vector_cache.get_or_build(&self.tfidf, &corpus)?;
+        let vector = self.query_vector(query, &corpus)?;
+
+        Ok(index.nearest(&vector, k))
+    }
+    
+    fn hybrid_search(&self, corpus_id: &str, query: &str, k: usize, dense_weight: f64) -> ApplicationResult<Vec<SimilarDocument>> {
+        let embedder = self.embedder.as_deref().ok_or_else(|| {
+            ApplicationError::NotPermitted("Hybrid search requires an embedder".to_string())
+        })?;
+        if !(0.0..=1.0).contains(&dense_weight) {
+            return Err(ApplicationError::InvalidInput(format!("Dense weight must be between 0 and 1, got {}", dense_weight)));
+        }
+
+        let corpus = find_corpus(&*self.corpus_repository, &CorpusId::new(corpus_id))?;
+        let index = self.vector_cache.get_or_embed(&self.tfidf, &corpus, embedder)?;
+        let vector = self.query_vector(query, &corpus)?;
+        let dense_vector = embedder.embed(query)?;
+
+        Ok(index.hybrid_nearest(&vector, &dense_vector, dense_weight, k)?)
+    }
+    
+    fn similarity_matrix(&self, corpus_id: &str) -> ApplicationResult<SimilarityMatrix> {
+        let corpus = find_corpus(&*self.corpus_repository, &CorpusId::new(corpus_id))?;
+        Ok(self.vector_cache.get_or_build(&self.tfidf, &corpus)?.similarity_matrix())
+    }
+    
+    fn clear_cache(&self) {
+        self.vector_cache.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::repository::InMemoryCorpusRepository;
+    use crate::domain::{Term, TfIdfOptions};
+    use crate::infrastructure::tokenizer::{PorterStemmer, SimpleTokenizer};
+
+    fn create_service() -> SimilarityServiceImpl<InMemoryCorpusRepository, SimpleTokenizer> {
+        let mut corpus = Corpus::new("corpus1", "Languages");
+        for (id, words) in [
+            ("doc1", "rust compiler safety"),
+            ("doc2", "rust compiler speed"),
+            ("doc3", "python scripting"),
+            ("doc4", "go concurrency"),
+        ] {
+            let mut document = Document::new(id, words);
+            document.add_terms(words.split(' ').map(Term::new));
+            corpus.add_document(document).unwrap();
+        }
+        corpus.build_index().unwrap();
+
+        let repository = InMemoryCorpusRepository::new();
+        repository.save(&corpus).unwrap();
+        SimilarityServiceImpl::new(Arc::new(repository), Arc::new(SimpleTokenizer::new()), TfIdf::default())
+    }
+
+    #[test]
+    fn test_document_similarities() {
+        let service = create_service();
+
+        assert!(service.document_similarity("corpus1", "doc1", "doc2").unwrap() > 0.0);
+        assert_eq!(service.document_similarity("corpus1", "doc1", "doc3").unwrap(), 0.0);
+        assert!(matches!(service.document_similarity("corpus1", "doc1", "missing"), Err(ApplicationError::NotFound(_))));
+
+        let similar = service.most_similar("corpus1", "doc1", 3).unwrap();
+        assert_eq!(similar.len(), 1);
+        assert_eq!(similar[0].document_id().value(), "doc2");
+
+        let matrix = service.similarity_matrix("corpus1").unwrap();
+        assert_eq!(matrix.len(), 4);
+    }
+
+    #[test]
+    fn test_documents_above_similarity() {
+        let service = create_service();
+        let similarity = service.document_similarity("corpus1", "doc1", "doc2").unwrap();
+
+        let above = service.find_documents_above_similarity("corpus1", "doc1", similarity / 2.0).unwrap();
+        assert_eq!(above.len(), 1);
+        assert_eq!(above[0].document_id().value(), "doc2");
+        assert!(service.find_documents_above_similarity("corpus1", "doc1", similarity).unwrap().is_empty());
+        assert!(service.find_documents_above_similarity("corpus1", "doc1", 1.5).is_err());
+    }
+
+    #[test]
+    fn test_metrics() {
+        let service = create_service();
+
+        let jaccard = service.document_similarity_with("corpus1", "doc1", "doc2", SimilarityMetric::Jaccard).unwrap();
+        assert!((jaccard - 0.5).abs() < 1e-9);
+        assert_eq!(service.document_similarity_with("corpus1", "doc1", "doc1", SimilarityMetric::Euclidean).unwrap(), 0.0);
+
+        let nearest = service.most_similar_with("corpus1", "doc1", 3, SimilarityMetric::Manhattan).unwrap();
+        assert_eq!(nearest.len(), 3);
+        assert_eq!(nearest[0].document_id().value(), "doc2");
+        assert!(nearest[0].distance().unwrap() <= nearest[1].distance().unwrap());
+        assert!(nearest[0].similarity() >= nearest[1].similarity());
+
+        let sharing = service.most_similar_with("corpus1", "doc1", 3, SimilarityMetric::Jaccard).unwrap();
+        assert_eq!(sharing.len(), 1);
+        assert_eq!(sharing[0].distance(), None);
+    }
+
+    #[test]
+    fn test_query_similarities() {
+        let service = create_service();
+
+        assert!(service.query_similarity("corpus1", "doc3", "Python").unwrap() > 0.0);
+        assert_eq!(service.query_similarity("corpus1", "doc4", "python").unwrap(), 0.0);
+
+        let similar = service.most_similar_to_query("corpus1", "rust safety", 2).unwrap();
+        assert_eq!(similar[0].document_id().value(), "doc1");
+        assert!(service.most_similar_to_query("corpus1", "", 2).is_err());
+    }
+
+    #[test]
+    fn test_query_similarities_with_stems() {
+        let stemmer = Arc::new(PorterStemmer::new());
+        let mut corpus = Corpus::new("corpus1", "Stemmed");
+        for (id, words) in [
+            ("doc1", "compilers optimize loops"),
+            ("doc2", "scripts run quickly"),
+            ("doc3", "gardens grow slowly"),
+        ] {
+            let mut document = Document::new(id, words);
+            document.add_terms(words.split(' ').map(|word| Term::with_stem(word, stemmer.stem(word))));
+            corpus.add_document(document).unwrap();
+        }
+        corpus.build_index().unwrap();
+        let repository = InMemoryCorpusRepository::new();
+        repository.save(&corpus).unwrap();
+
+        let tfidf = TfIdf::new(TfIdfOptions { aggregate_stems: true, ..TfIdfOptions::default() });
+        let mut service = SimilarityServiceImpl::new(Arc::new(repository), Arc::new(SimpleTokenizer::new()), tfidf);
+
+        // Unstemmed, "compiling" shares no key with the stemmed document vectors
+        assert_eq!(service.query_similarity("corpus1", "doc1", "compiling").unwrap(), 0.0);
+
+        service.set_stemmer(Some(stemmer));
+        assert!(service.query_similarity("corpus1", "doc1", "compiling").unwrap() > 0.0);
+        let similar = service.most_similar_to_query("corpus1", "compiling optimizations", 2).unwrap();
+        assert_eq!(similar[0].document_id().value(), "doc1");
+    }
+
+    /// Embeds texts about concurrency along one axis and everything else along the other
+    struct TopicEmbedder;
+
+    impl DocumentEmbedder for TopicEmbedder {
+        fn embed(&self, text: &str) -> crate::domain::DomainResult<Vec<f32>> {
+            let concurrent = ["concurrency", "parallel"].iter().any(|word| text.contains(word));
+            Ok(if concurrent { vec![1.0, 0.0] } else { vec![0.0, 1.0] })
+        }
+    }
+
+    /// Embeds every text into three dimensions
+    struct WideEmbedder;
+
+    impl DocumentEmbedder for WideEmbedder {
+        fn embed(&self, _text: &str) -> crate::domain::DomainResult<Vec<f32>> {
+            Ok(vec![1.0, 0.0, 0.0])
+        }
+    }
+
+    #[test]
+    fn test_hybrid_search() {
+        let mut service = create_service();
+        assert!(matches!(service.hybrid_search("corpus1", "parallel", 2, 0.5), Err(ApplicationError::NotPermitted(_))));
+
+        service.set_embedder(Some(Arc::new(TopicEmbedder)));
+
+        // No document contains "parallel", but the embeddings relate it to doc4
+        let similar = service.hybrid_search("corpus1", "parallel", 2, 0.5).unwrap();
+        assert_eq!(similar.len(), 1);
+        assert_eq!(similar[0].document_id().value(), "doc4");
+
+        // Switching models drops the embeddings of the previous one
+        service.set_embedder(Some(Arc::new(WideEmbedder)));
+        assert!(service.hybrid_search("corpus1", "parallel", 2, 0.5).is_ok());
+        service.set_embedder(Some(Arc::new(TopicEmbedder)));
+
+        let similar = service.hybrid_search("corpus1", "rust safety", 4, 0.0).unwrap();
+        assert_eq!(similar, service.most_similar_to_query("corpus1", "rust safety", 4).unwrap());
+        assert!(service.hybrid_search("corpus1", "rust", 2, 1.5).is_err());
+    }
+}
@@ -0,0 +1,372 @@
+// src/application/tf_idf_service.rs
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::domain::{Corpus, CorpusId, CorpusSnapshot, DocumentId, ScoredDocument, SearchRequest, Summarizer, Summary, Term, TermStats, TfIdf, TfIdfError, TfIdfScore, DomainError};
+use crate::infrastructure::repository::CorpusRepository;
+use crate::infrastructure::tokenizer::Tokenizer;
+
+use super::{corpus_calculator, find_corpus, ApplicationError, ApplicationResult};
+
+/// A term and how characteristic it is of a document or corpus
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keyword {
+    term: String,
+    score: f64,
+}
+
+impl Keyword {
+    /// Create a keyword
+    pub fn new(term: impl Into<String>, score: f64) -> Self {
+        Self { term: term.into(), score }
+    }
+
+    /// Get the term text
+    pub fn term(&self) -> &str {
+        &self.term
+    }
+
+    /// Get the keyword score
+    pub fn score(&self) -> f64 {
+        self.score
+    }
+}
+
+/// Service interface for TF-IDF analyses of stored corpora
+pub trait TfIdfService {
+    /// Get the `n` highest-weighted terms of a document within a corpus, best first
+    fn extract_keywords(&self, document_id: &str, corpus_id: &str, n: usize) -> ApplicationResult<Vec<Keyword>>;
+
+    /// Get the `n` terms most characteristic of a whole corpus, best first
+    ///
+    /// A term scores its TF-IDF weight averaged over all documents, so it ranks
+    /// high when it is both frequent in some documents and absent from most.
+    fn characteristic_terms(&self, corpus_id: &str, n: usize) -> ApplicationResult<Vec<Keyword>>;
+
+    /// Summarize a document by its `k` sentences with the highest summed TF-IDF weight, in original order
+    fn summarize(&self, document_id: &str, corpus_id: &str, k: usize) -> ApplicationResult<Summary>;
+    
+    /// Score every term of a document within a corpus, highest first
+    fn score_document(&self, document_id: &str, corpus_id: &str) -> ApplicationResult<Vec<TfIdfScore>>;
+    
+    /// Search a corpus with a raw query string, tokenized like the documents
+    fn search_corpus(&self, corpus_id: &str, query: &str) -> ApplicationResult<Vec<ScoredDocument>>;
+    
+    /// Calculate the cosine similarity between two documents of a corpus
+    fn document_similarity(&self, corpus_id: &str, first_id: &str, second_id: &str) -> ApplicationResult<f64>;
+    
+    /// Get the `n` terms found in the most documents of a corpus, with their statistics
+    fn top_terms(&self, corpus_id: &str, n: usize) -> ApplicationResult<Vec<(Term, TermStats)>>;
+    
+    /// Get the `n` terms found in the fewest documents of a corpus, i.e. with the highest IDF, with their statistics
+    fn rarest_terms(&self, corpus_id: &str, n: usize) -> ApplicationResult<Vec<(Term, TermStats)>>;
+}
+
+/// Implementation of the TfIdfService
+pub struct TfIdfServiceImpl<CR, T>
+where
+    CR: CorpusRepository,
+    T: Tokenizer,
+{
+    corpus_repository: Arc<CR>,
+
+    /// Tokenizer locating terms in document content, as used when the documents were analyzed
+    tokenizer: Arc<T>,
+    tfidf: TfIdf,
+    summarizer: Summarizer,
+
+    /// Whether `tfidf`'s options win over options stored on a corpus
+    override_corpus_options: bool,
+}
+
+impl<CR, T> TfIdfServiceImpl<CR, T>
+where
+    CR: CorpusRepository,
+    T: Tokenizer,
+{
+    /// Create a new TfIdfServiceImpl
+    pub fn new(corpus_repository: Arc<CR>, tokenizer: Arc<T>, tfidf: TfIdf) -> Self {
+        Self {
+            corpus_repository,
+            tokenizer,
+            tfidf,
+            summarizer: Summarizer::new(),
+            override_corpus_options: false,
+        }
+    }
+
+    /// Get the TF-IDF calculator
+    pub fn tfidf(&self) -> &TfIdf {
+        &self.tfidf
+    }
+
+    /// Score every corpus with this service's options, ignoring options stored on the corpus
+    pub fn set_override_corpus_options(&mut self, override_corpus_options: bool) {
+        self.override_corpus_options = override_corpus_options;
+    }
+
+    /// Look up a corpus by ID, requiring it to be indexed
+    fn find_indexed_corpus(&self, corpus_id: &str) -> ApplicationResult<CorpusSnapshot> {
+        let corpus = find_corpus(&*self.corpus_repository, &CorpusId::new(corpus_id))?;
+        if !corpus.is_indexed() {
+            return Err(calculation_error(DomainError::TfIdfError(TfIdfError::CorpusNotIndexed), corpus_id));
+        }
+        Ok(corpus)
+    }
+}
+
+/// Map a calculation error, reporting an unindexed corpus as a precondition failure
+fn calculation_error(e: DomainError, corpus_id: &str) -> ApplicationError {
+    match e {
+        DomainError::TfIdfError(TfIdfError::CorpusNotIndexed) => ApplicationError::NotPermitted(
+            format!("Corpus '{}' must be indexed first", corpus_id)
+        ),
+        DomainError::TfIdfError(TfIdfError::DocumentNotFound(id)) => ApplicationError::NotFound(
+            format!("Document '{}' not found in corpus '{}'", id, corpus_id)
+        ),
+        e => e.into(),
+    }
+}
+
+/// Pair terms selected from a corpus with their vocabulary statistics
+fn with_stats(corpus: &Corpus, terms: Vec<(&Term, usize)>) -> Vec<(Term, TermStats)> {
+    terms.into_iter()
+        .filter_map(|(term, _)| corpus.vocabulary().stats(term).map(|stats| (term.clone(), *stats)))
+        .collect()
+}
+
+/// Sort keywords best first, breaking ties alphabetically so results are stable, and keep `n`
+fn top_keywords(mut keywords: Vec<Keyword>, n: usize) -> Vec<Keyword> {
+    keywords.sort_by(|a, b| {
+        b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal).then_with(|| a.term.cmp(&b.term))
+    });
+    keywords.truncate(n);
+    keywords
+}
+
+impl<CR, T> TfIdfService for TfIdfServiceImpl<CR, T>
+where
+    CR: CorpusRepository,
+    T: Tokenizer,
+{
+    fn extract_keywords(&self, document_id: &str, corpus_id: &str, n: usize) -> ApplicationResult<Vec<Keyword>> {
+        let corpus = find_corpus(&*self.corpus_repository, &CorpusId::new(corpus_id))?;
+        let document = corpus.get_document(&DocumentId::new(document_id))?.ok_or_else(|| {
+            ApplicationError::NotFound(format!("Document '{}' not found in corpus '{}'", document_id, corpus_id))
+        })?;
+
+        let keywords = corpus_calculator(&self.tfidf, &corpus, self.override_corpus_options).document_vector(document, &corpus)?
+            .into_iter()
+            .map(|(term, score)| Keyword::new(term, score))
+            .collect();
+
+        Ok(top_keywords(keywords, n))
+    }
+
+    fn characteristic_terms(&self, corpus_id: &str, n: usize) -> ApplicationResult<Vec<Keyword>> {
+        let corpus = find_corpus(&*self.corpus_repository, &CorpusId::new(corpus_id))?;
+        if corpus.document_count() == 0 {
+            return Ok(Vec::new());
+        }
+
+        let index = corpus_calculator(&self.tfidf, &corpus, self.override_corpus_options).build_vector_index(&corpus).map_err(|e| calculation_error(e, corpus_id))?;
+
+        let mut totals: HashMap<&str, f64> = HashMap::new();
+        for (_, vector) in index.vectors() {
+            for (term, weight) in vector {
+                *totals.entry(term).or_insert(0.0) += weight;
+            }
+        }
+
+        let document_count = corpus.document_count() as f64;
+        let keywords = totals.into_iter()
+            .map(|(term, total)| Keyword::new(term, total / document_count))
+            .collect();
+
+        Ok(top_keywords(keywords, n))
+    }
+
+    fn summarize(&self, document_id: &str, corpus_id: &str, k: usize) -> ApplicationResult<Summary> {
+        let corpus = find_corpus(&*self.corpus_repository, &CorpusId::new(corpus_id))?;
+        let document = corpus.get_document(&DocumentId::new(document_id))?.ok_or_else(|| {
+            ApplicationError::NotFound(format!("Document '{}' not found in corpus '{}'", document_id, corpus_id))
+        })?;
+
+        let weights = corpus_calculator(&self.tfidf, &corpus, self.override_corpus_options).document_vector(document, &corpus)?;
+        let tokens = self.tokenizer.tokenize_with_offsets(document.content());
+
+        Ok(self.summarizer.summarize(document.content(), &tokens, &weights, k))
+    }
+
+    fn score_document(&self, document_id: &str, corpus_id: &str) -> ApplicationResult<Vec<TfIdfScore>> {
+        let corpus = find_corpus(&*self.corpus_repository, &CorpusId::new(corpus_id))?;
+        let document = corpus.get_document(&DocumentId::new(document_id))?.ok_or_else(|| {
+            ApplicationError::NotFound(format!("Document '{}' not found in corpus '{}'", document_id, corpus_id))
+        })?;
+
+        corpus_calculator(&self.tfidf, &corpus, self.override_corpus_options).calculate_document_tfidf(document, &corpus).map_err(|e| calculation_error(e, corpus_id))
+    }
+
+    fn search_corpus(&self, corpus_id: &str, query: &str) -> ApplicationResult<Vec<ScoredDocument>> {
+        // Stopwords are kept but marked, so the calculator's filter_stopwords option decides
+        let terms: Vec<Term> = self.tokenizer.tokenize(query)
+            .into_iter()
+            .map(|token| if self.tokenizer.is_stopword(&token) { Term::stopword(token) } else { Term::new(token) })
+            .collect();
+        if terms.is_empty() {
+            return Err(ApplicationError::InvalidInput("Query contains no searchable terms".to_string()));
+        }
+
+        let corpus = find_corpus(&*self.corpus_repository, &CorpusId::new(corpus_id))?;
+        let hits = corpus_calculator(&self.tfidf, &corpus, self.override_corpus_options).search_request(&SearchRequest::new(terms), &corpus).map_err(|e| calculation_error(e, corpus_id))?;
+        Ok(TfIdf::resolve_hits(hits.into_results(), &corpus)?)
+    }
+
+    fn document_similarity(&self, corpus_id: &str, first_id: &str, second_id: &str) -> ApplicationResult<f64> {
+        let corpus = find_corpus(&*self.corpus_repository, &CorpusId::new(corpus_id))?;
+        corpus_calculator(&self.tfidf, &corpus, self.override_corpus_options).cosine_similarity(first_id, second_id, &corpus).map_err(|e| calculation_error(e, corpus_id))
+    }
+
+    fn top_terms(&self, corpus_id: &str, n: usize) -> ApplicationResult<Vec<(Term, TermStats)>> {
+        let corpus = self.find_indexed_corpus(corpus_id)?;
+        Ok(with_stats(&corpus, corpus.top_terms_by_df(n)))
+    }
+
+    fn rarest_terms(&self, corpus_id: &str, n: usize) -> ApplicationResult<Vec<(Term, TermStats)>> {
+        let corpus = self.find_indexed_corpus(corpus_id)?;
+        Ok(with_stats(&corpus, corpus.rarest_terms(n)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Document, Scheme, Term, TfIdfOptions};
+    use crate::infrastructure::persistence::{InMemoryStorage, Storage};
+    use crate::infrastructure::repository::{DocumentRepository, InMemoryCorpusRepository, StorageDocumentRepository};
+    use crate::infrastructure::tokenizer::SimpleTokenizer;
+
+    fn create_service() -> TfIdfServiceImpl<InMemoryCorpusRepository, SimpleTokenizer> {
+        let mut corpus = Corpus::new("corpus1", "Languages");
+        for (id, words) in [("doc1", "rust rust fast"), ("doc2", "rust safe"), ("doc3", "go simple fast")] {
+            let mut document = Document::new(id, words);
+            document.add_terms(words.split(' ').map(Term::new));
+            corpus.add_document(document).unwrap();
+        }
+        corpus.build_index().unwrap();
+
+        let repository = InMemoryCorpusRepository::new();
+        repository.save(&corpus).unwrap();
+        TfIdfServiceImpl::new(Arc::new(repository), Arc::new(SimpleTokenizer::new()), TfIdf::default())
+    }
+
+    #[test]
+    fn test_unreadable_document_fails() {
+        let documents = Arc::new(StorageDocumentRepository::new(InMemoryStorage::new()));
+        let mut repository = InMemoryCorpusRepository::new();
+        repository.set_document_repository(Some(documents.clone()));
+        let corpus = find_corpus(&*create_service().corpus_repository, &CorpusId::new("corpus1")).unwrap();
+        for document in corpus.documents() {
+            documents.save(document.unwrap()).unwrap();
+        }
+        repository.save(&corpus).unwrap();
+        let service = TfIdfServiceImpl::new(Arc::new(repository), Arc::new(SimpleTokenizer::new()), TfIdf::default());
+        assert!(service.score_document("doc1", "corpus1").is_ok());
+
+        // A document that cannot be read fails the call instead of dropping out of the document frequencies
+        documents.storage().save("documents/doc2", b"not a document").unwrap();
+        assert!(service.score_document("doc1", "corpus1").is_err());
+    }
+
+    #[test]
+    fn test_extract_keywords() {
+        let service = create_service();
+
+        let keywords = service.extract_keywords("doc2", "corpus1", 1).unwrap();
+        assert_eq!(keywords.len(), 1);
+        assert_eq!(keywords[0].term(), "safe");
+
+        assert!(matches!(service.extract_keywords("missing", "corpus1", 1), Err(ApplicationError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_characteristic_terms() {
+        let service = create_service();
+
+        let keywords = service.characteristic_terms("corpus1", 10).unwrap();
+        assert_eq!(keywords.len(), 5);
+        assert!(keywords.windows(2).all(|pair| pair[0].score() >= pair[1].score()));
+        assert_eq!(service.characteristic_terms("corpus1", 2).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_summarize() {
+        let mut corpus = Corpus::new("corpus1", "Articles");
+        let content = "Rust is a language. Rust prevents data races. It is popular.";
+        let mut document = Document::new("doc1", content);
+        document.add_terms(SimpleTokenizer::new().tokenize(content).into_iter().map(Term::new));
+        corpus.add_document(document).unwrap();
+        let mut other = Document::new("doc2", "it is a language");
+        other.add_terms(["it", "is", "a", "language"].map(Term::new));
+        corpus.add_document(other).unwrap();
+        corpus.build_index().unwrap();
+
+        let repository = InMemoryCorpusRepository::new();
+        repository.save(&corpus).unwrap();
+        let service = TfIdfServiceImpl::new(Arc::new(repository), Arc::new(SimpleTokenizer::new()), TfIdf::default());
+
+        let summary = service.summarize("doc1", "corpus1", 1).unwrap();
+        assert_eq!(summary.text(), "Rust prevents data races.");
+    }
+
+    #[test]
+    fn test_corpus_options() {
+        let repository = Arc::new(InMemoryCorpusRepository::new());
+        let mut corpus = find_corpus(&*create_service().corpus_repository, &CorpusId::new("corpus1")).unwrap().corpus().clone();
+        corpus.set_options(Some(TfIdfOptions::with_scheme(Scheme::ntn())));
+        repository.save(&corpus).unwrap();
+        let mut service = TfIdfServiceImpl::new(repository, Arc::new(SimpleTokenizer::new()), TfIdf::default());
+
+        // "rust" occurs twice in doc1 and in two of three documents: 2 * ln(3 / 2), unnormalized
+        let scores = service.score_document("doc1", "corpus1").unwrap();
+        let rust = scores.iter().find(|score| score.term().text() == "rust").unwrap();
+        assert!((rust.score() - 2.0 * 1.5f64.ln()).abs() < 1e-12);
+
+        // The default smoothed IDF of a term in two of three documents is ln(3 / 3)
+        service.set_override_corpus_options(true);
+        let scores = service.score_document("doc1", "corpus1").unwrap();
+        assert_eq!(scores.iter().find(|score| score.term().text() == "rust").map_or(0.0, |score| score.score()), 0.0);
+    }
+
+    #[test]
+    fn test_score_and_search() {
+        let service = create_service();
+
+        let scores = service.score_document("doc2", "corpus1").unwrap();
+        assert_eq!(scores[0].term().text(), "safe");
+        assert!(matches!(service.score_document("missing", "corpus1"), Err(ApplicationError::NotFound(_))));
+
+        let results = service.search_corpus("corpus1", "Safe").unwrap();
+        assert_eq!(results[0].document().id().value(), "doc2");
+        assert!(service.search_corpus("corpus1", "").is_err());
+    }
+
+    #[test]
+    fn test_similarity_and_top_terms() {
+        let service = create_service();
+
+        let same = service.document_similarity("corpus1", "doc2", "doc2").unwrap();
+        assert!((same - 1.0).abs() < 1e-9);
+        // "rust" is in two of three documents, so it carries no weight with smoothed IDF
+        assert_eq!(service.document_similarity("corpus1", "doc1", "doc2").unwrap(), 0.0);
+        assert!(matches!(service.document_similarity("corpus1", "doc1", "missing"), Err(ApplicationError::NotFound(_))));
+
+        let top = service.top_terms("corpus1", 2).unwrap();
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].1.document_frequency(), 2);
+
+        let rarest = service.rarest_terms("corpus1", 1).unwrap();
+        assert_eq!(rarest[0].1.document_frequency(), 1);
+    }
+}
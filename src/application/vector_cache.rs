@@ -0,0 +1,175 @@
+// src/application/vector_cache.rs
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+
+use crate::domain::{Corpus, CorpusId, DocumentEmbedder, DocumentId, DomainError, DomainResult, SimilarityMetric, TfIdf, TfIdfError, VectorIndex};
+use crate::infrastructure::telemetry;
+
+use super::{ApplicationError, ApplicationResult};
+
+/// Pairs of documents compared under a metric, with the smaller ID first
+type SimilarityKey = (DocumentId, DocumentId, SimilarityMetric);
+
+/// Similarities memoized for one corpus, with the index revision they were computed at
+type Similarities = (u64, HashMap<SimilarityKey, f64>);
+
+/// Vector indexes of corpora, and the similarities computed from them, reused until a corpus revision changes
+#[derive(Debug, Default)]
+pub(crate) struct VectorCache {
+    indexes: RwLock<HashMap<CorpusId, Arc<VectorIndex>>>,
+
+    /// Similarities of document pairs in each corpus
+    similarities: Mutex<HashMap<CorpusId, Similarities>>,
+}
+
+impl VectorCache {
+    /// Create an empty cache
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the vectors of a corpus, building them if missing and updating them if stale
+    pub(crate) fn get_or_build(&self, tfidf: &TfIdf, corpus: &Corpus) -> ApplicationResult<Arc<VectorIndex>> {
+        let cached = self.indexes.read().map_err(|e| {
+            ApplicationError::Other(format!("Lock error: {}", e))
+        })?.get(corpus.id()).cloned();
+        if let Some(index) = cached.as_ref().filter(|index| !index.is_stale(corpus)) {
+            tracing::debug!(target: telemetry::CACHE, corpus_id = corpus.id().value(), "vector cache hit");
+            return Ok(Arc::clone(index));
+        }
+
+        let _span = tracing::debug_span!(
+            target: telemetry::CACHE, "vector cache miss", corpus_id = corpus.id().value(), documents = corpus.document_count()
+        ).entered();
+
+        // Stale vectors are brought up to date incrementally when only documents were added or removed
+        let index = match cached {
+            Some(cached) => {
+                let mut index = Arc::unwrap_or_clone(cached);
+                tfidf.update_vector_index(&mut index, corpus).map(|_| index)
+            },
+            None => tfidf.build_vector_index(corpus),
+        };
+        let index = Arc::new(index.map_err(|e| match e {
+            DomainError::TfIdfError(TfIdfError::CorpusNotIndexed) => ApplicationError::NotPermitted(
+                format!("Corpus '{}' must be indexed first", corpus.id().value())
+            ),
+            e => e.into(),
+        })?);
+
+        self.indexes.write().map_err(|e| {
+            ApplicationError::Other(format!("Lock error: {}", e))
+        })?.insert(corpus.id().clone(), Arc::clone(&index));
+        Ok(index)
+    }
+
+    /// Get the vectors of a corpus like `get_or_build`, embedding documents that lack a dense vector
+    pub(crate) fn get_or_embed(
+        &self,
+        tfidf: &TfIdf,
+        corpus: &Corpus,
+        embedder: &dyn DocumentEmbedder,
+    ) -> ApplicationResult<Arc<VectorIndex>> {
+        let index = self.get_or_build(tfidf, corpus)?;
+        if index.vectors().all(|(id, _)| index.dense_vector(id).is_some()) {
+            return Ok(index);
+        }
+
+        let mut index = Arc::unwrap_or_clone(index);
+        let embedded = index.embed_documents(corpus, embedder)?;
+        tracing::debug!(target: telemetry::CACHE, corpus_id = corpus.id().value(), documents = embedded, "documents embedded");
+        let index = Arc::new(index);
+
+        self.indexes.write().map_err(|e| {
+            ApplicationError::Other(format!("Lock error: {}", e))
+        })?.insert(corpus.id().clone(), Arc::clone(&index));
+        Ok(index)
+    }
+
+    /// Compare two documents of an index under a metric, reusing the result until the index revision changes
+    pub(crate) fn similarity(
+        &self,
+        index: &VectorIndex,
+        first: &DocumentId,
+        second: &DocumentId,
+        metric: SimilarityMetric,
+    ) -> DomainResult<f64> {
+        let Some(corpus_id) = index.corpus_id() else {
+            return index.compare(first, second, metric);
+        };
+        // Every metric is symmetric, so both orders of a pair share one entry
+        let key = if first.value() <= second.value() {
+            (first.clone(), second.clone(), metric)
+        } else {
+            (second.clone(), first.clone(), metric)
+        };
+
+        let cached = self.similarities.lock().ok().and_then(|similarities| {
+            similarities.get(corpus_id)
+                .filter(|(revision, _)| *revision == index.revision())
+                .and_then(|(_, values)| values.get(&key).copied())
+        });
+        if let Some(similarity) = cached {
+            tracing::debug!(target: telemetry::CACHE, corpus_id = corpus_id.value(), "similarity cache hit");
+            return Ok(similarity);
+        }
+
+        let similarity = index.compare(first, second, metric)?;
+        if let Ok(mut similarities) = self.similarities.lock() {
+            let (revision, values) = similarities.entry(corpus_id.clone()).or_default();
+            if *revision != index.revision() {
+                *revision = index.revision();
+                values.clear();
+            }
+            values.insert(key, similarity);
+        }
+        Ok(similarity)
+    }
+
+    /// Drop all cached vectors and similarities
+    pub(crate) fn clear(&self) {
+        if let Ok(mut indexes) = self.indexes.write() {
+            indexes.clear();
+        }
+        if let Ok(mut similarities) = self.similarities.lock() {
+            similarities.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Document, Term};
+
+    #[test]
+    fn test_memoized_similarity() {
+        let mut corpus = Corpus::new("corpus1", "Test Corpus");
+        for (id, words) in [("doc1", ["rust", "fast"]), ("doc2", ["rust", "safe"]), ("doc3", ["go", "fast"])] {
+            let mut document = Document::new(id, words.join(" "));
+            document.add_terms(words.map(Term::new));
+            corpus.add_document(document).unwrap();
+        }
+        corpus.build_index().unwrap();
+
+        let cache = VectorCache::new();
+        let tfidf = TfIdf::default();
+        let (doc1, doc2) = (DocumentId::new("doc1"), DocumentId::new("doc2"));
+        let index = cache.get_or_build(&tfidf, &corpus).unwrap();
+        let similarity = cache.similarity(&index, &doc2, &doc1, SimilarityMetric::Cosine).unwrap();
+        assert_eq!(similarity, index.cosine_similarity(&doc1, &doc2).unwrap());
+
+        // Both orders of a pair share the memoized entry
+        let memoized = |cache: &VectorCache| cache.similarities.lock().unwrap()[&CorpusId::new("corpus1")].1.len();
+        assert_eq!(cache.similarity(&index, &doc1, &doc2, SimilarityMetric::Cosine).unwrap(), similarity);
+        assert_eq!(memoized(&cache), 1);
+        assert!(cache.similarity(&index, &doc1, &DocumentId::new("missing"), SimilarityMetric::Cosine).is_err());
+
+        // A new corpus revision starts over
+        corpus.remove_document(&DocumentId::new("doc3")).unwrap();
+        let index = cache.get_or_build(&tfidf, &corpus).unwrap();
+        cache.similarity(&index, &doc1, &doc2, SimilarityMetric::Jaccard).unwrap();
+        assert_eq!(memoized(&cache), 1);
+    }
+}
@@ -0,0 +1,187 @@
+// src/application/vectorizer.rs
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::domain::{Corpus, Document, FeatureHasher, HashedVector, TfIdf, TfIdfOptions, Vocabulary};
+
+use super::{ApplicationError, ApplicationResult, DocumentService};
+
+/// Learns corpus statistics from a set of texts and turns any text into a TF-IDF vector
+///
+/// `fit` analyzes the training texts with the document service's pipeline
+/// (tokenizer, stemmer, preprocessor, ...) and records their vocabulary and
+/// document frequencies. `transform` vectorizes new texts against those
+/// statistics without changing them, so held-out or query documents are
+/// weighted exactly like the training set. Nothing is saved to a repository.
+///
+/// With a feature hasher set, `transform_hashed` produces fixed-size vectors
+/// instead of vectors keyed by term, which works without fitting for
+/// streams whose vocabulary grows without limit.
+pub struct TfIdfVectorizer<DS: DocumentService> {
+    document_service: Arc<DS>,
+    tfidf: TfIdf,
+
+    /// The analyzed training texts, once fitted
+    fitted: Option<Corpus>,
+
+    /// Hasher for fixed-size output vectors, if any
+    hasher: Option<FeatureHasher>,
+}
+
+impl<DS: DocumentService> TfIdfVectorizer<DS> {
+    /// Create an unfitted vectorizer with the default TF-IDF options
+    pub fn new(document_service: Arc<DS>) -> Self {
+        Self::with_options(document_service, TfIdfOptions::default())
+    }
+
+    /// Create an unfitted vectorizer with custom TF-IDF options
+    pub fn with_options(document_service: Arc<DS>, options: TfIdfOptions) -> Self {
+        Self {
+            document_service,
+            tfidf: TfIdf::new(options),
+            fitted: None,
+            hasher: None,
+        }
+    }
+
+    /// Get the TF-IDF calculator
+    pub fn tfidf(&self) -> &TfIdf {
+        &self.tfidf
+    }
+
+    /// Get the feature hasher used by `transform_hashed`, if any
+    pub fn feature_hasher(&self) -> Option<&FeatureHasher> {
+        self.hasher.as_ref()
+    }
+
+    /// Set the feature hasher used by `transform_hashed` (None = term-keyed vectors only)
+    pub fn set_feature_hasher(&mut self, hasher: Option<FeatureHasher>) {
+        self.hasher = hasher;
+    }
+
+    /// Check if the vectorizer has been fitted
+    pub fn is_fitted(&self) -> bool {
+        self.fitted.is_some()
+    }
+
+    /// Get the learned vocabulary, if fitted
+    pub fn vocabulary(&self) -> Option<&Vocabulary> {
+        self.fitted.as_ref().map(Corpus::vocabulary)
+    }
+
+    /// Get the number of training texts, if fitted
+    pub fn document_count(&self) -> Option<usize> {
+        self.fitted.as_ref().map(Corpus::document_count)
+    }
+
+    /// Learn the vocabulary and document frequencies of a set of texts, replacing earlier ones
+    pub fn fit<S: AsRef<str>>(&mut self, texts: &[S]) -> ApplicationResult<()> {
+        let mut corpus = Corpus::new("vectorizer", "Vectorizer training set");
+        for document in self.analyze(texts)? {
+            corpus.add_document(document)?;
+        }
+        corpus.build_index()?;
+
+        self.fitted = Some(corpus);
+        Ok(())
+    }
+
+    /// Vectorize texts against the learned statistics, one vector per text keyed by term
+    pub fn transform<S: AsRef<str>>(&self, texts: &[S]) -> ApplicationResult<Vec<HashMap<String, f64>>> {
+        let corpus = self.fitted.as_ref().ok_or_else(|| {
+            ApplicationError::NotPermitted("Vectorizer must be fitted before transforming".to_string())
+        })?;
+
+        self.analyze(texts)?
+            .iter()
+            .map(|document| Ok(self.tfidf.document_vector(document, corpus)?))
+            .collect()
+    }
+
+    /// Fit on texts and return their vectors
+    pub fn fit_transform<S: AsRef<str>>(&mut self, texts: &[S]) -> ApplicationResult<Vec<HashMap<String, f64>>> {
+        self.fit(texts)?;
+        self.transform(texts)
+    }
+
+    /// Vectorize texts into the feature hasher's fixed-size space
+    ///
+    /// Once fitted, TF-IDF weights are hashed; before that, raw term
+    /// frequencies are, so texts can be vectorized without any corpus statistics.
+    pub fn transform_hashed<S: AsRef<str>>(&self, texts: &[S]) -> ApplicationResult<Vec<HashedVector>> {
+        let hasher = self.hasher.as_ref().ok_or_else(|| {
+            ApplicationError::NotPermitted("Vectorizer has no feature hasher".to_string())
+        })?;
+
+        let documents = self.analyze(texts)?;
+        match &self.fitted {
+            Some(corpus) => documents.iter()
+                .map(|document| Ok(hasher.hash_vector(&self.tfidf.document_vector(document, corpus)?)))
+                .collect(),
+            None => Ok(documents.iter().map(|document| hasher.hash_document(document)).collect()),
+        }
+    }
+
+    /// Analyze texts into unsaved documents
+    fn analyze<S: AsRef<str>>(&self, texts: &[S]) -> ApplicationResult<Vec<Document>> {
+        texts.iter()
+            .enumerate()
+            .map(|(index, text)| {
+                self.document_service.analyze_document(&Document::new(index.to_string(), text.as_ref()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::DocumentServiceImpl;
+    use crate::infrastructure::repository::InMemoryDocumentRepository;
+    use crate::infrastructure::tokenizer::SimpleTokenizer;
+
+    fn create_vectorizer() -> TfIdfVectorizer<DocumentServiceImpl<InMemoryDocumentRepository, SimpleTokenizer>> {
+        let service = DocumentServiceImpl::new(
+            Arc::new(InMemoryDocumentRepository::new()),
+            Arc::new(SimpleTokenizer::new()),
+        );
+        TfIdfVectorizer::new(Arc::new(service))
+    }
+
+    #[test]
+    fn test_fit_transform() {
+        let mut vectorizer = create_vectorizer();
+        assert!(vectorizer.transform(&["rust"]).is_err());
+
+        let training = ["rust is fast", "rust is safe", "go is simple"];
+        let vectors = vectorizer.fit_transform(&training).unwrap();
+        assert_eq!(vectors.len(), 3);
+        assert_eq!(vectorizer.document_count(), Some(3));
+
+        // "safe" is rarer than "rust" in the training set, so it weighs more
+        assert!(vectors[1]["safe"] > vectors[1]["rust"]);
+
+        // New texts do not change the learned statistics
+        let vectors = vectorizer.transform(&["rust rust and zig"]).unwrap();
+        assert!(vectors[0].contains_key("zig"));
+        assert_eq!(vectorizer.document_count(), Some(3));
+        assert!(!vectorizer.vocabulary().unwrap().contains(&crate::domain::Term::new("zig")));
+    }
+
+    #[test]
+    fn test_transform_hashed() {
+        let mut vectorizer = create_vectorizer();
+        assert!(vectorizer.transform_hashed(&["rust"]).is_err());
+
+        vectorizer.set_feature_hasher(Some(FeatureHasher::new(8)));
+        let unfitted = vectorizer.transform_hashed(&["rust is fast", "a brand new word stream"]).unwrap();
+        assert!(unfitted.iter().all(|vector| vector.dimensions() == 8));
+        assert!(unfitted[1].nnz() > 0);
+
+        vectorizer.fit(&["rust is fast", "go is simple"]).unwrap();
+        let fitted = vectorizer.transform_hashed(&["rust is fast"]).unwrap();
+        assert_eq!(fitted[0].dimensions(), 8);
+        assert_ne!(fitted[0], unfitted[0]);
+    }
+}
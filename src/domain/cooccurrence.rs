@@ -0,0 +1,194 @@
+// src/domain/cooccurrence.rs
+
+use std::collections::{HashMap, HashSet};
+
+use super::{Corpus, DomainResult, Term};
+
+/// The unit within which two terms count as co-occurring
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CooccurrenceWindow {
+    /// Both terms appear somewhere in the same document's content
+    Document,
+
+    /// Both terms appear at most this many positions apart
+    Tokens(usize),
+}
+
+/// Sparse symmetric matrix of term co-occurrence counts over a corpus
+///
+/// Alongside the pair counts it keeps each term's marginal count and the total
+/// number of observations, which is enough to derive association measures such
+/// as PMI. In document mode the marginal is the number of documents containing
+/// the term; in token window mode it is the term's number of occurrences.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CooccurrenceMatrix {
+    /// Count per unordered pair, keyed with the lexicographically smaller term first
+    pairs: HashMap<(String, String), usize>,
+
+    /// Marginal count per term
+    marginals: HashMap<String, usize>,
+
+    /// Number of documents (document mode) or term occurrences (token window mode)
+    total: usize,
+}
+
+impl CooccurrenceMatrix {
+    /// Count co-occurrences across the content of every document in a corpus
+    pub fn from_corpus(corpus: &Corpus, window: CooccurrenceWindow) -> DomainResult<Self> {
+        let mut matrix = Self::default();
+
+        for document in corpus.documents() {
+            let document = document?;
+            match window {
+                CooccurrenceWindow::Document => {
+                    let mut terms: Vec<&str> = document.term_frequencies().keys().map(Term::text).collect();
+                    terms.sort_unstable();
+
+                    for (i, a) in terms.iter().enumerate() {
+                        *matrix.marginals.entry(a.to_string()).or_insert(0) += 1;
+                        for b in &terms[i + 1..] {
+                            matrix.increment(a, b);
+                        }
+                    }
+                    matrix.total += 1;
+                },
+                CooccurrenceWindow::Tokens(size) => {
+                    let sequence = document.term_sequence();
+
+                    for (i, (position, a)) in sequence.iter().enumerate() {
+                        *matrix.marginals.entry(a.text().to_string()).or_insert(0) += 1;
+                        for (_, b) in sequence[i + 1..].iter().take_while(|(next, _)| next - position <= size) {
+                            if a != b {
+                                matrix.increment(a.text(), b.text());
+                            }
+                        }
+                    }
+                    matrix.total += sequence.len();
+                },
+            }
+        }
+
+        Ok(matrix)
+    }
+
+    fn increment(&mut self, a: &str, b: &str) {
+        *self.pairs.entry(Self::key(a, b)).or_insert(0) += 1;
+    }
+
+    fn key(a: &str, b: &str) -> (String, String) {
+        if a <= b {
+            (a.to_string(), b.to_string())
+        } else {
+            (b.to_string(), a.to_string())
+        }
+    }
+
+    /// Get the number of times two terms co-occur (in either order)
+    pub fn count(&self, a: &str, b: &str) -> usize {
+        self.pairs.get(&Self::key(a, b)).copied().unwrap_or(0)
+    }
+
+    /// Get the marginal count of a term
+    pub fn marginal(&self, term: &str) -> usize {
+        self.marginals.get(term).copied().unwrap_or(0)
+    }
+
+    /// Get the total number of observations the marginals are counted against
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Get the number of distinct co-occurring pairs
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    /// Check whether no terms co-occur
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// Iterate over the non-zero entries as (term, term, count)
+    pub fn pairs(&self) -> impl Iterator<Item = (&str, &str, usize)> {
+        self.pairs.iter().map(|((a, b), count)| (a.as_str(), b.as_str(), *count))
+    }
+
+    /// Get the terms co-occurring with a term, most frequent first
+    pub fn neighbors(&self, term: &str) -> Vec<(&str, usize)> {
+        let mut neighbors: Vec<(&str, usize)> = self.pairs
+            .iter()
+            .filter_map(|((a, b), count)| {
+                if a == term {
+                    Some((b.as_str(), *count))
+                } else if b == term {
+                    Some((a.as_str(), *count))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        neighbors.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        neighbors
+    }
+
+    /// Get the pointwise mutual information of two terms, ln(P(a,b) / (P(a) P(b)))
+    ///
+    /// Returns `None` when the pair never co-occurs.
+    pub fn pmi(&self, a: &str, b: &str) -> Option<f64> {
+        let count = self.count(a, b);
+        let (marginal_a, marginal_b) = (self.marginal(a), self.marginal(b));
+        if count == 0 || marginal_a == 0 || marginal_b == 0 {
+            return None;
+        }
+
+        Some((count as f64 * self.total as f64 / (marginal_a as f64 * marginal_b as f64)).ln())
+    }
+
+    /// Get the distinct terms with a marginal count
+    pub fn terms(&self) -> HashSet<&str> {
+        self.marginals.keys().map(String::as_str).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Document;
+
+    fn corpus() -> Corpus {
+        let mut corpus = Corpus::new("corpus1", "Test Corpus");
+        for (id, words) in [("doc1", "new york city"), ("doc2", "new york state"), ("doc3", "old city")] {
+            let mut document = Document::new(id, words);
+            document.add_terms(words.split(' ').map(Term::new));
+            corpus.add_document(document).unwrap();
+        }
+        corpus
+    }
+
+    #[test]
+    fn test_document_cooccurrence() {
+        let matrix = CooccurrenceMatrix::from_corpus(&corpus(), CooccurrenceWindow::Document).unwrap();
+
+        assert_eq!(matrix.count("new", "york"), 2);
+        assert_eq!(matrix.count("york", "new"), 2);
+        assert_eq!(matrix.count("new", "old"), 0);
+        assert_eq!(matrix.marginal("city"), 2);
+        assert_eq!(matrix.total(), 3);
+        assert_eq!(matrix.neighbors("new"), vec![("york", 2), ("city", 1), ("state", 1)]);
+
+        // "new" and "york" always appear together: ln(2 * 3 / (2 * 2))
+        assert!((matrix.pmi("new", "york").unwrap() - 1.5_f64.ln()).abs() < 1e-9);
+        assert!(matrix.pmi("new", "old").is_none());
+    }
+
+    #[test]
+    fn test_window_cooccurrence() {
+        let matrix = CooccurrenceMatrix::from_corpus(&corpus(), CooccurrenceWindow::Tokens(1)).unwrap();
+
+        assert_eq!(matrix.count("new", "york"), 2);
+        assert_eq!(matrix.count("york", "city"), 1);
+        assert_eq!(matrix.count("new", "city"), 0);
+        assert_eq!(matrix.total(), 8);
+    }
+}
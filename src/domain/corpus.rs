@@ -1,9 +1,14 @@
 // src/domain/corpus.rs
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
 use serde::{Serialize, Deserialize};
 
-use super::{Document, DocumentId, Term, DomainError, DomainResult};
+use super::{CooccurrenceMatrix, CooccurrenceWindow, Document, DocumentId, Duplicate, DuplicatePolicy, Term, TfIdf, TfIdfOptions, Vocabulary, VocabularyEntry, DomainError, DomainResult};
+use super::dedup::DuplicateIndex;
+use super::members::{DocumentResolver, Members};
+use super::query::matches_wildcard;
+use super::term::shared_term_map;
 
 /// Unique identifier for a corpus
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -22,6 +27,9 @@ impl CorpusId {
 }
 
 /// Corpus represents a collection of documents
+///
+/// The documents and index structures are shared between clones and copied
+/// on first write, so cloning a corpus to build its next version is cheap.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Corpus {
     /// Unique identifier for the corpus
@@ -33,11 +41,28 @@ pub struct Corpus {
     /// Description of the corpus
     description: Option<String>,
     
-    /// Collection of documents in this corpus
-    documents: HashMap<DocumentId, Document>,
+    /// IDs and revisions of the documents in this corpus, with the documents loaded so far
+    ///
+    /// Documents added to the corpus are held in memory, shared rather than copied
+    /// when the corpus is cloned. Documents put back with `attach_documents` are
+    /// held by ID and loaded through a `DocumentResolver` on first use.
+    documents: Members,
     
-    /// Document frequency for each term (how many documents contain the term)
-    document_frequencies: HashMap<Term, usize>,
+    /// Document frequency for each term (how many documents contain the term in their content)
+    #[serde(with = "shared_term_map")]
+    document_frequencies: Arc<HashMap<Term, usize>>,
+    
+    /// Document frequency for each canonical term form (stem, or text when unstemmed)
+    #[serde(default)]
+    canonical_document_frequencies: Arc<HashMap<String, usize>>,
+    
+    /// Sorted texts of the indexed terms, for prefix and wildcard lookups
+    #[serde(default)]
+    term_dictionary: Arc<BTreeSet<String>>,
+    
+    /// Indexed terms with their IDs and corpus-wide statistics
+    #[serde(default)]
+    vocabulary: Arc<Vocabulary>,
     
     /// Stopwords specific to this corpus
     stopwords: HashSet<String>,
@@ -47,6 +72,79 @@ pub struct Corpus {
     
     /// Metadata associated with the corpus
     metadata: HashMap<String, String>,
+    
+    /// Incremented whenever documents or the index change, so caches can detect staleness
+    #[serde(default)]
+    revision: u64,
+    
+    /// Total number of content terms across all documents, kept up to date as documents change
+    ///
+    /// None when unknown, e.g. for corpora stored before it was tracked, until the next change counts it.
+    #[serde(default)]
+    token_count: Option<usize>,
+    
+    /// Preferred TF-IDF options for scoring this corpus (None = the caller's defaults)
+    #[serde(default)]
+    options: Option<TfIdfOptions>,
+    
+    /// Whether the index is built right away and kept current as documents are added or removed
+    #[serde(default)]
+    auto_index: bool,
+    
+    /// How documents duplicating one already in the corpus are handled
+    #[serde(default)]
+    duplicate_policy: DuplicatePolicy,
+    
+    /// Duplicates added under `DuplicatePolicy::Flag`, in the order they were added
+    #[serde(default)]
+    flagged_duplicates: Vec<Duplicate>,
+    
+    /// Lookup structures for duplicate checks, built on the first check
+    #[serde(skip)]
+    duplicate_index: Option<Arc<DuplicateIndex>>,
+}
+
+/// Outcome of merging one corpus into another
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MergeReport {
+    /// Number of documents copied over
+    documents_added: usize,
+
+    /// IDs of documents present in both corpora, whose incoming copy was skipped
+    document_collisions: Vec<DocumentId>,
+
+    /// Metadata keys with different values in both corpora, whose incoming value was skipped
+    metadata_conflicts: Vec<String>,
+
+    /// Incoming documents refused by this corpus's duplicate policy, ordered by ID
+    rejected_duplicates: Vec<Duplicate>,
+}
+
+impl MergeReport {
+    /// Get the number of documents copied over
+    pub fn documents_added(&self) -> usize {
+        self.documents_added
+    }
+
+    /// Get the IDs of documents present in both corpora, ordered by ID
+    pub fn document_collisions(&self) -> &[DocumentId] {
+        &self.document_collisions
+    }
+
+    /// Get the conflicting metadata keys, in order
+    pub fn metadata_conflicts(&self) -> &[String] {
+        &self.metadata_conflicts
+    }
+
+    /// Get the incoming documents refused by the duplicate policy, ordered by ID
+    pub fn rejected_duplicates(&self) -> &[Duplicate] {
+        &self.rejected_duplicates
+    }
+
+    /// Check if the merge had no collisions, conflicts or rejected duplicates
+    pub fn is_clean(&self) -> bool {
+        self.document_collisions.is_empty() && self.metadata_conflicts.is_empty() && self.rejected_duplicates.is_empty()
+    }
 }
 
 impl Corpus {
@@ -59,11 +157,21 @@ impl Corpus {
             id: CorpusId::new(id.into()),
             name: name.into(),
             description: None,
-            documents: HashMap::new(),
-            document_frequencies: HashMap::new(),
+            documents: Members::default(),
+            document_frequencies: Arc::default(),
+            canonical_document_frequencies: Arc::default(),
+            term_dictionary: Arc::default(),
+            vocabulary: Arc::new(Vocabulary::new()),
             stopwords: HashSet::new(),
             indexed: false,
             metadata: HashMap::new(),
+            revision: 0,
+            token_count: Some(0),
+            options: None,
+            auto_index: false,
+            duplicate_policy: DuplicatePolicy::Allow,
+            flagged_duplicates: Vec::new(),
+            duplicate_index: None,
         }
     }
     
@@ -108,29 +216,62 @@ impl Corpus {
         self.description = None;
     }
     
-    /// Get the number of documents in the corpus
+    /// Get the number of documents in the corpus, including any that can no longer be loaded
     pub fn document_count(&self) -> usize {
         self.documents.len()
     }
     
     /// Check if the corpus contains a document with the given ID
     pub fn contains_document(&self, document_id: &DocumentId) -> bool {
-        self.documents.contains_key(document_id)
+        self.documents.contains(document_id)
+    }
+    
+    /// Get a document by ID, loading it if the corpus holds it by ID
+    ///
+    /// None if the document is not in the corpus or no longer exists; fails if
+    /// the document resolver cannot look it up.
+    pub fn get_document(&self, document_id: &DocumentId) -> DomainResult<Option<&Document>> {
+        Ok(self.documents.get(document_id)?.map(Arc::as_ref))
     }
     
-    /// Get a document by ID
-    pub fn get_document(&self, document_id: &DocumentId) -> Option<&Document> {
+    /// Get a shared handle to a document by ID, to keep it without copying it
+    pub fn get_shared_document(&self, document_id: &DocumentId) -> DomainResult<Option<&Arc<Document>>> {
         self.documents.get(document_id)
     }
     
     /// Get a mutable reference to a document by ID
-    pub fn get_document_mut(&mut self, document_id: &DocumentId) -> Option<&mut Document> {
+    ///
+    /// A document still shared with a clone of the corpus, a search result or a
+    /// document repository is copied first.
+    pub fn get_document_mut(&mut self, document_id: &DocumentId) -> DomainResult<Option<&mut Document>> {
+        // The caller may change the document's terms, so treat this as a modification
+        self.revision += 1;
+        self.duplicate_index = None;
         self.documents.get_mut(document_id)
     }
 
-    pub fn add_document(&mut self, document: Document) -> DomainResult<()> {
-        let document_id = document.id().clone();
+    /// Check whether the document of a corpus held by ID has been loaded
+    ///
+    /// Documents added to the corpus are always loaded.
+    pub fn is_document_loaded(&self, document_id: &DocumentId) -> bool {
+        self.documents.is_loaded(document_id)
+    }
 
+    /// Load every document not loaded yet, returning the IDs of those that no longer exist, ordered by ID
+    ///
+    /// Documents that no longer exist are skipped by every other read; this
+    /// reports them. Fails if the resolver cannot look a document up.
+    pub fn load_documents(&self) -> DomainResult<Vec<DocumentId>> {
+        self.documents.load_all()
+    }
+
+    /// Add a document, owned or already shared
+    ///
+    /// A document duplicating one already in the corpus is refused with
+    /// `DomainError::Duplicate` or flagged, according to the duplicate policy.
+    pub fn add_document(&mut self, document: impl Into<Arc<Document>>) -> DomainResult<()> {
+        let document: Arc<Document> = document.into();
+        let document_id = document.id().clone();
 
         if self.contains_document(&document_id) {
             return Err(DomainError::InvalidOperation(
@@ -138,54 +279,238 @@ impl Corpus {
             ))
         }
 
+        if let Some(duplicate) = self.check_duplicate(&document)?.map_err(DomainError::Duplicate)? {
+            self.flagged_duplicates.push(duplicate);
+        }
+        self.insert_document(document)
+    }
+
+    /// Add a document that passed the duplicate policy, updating an existing index
+    fn insert_document(&mut self, document: Arc<Document>) -> DomainResult<()> {
+        if let Some(index) = self.duplicate_index.as_mut() {
+            Arc::make_mut(index).insert(&document);
+        }
+
         // If the corpus is already indexed, we need to update document frequencies
         if self.indexed {
-
-            let unique_terms: HashSet<_> = document.term_frequencies().keys().collect();
-            for term in unique_terms {
-                let count = self.document_frequencies.entry(term.clone()).or_insert(0);
+            let document_frequencies = Arc::make_mut(&mut self.document_frequencies);
+            let term_dictionary = Arc::make_mut(&mut self.term_dictionary);
+            for term in document.content_terms() {
+                let count = document_frequencies.entry(term.clone()).or_insert(0);
                 *count += 1;
+                term_dictionary.insert(term.text().to_string());
+            }
+
+            let canonical_document_frequencies = Arc::make_mut(&mut self.canonical_document_frequencies);
+            for canonical in document.canonical_frequencies().keys() {
+                *canonical_document_frequencies.entry(canonical.clone()).or_insert(0) += 1;
             }
 
+            Arc::make_mut(&mut self.vocabulary).add_document(&document);
         }
 
-        self.documents.insert(document_id, document);
+        self.token_count = Some(self.total_token_count() + document.term_count());
+        self.documents.insert(document);
+        self.revision += 1;
+
+        // An auto-indexed corpus loaded before its index was ever built catches up here
+        if self.auto_index && !self.indexed {
+            self.build_index()?;
+        }
         Ok(())
     }
 
-     /// Remove a document from the corpus
-    pub fn remove_document(&mut self, document_id: &DocumentId) -> DomainResult<Document> {
-        if !self.contains_document(document_id) {
-            return Err(DomainError::NotFound(
-                format!("Document with ID '{}' not found in corpus", document_id.value())
-            ));
+    /// Remove a document from the corpus, returning it unless it no longer exists
+    ///
+    /// An existing index is updated incrementally when the document is still at
+    /// the revision it was added at, and rebuilt otherwise, since only that
+    /// revision's terms can be subtracted from it.
+    pub fn remove_document(&mut self, document_id: &DocumentId) -> DomainResult<Option<Arc<Document>>> {
+        let token_count = self.total_token_count();
+        let (revision, document) = self.documents.remove(document_id)?.ok_or_else(|| DomainError::NotFound(
+            format!("Document with ID '{}' not found in corpus", document_id.value())
+        ))?;
+        self.revision += 1;
+        self.flagged_duplicates.retain(|duplicate| duplicate.document_id() != document_id && duplicate.existing_id() != document_id);
+
+        let Some(counted) = document.as_ref().filter(|document| document.revision() == revision) else {
+            self.duplicate_index = None;
+            if self.indexed {
+                self.build_index()?;
+            } else {
+                self.token_count = Some(self.count_tokens()?);
+            }
+            return Ok(document);
+        };
+
+        self.token_count = Some(token_count.saturating_sub(counted.term_count()));
+        if let Some(index) = self.duplicate_index.as_mut() {
+            Arc::make_mut(index).remove(counted);
         }
         
-        let document = self.documents.remove(document_id).unwrap();
-        
         // If the corpus is indexed, update document frequencies
         if self.indexed {
-            let unique_terms: HashSet<_> = document.term_frequencies().keys().collect();
-            for term in unique_terms {
-                if let Some(count) = self.document_frequencies.get_mut(term) {
+            let document_frequencies = Arc::make_mut(&mut self.document_frequencies);
+            let term_dictionary = Arc::make_mut(&mut self.term_dictionary);
+            for term in counted.content_terms() {
+                if let Some(count) = document_frequencies.get_mut(term) {
                     *count = count.saturating_sub(1);
                     if *count == 0 {
-                        self.document_frequencies.remove(term);
+                        document_frequencies.remove(term);
+                        term_dictionary.remove(term.text());
                     }
                 }
             }
+
+            let canonical_document_frequencies = Arc::make_mut(&mut self.canonical_document_frequencies);
+            for canonical in counted.canonical_frequencies().keys() {
+                if let Some(count) = canonical_document_frequencies.get_mut(canonical) {
+                    *count = count.saturating_sub(1);
+                    if *count == 0 {
+                        canonical_document_frequencies.remove(canonical);
+                    }
+                }
+            }
+
+            Arc::make_mut(&mut self.vocabulary).remove_document(counted);
         }
         
         Ok(document)
     }
-    /// Get all documents in the corpus
-    pub fn documents(&self) -> impl Iterator<Item = &Document> {
-        self.documents.values()
+
+    /// Replace the corpus copy of a document with a newer one, returning the old copy unless it can no longer be loaded
+    ///
+    /// An existing index is updated as when removing and adding the document.
+    /// The newer copy is checked against the duplicate policy first, so a
+    /// rejected replacement leaves the old copy in place.
+    pub fn replace_document(&mut self, document: impl Into<Arc<Document>>) -> DomainResult<Option<Arc<Document>>> {
+        let document: Arc<Document> = document.into();
+        if !self.contains_document(document.id()) {
+            return Err(DomainError::NotFound(
+                format!("Document with ID '{}' not found in corpus", document.id().value())
+            ));
+        }
+
+        let duplicate = self.check_duplicate(&document)?.map_err(DomainError::Duplicate)?;
+        let old = self.remove_document(document.id())?;
+        self.flagged_duplicates.extend(duplicate);
+        self.insert_document(document)?;
+        Ok(old)
+    }
+
+    /// Check whether the corpus added the document at a different revision
+    pub fn has_outdated_copy(&self, document: &Document) -> bool {
+        self.documents.revision(document.id())
+            .is_some_and(|revision| revision != document.revision())
+    }
+
+    /// Split the corpus into a copy without documents and the ID and revision of each document, ordered by ID
+    ///
+    /// The copy keeps the index data, so the documents can be stored elsewhere
+    /// and put back with `attach_documents`.
+    pub fn detach_documents(&self) -> (Corpus, Vec<(DocumentId, u64)>) {
+        // Without documents to count, the token count must be known
+        let mut detached = self.clone_without_documents();
+        detached.token_count = Some(self.total_token_count());
+        (detached, self.documents.revisions())
+    }
+
+    /// Put back the documents of a corpus split with `detach_documents`, to be loaded through `resolver` on first use
+    ///
+    /// Nothing is loaded here. A document that can no longer be found stays in
+    /// the corpus and is skipped by reads; `load_documents` reports it. The index
+    /// keeps describing the revisions the documents were added at, so documents
+    /// changed since then are reported by `has_outdated_copy` until replaced.
+    pub fn attach_documents(&mut self, members: &[(DocumentId, u64)], resolver: Arc<dyn DocumentResolver>) {
+        self.documents.attach(members, resolver);
+        self.duplicate_index = None;
+    }
+
+    /// Check a document against the duplicate policy
+    ///
+    /// The inner result fails with the duplicate under `DuplicatePolicy::Reject`,
+    /// and holds the duplicate to flag under `DuplicatePolicy::Flag`. Fails if
+    /// the documents to compare against cannot be looked up.
+    fn check_duplicate(&mut self, document: &Document) -> DomainResult<Result<Option<Duplicate>, Duplicate>> {
+        if self.duplicate_policy == DuplicatePolicy::Allow {
+            return Ok(Ok(None));
+        }
+
+        let documents = &self.documents;
+        let index = match self.duplicate_index.as_mut() {
+            Some(index) => index,
+            None => {
+                let existing = documents.iter().collect::<DomainResult<Vec<_>>>()?;
+                self.duplicate_index.insert(Arc::new(DuplicateIndex::new(existing.into_iter().map(Arc::as_ref))))
+            },
+        };
+        let duplicate = Arc::make_mut(index).find(document, documents, self.duplicate_policy.similarity_threshold())?;
+        Ok(match (self.duplicate_policy, duplicate) {
+            (DuplicatePolicy::Reject { .. }, Some(duplicate)) => Err(duplicate),
+            (_, duplicate) => Ok(duplicate),
+        })
+    }
+
+    /// Get how documents duplicating one already in the corpus are handled
+    pub fn duplicate_policy(&self) -> DuplicatePolicy {
+        self.duplicate_policy
+    }
+
+    /// Set how documents duplicating one already in the corpus are handled
+    ///
+    /// Only documents added afterwards are checked. Fails if the similarity
+    /// threshold does not lie between 0 and 1.
+    pub fn set_duplicate_policy(&mut self, duplicate_policy: DuplicatePolicy) -> DomainResult<()> {
+        duplicate_policy.validate()?;
+        self.duplicate_policy = duplicate_policy;
+        Ok(())
+    }
+
+    /// Get the duplicates added under `DuplicatePolicy::Flag` whose documents are still in the corpus
+    pub fn flagged_duplicates(&self) -> &[Duplicate] {
+        &self.flagged_duplicates
+    }
+
+    /// Clone every field except the documents, which are left empty
+    fn clone_without_documents(&self) -> Corpus {
+        Corpus {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            description: self.description.clone(),
+            documents: Members::default(),
+            document_frequencies: self.document_frequencies.clone(),
+            canonical_document_frequencies: self.canonical_document_frequencies.clone(),
+            term_dictionary: self.term_dictionary.clone(),
+            vocabulary: self.vocabulary.clone(),
+            stopwords: self.stopwords.clone(),
+            indexed: self.indexed,
+            metadata: self.metadata.clone(),
+            revision: self.revision,
+            token_count: self.token_count,
+            options: self.options.clone(),
+            auto_index: self.auto_index,
+            duplicate_policy: self.duplicate_policy,
+            flagged_duplicates: self.flagged_duplicates.clone(),
+            duplicate_index: None,
+        }
+    }
+
+    /// Get all documents in the corpus, loading those held by ID and skipping any that no longer exist
+    ///
+    /// Yields an error for each document the resolver cannot look up, so
+    /// statistics are never computed from part of the corpus unknowingly.
+    pub fn documents(&self) -> impl Iterator<Item = DomainResult<&Document>> {
+        self.documents.iter().map(|document| document.map(Arc::as_ref))
+    }
+
+    /// Get shared handles to all documents in the corpus, loading them like `documents`
+    pub fn shared_documents(&self) -> impl Iterator<Item = DomainResult<&Arc<Document>>> {
+        self.documents.iter()
     }
     
-    /// Get document IDs in the corpus
+    /// Get the IDs of all documents in the corpus, without loading them
     pub fn document_ids(&self) -> impl Iterator<Item = &DocumentId> {
-        self.documents.keys()
+        self.documents.ids()
     }
     
     /// Add a stopword to the corpus
@@ -220,6 +545,75 @@ impl Corpus {
         self.document_frequencies.get(term).copied().unwrap_or(0)
     }
 
+    /// Get the number of documents containing any term with the given canonical form
+    pub fn canonical_document_frequency(&self, canonical: &str) -> usize {
+        self.canonical_document_frequencies.get(canonical).copied().unwrap_or(0)
+    }
+
+    /// Get the `n` indexed terms found in the most documents, most common first
+    ///
+    /// Ties are broken by term text. Useful for spotting stopword candidates.
+    pub fn top_terms_by_df(&self, n: usize) -> Vec<(&Term, usize)> {
+        self.terms_by_df(n, |a, b| b.cmp(&a))
+    }
+
+    /// Get the `n` indexed terms found in the fewest documents, i.e. with the highest IDF, rarest first
+    ///
+    /// Ties are broken by term text. Useful for spotting typos and pruning candidates.
+    pub fn rarest_terms(&self, n: usize) -> Vec<(&Term, usize)> {
+        self.terms_by_df(n, |a, b| a.cmp(&b))
+    }
+
+    /// Select the first `n` indexed terms by document frequency order, then term text
+    fn terms_by_df(&self, n: usize, order: impl Fn(usize, usize) -> std::cmp::Ordering) -> Vec<(&Term, usize)> {
+        let compare = |a: &(&Term, usize), b: &(&Term, usize)| order(a.1, b.1).then_with(|| a.0.text().cmp(b.0.text()));
+        let mut terms: Vec<(&Term, usize)> = self.document_frequencies().collect();
+        if n < terms.len() {
+            // Only the selected terms need sorting
+            terms.select_nth_unstable_by(n, compare);
+            terms.truncate(n);
+        }
+        terms.sort_by(compare);
+        terms
+    }
+
+    /// Iterate over the indexed terms with their document frequencies
+    pub(super) fn document_frequencies(&self) -> impl Iterator<Item = (&Term, usize)> {
+        self.document_frequencies.iter().map(|(term, count)| (term, *count))
+    }
+
+    /// Iterate over the indexed canonical forms with their document frequencies
+    pub(super) fn canonical_document_frequencies(&self) -> impl Iterator<Item = (&String, usize)> {
+        self.canonical_document_frequencies.iter().map(|(canonical, count)| (canonical, *count))
+    }
+
+    /// Get the total number of occurrences of a term across all document content
+    pub fn collection_frequency(&self, term: &Term) -> usize {
+        self.vocabulary.collection_frequency(term)
+    }
+
+    /// Get the total number of occurrences of every term with the given canonical form across all document content
+    pub fn canonical_collection_frequency(&self, canonical: &str) -> usize {
+        self.vocabulary.canonical_collection_frequency(canonical)
+    }
+
+    /// Get the total number of terms across all document content
+    pub fn total_term_count(&self) -> usize {
+        self.vocabulary.total_term_count()
+    }
+
+    /// Get the probability of drawing a term from the whole collection, cf / total terms
+    ///
+    /// This is the background model used by language-model smoothing.
+    pub fn collection_probability(&self, term: &Term) -> f64 {
+        let total = self.total_term_count();
+        if total == 0 {
+            return 0.0;
+        }
+
+        self.collection_frequency(term) as f64 / total as f64
+    }
+
     pub fn inverse_document_frequency(&self, term: &Term) -> f64 {
         let doc_count = self.document_count() as f64;
         if doc_count == 0.0 {
@@ -236,25 +630,182 @@ impl Corpus {
 
     }
 
-     /// Build or rebuild the document frequency index
-    pub fn build_index(&mut self) {
-        self.document_frequencies.clear();
+    /// Get the average number of terms per document
+    pub fn average_document_length(&self) -> f64 {
+        if self.documents.is_empty() {
+            return 0.0;
+        }
+
+        self.total_token_count() as f64 / self.documents.len() as f64
+    }
+
+    /// Get the total number of content terms across all documents, indexed or not
+    ///
+    /// Documents edited in place through `get_document_mut` are only recounted by `build_index`.
+    pub fn total_token_count(&self) -> usize {
+        // Only corpora stored before the count was tracked lack it, and those hold every document in memory
+        self.token_count.unwrap_or_else(|| self.documents.loaded().map(|document| document.term_count()).sum())
+    }
+
+    /// Count the content terms of every document, loading those held by ID
+    fn count_tokens(&self) -> DomainResult<usize> {
+        self.documents.iter().map(|document| document.map(|document| document.term_count())).sum()
+    }
+
+    /// Build or rebuild the document frequency index
+    ///
+    /// Fails, leaving the index as it was, if a document held by ID cannot be looked up.
+    pub fn build_index(&mut self) -> DomainResult<()> {
+        let documents = self.documents.iter().collect::<DomainResult<Vec<_>>>()?;
+        let mut document_frequencies = HashMap::new();
+        let mut canonical_document_frequencies = HashMap::new();
+        let mut term_dictionary = BTreeSet::new();
         
-        for document in self.documents.values() {
-            let unique_terms: HashSet<_> = document.term_frequencies().keys().collect();
-            for term in unique_terms {
-                let count = self.document_frequencies.entry(term.clone()).or_insert(0);
+        for document in &documents {
+            for term in document.content_terms() {
+                let count = document_frequencies.entry(term.clone()).or_insert(0);
                 *count += 1;
+                term_dictionary.insert(term.text().to_string());
+            }
+
+            for canonical in document.canonical_frequencies().keys() {
+                *canonical_document_frequencies.entry(canonical.clone()).or_insert(0) += 1;
             }
         }
+        self.document_frequencies = Arc::new(document_frequencies);
+        self.canonical_document_frequencies = Arc::new(canonical_document_frequencies);
+        self.term_dictionary = Arc::new(term_dictionary);
+        self.vocabulary = Arc::new(Vocabulary::from_documents(documents.iter().map(|document| document.as_ref())));
+        self.token_count = Some(documents.iter().map(|document| document.term_count()).sum());
 
         self.indexed = true;
+        self.revision += 1;
+        Ok(())
+    }
+
+    /// Get the indexed terms with their IDs and statistics, as of the last index update
+    pub fn vocabulary(&self) -> &Vocabulary {
+        &self.vocabulary
     }
 
-     /// Check if the corpus is indexed
+    /// Get every vocabulary term with its statistics and IDF, ordered by term text
+    ///
+    /// The IDF is weighted by the corpus's TF-IDF options, or the default options if it has none.
+    pub fn vocabulary_entries(&self) -> Vec<VocabularyEntry> {
+        let tfidf = TfIdf::new(self.options.clone().unwrap_or_default());
+        let mut entries: Vec<VocabularyEntry> = self.vocabulary.iter()
+            .map(|(term, stats)| VocabularyEntry::new(
+                term.text(),
+                stats.document_frequency(),
+                stats.collection_frequency(),
+                tfidf.term_idf(term, self)
+            ))
+            .collect();
+        entries.sort_by(|a, b| a.term().cmp(b.term()));
+        entries
+    }
+
+    /// Count how often pairs of terms co-occur across the documents' content
+    pub fn cooccurrence_matrix(&self, window: CooccurrenceWindow) -> DomainResult<CooccurrenceMatrix> {
+        CooccurrenceMatrix::from_corpus(self, window)
+    }
+
+    /// Get the indexed terms starting with a prefix, in sorted order
+    pub fn terms_with_prefix<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.term_dictionary
+            .range(prefix.to_string()..)
+            .take_while(move |text| text.starts_with(prefix))
+            .map(String::as_str)
+    }
+
+    /// Expand a wildcard pattern into the matching indexed terms
+    ///
+    /// `*` matches any run of characters and `?` a single character, so
+    /// `rust*` is a prefix query. A pattern without wildcards matches itself.
+    pub fn expand_wildcard(&self, pattern: &str) -> Vec<Term> {
+        // Only terms sharing the literal prefix can match, so scan just that range
+        let prefix_len = pattern.find(['*', '?']).unwrap_or(pattern.len());
+
+        self.terms_with_prefix(&pattern[..prefix_len])
+            .filter(|text| matches_wildcard(pattern, text))
+            .map(Term::new)
+            .collect()
+    }
+
+    /// Get the corpus revision, incremented on every change to documents or the index
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Get the TF-IDF options this corpus prefers to be scored with, if any
+    ///
+    /// Custom weighting functions are not serialized, so they are lost when the corpus is stored.
+    pub fn options(&self) -> Option<&TfIdfOptions> {
+        self.options.as_ref()
+    }
+
+    /// Set the TF-IDF options this corpus prefers (None = the caller's defaults)
+    pub fn set_options(&mut self, options: Option<TfIdfOptions>) {
+        self.options = options;
+        // Cached vectors were weighted with the old options
+        self.revision += 1;
+    }
+
+    /// Check whether the corpus keeps its index current automatically
+    pub fn auto_index(&self) -> bool {
+        self.auto_index
+    }
+
+    /// Keep the index current automatically, building it now if it was never built
+    ///
+    /// Adding and removing documents update an existing index incrementally, so
+    /// the corpus stays indexed without calling `build_index`; a stored corpus
+    /// with the option set but no index builds it when its next document is added.
+    /// Documents changed through `get_document_mut` still need a rebuild.
+    pub fn set_auto_index(&mut self, auto_index: bool) -> DomainResult<()> {
+        self.auto_index = auto_index;
+        if auto_index && !self.indexed {
+            self.build_index()?;
+        }
+        Ok(())
+    }
+
+    /// Check if the corpus is indexed
     pub fn is_indexed(&self) -> bool {
         self.indexed
     }
+
+    /// Check whether the document frequency index disagrees with the documents it was built from
+    ///
+    /// Fails if a document held by ID cannot be looked up.
+    pub fn has_stale_index(&self) -> DomainResult<bool> {
+        if !self.indexed {
+            return Ok(false);
+        }
+
+        // (document frequency, collection frequency) per term
+        let mut expected: HashMap<&Term, (usize, usize)> = HashMap::new();
+        let mut total_term_count = 0;
+        for document in self.documents() {
+            let document = document?;
+            for term in document.content_terms() {
+                let (document_frequency, collection_frequency) = expected.entry(term).or_insert((0, 0));
+                *document_frequency += 1;
+                *collection_frequency += document.term_frequency(term).value();
+            }
+            total_term_count += document.term_count();
+        }
+
+        Ok(expected.len() != self.document_frequencies.len()
+            || expected.len() != self.term_dictionary.len()
+            || expected.len() != self.vocabulary.len()
+            || total_term_count != self.total_term_count()
+            || expected.iter().any(|(term, (document_frequency, collection_frequency))| {
+                self.document_frequency(term) != *document_frequency
+                    || self.collection_frequency(term) != *collection_frequency
+                    || !self.term_dictionary.contains(term.text())
+            }))
+    }
     
     /// Get corpus metadata
     pub fn metadata(&self) -> &HashMap<String, String> {
@@ -271,10 +822,82 @@ impl Corpus {
         self.metadata.insert(key.into(), value.into());
     }
 
-   
+    /// Merge another corpus's documents, stopwords and metadata into this one
+    ///
+    /// On a document ID or metadata key present in both, this corpus's entry is
+    /// kept and the collision is reported. Incoming documents are checked
+    /// against this corpus's duplicate policy like added ones, and duplicates
+    /// flagged in the other corpus carry over when both documents are added.
+    /// The index is rebuilt from scratch if either corpus was indexed, so
+    /// document frequencies count every document once. Fails if a document of
+    /// either corpus held by ID cannot be looked up.
+    pub fn merge(&mut self, other: Corpus) -> DomainResult<MergeReport> {
+        let mut report = MergeReport::default();
+        let reindex = self.indexed || other.indexed;
+        // A stored corpus may not know its count yet, so count before adding to it
+        let mut token_count = self.total_token_count();
+
+        // Check incoming documents in ID order, so the same one of two duplicates always wins
+        let mut incoming: Vec<(DocumentId, Arc<Document>)> = other.documents.iter()
+            .map(|document| document.map(|document| (document.id().clone(), Arc::clone(document))))
+            .collect::<DomainResult<_>>()?;
+        incoming.sort_by(|a, b| a.0.value().cmp(b.0.value()));
+
+        self.stopwords.extend(other.stopwords);
+
+        for (key, value) in other.metadata {
+            if self.metadata.get(&key).is_some_and(|existing| *existing != value) {
+                report.metadata_conflicts.push(key);
+            } else {
+                self.metadata.insert(key, value);
+            }
+        }
+
+        let mut added = HashSet::new();
+        for (id, document) in incoming {
+            if self.documents.contains(&id) {
+                report.document_collisions.push(id);
+                continue;
+            }
+            match self.check_duplicate(&document)? {
+                Ok(duplicate) => {
+                    self.flagged_duplicates.extend(duplicate);
+                    if let Some(index) = self.duplicate_index.as_mut() {
+                        Arc::make_mut(index).insert(&document);
+                    }
+                    token_count += document.term_count();
+                    added.insert(id);
+                    self.documents.insert(document);
+                    report.documents_added += 1;
+                },
+                Err(duplicate) => report.rejected_duplicates.push(duplicate),
+            }
+        }
+
+        // Duplicates flagged in the other corpus still hold once both their documents are here
+        for duplicate in other.flagged_duplicates {
+            let carried = added.contains(duplicate.document_id()) && added.contains(duplicate.existing_id())
+                && !self.flagged_duplicates.iter().any(|flagged| flagged.document_id() == duplicate.document_id());
+            if carried {
+                self.flagged_duplicates.push(duplicate);
+            }
+        }
+
+        self.token_count = Some(token_count);
+        report.document_collisions.sort_by(|a, b| a.value().cmp(b.value()));
+        report.metadata_conflicts.sort();
+
+        self.revision += 1;
+        if reindex {
+            self.build_index()?;
+        }
+
+        Ok(report)
+    }
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
     use crate::domain::Term;
@@ -317,12 +940,16 @@ mod tests {
         doc2.add_term(Term::new("another"));
         doc2.add_term(Term::new("example"));
         
+        // Title terms are not content, so they do not count towards document frequencies
+        doc2.add_field_term(Document::TITLE_FIELD, Term::new("test"));
+        doc2.add_field_term(Document::TITLE_FIELD, Term::new("guide"));
+        
         // Add documents to corpus
         corpus.add_document(doc1).unwrap();
         corpus.add_document(doc2).unwrap();
         
         // Build index
-        corpus.build_index();
+        corpus.build_index().unwrap();
         assert!(corpus.is_indexed());
         
         // Check document frequencies
@@ -333,6 +960,8 @@ mod tests {
         assert_eq!(corpus.document_frequency(&Term::new("another")), 1);
         assert_eq!(corpus.document_frequency(&Term::new("example")), 1);
         assert_eq!(corpus.document_frequency(&Term::new("unknown")), 0);
+        assert_eq!(corpus.document_frequency(&Term::new("guide")), 0);
+        assert!(!corpus.has_stale_index().unwrap());
         
         // Check IDF calculations
         let idf_this = corpus.inverse_document_frequency(&Term::new("this"));
@@ -345,6 +974,57 @@ mod tests {
         assert!((idf_a - 2.0_f64.ln()).abs() < f64::EPSILON);
     }
     
+    #[test]
+    fn test_vocabulary_extremes() {
+        let mut corpus = Corpus::new("corpus1", "Test Corpus");
+        for (id, words) in [("doc1", ["the", "rust", "borrow"]), ("doc2", ["the", "rust", "cargo"]), ("doc3", ["the", "go", "typo"])] {
+            let mut document = Document::new(id, words.join(" "));
+            document.add_terms(words.map(Term::new));
+            corpus.add_document(document).unwrap();
+        }
+        corpus.build_index().unwrap();
+        
+        let top: Vec<(&str, usize)> = corpus.top_terms_by_df(2).into_iter().map(|(term, df)| (term.text(), df)).collect();
+        assert_eq!(top, vec![("the", 3), ("rust", 2)]);
+        
+        let rarest: Vec<&str> = corpus.rarest_terms(3).into_iter().map(|(term, _)| term.text()).collect();
+        assert_eq!(rarest, vec!["borrow", "cargo", "go"]);
+        assert_eq!(corpus.rarest_terms(10).len(), 6);
+        
+        // Exported IDFs follow the corpus's weighting, not the raw ln(N / df)
+        let rust = |corpus: &Corpus| corpus.vocabulary_entries().into_iter().find(|entry| entry.term() == "rust").unwrap().idf();
+        assert!((rust(&corpus) - TfIdf::default().term_idf(&Term::new("rust"), &corpus)).abs() < 1e-12);
+        assert_ne!(rust(&corpus), corpus.inverse_document_frequency(&Term::new("rust")));
+        corpus.set_options(Some(TfIdfOptions { smoothing: crate::domain::Smoothing::None, ..TfIdfOptions::default() }));
+        assert!((rust(&corpus) - (3.0f64 / 2.0).ln()).abs() < 1e-12);
+    }
+    
+    #[test]
+    fn test_collection_frequencies() {
+        let mut corpus = Corpus::new("corpus1", "Test Corpus");
+        
+        let mut doc1 = Document::new("doc1", "rust rust go");
+        doc1.add_terms(["rust", "rust", "go"].map(Term::new));
+        corpus.add_document(doc1).unwrap();
+        corpus.build_index().unwrap();
+        
+        // Documents added after indexing are counted incrementally
+        let mut doc2 = Document::new("doc2", "rust");
+        doc2.add_term(Term::new("rust"));
+        corpus.add_document(doc2).unwrap();
+        
+        assert_eq!(corpus.collection_frequency(&Term::new("rust")), 3);
+        assert_eq!(corpus.document_frequency(&Term::new("rust")), 2);
+        assert_eq!(corpus.total_term_count(), 4);
+        assert!((corpus.collection_probability(&Term::new("go")) - 0.25).abs() < f64::EPSILON);
+        assert!(!corpus.has_stale_index().unwrap());
+        
+        corpus.remove_document(&DocumentId::new("doc1")).unwrap();
+        assert_eq!(corpus.collection_frequency(&Term::new("rust")), 1);
+        assert_eq!(corpus.collection_frequency(&Term::new("go")), 0);
+        assert_eq!(corpus.total_term_count(), 1);
+    }
+    
     #[test]
     fn test_remove_document() {
         let mut corpus = Corpus::new("corpus1", "Test Corpus");
@@ -355,17 +1035,364 @@ mod tests {
         doc.add_term(Term::new("is"));
         
         corpus.add_document(doc).unwrap();
-        corpus.build_index();
+        corpus.build_index().unwrap();
         
         // Verify document frequency
         assert_eq!(corpus.document_frequency(&Term::new("this")), 1);
         
         // Remove the document
         let removed_doc = corpus.remove_document(&DocumentId::new("doc1")).unwrap();
-        assert_eq!(removed_doc.id().value(), "doc1");
+        assert_eq!(removed_doc.unwrap().id().value(), "doc1");
         assert_eq!(corpus.document_count(), 0);
         
         // Document frequency should be updated
         assert_eq!(corpus.document_frequency(&Term::new("this")), 0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_stale_index() {
+        let mut corpus = Corpus::new("corpus1", "Test Corpus");
+
+        let mut doc = Document::new("doc1", "This is a test");
+        doc.add_term(Term::new("this"));
+        corpus.add_document(doc).unwrap();
+        corpus.build_index().unwrap();
+        assert!(!corpus.has_stale_index().unwrap());
+
+        // Mutating a document behind the index's back makes it stale
+        corpus.get_document_mut(&DocumentId::new("doc1")).unwrap().unwrap().add_term(Term::new("test"));
+        assert!(corpus.has_stale_index().unwrap());
+
+        corpus.build_index().unwrap();
+        assert!(!corpus.has_stale_index().unwrap());
+    }
+
+    #[test]
+    fn test_wildcard_expansion() {
+        let mut corpus = Corpus::new("corpus1", "Test Corpus");
+
+        let mut doc = Document::new("doc1", "rust rusty rustacean trust");
+        doc.add_terms(["rust", "rusty", "rustacean", "trust"].map(Term::new));
+        corpus.add_document(doc).unwrap();
+        corpus.build_index().unwrap();
+
+        let prefixed: Vec<_> = corpus.terms_with_prefix("rust").collect();
+        assert_eq!(prefixed, vec!["rust", "rustacean", "rusty"]);
+
+        let texts = |pattern: &str| -> Vec<String> {
+            corpus.expand_wildcard(pattern).iter().map(|t| t.text().to_string()).collect()
+        };
+        assert_eq!(texts("rust?"), vec!["rusty"]);
+        assert_eq!(texts("*ust"), vec!["rust", "trust"]);
+        assert_eq!(texts("rust"), vec!["rust"]);
+        assert!(texts("go*").is_empty());
+
+        assert_eq!(corpus.vocabulary().len(), 4);
+        assert_eq!(corpus.vocabulary().id(&Term::new("rust")), Some(0));
+
+        // Removing the only document drops its terms from the dictionary
+        corpus.remove_document(&DocumentId::new("doc1")).unwrap();
+        assert_eq!(corpus.terms_with_prefix("").count(), 0);
+        assert!(corpus.vocabulary().is_empty());
+    }
+    
+    #[test]
+    fn test_length_statistics() {
+        let mut corpus = Corpus::new("corpus1", "Test Corpus");
+        let mut doc1 = Document::new("doc1", "rust is fast");
+        doc1.add_terms(["rust", "is", "fast"].map(Term::new));
+        let mut doc2 = Document::new("doc2", "go");
+        doc2.add_term(Term::new("go"));
+        corpus.add_document(doc1).unwrap();
+        corpus.add_document(doc2).unwrap();
+        
+        // Tracked without indexing
+        assert_eq!(corpus.total_token_count(), 4);
+        assert_eq!(corpus.average_document_length(), 2.0);
+        
+        corpus.remove_document(&DocumentId::new("doc1")).unwrap();
+        assert_eq!(corpus.total_token_count(), 1);
+        assert_eq!(corpus.average_document_length(), 1.0);
+        
+        corpus.build_index().unwrap();
+        assert_eq!(corpus.total_token_count(), 1);
+        
+        // Documents without terms keep the count known rather than zero as unknown
+        let mut empty = Corpus::new("corpus2", "Empty Documents");
+        empty.add_document(Document::new("doc1", "")).unwrap();
+        empty.add_document(Document::new("doc2", "")).unwrap();
+        assert_eq!(empty.token_count, Some(0));
+        
+        // A corpus stored before the count was tracked counts once, on its next change
+        let mut stored = serde_json::to_value(&corpus).unwrap();
+        stored.as_object_mut().unwrap().remove("token_count");
+        let mut stored: Corpus = serde_json::from_value(stored).unwrap();
+        assert_eq!(stored.token_count, None);
+        assert_eq!(stored.total_token_count(), 1);
+        stored.add_document(Document::new("doc3", "")).unwrap();
+        assert_eq!(stored.token_count, Some(1));
+    }
+    
+    #[test]
+    fn test_options_are_serialized() {
+        let mut corpus = Corpus::new("corpus1", "Test Corpus");
+        let revision = corpus.revision();
+        corpus.set_options(Some(TfIdfOptions::with_scheme(crate::domain::Scheme::ltc())));
+        assert!(corpus.revision() > revision);
+        
+        let json = serde_json::to_string(&corpus).unwrap();
+        let restored: Corpus = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.options().unwrap().scheme, Some(crate::domain::Scheme::ltc()));
+    }
+    
+    #[test]
+    fn test_replace_outdated_document() {
+        let mut corpus = Corpus::new("corpus1", "Test Corpus");
+        let mut doc = Document::new("doc1", "rust");
+        doc.add_term(Term::new("rust"));
+        corpus.add_document(doc.clone()).unwrap();
+        corpus.build_index().unwrap();
+        assert!(!corpus.has_outdated_copy(&doc));
+        
+        // Re-analysis bumps the revision
+        doc.set_content("go");
+        doc.clear_terms();
+        doc.add_term(Term::new("go"));
+        assert!(corpus.has_outdated_copy(&doc));
+        
+        let old = corpus.replace_document(doc.clone()).unwrap();
+        assert_eq!(old.unwrap().content(), "rust");
+        assert!(!corpus.has_outdated_copy(&doc));
+        assert_eq!(corpus.document_frequency(&Term::new("rust")), 0);
+        assert_eq!(corpus.document_frequency(&Term::new("go")), 1);
+        assert!(!corpus.has_stale_index().unwrap());
+    }
+    
+    #[test]
+    fn test_clones_share_documents() {
+        let mut corpus = Corpus::new("corpus1", "Test Corpus");
+        corpus.add_document(Document::new("doc1", "rust code")).unwrap();
+        let id = DocumentId::new("doc1");
+
+        let mut copy = corpus.clone();
+        assert!(Arc::ptr_eq(corpus.get_shared_document(&id).unwrap().unwrap(), copy.get_shared_document(&id).unwrap().unwrap()));
+
+        // Editing one copy leaves the other untouched
+        copy.get_document_mut(&id).unwrap().unwrap().add_term(Term::new("rust"));
+        assert!(!Arc::ptr_eq(corpus.get_shared_document(&id).unwrap().unwrap(), copy.get_shared_document(&id).unwrap().unwrap()));
+        assert_eq!(corpus.get_document(&id).unwrap().unwrap().term_count(), 0);
+    }
+
+    #[test]
+    fn test_detach_documents() {
+        let mut corpus = Corpus::new("corpus1", "Test Corpus");
+        for (id, text) in [("doc1", "rust code"), ("doc2", "go code")] {
+            let mut document = Document::new(id, text);
+            document.add_terms(text.split_whitespace().map(Term::new));
+            corpus.add_document(document).unwrap();
+        }
+        corpus.build_index().unwrap();
+        let stored: HashMap<DocumentId, Arc<Document>> = corpus.shared_documents().map(|d| d.map(|d| (d.id().clone(), Arc::clone(d)))).collect::<DomainResult<_>>().unwrap();
+        let stored = Arc::new(stored);
+        let (doc1, doc2) = (DocumentId::new("doc1"), DocumentId::new("doc2"));
+
+        let (detached, members) = corpus.detach_documents();
+        assert_eq!(detached.document_count(), 0);
+        assert_eq!(detached.document_frequency(&Term::new("code")), 2);
+        assert_eq!(members.len(), 2);
+
+        // Documents are loaded on first use, sharing the resolver's copies
+        let mut restored = detached.clone();
+        let documents = stored.clone();
+        restored.attach_documents(&members, Arc::new(move |id: &DocumentId| Ok::<_, DomainError>(documents.get(id).cloned())));
+        assert_eq!(restored.document_count(), 2);
+        assert!(!restored.is_document_loaded(&doc1));
+        assert!(Arc::ptr_eq(restored.get_shared_document(&doc1).unwrap().unwrap(), &stored[&doc1]));
+        assert!(restored.is_document_loaded(&doc1));
+        assert_eq!(restored.revision(), corpus.revision());
+        assert!(!restored.has_stale_index().unwrap());
+
+        // A missing document stays in the corpus until it is removed
+        let mut restored = detached.clone();
+        let documents = stored.clone();
+        restored.attach_documents(&members, Arc::new(move |id: &DocumentId| Ok::<_, DomainError>(documents.get(id).filter(|d| d.id().value() == "doc1").cloned())));
+        assert_eq!(restored.document_count(), 2);
+        assert_eq!(restored.documents().count(), 1);
+        assert_eq!(restored.load_documents().unwrap(), vec![doc2.clone()]);
+        assert_eq!(restored.document_frequency(&Term::new("code")), 2);
+        assert!(restored.remove_document(&doc2).unwrap().is_none());
+        assert_eq!(restored.document_frequency(&Term::new("code")), 1);
+        assert_eq!(restored.total_token_count(), 2);
+        assert!(!restored.has_stale_index().unwrap());
+
+        // A document changed since it was added is replaced by rebuilding the index, as its old terms are gone
+        let mut changed = Arc::unwrap_or_clone(Arc::clone(&stored[&doc2]));
+        changed.set_content("rust code");
+        changed.clear_terms();
+        changed.add_terms([Term::new("rust"), Term::new("code")]);
+        let changed = Arc::new(changed);
+        let mut restored = detached.clone();
+        let (documents, current) = (stored.clone(), changed.clone());
+        restored.attach_documents(&members, Arc::new(move |id: &DocumentId| {
+            Ok::<_, DomainError>(if id == current.id() { Some(current.clone()) } else { documents.get(id).cloned() })
+        }));
+        assert!(restored.has_outdated_copy(&changed));
+        restored.replace_document(changed.clone()).unwrap();
+        assert!(!restored.has_outdated_copy(&changed));
+        assert_eq!(restored.document_frequency(&Term::new("rust")), 2);
+        assert_eq!(restored.document_frequency(&Term::new("go")), 0);
+        assert!(!restored.has_stale_index().unwrap());
+
+        // Lookup failures fail reads and calculations instead of leaving documents out
+        let mut restored = detached;
+        restored.attach_documents(&members, Arc::new(|_: &DocumentId| Err(DomainError::Other("offline".to_string()))));
+        assert!(restored.get_document(&doc1).is_err());
+        assert!(restored.documents().all(|document| document.is_err()));
+        assert!(restored.load_documents().is_err());
+        assert!(TfIdf::default().search(&[Term::new("code")], &restored).is_err());
+        assert!(restored.has_stale_index().is_err());
+        assert!(restored.build_index().is_err());
+        assert!(restored.remove_document(&doc1).is_err());
+        assert_eq!(restored.document_count(), 2);
+    }
+
+    #[test]
+    fn test_duplicate_policy() {
+        let document = |id: &str, text: &str| {
+            let mut document = Document::new(id, text);
+            document.add_terms(text.split_whitespace().map(Term::new));
+            document
+        };
+        let mut corpus = Corpus::new("corpus1", "Test Corpus");
+        corpus.add_document(document("doc1", "rust is fast and safe")).unwrap();
+        corpus.set_duplicate_policy(DuplicatePolicy::Flag { similarity_threshold: Some(0.7) }).unwrap();
+
+        corpus.add_document(document("doc2", "rust is fast and safe")).unwrap();
+        corpus.add_document(document("doc3", "rust is fast and very safe")).unwrap();
+        corpus.add_document(document("doc4", "go is simple")).unwrap();
+        assert_eq!(corpus.flagged_duplicates().len(), 2);
+        assert!(corpus.flagged_duplicates()[0].is_exact());
+        assert_eq!(corpus.flagged_duplicates()[1].document_id().value(), "doc3");
+        assert!(!corpus.flagged_duplicates()[1].is_exact());
+
+        corpus.remove_document(&DocumentId::new("doc2")).unwrap();
+        assert_eq!(corpus.flagged_duplicates().len(), 1);
+
+        corpus.set_duplicate_policy(DuplicatePolicy::Reject { similarity_threshold: None }).unwrap();
+        let result = corpus.add_document(document("doc5", "go is simple"));
+        assert!(matches!(result, Err(DomainError::Duplicate(duplicate)) if duplicate.existing_id().value() == "doc4"));
+        assert!(corpus.add_document(document("doc6", "rust is fast and very safe indeed")).is_ok());
+        assert_eq!(corpus.document_count(), 4);
+
+        // A rejected replacement keeps the old copy; a document never duplicates itself
+        assert!(corpus.replace_document(document("doc6", "go is simple")).is_err());
+        assert_eq!(corpus.get_document(&DocumentId::new("doc6")).unwrap().unwrap().content(), "rust is fast and very safe indeed");
+        assert!(corpus.replace_document(document("doc4", "go is simple")).is_ok());
+
+        assert!(corpus.set_duplicate_policy(DuplicatePolicy::Flag { similarity_threshold: Some(1.5) }).is_err());
+        assert!(corpus.set_duplicate_policy(DuplicatePolicy::Reject { similarity_threshold: Some(f64::NAN) }).is_err());
+    }
+
+    #[test]
+    fn test_merge_duplicate_policy() {
+        let document = |id: &str, text: &str| {
+            let mut document = Document::new(id, text);
+            document.add_terms(text.split_whitespace().map(Term::new));
+            document
+        };
+        let mut corpus = Corpus::new("corpus1", "Test Corpus");
+        corpus.set_duplicate_policy(DuplicatePolicy::Reject { similarity_threshold: Some(0.7) }).unwrap();
+        corpus.add_document(document("doc1", "rust is fast and safe")).unwrap();
+
+        let mut other = Corpus::new("corpus2", "Other");
+        other.add_document(document("doc2", "rust is fast and very safe")).unwrap();
+        other.add_document(document("doc3", "go is simple")).unwrap();
+        other.add_document(document("doc4", "go is simple")).unwrap();
+
+        let report = corpus.merge(other).unwrap();
+        assert_eq!(report.documents_added(), 1);
+        let rejected: Vec<&str> = report.rejected_duplicates().iter().map(|d| d.document_id().value()).collect();
+        assert_eq!(rejected, vec!["doc2", "doc4"]);
+        assert!(!report.is_clean());
+    }
+
+    #[test]
+    fn test_auto_index() {
+        let mut corpus = Corpus::new("corpus1", "Test Corpus");
+        corpus.set_auto_index(true).unwrap();
+        assert!(corpus.is_indexed());
+        
+        let mut doc = Document::new("doc1", "rust is fast");
+        doc.add_terms([Term::new("rust"), Term::new("fast")]);
+        corpus.add_document(doc).unwrap();
+        let mut doc = Document::new("doc2", "rust is safe");
+        doc.add_terms([Term::new("rust"), Term::new("safe")]);
+        corpus.add_document(doc).unwrap();
+        
+        assert!(corpus.is_indexed());
+        assert!(!corpus.has_stale_index().unwrap());
+        assert_eq!(corpus.document_frequency(&Term::new("rust")), 2);
+        assert_eq!(corpus.vocabulary().len(), 3);
+        
+        corpus.remove_document(&DocumentId::new("doc1")).unwrap();
+        assert!(!corpus.has_stale_index().unwrap());
+        assert_eq!(corpus.document_frequency(&Term::new("fast")), 0);
+        
+        let restored: Corpus = serde_json::from_str(&serde_json::to_string(&corpus).unwrap()).unwrap();
+        assert!(restored.auto_index());
+        
+        // A stored corpus with the option but no index builds it on its next document
+        let mut stored = serde_json::to_value(Corpus::new("corpus2", "Stored Corpus")).unwrap();
+        stored["auto_index"] = serde_json::Value::Bool(true);
+        let mut stored: Corpus = serde_json::from_value(stored).unwrap();
+        assert!(!stored.is_indexed());
+        let mut doc = Document::new("doc3", "go is simple");
+        doc.add_terms([Term::new("go"), Term::new("simple")]);
+        stored.add_document(doc).unwrap();
+        assert!(stored.is_indexed());
+        assert_eq!(stored.document_frequency(&Term::new("go")), 1);
+    }
+    
+    #[test]
+    fn test_merge() {
+        let mut doc1 = Document::new("doc1", "rust");
+        doc1.add_term(Term::new("rust"));
+        let mut doc2 = Document::new("doc2", "rust go");
+        doc2.add_terms(["rust", "go"].map(Term::new));
+
+        let mut corpus1 = Corpus::new("corpus1", "First");
+        corpus1.add_document(doc1.clone()).unwrap();
+        corpus1.set_metadata("source", "web");
+        
+        // A stored corpus without a token count still counts its own documents once merged
+        let mut stored = serde_json::to_value(&corpus1).unwrap();
+        stored.as_object_mut().unwrap().remove("token_count");
+        let mut stored: Corpus = serde_json::from_value(stored).unwrap();
+        let mut incoming = Corpus::new("incoming", "Incoming");
+        incoming.add_document(doc2.clone()).unwrap();
+        stored.merge(incoming).unwrap();
+        assert_eq!(stored.total_token_count(), 3);
+        
+        corpus1.build_index().unwrap();
+
+        let mut corpus2 = Corpus::new("corpus2", "Second");
+        corpus2.add_document(doc1).unwrap();
+        corpus2.add_document(doc2).unwrap();
+        corpus2.add_stopword("the");
+        corpus2.set_metadata("source", "mail");
+        corpus2.set_metadata("lang", "en");
+
+        let report = corpus1.merge(corpus2).unwrap();
+        assert_eq!(report.documents_added(), 1);
+        assert_eq!(report.document_collisions(), &[DocumentId::new("doc1")]);
+        assert_eq!(report.metadata_conflicts(), &["source".to_string()]);
+        assert!(!report.is_clean());
+
+        assert_eq!(corpus1.document_count(), 2);
+        assert_eq!(corpus1.document_frequency(&Term::new("rust")), 2);
+        assert!(!corpus1.has_stale_index().unwrap());
+        assert!(corpus1.is_stopword("the"));
+        assert_eq!(corpus1.metadata()["source"], "web");
+        assert_eq!(corpus1.metadata()["lang"], "en");
+    }
+}
@@ -0,0 +1,191 @@
+// src/domain/corpus_stats.rs
+
+use std::collections::{BTreeMap, HashSet};
+use serde::{Serialize, Deserialize};
+
+use super::{Corpus, Document, DomainResult};
+
+/// Statistics of a group of documents
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GroupStats {
+    /// Number of documents in the group
+    document_count: usize,
+
+    /// Number of content terms across the group's documents
+    token_count: usize,
+
+    /// Distinct content terms of the group's documents
+    vocabulary: HashSet<String>,
+}
+
+impl GroupStats {
+    /// Add a document's content terms to the group
+    fn add_document(&mut self, document: &Document) {
+        self.document_count += 1;
+        self.token_count += document.term_count();
+        self.vocabulary.extend(document.term_frequencies()
+            .iter()
+            .filter(|(_, frequency)| frequency.value() > 0)
+            .map(|(term, _)| term.text().to_string()));
+    }
+
+    /// Get the number of documents in the group
+    pub fn document_count(&self) -> usize {
+        self.document_count
+    }
+
+    /// Get the number of content terms across the group's documents
+    pub fn token_count(&self) -> usize {
+        self.token_count
+    }
+
+    /// Get the average number of content terms per document
+    pub fn average_document_length(&self) -> f64 {
+        if self.document_count == 0 {
+            return 0.0;
+        }
+
+        self.token_count as f64 / self.document_count as f64
+    }
+
+    /// Get the number of distinct content terms
+    pub fn vocabulary_size(&self) -> usize {
+        self.vocabulary.len()
+    }
+
+    /// Check if any document of the group contains a term
+    pub fn contains_term(&self, term: &str) -> bool {
+        self.vocabulary.contains(term)
+    }
+}
+
+/// Statistics of a corpus, optionally broken down by the value of a metadata key such as "lang" or "source"
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CorpusStats {
+    /// Metadata key the documents are grouped by (None = no breakdown)
+    key: Option<String>,
+
+    /// Statistics of all documents
+    overall: GroupStats,
+
+    /// Statistics per metadata value
+    groups: BTreeMap<String, GroupStats>,
+
+    /// Number of documents without the metadata key
+    unlabeled_count: usize,
+}
+
+impl CorpusStats {
+    /// Gather the statistics of a corpus without a breakdown
+    pub fn from_corpus(corpus: &Corpus) -> DomainResult<Self> {
+        let mut overall = GroupStats::default();
+        for document in corpus.documents() {
+            overall.add_document(document?);
+        }
+
+        Ok(Self { overall, ..Self::default() })
+    }
+
+    /// Gather the statistics of a corpus, grouping documents by the value of a metadata key
+    ///
+    /// Documents without the key count only towards the overall statistics.
+    pub fn by_metadata(corpus: &Corpus, key: &str) -> DomainResult<Self> {
+        let mut stats = Self { key: Some(key.to_string()), ..Self::default() };
+        for document in corpus.documents() {
+            let document = document?;
+            stats.overall.add_document(document);
+            match document.metadata().get(key) {
+                Some(value) => stats.groups.entry(value.clone()).or_default().add_document(document),
+                None => stats.unlabeled_count += 1,
+            }
+        }
+        Ok(stats)
+    }
+
+    /// Get the metadata key the documents are grouped by
+    pub fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
+    /// Get the statistics of all documents
+    pub fn overall(&self) -> &GroupStats {
+        &self.overall
+    }
+
+    /// Get the statistics per metadata value, ordered by value
+    pub fn groups(&self) -> &BTreeMap<String, GroupStats> {
+        &self.groups
+    }
+
+    /// Get the statistics of the documents with a metadata value
+    pub fn group(&self, value: &str) -> Option<&GroupStats> {
+        self.groups.get(value)
+    }
+
+    /// Get the number of documents without the metadata key
+    pub fn unlabeled_count(&self) -> usize {
+        self.unlabeled_count
+    }
+
+    /// Get the number of terms two groups have in common (0 if either group is missing)
+    pub fn shared_vocabulary(&self, first: &str, second: &str) -> usize {
+        match (self.group(first), self.group(second)) {
+            (Some(first), Some(second)) => first.vocabulary.intersection(&second.vocabulary).count(),
+            _ => 0,
+        }
+    }
+
+    /// Get the Jaccard overlap of two groups' vocabularies, from 0 (disjoint or missing) to 1 (identical)
+    pub fn vocabulary_overlap(&self, first: &str, second: &str) -> f64 {
+        let (Some(first_group), Some(second_group)) = (self.group(first), self.group(second)) else {
+            return 0.0;
+        };
+
+        let shared = self.shared_vocabulary(first, second);
+        let union = first_group.vocabulary_size() + second_group.vocabulary_size() - shared;
+        if union == 0 {
+            return 0.0;
+        }
+
+        shared as f64 / union as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Term;
+
+    fn document(id: &str, terms: &[&str], lang: Option<&str>) -> Document {
+        let mut document = Document::new(id, terms.join(" "));
+        document.add_terms(terms.iter().map(Term::new));
+        if let Some(lang) = lang {
+            document.set_metadata("lang", lang);
+        }
+        document
+    }
+
+    #[test]
+    fn test_stats_by_metadata() {
+        let mut corpus = Corpus::new("corpus1", "Mixed");
+        corpus.add_document(document("doc1", &["rust", "compiler", "fast"], Some("en"))).unwrap();
+        corpus.add_document(document("doc2", &["rust", "code"], Some("en"))).unwrap();
+        corpus.add_document(document("doc3", &["rust", "compilateur"], Some("fr"))).unwrap();
+        corpus.add_document(document("doc4", &["untagged"], None)).unwrap();
+
+        let stats = CorpusStats::by_metadata(&corpus, "lang").unwrap();
+        assert_eq!(stats.key(), Some("lang"));
+        assert_eq!(stats.overall().document_count(), 4);
+        assert_eq!(stats.unlabeled_count(), 1);
+
+        let english = stats.group("en").unwrap();
+        assert_eq!(english.document_count(), 2);
+        assert_eq!(english.vocabulary_size(), 4);
+        assert!((english.average_document_length() - 2.5).abs() < 1e-9);
+
+        assert_eq!(stats.shared_vocabulary("en", "fr"), 1);
+        assert!((stats.vocabulary_overlap("en", "fr") - 0.2).abs() < 1e-9);
+        assert_eq!(stats.vocabulary_overlap("en", "de"), 0.0);
+        assert!(CorpusStats::from_corpus(&corpus).unwrap().groups().is_empty());
+    }
+}
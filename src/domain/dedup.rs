@@ -0,0 +1,483 @@
+// src/domain/dedup.rs
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+use serde::{Serialize, Deserialize};
+
+use super::{Corpus, Document, DocumentId, DomainError, DomainResult};
+use super::members::Members;
+
+/// MinHash signature of a document's set of content terms
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MinHashSignature(Vec<u64>);
+
+impl MinHashSignature {
+    /// Get the minimum hash of each hash function
+    pub fn values(&self) -> &[u64] {
+        &self.0
+    }
+
+    /// Estimate the Jaccard similarity of the two term sets as the share of equal minimums
+    pub fn similarity(&self, other: &MinHashSignature) -> f64 {
+        if self.0.is_empty() || self.0.len() != other.0.len() {
+            return 0.0;
+        }
+
+        let equal = self.0.iter().zip(&other.0).filter(|(a, b)| a == b).count();
+        equal as f64 / self.0.len() as f64
+    }
+}
+
+/// A pair of documents whose content term sets are nearly the same
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NearDuplicate {
+    /// The document whose ID sorts first
+    first: DocumentId,
+
+    /// The document whose ID sorts second
+    second: DocumentId,
+
+    /// Jaccard similarity of the two term sets
+    similarity: f64,
+}
+
+impl NearDuplicate {
+    /// Get the document whose ID sorts first
+    pub fn first(&self) -> &DocumentId {
+        &self.first
+    }
+
+    /// Get the document whose ID sorts second
+    pub fn second(&self) -> &DocumentId {
+        &self.second
+    }
+
+    /// Get the Jaccard similarity of the two term sets
+    pub fn similarity(&self) -> f64 {
+        self.similarity
+    }
+}
+
+/// What `Corpus::add_document` does with a document duplicating one already in the corpus
+///
+/// A document is a duplicate if its content is identical to another's, or,
+/// with a similarity threshold, if the Jaccard similarity of their content
+/// term sets reaches it. Near-duplicates are looked up with the LSH bands of
+/// a default `MinHasher`, so thresholds below about 0.5 may miss some pairs.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum DuplicatePolicy {
+    /// Add duplicates without checking
+    #[default]
+    Allow,
+
+    /// Refuse duplicates with `DomainError::Duplicate`
+    Reject { similarity_threshold: Option<f64> },
+
+    /// Add duplicates, recording them in `Corpus::flagged_duplicates`
+    Flag { similarity_threshold: Option<f64> },
+}
+
+impl DuplicatePolicy {
+    /// Get the similarity threshold, if near-duplicates are checked
+    pub fn similarity_threshold(&self) -> Option<f64> {
+        match self {
+            Self::Allow => None,
+            Self::Reject { similarity_threshold } | Self::Flag { similarity_threshold } => *similarity_threshold,
+        }
+    }
+
+    /// Check that the similarity threshold, if any, lies between 0 and 1
+    pub fn validate(&self) -> DomainResult<()> {
+        match self.similarity_threshold() {
+            Some(threshold) if !(0.0..=1.0).contains(&threshold) => Err(DomainError::InvalidOperation(
+                format!("Similarity threshold must be between 0 and 1, got {}", threshold)
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A document found to duplicate one already in a corpus
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Duplicate {
+    /// The document being added
+    document_id: DocumentId,
+
+    /// The document already in the corpus
+    existing_id: DocumentId,
+
+    /// Jaccard similarity of the two term sets (1.0 for identical content)
+    similarity: f64,
+
+    /// Whether the two contents are identical
+    exact: bool,
+}
+
+impl Duplicate {
+    /// Get the ID of the document being added
+    pub fn document_id(&self) -> &DocumentId {
+        &self.document_id
+    }
+
+    /// Get the ID of the document already in the corpus
+    pub fn existing_id(&self) -> &DocumentId {
+        &self.existing_id
+    }
+
+    /// Get the Jaccard similarity of the two term sets
+    pub fn similarity(&self) -> f64 {
+        self.similarity
+    }
+
+    /// Check if the two contents are identical
+    pub fn is_exact(&self) -> bool {
+        self.exact
+    }
+}
+
+impl fmt::Display for Duplicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.exact {
+            write!(f, "Document '{}' has the same content as '{}'", self.document_id.value(), self.existing_id.value())
+        } else {
+            write!(
+                f,
+                "Document '{}' is a near-duplicate of '{}' (similarity {:.3})",
+                self.document_id.value(), self.existing_id.value(), self.similarity
+            )
+        }
+    }
+}
+
+impl Duplicate {
+    /// Record a document with the same content as an existing one
+    pub(super) fn exact(document_id: DocumentId, existing_id: DocumentId) -> Self {
+        Self { document_id, existing_id, similarity: 1.0, exact: true }
+    }
+}
+
+/// Find the candidate whose term set is most similar to the document's, if it reaches the threshold
+///
+/// Ties go to the smallest ID; documents without terms have no near-duplicates.
+fn find_near_duplicate<'a>(
+    document: &Document,
+    candidates: impl IntoIterator<Item = &'a Document>,
+    threshold: f64,
+) -> Option<Duplicate> {
+    if document.term_count() == 0 {
+        return None;
+    }
+
+    let mut best: Option<Duplicate> = None;
+    for candidate in candidates {
+        if candidate.id() == document.id() {
+            continue;
+        }
+
+        let similarity = jaccard(document, candidate);
+        let better = best.as_ref().is_none_or(|best| {
+            similarity > best.similarity || (similarity == best.similarity && candidate.id().value() < best.existing_id.value())
+        });
+        if similarity >= threshold && better {
+            best = Some(Duplicate { document_id: document.id().clone(), existing_id: candidate.id().clone(), similarity, exact: false });
+        }
+    }
+    best
+}
+
+/// Lookup structures for checking documents added to a corpus against its duplicate policy
+///
+/// Exact duplicates are found through a hash of the content. Near-duplicates
+/// are found through the LSH bands of a default `MinHasher`, built on the
+/// first similarity check, and confirmed with the exact Jaccard similarity.
+#[derive(Debug, Clone, Default)]
+pub(super) struct DuplicateIndex {
+    /// IDs of the documents with each content hash
+    content_hashes: HashMap<u64, Vec<DocumentId>>,
+
+    /// IDs of the documents in each bucket of each band (None = not built yet)
+    bands: Option<HashMap<(usize, u64), Vec<DocumentId>>>,
+}
+
+impl DuplicateIndex {
+    /// Index the given documents by content hash
+    pub(super) fn new<'a>(documents: impl IntoIterator<Item = &'a Document>) -> Self {
+        let mut index = Self::default();
+        for document in documents {
+            index.insert(document);
+        }
+        index
+    }
+
+    /// Add a document to the index
+    pub(super) fn insert(&mut self, document: &Document) {
+        self.content_hashes.entry(fnv1a(document.content())).or_default().push(document.id().clone());
+        if let Some(bands) = self.bands.as_mut() {
+            for key in band_keys(document) {
+                bands.entry(key).or_default().push(document.id().clone());
+            }
+        }
+    }
+
+    /// Remove a document from the index
+    pub(super) fn remove(&mut self, document: &Document) {
+        if let Some(ids) = self.content_hashes.get_mut(&fnv1a(document.content())) {
+            ids.retain(|id| id != document.id());
+        }
+        if let Some(bands) = self.bands.as_mut() {
+            for key in band_keys(document) {
+                if let Some(ids) = bands.get_mut(&key) {
+                    ids.retain(|id| id != document.id());
+                }
+            }
+        }
+    }
+
+    /// Find a document duplicating the given one: one with identical content, else the most similar reaching the threshold
+    ///
+    /// A document with the same ID is never its own duplicate. Fails if a
+    /// document to compare against cannot be looked up.
+    pub(super) fn find(
+        &mut self,
+        document: &Document,
+        documents: &Members,
+        similarity_threshold: Option<f64>,
+    ) -> DomainResult<Option<Duplicate>> {
+        let mut exact: Option<&DocumentId> = None;
+        for id in self.content_hashes.get(&fnv1a(document.content())).into_iter().flatten() {
+            if id == document.id() || exact.is_some_and(|current| current.value() <= id.value()) {
+                continue;
+            }
+            if documents.get(id)?.is_some_and(|existing| existing.content() == document.content()) {
+                exact = Some(id);
+            }
+        }
+        if let Some(existing_id) = exact {
+            return Ok(Some(Duplicate::exact(document.id().clone(), existing_id.clone())));
+        }
+
+        let Some(threshold) = similarity_threshold else {
+            return Ok(None);
+        };
+        let bands = match self.bands.as_mut() {
+            Some(bands) => bands,
+            None => {
+                let mut bands: HashMap<(usize, u64), Vec<DocumentId>> = HashMap::new();
+                for candidate in documents.iter() {
+                    let candidate = candidate?;
+                    for key in band_keys(candidate) {
+                        bands.entry(key).or_default().push(candidate.id().clone());
+                    }
+                }
+                self.bands.insert(bands)
+            },
+        };
+
+        let candidates: HashSet<&DocumentId> = band_keys(document).into_iter()
+            .filter_map(|key| bands.get(&key))
+            .flatten()
+            .collect();
+        let candidates = candidates.into_iter()
+            .filter_map(|id| documents.get(id).transpose())
+            .collect::<DomainResult<Vec<_>>>()?;
+        Ok(find_near_duplicate(document, candidates.into_iter().map(Arc::as_ref), threshold))
+    }
+}
+
+/// Hash each LSH band of a document's signature, keyed by band number (none for documents without terms)
+fn band_keys(document: &Document) -> Vec<(usize, u64)> {
+    if document.term_count() == 0 {
+        return Vec::new();
+    }
+
+    let hasher = MinHasher::default();
+    let rows = hasher.num_hashes / hasher.bands;
+    hasher.signature(document).0
+        .chunks(rows)
+        .map(|values| values.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &value| mix(hash ^ value)))
+        .enumerate()
+        .collect()
+}
+
+/// Finds near-duplicate documents with MinHash signatures and locality-sensitive hashing
+///
+/// Signatures are split into bands; only documents agreeing on every value of
+/// at least one band are compared, so a corpus is not checked pair by pair.
+/// Candidates are then confirmed with the exact Jaccard similarity of their
+/// term sets. With the default 128 hashes in 32 bands, pairs above a
+/// similarity of about 0.5 are found with high probability; use more bands
+/// to catch lower thresholds reliably, fewer to compare fewer candidates.
+#[derive(Debug, Clone)]
+pub struct MinHasher {
+    num_hashes: usize,
+    bands: usize,
+}
+
+impl Default for MinHasher {
+    fn default() -> Self {
+        Self::new(128, 32)
+    }
+}
+
+impl MinHasher {
+    /// Create a hasher with the given signature length and number of LSH bands
+    ///
+    /// The band count is clamped to divide the signature length evenly.
+    pub fn new(num_hashes: usize, bands: usize) -> Self {
+        let num_hashes = num_hashes.max(1);
+        let bands = (1..=bands.clamp(1, num_hashes)).rev().find(|b| num_hashes.is_multiple_of(*b)).unwrap_or(1);
+        Self { num_hashes, bands }
+    }
+
+    /// Get the signature length
+    pub fn num_hashes(&self) -> usize {
+        self.num_hashes
+    }
+
+    /// Get the number of LSH bands
+    pub fn bands(&self) -> usize {
+        self.bands
+    }
+
+    /// Compute the signature of a document's content terms
+    pub fn signature(&self, document: &Document) -> MinHashSignature {
+        let term_hashes: Vec<u64> = document.term_frequencies().keys().map(|term| fnv1a(term.text())).collect();
+
+        MinHashSignature(
+            (0..self.num_hashes as u64)
+                .map(|seed| {
+                    term_hashes.iter().map(|&hash| mix(hash ^ seed.wrapping_mul(0x9E37_79B9_7F4A_7C15))).min().unwrap_or(u64::MAX)
+                })
+                .collect()
+        )
+    }
+
+    /// Find the pairs of documents whose term sets have a Jaccard similarity of at least `threshold`
+    ///
+    /// Pairs are ordered by descending similarity, then by ID. Documents without
+    /// terms are skipped; documents that cannot be looked up fail the search.
+    pub fn find_near_duplicates(&self, corpus: &Corpus, threshold: f64) -> DomainResult<Vec<NearDuplicate>> {
+        let mut documents: Vec<&Document> = corpus.documents().collect::<DomainResult<_>>()?;
+        documents.retain(|d| d.term_count() > 0);
+        let rows = self.num_hashes / self.bands;
+
+        let mut candidates = BTreeSet::new();
+        let mut buckets: HashMap<(usize, &[u64]), Vec<usize>> = HashMap::new();
+        let signatures: Vec<MinHashSignature> = documents.iter().map(|d| self.signature(d)).collect();
+        for (index, signature) in signatures.iter().enumerate() {
+            for (band, values) in signature.0.chunks(rows).enumerate() {
+                let bucket = buckets.entry((band, values)).or_default();
+                for &other in bucket.iter() {
+                    candidates.insert((other, index));
+                }
+                bucket.push(index);
+            }
+        }
+
+        let mut duplicates: Vec<NearDuplicate> = candidates.into_iter()
+            .filter_map(|(a, b)| {
+                let similarity = jaccard(documents[a], documents[b]);
+                if similarity < threshold {
+                    return None;
+                }
+                let (first, second) = if documents[a].id().value() <= documents[b].id().value() {
+                    (documents[a].id().clone(), documents[b].id().clone())
+                } else {
+                    (documents[b].id().clone(), documents[a].id().clone())
+                };
+                Some(NearDuplicate { first, second, similarity })
+            })
+            .collect();
+
+        duplicates.sort_by(|a, b| {
+            b.similarity.partial_cmp(&a.similarity).unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.first.value().cmp(b.first.value()))
+                .then_with(|| a.second.value().cmp(b.second.value()))
+        });
+        Ok(duplicates)
+    }
+
+    /// Get the documents to drop so that no near-duplicate pair remains, keeping the smallest ID of each group
+    pub fn redundant_documents(&self, corpus: &Corpus, threshold: f64) -> DomainResult<Vec<DocumentId>> {
+        let mut pairs = self.find_near_duplicates(corpus, threshold)?;
+        pairs.sort_by(|a, b| a.first.value().cmp(b.first.value()).then_with(|| a.second.value().cmp(b.second.value())));
+
+        let mut dropped: HashSet<DocumentId> = HashSet::new();
+        for pair in pairs {
+            if !dropped.contains(&pair.first) {
+                dropped.insert(pair.second);
+            }
+        }
+
+        let mut dropped: Vec<DocumentId> = dropped.into_iter().collect();
+        dropped.sort_by(|a, b| a.value().cmp(b.value()));
+        Ok(dropped)
+    }
+}
+
+/// Exact Jaccard similarity of two documents' content term sets
+fn jaccard(a: &Document, b: &Document) -> f64 {
+    let a: HashSet<&str> = a.term_frequencies().keys().map(|term| term.text()).collect();
+    let b: HashSet<&str> = b.term_frequencies().keys().map(|term| term.text()).collect();
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f64 / union as f64
+}
+
+/// 64-bit FNV-1a hash, stable across runs and platforms
+pub(crate) fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3))
+}
+
+/// SplitMix64 finalizer, turning one term hash into an independent-looking hash per seed
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Term;
+
+    fn document(id: &str, text: &str) -> Document {
+        let mut document = Document::new(id, text);
+        document.add_terms(text.split(' ').map(Term::new));
+        document
+    }
+
+    #[test]
+    fn test_signature_similarity() {
+        let hasher = MinHasher::default();
+        let a = hasher.signature(&document("a", "one two three four five six seven eight nine ten"));
+        let b = hasher.signature(&document("b", "one two three four five six seven eight nine eleven"));
+        let c = hasher.signature(&document("c", "alpha beta gamma delta"));
+
+        assert_eq!(a.similarity(&a), 1.0);
+        assert!((a.similarity(&b) - 9.0 / 11.0).abs() < 0.15);
+        assert!(a.similarity(&c) < 0.1);
+    }
+
+    #[test]
+    fn test_find_near_duplicates() {
+        let mut corpus = Corpus::new("corpus1", "Scraped");
+        let page = "breaking news rust release adds faster compile times and better error messages today";
+        corpus.add_document(document("page1", page)).unwrap();
+        corpus.add_document(document("page2", &format!("{} updated", page))).unwrap();
+        corpus.add_document(document("page3", page)).unwrap();
+        corpus.add_document(document("other", "recipe for pancakes with maple syrup")).unwrap();
+
+        let duplicates = MinHasher::default().find_near_duplicates(&corpus, 0.8).unwrap();
+        assert_eq!(duplicates.len(), 3);
+        assert_eq!(duplicates[0].first().value(), "page1");
+        assert_eq!(duplicates[0].second().value(), "page3");
+        assert_eq!(duplicates[0].similarity(), 1.0);
+
+        let redundant = MinHasher::default().redundant_documents(&corpus, 0.8).unwrap();
+        assert_eq!(redundant, vec![DocumentId::new("page2"), DocumentId::new("page3")]);
+    }
+}
@@ -1,8 +1,8 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 use serde::{Deserialize, Serialize};
 
-use super::term::{Term, TermFrequency};
+use super::term::{named_term_maps, term_map, Term, TermFrequency};
 
 /// Unique identifier for a document
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -20,6 +20,24 @@ impl DocumentId {
     }
 }
 
+/// Which details a document records about each occurrence of a content term
+///
+/// Positions drive phrase queries and co-occurrence windows; offsets locate each
+/// occurrence in the analyzed text for highlighting. Without positions, phrase
+/// queries never match.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PositionTracking {
+    /// Record term frequencies only
+    None,
+
+    /// Record the token position of every occurrence
+    #[default]
+    Positions,
+
+    /// Record token positions and byte offsets of every occurrence
+    PositionsAndOffsets,
+}
+
 /// Document represents a text document in the system
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Document {
@@ -29,16 +47,66 @@ pub struct Document {
 
     title: Option<String>,
 
+    /// Named text fields analyzed separately from the content, e.g. abstract, tags or author
+    #[serde(default)]
+    fields: HashMap<String, String>,
+
      /// Map of terms to their frequencies in this document
+    #[serde(with = "term_map")]
     term_frequencies: HashMap<Term, TermFrequency>,
 
      /// Total number of terms in the document (for normalization)
     term_count: usize,
 
-    metadata: HashMap<String, String>
+    /// Frequencies of each term's canonical form (its stem, or its text when unstemmed)
+    #[serde(default)]
+    canonical_frequencies: HashMap<String, TermFrequency>,
+
+    /// Highest frequency of any content term (0 when unknown, e.g. stored before it was tracked)
+    #[serde(default)]
+    max_term_frequency: usize,
+
+    /// Highest frequency of any canonical form, tracked like `max_term_frequency`
+    #[serde(default)]
+    max_canonical_frequency: usize,
+
+    /// Positions of each term's occurrences in the token stream, in ascending order
+    #[serde(default, with = "term_map")]
+    term_positions: HashMap<Term, Vec<usize>>,
+
+    /// Byte offsets (start, end) of each term's occurrences, in the same order as its positions
+    #[serde(default, with = "term_map")]
+    term_offsets: HashMap<Term, Vec<(usize, usize)>>,
+
+    /// Which occurrence details are recorded for content terms
+    #[serde(default)]
+    position_tracking: PositionTracking,
+
+    /// Term frequencies of fields analyzed separately from the content, keyed by field name
+    #[serde(default, with = "named_term_maps")]
+    field_term_frequencies: HashMap<String, HashMap<Term, TermFrequency>>,
+
+    metadata: HashMap<String, String>,
+
+    /// Whether the "lang" metadata was set by language detection rather than tagged by the user
+    #[serde(default)]
+    language_detected: bool,
+
+    /// Incremented whenever the terms are re-analyzed or content is appended, so copies can detect staleness
+    #[serde(default)]
+    revision: u64
 }
 
 impl Document {
+    /// Name of the field holding the analyzed title
+    pub const TITLE_FIELD: &'static str = "title";
+
+    /// Name of the content, for targeting it alongside other fields
+    pub const CONTENT_FIELD: &'static str = "content";
+
+    /// Metadata key holding the document's language, e.g. "en", tagged by the user or detected
+    pub const LANG_METADATA: &'static str = "lang";
+
     pub fn new(
         id: impl Into<String>,
         content: impl Into<String>
@@ -47,9 +115,19 @@ impl Document {
             id: DocumentId(id.into()),
             content: content.into(),
             title: None,
+            fields: HashMap::new(),
             term_frequencies: HashMap::new(),
             term_count: 0,
-            metadata: HashMap::new()
+            canonical_frequencies: HashMap::new(),
+            max_term_frequency: 0,
+            max_canonical_frequency: 0,
+            term_positions: HashMap::new(),
+            term_offsets: HashMap::new(),
+            position_tracking: PositionTracking::default(),
+            field_term_frequencies: HashMap::new(),
+            metadata: HashMap::new(),
+            language_detected: false,
+            revision: 0
         }
     }
 
@@ -73,6 +151,27 @@ impl Document {
         &self.content
     }
     
+    /// Replace the content; its terms are stale until the document is analyzed again
+    pub fn set_content(&mut self, content: impl Into<String>) {
+        self.content = content.into();
+    }
+
+    /// Append text to the content, keeping the terms analyzed so far
+    ///
+    /// Returns the byte offset at which the appended text starts; its terms are
+    /// added with `add_term` as usual.
+    pub fn append_content(&mut self, text: &str) -> usize {
+        let start = self.content.len();
+        self.content.push_str(text);
+        self.revision += 1;
+        start
+    }
+
+    /// Get the document revision, incremented whenever its terms are cleared for re-analysis or content is appended
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+    
     /// Get the document title, if available
     pub fn title(&self) -> Option<&str> {
         self.title.as_deref()
@@ -83,6 +182,26 @@ impl Document {
         self.title = Some(title.into());
     }
     
+    /// Get the text of a named field
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields.get(name).map(String::as_str)
+    }
+
+    /// Get all named fields
+    pub fn fields(&self) -> &HashMap<String, String> {
+        &self.fields
+    }
+
+    /// Set the text of a named field
+    pub fn set_field(&mut self, name: impl Into<String>, text: impl Into<String>) {
+        self.fields.insert(name.into(), text.into());
+    }
+
+    /// Remove a named field, returning its text
+    pub fn remove_field(&mut self, name: &str) -> Option<String> {
+        self.fields.remove(name)
+    }
+    
     /// Get the term frequencies for this document
     pub fn term_frequencies(&self) -> &HashMap<Term, TermFrequency> {
         &self.term_frequencies
@@ -93,12 +212,114 @@ impl Document {
         &mut self.term_frequencies
     }
 
+    /// Add the next term of the token stream, at position `term_count()`
     pub fn add_term(&mut self, term: Term) {
-        let count = self.term_frequencies.entry(term).or_insert(TermFrequency(0));
+        let position = self.term_count;
+        self.add_term_at(term, position);
+    }
+
+    /// Add the next term of the token stream together with its byte offsets in the analyzed text
+    ///
+    /// The offsets are only kept when tracking `PositionsAndOffsets`.
+    pub fn add_term_with_offsets(&mut self, term: Term, start: usize, end: usize) {
+        self.add_term_ref_with_offsets(&term, start, end);
+    }
+
+    /// Add the next term of the token stream, copying the term only the first time the document sees it
+    pub fn add_term_ref(&mut self, term: &Term) {
+        let position = self.term_count;
+        self.record_term(term, position);
+    }
+
+    /// Add the next term of the token stream with its byte offsets, copying the term only the first time
+    pub fn add_term_ref_with_offsets(&mut self, term: &Term, start: usize, end: usize) {
+        if self.position_tracking == PositionTracking::PositionsAndOffsets {
+            match self.term_offsets.get_mut(term) {
+                Some(offsets) => offsets.push((start, end)),
+                None => { self.term_offsets.insert(term.clone(), vec![(start, end)]); },
+            }
+        }
+        self.add_term_ref(term);
+    }
+
+    /// Add a term occurrence at an explicit token position
+    pub fn add_term_at(&mut self, term: Term, position: usize) {
+        self.record_term(&term, position);
+    }
+
+    /// Count a term occurrence at a token position, cloning keys only for terms new to the document
+    fn record_term(&mut self, term: &Term, position: usize) {
+        if self.position_tracking != PositionTracking::None {
+            match self.term_positions.get_mut(term) {
+                Some(positions) => {
+                    let index = positions.partition_point(|&p| p < position);
+                    positions.insert(index, position);
+                },
+                None => { self.term_positions.insert(term.clone(), vec![position]); },
+            }
+        }
+
+        let canonical = match self.canonical_frequencies.get_mut(term.canonical()) {
+            Some(canonical) => canonical,
+            None => self.canonical_frequencies.entry(term.canonical().to_string()).or_insert(TermFrequency(0)),
+        };
+        canonical.increment();
+        self.max_canonical_frequency = self.max_canonical_frequency.max(canonical.value());
+
+        let count = match self.term_frequencies.get_mut(term) {
+            Some(count) => count,
+            None => self.term_frequencies.entry(term.clone()).or_insert(TermFrequency(0)),
+        };
         count.0 += 1;
+        self.max_term_frequency = self.max_term_frequency.max(count.0);
         self.term_count += 1;
     }
 
+    /// Remove the last occurrence of a content term, e.g. to analyze the end of the content again
+    ///
+    /// Returns false if the content does not contain the term.
+    pub fn remove_last_term(&mut self, term: &Term) -> bool {
+        let Some(count) = self.term_frequencies.get_mut(term) else {
+            return false;
+        };
+        let was_max = count.0 == self.max_term_frequency;
+        count.0 -= 1;
+        if count.0 == 0 {
+            self.term_frequencies.remove(term);
+        }
+
+        let mut was_max_canonical = false;
+        if let Some(canonical) = self.canonical_frequencies.get_mut(term.canonical()) {
+            was_max_canonical = canonical.0 == self.max_canonical_frequency;
+            canonical.0 -= 1;
+            if canonical.0 == 0 {
+                self.canonical_frequencies.remove(term.canonical());
+            }
+        }
+
+        if let Some(positions) = self.term_positions.get_mut(term) {
+            positions.pop();
+            if positions.is_empty() {
+                self.term_positions.remove(term);
+            }
+        }
+        if let Some(offsets) = self.term_offsets.get_mut(term) {
+            offsets.pop();
+            if offsets.is_empty() {
+                self.term_offsets.remove(term);
+            }
+        }
+
+        self.term_count -= 1;
+        if was_max {
+            self.max_term_frequency = self.term_frequencies.values().map(TermFrequency::value).max().unwrap_or(0);
+        }
+        if was_max_canonical {
+            self.max_canonical_frequency = self.canonical_frequencies.values().map(TermFrequency::value).max().unwrap_or(0);
+        }
+        true
+    }
+
     pub fn add_terms(&mut self, terms: impl IntoIterator<Item = Term>) {
         for term in terms {
             self.add_term(term);
@@ -112,6 +333,178 @@ impl Document {
         .unwrap_or(TermFrequency(0))
     }
 
+    /// Get the frequencies of the content's canonical term forms
+    pub fn canonical_frequencies(&self) -> &HashMap<String, TermFrequency> {
+        &self.canonical_frequencies
+    }
+
+    /// Get the combined frequency of every content term with the given canonical form
+    pub fn canonical_term_frequency(&self, canonical: &str) -> TermFrequency {
+        self.canonical_frequencies
+            .get(canonical)
+            .copied()
+            .unwrap_or(TermFrequency(0))
+    }
+
+    /// Get the highest frequency of any content term, e.g. for augmented term frequency
+    pub fn max_term_frequency(&self) -> TermFrequency {
+        if self.max_term_frequency == 0 {
+            return TermFrequency(self.term_frequencies.values().map(TermFrequency::value).max().unwrap_or(0));
+        }
+        TermFrequency(self.max_term_frequency)
+    }
+
+    /// Get the highest combined frequency of any canonical term form
+    pub fn max_canonical_term_frequency(&self) -> TermFrequency {
+        if self.max_canonical_frequency == 0 {
+            return TermFrequency(self.canonical_frequencies.values().map(TermFrequency::value).max().unwrap_or(0));
+        }
+        TermFrequency(self.max_canonical_frequency)
+    }
+
+    /// Get the token positions at which a term occurs
+    pub fn term_positions(&self, term: &Term) -> &[usize] {
+        self.term_positions.get(term).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Get the byte offsets of a term's occurrences in the analyzed text
+    pub fn term_offsets(&self, term: &Term) -> &[(usize, usize)] {
+        self.term_offsets.get(term).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Get which occurrence details are recorded for content terms
+    pub fn position_tracking(&self) -> PositionTracking {
+        self.position_tracking
+    }
+
+    /// Choose which occurrence details are recorded for terms added from now on
+    ///
+    /// Details the new setting no longer tracks are dropped right away to free memory.
+    pub fn set_position_tracking(&mut self, position_tracking: PositionTracking) {
+        self.position_tracking = position_tracking;
+        match position_tracking {
+            PositionTracking::None => {
+                self.term_positions.clear();
+                self.term_offsets.clear();
+            },
+            PositionTracking::Positions => self.term_offsets.clear(),
+            PositionTracking::PositionsAndOffsets => {}
+        }
+    }
+
+    /// Get the content terms in token stream order, with their positions
+    pub fn term_sequence(&self) -> Vec<(usize, &Term)> {
+        let mut sequence: Vec<(usize, &Term)> = self.term_positions
+            .iter()
+            .flat_map(|(term, positions)| positions.iter().map(move |&position| (position, term)))
+            .collect();
+        sequence.sort_by_key(|(position, _)| *position);
+        sequence
+    }
+
+    /// Count the occurrences of a phrase: the terms at consecutive positions
+    pub fn phrase_frequency(&self, phrase: &[Term]) -> usize {
+        let Some((first, rest)) = phrase.split_first() else {
+            return 0;
+        };
+
+        self.term_positions(first)
+            .iter()
+            .filter(|&&start| {
+                rest.iter().enumerate().all(|(offset, term)| {
+                    self.term_positions(term).binary_search(&(start + offset + 1)).is_ok()
+                })
+            })
+            .count()
+    }
+
+    /// Check whether the document contains a phrase
+    pub fn contains_phrase(&self, phrase: &[Term]) -> bool {
+        self.phrase_frequency(phrase) > 0
+    }
+
+    /// Get the sorted token positions of every content term with the given canonical form
+    pub fn canonical_positions(&self, canonical: &str) -> Vec<usize> {
+        let mut positions: Vec<usize> = self.term_positions
+            .iter()
+            .filter(|(term, _)| term.canonical() == canonical)
+            .flat_map(|(_, positions)| positions.iter().copied())
+            .collect();
+        positions.sort_unstable();
+        positions
+    }
+
+    /// Count the occurrences of a phrase, matching each word by its canonical form
+    pub fn canonical_phrase_frequency(&self, phrase: &[Term]) -> usize {
+        let Some((first, rest)) = phrase.split_first() else {
+            return 0;
+        };
+        let rest: Vec<Vec<usize>> = rest.iter().map(|term| self.canonical_positions(term.canonical())).collect();
+
+        self.canonical_positions(first.canonical())
+            .iter()
+            .filter(|&&start| {
+                rest.iter().enumerate().all(|(offset, positions)| {
+                    positions.binary_search(&(start + offset + 1)).is_ok()
+                })
+            })
+            .count()
+    }
+
+    /// Check whether the document contains a phrase, matching each word by its canonical form
+    pub fn contains_canonical_phrase(&self, phrase: &[Term]) -> bool {
+        self.canonical_phrase_frequency(phrase) > 0
+    }
+
+    /// Add a term occurrence to a field other than the content
+    pub fn add_field_term(&mut self, field: &str, term: Term) {
+        let frequencies = self.field_term_frequencies.entry(field.to_string()).or_default();
+        frequencies.entry(term).or_insert(TermFrequency(0)).increment();
+    }
+
+    /// Get the term frequencies of a field, if it has any terms
+    pub fn field_term_frequencies(&self, field: &str) -> Option<&HashMap<Term, TermFrequency>> {
+        self.field_term_frequencies.get(field)
+    }
+
+    /// Get the frequency of a term in a field
+    pub fn field_term_frequency(&self, field: &str, term: &Term) -> TermFrequency {
+        self.field_term_frequencies
+            .get(field)
+            .and_then(|frequencies| frequencies.get(term))
+            .copied()
+            .unwrap_or(TermFrequency(0))
+    }
+
+    /// Get the names of the fields that have terms
+    pub fn field_names(&self) -> impl Iterator<Item = &str> {
+        self.field_term_frequencies.keys().map(String::as_str)
+    }
+
+    /// Check whether a term occurs in the content or in any field
+    pub fn contains_term(&self, term: &Term) -> bool {
+        self.term_frequencies.contains_key(term)
+            || self.field_term_frequencies.values().any(|frequencies| frequencies.contains_key(term))
+    }
+
+    /// Check whether any term with the given canonical form occurs in the content or in any field
+    pub fn contains_canonical(&self, canonical: &str) -> bool {
+        self.canonical_frequencies.contains_key(canonical)
+            || self.field_term_frequencies.values().any(|frequencies| frequencies.keys().any(|term| term.canonical() == canonical))
+    }
+
+    /// Get every distinct term of the content, without the terms found only in fields
+    pub fn content_terms(&self) -> impl Iterator<Item = &Term> {
+        self.term_frequencies.keys()
+    }
+
+    /// Get every distinct term of the content and the fields
+    pub fn unique_terms(&self) -> HashSet<&Term> {
+        self.term_frequencies.keys()
+            .chain(self.field_term_frequencies.values().flat_map(|frequencies| frequencies.keys()))
+            .collect()
+    }
+
      /// Get the total number of terms in the document
     pub fn term_count(&self) -> usize {
         self.term_count
@@ -128,8 +521,44 @@ impl Document {
     }
     
     /// Set a metadata field
+    ///
+    /// Setting "lang" tags the document's language, which detection then leaves alone.
     pub fn set_metadata(&mut self, key: impl Into<String>, value: impl Into<String>) {
-        self.metadata.insert(key.into(), value.into());
+        let key = key.into();
+        if key == Self::LANG_METADATA {
+            self.language_detected = false;
+        }
+        self.metadata.insert(key, value.into());
+    }
+    
+    /// Get the document's language from the "lang" metadata
+    pub fn language(&self) -> Option<&str> {
+        self.metadata.get(Self::LANG_METADATA).map(String::as_str)
+    }
+    
+    /// Check whether the language was detected rather than tagged
+    pub fn is_language_detected(&self) -> bool {
+        self.language_detected && self.metadata.contains_key(Self::LANG_METADATA)
+    }
+    
+    /// Record the language detected from the content (None = not detected)
+    ///
+    /// A language tagged by the user is kept; a previously detected one is
+    /// replaced, or removed when detection failed.
+    pub fn set_detected_language(&mut self, language: Option<String>) {
+        if self.metadata.contains_key(Self::LANG_METADATA) && !self.language_detected {
+            return;
+        }
+        match language {
+            Some(language) => {
+                self.metadata.insert(Self::LANG_METADATA.to_string(), language);
+                self.language_detected = true;
+            },
+            None => {
+                self.metadata.remove(Self::LANG_METADATA);
+                self.language_detected = false;
+            }
+        }
     }
     
     pub fn normalized_term_frequency(&self, term: &Term) -> f64 {
@@ -144,7 +573,14 @@ impl Document {
      /// Clear all term frequencies (e.g., before reprocessing)
     pub fn clear_terms(&mut self) {
         self.term_frequencies.clear();
+        self.canonical_frequencies.clear();
+        self.term_positions.clear();
+        self.term_offsets.clear();
+        self.field_term_frequencies.clear();
         self.term_count = 0;
+        self.max_term_frequency = 0;
+        self.max_canonical_frequency = 0;
+        self.revision += 1;
     }
 }
 
@@ -190,4 +626,108 @@ mod tests {
         let normalized_freq = doc.normalized_term_frequency(&Term::new("this"));
         assert!((normalized_freq - 0.4).abs() < f64::EPSILON);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_canonical_frequencies() {
+        let mut doc = Document::new("doc1", "runs running ran");
+        doc.add_term(Term::with_stem("runs", "run"));
+        doc.add_term(Term::with_stem("running", "run"));
+        doc.add_term(Term::new("ran"));
+
+        assert_eq!(doc.term_frequency(&Term::new("runs")), TermFrequency(1));
+        assert_eq!(doc.canonical_term_frequency("run"), TermFrequency(2));
+        assert_eq!(doc.canonical_term_frequency("ran"), TermFrequency(1));
+        assert_eq!(doc.max_term_frequency(), TermFrequency(1));
+        assert_eq!(doc.max_canonical_term_frequency(), TermFrequency(2));
+
+        doc.clear_terms();
+        assert_eq!(doc.max_term_frequency(), TermFrequency(0));
+    }
+
+    #[test]
+    fn test_canonical_phrases() {
+        let mut doc = Document::new("doc1", "running shoes and runs");
+        doc.add_terms([
+            Term::with_stem("running", "run"),
+            Term::with_stem("shoes", "shoe"),
+            Term::new("and"),
+            Term::with_stem("runs", "run"),
+        ]);
+
+        let phrase = [Term::with_stem("run", "run"), Term::with_stem("shoe", "shoe")];
+        assert!(!doc.contains_phrase(&phrase));
+        assert_eq!(doc.canonical_phrase_frequency(&phrase), 1);
+        assert_eq!(doc.canonical_positions("run"), vec![0, 3]);
+        assert!(doc.contains_canonical("run"));
+        assert!(!doc.contains_term(&Term::new("run")));
+    }
+
+    #[test]
+    fn test_phrases() {
+        let mut doc = Document::new("doc1", "machine learning and learning machines");
+        doc.add_terms(["machine", "learning", "and", "learning", "machines"].map(Term::new));
+
+        assert_eq!(doc.term_positions(&Term::new("learning")), &[1, 3]);
+
+        let phrase = [Term::new("machine"), Term::new("learning")];
+        assert!(doc.contains_phrase(&phrase));
+        assert_eq!(doc.phrase_frequency(&phrase), 1);
+
+        let reversed = [Term::new("learning"), Term::new("machine")];
+        assert!(!doc.contains_phrase(&reversed));
+        assert!(!doc.contains_phrase(&[]));
+    }
+
+    #[test]
+    fn test_field_terms() {
+        let mut doc = Document::with_title("doc1", "Rust Guide", "learning rust");
+        doc.add_terms(["learning", "rust"].map(Term::new));
+        doc.add_field_term(Document::TITLE_FIELD, Term::new("rust"));
+        doc.add_field_term(Document::TITLE_FIELD, Term::new("guide"));
+
+        // Field terms are tracked apart from the content
+        assert_eq!(doc.term_count(), 2);
+        assert_eq!(doc.term_frequency(&Term::new("guide")), TermFrequency(0));
+        assert_eq!(doc.field_term_frequency(Document::TITLE_FIELD, &Term::new("guide")), TermFrequency(1));
+        assert!(doc.contains_term(&Term::new("guide")));
+        assert_eq!(doc.unique_terms().len(), 3);
+        assert_eq!(doc.content_terms().count(), 2);
+
+        doc.clear_terms();
+        assert!(doc.field_term_frequencies(Document::TITLE_FIELD).is_none());
+    }
+
+    #[test]
+    fn test_named_fields() {
+        let mut doc = Document::new("doc1", "body text");
+        doc.set_field("author", "Ada Lovelace");
+        doc.set_field("tags", "math engines");
+
+        assert_eq!(doc.field("author"), Some("Ada Lovelace"));
+        assert_eq!(doc.fields().len(), 2);
+        assert_eq!(doc.remove_field("tags").as_deref(), Some("math engines"));
+        assert_eq!(doc.field("tags"), None);
+    }
+
+    #[test]
+    fn test_position_tracking() {
+        let mut doc = Document::new("doc1", "new york");
+        doc.set_position_tracking(PositionTracking::PositionsAndOffsets);
+        doc.add_term_with_offsets(Term::new("new"), 0, 3);
+        doc.add_term_with_offsets(Term::new("york"), 4, 8);
+
+        assert_eq!(doc.term_positions(&Term::new("york")), &[1]);
+        assert_eq!(doc.term_offsets(&Term::new("york")), &[(4, 8)]);
+
+        // Lowering the tracking level drops what is no longer tracked
+        doc.set_position_tracking(PositionTracking::Positions);
+        assert!(doc.term_offsets(&Term::new("york")).is_empty());
+        assert!(doc.contains_phrase(&[Term::new("new"), Term::new("york")]));
+
+        let mut doc = Document::new("doc2", "new york");
+        doc.set_position_tracking(PositionTracking::None);
+        doc.add_terms(["new", "york"].map(Term::new));
+        assert_eq!(doc.term_frequency(&Term::new("new")), TermFrequency(1));
+        assert!(doc.term_positions(&Term::new("new")).is_empty());
+    }
+}
@@ -0,0 +1,59 @@
+// src/domain/embedding.rs
+
+use super::{DomainError, DomainResult};
+
+/// Turns text into a dense vector, e.g. by calling out to an external embedding model
+///
+/// The crate ships no implementation, so it never depends on a model runtime;
+/// wrap an ONNX session, an HTTP embedding service or similar in this trait.
+/// Every vector an embedder returns should have the same dimension.
+pub trait DocumentEmbedder: Send + Sync {
+    /// Embed one text
+    fn embed(&self, text: &str) -> DomainResult<Vec<f32>>;
+
+    /// Embed several texts, in order
+    ///
+    /// Override this when the model is faster on batches.
+    fn embed_batch(&self, texts: &[&str]) -> DomainResult<Vec<Vec<f32>>> {
+        texts.iter().map(|text| self.embed(text)).collect()
+    }
+}
+
+/// Calculate the cosine similarity between two dense vectors
+///
+/// Vectors of different dimensions, e.g. from different models, cannot be compared.
+pub fn dense_cosine_similarity(vec1: &[f32], vec2: &[f32]) -> DomainResult<f64> {
+    if vec1.len() != vec2.len() {
+        return Err(DomainError::InvalidOperation(format!(
+            "Cannot compare dense vectors of dimensions {} and {}", vec1.len(), vec2.len()
+        )));
+    }
+
+    let (mut dot_product, mut norm1, mut norm2) = (0.0, 0.0, 0.0);
+    for (a, b) in vec1.iter().zip(vec2) {
+        let (a, b) = (f64::from(*a), f64::from(*b));
+        dot_product += a * b;
+        norm1 += a * a;
+        norm2 += b * b;
+    }
+
+    let magnitude = (norm1 * norm2).sqrt();
+    if magnitude == 0.0 {
+        return Ok(0.0);
+    }
+
+    Ok(dot_product / magnitude)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dense_cosine_similarity() {
+        assert!((dense_cosine_similarity(&[1.0, 0.0], &[2.0, 0.0]).unwrap() - 1.0).abs() < 1e-9);
+        assert_eq!(dense_cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]).unwrap(), 0.0);
+        assert!(dense_cosine_similarity(&[1.0, 0.0], &[1.0, 0.0, 0.0]).is_err());
+        assert_eq!(dense_cosine_similarity(&[0.0, 0.0], &[1.0, 0.0]).unwrap(), 0.0);
+    }
+}
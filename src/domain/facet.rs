@@ -0,0 +1,114 @@
+// src/domain/facet.rs
+
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
+
+/// Counts of matching documents per metadata value, for each requested metadata key
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Facets {
+    /// Metadata key -> metadata value -> number of matching documents
+    counts: HashMap<String, HashMap<String, usize>>,
+}
+
+impl Facets {
+    /// Create empty facets for the given metadata keys
+    pub fn new<'a>(keys: impl IntoIterator<Item = &'a str>) -> Self {
+        Self {
+            counts: keys.into_iter().map(|key| (key.to_string(), HashMap::new())).collect(),
+        }
+    }
+
+    /// Count one matching document with the given value for a key
+    pub fn add(&mut self, key: &str, value: &str) {
+        let values = self.counts.entry(key.to_string()).or_default();
+        *values.entry(value.to_string()).or_insert(0) += 1;
+    }
+
+    /// Get the counts per value for a key
+    pub fn counts(&self, key: &str) -> Option<&HashMap<String, usize>> {
+        self.counts.get(key)
+    }
+
+    /// Get the number of matching documents with a value for a key
+    pub fn count(&self, key: &str, value: &str) -> usize {
+        self.counts
+            .get(key)
+            .and_then(|values| values.get(value))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Get the values of a key ordered by count (highest first), then by value
+    pub fn sorted(&self, key: &str) -> Vec<(&str, usize)> {
+        let mut values: Vec<(&str, usize)> = self.counts
+            .get(key)
+            .map(|values| values.iter().map(|(value, count)| (value.as_str(), *count)).collect())
+            .unwrap_or_default();
+
+        values.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        values
+    }
+
+    /// Get the metadata keys being counted
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.counts.keys().map(String::as_str)
+    }
+}
+
+/// Search results together with facet counts over all matching documents
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FacetedResults<T> {
+    /// The ranked results
+    results: Vec<T>,
+
+    /// Facet counts over every matching document
+    facets: Facets,
+}
+
+impl<T> FacetedResults<T> {
+    /// Create faceted results
+    pub fn new(results: Vec<T>, facets: Facets) -> Self {
+        Self { results, facets }
+    }
+
+    /// Get the ranked results
+    pub fn results(&self) -> &[T] {
+        &self.results
+    }
+
+    /// Get the facet counts
+    pub fn facets(&self) -> &Facets {
+        &self.facets
+    }
+
+    /// Take the ranked results, dropping the facet counts
+    pub fn into_results(self) -> Vec<T> {
+        self.results
+    }
+
+    /// Split into the results and the facet counts
+    pub fn into_parts(self) -> (Vec<T>, Facets) {
+        (self.results, self.facets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_facet_counts() {
+        let mut facets = Facets::new(["category", "lang"]);
+        facets.add("category", "news");
+        facets.add("category", "blog");
+        facets.add("category", "news");
+
+        assert_eq!(facets.count("category", "news"), 2);
+        assert_eq!(facets.count("category", "missing"), 0);
+        assert_eq!(facets.sorted("category"), vec![("news", 2), ("blog", 1)]);
+
+        // Requested keys are present even without matches
+        assert!(facets.counts("lang").unwrap().is_empty());
+        assert!(facets.counts("author").is_none());
+    }
+}
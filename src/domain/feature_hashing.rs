@@ -0,0 +1,157 @@
+// src/domain/feature_hashing.rs
+
+use std::collections::{BTreeMap, HashMap};
+use serde::{Serialize, Deserialize};
+
+use super::dedup::fnv1a;
+use super::Document;
+
+/// Sparse vector over a fixed number of hashed feature indices
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HashedVector {
+    dimensions: usize,
+
+    /// Non-zero weights by feature index
+    entries: BTreeMap<usize, f64>,
+}
+
+impl HashedVector {
+    /// Get the number of feature indices
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// Get the weight at a feature index
+    pub fn get(&self, index: usize) -> f64 {
+        self.entries.get(&index).copied().unwrap_or(0.0)
+    }
+
+    /// Get the number of non-zero weights
+    pub fn nnz(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Iterate over the non-zero weights in index order
+    pub fn iter(&self) -> impl Iterator<Item = (usize, f64)> + '_ {
+        self.entries.iter().map(|(index, weight)| (*index, *weight))
+    }
+
+    /// Expand into a dense vector of `dimensions` weights
+    pub fn to_dense(&self) -> Vec<f64> {
+        let mut dense = vec![0.0; self.dimensions];
+        for (index, weight) in self.iter() {
+            dense[index] = weight;
+        }
+        dense
+    }
+
+    /// Calculate the cosine similarity with another vector of the same feature space
+    pub fn cosine_similarity(&self, other: &HashedVector) -> f64 {
+        let dot: f64 = self.iter().map(|(index, weight)| weight * other.get(index)).sum();
+        let magnitude = self.norm() * other.norm();
+        if magnitude == 0.0 { 0.0 } else { dot / magnitude }
+    }
+
+    fn norm(&self) -> f64 {
+        self.entries.values().map(|weight| weight * weight).sum::<f64>().sqrt()
+    }
+}
+
+/// Maps terms to a fixed number of feature indices by hashing (the "hashing trick")
+///
+/// No vocabulary is kept, so vectors stay bounded however many distinct terms
+/// a stream produces, at the cost of unrelated terms sharing an index now and
+/// then. With alternating signs (the default), half of the terms subtract from
+/// their index, so collisions cancel out in expectation instead of piling up.
+/// Hashes are stable across runs and platforms.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeatureHasher {
+    dimensions: usize,
+    alternate_sign: bool,
+}
+
+impl FeatureHasher {
+    /// Create a hasher into `dimensions` feature indices (at least one)
+    pub fn new(dimensions: usize) -> Self {
+        Self { dimensions: dimensions.max(1), alternate_sign: true }
+    }
+
+    /// Create a hasher with alternating signs turned on or off
+    pub fn with_alternate_sign(dimensions: usize, alternate_sign: bool) -> Self {
+        Self { alternate_sign, ..Self::new(dimensions) }
+    }
+
+    /// Get the number of feature indices
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// Check if colliding terms get alternating signs
+    pub fn alternate_sign(&self) -> bool {
+        self.alternate_sign
+    }
+
+    /// Get the feature index and sign of a term
+    pub fn feature(&self, term: &str) -> (usize, f64) {
+        let hash = fnv1a(term);
+        let sign = if self.alternate_sign && hash >> 63 == 1 { -1.0 } else { 1.0 };
+        ((hash % self.dimensions as u64) as usize, sign)
+    }
+
+    /// Hash a vector keyed by term, summing weights that land on the same index
+    pub fn hash_vector(&self, vector: &HashMap<String, f64>) -> HashedVector {
+        self.hash_weights(vector.iter().map(|(term, weight)| (term.as_str(), *weight)))
+    }
+
+    /// Hash a document's content term frequencies, without any corpus statistics
+    pub fn hash_document(&self, document: &Document) -> HashedVector {
+        self.hash_weights(document.term_frequencies().iter().map(|(term, frequency)| (term.text(), frequency.value() as f64)))
+    }
+
+    fn hash_weights<'a>(&self, weights: impl Iterator<Item = (&'a str, f64)>) -> HashedVector {
+        let mut entries: BTreeMap<usize, f64> = BTreeMap::new();
+        for (term, weight) in weights {
+            let (index, sign) = self.feature(term);
+            *entries.entry(index).or_insert(0.0) += sign * weight;
+        }
+        entries.retain(|_, weight| *weight != 0.0);
+
+        HashedVector { dimensions: self.dimensions, entries }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Term;
+
+    #[test]
+    fn test_hash_document() {
+        let hasher = FeatureHasher::new(16);
+        let mut document = Document::new("doc1", "rust rust safe");
+        document.add_terms(["rust", "rust", "safe"].map(Term::new));
+
+        let vector = hasher.hash_document(&document);
+        assert_eq!(vector.dimensions(), 16);
+        assert_eq!(vector.to_dense().len(), 16);
+
+        let (index, sign) = hasher.feature("rust");
+        assert!(index < 16);
+        assert_eq!(hasher.feature("rust"), (index, sign));
+        if hasher.feature("safe").0 != index {
+            assert_eq!(vector.get(index), 2.0 * sign);
+        }
+        assert!((vector.cosine_similarity(&vector) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_collisions_are_summed() {
+        let hasher = FeatureHasher::with_alternate_sign(1, false);
+        let vector = hasher.hash_vector(&HashMap::from([
+            ("a".to_string(), 0.5),
+            ("b".to_string(), 0.25),
+        ]));
+        assert_eq!(vector.nnz(), 1);
+        assert_eq!(vector.get(0), 0.75);
+    }
+}
@@ -0,0 +1,117 @@
+// src/domain/global_stats.rs
+
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
+
+use super::{Corpus, Term};
+
+/// Document statistics aggregated over a set of corpora
+///
+/// Scoring each shard of a split collection against its own statistics skews
+/// IDF toward whatever happened to land in that shard; scoring every shard
+/// against the same `GlobalStats` gives a term the same IDF everywhere.
+/// Statistics are read from each corpus index, so corpora should be indexed
+/// before they are added.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GlobalStats {
+    /// Number of documents across all corpora
+    document_count: usize,
+
+    /// Number of documents containing each term, keyed by term text
+    document_frequencies: HashMap<String, usize>,
+
+    /// Number of documents containing each canonical term form
+    canonical_document_frequencies: HashMap<String, usize>,
+
+    /// Number of content terms across all documents
+    token_count: usize,
+}
+
+impl GlobalStats {
+    /// Create empty statistics
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Aggregate the statistics of several corpora
+    pub fn from_corpora<'a>(corpora: impl IntoIterator<Item = &'a Corpus>) -> Self {
+        let mut stats = Self::new();
+        for corpus in corpora {
+            stats.add_corpus(corpus);
+        }
+        stats
+    }
+
+    /// Add the statistics of a corpus
+    ///
+    /// A document held by several corpora is counted once per corpus.
+    pub fn add_corpus(&mut self, corpus: &Corpus) {
+        self.document_count += corpus.document_count();
+        self.token_count += corpus.total_token_count();
+        for (term, count) in corpus.document_frequencies() {
+            *self.document_frequencies.entry(term.text().to_string()).or_insert(0) += count;
+        }
+        for (canonical, count) in corpus.canonical_document_frequencies() {
+            *self.canonical_document_frequencies.entry(canonical.clone()).or_insert(0) += count;
+        }
+    }
+
+    /// Get the number of documents across all corpora
+    pub fn document_count(&self) -> usize {
+        self.document_count
+    }
+
+    /// Get the number of documents containing a term across all corpora
+    pub fn document_frequency(&self, term: &Term) -> usize {
+        self.document_frequencies.get(term.text()).copied().unwrap_or(0)
+    }
+
+    /// Get the number of documents containing any term with the given canonical form
+    pub fn canonical_document_frequency(&self, canonical: &str) -> usize {
+        self.canonical_document_frequencies.get(canonical).copied().unwrap_or(0)
+    }
+
+    /// Get the average number of terms per document across all corpora
+    pub fn average_document_length(&self) -> f64 {
+        if self.document_count == 0 {
+            return 0.0;
+        }
+
+        self.token_count as f64 / self.document_count as f64
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+    use crate::domain::Document;
+
+    /// Build an indexed corpus of documents whose terms are their whitespace-separated words
+    pub(crate) fn shard(id: &str, texts: &[(&str, &str)]) -> Corpus {
+        let mut corpus = Corpus::new(id, id);
+        for (doc_id, text) in texts {
+            let mut document = Document::new(*doc_id, *text);
+            for word in text.split_whitespace() {
+                document.add_term(Term::new(word));
+            }
+            corpus.add_document(document).unwrap();
+        }
+        corpus.build_index().unwrap();
+        corpus
+    }
+
+    #[test]
+    fn test_from_corpora() {
+        let first = shard("first", &[("doc1", "rust is fast"), ("doc2", "rust is safe")]);
+        let second = shard("second", &[("doc3", "go is simple")]);
+
+        let stats = GlobalStats::from_corpora([&first, &second]);
+
+        assert_eq!(stats.document_count(), 3);
+        assert_eq!(stats.document_frequency(&Term::new("rust")), 2);
+        assert_eq!(stats.document_frequency(&Term::new("is")), 3);
+        assert_eq!(stats.canonical_document_frequency("go"), 1);
+        assert_eq!(stats.document_frequency(&Term::new("java")), 0);
+        assert_eq!(stats.average_document_length(), 3.0);
+    }
+}
@@ -0,0 +1,121 @@
+// src/domain/idf_model.rs
+
+use std::collections::{BTreeSet, HashMap};
+use serde::{Serialize, Deserialize};
+
+use super::{DomainError, DomainResult};
+
+/// Precomputed IDF weights of a corpus, for scoring documents without the corpus at hand
+///
+/// Built by `TfIdf::idf_model` and used by `TfIdf::score_with_model`, so IDF
+/// can be trained offline on a large corpus and shipped to a stateless scorer.
+/// The weights follow the options of the `TfIdf` that built the model, so score
+/// with a `TfIdf` using the same options.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IdfModel {
+    /// Artifact layout version, checked when loading
+    format_version: u32,
+
+    /// Number of documents the model was trained on
+    document_count: usize,
+
+    /// Average number of content terms per training document, for length normalization
+    average_document_length: f64,
+
+    /// IDF of each training term, keyed by text (or canonical form when stems were aggregated)
+    idf: HashMap<String, f64>,
+
+    /// IDF of a term no training document contains
+    unseen_idf: f64,
+
+    /// Whether terms are keyed by canonical form
+    aggregate_stems: bool,
+
+    /// Corpus stopwords excluded from scoring
+    #[serde(default)]
+    stopwords: BTreeSet<String>,
+}
+
+impl IdfModel {
+    /// Current artifact layout version
+    const FORMAT_VERSION: u32 = 1;
+
+    /// Create a model from trained statistics
+    pub(super) fn new(
+        document_count: usize,
+        average_document_length: f64,
+        idf: HashMap<String, f64>,
+        unseen_idf: f64,
+        aggregate_stems: bool,
+        stopwords: BTreeSet<String>,
+    ) -> Self {
+        Self {
+            format_version: Self::FORMAT_VERSION,
+            document_count,
+            average_document_length,
+            idf,
+            unseen_idf,
+            aggregate_stems,
+            stopwords,
+        }
+    }
+
+    /// Get the number of documents the model was trained on
+    pub fn document_count(&self) -> usize {
+        self.document_count
+    }
+
+    /// Get the average number of content terms per training document
+    pub fn average_document_length(&self) -> f64 {
+        self.average_document_length
+    }
+
+    /// Get the IDF of a term, or the IDF of an unseen term if no training document contains it
+    pub fn idf(&self, term: &str) -> f64 {
+        self.idf.get(term).copied().unwrap_or(self.unseen_idf)
+    }
+
+    /// Check if a training document contains a term
+    pub fn contains(&self, term: &str) -> bool {
+        self.idf.contains_key(term)
+    }
+
+    /// Check if terms are keyed by canonical form
+    pub fn aggregate_stems(&self) -> bool {
+        self.aggregate_stems
+    }
+
+    /// Check if a term is a corpus stopword excluded from scoring
+    pub fn is_stopword(&self, term: &str) -> bool {
+        self.stopwords.contains(term)
+    }
+
+    /// Get the number of terms with a trained IDF
+    pub fn len(&self) -> usize {
+        self.idf.len()
+    }
+
+    /// Check if the model has no trained terms
+    pub fn is_empty(&self) -> bool {
+        self.idf.is_empty()
+    }
+
+    /// Serialize the model to bytes
+    pub fn to_bytes(&self) -> DomainResult<Vec<u8>> {
+        serde_json::to_vec(self).map_err(|e| DomainError::Other(format!("Error serializing IDF model: {}", e)))
+    }
+
+    /// Load a model serialized by `to_bytes`
+    pub fn from_bytes(bytes: &[u8]) -> DomainResult<Self> {
+        let model: Self = serde_json::from_slice(bytes)
+            .map_err(|e| DomainError::InvalidOperation(format!("Invalid IDF model: {}", e)))?;
+
+        if model.format_version > Self::FORMAT_VERSION {
+            return Err(DomainError::InvalidOperation(
+                format!("Unsupported IDF model version {}", model.format_version)
+            ));
+        }
+
+        Ok(model)
+    }
+}
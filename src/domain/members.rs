@@ -0,0 +1,200 @@
+// src/domain/members.rs
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, OnceLock};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::ser::{Error as _, SerializeMap};
+
+use super::{Document, DocumentId, DomainError, DomainResult};
+
+/// Looks up documents by ID, for corpora that hold only the IDs of their documents
+///
+/// Implemented for closures, so a repository lookup can be passed as
+/// `move |id: &DocumentId| ...`.
+pub trait DocumentResolver: Send + Sync {
+    /// Find a document by ID, or None if it no longer exists
+    fn resolve(&self, document_id: &DocumentId) -> DomainResult<Option<Arc<Document>>>;
+}
+
+impl<F> DocumentResolver for F
+where
+    F: Fn(&DocumentId) -> DomainResult<Option<Arc<Document>>> + Send + Sync,
+{
+    fn resolve(&self, document_id: &DocumentId) -> DomainResult<Option<Arc<Document>>> {
+        self(document_id)
+    }
+}
+
+/// A document of a corpus: the revision it was added at, and the document once loaded
+#[derive(Debug, Clone)]
+struct Member {
+    revision: u64,
+    document: OnceLock<Arc<Document>>,
+}
+
+/// The documents of a corpus by ID
+///
+/// Documents added to the corpus are held in memory. With a resolver set, the
+/// members put back by `attach` are held by ID and revision only, and each is
+/// loaded through the resolver on first use; one that no longer exists stays
+/// a member, so reading a corpus never changes what it contains. Clones share
+/// the members until one of them changes.
+#[derive(Clone, Default)]
+pub(super) struct Members {
+    entries: Arc<HashMap<DocumentId, Member>>,
+    resolver: Option<Arc<dyn DocumentResolver>>,
+}
+
+impl Members {
+    /// Get the number of members, loaded or not
+    pub(super) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if the corpus has no members
+    pub(super) fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Check if a document is a member
+    pub(super) fn contains(&self, document_id: &DocumentId) -> bool {
+        self.entries.contains_key(document_id)
+    }
+
+    /// Check if a member's document is held in memory
+    pub(super) fn is_loaded(&self, document_id: &DocumentId) -> bool {
+        self.entries.get(document_id).is_some_and(|member| member.document.get().is_some())
+    }
+
+    /// Get the IDs of all members
+    pub(super) fn ids(&self) -> impl Iterator<Item = &DocumentId> {
+        self.entries.keys()
+    }
+
+    /// Get the revision a member's document was added at
+    pub(super) fn revision(&self, document_id: &DocumentId) -> Option<u64> {
+        self.entries.get(document_id).map(|member| member.revision)
+    }
+
+    /// Get the ID and revision of every member, ordered by ID
+    pub(super) fn revisions(&self) -> Vec<(DocumentId, u64)> {
+        let mut revisions: Vec<(DocumentId, u64)> = self.entries.iter()
+            .map(|(document_id, member)| (document_id.clone(), member.revision))
+            .collect();
+        revisions.sort_by(|a, b| a.0.value().cmp(b.0.value()));
+        revisions
+    }
+
+    /// Get a member's document, loading it if needed
+    ///
+    /// None if the document is not a member or no longer exists; fails if the
+    /// resolver cannot look it up.
+    pub(super) fn get(&self, document_id: &DocumentId) -> DomainResult<Option<&Arc<Document>>> {
+        let Some(member) = self.entries.get(document_id) else {
+            return Ok(None);
+        };
+        if let Some(document) = member.document.get() {
+            return Ok(Some(document));
+        }
+
+        let Some(resolver) = self.resolver.as_ref() else {
+            return Ok(None);
+        };
+        Ok(resolver.resolve(document_id)?.map(|document| member.document.get_or_init(|| document)))
+    }
+
+    /// Get a mutable reference to a member's document, loading it and copying it first if shared
+    pub(super) fn get_mut(&mut self, document_id: &DocumentId) -> DomainResult<Option<&mut Document>> {
+        if self.get(document_id)?.is_none() {
+            return Ok(None);
+        }
+        Ok(Arc::make_mut(&mut self.entries).get_mut(document_id)
+            .and_then(|member| member.document.get_mut())
+            .map(Arc::make_mut))
+    }
+
+    /// Get the documents of all members that still exist, loading them if needed
+    ///
+    /// Yields an error for each document the resolver cannot look up.
+    pub(super) fn iter(&self) -> impl Iterator<Item = DomainResult<&Arc<Document>>> {
+        self.entries.keys().filter_map(|document_id| self.get(document_id).transpose())
+    }
+
+    /// Get the documents held in memory, without loading any
+    pub(super) fn loaded(&self) -> impl Iterator<Item = &Arc<Document>> {
+        self.entries.values().filter_map(|member| member.document.get())
+    }
+
+    /// Load every member's document not loaded yet, returning the IDs of those that no longer exist, ordered by ID
+    ///
+    /// Fails on the first document the resolver cannot look up.
+    pub(super) fn load_all(&self) -> DomainResult<Vec<DocumentId>> {
+        let mut missing = Vec::new();
+        for document_id in self.entries.keys() {
+            if self.get(document_id)?.is_none() {
+                missing.push(document_id.clone());
+            }
+        }
+        missing.sort_by(|a, b| a.value().cmp(b.value()));
+        Ok(missing)
+    }
+
+    /// Add a document held in memory, replacing any member with the same ID
+    pub(super) fn insert(&mut self, document: Arc<Document>) {
+        Arc::make_mut(&mut self.entries).insert(document.id().clone(), Member { revision: document.revision(), document: OnceLock::from(document) });
+    }
+
+    /// Remove a member, returning the revision it was added at and its document if it still exists
+    ///
+    /// Fails, keeping the member, if the resolver cannot look its document up.
+    pub(super) fn remove(&mut self, document_id: &DocumentId) -> DomainResult<Option<(u64, Option<Arc<Document>>)>> {
+        if !self.contains(document_id) {
+            return Ok(None);
+        }
+        let document = self.get(document_id)?.cloned();
+        Ok(Arc::make_mut(&mut self.entries).remove(document_id).map(|member| (member.revision, document)))
+    }
+
+    /// Replace the members with the given IDs and revisions, loaded through the resolver on first use
+    pub(super) fn attach(&mut self, members: &[(DocumentId, u64)], resolver: Arc<dyn DocumentResolver>) {
+        self.entries = Arc::new(members.iter()
+            .map(|(document_id, revision)| (document_id.clone(), Member { revision: *revision, document: OnceLock::new() }))
+            .collect());
+        self.resolver = Some(resolver);
+    }
+}
+
+impl fmt::Debug for Members {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Members")
+            .field("entries", &self.entries)
+            .field("resolved", &self.resolver.is_some())
+            .finish()
+    }
+}
+
+/// Members are stored as the map of their documents, which must all be loadable
+impl Serialize for Members {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.entries.len()))?;
+        for document_id in self.entries.keys() {
+            let document = self.get(document_id).map_err(S::Error::custom)?.ok_or_else(|| S::Error::custom(DomainError::NotFound(
+                format!("Document '{}' could not be loaded to store with its corpus", document_id.value())
+            )))?;
+            map.serialize_entry(document_id, document)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Members {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let documents = HashMap::<DocumentId, Arc<Document>>::deserialize(deserializer)?;
+        let mut members = Members::default();
+        for document in documents.into_values() {
+            members.insert(document);
+        }
+        Ok(members)
+    }
+}
@@ -7,13 +7,63 @@
 
 mod document;
 mod corpus;
+mod members;
 mod term;
+mod term_interner;
 mod tf_idf;
+mod token;
+mod snippet;
+mod ranking;
+mod vector_index;
+mod query;
+mod search_request;
+mod page;
+mod facet;
+mod vocabulary;
+mod cooccurrence;
+mod term_matrix;
+mod summary;
+mod dedup;
+mod feature_hashing;
+mod weighting;
+mod global_stats;
+mod corpus_stats;
+mod idf_model;
+mod snapshot;
+mod embedding;
+mod similarity_metric;
+#[cfg(feature = "lda")]
+mod topic_model;
 
-pub use document::{Document, DocumentId};
-pub use corpus::{Corpus, CorpusId};
+pub use document::{Document, DocumentId, PositionTracking};
+pub use corpus::{Corpus, CorpusId, MergeReport};
+pub use members::DocumentResolver;
 pub use term::{Term, TermId, TermFrequency};
-pub use tf_idf::{TfIdf, TfIdfScore, TfIdfError};
+pub use term_interner::TermInterner;
+pub use tf_idf::{TfIdf, TfIdfScore, TfIdfError, TfIdfOptions, ScoredDocument, SearchHit};
+pub use token::{Token, TokenSpan};
+pub use snippet::{Snippet, SnippetMatch, SnippetGenerator, HighlightedDocument};
+pub use ranking::{RankingModel, Bm25, Scorer, ScoringContext};
+pub use vector_index::{VectorIndex, SimilarDocument, SimilarityMatrix};
+pub use query::Query;
+pub use search_request::SearchRequest;
+pub use page::{Page, PageRequest};
+pub use facet::{Facets, FacetedResults};
+pub use vocabulary::{Vocabulary, TermStats, VocabularyEntry};
+pub use cooccurrence::{CooccurrenceMatrix, CooccurrenceWindow};
+pub use term_matrix::DocumentTermMatrix;
+pub use summary::{Summarizer, Summary, SummarySentence};
+pub use dedup::{Duplicate, DuplicatePolicy, MinHasher, MinHashSignature, NearDuplicate};
+pub use feature_hashing::{FeatureHasher, HashedVector};
+pub use weighting::{Scheme, TfWeight, IdfWeight, Normalization, Smoothing};
+pub use global_stats::GlobalStats;
+pub use corpus_stats::{CorpusStats, GroupStats};
+pub use idf_model::IdfModel;
+pub use snapshot::{CorpusSnapshot, VersionedCorpus};
+pub use embedding::{DocumentEmbedder, dense_cosine_similarity};
+pub use similarity_metric::SimilarityMetric;
+#[cfg(feature = "lda")]
+pub use topic_model::{Lda, LdaOptions, TopicModel};
 
 #[derive(Debug, thiserror::Error)]
 pub enum DomainError {
@@ -23,6 +73,9 @@ pub enum DomainError {
     #[error("Invalid operation: {0}")]
     InvalidOperation(String),
     
+    #[error("Duplicate document: {0}")]
+    Duplicate(Duplicate),
+    
     #[error("TF-IDF calculation error: {0}")]
     TfIdfError(#[from] TfIdfError),
     
@@ -0,0 +1,134 @@
+// src/domain/page.rs
+
+use serde::{Serialize, Deserialize};
+
+/// A request for one window of an ordered result list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageRequest {
+    /// Number of results to skip
+    offset: usize,
+
+    /// Maximum number of results to return
+    limit: usize,
+}
+
+impl PageRequest {
+    /// Create a page request
+    pub fn new(offset: usize, limit: usize) -> Self {
+        Self { offset, limit }
+    }
+
+    /// Request the first `limit` results
+    pub fn first(limit: usize) -> Self {
+        Self::new(0, limit)
+    }
+
+    /// Get the number of results to skip
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Get the maximum number of results to return
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Get the request for the page after this one
+    pub fn next(&self) -> Self {
+        Self::new(self.offset.saturating_add(self.limit), self.limit)
+    }
+}
+
+/// One page of an ordered result list, with the total number of results available
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Page<T> {
+    /// The results on this page
+    items: Vec<T>,
+
+    /// The request that produced this page
+    request: PageRequest,
+
+    /// Total number of results across all pages
+    total: usize,
+}
+
+impl<T> Page<T> {
+    /// Create a page from results already restricted to the request
+    pub fn new(items: Vec<T>, request: PageRequest, total: usize) -> Self {
+        Self { items, request, total }
+    }
+
+    /// Cut the requested page out of a full result list
+    pub fn from_vec(items: Vec<T>, request: PageRequest) -> Self {
+        let total = items.len();
+        let items = items.into_iter()
+            .skip(request.offset)
+            .take(request.limit)
+            .collect();
+
+        Self { items, request, total }
+    }
+
+    /// Get the results on this page
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Take the results on this page
+    pub fn into_items(self) -> Vec<T> {
+        self.items
+    }
+
+    /// Get the request that produced this page
+    pub fn request(&self) -> PageRequest {
+        self.request
+    }
+
+    /// Get the total number of results across all pages
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Check whether results remain after this page
+    pub fn has_next(&self) -> bool {
+        self.request.offset.saturating_add(self.items.len()) < self.total
+    }
+
+    /// Get the request for the next page, if results remain
+    pub fn next_request(&self) -> Option<PageRequest> {
+        self.has_next().then(|| self.request.next())
+    }
+
+    /// Transform the results on this page, keeping its position and total
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            request: self.request,
+            total: self.total,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paging_through_results() {
+        let page = Page::from_vec((0..5).collect(), PageRequest::first(2));
+        assert_eq!(page.items(), &[0, 1]);
+        assert_eq!(page.total(), 5);
+        assert!(page.has_next());
+
+        let request = page.next_request().unwrap();
+        assert_eq!(request, PageRequest::new(2, 2));
+
+        let last = Page::from_vec((0..5).collect(), PageRequest::new(4, 2));
+        assert_eq!(last.items(), &[4]);
+        assert!(last.next_request().is_none());
+
+        let beyond = Page::from_vec((0..5).collect::<Vec<i32>>(), PageRequest::new(10, 2));
+        assert!(beyond.items().is_empty());
+        assert_eq!(beyond.map(|i| i * 2).total(), 5);
+    }
+}
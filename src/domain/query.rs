@@ -0,0 +1,256 @@
+// src/domain/query.rs
+
+use serde::{Serialize, Deserialize};
+
+use super::{Corpus, Document, Term};
+
+/// A structured search query combining terms with boolean operators
+///
+/// Boolean operators decide which documents match; matching documents are
+/// then ranked by the TF-IDF scores of the query's positive terms, i.e. the
+/// terms that do not appear under a `Not`. A query without positive terms
+/// (such as a lone `Not`) therefore matches documents but ranks none of them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Query {
+    /// Documents containing the term in their content or any field
+    Term(Term),
+    
+    /// Documents containing the terms at consecutive positions
+    Phrase(Vec<Term>),
+    
+    /// Documents containing a term matching a pattern such as `rust*`
+    Wildcard(String),
+    
+    /// Documents matching every sub-query
+    And(Vec<Query>),
+    
+    /// Documents matching at least one sub-query
+    Or(Vec<Query>),
+    
+    /// Documents not matching the sub-query
+    Not(Box<Query>),
+    
+    /// Documents matching the sub-query, with its terms' scores multiplied by the boost
+    Boost(Box<Query>, f64),
+}
+
+impl Query {
+    /// Create a single-term query
+    pub fn term(term: Term) -> Self {
+        Query::Term(term)
+    }
+    
+    /// Create a phrase query
+    pub fn phrase(terms: impl IntoIterator<Item = Term>) -> Self {
+        Query::Phrase(terms.into_iter().collect())
+    }
+    
+    /// Create a wildcard query, where `*` matches any run of characters and `?` a single one
+    pub fn wildcard(pattern: impl Into<String>) -> Self {
+        Query::Wildcard(pattern.into())
+    }
+    
+    /// Create a query matching every sub-query
+    pub fn and(queries: impl IntoIterator<Item = Query>) -> Self {
+        Query::And(queries.into_iter().collect())
+    }
+    
+    /// Create a query matching any sub-query
+    pub fn or(queries: impl IntoIterator<Item = Query>) -> Self {
+        Query::Or(queries.into_iter().collect())
+    }
+    
+    /// Create a query excluding documents that match the sub-query
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(query: Query) -> Self {
+        Query::Not(Box::new(query))
+    }
+    
+    /// Create a query whose terms weigh `boost` times as much in the ranking
+    pub fn boost(query: Query, boost: f64) -> Self {
+        Query::Boost(Box::new(query), boost)
+    }
+    
+    /// Check whether a document matches the query
+    pub fn matches(&self, document: &Document) -> bool {
+        self.matches_with(document, false)
+    }
+
+    /// Check whether a document matches the query, comparing terms by canonical form when aggregating stems
+    ///
+    /// Wildcards always match the raw term text.
+    pub fn matches_with(&self, document: &Document, aggregate_stems: bool) -> bool {
+        match self {
+            Query::Term(term) if aggregate_stems => document.contains_canonical(term.canonical()),
+            Query::Term(term) => document.contains_term(term),
+            Query::Phrase(terms) if aggregate_stems => document.contains_canonical_phrase(terms),
+            Query::Phrase(terms) => document.contains_phrase(terms),
+            Query::Wildcard(pattern) => {
+                document.unique_terms().iter().any(|term| matches_wildcard(pattern, term.text()))
+            },
+            Query::And(queries) => queries.iter().all(|q| q.matches_with(document, aggregate_stems)),
+            Query::Or(queries) => queries.iter().any(|q| q.matches_with(document, aggregate_stems)),
+            Query::Not(query) => !query.matches_with(document, aggregate_stems),
+            Query::Boost(query, _) => query.matches_with(document, aggregate_stems),
+        }
+    }
+    
+    /// Replace wildcards with an `Or` of the corpus terms they match
+    ///
+    /// Wildcards contribute no scoring terms until expanded.
+    pub fn expand_wildcards(&self, corpus: &Corpus) -> Query {
+        match self {
+            Query::Wildcard(pattern) => {
+                Query::or(corpus.expand_wildcard(pattern).into_iter().map(Query::term))
+            },
+            Query::And(queries) => Query::and(queries.iter().map(|q| q.expand_wildcards(corpus))),
+            Query::Or(queries) => Query::or(queries.iter().map(|q| q.expand_wildcards(corpus))),
+            Query::Not(query) => Query::not(query.expand_wildcards(corpus)),
+            Query::Boost(query, boost) => Query::boost(query.expand_wildcards(corpus), *boost),
+            Query::Term(_) | Query::Phrase(_) => self.clone(),
+        }
+    }
+    
+    /// Get the terms used to rank matching documents, excluding negated terms
+    pub fn scoring_terms(&self) -> Vec<Term> {
+        self.weighted_scoring_terms().into_iter().map(|(term, _)| term).collect()
+    }
+    
+    /// Get the terms used to rank matching documents with their combined boosts
+    ///
+    /// Nested boosts multiply; a term occurring several times keeps its highest boost.
+    pub fn weighted_scoring_terms(&self) -> Vec<(Term, f64)> {
+        let mut terms = Vec::new();
+        self.collect_scoring_terms(&mut terms, 1.0);
+        terms
+    }
+    
+    fn collect_scoring_terms(&self, terms: &mut Vec<(Term, f64)>, boost: f64) {
+        let mut add = |term: &Term| {
+            match terms.iter_mut().find(|(existing, _)| existing == term) {
+                Some((_, weight)) => *weight = weight.max(boost),
+                None => terms.push((term.clone(), boost)),
+            }
+        };
+        
+        match self {
+            Query::Term(term) => add(term),
+            Query::Phrase(phrase) => phrase.iter().for_each(add),
+            Query::And(queries) | Query::Or(queries) => {
+                for query in queries {
+                    query.collect_scoring_terms(terms, boost);
+                }
+            },
+            Query::Boost(query, factor) => query.collect_scoring_terms(terms, boost * factor),
+            Query::Wildcard(_) | Query::Not(_) => {}
+        }
+    }
+}
+
+/// Check whether text matches a pattern where `*` matches any run of characters and `?` a single one
+pub(crate) fn matches_wildcard(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and the text position it is currently matched up to
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            },
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            },
+            _ => match backtrack {
+                // Let the last `*` absorb one more character and retry
+                Some((star, matched)) => {
+                    p = star + 1;
+                    t = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                },
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn document(id: &str, words: &[&str]) -> Document {
+        let mut doc = Document::new(id, words.join(" "));
+        doc.add_terms(words.iter().map(|w| Term::new(*w)));
+        doc
+    }
+    
+    #[test]
+    fn test_boolean_matching() {
+        let rust = document("doc1", &["rust", "async", "runtime"]);
+        let python = document("doc2", &["python", "async", "io"]);
+        
+        let term = |t: &str| Query::term(Term::new(t));
+        
+        let both = Query::and([term("async"), term("rust")]);
+        assert!(both.matches(&rust));
+        assert!(!both.matches(&python));
+        
+        let either = Query::or([term("rust"), term("python")]);
+        assert!(either.matches(&rust) && either.matches(&python));
+        
+        let not_rust = Query::and([term("async"), Query::not(term("rust"))]);
+        assert!(!not_rust.matches(&rust));
+        assert!(not_rust.matches(&python));
+        
+        let phrase = Query::phrase([Term::new("async"), Term::new("runtime")]);
+        assert!(phrase.matches(&rust));
+        assert!(!phrase.matches(&python));
+    }
+    
+    #[test]
+    fn test_scoring_terms_skip_negations() {
+        let query = Query::and([
+            Query::term(Term::new("async")),
+            Query::or([Query::term(Term::new("rust")), Query::term(Term::new("async"))]),
+            Query::not(Query::term(Term::new("python"))),
+        ]);
+        
+        let terms: Vec<_> = query.scoring_terms().iter().map(|t| t.text().to_string()).collect();
+        assert_eq!(terms, vec!["async", "rust"]);
+    }
+    
+    #[test]
+    fn test_boosted_scoring_terms() {
+        let query = Query::or([
+            Query::boost(Query::term(Term::new("rust")), 2.0),
+            Query::term(Term::new("async")),
+            Query::boost(Query::or([Query::boost(Query::term(Term::new("tokio")), 1.5)]), 2.0),
+        ]);
+        
+        let weights: Vec<_> = query.weighted_scoring_terms().iter().map(|(t, w)| (t.text().to_string(), *w)).collect();
+        assert_eq!(weights, vec![("rust".to_string(), 2.0), ("async".to_string(), 1.0), ("tokio".to_string(), 3.0)]);
+        assert!(query.matches(&document("doc1", &["tokio"])));
+    }
+    
+    #[test]
+    fn test_wildcard_patterns() {
+        assert!(matches_wildcard("rust*", "rustacean"));
+        assert!(matches_wildcard("rust*", "rust"));
+        assert!(matches_wildcard("r?st", "rust"));
+        assert!(matches_wildcard("*ing*s", "kingdoms"));
+        assert!(!matches_wildcard("rust*", "trust"));
+        assert!(!matches_wildcard("r?st", "roast"));
+        
+        let doc = document("doc1", &["rustacean", "crab"]);
+        assert!(Query::wildcard("rust*").matches(&doc));
+        assert!(!Query::wildcard("go*").matches(&doc));
+        assert!(Query::wildcard("rust*").scoring_terms().is_empty());
+    }
+}
@@ -0,0 +1,267 @@
+// src/domain/ranking.rs
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::OnceLock;
+use serde::{Serialize, Deserialize};
+
+use super::{Corpus, Document, DomainError, DomainResult, GlobalStats, Term, TfIdfScore};
+
+/// A ranking algorithm that scores a term in a document against a corpus
+///
+/// Implementations return the score split into a term-frequency and an
+/// inverse-document-frequency component; schemes without that split can
+/// report the whole score as `tf` with an `idf` of 1.0.
+pub trait Scorer: Send + Sync + Debug {
+    /// Score a term in a document
+    fn score_term(&self, term: &Term, document: &Document, context: &ScoringContext) -> TfIdfScore;
+}
+
+/// Corpus statistics shared by every term scored in one pass
+///
+/// Expensive statistics are computed lazily, the first time a scorer asks for them.
+#[derive(Debug)]
+pub struct ScoringContext<'a> {
+    corpus: &'a Corpus,
+    field_boosts: Option<&'a HashMap<String, f64>>,
+    aggregate_stems: bool,
+    global_stats: Option<&'a GlobalStats>,
+    average_document_length: OnceLock<f64>,
+}
+
+impl<'a> ScoringContext<'a> {
+    /// Create a scoring context for a corpus
+    pub fn new(corpus: &'a Corpus) -> Self {
+        Self { corpus, field_boosts: None, aggregate_stems: false, global_stats: None, average_document_length: OnceLock::new() }
+    }
+
+    /// Create a scoring context that also counts term occurrences in boosted fields
+    pub fn with_field_boosts(corpus: &'a Corpus, field_boosts: &'a HashMap<String, f64>) -> Self {
+        Self { corpus, field_boosts: Some(field_boosts), aggregate_stems: false, global_stats: None, average_document_length: OnceLock::new() }
+    }
+
+    /// Get the corpus being scored against
+    pub fn corpus(&self) -> &'a Corpus {
+        self.corpus
+    }
+
+    /// Count content occurrences by canonical form, so a term matches every word sharing its stem
+    ///
+    /// Field occurrences are still matched by exact text.
+    pub fn set_aggregate_stems(&mut self, aggregate_stems: bool) {
+        self.aggregate_stems = aggregate_stems;
+    }
+
+    /// Take document counts and lengths from statistics shared across corpora instead of the corpus
+    ///
+    /// Term frequencies still come from the scored document.
+    pub fn set_global_stats(&mut self, global_stats: Option<&'a GlobalStats>) {
+        self.global_stats = global_stats;
+    }
+
+    /// Get the number of documents IDF is computed over
+    pub fn document_count(&self) -> usize {
+        match self.global_stats {
+            Some(stats) => stats.document_count(),
+            None => self.corpus.document_count(),
+        }
+    }
+
+    /// Get the number of documents containing a term, by canonical form when aggregating stems
+    pub fn document_frequency(&self, term: &Term) -> usize {
+        match (self.global_stats, self.aggregate_stems) {
+            (Some(stats), true) => stats.canonical_document_frequency(term.canonical()),
+            (Some(stats), false) => stats.document_frequency(term),
+            (None, true) => self.corpus.canonical_document_frequency(term.canonical()),
+            (None, false) => self.corpus.document_frequency(term),
+        }
+    }
+
+    /// Get the total occurrences of a term in the corpus's content, by canonical form when aggregating stems
+    ///
+    /// Counted like `document_frequency`, but always from the corpus, even with global statistics.
+    pub fn collection_frequency(&self, term: &Term) -> usize {
+        if self.aggregate_stems {
+            self.corpus.canonical_collection_frequency(term.canonical())
+        } else {
+            self.corpus.collection_frequency(term)
+        }
+    }
+
+    /// Get the unsmoothed inverse document frequency ln(N / df) of a term, or 0 if no document contains it
+    pub fn inverse_document_frequency(&self, term: &Term) -> f64 {
+        let doc_count = self.document_count() as f64;
+        let doc_freq = self.document_frequency(term) as f64;
+
+        if doc_count == 0.0 || doc_freq == 0.0 {
+            return 0.0;
+        }
+
+        (doc_count / doc_freq).ln()
+    }
+
+    /// Get the frequency of a term in a document, weighting each boosted field's occurrences by its boost
+    ///
+    /// Content occurrences count once unless `Document::CONTENT_FIELD` has a
+    /// boost of its own; other fields without a boost are ignored.
+    pub fn term_frequency(&self, term: &Term, document: &Document) -> f64 {
+        boosted_term_frequency(term, document, self.field_boosts, self.aggregate_stems)
+    }
+
+    /// Get the average number of terms per document in the corpus, or across corpora with global statistics
+    pub fn average_document_length(&self) -> f64 {
+        *self.average_document_length.get_or_init(|| match self.global_stats {
+            Some(stats) => stats.average_document_length(),
+            None => self.corpus.average_document_length(),
+        })
+    }
+}
+
+/// Get the frequency of a term in a document, weighting each boosted field's occurrences by its boost
+pub(super) fn boosted_term_frequency(
+    term: &Term,
+    document: &Document,
+    field_boosts: Option<&HashMap<String, f64>>,
+    aggregate_stems: bool
+) -> f64 {
+    let content = if aggregate_stems {
+        document.canonical_term_frequency(term.canonical()).value() as f64
+    } else {
+        document.term_frequency(term).value() as f64
+    };
+
+    let Some(boosts) = field_boosts else {
+        return content;
+    };
+
+    let content_boost = boosts.get(Document::CONTENT_FIELD).copied().unwrap_or(1.0);
+    let fields: f64 = boosts.iter()
+        .filter(|(field, _)| field.as_str() != Document::CONTENT_FIELD)
+        .map(|(field, boost)| boost * document.field_term_frequency(field, term).value() as f64)
+        .sum();
+
+    content_boost * content + fields
+}
+
+/// The ranking function used to score terms in documents
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum RankingModel {
+    /// Classic TF-IDF, configured by the remaining `TfIdfOptions` fields
+    #[default]
+    TfIdf,
+
+    /// Okapi BM25 with document length normalization
+    Bm25(Bm25),
+}
+
+/// Okapi BM25 parameters
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Bm25 {
+    /// Term frequency saturation (typically 1.2 to 2.0)
+    pub k1: f64,
+
+    /// Document length normalization strength (0.0 = none, 1.0 = full)
+    pub b: f64,
+}
+
+impl Default for Bm25 {
+    fn default() -> Self {
+        Self { k1: 1.2, b: 0.75 }
+    }
+}
+
+impl Bm25 {
+    /// Create BM25 parameters
+    pub fn new(k1: f64, b: f64) -> Self {
+        Self { k1, b }
+    }
+
+    /// Check that `k1` is finite and not negative, and `b` lies between 0 and 1
+    pub fn validate(&self) -> DomainResult<()> {
+        if !self.k1.is_finite() || self.k1 < 0.0 {
+            return Err(DomainError::InvalidOperation(
+                format!("BM25 k1 must be finite and at least 0, got {}", self.k1)
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.b) {
+            return Err(DomainError::InvalidOperation(
+                format!("BM25 b must be between 0 and 1, got {}", self.b)
+            ));
+        }
+        Ok(())
+    }
+
+    /// BM25 inverse document frequency: ln(1 + (N - df + 0.5) / (df + 0.5))
+    ///
+    /// This variant never goes negative, even for terms present in most documents.
+    pub fn idf(&self, term: &Term, corpus: &Corpus) -> f64 {
+        self.idf_from(corpus.document_frequency(term), corpus.document_count())
+    }
+
+    /// BM25 inverse document frequency from raw counts, e.g. for statistics kept outside a corpus
+    pub fn idf_from(&self, document_frequency: usize, document_count: usize) -> f64 {
+        let doc_count = document_count as f64;
+        let doc_freq = document_frequency as f64;
+
+        (1.0 + (doc_count - doc_freq + 0.5) / (doc_freq + 0.5)).ln()
+    }
+
+    /// BM25 saturated term frequency for a document of the given length
+    pub fn tf(&self, term_frequency: usize, document_length: usize, average_document_length: f64) -> f64 {
+        self.weighted_tf(term_frequency as f64, document_length, average_document_length)
+    }
+
+    /// BM25 saturated term frequency for a possibly field-weighted frequency
+    pub fn weighted_tf(&self, term_frequency: f64, document_length: usize, average_document_length: f64) -> f64 {
+        if term_frequency <= 0.0 {
+            return 0.0;
+        }
+
+        let tf = term_frequency;
+        let length_ratio = if average_document_length > 0.0 {
+            document_length as f64 / average_document_length
+        } else {
+            1.0
+        };
+
+        tf * (self.k1 + 1.0) / (tf + self.k1 * (1.0 - self.b + self.b * length_ratio))
+    }
+}
+
+impl Scorer for Bm25 {
+    fn score_term(&self, term: &Term, document: &Document, context: &ScoringContext) -> TfIdfScore {
+        let tf = self.weighted_tf(context.term_frequency(term, document), document.term_count(), context.average_document_length());
+        let idf = self.idf_from(context.document_frequency(term), context.document_count());
+
+        TfIdfScore::new(term.clone(), tf, idf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bm25_tf_saturates() {
+        let bm25 = Bm25::default();
+
+        let once = bm25.tf(1, 10, 10.0);
+        let many = bm25.tf(100, 10, 10.0);
+
+        assert!(many > once);
+        assert!(many < bm25.k1 + 1.0);
+        assert_eq!(bm25.tf(0, 10, 10.0), 0.0);
+    }
+
+    #[test]
+    fn test_bm25_length_normalization() {
+        let bm25 = Bm25::default();
+
+        // The same count is worth less in a longer document
+        assert!(bm25.tf(2, 40, 10.0) < bm25.tf(2, 5, 10.0));
+
+        // With b = 0 the length is ignored
+        let flat = Bm25::new(1.2, 0.0);
+        assert!((flat.tf(2, 40, 10.0) - flat.tf(2, 5, 10.0)).abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,98 @@
+// src/domain/search_request.rs
+
+use super::{Query, Term};
+
+/// What to search a corpus for, and how to restrict and summarize the matches
+///
+/// Documents are ranked by the weighted terms. Phrases and a boolean query
+/// restrict which documents match, fields restrict where terms are counted,
+/// and facet keys name the metadata to count matching documents by.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchRequest {
+    /// Ranking terms with the boost their scores are multiplied by
+    terms: Vec<(Term, f64)>,
+
+    /// Phrases every matching document must contain at consecutive positions
+    phrases: Vec<Vec<Term>>,
+
+    /// Boolean query matching documents must satisfy, also ranking by its positive terms
+    query: Option<Query>,
+
+    /// The only fields terms are counted in, or None for the configured field boosts
+    fields: Option<Vec<String>>,
+
+    /// Metadata keys to count matching documents by
+    facet_keys: Vec<String>,
+}
+
+impl SearchRequest {
+    /// Search for terms, each with a boost of 1.0
+    pub fn new(terms: impl IntoIterator<Item = Term>) -> Self {
+        Self::weighted(terms.into_iter().map(|term| (term, 1.0)))
+    }
+
+    /// Search for terms, multiplying each term's score by its boost
+    pub fn weighted(terms: impl IntoIterator<Item = (Term, f64)>) -> Self {
+        Self {
+            terms: terms.into_iter().collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Search with a boolean query, ranking its matches by the query's positive terms
+    pub fn boolean(query: Query) -> Self {
+        Self {
+            query: Some(query),
+            ..Self::default()
+        }
+    }
+
+    /// Only match documents containing every phrase
+    ///
+    /// The phrase terms only restrict matches; rank by them too by including
+    /// them among the request's terms.
+    pub fn with_phrases(mut self, phrases: impl IntoIterator<Item = Vec<Term>>) -> Self {
+        self.phrases.extend(phrases);
+        self
+    }
+
+    /// Only count terms in the given fields, e.g. `["abstract", Document::CONTENT_FIELD]`
+    ///
+    /// Targeted fields keep their configured boost, or 1.0 when they have none;
+    /// occurrences in other fields are ignored.
+    pub fn in_fields(mut self, fields: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.fields = Some(fields.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Count matching documents per value of each metadata key
+    pub fn with_facets(mut self, keys: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.facet_keys.extend(keys.into_iter().map(Into::into));
+        self
+    }
+
+    /// Get the ranking terms with their boosts
+    pub fn terms(&self) -> &[(Term, f64)] {
+        &self.terms
+    }
+
+    /// Get the phrases matching documents must contain
+    pub fn phrases(&self) -> &[Vec<Term>] {
+        &self.phrases
+    }
+
+    /// Get the boolean query matching documents must satisfy, if any
+    pub fn query(&self) -> Option<&Query> {
+        self.query.as_ref()
+    }
+
+    /// Get the fields terms are restricted to, if any
+    pub fn fields(&self) -> Option<&[String]> {
+        self.fields.as_deref()
+    }
+
+    /// Get the metadata keys to count matching documents by
+    pub fn facet_keys(&self) -> &[String] {
+        &self.facet_keys
+    }
+}
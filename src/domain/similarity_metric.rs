@@ -0,0 +1,85 @@
+// src/domain/similarity_metric.rs
+
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
+
+use super::vector_index::cosine_similarity;
+
+/// How two document vectors are compared
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum SimilarityMetric {
+    /// Cosine of the angle between the TF-IDF vectors (higher = more similar)
+    #[default]
+    Cosine,
+
+    /// Shared terms over all terms of both documents, ignoring weights (higher = more similar)
+    Jaccard,
+
+    /// Straight-line distance between the TF-IDF vectors (lower = more similar)
+    Euclidean,
+
+    /// Sum of absolute weight differences between the TF-IDF vectors (lower = more similar)
+    Manhattan,
+}
+
+impl SimilarityMetric {
+    /// Check if the metric is a distance, where lower values mean more similar documents
+    pub fn is_distance(&self) -> bool {
+        matches!(self, SimilarityMetric::Euclidean | SimilarityMetric::Manhattan)
+    }
+
+    /// Compare two sparse vectors
+    pub fn compare(&self, vec1: &HashMap<String, f64>, vec2: &HashMap<String, f64>) -> f64 {
+        match self {
+            SimilarityMetric::Cosine => cosine_similarity(vec1, vec2),
+            SimilarityMetric::Jaccard => jaccard_similarity(vec1, vec2),
+            SimilarityMetric::Euclidean => differences(vec1, vec2).map(|d| d * d).sum::<f64>().sqrt(),
+            SimilarityMetric::Manhattan => differences(vec1, vec2).sum(),
+        }
+    }
+}
+
+/// Jaccard similarity of the sets of terms with a non-zero weight
+fn jaccard_similarity(vec1: &HashMap<String, f64>, vec2: &HashMap<String, f64>) -> f64 {
+    let size1 = vec1.values().filter(|weight| **weight != 0.0).count();
+    let size2 = vec2.values().filter(|weight| **weight != 0.0).count();
+    let shared = vec1.iter()
+        .filter(|(term, weight)| **weight != 0.0 && vec2.get(*term).is_some_and(|other| *other != 0.0))
+        .count();
+
+    let union = size1 + size2 - shared;
+    if union == 0 {
+        return 0.0;
+    }
+
+    shared as f64 / union as f64
+}
+
+/// Absolute weight differences over the union of both vectors' terms
+fn differences<'a>(vec1: &'a HashMap<String, f64>, vec2: &'a HashMap<String, f64>) -> impl Iterator<Item = f64> + 'a {
+    let in_both = vec1.iter().map(move |(term, weight)| (weight - vec2.get(term).copied().unwrap_or(0.0)).abs());
+    let only_second = vec2.iter().filter(move |(term, _)| !vec1.contains_key(*term)).map(|(_, weight)| weight.abs());
+    in_both.chain(only_second)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vector(weights: &[(&str, f64)]) -> HashMap<String, f64> {
+        weights.iter().map(|(term, weight)| (term.to_string(), *weight)).collect()
+    }
+
+    #[test]
+    fn test_metrics() {
+        let first = vector(&[("rust", 3.0), ("code", 1.0)]);
+        let second = vector(&[("rust", 0.0), ("code", 1.0), ("go", 4.0)]);
+
+        assert!((SimilarityMetric::Jaccard.compare(&first, &second) - 1.0 / 3.0).abs() < 1e-9);
+        assert!((SimilarityMetric::Euclidean.compare(&first, &second) - 5.0).abs() < 1e-9);
+        assert!((SimilarityMetric::Manhattan.compare(&first, &second) - 7.0).abs() < 1e-9);
+        assert_eq!(SimilarityMetric::Manhattan.compare(&first, &first), 0.0);
+        assert!(SimilarityMetric::Euclidean.is_distance());
+        assert!(!SimilarityMetric::default().is_distance());
+    }
+}
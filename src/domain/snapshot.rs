@@ -0,0 +1,137 @@
+// src/domain/snapshot.rs
+
+use std::convert::Infallible;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+
+use super::Corpus;
+
+/// An immutable view of a corpus at one revision, cheap to clone and share across threads
+///
+/// Derefs to `Corpus`, so it can be passed to any search or scoring method.
+#[derive(Debug, Clone)]
+pub struct CorpusSnapshot {
+    corpus: Arc<Corpus>,
+}
+
+impl CorpusSnapshot {
+    /// Freeze a corpus into a snapshot
+    pub fn new(corpus: Corpus) -> Self {
+        Self { corpus: Arc::new(corpus) }
+    }
+
+    /// Get the frozen corpus
+    pub fn corpus(&self) -> &Corpus {
+        &self.corpus
+    }
+}
+
+impl Deref for CorpusSnapshot {
+    type Target = Corpus;
+
+    fn deref(&self) -> &Corpus {
+        &self.corpus
+    }
+}
+
+/// A corpus that readers search through snapshots while writers build its next version
+///
+/// A writer edits a private copy of the current version and publishes it in a
+/// single swap once done, so readers never wait on a long update or see a
+/// half-updated index, and a search keeps its snapshot however long it runs.
+/// Writers are serialized. The copy shares its documents and index structures
+/// with the published version, so an update copies only the ones it changes.
+#[derive(Debug)]
+pub struct VersionedCorpus {
+    /// The latest published version
+    current: RwLock<Arc<Corpus>>,
+
+    /// Held for the whole of an update, so concurrent writers do not lose each other's changes
+    writer: Mutex<()>,
+}
+
+impl VersionedCorpus {
+    /// Publish a corpus as the first version
+    pub fn new(corpus: Corpus) -> Self {
+        Self {
+            current: RwLock::new(Arc::new(corpus)),
+            writer: Mutex::new(()),
+        }
+    }
+
+    /// Get a snapshot of the latest published version
+    pub fn snapshot(&self) -> CorpusSnapshot {
+        // A panicking writer never leaves a half-swapped version, so poisoning is harmless
+        let current = self.current.read().unwrap_or_else(PoisonError::into_inner);
+        CorpusSnapshot { corpus: Arc::clone(&current) }
+    }
+
+    /// Change a copy of the latest version and publish it
+    pub fn update<T>(&self, change: impl FnOnce(&mut Corpus) -> T) -> T {
+        let Ok(value) = self.try_update(|corpus| Ok::<T, Infallible>(change(corpus)));
+        value
+    }
+
+    /// Change a copy of the latest version, publishing it only if the change succeeds
+    pub fn try_update<T, E>(&self, change: impl FnOnce(&mut Corpus) -> Result<T, E>) -> Result<T, E> {
+        let _writer = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let mut next = Corpus::clone(&self.snapshot());
+        let value = change(&mut next)?;
+
+        *self.current.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(next);
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Document, DocumentId, DomainError, Term, TfIdf};
+
+    fn document(id: &str, text: &str) -> Document {
+        let mut document = Document::new(id, text);
+        document.add_terms(text.split_whitespace().map(Term::new));
+        document
+    }
+
+    #[test]
+    fn test_snapshots_are_isolated_from_updates() {
+        let mut corpus = Corpus::new("corpus1", "Test Corpus");
+        corpus.add_document(document("doc1", "rust code")).unwrap();
+        corpus.add_document(document("doc2", "go code")).unwrap();
+        corpus.add_document(document("doc3", "java tools")).unwrap();
+        corpus.build_index().unwrap();
+        let versioned = VersionedCorpus::new(corpus);
+
+        let before = versioned.snapshot();
+        versioned.update(|corpus| corpus.add_document(document("doc4", "rust tools"))).unwrap();
+        let after = versioned.snapshot();
+
+        assert_eq!(before.document_count(), 3);
+        assert_eq!(after.document_count(), 4);
+        assert!(after.revision() > before.revision());
+
+        versioned.update(|corpus| corpus.remove_document(&DocumentId::new("doc1"))).unwrap();
+        assert!(before.get_document(&DocumentId::new("doc1")).unwrap().is_some());
+        assert_eq!(before.document_frequency(&Term::new("code")), 2);
+        assert_eq!(versioned.snapshot().document_frequency(&Term::new("code")), 1);
+
+        let tfidf = TfIdf::default();
+        assert_eq!(tfidf.search(&[Term::new("rust")], &before).unwrap().len(), 1);
+        assert_eq!(tfidf.search(&[Term::new("rust")], &after).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_failed_update_is_not_published() {
+        let versioned = VersionedCorpus::new(Corpus::new("corpus1", "Test Corpus"));
+
+        let result = versioned.try_update(|corpus| {
+            corpus.add_document(document("doc1", "rust"))?;
+            corpus.add_document(document("doc1", "rust"))
+        });
+
+        assert!(matches!(result, Err(DomainError::InvalidOperation(_))));
+        assert_eq!(versioned.snapshot().document_count(), 0);
+    }
+}
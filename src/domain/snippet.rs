@@ -0,0 +1,298 @@
+// src/domain/snippet.rs
+
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
+
+use super::Token;
+use super::tf_idf::ScoredDocument;
+
+/// A query term occurrence inside a snippet
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnippetMatch {
+    /// The matched term
+    term: String,
+
+    /// Byte offset where the match starts in the document content
+    start: usize,
+
+    /// Byte offset where the match ends in the document content (exclusive)
+    end: usize,
+}
+
+impl SnippetMatch {
+    /// Create a new snippet match
+    pub fn new(term: impl Into<String>, start: usize, end: usize) -> Self {
+        Self { term: term.into(), start, end }
+    }
+
+    /// Get the matched term
+    pub fn term(&self) -> &str {
+        &self.term
+    }
+
+    /// Get the start byte offset in the document content
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Get the end byte offset in the document content
+    pub fn end(&self) -> usize {
+        self.end
+    }
+}
+
+/// The best-scoring passage of a document for a query
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snippet {
+    /// The passage text, sliced from the document content
+    text: String,
+
+    /// Byte offset where the passage starts in the document content
+    start: usize,
+
+    /// Byte offset where the passage ends in the document content (exclusive)
+    end: usize,
+
+    /// Sum of query-term weights inside the passage
+    score: f64,
+
+    /// Query term occurrences inside the passage
+    matches: Vec<SnippetMatch>,
+}
+
+impl Snippet {
+    /// Get the passage text
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Get the start byte offset in the document content
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Get the end byte offset in the document content
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// Get the passage score
+    pub fn score(&self) -> f64 {
+        self.score
+    }
+
+    /// Get the query term occurrences inside the passage
+    pub fn matches(&self) -> &[SnippetMatch] {
+        &self.matches
+    }
+
+    /// Get the passage text with every match wrapped in `open` and `close`, e.g. `<em>` and `</em>`
+    pub fn highlight(&self, open: &str, close: &str) -> String {
+        let mut highlighted = String::with_capacity(self.text.len());
+        let mut cursor = 0;
+
+        for m in &self.matches {
+            // Match offsets point into the document content; shift them into the passage
+            let start = m.start - self.start;
+            let end = m.end - self.start;
+            highlighted.push_str(&self.text[cursor..start]);
+            highlighted.push_str(open);
+            highlighted.push_str(&self.text[start..end]);
+            highlighted.push_str(close);
+            cursor = end;
+        }
+
+        highlighted.push_str(&self.text[cursor..]);
+        highlighted
+    }
+}
+
+/// A search result paired with its best-matching passage
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighlightedDocument {
+    /// The scored search result
+    scored: ScoredDocument,
+
+    /// The best passage, or `None` if the document has no tokens
+    snippet: Option<Snippet>,
+}
+
+impl HighlightedDocument {
+    /// Create a new highlighted document
+    pub fn new(scored: ScoredDocument, snippet: Option<Snippet>) -> Self {
+        Self { scored, snippet }
+    }
+
+    /// Get the scored search result
+    pub fn scored(&self) -> &ScoredDocument {
+        &self.scored
+    }
+
+    /// Get the best passage, if any
+    pub fn snippet(&self) -> Option<&Snippet> {
+        self.snippet.as_ref()
+    }
+}
+
+/// Selects the highest-scoring window of tokens in a document
+///
+/// Every token is weighted by the TF-IDF score of its term for the query,
+/// and the window of `window_size` consecutive tokens with the largest
+/// summed weight is returned. When nothing matches, the leading window is
+/// used so results still get a readable passage.
+#[derive(Debug, Clone)]
+pub struct SnippetGenerator {
+    /// Number of tokens in a passage
+    window_size: usize,
+}
+
+impl Default for SnippetGenerator {
+    fn default() -> Self {
+        Self::new(30)
+    }
+}
+
+impl SnippetGenerator {
+    /// Create a new snippet generator with the given window size in tokens
+    pub fn new(window_size: usize) -> Self {
+        Self { window_size: window_size.max(1) }
+    }
+
+    /// Get the window size in tokens
+    pub fn window_size(&self) -> usize {
+        self.window_size
+    }
+
+    /// Pair a search result with its best passage
+    pub fn highlight(&self, scored: ScoredDocument, tokens: &[Token]) -> HighlightedDocument {
+        let snippet = self.generate_for(&scored, tokens);
+        HighlightedDocument::new(scored, snippet)
+    }
+
+    /// Generate a snippet for a search result, weighting tokens by the result's term scores
+    pub fn generate_for(&self, scored: &ScoredDocument, tokens: &[Token]) -> Option<Snippet> {
+        let term_weights: HashMap<String, f64> = scored.term_scores()
+            .iter()
+            .map(|score| (score.term().text().to_string(), score.score()))
+            .collect();
+
+        self.generate(scored.document().content(), tokens, &term_weights)
+    }
+
+    /// Generate a snippet from content and its tokens, given per-term weights
+    ///
+    /// `tokens` must have been produced from `content`, so that their offsets
+    /// point into it. Returns `None` if there are no tokens.
+    pub fn generate(
+        &self,
+        content: &str,
+        tokens: &[Token],
+        term_weights: &HashMap<String, f64>,
+    ) -> Option<Snippet> {
+        if tokens.is_empty() {
+            return None;
+        }
+
+        let weight = |token: &Token| term_weights.get(token.text()).copied().unwrap_or(0.0);
+        let window = self.window_size.min(tokens.len());
+
+        // Slide the window over the token stream, keeping a running sum
+        let mut window_score: f64 = tokens[..window].iter().map(weight).sum();
+        let mut best_score = window_score;
+        let mut best_start = 0;
+
+        for start in 1..=(tokens.len() - window) {
+            window_score += weight(&tokens[start + window - 1]) - weight(&tokens[start - 1]);
+            if window_score > best_score {
+                best_score = window_score;
+                best_start = start;
+            }
+        }
+
+        let passage = &tokens[best_start..best_start + window];
+        let start = passage[0].start();
+        let end = passage[window - 1].end();
+
+        let matches = passage.iter()
+            .filter(|token| term_weights.contains_key(token.text()))
+            .map(|token| SnippetMatch::new(token.text(), token.start(), token.end()))
+            .collect();
+
+        Some(Snippet {
+            text: content.get(start..end).unwrap_or_default().to_string(),
+            start,
+            end,
+            score: best_score,
+            matches,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens_of(content: &str) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        let mut offset = 0;
+        for (position, word) in content.split(' ').enumerate() {
+            tokens.push(Token::new(word, position, offset, offset + word.len()));
+            offset += word.len() + 1;
+        }
+        tokens
+    }
+
+    #[test]
+    fn test_best_window_selected() {
+        let content = "alpha beta gamma delta rust tfidf rust epsilon zeta";
+        let tokens = tokens_of(content);
+
+        let mut weights = HashMap::new();
+        weights.insert("rust".to_string(), 1.0);
+        weights.insert("tfidf".to_string(), 2.0);
+
+        let snippet = SnippetGenerator::new(3).generate(content, &tokens, &weights).unwrap();
+        assert_eq!(snippet.text(), "rust tfidf rust");
+        assert!((snippet.score() - 4.0).abs() < f64::EPSILON);
+        assert_eq!(snippet.matches().len(), 3);
+
+        let first = &snippet.matches()[0];
+        assert_eq!(&content[first.start()..first.end()], "rust");
+    }
+
+    #[test]
+    fn test_highlight() {
+        let content = "alpha beta rust gamma tfidf";
+        let tokens = tokens_of(content);
+
+        let mut weights = HashMap::new();
+        weights.insert("rust".to_string(), 1.0);
+        weights.insert("tfidf".to_string(), 1.0);
+
+        let snippet = SnippetGenerator::new(3).generate(content, &tokens, &weights).unwrap();
+        assert_eq!(snippet.highlight("[", "]"), "[rust] gamma [tfidf]");
+
+        let plain = SnippetGenerator::new(2).generate(content, &tokens, &HashMap::new()).unwrap();
+        assert_eq!(plain.highlight("[", "]"), "alpha beta");
+    }
+
+    #[test]
+    fn test_no_matches_uses_leading_window() {
+        let content = "one two three four";
+        let tokens = tokens_of(content);
+
+        let snippet = SnippetGenerator::new(2).generate(content, &tokens, &HashMap::new()).unwrap();
+        assert_eq!(snippet.text(), "one two");
+        assert!(snippet.matches().is_empty());
+    }
+
+    #[test]
+    fn test_window_larger_than_document() {
+        let content = "short text";
+        let tokens = tokens_of(content);
+
+        let snippet = SnippetGenerator::new(10).generate(content, &tokens, &HashMap::new()).unwrap();
+        assert_eq!(snippet.text(), "short text");
+        assert!(SnippetGenerator::new(10).generate("", &[], &HashMap::new()).is_none());
+    }
+}
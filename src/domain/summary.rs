@@ -0,0 +1,201 @@
+// src/domain/summary.rs
+
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
+
+use super::Token;
+
+/// A sentence picked for a summary
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SummarySentence {
+    /// The sentence text, sliced from the document content
+    text: String,
+
+    /// Byte offset where the sentence starts in the document content
+    start: usize,
+
+    /// Byte offset where the sentence ends in the document content (exclusive)
+    end: usize,
+
+    /// Sum of the term weights of the sentence's tokens
+    score: f64,
+}
+
+impl SummarySentence {
+    /// Get the sentence text
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Get the start byte offset in the document content
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Get the end byte offset in the document content
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// Get the sentence score
+    pub fn score(&self) -> f64 {
+        self.score
+    }
+}
+
+/// An extractive summary: the best sentences of a document, in their original order
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Summary {
+    sentences: Vec<SummarySentence>,
+}
+
+impl Summary {
+    /// Get the selected sentences in document order
+    pub fn sentences(&self) -> &[SummarySentence] {
+        &self.sentences
+    }
+
+    /// Check if no sentence was selected
+    pub fn is_empty(&self) -> bool {
+        self.sentences.is_empty()
+    }
+
+    /// Join the selected sentences with single spaces
+    pub fn text(&self) -> String {
+        self.sentences.iter().map(SummarySentence::text).collect::<Vec<_>>().join(" ")
+    }
+}
+
+/// Builds extractive summaries by scoring sentences with per-term weights
+///
+/// Content is split into sentences at `.`, `!` and `?` followed by whitespace,
+/// and at blank lines. Each sentence scores the summed weight of its tokens,
+/// typically the document's TF-IDF weights, and the `k` best are kept.
+#[derive(Debug, Clone, Default)]
+pub struct Summarizer;
+
+impl Summarizer {
+    /// Create a summarizer
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Summarize content with its tokens, keeping the `k` highest-scoring sentences
+    ///
+    /// `tokens` must have been produced from `content`, so that their offsets
+    /// point into it. Ties go to the earlier sentence.
+    pub fn summarize(
+        &self,
+        content: &str,
+        tokens: &[Token],
+        term_weights: &HashMap<String, f64>,
+        k: usize,
+    ) -> Summary {
+        let mut sentences: Vec<SummarySentence> = split_sentences(content)
+            .into_iter()
+            .map(|(start, end)| SummarySentence {
+                text: content[start..end].to_string(),
+                start,
+                end,
+                score: 0.0,
+            })
+            .collect();
+
+        for token in tokens {
+            let Some(weight) = term_weights.get(token.text()) else { continue };
+            // Sentences are sorted and disjoint, so the owner is the last one starting at or before the token
+            let index = sentences.partition_point(|sentence| sentence.start <= token.start());
+            if let Some(sentence) = index.checked_sub(1).map(|index| &mut sentences[index])
+                && token.start() < sentence.end
+            {
+                sentence.score += weight;
+            }
+        }
+
+        let mut ranked: Vec<usize> = (0..sentences.len()).collect();
+        ranked.sort_by(|&a, &b| {
+            sentences[b].score.partial_cmp(&sentences[a].score).unwrap_or(std::cmp::Ordering::Equal).then(a.cmp(&b))
+        });
+        ranked.truncate(k);
+        ranked.sort_unstable();
+
+        Summary {
+            sentences: ranked.into_iter().map(|index| sentences[index].clone()).collect(),
+        }
+    }
+}
+
+/// Split content into trimmed, non-empty sentence byte ranges
+fn split_sentences(content: &str) -> Vec<(usize, usize)> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = content.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        let next = chars.peek().map(|&(_, next)| next);
+        let end = match c {
+            '.' | '!' | '?' if next.is_none_or(char::is_whitespace) => index + c.len_utf8(),
+            '\n' if next == Some('\n') || next == Some('\r') => index,
+            _ => continue,
+        };
+        push_trimmed(content, start, end, &mut sentences);
+        start = end;
+    }
+    push_trimmed(content, start, content.len(), &mut sentences);
+
+    sentences
+}
+
+fn push_trimmed(content: &str, start: usize, end: usize, sentences: &mut Vec<(usize, usize)>) {
+    let text = &content[start..end];
+    let trimmed = text.trim();
+    if !trimmed.is_empty() {
+        let offset = start + (text.len() - text.trim_start().len());
+        sentences.push((offset, offset + trimmed.len()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens_of(content: &str) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        let mut word_start = None;
+        for (index, c) in content.char_indices().chain([(content.len(), ' ')]) {
+            match (c.is_alphanumeric(), word_start) {
+                (true, None) => word_start = Some(index),
+                (false, Some(start)) => {
+                    tokens.push(Token::new(&content[start..index], tokens.len(), start, index));
+                    word_start = None;
+                },
+                _ => {},
+            }
+        }
+        tokens
+    }
+
+    #[test]
+    fn test_top_sentences_in_original_order() {
+        let content = "Rust is fast. The weather is nice!\n\nRust has safe memory. Nothing here?";
+        let tokens = tokens_of(content);
+        let weights = HashMap::from([
+            ("Rust".to_string(), 1.0),
+            ("safe".to_string(), 2.0),
+            ("memory".to_string(), 1.0),
+        ]);
+
+        let summary = Summarizer::new().summarize(content, &tokens, &weights, 2);
+        assert_eq!(summary.text(), "Rust is fast. Rust has safe memory.");
+        assert_eq!(summary.sentences()[1].score(), 4.0);
+        assert_eq!(&content[summary.sentences()[0].start()..summary.sentences()[0].end()], "Rust is fast.");
+    }
+
+    #[test]
+    fn test_split_sentences() {
+        let content = "Version 1.5 is out. Really?  Yes\n\nHeadline\nbody";
+        let sentences: Vec<&str> = split_sentences(content).into_iter().map(|(s, e)| &content[s..e]).collect();
+        assert_eq!(sentences, vec!["Version 1.5 is out.", "Really?", "Yes", "Headline\nbody"]);
+        assert!(Summarizer::new().summarize("", &[], &HashMap::new(), 3).is_empty());
+    }
+}
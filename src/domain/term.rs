@@ -1,24 +1,33 @@
 use std::hash::Hash;
+use std::sync::Arc;
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+use super::{term_interner, DomainResult};
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub struct TermId(pub String);
+/// Numeric ID of an interned term text, unique within the process
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct TermId(pub u32);
 
 impl TermId {
-    pub fn new(id: impl Into<String>) -> Self {
-        Self(id.into())
+    pub fn new(id: u32) -> Self {
+        Self(id)
     }
 
-    pub fn value(&self) -> &str {
-        &self.0
+    pub fn value(&self) -> u32 {
+        self.0
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// A word of a document or query
+///
+/// The text is interned, so equal terms share one copy of it and maps keyed by
+/// `Term` hash and compare their `TermId` rather than the text.
+#[derive(Debug, Clone)]
 pub struct Term {
-    text: String,
+    id: TermId,
+
+    text: Arc<str>,
 
     is_stopword: bool,
 
@@ -27,19 +36,62 @@ pub struct Term {
     stem: Option<String>
 }
 
+/// How a term is serialized: by text, as IDs do not outlive the process
+#[derive(Serialize)]
+struct StoredTermRef<'a> {
+    text: &'a str,
+    is_stopword: bool,
+    stem: Option<&'a str>,
+}
+
+#[derive(Deserialize)]
+struct StoredTerm {
+    text: String,
+    is_stopword: bool,
+    stem: Option<String>,
+}
+
+impl Serialize for Term {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        StoredTermRef { text: &self.text, is_stopword: self.is_stopword, stem: self.stem.as_deref() }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Term {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let stored = StoredTerm::deserialize(deserializer)?;
+        let mut term = Term::try_new(stored.text).map_err(serde::de::Error::custom)?;
+        term.is_stopword = stored.is_stopword;
+        term.stem = stored.stem;
+        Ok(term)
+    }
+}
+
 
 impl Term {
 
-    pub fn new(text: impl Into<String>) -> Self {
-        Self {
-            text: text.into(),
+    /// Create a term, interning its text
+    ///
+    /// # Panics
+    ///
+    /// Panics if the text is new and every `TermId` is in use; `try_new` returns the error instead.
+    pub fn new(text: impl AsRef<str>) -> Self {
+        Self::try_new(text).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Create a term, failing if its text is new and every `TermId` is in use
+    pub fn try_new(text: impl AsRef<str>) -> DomainResult<Self> {
+        let (id, text) = term_interner::intern(text.as_ref())?;
+        Ok(Self {
+            id,
+            text,
             is_stopword: false,
             stem: None
-        }
+        })
     }
 
-
-    pub fn stopword(text: impl Into<String>) -> Self {
+    /// Create a stopword term, panicking like `new` if no `TermId` is left
+    pub fn stopword(text: impl AsRef<str>) -> Self {
         let mut term = Self::new(text);
 
         term.is_stopword = true;
@@ -47,14 +99,18 @@ impl Term {
         term
     }
 
-    pub fn with_stem(text: impl Into<String>, stem: impl Into<String>) -> Self {
-        let text = text.into();
-        let stem = stem.into();
-        Self {
-            text,
-            is_stopword: false,
-            stem: Some(stem)
-        }
+    /// Create a term with a stem, panicking like `new` if no `TermId` is left
+    pub fn with_stem(text: impl AsRef<str>, stem: impl Into<String>) -> Self {
+        let mut term = Self::new(text);
+
+        term.stem = Some(stem.into());
+
+        term
+    }
+
+    /// Get the interned ID of the term text
+    pub fn id(&self) -> TermId {
+        self.id
     }
 
     /// Get the term text
@@ -93,7 +149,7 @@ impl Term {
 
 impl PartialEq for Term {
     fn eq(&self, other: &Self) -> bool {
-        self.text == other.text
+        self.id == other.id
     }
 }
 
@@ -104,7 +160,7 @@ impl Eq for Term {
 impl Hash for Term {
 
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.text.hash(state);
+        self.id.hash(state);
     }
 }
 
@@ -138,7 +194,66 @@ impl From<usize> for TermFrequency {
     }
 }
 
+/// Serde adapter storing a map keyed by `Term` as a list of (term, value) pairs
+///
+/// Formats such as JSON only allow string map keys, and a `Term` is a struct.
+pub(crate) mod term_map {
+    use std::collections::HashMap;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::Term;
+
+    pub fn serialize<V: Serialize, S: Serializer>(map: &HashMap<Term, V>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(map.iter())
+    }
+
+    pub fn deserialize<'de, V: Deserialize<'de>, D: Deserializer<'de>>(deserializer: D) -> Result<HashMap<Term, V>, D::Error> {
+        Vec::<(Term, V)>::deserialize(deserializer).map(|pairs| pairs.into_iter().collect())
+    }
+}
+
+/// Serde adapter storing a shared map keyed by `Term` like `term_map`
+pub(crate) mod shared_term_map {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{term_map, Term};
+
+    pub fn serialize<V: Serialize, S: Serializer>(map: &Arc<HashMap<Term, V>>, serializer: S) -> Result<S::Ok, S::Error> {
+        term_map::serialize(map, serializer)
+    }
+
+    pub fn deserialize<'de, V: Deserialize<'de>, D: Deserializer<'de>>(deserializer: D) -> Result<Arc<HashMap<Term, V>>, D::Error> {
+        term_map::deserialize(deserializer).map(Arc::new)
+    }
+}
+
+/// Serde adapter for named maps keyed by `Term`, e.g. per-field term frequencies
+pub(crate) mod named_term_maps {
+    use std::collections::HashMap;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::Term;
+
+    pub fn serialize<V: Serialize, S: Serializer>(
+        maps: &HashMap<String, HashMap<Term, V>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(maps.iter().map(|(name, map)| (name, map.iter().collect::<Vec<_>>())))
+    }
+
+    pub fn deserialize<'de, V: Deserialize<'de>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<String, HashMap<Term, V>>, D::Error> {
+        HashMap::<String, Vec<(Term, V)>>::deserialize(deserializer).map(|maps| {
+            maps.into_iter().map(|(name, pairs)| (name, pairs.into_iter().collect())).collect()
+        })
+    }
+}
+
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
 
@@ -146,6 +261,8 @@ mod tests {
     fn test_term_creation() {
         let term = Term::new("test");
         assert_eq!(term.text(), "test");
+        assert_eq!(term.id(), Term::new(String::from("test")).id());
+        assert_ne!(term.id(), Term::new("other").id());
         assert_eq!(term.is_stopword(), false);
         assert_eq!(term.stem(), None);
         assert_eq!(term.canonical(), "test");
@@ -0,0 +1,175 @@
+// src/domain/term_interner.rs
+
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, PoisonError, RwLock};
+
+use super::{DomainError, DomainResult, TermId};
+
+/// A table assigning each distinct term text a numeric `TermId`
+///
+/// Every `Term` is interned in the process-wide table returned by `global`, so
+/// terms share one copy of their text and hash and compare by ID. IDs are only
+/// meaningful within one process: terms are always stored by text.
+///
+/// Texts no `Term` refers to any more are evicted as the table grows, and
+/// their IDs reused, so the table stays proportional to the live terms.
+#[derive(Debug, Default)]
+pub struct TermInterner {
+    /// ID of each interned text
+    ids: HashMap<Arc<str>, TermId>,
+
+    /// Interned texts, indexed by ID (None = evicted, ID free for reuse)
+    texts: Vec<Option<Arc<str>>>,
+
+    /// IDs of evicted texts, reused before new ones are assigned
+    free: Vec<TermId>,
+
+    /// Number of texts at which unused ones are next evicted
+    sweep_at: usize,
+}
+
+/// Fewest texts at which the interner starts evicting unused ones
+const MIN_SWEEP: usize = 1024;
+
+static GLOBAL: OnceLock<RwLock<TermInterner>> = OnceLock::new();
+
+impl TermInterner {
+    /// Create an empty interner
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the process-wide interner used by `Term`
+    pub fn global() -> &'static RwLock<TermInterner> {
+        GLOBAL.get_or_init(|| RwLock::new(TermInterner::new()))
+    }
+
+    /// Get the ID and shared text of a text, interning it if it is new
+    ///
+    /// Fails if every `TermId` is taken by a text still in use.
+    pub fn intern(&mut self, text: &str) -> DomainResult<(TermId, Arc<str>)> {
+        if let Some((text, id)) = self.ids.get_key_value(text) {
+            return Ok((*id, Arc::clone(text)));
+        }
+
+        if self.ids.len() >= self.sweep_at {
+            self.evict_unused();
+            self.sweep_at = (self.ids.len() * 2).max(MIN_SWEEP);
+        }
+
+        let text: Arc<str> = Arc::from(text);
+        let id = match self.free.pop() {
+            Some(id) => {
+                self.texts[id.value() as usize] = Some(Arc::clone(&text));
+                id
+            },
+            None => {
+                let id = u32::try_from(self.texts.len()).map_err(|_| DomainError::InvalidOperation(
+                    format!("Cannot intern '{}': more than {} distinct terms are in use", text, u32::MAX)
+                ))?;
+                self.texts.push(Some(Arc::clone(&text)));
+                TermId::new(id)
+            }
+        };
+        self.ids.insert(Arc::clone(&text), id);
+        Ok((id, text))
+    }
+
+    /// Drop the texts only the interner still refers to, returning how many were evicted
+    ///
+    /// Their IDs are handed out again to new texts, so a `TermId` kept apart
+    /// from its `Term` may later name a different text.
+    pub fn evict_unused(&mut self) -> usize {
+        let before = self.ids.len();
+        // The interner holds two references to each text: its key in `ids` and its slot in `texts`
+        self.ids.retain(|text, id| {
+            let used = Arc::strong_count(text) > 2;
+            if !used {
+                self.texts[id.value() as usize] = None;
+                self.free.push(*id);
+            }
+            used
+        });
+        before - self.ids.len()
+    }
+
+    /// Get the ID of a text, if it has been interned
+    pub fn get(&self, text: &str) -> Option<TermId> {
+        self.ids.get(text).copied()
+    }
+
+    /// Get the text of an ID, if it was assigned by this interner
+    pub fn resolve(&self, id: TermId) -> Option<&str> {
+        self.texts.get(id.value() as usize)?.as_deref()
+    }
+
+    /// Get the number of interned texts
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Check if no text has been interned
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+}
+
+/// Intern a text in the global interner, taking the write lock only for new texts
+pub(super) fn intern(text: &str) -> DomainResult<(TermId, Arc<str>)> {
+    let interner = TermInterner::global();
+    // An interrupted insert leaves at most an unused entry, so poisoning is harmless
+    if let Some((text, id)) = interner.read().unwrap_or_else(PoisonError::into_inner).ids.get_key_value(text) {
+        return Ok((*id, Arc::clone(text)));
+    }
+
+    interner.write().unwrap_or_else(PoisonError::into_inner).intern(text)
+}
+
+/// Get the ID of a text in the global interner, without interning it
+pub(super) fn lookup(text: &str) -> Option<TermId> {
+    TermInterner::global().read().unwrap_or_else(PoisonError::into_inner).get(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intern() {
+        let mut interner = TermInterner::new();
+
+        let (rust, text) = interner.intern("rust").unwrap();
+        let (go, _) = interner.intern("go").unwrap();
+        let (again, shared) = interner.intern("rust").unwrap();
+
+        assert_eq!(rust, again);
+        assert_ne!(rust, go);
+        assert!(Arc::ptr_eq(&text, &shared));
+        assert_eq!(interner.get("go"), Some(go));
+        assert_eq!(interner.get("java"), None);
+        assert_eq!(interner.resolve(rust), Some("rust"));
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn test_evict_unused() {
+        let mut interner = TermInterner::new();
+
+        let (rust, text) = interner.intern("rust").unwrap();
+        let (go, _) = interner.intern("go").unwrap();
+        assert_eq!(interner.evict_unused(), 1);
+        assert_eq!(interner.get("go"), None);
+        assert_eq!(interner.resolve(go), None);
+        assert_eq!(interner.resolve(rust), Some(&*text));
+
+        // The evicted ID is reused for the next new text
+        let (java, _) = interner.intern("java").unwrap();
+        assert_eq!(java, go);
+        assert_eq!(interner.len(), 2);
+
+        // Terms keep their texts alive in the global interner
+        let term = crate::domain::Term::new("interner-eviction-test");
+        TermInterner::global().write().unwrap().evict_unused();
+        assert_eq!(TermInterner::global().read().unwrap().get(term.text()), Some(term.id()));
+    }
+}
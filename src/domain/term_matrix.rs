@@ -0,0 +1,153 @@
+// src/domain/term_matrix.rs
+
+use std::collections::{BTreeSet, HashMap};
+use serde::{Serialize, Deserialize};
+
+use super::DocumentId;
+
+/// Sparse document-term matrix of TF-IDF weights, in compressed sparse row (CSR) form
+///
+/// Rows are documents ordered by ID and columns are terms in alphabetical order,
+/// so exports of the same corpus state are byte-for-byte reproducible.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DocumentTermMatrix {
+    /// Document IDs in row order
+    document_ids: Vec<DocumentId>,
+
+    /// Term texts in column order
+    terms: Vec<String>,
+
+    /// Start of each row in `columns`/`values`, with a final entry equal to the number of non-zeros
+    row_offsets: Vec<usize>,
+
+    /// Column of each non-zero value, ascending within a row
+    columns: Vec<usize>,
+
+    /// Non-zero weights
+    values: Vec<f64>,
+}
+
+impl DocumentTermMatrix {
+    /// Build a matrix from sparse document vectors, dropping zero weights
+    pub fn from_vectors<'a>(
+        vectors: impl IntoIterator<Item = (&'a DocumentId, &'a HashMap<String, f64>)>,
+    ) -> Self {
+        let mut vectors: Vec<_> = vectors.into_iter().collect();
+        vectors.sort_by(|(a, _), (b, _)| a.value().cmp(b.value()));
+
+        let terms: Vec<String> = vectors.iter()
+            .flat_map(|(_, vector)| vector.iter())
+            .filter(|(_, weight)| **weight != 0.0)
+            .map(|(term, _)| term.clone())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let positions: HashMap<&str, usize> = terms.iter()
+            .enumerate()
+            .map(|(column, term)| (term.as_str(), column))
+            .collect();
+
+        let mut matrix = Self {
+            document_ids: Vec::with_capacity(vectors.len()),
+            row_offsets: vec![0],
+            ..Self::default()
+        };
+        for (id, vector) in vectors {
+            let mut row: Vec<(usize, f64)> = vector.iter()
+                .filter(|(_, weight)| **weight != 0.0)
+                .map(|(term, weight)| (positions[term.as_str()], *weight))
+                .collect();
+            row.sort_by_key(|(column, _)| *column);
+
+            matrix.document_ids.push(id.clone());
+            for (column, weight) in row {
+                matrix.columns.push(column);
+                matrix.values.push(weight);
+            }
+            matrix.row_offsets.push(matrix.values.len());
+        }
+        matrix.terms = terms;
+
+        matrix
+    }
+
+    /// Get the document IDs in row order
+    pub fn document_ids(&self) -> &[DocumentId] {
+        &self.document_ids
+    }
+
+    /// Get the terms in column order
+    pub fn terms(&self) -> &[String] {
+        &self.terms
+    }
+
+    /// Get the number of rows (documents)
+    pub fn rows(&self) -> usize {
+        self.document_ids.len()
+    }
+
+    /// Get the number of columns (terms)
+    pub fn columns(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// Get the number of non-zero weights
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Get the start of each row in the column and value arrays, plus the end of the last row
+    pub fn row_offsets(&self) -> &[usize] {
+        &self.row_offsets
+    }
+
+    /// Get the column of each non-zero weight
+    pub fn column_indices(&self) -> &[usize] {
+        &self.columns
+    }
+
+    /// Get the non-zero weights, row by row
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Get the non-zero (column, weight) pairs of a row
+    pub fn row(&self, row: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        let range = self.row_offsets[row]..self.row_offsets[row + 1];
+        self.columns[range.clone()].iter().copied().zip(self.values[range].iter().copied())
+    }
+
+    /// Get a row with zeros filled in, one weight per term
+    pub fn dense_row(&self, row: usize) -> Vec<f64> {
+        let mut dense = vec![0.0; self.columns()];
+        for (column, weight) in self.row(row) {
+            dense[column] = weight;
+        }
+        dense
+    }
+
+    /// Iterate over the non-zero weights as (row, column, weight) triples
+    pub fn triples(&self) -> impl Iterator<Item = (usize, usize, f64)> + '_ {
+        (0..self.rows()).flat_map(move |row| self.row(row).map(move |(column, weight)| (row, column, weight)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_vectors() {
+        let b = DocumentId::new("b");
+        let a = DocumentId::new("a");
+        let vector_b = HashMap::from([("zeta".to_string(), 0.5), ("alpha".to_string(), 0.0)]);
+        let vector_a = HashMap::from([("zeta".to_string(), 0.25), ("beta".to_string(), 1.0)]);
+
+        let matrix = DocumentTermMatrix::from_vectors([(&b, &vector_b), (&a, &vector_a)]);
+        assert_eq!(matrix.document_ids(), &[a, b]);
+        assert_eq!(matrix.terms(), &["beta", "zeta"]);
+        assert_eq!(matrix.row_offsets(), &[0, 2, 3]);
+        assert_eq!(matrix.dense_row(1), vec![0.0, 0.5]);
+        assert_eq!(matrix.triples().collect::<Vec<_>>(), vec![(0, 0, 1.0), (0, 1, 0.25), (1, 1, 0.5)]);
+    }
+}
@@ -1,9 +1,13 @@
 // src/domain/tf_idf.rs
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
 use serde::{Serialize, Deserialize};
 
-use super::{Document, Corpus, Term, DomainError, DomainResult};
+use super::{Document, DocumentId, DocumentTermMatrix, Corpus, Facets, FacetedResults, GlobalStats, IdfModel, Page, SearchRequest, Term, DomainError, DomainResult};
+use super::ranking::{boosted_term_frequency, RankingModel, Scorer, ScoringContext};
+use super::weighting::{Normalization, Scheme, Smoothing};
+use super::vector_index::{CachedVectorIndex, VectorIndex, SimilarDocument, SimilarityMatrix, cosine_similarity};
 
 /// Error type specific to TF-IDF operations
 #[derive(Debug, thiserror::Error)]
@@ -71,8 +75,8 @@ impl PartialOrd for TfIdfScore {
 /// A document with its relevance score for a query
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ScoredDocument {
-    /// The document
-    document: Document,
+    /// The document, shared with the corpus it was found in
+    document: Arc<Document>,
     
     /// The overall relevance score
     score: f64,
@@ -83,8 +87,8 @@ pub struct ScoredDocument {
 
 impl ScoredDocument {
     /// Create a new scored document
-    pub fn new(document: Document, score: f64, term_scores: Vec<TfIdfScore>) -> Self {
-        Self { document, score, term_scores }
+    pub fn new(document: impl Into<Arc<Document>>, score: f64, term_scores: Vec<TfIdfScore>) -> Self {
+        Self { document: document.into(), score, term_scores }
     }
     
     /// Get the document
@@ -92,6 +96,11 @@ impl ScoredDocument {
         &self.document
     }
     
+    /// Get a shared handle to the document
+    pub fn shared_document(&self) -> &Arc<Document> {
+        &self.document
+    }
+    
     /// Get the overall relevance score
     pub fn score(&self) -> f64 {
         self.score
@@ -117,12 +126,64 @@ impl PartialOrd for ScoredDocument {
     }
 }
 
+/// A lightweight search result referring to its document by ID
+///
+/// Unlike `ScoredDocument`, this does not copy the document's content or term map.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchHit {
+    /// The ID of the matching document
+    document_id: DocumentId,
+    
+    /// The overall relevance score
+    score: f64,
+    
+    /// Individual term scores that contributed to the overall score
+    term_scores: Vec<TfIdfScore>,
+}
+
+impl SearchHit {
+    /// Create a new search hit
+    pub fn new(document_id: DocumentId, score: f64, term_scores: Vec<TfIdfScore>) -> Self {
+        Self { document_id, score, term_scores }
+    }
+    
+    /// Get the ID of the matching document
+    pub fn document_id(&self) -> &DocumentId {
+        &self.document_id
+    }
+    
+    /// Get the overall relevance score
+    pub fn score(&self) -> f64 {
+        self.score
+    }
+    
+    /// Get the individual term scores
+    pub fn term_scores(&self) -> &[TfIdfScore] {
+        &self.term_scores
+    }
+    
+    /// Get the most important terms (highest TF-IDF scores)
+    pub fn top_terms(&self, limit: usize) -> Vec<&TfIdfScore> {
+        let mut scores = self.term_scores.iter().collect::<Vec<_>>();
+        scores.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        scores.truncate(limit);
+        scores
+    }
+}
+
+impl PartialOrd for SearchHit {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.score.partial_cmp(&other.score)
+    }
+}
+
 /// Options for TF-IDF calculation
 /// Options for TF-IDF calculation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TfIdfOptions {
-    /// Whether to apply smoothing to prevent zero IDF scores
-    pub apply_smoothing: bool,
+    /// How to smooth IDF scores
+    #[serde(default)]
+    pub smoothing: Smoothing,
     
     /// Whether to normalize TF-IDF vectors
     pub normalize: bool,
@@ -132,6 +193,10 @@ pub struct TfIdfOptions {
     
     /// Whether to filter out stopwords
     pub filter_stopwords: bool,
+
+    /// Whether to also skip terms listed in the corpus's own stopwords
+    #[serde(default)]
+    pub use_corpus_stopwords: bool,
     
     /// Custom TF weighting function (None = use default)
     #[serde(skip)]
@@ -140,17 +205,86 @@ pub struct TfIdfOptions {
     /// Custom IDF weighting function (None = use default)
     #[serde(skip)]
     pub idf_weighting: Option<fn(usize, usize) -> f64>,
+    
+    /// Ranking function used to score terms
+    #[serde(default)]
+    pub ranking: RankingModel,
+    
+    /// Weight of a term occurrence in each named field, relative to one in the content
+    ///
+    /// A boost of 2.0 for `Document::TITLE_FIELD` makes a title hit count twice
+    /// as much as a content hit. Fields without a boost do not affect scores;
+    /// `Document::CONTENT_FIELD` defaults to a boost of 1.0.
+    /// Custom `tf_weighting` functions receive the weighted count rounded to the
+    /// nearest integer.
+    #[serde(default)]
+    pub field_boosts: HashMap<String, f64>,
+    
+    /// Whether to score terms by their canonical form (stem), so inflections of a word count together
+    #[serde(default)]
+    pub aggregate_stems: bool,
+    
+    /// SMART weighting scheme (None = use `use_log_tf`, `smoothing` and `normalize`)
+    ///
+    /// When set, it replaces those three flags; custom weighting functions still take precedence.
+    #[serde(default)]
+    pub scheme: Option<Scheme>,
+    
+    /// Terms scoring is restricted to, by text (None = every term)
+    ///
+    /// Fixing the vocabulary, e.g. to one exported from a training corpus, keeps
+    /// vectors comparable across corpora and runs.
+    #[serde(default)]
+    pub fixed_vocabulary: Option<Arc<BTreeSet<String>>>,
 }
 
 impl Default for TfIdfOptions {
     fn default() -> Self {
         Self {
-            apply_smoothing: true,
+            smoothing: Smoothing::AddOne,
             normalize: true,
             use_log_tf: true,
             filter_stopwords: true,
+            use_corpus_stopwords: false,
             tf_weighting: None,
             idf_weighting: None,
+            ranking: RankingModel::TfIdf,
+            field_boosts: HashMap::new(),
+            aggregate_stems: false,
+            scheme: None,
+            fixed_vocabulary: None,
+        }
+    }
+}
+
+impl TfIdfOptions {
+    /// Create options weighting terms with a SMART scheme
+    pub fn with_scheme(scheme: Scheme) -> Self {
+        Self { scheme: Some(scheme), ..Self::default() }
+    }
+    
+    /// Restrict scoring to a fixed set of terms
+    pub fn with_fixed_vocabulary(mut self, terms: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.fixed_vocabulary = Some(Arc::new(terms.into_iter().map(Into::into).collect()));
+        self
+    }
+    
+    /// Check that the options describe a valid weighting
+    pub fn validate(&self) -> DomainResult<()> {
+        self.normalization().validate()?;
+        self.smoothing.validate()?;
+        match self.ranking {
+            RankingModel::Bm25(bm25) => bm25.validate(),
+            RankingModel::TfIdf => Ok(()),
+        }
+    }
+    
+    /// Get how document vectors are scaled
+    fn normalization(&self) -> Normalization {
+        match self.scheme {
+            Some(scheme) => scheme.normalization,
+            None if self.normalize => Normalization::Cosine,
+            None => Normalization::None,
         }
     }
 }
@@ -160,6 +294,15 @@ impl Default for TfIdfOptions {
 pub struct TfIdf {
     /// Options for TF-IDF calculation
     options: TfIdfOptions,
+    
+    /// Custom scorer replacing the built-in ranking model, if any
+    scorer: Option<Arc<dyn Scorer>>,
+
+    /// Statistics shared across corpora to compute IDF from, if any
+    global_stats: Option<Arc<GlobalStats>>,
+
+    /// Vector index of the corpus compared last, for similarity queries
+    vector_index: CachedVectorIndex,
 }
 
 impl Default for TfIdf {
@@ -171,7 +314,14 @@ impl Default for TfIdf {
 impl TfIdf {
     /// Create a new TF-IDF calculator with the given options
     pub fn new(options: TfIdfOptions) -> Self {
-        Self { options }
+        Self { options, scorer: None, global_stats: None, vector_index: CachedVectorIndex::default() }
+    }
+    
+    /// Create a calculator that delegates term scoring to a custom scorer
+    ///
+    /// Stopword filtering and normalization still follow `options`.
+    pub fn with_scorer(options: TfIdfOptions, scorer: Arc<dyn Scorer>) -> Self {
+        Self { options, scorer: Some(scorer), global_stats: None, vector_index: CachedVectorIndex::default() }
     }
     
     /// Get the current options
@@ -182,6 +332,43 @@ impl TfIdf {
     /// Update the options
     pub fn set_options(&mut self, options: TfIdfOptions) {
         self.options = options;
+        self.vector_index.clear();
+    }
+    
+    /// Replace the scorer used for ranking (None = use the built-in ranking model)
+    pub fn set_scorer(&mut self, scorer: Option<Arc<dyn Scorer>>) {
+        self.scorer = scorer;
+        self.vector_index.clear();
+    }
+
+    /// Compute IDF and average document length from statistics shared across corpora (None = use each corpus's own)
+    pub fn set_global_stats(&mut self, global_stats: Option<Arc<GlobalStats>>) {
+        self.global_stats = global_stats;
+        self.vector_index.clear();
+    }
+
+    /// Get the shared statistics IDF is computed from, if any
+    pub fn global_stats(&self) -> Option<&GlobalStats> {
+        self.global_stats.as_deref()
+    }
+
+    /// Create the context for a scoring pass, applying the configured field boosts
+    fn scoring_context<'a>(&'a self, corpus: &'a Corpus) -> ScoringContext<'a> {
+        self.scoring_context_with(corpus, &self.options.field_boosts)
+    }
+
+    /// Check the options once and create the context for a scoring pass
+    fn validated_context<'a>(&'a self, corpus: &'a Corpus) -> DomainResult<ScoringContext<'a>> {
+        self.options.validate()?;
+        Ok(self.scoring_context(corpus))
+    }
+
+    /// Create the context for a scoring pass with explicit field boosts
+    fn scoring_context_with<'a>(&'a self, corpus: &'a Corpus, field_boosts: &'a HashMap<String, f64>) -> ScoringContext<'a> {
+        let mut context = ScoringContext::with_field_boosts(corpus, field_boosts);
+        context.set_aggregate_stems(self.options.aggregate_stems);
+        context.set_global_stats(self.global_stats.as_deref());
+        context
     }
 
     pub fn calculate_term_tfidf(
@@ -190,51 +377,37 @@ impl TfIdf {
         document: &Document,
         corpus: &Corpus
     ) -> DomainResult<TfIdfScore> {
-        println!("[DEBUG] At start of calculate_term_tfidf for term '{}': corpus.is_indexed() = {}", term.text(), corpus.is_indexed());
+        self.score_term_in(term, document, &self.scoring_context(corpus))
+    }
+
+    /// Check whether a term is a stopword or outside the fixed vocabulary, which the options exclude from scoring
+    fn skips(&self, term: &Term, corpus: &Corpus) -> bool {
+        (self.options.filter_stopwords && term.is_stopword())
+            || (self.options.use_corpus_stopwords && corpus.is_stopword(term.text()))
+            || self.options.fixed_vocabulary.as_ref().is_some_and(|vocabulary| !vocabulary.contains(term.text()))
+    }
+
+    /// Score a single term, sharing corpus statistics across a scoring pass
+    fn score_term_in(
+        &self,
+        term: &Term,
+        document: &Document,
+        context: &ScoringContext
+    ) -> DomainResult<TfIdfScore> {
+        let corpus = context.corpus();
         if !corpus.is_indexed() {
             return Err(DomainError::TfIdfError(TfIdfError::CorpusNotIndexed));
         }
 
         //Skip stopwords if configured to do so
-        if self.options.filter_stopwords && term.is_stopword() {
+        if self.skips(term, corpus) {
             return Err(DomainError::TfIdfError(TfIdfError::InvalidCalculation("Term is a stopword".to_string())));
         }
 
-        let tf = if let Some(tf_fn) = self.options.tf_weighting {
-            //Use custom weighting function
-            let term_count = document.term_frequency(term).0;
-            let total_terms = document.term_count();
-            tf_fn(term_count, total_terms)
-
-        } else if self.options.use_log_tf {
-            let tf_raw = document.term_frequency(term).0 as f64;
-            if tf_raw > 0.0 {
-                1.0 + tf_raw.ln()
-            } else {
-                0.0
-            }
-        } else {
-            document.normalized_term_frequency(term)
-        };
-
-        let idf = if let Some(idf_fn) = self.options.idf_weighting {
-            let doc_freq = corpus.document_frequency(term);
-            let total_docs = corpus.document_count();
-            idf_fn(doc_freq, total_docs)
-        } else {
-            let mut idf = corpus.inverse_document_frequency(term);
-
-            if self.options.apply_smoothing {
-                // Add 1 to document frequency to prevent division by zero
-                let doc_count = corpus.document_count() as f64;
-                let doc_freq = corpus.document_frequency(term) as f64 + 1.0;
-                idf = (doc_count / doc_freq).ln();
-            }
-
-            idf
-        };
-
-        Ok(TfIdfScore::new(term.clone(), tf, idf))
+        Ok(match &self.scorer {
+            Some(scorer) => scorer.score_term(term, document, context),
+            None => Scorer::score_term(self, term, document, context),
+        })
     }
 
     pub fn calculate_document_tfidf(
@@ -242,33 +415,132 @@ impl TfIdf {
         document: &Document,
         corpus: &Corpus
     ) -> DomainResult<Vec<TfIdfScore>> {
-        if !corpus.is_indexed() {
+        self.document_scores_in(document, &self.validated_context(corpus)?)
+    }
+
+    /// Score every term of a document, sharing corpus statistics across a scoring pass
+    fn document_scores_in(
+        &self,
+        document: &Document,
+        context: &ScoringContext
+    ) -> DomainResult<Vec<TfIdfScore>> {
+        if !context.corpus().is_indexed() {
             return Err(DomainError::TfIdfError(TfIdfError::CorpusNotIndexed))
         }
 
         let mut scores = Vec::new();
+        let mut seen_canonical = HashSet::new();
+
+        for term in document.unique_terms() {
+            if self.skips(term, context.corpus()) {
+                continue;
+            }
+
+            // Inflections sharing a stem score identically, so keep only one of them
+            if self.options.aggregate_stems && !seen_canonical.insert(term.canonical()) {
+                continue;
+            }
 
-        for (term, _) in document.term_frequencies() {
-            if self.options.filter_stopwords && term.is_stopword() {
+            // Terms found only in unboosted fields are not part of the vector
+            if context.term_frequency(term, document) == 0.0 {
                 continue;
             }
 
-            match self.calculate_term_tfidf(term, document, corpus) {
+            match self.score_term_in(term, document, context) {
                 Ok(score) => scores.push(score),
                 Err(DomainError::TfIdfError(TfIdfError::InvalidCalculation(_))) => {
                     continue
                 },
-                Err(e) => return Err(e) 
+                Err(e) => return Err(e)
             }
         }
 
-        if self.options.normalize {
-            self.normalize_scores(&mut scores);
+        self.finish_scores(&mut scores, document, || context.average_document_length());
+        Ok(scores)
+    }
+
+    /// Normalize a document's term scores as the options require and sort them, highest first
+    fn finish_scores(
+        &self,
+        scores: &mut [TfIdfScore],
+        document: &Document,
+        average_document_length: impl FnOnce() -> f64
+    ) {
+        let normalization = self.options.normalization();
+        if normalization == Normalization::Cosine {
+            self.normalize_scores(scores);
+        } else if let Some(divisor) = normalization.pivoted_divisor(document.term_count(), average_document_length()) {
+            for score in scores.iter_mut() {
+                score.score /= divisor;
+            }
         }
 
-       // Sort by score (highest first)
+        // Sort by score (highest first)
         scores.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
-        
+    }
+
+    /// Capture the IDF of every indexed term of a corpus, as these options weight it
+    ///
+    /// With global statistics set, document counts come from them instead of the corpus.
+    pub fn idf_model(&self, corpus: &Corpus) -> DomainResult<IdfModel> {
+        if !corpus.is_indexed() {
+            return Err(DomainError::TfIdfError(TfIdfError::CorpusNotIndexed));
+        }
+
+        let context = self.scoring_context(corpus);
+        let idf = corpus.document_frequencies()
+            .map(|(term, _)| {
+                let key = if self.options.aggregate_stems { term.canonical() } else { term.text() };
+                (key.to_string(), self.weight_idf(context.document_frequency(term), context.document_count()))
+            })
+            .collect();
+        let stopwords = if self.options.use_corpus_stopwords {
+            corpus.stopwords().cloned().collect()
+        } else {
+            BTreeSet::new()
+        };
+
+        Ok(IdfModel::new(
+            context.document_count(),
+            context.average_document_length(),
+            idf,
+            self.weight_idf(0, context.document_count()),
+            self.options.aggregate_stems,
+            stopwords
+        ))
+    }
+
+    /// Score every term of a document against a precomputed IDF model instead of a corpus, highest first
+    ///
+    /// The document need not belong to any corpus, e.g. one freshly analyzed by
+    /// a stateless service. Terms the model has never seen get the IDF of a zero
+    /// document frequency. The built-in ranking model is used even when a custom
+    /// scorer is set.
+    pub fn score_with_model(&self, document: &Document, model: &IdfModel) -> DomainResult<Vec<TfIdfScore>> {
+        self.options.validate()?;
+        let mut scores = Vec::new();
+        let mut seen = HashSet::new();
+
+        for term in document.unique_terms() {
+            let key = if model.aggregate_stems() { term.canonical() } else { term.text() };
+            if (self.options.filter_stopwords && term.is_stopword())
+                || model.is_stopword(term.text())
+                || self.options.fixed_vocabulary.as_ref().is_some_and(|vocabulary| !vocabulary.contains(term.text()))
+                || !seen.insert(key) {
+                continue;
+            }
+
+            let term_frequency = boosted_term_frequency(term, document, Some(&self.options.field_boosts), model.aggregate_stems());
+            // Terms found only in unboosted fields are not part of the vector
+            if term_frequency == 0.0 {
+                continue;
+            }
+
+            let tf = self.weight_tf(term_frequency, document, || model.average_document_length());
+            scores.push(TfIdfScore::new(term.clone(), tf, model.idf(key)));
+        }
+
+        self.finish_scores(&mut scores, document, || model.average_document_length());
         Ok(scores)
     }
 
@@ -277,49 +549,210 @@ impl TfIdf {
         query_terms: &[Term],
         corpus: &Corpus
     ) -> DomainResult<Vec<ScoredDocument>> {
+        let hits = self.search_hits(query_terms, corpus)?;
+        Self::resolve_hits(hits, corpus)
+    }
+
+    /// Search the corpus, returning document IDs and scores without cloning documents
+    pub fn search_hits(
+        &self,
+        query_terms: &[Term],
+        corpus: &Corpus
+    ) -> DomainResult<Vec<SearchHit>> {
+        let request = SearchRequest::new(query_terms.iter().cloned());
+        Ok(self.search_request(&request, corpus)?.into_results())
+    }
 
+    /// Search the corpus with a request's boosted terms, phrases, boolean query, fields and facets
+    ///
+    /// Each term's score is multiplied by its boost, while the term scores of
+    /// each hit are reported before boosting. Only documents containing every
+    /// phrase and matching the boolean query, if any, are scored. Facets count
+    /// every matching document; attach documents with `resolve_hits`.
+    pub fn search_request(&self, request: &SearchRequest, corpus: &Corpus) -> DomainResult<FacetedResults<SearchHit>> {
         if !corpus.is_indexed() {
-             return Err(DomainError::TfIdfError(TfIdfError::CorpusNotIndexed))
+            return Err(DomainError::TfIdfError(TfIdfError::CorpusNotIndexed))
         }
-        let mut results = Vec::new();
+        self.options.validate()?;
 
-        for document in corpus.documents() {
-            let mut doc_score = 0.0;
-            let mut term_scores = Vec::new();
+        let query = request.query().map(|query| query.expand_wildcards(corpus));
+        let mut query_terms = request.terms().to_vec();
+        if let Some(query) = &query {
+            query_terms.extend(query.weighted_scoring_terms());
+        }
 
-            for term in query_terms {
-                if self.options.filter_stopwords && term.is_stopword() {
-                    continue;
-                }
+        let field_boosts;
+        let context = match request.fields() {
+            Some(fields) => {
+                field_boosts = self.field_boosts_for(fields);
+                self.scoring_context_with(corpus, &field_boosts)
+            },
+            None => self.scoring_context(corpus),
+        };
 
-                match  self.calculate_term_tfidf(term, document, corpus) {
-                    Ok(score) => {
-                        doc_score += score.score();
-                        term_scores.push(score);
-                    },
-                    Err(DomainError::TfIdfError(TfIdfError::InvalidCalculation(_))) => {
-                        continue
-                    },
-                    Err(e) => return Err(e) 
+        let phrases = self.phrase_filter(request.phrases());
+        let aggregate_stems = self.options.aggregate_stems;
+        let filter = |document: &Document| {
+            phrases(document) && query.as_ref().is_none_or(|query| query.matches_with(document, aggregate_stems))
+        };
+        let facet_keys = request.facet_keys();
+        let results = self.score_documents(&query_terms, &context, filter, |document| {
+            facet_keys.iter()
+                .filter_map(|key| document.metadata().get(key).map(|value| (key.as_str(), value.clone())))
+                .collect::<Vec<_>>()
+        })?;
+
+        let mut facets = Facets::new(facet_keys.iter().map(String::as_str));
+        let hits = results.into_iter()
+            .map(|(hit, values)| {
+                for (key, value) in values {
+                    facets.add(key, &value);
                 }
-            }
+                hit
+            })
+            .collect();
 
-            if doc_score > 0.0 {
-                results.push(ScoredDocument::new(
-                    document.clone(),
-                    doc_score,
-                    term_scores
-                ));
-            }
+        Ok(FacetedResults::new(hits, facets))
+    }
+
+    /// Accept documents containing every phrase (no phrases = no restriction)
+    fn phrase_filter<'a>(&self, phrases: &'a [Vec<Term>]) -> impl Fn(&Document) -> bool + Send + Sync + 'a {
+        let aggregate_stems = self.options.aggregate_stems;
+        move |document| {
+            phrases.iter().all(|phrase| if aggregate_stems {
+                document.contains_canonical_phrase(phrase)
+            } else {
+                document.contains_phrase(phrase)
+            })
         }
+    }
+
+    /// Boost only the given fields, each by its configured boost or 1.0, and ignore the content unless listed
+    fn field_boosts_for(&self, fields: &[String]) -> HashMap<String, f64> {
+        let mut boosts: HashMap<String, f64> = fields.iter()
+            .map(|field| {
+                let boost = self.options.field_boosts.get(field).copied().unwrap_or(1.0);
+                (field.clone(), boost)
+            })
+            .collect();
+        boosts.entry(Document::CONTENT_FIELD.to_string()).or_insert(0.0);
+        boosts
+    }
+
+    /// Score every document accepted by `filter`, pairing each hit with what `extract` reads from its document
+    fn score_documents<F, G, T>(
+        &self,
+        query_terms: &[(Term, f64)],
+        context: &ScoringContext,
+        filter: F,
+        extract: G
+    ) -> DomainResult<Vec<(SearchHit, T)>>
+    where
+        F: Fn(&Document) -> bool + Send + Sync,
+        G: Fn(&Document) -> T + Send + Sync,
+        T: Send,
+    {
+        let corpus = context.corpus();
+        let mut results: Vec<(SearchHit, T)> = self.map_documents(corpus, |document| {
+            if !filter(document) {
+                return Ok(None);
+            }
+            let hit = self.score_query(query_terms, document, context)?;
+            Ok(hit.map(|hit| (hit, extract(document))))
+        })?.into_iter().flatten().collect();
 
-         // Sort by score (highest first)
-        results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        // Sort by score (highest first)
+        results.sort_by(|a, b| b.0.score.partial_cmp(&a.0.score).unwrap_or(std::cmp::Ordering::Equal));
         
         Ok(results)
     }
 
-      /// Generate document vectors for all documents in a corpus
+    /// Attach the corpus documents to search hits, skipping those no longer in the corpus
+    ///
+    /// Fails if a document held by ID cannot be looked up.
+    pub fn resolve_hits(hits: Vec<SearchHit>, corpus: &Corpus) -> DomainResult<Vec<ScoredDocument>> {
+        hits.into_iter()
+            .filter_map(|hit| {
+                let document = corpus.get_shared_document(&hit.document_id).transpose()?;
+                Some(document.map(|document| ScoredDocument::new(Arc::clone(document), hit.score, hit.term_scores)))
+            })
+            .collect()
+    }
+
+    /// Attach the corpus documents to a page of search hits
+    pub fn resolve_page(hits: Page<SearchHit>, corpus: &Corpus) -> DomainResult<Page<ScoredDocument>> {
+        let (request, total) = (hits.request(), hits.total());
+        Ok(Page::new(Self::resolve_hits(hits.into_items(), corpus)?, request, total))
+    }
+
+    /// Score one document against a query, returning a hit if it scores above zero
+    fn score_query(
+        &self,
+        query_terms: &[(Term, f64)],
+        document: &Document,
+        context: &ScoringContext
+    ) -> DomainResult<Option<SearchHit>> {
+        let mut doc_score = 0.0;
+        let mut term_scores = Vec::new();
+        let mut seen_canonical = HashSet::new();
+
+        for (term, boost) in query_terms {
+            if self.skips(term, context.corpus()) {
+                continue;
+            }
+
+            // "run" and "runs" in one query would otherwise count the same occurrences twice
+            if self.options.aggregate_stems && !seen_canonical.insert(term.canonical()) {
+                continue;
+            }
+
+            match self.score_term_in(term, document, context) {
+                Ok(score) => {
+                    doc_score += score.score() * boost;
+                    term_scores.push(score);
+                },
+                Err(DomainError::TfIdfError(TfIdfError::InvalidCalculation(_))) => {
+                    continue
+                },
+                Err(e) => return Err(e)
+            }
+        }
+
+        // Length normalization applies to query scores as it does to document vectors
+        if let Some(divisor) = self.options.normalization().pivoted_divisor(document.term_count(), context.average_document_length()) {
+            doc_score /= divisor;
+        }
+
+        if doc_score > 0.0 {
+            Ok(Some(SearchHit::new(document.id().clone(), doc_score, term_scores)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Apply `f` to every document in the corpus, in parallel when the `parallel` feature is enabled
+    #[cfg(feature = "parallel")]
+    fn map_documents<T, F>(&self, corpus: &Corpus, f: F) -> DomainResult<Vec<T>>
+    where
+        T: Send,
+        F: Fn(&Document) -> DomainResult<T> + Send + Sync,
+    {
+        use rayon::prelude::*;
+
+        let documents: Vec<&Document> = corpus.documents().collect::<DomainResult<_>>()?;
+        documents.into_par_iter().map(f).collect()
+    }
+
+    /// Apply `f` to every document in the corpus, in parallel when the `parallel` feature is enabled
+    #[cfg(not(feature = "parallel"))]
+    fn map_documents<T, F>(&self, corpus: &Corpus, f: F) -> DomainResult<Vec<T>>
+    where
+        F: Fn(&Document) -> DomainResult<T>,
+    {
+        corpus.documents().map(|document| f(document?)).collect()
+    }
+
+    /// Generate document vectors for all documents in a corpus
     pub fn generate_document_vectors(
         &self,
         corpus: &Corpus,
@@ -328,68 +761,184 @@ impl TfIdf {
             return Err(DomainError::TfIdfError(TfIdfError::CorpusNotIndexed))
         }
 
-        let mut documents_vector = HashMap::new();
-        for document in corpus.documents() {
-            let mut vector = HashMap::new();
-            let scores = self.calculate_document_tfidf(document, corpus)?;
+        let documents_vector = self.vector_index(corpus)?.vectors()
+            .map(|(id, vector)| (id.value().to_string(), vector.clone()))
+            .collect();
+        
+        Ok(documents_vector)
+    }
 
-            for score in scores {
-                vector.insert(score.term().text().to_string(), score.score());
-            }
+    /// Calculate the TF-IDF vector of a document against a corpus's statistics
+    ///
+    /// The document need not belong to the corpus; terms the corpus has never
+    /// seen get the IDF of a zero document frequency.
+    pub fn document_vector(&self, document: &Document, corpus: &Corpus) -> DomainResult<HashMap<String, f64>> {
+        self.document_vector_in(document, &self.validated_context(corpus)?)
+    }
 
-            documents_vector.insert(document.id().value().to_string(), vector);
+    /// Build the sparse document-term matrix of TF-IDF weights for a corpus
+    pub fn document_term_matrix(&self, corpus: &Corpus) -> DomainResult<DocumentTermMatrix> {
+        Ok(self.vector_index(corpus)?.term_matrix())
+    }
+
+    /// Get the vector index of a corpus, reusing the one built last until the corpus changes
+    ///
+    /// Only the most recent corpus is kept; changing the calculator's options,
+    /// scorer or global statistics drops it.
+    pub fn vector_index(&self, corpus: &Corpus) -> DomainResult<Arc<VectorIndex>> {
+        self.vector_index.get_or_build(corpus, || self.build_vector_index(corpus))
+    }
+
+    /// Build a vector index caching the TF-IDF vector of every document in the corpus
+    pub fn build_vector_index(&self, corpus: &Corpus) -> DomainResult<VectorIndex> {
+        if !corpus.is_indexed() {
+            return Err(DomainError::TfIdfError(TfIdfError::CorpusNotIndexed))
         }
-        
-        Ok(documents_vector)
+
+        let context = self.validated_context(corpus)?;
+        let vectors = self.map_documents(corpus, |document| {
+            Ok((document.id().clone(), self.document_vector_in(document, &context)?))
+        })?;
+
+        Ok(VectorIndex::new(corpus, vectors.into_iter().collect()))
     }
 
-     /// Calculate the cosine similarity between two documents
+    /// Rebuild a vector index if the corpus changed since it was built
+    ///
+    /// Returns whether the index was rebuilt.
+    pub fn refresh_vector_index(&self, index: &mut VectorIndex, corpus: &Corpus) -> DomainResult<bool> {
+        if !index.is_stale(corpus) {
+            return Ok(false);
+        }
+
+        self.rebuild_vector_index(index, corpus)?;
+        Ok(true)
+    }
+
+    /// Bring a vector index up to date by vectorizing only documents added to the corpus since it was built
+    ///
+    /// Vectors of removed documents are dropped. Vectors already in the index
+    /// keep the IDF weights they were computed with, which drift as document
+    /// frequencies change, so rebuild with `build_vector_index` from time to
+    /// time. Falls back to a full rebuild when the corpus changed in any other
+    /// way than adding and removing documents. Returns whether the index changed.
+    pub fn update_vector_index(&self, index: &mut VectorIndex, corpus: &Corpus) -> DomainResult<bool> {
+        if !index.is_stale(corpus) {
+            return Ok(false);
+        }
+        if !corpus.is_indexed() {
+            return Err(DomainError::TfIdfError(TfIdfError::CorpusNotIndexed))
+        }
+
+        let mut added: Vec<&Document> = corpus.documents().collect::<DomainResult<_>>()?;
+        added.retain(|document| index.vector(document.id()).is_none());
+        let removed: Vec<DocumentId> = index.vectors()
+            .map(|(id, _)| id)
+            .filter(|id| !corpus.contains_document(id))
+            .cloned()
+            .collect();
+
+        // Each add or remove bumps the revision once; any other bump may have changed existing documents
+        let changes = (added.len() + removed.len()) as u64;
+        if index.corpus_id() != Some(corpus.id()) || corpus.revision().checked_sub(index.revision()) != Some(changes) {
+            self.rebuild_vector_index(index, corpus)?;
+            return Ok(true);
+        }
+
+        let context = self.validated_context(corpus)?;
+        for document_id in &removed {
+            index.remove_vector(document_id);
+        }
+        for document in added {
+            index.insert_vector(document.id().clone(), self.document_vector_in(document, &context)?);
+        }
+        index.mark_current(corpus);
+
+        Ok(true)
+    }
+
+    /// Replace a vector index with a full rebuild, keeping the dense embeddings of unchanged documents
+    fn rebuild_vector_index(&self, index: &mut VectorIndex, corpus: &Corpus) -> DomainResult<()> {
+        let previous = std::mem::replace(index, self.build_vector_index(corpus)?);
+        index.keep_dense_vectors(previous, corpus)
+    }
+
+    /// Calculate the cosine similarity between two documents
     pub fn cosine_similarity(
         &self,
         doc1_id: &str,
         doc2_id: &str,
         corpus: &Corpus,
     ) -> DomainResult<f64> {
-        let vectors = self.generate_document_vectors(corpus)?;
-        
-        let vec1 = vectors.get(doc1_id).ok_or_else(|| {
+        if !corpus.is_indexed() {
+            return Err(DomainError::TfIdfError(TfIdfError::CorpusNotIndexed))
+        }
+
+        let doc1 = corpus.get_document(&DocumentId::new(doc1_id))?.ok_or_else(|| {
             DomainError::TfIdfError(TfIdfError::DocumentNotFound(doc1_id.to_string()))
         })?;
         
-        let vec2 = vectors.get(doc2_id).ok_or_else(|| {
+        let doc2 = corpus.get_document(&DocumentId::new(doc2_id))?.ok_or_else(|| {
             DomainError::TfIdfError(TfIdfError::DocumentNotFound(doc2_id.to_string()))
         })?;
         
-        // Calculate dot product
-        let mut dot_product = 0.0;
-        let mut magnitude1 = 0.0;
-        let mut magnitude2 = 0.0;
-        
-        // Get all unique terms from both vectors
-        let mut all_terms = HashSet::new();
-        all_terms.extend(vec1.keys().cloned());
-        all_terms.extend(vec2.keys().cloned());
-        
-        // Calculate dot product and magnitudes
-        for term in all_terms {
-            let val1 = vec1.get(&term).copied().unwrap_or(0.0);
-            let val2 = vec2.get(&term).copied().unwrap_or(0.0);
-            
-            dot_product += val1 * val2;
-            magnitude1 += val1 * val1;
-            magnitude2 += val2 * val2;
-        }
-        
-        // Calculate cosine similarity
-        let magnitude = magnitude1.sqrt() * magnitude2.sqrt();
-        if magnitude == 0.0 {
-            Ok(0.0)
-        } else {
-            Ok(dot_product / magnitude)
-        }
+        self.document_similarity(doc1, doc2, corpus)
+    }
+
+    /// Find the `k` documents in the corpus most similar to the given one
+    ///
+    /// The corpus's vector index is reused across calls until the corpus changes.
+    pub fn find_similar_documents(
+        &self,
+        document_id: &str,
+        k: usize,
+        corpus: &Corpus,
+    ) -> DomainResult<Vec<SimilarDocument>> {
+        self.vector_index(corpus)?.most_similar(&DocumentId::new(document_id), k)
+    }
+
+    /// Compute pairwise cosine similarities between all documents in the corpus
+    pub fn similarity_matrix(&self, corpus: &Corpus) -> DomainResult<SimilarityMatrix> {
+        Ok(self.vector_index(corpus)?.similarity_matrix())
+    }
+
+    /// Calculate the cosine similarity between two documents from their own TF-IDF vectors
+    ///
+    /// Only the two documents are vectorized, so the cost does not grow with the corpus size.
+    pub fn document_similarity(
+        &self,
+        doc1: &Document,
+        doc2: &Document,
+        corpus: &Corpus,
+    ) -> DomainResult<f64> {
+        let context = self.validated_context(corpus)?;
+        let vec1 = self.document_vector_in(doc1, &context)?;
+        let vec2 = self.document_vector_in(doc2, &context)?;
+        
+        Ok(cosine_similarity(&vec1, &vec2))
+    }
+
+    /// Compute a document's TF-IDF vector, keyed by term text (or canonical form when aggregating stems)
+    fn document_vector_in(
+        &self,
+        document: &Document,
+        context: &ScoringContext
+    ) -> DomainResult<HashMap<String, f64>> {
+        let scores = self.document_scores_in(document, context)?;
+
+        Ok(scores.into_iter()
+            .map(|score| {
+                let key = if self.options.aggregate_stems {
+                    score.term().canonical()
+                } else {
+                    score.term().text()
+                };
+                (key.to_string(), score.score())
+            })
+            .collect())
     }
 
-     /// Normalize a set of TF-IDF scores using L2 normalization
+    /// Normalize a set of TF-IDF scores using L2 normalization
     fn normalize_scores(&self, scores: &mut [TfIdfScore]) {
         // Calculate the sum of squares
 
@@ -408,10 +957,81 @@ impl TfIdf {
     }
 }
 
+/// The built-in ranking: classic TF-IDF driven by `TfIdfOptions`, or BM25
+impl TfIdf {
+    /// Weight a term frequency by the ranking model or weighting options
+    fn weight_tf(&self, term_frequency: f64, document: &Document, average_document_length: impl FnOnce() -> f64) -> f64 {
+        if let RankingModel::Bm25(bm25) = self.options.ranking {
+            return bm25.weighted_tf(term_frequency, document.term_count(), average_document_length());
+        }
+
+        if let Some(tf_fn) = self.options.tf_weighting {
+            //Use custom weighting function
+            let term_count = term_frequency.round() as usize;
+            let total_terms = document.term_count();
+            tf_fn(term_count, total_terms)
+
+        } else if let Some(scheme) = self.options.scheme {
+            let max_term_frequency = if self.options.aggregate_stems {
+                document.max_canonical_term_frequency()
+            } else {
+                document.max_term_frequency()
+            };
+            scheme.tf.weight(term_frequency, max_term_frequency.value() as f64)
+        } else if self.options.use_log_tf {
+            if term_frequency > 0.0 {
+                1.0 + term_frequency.ln()
+            } else {
+                0.0
+            }
+        } else if document.term_count() > 0 {
+            term_frequency / document.term_count() as f64
+        } else {
+            0.0
+        }
+    }
+
+    /// Get the IDF of a term in a corpus, weighted as when scoring
+    pub fn term_idf(&self, term: &Term, corpus: &Corpus) -> f64 {
+        let context = self.scoring_context(corpus);
+        self.weight_idf(context.document_frequency(term), context.document_count())
+    }
+
+    /// Weight a document frequency into an IDF by the ranking model or weighting options
+    fn weight_idf(&self, doc_freq: usize, total_docs: usize) -> f64 {
+        if let RankingModel::Bm25(bm25) = self.options.ranking {
+            bm25.idf_from(doc_freq, total_docs)
+        } else if let Some(idf_fn) = self.options.idf_weighting {
+            idf_fn(doc_freq, total_docs)
+        } else if let Some(scheme) = self.options.scheme {
+            scheme.idf.weight(doc_freq, total_docs)
+        } else {
+            self.options.smoothing.idf(doc_freq, total_docs)
+        }
+    }
+}
+
+impl Scorer for TfIdf {
+    fn score_term(&self, term: &Term, document: &Document, context: &ScoringContext) -> TfIdfScore {
+        let term_frequency = context.term_frequency(term, document);
+        let tf = self.weight_tf(term_frequency, document, || context.average_document_length());
+        let idf = self.weight_idf(context.document_frequency(term), context.document_count());
+
+        TfIdfScore::new(term.clone(), tf, idf)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::{Document, Term, DocumentId};
+    use crate::domain::{Document, Term, DocumentId, Bm25, IdfWeight, TfWeight};
+    use crate::domain::global_stats::tests::shard;
+    use crate::domain::{PageRequest, Query};
+    
+    /// Rank a request's matches and attach their documents
+    fn search_with(tfidf: &TfIdf, request: &SearchRequest, corpus: &Corpus) -> DomainResult<Vec<ScoredDocument>> {
+        TfIdf::resolve_hits(tfidf.search_request(request, corpus)?.into_results(), corpus)
+    }
     
     fn create_test_corpus() -> Corpus {
         let mut corpus = Corpus::new("test", "Test Corpus");
@@ -440,8 +1060,7 @@ mod tests {
         corpus.add_document(doc2).unwrap();
         corpus.add_document(doc3).unwrap();
         
-        corpus.build_index();
-        println!("[DEBUG] In create_test_corpus, after build_index: corpus.is_indexed() = {}", corpus.is_indexed());
+        corpus.build_index().unwrap();
         corpus
     }
     
@@ -450,7 +1069,7 @@ mod tests {
         let corpus = create_test_corpus();
         let tfidf = TfIdf::default();
         
-        let doc1 = corpus.get_document(&DocumentId::new("doc1")).unwrap();
+        let doc1 = corpus.get_document(&DocumentId::new("doc1")).unwrap().unwrap();
         
         // Calculate TF-IDF for the term "test" in doc1
         let term = Term::new("test");
@@ -460,7 +1079,7 @@ mod tests {
         assert!((score.tf() - 1.0).abs() < f64::EPSILON);
         
         // IDF should be ln(3/(2+1)) = ln(1) = 0.0 with default smoothing
-        let expected_idf = (3.0f64 / (corpus.document_frequency(&term) as f64 + 1.0)).ln(); 
+        let expected_idf = (3.0f64 / (corpus.document_frequency(&term) as f64 + 1.0)).ln();
         // Or simply: let expected_idf = 0.0; for this specific "test" term
         assert!((score.idf() - expected_idf).abs() < f64::EPSILON);
         
@@ -473,7 +1092,7 @@ mod tests {
         let corpus = create_test_corpus();
         let tfidf = TfIdf::default();
         
-        let doc1 = corpus.get_document(&DocumentId::new("doc1")).unwrap();
+        let doc1 = corpus.get_document(&DocumentId::new("doc1")).unwrap().unwrap();
         
         // Calculate TF-IDF for all terms in doc1
         let scores = tfidf.calculate_document_tfidf(doc1, &corpus).unwrap();
@@ -499,7 +1118,7 @@ mod tests {
     fn test_search() {
         let corpus = create_test_corpus(); // Uses your existing helper
 
-        // --- Test with DEFAULT TfIdf options (apply_smoothing = true) ---
+        // --- Test with DEFAULT TfIdf options (smoothing = AddOne) ---
         let tfidf_default = TfIdf::default();
 
         // Search for "test"
@@ -510,10 +1129,6 @@ mod tests {
         let query_terms_test_default = vec![Term::new("test")];
         let results_test_default = tfidf_default.search(&query_terms_test_default, &corpus).unwrap();
         
-        println!("[DEBUG] test_search (default options) - Query 'test': Results len = {}", results_test_default.len());
-        for (i, res) in results_test_default.iter().enumerate() {
-            println!("[DEBUG] test_search (default options) - Result {}: Doc ID = {}, Score = {}", i, res.document().id().value(), res.score());
-        }
         assert_eq!(results_test_default.len(), 0, "With default smoothing, 'test' should have a TF-IDF score of 0, leading to 0 search results for this query.");
 
         // Search for "another example"
@@ -525,18 +1140,14 @@ mod tests {
         let query_terms_another_example_default = vec![Term::new("another"), Term::new("example")];
         let results_another_example_default = tfidf_default.search(&query_terms_another_example_default, &corpus).unwrap();
         
-        println!("[DEBUG] test_search (default options) - Query 'another example': Results len = {}", results_another_example_default.len());
-        for (i, res) in results_another_example_default.iter().enumerate() {
-            println!("[DEBUG] test_search (default options) - Result {}: Doc ID = {}, Score = {}", i, res.document().id().value(), res.score());
-        }
         assert_eq!(results_another_example_default.len(), 1, "Only doc3 should have a non-zero score for 'another example' with default smoothing.");
         if !results_another_example_default.is_empty() {
             assert_eq!(results_another_example_default[0].document().id().value(), "doc3");
         }
 
-        // --- Test with TfIdfOptions where apply_smoothing = false ---
+        // --- Test with TfIdfOptions where smoothing = None ---
         let options_no_smoothing = TfIdfOptions {
-            apply_smoothing: false,
+            smoothing: Smoothing::None,
             ..TfIdfOptions::default() // use other defaults like use_log_tf = true
         };
         let tfidf_no_smoothing = TfIdf::new(options_no_smoothing);
@@ -546,10 +1157,6 @@ mod tests {
         let query_terms_test_no_smoothing = vec![Term::new("test")];
         let results_test_no_smoothing = tfidf_no_smoothing.search(&query_terms_test_no_smoothing, &corpus).unwrap();
         
-        println!("[DEBUG] test_search (no smoothing) - Query 'test': Results len = {}", results_test_no_smoothing.len());
-        for (i, res) in results_test_no_smoothing.iter().enumerate() {
-            println!("[DEBUG] test_search (no smoothing) - Result {}: Doc ID = {}, Score = {}", i, res.document().id().value(), res.score());
-        }
         assert_eq!(results_test_no_smoothing.len(), 2, "Without smoothing, 'test' should match doc1 and doc2.");
         if results_test_no_smoothing.len() == 2 {
             // doc1: "this is a test" (4 terms)
@@ -572,11 +1179,6 @@ mod tests {
         let query_terms_another_example_no_smoothing = vec![Term::new("another"), Term::new("example")];
         let results_another_example_no_smoothing = tfidf_no_smoothing.search(&query_terms_another_example_no_smoothing, &corpus).unwrap();
         
-        println!("[DEBUG] test_search (no smoothing) - Query 'another example': Results len = {}", results_another_example_no_smoothing.len());
-        for (i, res) in results_another_example_no_smoothing.iter().enumerate() {
-            println!("[DEBUG] test_search (no smoothing) - Result {}: Doc ID = {}, Score = {}", i, res.document().id().value(), res.score());
-        }
-
         // doc1 ("this is a test"): "another"=0, "example"=0. Score = 0.
         // doc2 ("this is another test"): TF-IDF("another") > 0, "example"=0. Score for "another" > 0.
         // doc3 ("yet another example"): TF-IDF("another") > 0, TF-IDF("example") > 0. Highest score.
@@ -596,7 +1198,6 @@ mod tests {
         let similarity = tfidf.cosine_similarity("doc1", "doc2", &corpus).unwrap();
         assert!((similarity - 0.0).abs() < f64::EPSILON); // Expect 0.0
 
-        
         // Calculate similarity between doc1 and doc3
         let similarity = tfidf.cosine_similarity("doc1", "doc3", &corpus).unwrap();
         
@@ -610,17 +1211,23 @@ mod tests {
         
         // Create TF-IDF with custom options
         let options = TfIdfOptions {
-            apply_smoothing: false,
+            smoothing: Smoothing::None,
             normalize: false,
             use_log_tf: false,
             filter_stopwords: false,
+            use_corpus_stopwords: false,
             tf_weighting: None,
             idf_weighting: None,
+            ranking: RankingModel::TfIdf,
+            field_boosts: HashMap::new(),
+            aggregate_stems: false,
+            scheme: None,
+            fixed_vocabulary: None,
         };
         
         let tfidf = TfIdf::new(options);
         
-        let doc1 = corpus.get_document(&DocumentId::new("doc1")).unwrap();
+        let doc1 = corpus.get_document(&DocumentId::new("doc1")).unwrap().unwrap();
         
         // Calculate TF-IDF for the term "test" in doc1
         let term = Term::new("test");
@@ -633,4 +1240,651 @@ mod tests {
         let expected_idf = (3.0f64 / 2.0f64).ln();
         assert!((score.idf() - expected_idf).abs() < f64::EPSILON);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_bm25_search() {
+        let mut corpus = create_test_corpus();
+        
+        // A long document mentioning "example" once should rank below a short one
+        let mut long_doc = Document::new("doc4", "example padded with many other words");
+        long_doc.add_term(Term::new("example"));
+        for word in ["padded", "with", "many", "other", "words", "and", "more", "words"] {
+            long_doc.add_term(Term::new(word));
+        }
+        corpus.add_document(long_doc).unwrap();
+        corpus.build_index().unwrap();
+        
+        let options = TfIdfOptions {
+            ranking: RankingModel::Bm25(Bm25::default()),
+            ..TfIdfOptions::default()
+        };
+        let tfidf = TfIdf::new(options);
+        
+        let results = tfidf.search(&[Term::new("example")], &corpus).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].document().id().value(), "doc3");
+        assert_eq!(results[1].document().id().value(), "doc4");
+        assert!(results[0].score() > results[1].score());
+        
+        // BM25 IDF stays positive even for terms in most documents
+        let score = tfidf.calculate_term_tfidf(&Term::new("another"), corpus.get_document(&DocumentId::new("doc2")).unwrap().unwrap(), &corpus).unwrap();
+        assert!(score.idf() > 0.0);
+    }
+    
+    #[derive(Debug)]
+    struct RawCountScorer;
+    
+    impl Scorer for RawCountScorer {
+        fn score_term(&self, term: &Term, document: &Document, _context: &ScoringContext) -> TfIdfScore {
+            TfIdfScore::new(term.clone(), document.term_frequency(term).0 as f64, 1.0)
+        }
+    }
+    
+    #[test]
+    fn test_custom_scorer() {
+        let corpus = create_test_corpus();
+        let tfidf = TfIdf::with_scorer(TfIdfOptions::default(), Arc::new(RawCountScorer));
+        
+        // "test" scores 0 under smoothed TF-IDF but 1 per occurrence under the custom scorer
+        let results = tfidf.search(&[Term::new("test")], &corpus).unwrap();
+        assert_eq!(results.len(), 2);
+        assert!((results[0].score() - 1.0).abs() < f64::EPSILON);
+        
+        let doc1 = corpus.get_document(&DocumentId::new("doc1")).unwrap().unwrap();
+        let scores = tfidf.calculate_document_tfidf(doc1, &corpus).unwrap();
+        assert_eq!(scores.len(), 4);
+    }
+    
+    #[test]
+    fn test_global_stats() {
+        let first = shard("first", &[("doc1", "rust code"), ("doc2", "rust tools"), ("doc3", "go code")]);
+        let second = shard("second", &[("doc4", "rust code"), ("doc5", "java tools"), ("doc6", "java code"), ("doc7", "go tools")]);
+        let term = Term::new("rust");
+
+        let mut tfidf = TfIdf::new(TfIdfOptions { smoothing: Smoothing::None, ..TfIdfOptions::default() });
+        let idf_in = |tfidf: &TfIdf, corpus: &Corpus, doc_id: &str| {
+            let document = corpus.get_document(&DocumentId::new(doc_id)).unwrap().unwrap();
+            tfidf.calculate_term_tfidf(&term, document, corpus).unwrap().idf()
+        };
+
+        // Each shard on its own sees a different share of "rust"
+        assert!((idf_in(&tfidf, &first, "doc1") - idf_in(&tfidf, &second, "doc4")).abs() > 0.1);
+
+        let stats = GlobalStats::from_corpora([&first, &second]);
+        tfidf.set_global_stats(Some(Arc::new(stats)));
+        let expected = (7.0f64 / 3.0).ln();
+        assert!((idf_in(&tfidf, &first, "doc1") - expected).abs() < 1e-9);
+        assert!((idf_in(&tfidf, &second, "doc4") - expected).abs() < 1e-9);
+        assert_eq!(tfidf.global_stats().unwrap().document_count(), 7);
+    }
+
+    #[test]
+    fn test_search_hits() {
+        let corpus = create_test_corpus();
+        let tfidf = TfIdf::default();
+        
+        let query = vec![Term::new("another"), Term::new("example")];
+        let hits = tfidf.search_hits(&query, &corpus).unwrap();
+        let results = tfidf.search(&query, &corpus).unwrap();
+        
+        assert_eq!(hits.len(), results.len());
+        assert_eq!(hits[0].document_id().value(), "doc3");
+        assert!((hits[0].score() - results[0].score()).abs() < f64::EPSILON);
+        assert_eq!(hits[0].top_terms(1)[0].term().text(), "example");
+    }
+    
+    #[test]
+    fn test_vector_index() {
+        let mut corpus = create_test_corpus();
+        let tfidf = TfIdf::default();
+        
+        let mut index = tfidf.build_vector_index(&corpus).unwrap();
+        assert_eq!(index.len(), 3);
+        assert!(!index.is_stale(&corpus));
+        
+        // The cached similarity matches the uncached calculation
+        let cached = index.cosine_similarity(&DocumentId::new("doc2"), &DocumentId::new("doc3")).unwrap();
+        let uncached = tfidf.cosine_similarity("doc2", "doc3", &corpus).unwrap();
+        assert!((cached - uncached).abs() < 1e-12);
+        
+        // Refreshing a fresh index is a no-op
+        assert!(!tfidf.refresh_vector_index(&mut index, &corpus).unwrap());
+
+        // Postings are keyed by process-local term IDs, so a loaded index rebuilds them
+        let loaded: VectorIndex = serde_json::from_str(&serde_json::to_string(&index).unwrap()).unwrap();
+        let vector = index.vector(&DocumentId::new("doc3")).unwrap();
+        let nearest = loaded.nearest(vector, 3);
+        assert_eq!(nearest.len(), 1);
+        assert_eq!(nearest[0].document_id(), &DocumentId::new("doc3"));
+        assert_eq!(nearest, index.nearest(vector, 3));
+
+        // Embeddings survive a rebuild only while the content they were computed from is unchanged
+        let doc1 = corpus.get_document(&DocumentId::new("doc1")).unwrap().unwrap().clone();
+        index.set_dense_vector(&doc1, vec![1.0, 0.0]).unwrap();
+        index.set_dense_vector(&Document::new("doc2", "outdated content"), vec![0.0, 1.0]).unwrap();
+        
+        // Adding a document invalidates the index
+        let mut doc4 = Document::new("doc4", "example");
+        doc4.add_term(Term::new("example"));
+        corpus.add_document(doc4).unwrap();
+        assert!(index.is_stale(&corpus));
+        assert!(tfidf.refresh_vector_index(&mut index, &corpus).unwrap());
+        assert_eq!(index.len(), 4);
+        assert_eq!(index.dense_vector(&DocumentId::new("doc1")), Some(&[1.0, 0.0][..]));
+        assert_eq!(index.dense_vector(&DocumentId::new("doc2")), None);
+        
+        assert!(index.cosine_similarity(&DocumentId::new("doc1"), &DocumentId::new("missing")).is_err());
+    }
+    
+    #[test]
+    fn test_update_vector_index() {
+        let mut corpus = create_test_corpus();
+        let tfidf = TfIdf::new(TfIdfOptions { smoothing: Smoothing::None, ..TfIdfOptions::default() });
+        let mut index = tfidf.build_vector_index(&corpus).unwrap();
+        let doc1 = index.vector(&DocumentId::new("doc1")).unwrap().clone();
+        
+        let mut doc4 = Document::new("doc4", "another example");
+        doc4.add_term(Term::new("another"));
+        doc4.add_term(Term::new("example"));
+        corpus.add_document(doc4).unwrap();
+        corpus.remove_document(&DocumentId::new("doc2")).unwrap();
+        
+        // Only the new document is vectorized; existing vectors are kept as they were
+        assert!(tfidf.update_vector_index(&mut index, &corpus).unwrap());
+        assert!(!index.is_stale(&corpus));
+        assert_eq!(index.len(), 3);
+        assert!(index.vector(&DocumentId::new("doc2")).is_none());
+        assert_eq!(index.vector(&DocumentId::new("doc1")).unwrap(), &doc1);
+        let doc4 = tfidf.document_vector(corpus.get_document(&DocumentId::new("doc4")).unwrap().unwrap(), &corpus).unwrap();
+        assert_eq!(index.vector(&DocumentId::new("doc4")).unwrap(), &doc4);
+        let similar = index.most_similar(&DocumentId::new("doc4"), 3).unwrap();
+        assert_eq!(similar[0].document_id().value(), "doc3");
+        assert!(!tfidf.update_vector_index(&mut index, &corpus).unwrap());
+        
+        // Editing a document in place forces a full rebuild
+        corpus.get_document_mut(&DocumentId::new("doc1")).unwrap().unwrap().add_term(Term::new("example"));
+        corpus.build_index().unwrap();
+        assert!(tfidf.update_vector_index(&mut index, &corpus).unwrap());
+        assert!(index.vector(&DocumentId::new("doc1")).unwrap().contains_key("example"));
+    }
+    
+    #[test]
+    fn test_document_similarity() {
+        let corpus = create_test_corpus();
+        let tfidf = TfIdf::default();
+        
+        let doc2 = corpus.get_document(&DocumentId::new("doc2")).unwrap().unwrap();
+        let doc3 = corpus.get_document(&DocumentId::new("doc3")).unwrap().unwrap();
+        
+        let similarity = tfidf.document_similarity(doc2, doc3, &corpus).unwrap();
+        let by_id = tfidf.cosine_similarity("doc2", "doc3", &corpus).unwrap();
+        assert!((similarity - by_id).abs() < f64::EPSILON);
+        
+        // A document is perfectly similar to itself when it has any weight
+        let self_similarity = tfidf.document_similarity(doc3, doc3, &corpus).unwrap();
+        assert!((self_similarity - 1.0).abs() < 1e-12);
+        
+        assert!(tfidf.cosine_similarity("doc1", "missing", &corpus).is_err());
+    }
+    
+    #[test]
+    fn test_find_similar_documents() {
+        let corpus = create_test_corpus();
+        let tfidf = TfIdf::new(TfIdfOptions {
+            smoothing: Smoothing::None,
+            ..TfIdfOptions::default()
+        });
+        
+        let similar = tfidf.find_similar_documents("doc2", 5, &corpus).unwrap();
+        assert!(!similar.is_empty());
+        assert!(similar.iter().all(|s| s.document_id().value() != "doc2"));
+        assert!(similar.windows(2).all(|w| w[0].similarity() >= w[1].similarity()));
+        
+        // Similarities agree with the pairwise calculation
+        for entry in &similar {
+            let expected = tfidf.cosine_similarity("doc2", entry.document_id().value(), &corpus).unwrap();
+            assert!((entry.similarity() - expected).abs() < 1e-12);
+        }
+        
+        let top = tfidf.find_similar_documents("doc2", 1, &corpus).unwrap();
+        assert_eq!(top.len(), 1);
+        
+        // The index is built once and reused until the corpus changes
+        let index = tfidf.vector_index(&corpus).unwrap();
+        assert!(Arc::ptr_eq(&index, &tfidf.vector_index(&corpus).unwrap()));
+        let mut changed = corpus.clone();
+        changed.remove_document(&DocumentId::new("doc1")).unwrap();
+        assert!(!Arc::ptr_eq(&index, &tfidf.vector_index(&changed).unwrap()));
+        assert!(tfidf.find_similar_documents("missing", 1, &corpus).is_err());
+    }
+    
+    #[test]
+    fn test_similarity_matrix() {
+        let corpus = create_test_corpus();
+        let tfidf = TfIdf::new(TfIdfOptions {
+            smoothing: Smoothing::None,
+            ..TfIdfOptions::default()
+        });
+        
+        let matrix = tfidf.similarity_matrix(&corpus).unwrap();
+        assert_eq!(matrix.len(), 3);
+        
+        let ids: Vec<_> = matrix.document_ids().iter().map(|id| id.value()).collect();
+        assert_eq!(ids, vec!["doc1", "doc2", "doc3"]);
+        
+        for row in 0..3 {
+            assert!((matrix.get(row, row) - 1.0).abs() < 1e-12);
+            for column in 0..3 {
+                assert!((matrix.get(row, column) - matrix.get(column, row)).abs() < 1e-12);
+                let expected = tfidf.cosine_similarity(ids[row], ids[column], &corpus).unwrap();
+                assert!((matrix.get(row, column) - expected).abs() < 1e-12);
+            }
+        }
+        
+        assert!(matrix.triples().iter().all(|(row, column, _)| row < column));
+    }
+    
+    #[test]
+    fn test_phrase_search() {
+        let mut corpus = Corpus::new("test", "Test Corpus");
+        
+        let mut doc1 = Document::new("doc1", "machine learning is fun");
+        doc1.add_terms(["machine", "learning", "is", "fun"].map(Term::new));
+        let mut doc2 = Document::new("doc2", "learning about machine tools");
+        doc2.add_terms(["learning", "about", "machine", "tools"].map(Term::new));
+        let mut doc3 = Document::new("doc3", "cooking recipes");
+        doc3.add_terms(["cooking", "recipes"].map(Term::new));
+        
+        corpus.add_document(doc1).unwrap();
+        corpus.add_document(doc2).unwrap();
+        corpus.add_document(doc3).unwrap();
+        corpus.build_index().unwrap();
+        
+        let tfidf = TfIdf::new(TfIdfOptions {
+            smoothing: Smoothing::None,
+            ..TfIdfOptions::default()
+        });
+        let phrase = vec![Term::new("machine"), Term::new("learning")];
+        
+        // Both documents contain the words, but only doc1 has them adjacent
+        assert_eq!(tfidf.search(&phrase, &corpus).unwrap().len(), 2);
+        
+        let results = search_with(&tfidf, &SearchRequest::new(phrase.clone()).with_phrases([phrase.clone()]), &corpus).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].document().id().value(), "doc1");
+    }
+    
+    #[test]
+    fn test_boolean_query_search() {
+        let corpus = create_test_corpus();
+        let tfidf = TfIdf::new(TfIdfOptions {
+            smoothing: Smoothing::None,
+            ..TfIdfOptions::default()
+        });
+        
+        // "another" matches doc2 and doc3; excluding "example" leaves doc2
+        let query = Query::and([
+            Query::term(Term::new("another")),
+            Query::not(Query::term(Term::new("example"))),
+        ]);
+        let results = search_with(&tfidf, &SearchRequest::boolean(query), &corpus).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].document().id().value(), "doc2");
+        
+        let query = Query::or([Query::term(Term::new("yet")), Query::term(Term::new("a"))]);
+        let hits = tfidf.search_request(&SearchRequest::boolean(query), &corpus).unwrap().into_results();
+        assert_eq!(hits.len(), 2);
+        
+        // "ex*" expands to "example"; "an*" to "another"
+        let query = Query::and([Query::wildcard("an*"), Query::not(Query::wildcard("ex*"))]);
+        let hits = tfidf.search_request(&SearchRequest::boolean(query), &corpus).unwrap().into_results();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].document_id().value(), "doc2");
+    }
+    
+    #[test]
+    fn test_field_boosts() {
+        let mut corpus = create_test_corpus();
+        
+        // doc4 mentions "rust" in its title, doc5 only in its content
+        let mut doc4 = Document::with_title("doc4", "Rust", "a guide");
+        doc4.add_terms(["a", "guide"].map(Term::new));
+        doc4.add_field_term(Document::TITLE_FIELD, Term::new("rust"));
+        let mut doc5 = Document::new("doc5", "rust guide");
+        doc5.add_terms(["rust", "guide"].map(Term::new));
+        corpus.add_document(doc4).unwrap();
+        corpus.add_document(doc5).unwrap();
+        corpus.build_index().unwrap();
+        
+        // Only content terms count toward document frequency
+        assert_eq!(corpus.document_frequency(&Term::new("rust")), 1);
+        
+        // Without a boost the title is ignored
+        let results = TfIdf::default().search(&[Term::new("rust")], &corpus).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].document().id().value(), "doc5");
+        
+        let mut options = TfIdfOptions::default();
+        options.field_boosts.insert(Document::TITLE_FIELD.to_string(), 2.0);
+        let tfidf = TfIdf::new(options);
+        
+        let results = tfidf.search(&[Term::new("rust")], &corpus).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].document().id().value(), "doc4");
+        assert!(results[0].score() > results[1].score());
+    }
+    
+    #[test]
+    fn test_search_in_fields() {
+        let mut corpus = Corpus::new("test", "Test Corpus");
+        
+        let mut doc1 = Document::new("doc1", "notes on engines");
+        doc1.add_terms(["notes", "on", "engines"].map(Term::new));
+        doc1.add_field_term("author", Term::new("lovelace"));
+        let mut doc2 = Document::new("doc2", "lovelace and engines");
+        doc2.add_terms(["lovelace", "and", "engines"].map(Term::new));
+        let doc3 = Document::new("doc3", "unrelated");
+        
+        corpus.add_document(doc1).unwrap();
+        corpus.add_document(doc2).unwrap();
+        corpus.add_document(doc3).unwrap();
+        corpus.build_index().unwrap();
+        
+        let tfidf = TfIdf::new(TfIdfOptions {
+            smoothing: Smoothing::None,
+            ..TfIdfOptions::default()
+        });
+        let query = [Term::new("lovelace")];
+        
+        let results = search_with(&tfidf, &SearchRequest::new(query.clone()).in_fields(["author"]), &corpus).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].document().id().value(), "doc1");
+        
+        let results = search_with(&tfidf, &SearchRequest::new(query.clone()).in_fields([Document::CONTENT_FIELD]), &corpus).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].document().id().value(), "doc2");
+        
+        assert_eq!(tfidf.search_request(&SearchRequest::new(query).in_fields(["author", Document::CONTENT_FIELD]), &corpus).unwrap().results().len(), 2);
+    }
+    
+    #[test]
+    fn test_search_page() {
+        let corpus = create_test_corpus();
+        let tfidf = TfIdf::new(TfIdfOptions {
+            smoothing: Smoothing::None,
+            ..TfIdfOptions::default()
+        });
+        let query = [Term::new("another"), Term::new("example")];
+        
+        let all = tfidf.search(&query, &corpus).unwrap();
+        let page = |request| Page::from_vec(tfidf.search(&query, &corpus).unwrap(), request);
+        let first = page(PageRequest::first(1));
+        assert_eq!(first.total(), 2);
+        assert_eq!(first.items().len(), 1);
+        assert_eq!(first.items()[0].document().id(), all[0].document().id());
+        
+        let second = page(first.next_request().unwrap());
+        assert_eq!(second.items()[0].document().id(), all[1].document().id());
+        assert!(!second.has_next());
+    }
+    
+    #[test]
+    fn test_faceted_search() {
+        let mut corpus = Corpus::new("test", "Test Corpus");
+        
+        for (id, words, category) in [
+            ("doc1", ["rust", "memory"], "systems"),
+            ("doc2", ["rust", "web"], "web"),
+            ("doc3", ["rust", "kernel"], "systems"),
+            ("doc4", ["python", "web"], "web"),
+        ] {
+            let mut doc = Document::new(id, words.join(" "));
+            doc.add_terms(words.map(Term::new));
+            doc.set_metadata("category", category);
+            corpus.add_document(doc).unwrap();
+        }
+        corpus.build_index().unwrap();
+        
+        let tfidf = TfIdf::new(TfIdfOptions {
+            smoothing: Smoothing::None,
+            ..TfIdfOptions::default()
+        });
+        let faceted = tfidf.search_request(&SearchRequest::new([Term::new("rust")]).with_facets(["category", "author"]), &corpus).unwrap();
+        
+        assert_eq!(faceted.results().len(), 3);
+        assert_eq!(faceted.facets().sorted("category"), vec![("systems", 2), ("web", 1)]);
+        assert!(faceted.facets().counts("author").unwrap().is_empty());
+        
+        // Phrases restrict both the results and the facet counts; boosts reorder the results
+        let phrase = vec![Term::new("rust"), Term::new("web")];
+        let query = [(Term::new("rust"), 1.0), (Term::new("web"), 5.0)];
+        let faceted = tfidf.search_request(&SearchRequest::weighted(query.clone()).with_phrases([phrase]).with_facets(["category"]), &corpus).unwrap();
+        assert_eq!(faceted.results().len(), 1);
+        assert_eq!(faceted.facets().sorted("category"), vec![("web", 1)]);
+        let faceted = tfidf.search_request(&SearchRequest::weighted(query).with_facets(["category"]), &corpus).unwrap();
+        assert_eq!(faceted.results()[0].document_id().value(), "doc2");
+        assert_eq!(faceted.facets().sorted("category"), vec![("systems", 2), ("web", 2)]);
+    }
+    
+    #[test]
+    fn test_aggregate_stems() {
+        let mut corpus = Corpus::new("test", "Test Corpus");
+        
+        let mut doc1 = Document::new("doc1", "running fast");
+        doc1.add_terms([Term::with_stem("running", "run"), Term::new("fast")]);
+        let mut doc2 = Document::new("doc2", "he runs");
+        doc2.add_terms([Term::new("he"), Term::with_stem("runs", "run")]);
+        let mut doc3 = Document::new("doc3", "walking slowly");
+        doc3.add_terms([Term::with_stem("walking", "walk"), Term::new("slowly")]);
+        
+        corpus.add_document(doc1).unwrap();
+        corpus.add_document(doc2).unwrap();
+        corpus.add_document(doc3).unwrap();
+        corpus.build_index().unwrap();
+        assert_eq!(corpus.canonical_document_frequency("run"), 2);
+        assert_eq!(corpus.canonical_collection_frequency("run"), 2);
+        
+        let query = [Term::with_stem("run", "run")];
+        let options = TfIdfOptions {
+            smoothing: Smoothing::None,
+            ..TfIdfOptions::default()
+        };
+        
+        // By raw text, "run" occurs nowhere
+        assert!(TfIdf::new(options.clone()).search(&query, &corpus).unwrap().is_empty());
+        
+        let tfidf = TfIdf::new(TfIdfOptions { aggregate_stems: true, ..options });
+        let results = tfidf.search(&query, &corpus).unwrap();
+        assert_eq!(results.len(), 2);
+        
+        // Exported IDFs count documents by stem too
+        let running = Term::with_stem("running", "run");
+        assert!((tfidf.term_idf(&running, &corpus) - (3.0f64 / 2.0).ln()).abs() < 1e-12);
+        let mut stemmed = corpus.clone();
+        stemmed.set_options(Some(tfidf.options().clone()));
+        let entry = stemmed.vocabulary_entries().into_iter().find(|entry| entry.term() == "running").unwrap();
+        assert_eq!(entry.document_frequency(), 1);
+        assert!((entry.idf() - (3.0f64 / 2.0).ln()).abs() < 1e-12);
+        
+        let vectors = tfidf.generate_document_vectors(&corpus).unwrap();
+        assert!(vectors["doc1"].contains_key("run"));
+        assert!(!vectors["doc1"].contains_key("running"));
+        
+        // Inflections in one query count once, and boolean queries match by stem too
+        let inflected = [Term::with_stem("run", "run"), Term::with_stem("running", "run")];
+        let single = tfidf.search_hits(&query, &corpus).unwrap();
+        let double = tfidf.search_hits(&inflected, &corpus).unwrap();
+        assert_eq!(single.len(), double.len());
+        for (a, b) in single.iter().zip(&double) {
+            assert!((a.score() - b.score()).abs() < 1e-12);
+        }
+        
+        let boolean = Query::and([Query::term(Term::with_stem("run", "run")), Query::not(Query::term(Term::new("fast")))]);
+        let results = search_with(&tfidf, &SearchRequest::boolean(boolean), &corpus).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].document().id().value(), "doc2");
+    }
+    
+    #[test]
+    fn test_weighted_search() {
+        let corpus = create_test_corpus();
+        let tfidf = TfIdf::new(TfIdfOptions { smoothing: Smoothing::None, ..TfIdfOptions::default() });
+        let ids = |results: Vec<ScoredDocument>| results.iter().map(|r| r.document().id().value().to_string()).collect::<Vec<_>>();
+        
+        // The rarer "yet" outranks "test" until "test" is boosted
+        let plain = tfidf.search(&[Term::new("test"), Term::new("yet")], &corpus).unwrap();
+        assert_eq!(ids(plain)[0], "doc3");
+        
+        // doc1 and doc2 tie, so only the last place is ordered
+        let boosted = search_with(&tfidf, &SearchRequest::weighted([(Term::new("test"), 3.0), (Term::new("yet"), 1.0)]), &corpus).unwrap();
+        assert_eq!(ids(boosted)[2], "doc3");
+        
+        let query = Query::or([Query::boost(Query::term(Term::new("test")), 3.0), Query::term(Term::new("yet"))]);
+        assert_eq!(ids(search_with(&tfidf, &SearchRequest::boolean(query), &corpus).unwrap())[2], "doc3");
+    }
+    
+    #[test]
+    fn test_corpus_stopwords() {
+        let mut corpus = create_test_corpus();
+        corpus.add_stopword("test");
+        let query = [Term::new("test"), Term::new("yet")];
+        
+        let options = TfIdfOptions { smoothing: Smoothing::None, ..TfIdfOptions::default() };
+        
+        // The corpus list is ignored unless the options opt in
+        assert_eq!(TfIdf::new(options.clone()).search(&query, &corpus).unwrap().len(), 3);
+        
+        let tfidf = TfIdf::new(TfIdfOptions { use_corpus_stopwords: true, ..options });
+        let results = tfidf.search(&query, &corpus).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].document().id().value(), "doc3");
+        
+        let doc1 = corpus.get_document(&DocumentId::new("doc1")).unwrap().unwrap();
+        assert!(tfidf.calculate_term_tfidf(&Term::new("test"), doc1, &corpus).is_err());
+        assert!(!tfidf.document_vector(doc1, &corpus).unwrap().contains_key("test"));
+    }
+    
+    #[test]
+    fn test_idf_model() {
+        let corpus = create_test_corpus();
+        let tfidf = TfIdf::default();
+        let model = IdfModel::from_bytes(&tfidf.idf_model(&corpus).unwrap().to_bytes().unwrap()).unwrap();
+        assert_eq!(model.document_count(), 3);
+        
+        // A trained document scores as it does against the corpus
+        let doc3 = corpus.get_document(&DocumentId::new("doc3")).unwrap().unwrap();
+        let expected = tfidf.calculate_document_tfidf(doc3, &corpus).unwrap();
+        let scores = tfidf.score_with_model(doc3, &model).unwrap();
+        assert_eq!(scores.len(), expected.len());
+        for score in &scores {
+            let other = expected.iter().find(|other| other.term() == score.term()).unwrap();
+            assert!((score.score() - other.score()).abs() < 1e-9);
+        }
+        
+        // Unseen terms get the IDF of a zero document frequency
+        let mut incoming = Document::new("new", "novel test");
+        incoming.add_terms([Term::new("novel"), Term::new("test")]);
+        let scores = tfidf.score_with_model(&incoming, &model).unwrap();
+        assert_eq!(scores[0].term().text(), "novel");
+        assert!((scores[0].idf() - Smoothing::AddOne.idf(0, 3)).abs() < 1e-9);
+        assert!(IdfModel::from_bytes(b"not a model").is_err());
+    }
+    
+    #[test]
+    fn test_fixed_vocabulary() {
+        let corpus = create_test_corpus();
+        let options = TfIdfOptions { smoothing: Smoothing::None, ..TfIdfOptions::default() };
+        let tfidf = TfIdf::new(options.with_fixed_vocabulary(["yet"]));
+        
+        // Terms outside the vocabulary neither match nor enter vectors
+        let results = tfidf.search(&[Term::new("test"), Term::new("yet")], &corpus).unwrap();
+        assert_eq!(results.len(), 1);
+        let doc3 = corpus.get_document(&DocumentId::new("doc3")).unwrap().unwrap();
+        let vector = tfidf.document_vector(doc3, &corpus).unwrap();
+        assert!(vector.keys().all(|term| term == "yet"));
+    }
+    
+    #[test]
+    fn test_weighting_scheme() {
+        let corpus = create_test_corpus();
+        let document = corpus.get_document(&DocumentId::new("doc3")).unwrap().unwrap();
+        
+        // The default flags compute the `lsc` scheme
+        let legacy = TfIdf::default().calculate_document_tfidf(document, &corpus).unwrap();
+        let lsc = TfIdf::new(TfIdfOptions::with_scheme(Scheme::lsc())).calculate_document_tfidf(document, &corpus).unwrap();
+        let pairs = |scores: &[TfIdfScore]| {
+            let mut pairs: Vec<_> = scores.iter().map(|s| (s.term().text().to_string(), s.score())).collect();
+            pairs.sort_by(|a, b| a.0.cmp(&b.0));
+            pairs
+        };
+        assert_eq!(pairs(&legacy), pairs(&lsc));
+        
+        // Including for documents outside the corpus, whose new terms have no document frequency
+        let mut outside = Document::new("doc4", "unseen example");
+        outside.add_terms(["unseen", "example"].map(Term::new));
+        let legacy = TfIdf::default().calculate_document_tfidf(&outside, &corpus).unwrap();
+        let lsc = TfIdf::new(TfIdfOptions::with_scheme(Scheme::lsc())).calculate_document_tfidf(&outside, &corpus).unwrap();
+        assert_eq!(pairs(&legacy), pairs(&lsc));
+        
+        // Unnormalized raw counts times ln(N / df)
+        let ntn = TfIdf::new(TfIdfOptions::with_scheme("ntn".parse().unwrap()));
+        let scores = ntn.calculate_document_tfidf(document, &corpus).unwrap();
+        let example = scores.iter().find(|s| s.term().text() == "example").unwrap();
+        assert!((example.score() - 3f64.ln()).abs() < 1e-12);
+        
+        // Augmented TF relative to the document's most frequent term
+        let mut corpus = Corpus::new("augmented", "Augmented");
+        let mut document = Document::new("doc1", "rust rust rust rust go");
+        document.add_terms(["rust", "rust", "rust", "rust", "go"].map(Term::new));
+        corpus.add_document(document).unwrap();
+        corpus.build_index().unwrap();
+        let document = corpus.get_document(&DocumentId::new("doc1")).unwrap().unwrap();
+        let ann = TfIdf::new(TfIdfOptions::with_scheme("ann".parse().unwrap()));
+        let scores = ann.calculate_document_tfidf(document, &corpus).unwrap();
+        assert_eq!(scores.iter().map(|s| s.score()).collect::<Vec<_>>(), vec![1.0, 0.625]);
+    }
+    
+    #[test]
+    fn test_pivoted_normalization() {
+        let mut corpus = Corpus::new("pivoted", "Pivoted");
+        let mut short = Document::new("short", "rust");
+        short.add_term(Term::new("rust"));
+        let mut long = Document::new("long", "rust go rust");
+        long.add_terms(["rust", "go", "rust"].map(Term::new));
+        corpus.add_document(short).unwrap();
+        corpus.add_document(long).unwrap();
+        corpus.build_index().unwrap();
+        
+        // Average length 2: the short document is scaled up, the long one down
+        let scheme = Scheme::new(TfWeight::Natural, IdfWeight::None, Normalization::Pivoted { slope: 0.5, pivot: None });
+        let tfidf = TfIdf::new(TfIdfOptions::with_scheme(scheme));
+        let short = tfidf.calculate_document_tfidf(corpus.get_document(&DocumentId::new("short")).unwrap().unwrap(), &corpus).unwrap();
+        assert!((short[0].score() - 1.0 / 0.75).abs() < 1e-12);
+        let long = tfidf.calculate_document_tfidf(corpus.get_document(&DocumentId::new("long")).unwrap().unwrap(), &corpus).unwrap();
+        assert!((long[0].score() - 2.0 / 1.25).abs() < 1e-12);
+        
+        // Query scores are normalized the same way
+        let hits = tfidf.search_hits(&[Term::new("rust")], &corpus).unwrap();
+        let scores: HashMap<&str, f64> = hits.iter().map(|hit| (hit.document_id().value(), hit.score())).collect();
+        assert!((scores["short"] - 1.0 / 0.75).abs() < 1e-12);
+        assert!((scores["long"] - 2.0 / 1.25).abs() < 1e-12);
+        
+        let steep = Scheme::new(TfWeight::Natural, IdfWeight::None, Normalization::Pivoted { slope: 1.5, pivot: None });
+        let tfidf = TfIdf::new(TfIdfOptions::with_scheme(steep));
+        assert!(tfidf.search_hits(&[Term::new("rust")], &corpus).is_err());
+        assert!(tfidf.calculate_document_tfidf(corpus.get_document(&DocumentId::new("long")).unwrap().unwrap(), &corpus).is_err());
+        
+        // Smoothing constants and BM25 parameters are checked the same way
+        let invalid = [
+            TfIdfOptions { smoothing: Smoothing::AddK(f64::NAN), ..TfIdfOptions::default() },
+            TfIdfOptions { smoothing: Smoothing::AddK(-0.999), ..TfIdfOptions::default() },
+            TfIdfOptions { ranking: RankingModel::Bm25(Bm25::new(1.2, 1.5)), ..TfIdfOptions::default() },
+            TfIdfOptions { ranking: RankingModel::Bm25(Bm25::new(f64::NAN, 0.75)), ..TfIdfOptions::default() },
+        ];
+        for options in invalid {
+            assert!(options.validate().is_err());
+            assert!(TfIdf::new(options).search_hits(&[Term::new("rust")], &corpus).is_err());
+        }
+        assert!(TfIdfOptions { smoothing: Smoothing::AddK(0.5), ..TfIdfOptions::default() }.validate().is_ok());
+    }
+}
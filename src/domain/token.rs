@@ -0,0 +1,109 @@
+// src/domain/token.rs
+
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+
+/// A single token produced by analysis, located within the source text
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Token {
+    /// The normalized token text
+    text: String,
+
+    /// Position of the token in the token stream (0-based)
+    position: usize,
+
+    /// Byte offset where the token starts in the source text
+    start: usize,
+
+    /// Byte offset where the token ends in the source text (exclusive)
+    end: usize,
+}
+
+impl Token {
+    /// Create a new token
+    pub fn new(text: impl Into<String>, position: usize, start: usize, end: usize) -> Self {
+        Self {
+            text: text.into(),
+            position,
+            start,
+            end,
+        }
+    }
+
+    /// Get the token text
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Get the position of the token in the token stream
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Get the start byte offset in the source text
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Get the end byte offset in the source text
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// Take the token text
+    pub fn into_text(self) -> String {
+        self.text
+    }
+}
+
+/// A token located by byte range, borrowing its text from the source when normalization left it unchanged
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenSpan<'a> {
+    /// The normalized token text
+    text: Cow<'a, str>,
+
+    /// Byte offset where the token starts in the source text
+    start: usize,
+
+    /// Byte offset where the token ends in the source text (exclusive)
+    end: usize,
+}
+
+impl<'a> TokenSpan<'a> {
+    /// Create a new token span
+    pub fn new(text: impl Into<Cow<'a, str>>, start: usize, end: usize) -> Self {
+        Self { text: text.into(), start, end }
+    }
+
+    /// Get the token text
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Get the start byte offset in the source text
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Get the end byte offset in the source text
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// Check whether the text is borrowed from the source rather than allocated
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self.text, Cow::Borrowed(_))
+    }
+
+    /// Take the token text, allocating only if it is still borrowed
+    pub fn into_text(self) -> String {
+        self.text.into_owned()
+    }
+}
+
+impl From<Token> for TokenSpan<'_> {
+    fn from(token: Token) -> Self {
+        Self { text: Cow::Owned(token.text), start: token.start, end: token.end }
+    }
+}
@@ -0,0 +1,312 @@
+// src/domain/topic_model.rs
+
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
+
+use super::{Corpus, Document, DocumentId, DomainError, DomainResult, TfIdfError};
+
+/// Options for fitting an LDA topic model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LdaOptions {
+    /// Number of topics to find
+    pub num_topics: usize,
+
+    /// Dirichlet prior on each document's topic mixture; smaller means fewer topics per document
+    pub alpha: f64,
+
+    /// Dirichlet prior on each topic's term distribution; smaller means fewer terms per topic
+    pub beta: f64,
+
+    /// Number of Gibbs sampling sweeps over the corpus
+    pub iterations: usize,
+
+    /// Seed of the sampler, so fitting the same corpus twice gives the same model
+    pub seed: u64,
+}
+
+impl Default for LdaOptions {
+    fn default() -> Self {
+        Self {
+            num_topics: 10,
+            alpha: 0.1,
+            beta: 0.01,
+            iterations: 200,
+            seed: 42,
+        }
+    }
+}
+
+/// Latent Dirichlet Allocation by collapsed Gibbs sampling
+///
+/// Documents are treated as bags of their content term frequencies over the
+/// corpus vocabulary, so they must be analyzed and the corpus indexed first.
+/// Stopwords are left out.
+#[derive(Debug, Clone, Default)]
+pub struct Lda {
+    options: LdaOptions,
+}
+
+impl Lda {
+    /// Create a new LDA fitter with the given options
+    pub fn new(options: LdaOptions) -> Self {
+        Self { options }
+    }
+
+    /// Get the current options
+    pub fn options(&self) -> &LdaOptions {
+        &self.options
+    }
+
+    /// Fit a topic model to an indexed corpus
+    pub fn fit(&self, corpus: &Corpus) -> DomainResult<TopicModel> {
+        if !corpus.is_indexed() {
+            return Err(DomainError::TfIdfError(TfIdfError::CorpusNotIndexed));
+        }
+        if self.options.num_topics == 0 {
+            return Err(DomainError::InvalidOperation("LDA needs at least one topic".to_string()));
+        }
+        if self.options.alpha <= 0.0 || self.options.beta <= 0.0 {
+            return Err(DomainError::InvalidOperation("LDA priors must be positive".to_string()));
+        }
+
+        // Vocabulary in ID order, without stopwords
+        let mut terms: Vec<(usize, String)> = corpus.vocabulary().iter()
+            .filter(|(term, _)| !term.is_stopword() && !corpus.is_stopword(term.text()))
+            .map(|(term, stats)| (stats.id(), term.text().to_string()))
+            .collect();
+        terms.sort();
+        let terms: Vec<String> = terms.into_iter().map(|(_, text)| text).collect();
+        let term_ids: HashMap<&str, usize> = terms.iter().enumerate().map(|(id, text)| (text.as_str(), id)).collect();
+
+        let mut documents: Vec<&Document> = corpus.documents().collect::<DomainResult<_>>()?;
+        documents.sort_by(|a, b| a.id().value().cmp(b.id().value()));
+
+        // One token per term occurrence, in a fixed order so the seed fully determines the result
+        let tokens: Vec<Vec<usize>> = documents.iter()
+            .map(|document| {
+                let mut counts: Vec<(usize, usize)> = document.term_frequencies().iter()
+                    .filter_map(|(term, frequency)| term_ids.get(term.text()).map(|id| (*id, frequency.value())))
+                    .collect();
+                counts.sort();
+                counts.into_iter().flat_map(|(id, count)| std::iter::repeat_n(id, count)).collect()
+            })
+            .collect();
+
+        let topics = self.options.num_topics;
+        let vocabulary_size = terms.len();
+        let mut rng = SplitMix64(self.options.seed);
+
+        let mut document_topic_counts = vec![vec![0usize; topics]; documents.len()];
+        let mut topic_term_counts = vec![vec![0usize; vocabulary_size]; topics];
+        let mut topic_totals = vec![0usize; topics];
+        let mut assignments: Vec<Vec<usize>> = tokens.iter()
+            .enumerate()
+            .map(|(document, tokens)| {
+                tokens.iter()
+                    .map(|&term| {
+                        let topic = rng.below(topics);
+                        document_topic_counts[document][topic] += 1;
+                        topic_term_counts[topic][term] += 1;
+                        topic_totals[topic] += 1;
+                        topic
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let beta_sum = self.options.beta * vocabulary_size as f64;
+        let mut weights = vec![0.0; topics];
+        for _ in 0..self.options.iterations {
+            for (document, document_tokens) in tokens.iter().enumerate() {
+                for (position, &term) in document_tokens.iter().enumerate() {
+                    let old = assignments[document][position];
+                    document_topic_counts[document][old] -= 1;
+                    topic_term_counts[old][term] -= 1;
+                    topic_totals[old] -= 1;
+
+                    let mut total = 0.0;
+                    for (topic, weight) in weights.iter_mut().enumerate() {
+                        *weight = (document_topic_counts[document][topic] as f64 + self.options.alpha)
+                            * (topic_term_counts[topic][term] as f64 + self.options.beta)
+                            / (topic_totals[topic] as f64 + beta_sum);
+                        total += *weight;
+                    }
+
+                    let mut target = rng.next_f64() * total;
+                    let mut new = topics - 1;
+                    for (topic, weight) in weights.iter().enumerate() {
+                        if target < *weight {
+                            new = topic;
+                            break;
+                        }
+                        target -= weight;
+                    }
+
+                    assignments[document][position] = new;
+                    document_topic_counts[document][new] += 1;
+                    topic_term_counts[new][term] += 1;
+                    topic_totals[new] += 1;
+                }
+            }
+        }
+
+        let topic_terms = topic_term_counts.iter()
+            .zip(&topic_totals)
+            .map(|(counts, total)| {
+                counts.iter()
+                    .map(|count| (*count as f64 + self.options.beta) / (*total as f64 + beta_sum))
+                    .collect()
+            })
+            .collect();
+
+        let alpha_sum = self.options.alpha * topics as f64;
+        let document_topics = document_topic_counts.iter()
+            .zip(&tokens)
+            .map(|(counts, tokens)| {
+                counts.iter()
+                    .map(|count| (*count as f64 + self.options.alpha) / (tokens.len() as f64 + alpha_sum))
+                    .collect()
+            })
+            .collect();
+
+        Ok(TopicModel {
+            terms,
+            document_ids: documents.iter().map(|document| document.id().clone()).collect(),
+            topic_terms,
+            document_topics,
+        })
+    }
+}
+
+/// Topic-term and document-topic distributions found by LDA
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopicModel {
+    /// Term texts, indexing the columns of `topic_terms`
+    terms: Vec<String>,
+
+    /// Document IDs in sorted order, indexing the rows of `document_topics`
+    document_ids: Vec<DocumentId>,
+
+    /// Probability of each term within each topic
+    topic_terms: Vec<Vec<f64>>,
+
+    /// Probability of each topic within each document
+    document_topics: Vec<Vec<f64>>,
+}
+
+impl TopicModel {
+    /// Get the number of topics
+    pub fn num_topics(&self) -> usize {
+        self.topic_terms.len()
+    }
+
+    /// Get the modeled terms, in the column order of the topic-term distributions
+    pub fn terms(&self) -> &[String] {
+        &self.terms
+    }
+
+    /// Get the modeled documents, in the row order of the document-topic distributions
+    pub fn document_ids(&self) -> &[DocumentId] {
+        &self.document_ids
+    }
+
+    /// Get the term distribution of a topic
+    pub fn topic_terms(&self, topic: usize) -> Option<&[f64]> {
+        self.topic_terms.get(topic).map(Vec::as_slice)
+    }
+
+    /// Get the topic distribution of a document
+    pub fn document_topics(&self, document_id: &DocumentId) -> Option<&[f64]> {
+        self.document_ids.iter()
+            .position(|id| id == document_id)
+            .map(|row| self.document_topics[row].as_slice())
+    }
+
+    /// Get the `n` most probable terms of a topic, most probable first
+    pub fn top_terms(&self, topic: usize, n: usize) -> Vec<(&str, f64)> {
+        let Some(distribution) = self.topic_terms.get(topic) else {
+            return Vec::new();
+        };
+
+        let mut terms: Vec<(&str, f64)> = self.terms.iter()
+            .map(String::as_str)
+            .zip(distribution.iter().copied())
+            .collect();
+        terms.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal).then_with(|| a.0.cmp(b.0)));
+        terms.truncate(n);
+        terms
+    }
+}
+
+/// Small seeded generator for the sampler, so no RNG dependency is needed
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [0, 1)
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Document, Term};
+
+    fn corpus() -> Corpus {
+        let mut corpus = Corpus::new("corpus1", "Topics");
+        let texts = [
+            ("1", "goal match striker goal keeper match"),
+            ("2", "striker goal match keeper goal"),
+            ("3", "compiler borrow rust compiler crate"),
+            ("4", "rust crate compiler borrow rust"),
+        ];
+        for (id, text) in texts {
+            let mut document = Document::new(id, text);
+            document.add_terms(text.split(' ').map(Term::new));
+            corpus.add_document(document).unwrap();
+        }
+        corpus.build_index().unwrap();
+        corpus
+    }
+
+    #[test]
+    fn test_fit_separates_topics() {
+        let options = LdaOptions { num_topics: 2, iterations: 100, ..LdaOptions::default() };
+        let model = Lda::new(options).fit(&corpus()).unwrap();
+
+        assert_eq!(model.num_topics(), 2);
+        assert_eq!(model.terms().len(), 8);
+        let total: f64 = model.topic_terms(0).unwrap().iter().sum();
+        assert!((total - 1.0).abs() < 1e-9);
+
+        let dominant = |id: &str| {
+            let topics = model.document_topics(&DocumentId::new(id)).unwrap();
+            if topics[0] > topics[1] { 0 } else { 1 }
+        };
+        assert_eq!(dominant("1"), dominant("2"));
+        assert_eq!(dominant("3"), dominant("4"));
+        assert_ne!(dominant("1"), dominant("3"));
+
+        let sports: Vec<&str> = model.top_terms(dominant("1"), 2).into_iter().map(|(term, _)| term).collect();
+        assert!(sports.contains(&"goal"));
+    }
+
+    #[test]
+    fn test_fit_requires_index() {
+        let corpus = Corpus::new("corpus2", "Empty");
+        assert!(Lda::default().fit(&corpus).is_err());
+    }
+}
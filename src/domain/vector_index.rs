@@ -0,0 +1,658 @@
+// src/domain/vector_index.rs
+
+use std::collections::HashMap;
+use std::sync::{Arc, PoisonError, RwLock};
+use serde::{Serialize, Deserialize};
+
+use super::{CorpusId, Corpus, Document, DocumentEmbedder, DocumentId, DocumentTermMatrix, DomainError, DomainResult, Term, TermId, TfIdfError};
+use super::embedding::dense_cosine_similarity;
+use super::term_interner;
+use super::SimilarityMetric;
+
+/// A document and its similarity to a reference document
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimilarDocument {
+    /// The similar document
+    document_id: DocumentId,
+    
+    /// Similarity to the reference document, higher is more similar: cosine
+    /// unless another metric was requested
+    similarity: f64,
+    
+    /// Distance to the reference document, for distance metrics
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    distance: Option<f64>,
+}
+
+impl SimilarDocument {
+    /// Create a new similar document entry
+    pub fn new(document_id: DocumentId, similarity: f64) -> Self {
+        Self { document_id, similarity, distance: None }
+    }
+    
+    /// Create an entry from a distance, with a similarity of `1 / (1 + distance)`
+    pub fn with_distance(document_id: DocumentId, distance: f64) -> Self {
+        Self { document_id, similarity: 1.0 / (1.0 + distance), distance: Some(distance) }
+    }
+    
+    /// Get the ID of the similar document
+    pub fn document_id(&self) -> &DocumentId {
+        &self.document_id
+    }
+    
+    /// Get the similarity to the reference document, higher for more similar documents under every metric
+    pub fn similarity(&self) -> f64 {
+        self.similarity
+    }
+    
+    /// Get the distance to the reference document, if it was found under a distance metric
+    pub fn distance(&self) -> Option<f64> {
+        self.distance
+    }
+}
+
+/// Pairwise cosine similarities between all documents of a corpus
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimilarityMatrix {
+    /// Document IDs in row/column order
+    document_ids: Vec<DocumentId>,
+    
+    /// Row-major N×N similarity values
+    values: Vec<f64>,
+}
+
+impl SimilarityMatrix {
+    /// Get the document IDs in row/column order
+    pub fn document_ids(&self) -> &[DocumentId] {
+        &self.document_ids
+    }
+    
+    /// Get the number of rows (and columns)
+    pub fn len(&self) -> usize {
+        self.document_ids.len()
+    }
+    
+    /// Check if the matrix is empty
+    pub fn is_empty(&self) -> bool {
+        self.document_ids.is_empty()
+    }
+    
+    /// Get the similarity at a row and column
+    pub fn get(&self, row: usize, column: usize) -> f64 {
+        self.values[row * self.len() + column]
+    }
+    
+    /// Get a row of the matrix
+    pub fn row(&self, row: usize) -> &[f64] {
+        let n = self.len();
+        &self.values[row * n..(row + 1) * n]
+    }
+    
+    /// Get the similarity between two documents by ID
+    pub fn similarity(&self, doc1_id: &DocumentId, doc2_id: &DocumentId) -> Option<f64> {
+        let row = self.document_ids.iter().position(|id| id == doc1_id)?;
+        let column = self.document_ids.iter().position(|id| id == doc2_id)?;
+        Some(self.get(row, column))
+    }
+    
+    /// Get the non-zero similarities above the diagonal as (row, column, similarity) triples
+    pub fn triples(&self) -> Vec<(usize, usize, f64)> {
+        let n = self.len();
+        (0..n)
+            .flat_map(|row| ((row + 1)..n).map(move |column| (row, column)))
+            .map(|(row, column)| (row, column, self.get(row, column)))
+            .filter(|(_, _, similarity)| *similarity != 0.0)
+            .collect()
+    }
+}
+
+/// The vector index a calculator built last, reused while its corpus is unchanged
+///
+/// Clones start empty, since a cloned calculator may be reconfigured.
+#[derive(Debug, Default)]
+pub(super) struct CachedVectorIndex(RwLock<Option<Arc<VectorIndex>>>);
+
+impl Clone for CachedVectorIndex {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl CachedVectorIndex {
+    /// Get the cached index if it is current for the corpus, building and caching a new one otherwise
+    pub(super) fn get_or_build(
+        &self,
+        corpus: &Corpus,
+        build: impl FnOnce() -> DomainResult<VectorIndex>,
+    ) -> DomainResult<Arc<VectorIndex>> {
+        // A panic while storing an index leaves a usable previous one, so poisoning is harmless
+        let cached = self.0.read().unwrap_or_else(PoisonError::into_inner).clone();
+        if let Some(index) = cached.filter(|index| !index.is_stale(corpus)) {
+            return Ok(index);
+        }
+
+        let index = Arc::new(build()?);
+        *self.0.write().unwrap_or_else(PoisonError::into_inner) = Some(Arc::clone(&index));
+        Ok(index)
+    }
+
+    /// Drop the cached index
+    pub(super) fn clear(&mut self) {
+        *self.0.get_mut().unwrap_or_else(PoisonError::into_inner) = None;
+    }
+}
+
+/// The documents with a non-zero weight for a term
+///
+/// The term is held only so its text stays interned and its ID is not reused.
+#[derive(Debug, Clone)]
+struct TermPostings {
+    _term: Term,
+    documents: Vec<(DocumentId, f64)>,
+}
+
+/// Cached TF-IDF vectors for every document in a corpus
+///
+/// The index remembers the corpus revision it was built from, so callers can
+/// tell when documents have been added or removed since and refresh it.
+/// Similarity queries against a fresh index only touch the two documents'
+/// vectors instead of re-vectorizing the corpus.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(from = "StoredVectorIndex")]
+pub struct VectorIndex {
+    /// The corpus the vectors were computed from
+    corpus_id: Option<CorpusId>,
+    
+    /// The corpus revision the vectors were computed from
+    revision: u64,
+    
+    /// TF-IDF vector of each document, keyed by term text
+    vectors: HashMap<DocumentId, HashMap<String, f64>>,
+    
+    /// L2 norm of each document vector
+    norms: HashMap<DocumentId, f64>,
+    
+    /// Inverted index from term ID to the documents with a non-zero weight for it,
+    /// rebuilt from `vectors` on load as IDs do not outlive the process
+    #[serde(skip)]
+    postings: HashMap<TermId, TermPostings>,
+    
+    /// Dense embedding of each document, for documents that have been embedded
+    #[serde(default)]
+    dense_vectors: HashMap<DocumentId, Vec<f32>>,
+    
+    /// Fingerprint of the content each dense embedding was computed from
+    #[serde(default)]
+    dense_sources: HashMap<DocumentId, u64>,
+}
+
+/// How a vector index is stored: without its postings
+#[derive(Deserialize)]
+struct StoredVectorIndex {
+    corpus_id: Option<CorpusId>,
+    revision: u64,
+    vectors: HashMap<DocumentId, HashMap<String, f64>>,
+    norms: HashMap<DocumentId, f64>,
+    #[serde(default)]
+    dense_vectors: HashMap<DocumentId, Vec<f32>>,
+    #[serde(default)]
+    dense_sources: HashMap<DocumentId, u64>,
+}
+
+impl From<StoredVectorIndex> for VectorIndex {
+    fn from(stored: StoredVectorIndex) -> Self {
+        let mut index = Self {
+            corpus_id: stored.corpus_id,
+            revision: stored.revision,
+            vectors: HashMap::new(),
+            norms: stored.norms,
+            postings: HashMap::new(),
+            dense_vectors: stored.dense_vectors,
+            dense_sources: stored.dense_sources,
+        };
+        for (id, vector) in &stored.vectors {
+            index.add_postings(id, vector);
+        }
+        index.vectors = stored.vectors;
+        index
+    }
+}
+
+impl VectorIndex {
+    /// Create an index from precomputed vectors for the given corpus state
+    pub fn new(corpus: &Corpus, vectors: HashMap<DocumentId, HashMap<String, f64>>) -> Self {
+        let norms = vectors.iter()
+            .map(|(id, vector)| (id.clone(), norm(vector)))
+            .collect();
+        
+        let mut index = Self {
+            corpus_id: Some(corpus.id().clone()),
+            revision: corpus.revision(),
+            vectors: HashMap::new(),
+            norms,
+            postings: HashMap::new(),
+            dense_vectors: HashMap::new(),
+            dense_sources: HashMap::new(),
+        };
+        for (id, vector) in &vectors {
+            index.add_postings(id, vector);
+        }
+        index.vectors = vectors;
+        index
+    }
+    
+    /// Check whether the corpus changed since this index was built
+    pub fn is_stale(&self, corpus: &Corpus) -> bool {
+        self.corpus_id.as_ref() != Some(corpus.id()) || self.revision != corpus.revision()
+    }
+    
+    /// Get the corpus revision the index was built from
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+    
+    /// Get the ID of the corpus the index was built from
+    pub fn corpus_id(&self) -> Option<&CorpusId> {
+        self.corpus_id.as_ref()
+    }
+    
+    /// Add or replace the vector of a document, updating its norm and postings
+    pub(super) fn insert_vector(&mut self, document_id: DocumentId, vector: HashMap<String, f64>) {
+        self.remove_vector(&document_id);
+        self.add_postings(&document_id, &vector);
+        self.norms.insert(document_id.clone(), norm(&vector));
+        self.vectors.insert(document_id, vector);
+    }
+    
+    /// Remove the vector of a document along with its norm and postings
+    pub(super) fn remove_vector(&mut self, document_id: &DocumentId) -> Option<HashMap<String, f64>> {
+        let vector = self.vectors.remove(document_id)?;
+        self.norms.remove(document_id);
+        self.dense_vectors.remove(document_id);
+        self.dense_sources.remove(document_id);
+        for term_id in vector.keys().filter_map(|text| term_interner::lookup(text)) {
+            if let Some(postings) = self.postings.get_mut(&term_id) {
+                postings.documents.retain(|(id, _)| id != document_id);
+                if postings.documents.is_empty() {
+                    self.postings.remove(&term_id);
+                }
+            }
+        }
+        Some(vector)
+    }
+    
+    /// Add a document's non-zero weights to the postings of their terms
+    fn add_postings(&mut self, document_id: &DocumentId, vector: &HashMap<String, f64>) {
+        for (text, weight) in vector {
+            if *weight != 0.0 {
+                let term = Term::new(text);
+                self.postings.entry(term.id())
+                    .or_insert_with(|| TermPostings { _term: term, documents: Vec::new() })
+                    .documents.push((document_id.clone(), *weight));
+            }
+        }
+    }
+    
+    /// Get the documents with a non-zero weight for a term text
+    fn term_postings(&self, text: &str) -> &[(DocumentId, f64)] {
+        term_interner::lookup(text)
+            .and_then(|term_id| self.postings.get(&term_id))
+            .map_or(&[], |postings| postings.documents.as_slice())
+    }
+    
+    /// Record that the index reflects the corpus's current revision
+    pub(super) fn mark_current(&mut self, corpus: &Corpus) {
+        self.revision = corpus.revision();
+    }
+    
+    /// Get the number of indexed documents
+    pub fn len(&self) -> usize {
+        self.vectors.len()
+    }
+    
+    /// Check if the index has no documents
+    pub fn is_empty(&self) -> bool {
+        self.vectors.is_empty()
+    }
+    
+    /// Get the vector of a document
+    pub fn vector(&self, document_id: &DocumentId) -> Option<&HashMap<String, f64>> {
+        self.vectors.get(document_id)
+    }
+    
+    /// Get the L2 norm of a document's vector
+    pub fn norm(&self, document_id: &DocumentId) -> Option<f64> {
+        self.norms.get(document_id).copied()
+    }
+    
+    /// Iterate over all document vectors
+    pub fn vectors(&self) -> impl Iterator<Item = (&DocumentId, &HashMap<String, f64>)> {
+        self.vectors.iter()
+    }
+    
+    /// Consume the index, returning the document vectors
+    pub fn into_vectors(self) -> HashMap<DocumentId, HashMap<String, f64>> {
+        self.vectors
+    }
+    
+    /// Get the vectors as a sparse document-term matrix
+    pub fn term_matrix(&self) -> DocumentTermMatrix {
+        DocumentTermMatrix::from_vectors(&self.vectors)
+    }
+    
+    /// Get the dense embedding of a document, if it has been embedded
+    pub fn dense_vector(&self, document_id: &DocumentId) -> Option<&[f32]> {
+        self.dense_vectors.get(document_id).map(Vec::as_slice)
+    }
+    
+    /// Store the dense embedding of an indexed document's content
+    ///
+    /// The embedding is dropped along with the document's TF-IDF vector when
+    /// the document is removed or re-vectorized, and kept across full
+    /// rebuilds as long as the content is unchanged.
+    pub fn set_dense_vector(&mut self, document: &Document, vector: Vec<f32>) -> DomainResult<()> {
+        self.lookup(document.id())?;
+        self.dense_sources.insert(document.id().clone(), content_fingerprint(document));
+        self.dense_vectors.insert(document.id().clone(), vector);
+        Ok(())
+    }
+    
+    /// Take over the dense embeddings of a previous index for documents whose content is unchanged
+    pub(super) fn keep_dense_vectors(&mut self, previous: VectorIndex, corpus: &Corpus) -> DomainResult<()> {
+        let VectorIndex { mut dense_vectors, dense_sources, .. } = previous;
+        for (id, source) in dense_sources {
+            let unchanged = self.vectors.contains_key(&id)
+                && corpus.get_document(&id)?.is_some_and(|document| content_fingerprint(document) == source);
+            if let Some(vector) = dense_vectors.remove(&id).filter(|_| unchanged) {
+                self.dense_sources.insert(id.clone(), source);
+                self.dense_vectors.insert(id, vector);
+            }
+        }
+        Ok(())
+    }
+    
+    /// Embed the content of every indexed document that has no dense vector yet, returning how many were embedded
+    pub fn embed_documents(&mut self, corpus: &Corpus, embedder: &dyn DocumentEmbedder) -> DomainResult<usize> {
+        let mut missing: Vec<&DocumentId> = self.vectors.keys()
+            .filter(|id| !self.dense_vectors.contains_key(*id))
+            .collect();
+        missing.sort_by(|a, b| a.value().cmp(b.value()));
+        
+        let documents = missing.iter()
+            .map(|id| corpus.get_document(id)?.ok_or_else(|| {
+                DomainError::TfIdfError(TfIdfError::DocumentNotFound(id.value().to_string()))
+            }))
+            .collect::<DomainResult<Vec<_>>>()?;
+        let texts: Vec<&str> = documents.iter().map(|document| document.content()).collect();
+        let embeddings = embedder.embed_batch(&texts)?;
+        if embeddings.len() != documents.len() {
+            return Err(DomainError::Other(format!(
+                "Embedder returned {} vectors for {} documents", embeddings.len(), documents.len()
+            )));
+        }
+        
+        let embedded = embeddings.len();
+        for (document, embedding) in documents.into_iter().zip(embeddings) {
+            self.dense_sources.insert(document.id().clone(), content_fingerprint(document));
+            self.dense_vectors.insert(document.id().clone(), embedding);
+        }
+        Ok(embedded)
+    }
+    
+    /// Find the `k` indexed documents most similar to a query, mixing TF-IDF and dense cosine similarity
+    ///
+    /// Each document scores `(1 - dense_weight) * sparse + dense_weight * dense`,
+    /// where `sparse` compares `vector` with its TF-IDF vector and `dense`
+    /// compares `dense_vector` with its embedding (0 if it has none). Fails if
+    /// `dense_vector` and the embeddings have different dimensions.
+    pub fn hybrid_nearest(
+        &self,
+        vector: &HashMap<String, f64>,
+        dense_vector: &[f32],
+        dense_weight: f64,
+        k: usize,
+    ) -> DomainResult<Vec<SimilarDocument>> {
+        let dense_weight = dense_weight.clamp(0.0, 1.0);
+        let mut scores: HashMap<&DocumentId, f64> = HashMap::new();
+        for similar in self.rank_against(vector, norm(vector), None, self.vectors.len()) {
+            if let Some((id, _)) = self.vectors.get_key_value(&similar.document_id) {
+                scores.insert(id, (1.0 - dense_weight) * similar.similarity);
+            }
+        }
+        if dense_weight > 0.0 {
+            for (id, embedding) in &self.dense_vectors {
+                *scores.entry(id).or_insert(0.0) += dense_weight * dense_cosine_similarity(dense_vector, embedding)?;
+            }
+        }
+        
+        let mut similar: Vec<SimilarDocument> = scores.into_iter()
+            .filter(|(_, score)| *score > 0.0)
+            .map(|(id, score)| SimilarDocument::new(id.clone(), score))
+            .collect();
+        similar.sort_by(|a, b| {
+            b.similarity.total_cmp(&a.similarity).then_with(|| a.document_id.value().cmp(b.document_id.value()))
+        });
+        similar.truncate(k);
+        
+        Ok(similar)
+    }
+    
+    /// Calculate the cosine similarity between two indexed documents
+    pub fn cosine_similarity(&self, doc1_id: &DocumentId, doc2_id: &DocumentId) -> DomainResult<f64> {
+        let vec1 = self.lookup(doc1_id)?;
+        let vec2 = self.lookup(doc2_id)?;
+        
+        let magnitude = self.norms[doc1_id] * self.norms[doc2_id];
+        if magnitude == 0.0 {
+            return Ok(0.0);
+        }
+        
+        Ok(dot_product(vec1, vec2) / magnitude)
+    }
+    
+    /// Compare two indexed documents under a metric
+    pub fn compare(&self, doc1_id: &DocumentId, doc2_id: &DocumentId, metric: SimilarityMetric) -> DomainResult<f64> {
+        match metric {
+            SimilarityMetric::Cosine => self.cosine_similarity(doc1_id, doc2_id),
+            metric => Ok(metric.compare(self.lookup(doc1_id)?, self.lookup(doc2_id)?)),
+        }
+    }
+    
+    /// Find the `k` documents most similar to the given document under a metric, most similar first
+    ///
+    /// Cosine and Jaccard only consider documents sharing a term, found through
+    /// the inverted index; distance metrics compare against every document.
+    pub fn most_similar_by(&self, document_id: &DocumentId, k: usize, metric: SimilarityMetric) -> DomainResult<Vec<SimilarDocument>> {
+        let vector = self.lookup(document_id)?;
+        let candidates: Vec<&DocumentId> = match metric {
+            SimilarityMetric::Cosine => return self.most_similar(document_id, k),
+            SimilarityMetric::Jaccard => {
+                let mut sharing: Vec<&DocumentId> = vector.keys()
+                    .flat_map(|term| self.term_postings(term).iter().map(|(id, _)| id))
+                    .collect();
+                sharing.sort_by(|a, b| a.value().cmp(b.value()));
+                sharing.dedup();
+                sharing
+            },
+            SimilarityMetric::Euclidean | SimilarityMetric::Manhattan => self.vectors.keys().collect(),
+        };
+        
+        let mut similar: Vec<SimilarDocument> = candidates.into_iter()
+            .filter(|other_id| *other_id != document_id)
+            .map(|other_id| {
+                let value = metric.compare(vector, &self.vectors[other_id]);
+                if metric.is_distance() {
+                    SimilarDocument::with_distance(other_id.clone(), value)
+                } else {
+                    SimilarDocument::new(other_id.clone(), value)
+                }
+            })
+            .collect();
+        similar.sort_by(|a, b| {
+            b.similarity.total_cmp(&a.similarity).then_with(|| a.document_id.value().cmp(b.document_id.value()))
+        });
+        similar.truncate(k);
+        
+        Ok(similar)
+    }
+    
+    /// Calculate the cosine similarity between an indexed document and an arbitrary vector
+    pub fn similarity_to(&self, document_id: &DocumentId, vector: &HashMap<String, f64>) -> DomainResult<f64> {
+        Ok(cosine_similarity(self.lookup(document_id)?, vector))
+    }
+    
+    /// Find the `k` documents most similar to the given document, most similar first
+    ///
+    /// Only documents sharing at least one weighted term are considered, found
+    /// through the inverted index rather than by comparing against every document.
+    pub fn most_similar(&self, document_id: &DocumentId, k: usize) -> DomainResult<Vec<SimilarDocument>> {
+        let vector = self.lookup(document_id)?;
+        Ok(self.rank_against(vector, self.norms[document_id], Some(document_id), k))
+    }
+    
+    /// Find every document whose cosine similarity to the given document exceeds a threshold, most similar first
+    ///
+    /// Candidates come from the inverted index, so documents sharing no term
+    /// (similarity 0) are never returned and no similarity matrix is built.
+    pub fn above_similarity(&self, document_id: &DocumentId, threshold: f64) -> DomainResult<Vec<SimilarDocument>> {
+        let vector = self.lookup(document_id)?;
+        let mut similar = self.rank_against(vector, self.norms[document_id], Some(document_id), self.vectors.len());
+        similar.retain(|document| document.similarity > threshold);
+        Ok(similar)
+    }
+    
+    /// Find the `k` indexed documents most similar to an arbitrary vector, most similar first
+    ///
+    /// The vector must be weighted like the indexed ones, e.g. with `TfIdf::document_vector`
+    /// against the same corpus.
+    pub fn nearest(&self, vector: &HashMap<String, f64>, k: usize) -> Vec<SimilarDocument> {
+        self.rank_against(vector, norm(vector), None, k)
+    }
+    
+    /// Rank documents sharing a weighted term with `vector` by cosine similarity, skipping `exclude`
+    fn rank_against(
+        &self,
+        vector: &HashMap<String, f64>,
+        norm: f64,
+        exclude: Option<&DocumentId>,
+        k: usize,
+    ) -> Vec<SimilarDocument> {
+        if norm == 0.0 || k == 0 {
+            return Vec::new();
+        }
+        
+        // Accumulate dot products with every document sharing a term
+        let mut dot_products: HashMap<&DocumentId, f64> = HashMap::new();
+        for (term, weight) in vector {
+            for (other_id, other_weight) in self.term_postings(term) {
+                if Some(other_id) != exclude {
+                    *dot_products.entry(other_id).or_insert(0.0) += weight * other_weight;
+                }
+            }
+        }
+        
+        let mut similar: Vec<SimilarDocument> = dot_products.into_iter()
+            .filter_map(|(other_id, dot_product)| {
+                let magnitude = norm * self.norms[other_id];
+                let similarity = if magnitude == 0.0 { 0.0 } else { dot_product / magnitude };
+                (similarity > 0.0).then(|| SimilarDocument::new(other_id.clone(), similarity))
+            })
+            .collect();
+        
+        similar.sort_by(|a, b| {
+            b.similarity.partial_cmp(&a.similarity).unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.document_id.value().cmp(b.document_id.value()))
+        });
+        similar.truncate(k);
+        
+        similar
+    }
+    
+    /// Compute the full pairwise cosine similarity matrix
+    ///
+    /// Dot products are accumulated through the inverted index with the cached
+    /// norms, so document pairs without shared terms cost nothing. Rows are
+    /// computed in parallel when the `parallel` feature is enabled.
+    pub fn similarity_matrix(&self) -> SimilarityMatrix {
+        let mut document_ids: Vec<DocumentId> = self.vectors.keys().cloned().collect();
+        document_ids.sort_by(|a, b| a.value().cmp(b.value()));
+        
+        let positions: HashMap<&DocumentId, usize> = document_ids.iter()
+            .enumerate()
+            .map(|(position, id)| (id, position))
+            .collect();
+        
+        let n = document_ids.len();
+        let compute_row = |id: &DocumentId| -> Vec<f64> {
+            let mut row = vec![0.0; n];
+            let norm = self.norms[id];
+            if norm == 0.0 {
+                return row;
+            }
+            
+            for (term, weight) in &self.vectors[id] {
+                for (other_id, other_weight) in self.term_postings(term) {
+                    row[positions[other_id]] += weight * other_weight;
+                }
+            }
+            
+            for (column, other_id) in document_ids.iter().enumerate() {
+                let magnitude = norm * self.norms[other_id];
+                row[column] = if magnitude == 0.0 { 0.0 } else { row[column] / magnitude };
+            }
+            
+            row
+        };
+        
+        #[cfg(feature = "parallel")]
+        let rows: Vec<Vec<f64>> = {
+            use rayon::prelude::*;
+            document_ids.par_iter().map(compute_row).collect()
+        };
+        
+        #[cfg(not(feature = "parallel"))]
+        let rows: Vec<Vec<f64>> = document_ids.iter().map(compute_row).collect();
+        
+        SimilarityMatrix {
+            values: rows.concat(),
+            document_ids,
+        }
+    }
+    
+    fn lookup(&self, document_id: &DocumentId) -> DomainResult<&HashMap<String, f64>> {
+        self.vectors.get(document_id).ok_or_else(|| {
+            DomainError::TfIdfError(TfIdfError::DocumentNotFound(document_id.value().to_string()))
+        })
+    }
+}
+
+/// Calculate the cosine similarity between two sparse vectors
+pub(crate) fn cosine_similarity(vec1: &HashMap<String, f64>, vec2: &HashMap<String, f64>) -> f64 {
+    let magnitude = norm(vec1) * norm(vec2);
+    if magnitude == 0.0 {
+        return 0.0;
+    }
+    
+    dot_product(vec1, vec2) / magnitude
+}
+
+/// L2 norm of a sparse vector
+fn norm(vector: &HashMap<String, f64>) -> f64 {
+    vector.values().map(|v| v * v).sum::<f64>().sqrt()
+}
+
+/// 64-bit FNV-1a hash of a document's content, to tell whether its embedding is still current
+fn content_fingerprint(document: &Document) -> u64 {
+    document.content().bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Dot product of two sparse vectors
+fn dot_product(vec1: &HashMap<String, f64>, vec2: &HashMap<String, f64>) -> f64 {
+    // Iterate over the smaller vector; terms missing from either side contribute nothing
+    let (small, large) = if vec1.len() <= vec2.len() { (vec1, vec2) } else { (vec2, vec1) };
+    small.iter()
+        .filter_map(|(term, value)| large.get(term).map(|other| value * other))
+        .sum()
+}
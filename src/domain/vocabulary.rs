@@ -0,0 +1,313 @@
+// src/domain/vocabulary.rs
+
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
+
+use super::{Document, Term};
+use super::term::term_map;
+
+/// Corpus-wide statistics of a single term
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TermStats {
+    /// Stable numeric ID of the term within the vocabulary
+    id: usize,
+
+    /// Number of documents containing the term in their content
+    document_frequency: usize,
+
+    /// Total occurrences of the term across all document content
+    collection_frequency: usize,
+}
+
+impl TermStats {
+    /// Get the term ID
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Get the number of documents containing the term
+    pub fn document_frequency(&self) -> usize {
+        self.document_frequency
+    }
+
+    /// Get the total occurrences of the term in document content
+    pub fn collection_frequency(&self) -> usize {
+        self.collection_frequency
+    }
+}
+
+/// A term's statistics in portable form, as exported from a corpus vocabulary
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VocabularyEntry {
+    term: String,
+    document_frequency: usize,
+    collection_frequency: usize,
+
+    /// IDF of the term in the exporting corpus, weighted by its TF-IDF options
+    idf: f64,
+}
+
+impl VocabularyEntry {
+    /// Create an entry
+    pub fn new(term: impl Into<String>, document_frequency: usize, collection_frequency: usize, idf: f64) -> Self {
+        Self { term: term.into(), document_frequency, collection_frequency, idf }
+    }
+
+    /// Get the term text
+    pub fn term(&self) -> &str {
+        &self.term
+    }
+
+    /// Get the number of documents containing the term
+    pub fn document_frequency(&self) -> usize {
+        self.document_frequency
+    }
+
+    /// Get the total occurrences of the term in document content
+    pub fn collection_frequency(&self) -> usize {
+        self.collection_frequency
+    }
+
+    /// Get the IDF of the term in the exporting corpus
+    pub fn idf(&self) -> f64 {
+        self.idf
+    }
+}
+
+/// The set of indexed terms of a corpus, with an ID and statistics for each
+///
+/// IDs are assigned in insertion order and stay stable while a term remains in
+/// the vocabulary; the ID of a term that drops out is not reused.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(from = "SerializedVocabulary")]
+pub struct Vocabulary {
+    /// Statistics for each term
+    #[serde(with = "term_map")]
+    terms: HashMap<Term, TermStats>,
+
+    /// Term with each ID, or `None` once the term has dropped out
+    #[serde(skip_serializing)]
+    ids: Vec<Option<Term>>,
+
+    /// Total occurrences of each canonical term form across all document content
+    #[serde(skip_serializing)]
+    canonical_collection_frequencies: HashMap<String, usize>,
+
+    /// ID to assign to the next new term
+    next_id: usize,
+
+    /// Total number of terms across all document content
+    total_term_count: usize,
+}
+
+/// The stored fields of a vocabulary, from which the lookups are rebuilt
+#[derive(Deserialize)]
+struct SerializedVocabulary {
+    #[serde(with = "term_map")]
+    terms: HashMap<Term, TermStats>,
+    next_id: usize,
+    total_term_count: usize,
+}
+
+impl From<SerializedVocabulary> for Vocabulary {
+    fn from(serialized: SerializedVocabulary) -> Self {
+        let mut ids = vec![None; serialized.next_id];
+        let mut canonical_collection_frequencies = HashMap::new();
+        for (term, stats) in &serialized.terms {
+            if let Some(slot) = ids.get_mut(stats.id) {
+                *slot = Some(term.clone());
+            }
+            *canonical_collection_frequencies.entry(term.canonical().to_string()).or_insert(0) += stats.collection_frequency;
+        }
+
+        Self {
+            terms: serialized.terms,
+            ids,
+            canonical_collection_frequencies,
+            next_id: serialized.next_id,
+            total_term_count: serialized.total_term_count,
+        }
+    }
+}
+
+impl Vocabulary {
+    /// Create an empty vocabulary
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a vocabulary from a set of documents
+    ///
+    /// Terms are numbered in sorted order, so the same documents always yield the same IDs.
+    pub fn from_documents<'a>(documents: impl IntoIterator<Item = &'a Document>) -> Self {
+        let documents: Vec<&Document> = documents.into_iter().collect();
+
+        let mut terms: Vec<&Term> = documents.iter().flat_map(|document| document.content_terms()).collect();
+        terms.sort_by(|a, b| a.text().cmp(b.text()));
+        terms.dedup();
+
+        let mut vocabulary = Self::new();
+        for term in terms {
+            vocabulary.insert(term);
+        }
+        for document in documents {
+            vocabulary.add_document(document);
+        }
+        vocabulary
+    }
+
+    /// Get the statistics of a term, adding it with a new ID if it is not known yet
+    fn insert(&mut self, term: &Term) -> &mut TermStats {
+        let next_id = &mut self.next_id;
+        let ids = &mut self.ids;
+        self.terms.entry(term.clone()).or_insert_with(|| {
+            ids.push(Some(term.clone()));
+            *next_id += 1;
+            TermStats { id: *next_id - 1, document_frequency: 0, collection_frequency: 0 }
+        })
+    }
+
+    /// Count a document's terms
+    pub fn add_document(&mut self, document: &Document) {
+        for term in document.content_terms() {
+            let frequency = document.term_frequency(term).value();
+            let stats = self.insert(term);
+            stats.document_frequency += 1;
+            stats.collection_frequency += frequency;
+            *self.canonical_collection_frequencies.entry(term.canonical().to_string()).or_insert(0) += frequency;
+        }
+        self.total_term_count += document.term_count();
+    }
+
+    /// Stop counting a document's terms, dropping terms no document contains anymore
+    pub fn remove_document(&mut self, document: &Document) {
+        for term in document.content_terms() {
+            if let Some(stats) = self.terms.get_mut(term) {
+                let frequency = document.term_frequency(term).value();
+                stats.document_frequency = stats.document_frequency.saturating_sub(1);
+                stats.collection_frequency = stats.collection_frequency.saturating_sub(frequency);
+                if stats.document_frequency == 0 {
+                    self.ids[stats.id] = None;
+                    self.terms.remove(term);
+                }
+
+                if let Some(count) = self.canonical_collection_frequencies.get_mut(term.canonical()) {
+                    *count = count.saturating_sub(frequency);
+                    if *count == 0 {
+                        self.canonical_collection_frequencies.remove(term.canonical());
+                    }
+                }
+            }
+        }
+        self.total_term_count = self.total_term_count.saturating_sub(document.term_count());
+    }
+
+    /// Get the ID of a term
+    pub fn id(&self, term: &Term) -> Option<usize> {
+        self.terms.get(term).map(TermStats::id)
+    }
+
+    /// Get the term with an ID
+    pub fn term(&self, id: usize) -> Option<&Term> {
+        self.ids.get(id).and_then(Option::as_ref)
+    }
+
+    /// Get the statistics of a term
+    pub fn stats(&self, term: &Term) -> Option<&TermStats> {
+        self.terms.get(term)
+    }
+
+    /// Get the number of documents containing a term
+    pub fn document_frequency(&self, term: &Term) -> usize {
+        self.stats(term).map_or(0, TermStats::document_frequency)
+    }
+
+    /// Get the total occurrences of a term in document content
+    pub fn collection_frequency(&self, term: &Term) -> usize {
+        self.stats(term).map_or(0, TermStats::collection_frequency)
+    }
+
+    /// Get the total occurrences of every term with the given canonical form in document content
+    pub fn canonical_collection_frequency(&self, canonical: &str) -> usize {
+        self.canonical_collection_frequencies.get(canonical).copied().unwrap_or(0)
+    }
+
+    /// Get the total number of terms across all document content
+    pub fn total_term_count(&self) -> usize {
+        self.total_term_count
+    }
+
+    /// Get the number of distinct terms
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// Check whether the vocabulary has no terms
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Check whether a term is in the vocabulary
+    pub fn contains(&self, term: &Term) -> bool {
+        self.terms.contains_key(term)
+    }
+
+    /// Iterate over the terms and their statistics in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (&Term, &TermStats)> {
+        self.terms.iter()
+    }
+
+    /// Get the terms ordered by document frequency (highest first), then by text
+    pub fn by_document_frequency(&self) -> Vec<(&Term, &TermStats)> {
+        let mut terms: Vec<(&Term, &TermStats)> = self.terms.iter().collect();
+        terms.sort_by(|a, b| {
+            b.1.document_frequency.cmp(&a.1.document_frequency).then_with(|| a.0.text().cmp(b.0.text()))
+        });
+        terms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(id: &str, words: &[&str]) -> Document {
+        let mut document = Document::new(id, words.join(" "));
+        document.add_terms(words.iter().map(|word| Term::new(*word)));
+        document
+    }
+
+    #[test]
+    fn test_term_statistics() {
+        let rust = document("doc1", &["rust", "is", "fast", "rust"]);
+        let go = document("doc2", &["go", "is", "simple"]);
+        let mut vocabulary = Vocabulary::from_documents([&rust, &go]);
+
+        assert_eq!(vocabulary.len(), 5);
+        assert_eq!(vocabulary.total_term_count(), 7);
+        assert_eq!(vocabulary.document_frequency(&Term::new("rust")), 1);
+        assert_eq!(vocabulary.collection_frequency(&Term::new("rust")), 2);
+        assert_eq!(vocabulary.document_frequency(&Term::new("is")), 2);
+
+        // IDs follow sorted term order and map back to their terms
+        assert_eq!(vocabulary.id(&Term::new("fast")), Some(0));
+        assert_eq!(vocabulary.term(4).map(Term::text), Some("simple"));
+
+        let ordered: Vec<&str> = vocabulary.by_document_frequency().iter().map(|(term, _)| term.text()).collect();
+        assert_eq!(ordered, vec!["is", "fast", "go", "rust", "simple"]);
+
+        vocabulary.remove_document(&go);
+        assert!(!vocabulary.contains(&Term::new("go")));
+        assert_eq!(vocabulary.document_frequency(&Term::new("is")), 1);
+        assert_eq!(vocabulary.total_term_count(), 4);
+        assert_eq!(vocabulary.id(&Term::new("simple")), None);
+        assert_eq!(vocabulary.id(&Term::new("rust")), Some(3));
+        assert_eq!(vocabulary.term(1), None);
+
+        // The ID lookup survives a round trip through serialization
+        let restored: Vocabulary = serde_json::from_str(&serde_json::to_string(&vocabulary).unwrap()).unwrap();
+        assert_eq!(restored.term(3).map(Term::text), Some("rust"));
+        assert_eq!(restored.term(1), None);
+        assert_eq!(restored.canonical_collection_frequency("rust"), 2);
+    }
+}
@@ -0,0 +1,363 @@
+// src/domain/weighting.rs
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Serialize, Deserialize};
+
+use super::{DomainError, DomainResult};
+
+/// How a term's raw count in a document becomes its term-frequency weight
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TfWeight {
+    /// The raw count (`n`)
+    Natural,
+
+    /// 1 + ln(count), or 0 for absent terms (`l`)
+    Logarithmic,
+
+    /// 0.5 + 0.5 * count / max count in the document, or 0 for absent terms (`a`)
+    Augmented,
+
+    /// 1 if the term occurs at all, else 0 (`b`)
+    Boolean,
+}
+
+impl TfWeight {
+    /// Weight a term frequency, given the highest term frequency in the same document
+    pub fn weight(&self, term_frequency: f64, max_term_frequency: f64) -> f64 {
+        if term_frequency <= 0.0 {
+            return 0.0;
+        }
+
+        match self {
+            TfWeight::Natural => term_frequency,
+            TfWeight::Logarithmic => 1.0 + term_frequency.ln(),
+            TfWeight::Augmented => {
+                // Boosted field hits can exceed the content maximum, so cap the ratio
+                let ratio = if max_term_frequency > 0.0 { (term_frequency / max_term_frequency).min(1.0) } else { 1.0 };
+                0.5 + 0.5 * ratio
+            },
+            TfWeight::Boolean => 1.0,
+        }
+    }
+
+    /// Get the SMART letter
+    pub fn letter(&self) -> char {
+        match self {
+            TfWeight::Natural => 'n',
+            TfWeight::Logarithmic => 'l',
+            TfWeight::Augmented => 'a',
+            TfWeight::Boolean => 'b',
+        }
+    }
+
+    fn from_letter(letter: char) -> Option<Self> {
+        match letter {
+            'n' => Some(TfWeight::Natural),
+            'l' => Some(TfWeight::Logarithmic),
+            'a' => Some(TfWeight::Augmented),
+            'b' => Some(TfWeight::Boolean),
+            _ => None,
+        }
+    }
+}
+
+/// How a term's document frequency becomes its inverse-document-frequency weight
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IdfWeight {
+    /// Every term weighs 1.0 (`n`)
+    None,
+
+    /// ln(N / df) (`t`)
+    Idf,
+
+    /// max(0, ln((N - df) / df)) (`p`)
+    Probabilistic,
+
+    /// ln(N / (df + 1)), as with `Smoothing::AddOne` (`s`)
+    Smoothed,
+}
+
+impl IdfWeight {
+    /// Weight a term found in `document_frequency` of `document_count` documents
+    ///
+    /// Terms no document contains weigh 0.0 under `IdfWeight::Idf` and
+    /// `IdfWeight::Probabilistic`; `IdfWeight::Smoothed` weighs them exactly as
+    /// `Smoothing::AddOne` does.
+    pub fn weight(&self, document_frequency: usize, document_count: usize) -> f64 {
+        let df = document_frequency as f64;
+        let n = document_count as f64;
+        match self {
+            IdfWeight::None => 1.0,
+            IdfWeight::Smoothed => Smoothing::AddOne.idf(document_frequency, document_count),
+            _ if document_frequency == 0 || document_count == 0 => 0.0,
+            IdfWeight::Idf => (n / df).ln(),
+            IdfWeight::Probabilistic => ((n - df) / df).ln().max(0.0),
+        }
+    }
+
+    /// Get the SMART letter
+    pub fn letter(&self) -> char {
+        match self {
+            IdfWeight::None => 'n',
+            IdfWeight::Idf => 't',
+            IdfWeight::Probabilistic => 'p',
+            IdfWeight::Smoothed => 's',
+        }
+    }
+
+    fn from_letter(letter: char) -> Option<Self> {
+        match letter {
+            'n' => Some(IdfWeight::None),
+            't' => Some(IdfWeight::Idf),
+            'p' => Some(IdfWeight::Probabilistic),
+            's' => Some(IdfWeight::Smoothed),
+            _ => None,
+        }
+    }
+}
+
+/// How the classic IDF is smoothed when no scheme or custom IDF function is set
+///
+/// Adding to the document frequency alone drives the IDF of a term found in
+/// nearly every document to zero or below, which in small corpora silences
+/// common but meaningful terms; `Bm25Style` keeps every IDF positive.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Smoothing {
+    /// ln(N / df), or 0 for terms no document contains
+    None,
+
+    /// ln(N / (df + 1))
+    #[default]
+    AddOne,
+
+    /// ln(N / (df + k))
+    AddK(f64),
+
+    /// ln(1 + (N - df + 0.5) / (df + 0.5)), the BM25 IDF
+    Bm25Style,
+}
+
+impl Smoothing {
+    /// Get the smoothed IDF of a term found in `document_frequency` of `document_count` documents
+    pub fn idf(&self, document_frequency: usize, document_count: usize) -> f64 {
+        if document_count == 0 {
+            return 0.0;
+        }
+
+        let df = document_frequency as f64;
+        let n = document_count as f64;
+        let added = match self {
+            Smoothing::None => 0.0,
+            Smoothing::AddOne => 1.0,
+            Smoothing::AddK(k) => *k,
+            Smoothing::Bm25Style => return (1.0 + (n - df + 0.5) / (df + 0.5)).ln(),
+        };
+
+        if df + added <= 0.0 {
+            return 0.0;
+        }
+        (n / (df + added)).ln()
+    }
+
+    /// Check that an added constant is finite and not negative
+    ///
+    /// A negative constant pushes `df + k` towards zero and the IDF towards infinity.
+    pub fn validate(&self) -> DomainResult<()> {
+        match *self {
+            Smoothing::AddK(k) if !k.is_finite() || k < 0.0 => Err(DomainError::InvalidOperation(
+                format!("Smoothing constant must be finite and at least 0, got {}", k)
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// How a document's term weights are scaled
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Normalization {
+    /// Leave weights as they are (`n`)
+    None,
+
+    /// Divide by the vector's L2 norm (`c`)
+    Cosine,
+
+    /// Divide by (1 - slope) + slope * length / pivot (`u`)
+    ///
+    /// Length is the document's term count and the pivot defaults to the
+    /// corpus's average document length, so documents of average length keep
+    /// their weights while longer ones are scaled down and shorter ones up.
+    Pivoted { slope: f64, pivot: Option<f64> },
+}
+
+impl Normalization {
+    /// Slope used when pivoted normalization is selected by its SMART letter
+    pub const DEFAULT_SLOPE: f64 = 0.2;
+
+    /// Get the divisor pivoted normalization applies to a document, or None for other normalizations
+    pub fn pivoted_divisor(&self, document_length: usize, average_document_length: f64) -> Option<f64> {
+        let Normalization::Pivoted { slope, pivot } = *self else {
+            return None;
+        };
+
+        let pivot = pivot.unwrap_or(average_document_length);
+        if pivot <= 0.0 {
+            return Some(1.0);
+        }
+        Some((1.0 - slope) + slope * document_length as f64 / pivot)
+    }
+
+    /// Check that a pivoted normalization's slope lies between 0 and 1
+    pub fn validate(&self) -> DomainResult<()> {
+        match *self {
+            Normalization::Pivoted { slope, .. } if !(0.0..=1.0).contains(&slope) => Err(DomainError::InvalidOperation(
+                format!("Pivoted normalization slope must be between 0 and 1, got {}", slope)
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Get the SMART letter
+    pub fn letter(&self) -> char {
+        match self {
+            Normalization::None => 'n',
+            Normalization::Cosine => 'c',
+            Normalization::Pivoted { .. } => 'u',
+        }
+    }
+
+    fn from_letter(letter: char) -> Option<Self> {
+        match letter {
+            'n' => Some(Normalization::None),
+            'c' => Some(Normalization::Cosine),
+            'u' => Some(Normalization::Pivoted { slope: Self::DEFAULT_SLOPE, pivot: None }),
+            _ => None,
+        }
+    }
+}
+
+/// A term weighting scheme in SMART notation: TF, IDF and normalization, e.g. `ltc`
+///
+/// The notation does not carry pivoted normalization parameters; `u` parses
+/// to the default slope and the corpus average as pivot.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Scheme {
+    pub tf: TfWeight,
+    pub idf: IdfWeight,
+    pub normalization: Normalization,
+}
+
+impl Scheme {
+    /// Create a scheme from its parts
+    pub const fn new(tf: TfWeight, idf: IdfWeight, normalization: Normalization) -> Self {
+        Self { tf, idf, normalization }
+    }
+
+    /// Logarithmic TF, IDF, cosine normalization
+    pub const fn ltc() -> Self {
+        Self::new(TfWeight::Logarithmic, IdfWeight::Idf, Normalization::Cosine)
+    }
+
+    /// Logarithmic TF, no IDF, cosine normalization; the usual document side of `lnc.ltc`
+    pub const fn lnc() -> Self {
+        Self::new(TfWeight::Logarithmic, IdfWeight::None, Normalization::Cosine)
+    }
+
+    /// Raw counts times IDF, cosine normalization
+    pub const fn ntc() -> Self {
+        Self::new(TfWeight::Natural, IdfWeight::Idf, Normalization::Cosine)
+    }
+
+    /// Raw counts times IDF, unnormalized
+    pub const fn ntn() -> Self {
+        Self::new(TfWeight::Natural, IdfWeight::Idf, Normalization::None)
+    }
+
+    /// Logarithmic TF, smoothed IDF, cosine normalization; what the default options compute
+    pub const fn lsc() -> Self {
+        Self::new(TfWeight::Logarithmic, IdfWeight::Smoothed, Normalization::Cosine)
+    }
+}
+
+impl fmt::Display for Scheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}{}", self.tf.letter(), self.idf.letter(), self.normalization.letter())
+    }
+}
+
+impl FromStr for Scheme {
+    type Err = DomainError;
+
+    /// Parse three SMART letters such as `ltc`, ignoring case
+    fn from_str(notation: &str) -> Result<Self, Self::Err> {
+        let invalid = || DomainError::InvalidOperation(format!("Unknown weighting scheme '{}'", notation));
+
+        let letters: Vec<char> = notation.to_ascii_lowercase().chars().collect();
+        let [tf, idf, normalization] = letters[..] else {
+            return Err(invalid());
+        };
+
+        Ok(Scheme::new(
+            TfWeight::from_letter(tf).ok_or_else(invalid)?,
+            IdfWeight::from_letter(idf).ok_or_else(invalid)?,
+            Normalization::from_letter(normalization).ok_or_else(invalid)?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weights() {
+        assert_eq!(TfWeight::Natural.weight(3.0, 4.0), 3.0);
+        assert_eq!(TfWeight::Logarithmic.weight(1.0, 4.0), 1.0);
+        assert_eq!(TfWeight::Logarithmic.weight(0.0, 4.0), 0.0);
+        assert_eq!(TfWeight::Augmented.weight(2.0, 4.0), 0.75);
+        assert_eq!(TfWeight::Augmented.weight(4.0, 4.0), 1.0);
+        assert_eq!(TfWeight::Augmented.weight(0.0, 4.0), 0.0);
+        assert_eq!(TfWeight::Boolean.weight(3.0, 4.0), 1.0);
+
+        assert_eq!(IdfWeight::None.weight(0, 4), 1.0);
+        assert!((IdfWeight::Idf.weight(1, 4) - 4f64.ln()).abs() < 1e-12);
+        assert!((IdfWeight::Probabilistic.weight(1, 4) - 3f64.ln()).abs() < 1e-12);
+        assert_eq!(IdfWeight::Probabilistic.weight(3, 4), 0.0);
+        assert!((IdfWeight::Smoothed.weight(1, 4) - 2f64.ln()).abs() < 1e-12);
+        assert_eq!(IdfWeight::Idf.weight(0, 4), 0.0);
+        assert_eq!(IdfWeight::Smoothed.weight(0, 4), Smoothing::AddOne.idf(0, 4));
+
+        assert_eq!(Smoothing::None.idf(0, 4), 0.0);
+        assert!((Smoothing::AddOne.idf(1, 4) - 2f64.ln()).abs() < 1e-12);
+        assert!((Smoothing::AddK(0.5).idf(1, 3) - 2f64.ln()).abs() < 1e-12);
+        assert_eq!(Smoothing::AddOne.idf(3, 4), 0.0);
+        assert!(Smoothing::Bm25Style.idf(4, 4) > 0.0);
+        assert!(Smoothing::AddK(0.5).validate().is_ok());
+        assert!(Smoothing::AddK(-0.999).validate().is_err());
+        assert!(Smoothing::AddK(f64::NAN).validate().is_err());
+        assert!(Smoothing::AddK(f64::INFINITY).validate().is_err());
+
+        let pivoted = Normalization::Pivoted { slope: 0.25, pivot: None };
+        assert_eq!(pivoted.pivoted_divisor(10, 10.0), Some(1.0));
+        assert_eq!(pivoted.pivoted_divisor(20, 10.0), Some(1.25));
+        assert_eq!(Normalization::Pivoted { slope: 0.25, pivot: Some(20.0) }.pivoted_divisor(20, 10.0), Some(1.0));
+        assert_eq!(Normalization::Cosine.pivoted_divisor(20, 10.0), None);
+        assert!(pivoted.validate().is_ok());
+        assert!(Normalization::Pivoted { slope: 1.5, pivot: None }.validate().is_err());
+        assert!(Normalization::Pivoted { slope: f64::NAN, pivot: None }.validate().is_err());
+    }
+
+    #[test]
+    fn test_notation() {
+        let scheme: Scheme = "LTC".parse().unwrap();
+        assert_eq!(scheme, Scheme::ltc());
+        assert_eq!(scheme.to_string(), "ltc");
+        assert_eq!("nsn".parse::<Scheme>().unwrap().idf, IdfWeight::Smoothed);
+        assert_eq!("atc".parse::<Scheme>().unwrap().tf, TfWeight::Augmented);
+
+        assert_eq!("ltu".parse::<Scheme>().unwrap().to_string(), "ltu");
+        assert!("lt".parse::<Scheme>().is_err());
+        assert!("xtc".parse::<Scheme>().is_err());
+    }
+}
@@ -0,0 +1,326 @@
+// src/infrastructure/export/columnar.rs
+
+use std::fs::File;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use arrow_array::{ArrayRef, Float64Array, RecordBatch, StringArray, UInt64Array};
+use arrow_ipc::writer::FileWriter;
+use arrow_schema::{DataType, Field, Schema};
+use parquet::arrow::ArrowWriter;
+
+use crate::domain::{DocumentTermMatrix, Vocabulary};
+use crate::infrastructure::{InfrastructureError, InfrastructureResult};
+
+/// Values of one column, typed as the matching Arrow array
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnData {
+    /// Arrow `Utf8`
+    Utf8(Vec<String>),
+
+    /// Arrow `UInt64`
+    UInt64(Vec<u64>),
+
+    /// Arrow `Float64`
+    Float64(Vec<f64>),
+}
+
+impl ColumnData {
+    /// Get the number of values
+    pub fn len(&self) -> usize {
+        match self {
+            ColumnData::Utf8(values) => values.len(),
+            ColumnData::UInt64(values) => values.len(),
+            ColumnData::Float64(values) => values.len(),
+        }
+    }
+
+    /// Check if the column has no values
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the Arrow data type of the column
+    pub fn data_type(&self) -> DataType {
+        match self {
+            ColumnData::Utf8(_) => DataType::Utf8,
+            ColumnData::UInt64(_) => DataType::UInt64,
+            ColumnData::Float64(_) => DataType::Float64,
+        }
+    }
+
+    /// Convert the values into an Arrow array
+    pub fn to_array(&self) -> ArrayRef {
+        match self {
+            ColumnData::Utf8(values) => Arc::new(StringArray::from_iter_values(values)),
+            ColumnData::UInt64(values) => Arc::new(UInt64Array::from(values.clone())),
+            ColumnData::Float64(values) => Arc::new(Float64Array::from(values.clone())),
+        }
+    }
+}
+
+/// A named, non-nullable column
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column {
+    name: String,
+    data: ColumnData,
+}
+
+impl Column {
+    /// Create a column
+    pub fn new(name: impl Into<String>, data: ColumnData) -> Self {
+        Self { name: name.into(), data }
+    }
+
+    /// Get the column name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the column values
+    pub fn data(&self) -> &ColumnData {
+        &self.data
+    }
+}
+
+/// A named table of equally long columns, mapping one-to-one onto an Arrow record batch
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnarTable {
+    name: String,
+    columns: Vec<Column>,
+}
+
+impl ColumnarTable {
+    /// Create a table, failing if the columns differ in length
+    pub fn new(name: impl Into<String>, columns: Vec<Column>) -> InfrastructureResult<Self> {
+        let name = name.into();
+        if let Some(first) = columns.first()
+            && columns.iter().any(|column| column.data.len() != first.data.len())
+        {
+            return Err(InfrastructureError::Other(format!("Columns of table '{}' differ in length", name)));
+        }
+
+        Ok(Self { name, columns })
+    }
+
+    /// Get the table name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the columns in schema order
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    /// Get a column by name
+    pub fn column(&self, name: &str) -> Option<&Column> {
+        self.columns.iter().find(|column| column.name == name)
+    }
+
+    /// Get the number of rows
+    pub fn num_rows(&self) -> usize {
+        self.columns.first().map_or(0, |column| column.data.len())
+    }
+
+    /// Get the Arrow schema of the table, with every column non-nullable
+    pub fn schema(&self) -> Schema {
+        Schema::new(
+            self.columns.iter()
+                .map(|column| Field::new(column.name.as_str(), column.data.data_type(), false))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// Convert the table into an Arrow record batch
+    pub fn to_record_batch(&self) -> InfrastructureResult<RecordBatch> {
+        let columns = self.columns.iter().map(|column| column.data.to_array()).collect();
+        RecordBatch::try_new(Arc::new(self.schema()), columns).map_err(|e| {
+            InfrastructureError::Other(format!("Error building record batch '{}': {}", self.name, e))
+        })
+    }
+
+    /// Build the `document_vectors` table: one `document_id, term, tfidf` row per non-zero weight
+    pub fn document_vectors(matrix: &DocumentTermMatrix) -> Self {
+        let (mut documents, mut terms, mut weights) = (Vec::new(), Vec::new(), Vec::new());
+        for (row, column, weight) in matrix.triples() {
+            documents.push(matrix.document_ids()[row].value().to_string());
+            terms.push(matrix.terms()[column].clone());
+            weights.push(weight);
+        }
+
+        Self {
+            name: "document_vectors".to_string(),
+            columns: vec![
+                Column::new("document_id", ColumnData::Utf8(documents)),
+                Column::new("term", ColumnData::Utf8(terms)),
+                Column::new("tfidf", ColumnData::Float64(weights)),
+            ],
+        }
+    }
+
+    /// Build the `vocabulary` table: `term_id, term, document_frequency, collection_frequency`, by term ID
+    pub fn vocabulary(vocabulary: &Vocabulary) -> Self {
+        let mut entries: Vec<_> = vocabulary.iter().collect();
+        entries.sort_by_key(|(_, stats)| stats.id());
+
+        Self {
+            name: "vocabulary".to_string(),
+            columns: vec![
+                Column::new("term_id", ColumnData::UInt64(entries.iter().map(|(_, s)| s.id() as u64).collect())),
+                Column::new("term", ColumnData::Utf8(entries.iter().map(|(t, _)| t.text().to_string()).collect())),
+                Column::new(
+                    "document_frequency",
+                    ColumnData::UInt64(entries.iter().map(|(_, s)| s.document_frequency() as u64).collect()),
+                ),
+                Column::new(
+                    "collection_frequency",
+                    ColumnData::UInt64(entries.iter().map(|(_, s)| s.collection_frequency() as u64).collect()),
+                ),
+            ],
+        }
+    }
+}
+
+/// Destination for columnar tables, such as an Arrow IPC or Parquet file writer
+pub trait RecordBatchSink {
+    /// Write one table as a record batch
+    fn write_table(&mut self, table: &ColumnarTable) -> InfrastructureResult<()>;
+}
+
+/// Sink writing each table to `<directory>/<table>.arrow` in the Arrow IPC file format
+#[derive(Debug, Clone)]
+pub struct ArrowIpcSink {
+    directory: PathBuf,
+}
+
+impl ArrowIpcSink {
+    /// Create a sink writing into an existing directory
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self { directory: directory.into() }
+    }
+}
+
+impl RecordBatchSink for ArrowIpcSink {
+    fn write_table(&mut self, table: &ColumnarTable) -> InfrastructureResult<()> {
+        let batch = table.to_record_batch()?;
+        let file = File::create(self.directory.join(format!("{}.arrow", table.name())))?;
+        let arrow_error = |e: arrow_schema::ArrowError| {
+            InfrastructureError::Other(format!("Error writing Arrow table '{}': {}", table.name(), e))
+        };
+
+        let mut writer = FileWriter::try_new(file, &batch.schema()).map_err(arrow_error)?;
+        writer.write(&batch).map_err(arrow_error)?;
+        writer.finish().map_err(arrow_error)
+    }
+}
+
+/// Sink writing each table to `<directory>/<table>.parquet`
+#[derive(Debug, Clone)]
+pub struct ParquetSink {
+    directory: PathBuf,
+}
+
+impl ParquetSink {
+    /// Create a sink writing into an existing directory
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self { directory: directory.into() }
+    }
+}
+
+impl RecordBatchSink for ParquetSink {
+    fn write_table(&mut self, table: &ColumnarTable) -> InfrastructureResult<()> {
+        let batch = table.to_record_batch()?;
+        let file = File::create(self.directory.join(format!("{}.parquet", table.name())))?;
+        let parquet_error = |e: parquet::errors::ParquetError| {
+            InfrastructureError::Other(format!("Error writing Parquet table '{}': {}", table.name(), e))
+        };
+
+        let mut writer = ArrowWriter::try_new(file, batch.schema(), None).map_err(parquet_error)?;
+        writer.write(&batch).map_err(parquet_error)?;
+        writer.close().map_err(parquet_error)?;
+        Ok(())
+    }
+}
+
+/// Write the document vectors and vocabulary (with document frequencies) of a corpus to a sink
+pub fn export_tables(
+    matrix: &DocumentTermMatrix,
+    vocabulary: &Vocabulary,
+    sink: &mut dyn RecordBatchSink,
+) -> InfrastructureResult<()> {
+    sink.write_table(&ColumnarTable::document_vectors(matrix))?;
+    sink.write_table(&ColumnarTable::vocabulary(vocabulary))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use crate::domain::{Document, DocumentId, Term};
+
+    #[derive(Default)]
+    struct CollectingSink(Vec<ColumnarTable>);
+
+    impl RecordBatchSink for CollectingSink {
+        fn write_table(&mut self, table: &ColumnarTable) -> InfrastructureResult<()> {
+            self.0.push(table.clone());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_export_tables() {
+        let mut document = Document::new("doc1", "rust rust go");
+        document.add_terms(["rust", "rust", "go"].map(Term::new));
+        let vocabulary = Vocabulary::from_documents([&document]);
+
+        let id = DocumentId::new("doc1");
+        let vector = HashMap::from([("rust".to_string(), 0.5), ("go".to_string(), 0.25)]);
+        let matrix = DocumentTermMatrix::from_vectors([(&id, &vector)]);
+
+        let mut sink = CollectingSink::default();
+        export_tables(&matrix, &vocabulary, &mut sink).unwrap();
+
+        let [vectors, terms] = &sink.0[..] else { panic!("expected two tables") };
+        assert_eq!(vectors.num_rows(), 2);
+        assert_eq!(vectors.column("tfidf").unwrap().data(), &ColumnData::Float64(vec![0.25, 0.5]));
+        assert_eq!(terms.column("term").unwrap().data(), &ColumnData::Utf8(vec!["go".into(), "rust".into()]));
+        assert_eq!(terms.column("collection_frequency").unwrap().data(), &ColumnData::UInt64(vec![1, 2]));
+
+        let batch = vectors.to_record_batch().unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.schema().field(2).data_type(), &DataType::Float64);
+
+        let uneven = vec![Column::new("a", ColumnData::UInt64(vec![1])), Column::new("b", ColumnData::UInt64(vec![]))];
+        assert!(ColumnarTable::new("uneven", uneven).is_err());
+    }
+
+    #[test]
+    fn test_file_sinks() {
+        let mut document = Document::new("doc1", "rust go");
+        document.add_terms(["rust", "go"].map(Term::new));
+        let vocabulary = Vocabulary::from_documents([&document]);
+        let id = DocumentId::new("doc1");
+        let vector = HashMap::from([("rust".to_string(), 0.5)]);
+        let matrix = DocumentTermMatrix::from_vectors([(&id, &vector)]);
+
+        let directory = std::env::temp_dir().join(format!("tfidf_columnar_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        export_tables(&matrix, &vocabulary, &mut ArrowIpcSink::new(&directory)).unwrap();
+        export_tables(&matrix, &vocabulary, &mut ParquetSink::new(&directory)).unwrap();
+
+        let file = File::open(directory.join("vocabulary.arrow")).unwrap();
+        let batches: Vec<_> = arrow_ipc::reader::FileReader::try_new(file, None).unwrap()
+            .collect::<Result<_, _>>().unwrap();
+        assert_eq!(batches[0].num_rows(), 2);
+
+        let file = File::open(directory.join("document_vectors.parquet")).unwrap();
+        let reader = parquet::arrow::arrow_reader::ParquetRecordBatchReader::try_new(file, 1024).unwrap();
+        let batches: Vec<_> = reader.collect::<Result<_, _>>().unwrap();
+        let weights = batches[0].column(2).as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(weights.values(), &[0.5]);
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,186 @@
+// src/infrastructure/export/csv.rs
+
+use std::io::Write;
+
+use crate::domain::DocumentTermMatrix;
+use crate::infrastructure::InfrastructureResult;
+
+use super::MatrixExporter;
+
+/// How a matrix is laid out in delimited text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatrixLayout {
+    /// One `document,term,tfidf` line per non-zero weight
+    #[default]
+    Triplets,
+
+    /// One line per document with a column per term, under a header naming the terms
+    Dense,
+}
+
+/// Exporter writing a matrix as CSV, TSV or any other single-byte delimited text
+///
+/// Fields containing the delimiter, quotes or line breaks are quoted as in RFC 4180.
+#[derive(Debug, Clone, Copy)]
+pub struct CsvExporter {
+    delimiter: u8,
+    layout: MatrixLayout,
+}
+
+impl Default for CsvExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CsvExporter {
+    /// Create a comma-separated exporter writing triplets
+    pub fn new() -> Self {
+        Self {
+            delimiter: b',',
+            layout: MatrixLayout::default(),
+        }
+    }
+
+    /// Create a tab-separated exporter writing triplets
+    pub fn tsv() -> Self {
+        Self::new().with_delimiter(b'\t')
+    }
+
+    /// Use another field delimiter
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Use another layout
+    pub fn with_layout(mut self, layout: MatrixLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Get the field delimiter
+    pub fn delimiter(&self) -> u8 {
+        self.delimiter
+    }
+
+    /// Get the layout
+    pub fn layout(&self) -> MatrixLayout {
+        self.layout
+    }
+
+    pub(super) fn write_line<'a>(
+        &self,
+        writer: &mut dyn Write,
+        fields: impl IntoIterator<Item = &'a str>,
+    ) -> InfrastructureResult<()> {
+        for (index, field) in fields.into_iter().enumerate() {
+            if index > 0 {
+                writer.write_all(&[self.delimiter])?;
+            }
+            writer.write_all(self.escape(field).as_bytes())?;
+        }
+        writer.write_all(b"\n")?;
+        Ok(())
+    }
+
+    /// Split a line written by `write_line` back into its fields
+    ///
+    /// Quoted fields spanning several lines are not supported.
+    pub(super) fn split_line(&self, line: &str) -> Vec<String> {
+        let delimiter = char::from(self.delimiter);
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        let mut quoted = false;
+        let mut chars = line.trim_end_matches(['\r', '\n']).chars().peekable();
+        while let Some(c) = chars.next() {
+            match (c, quoted) {
+                ('"', true) if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                },
+                ('"', _) => quoted = !quoted,
+                (c, false) if c == delimiter => fields.push(std::mem::take(&mut field)),
+                (c, _) => field.push(c),
+            }
+        }
+        fields.push(field);
+        fields
+    }
+
+    fn escape<'a>(&self, field: &'a str) -> std::borrow::Cow<'a, str> {
+        let needs_quotes = field.bytes().any(|byte| {
+            byte == self.delimiter || byte == b'"' || byte == b'\n' || byte == b'\r'
+        });
+
+        if needs_quotes {
+            format!("\"{}\"", field.replace('"', "\"\"")).into()
+        } else {
+            field.into()
+        }
+    }
+}
+
+impl MatrixExporter for CsvExporter {
+    fn export(&self, matrix: &DocumentTermMatrix, writer: &mut dyn Write) -> InfrastructureResult<()> {
+        match self.layout {
+            MatrixLayout::Triplets => {
+                self.write_line(writer, ["document", "term", "tfidf"])?;
+                for (row, column, weight) in matrix.triples() {
+                    let weight = weight.to_string();
+                    let id = matrix.document_ids()[row].value();
+                    self.write_line(writer, [id, &matrix.terms()[column], &weight])?;
+                }
+            },
+            MatrixLayout::Dense => {
+                let header = std::iter::once("document").chain(matrix.terms().iter().map(String::as_str));
+                self.write_line(writer, header)?;
+                for (row, id) in matrix.document_ids().iter().enumerate() {
+                    let weights: Vec<String> = matrix.dense_row(row).iter().map(f64::to_string).collect();
+                    let line = std::iter::once(id.value()).chain(weights.iter().map(String::as_str));
+                    self.write_line(writer, line)?;
+                }
+            },
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use crate::domain::DocumentId;
+
+    fn matrix() -> DocumentTermMatrix {
+        let doc1 = DocumentId::new("doc1");
+        let doc2 = DocumentId::new("doc,2");
+        let vector1 = HashMap::from([("rust".to_string(), 0.5)]);
+        let vector2 = HashMap::from([("say \"hi\"".to_string(), 0.25)]);
+        DocumentTermMatrix::from_vectors([(&doc1, &vector1), (&doc2, &vector2)])
+    }
+
+    #[test]
+    fn test_triplets() {
+        let mut output = Vec::new();
+        CsvExporter::new().export(&matrix(), &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "document,term,tfidf\n\"doc,2\",\"say \"\"hi\"\"\",0.25\ndoc1,rust,0.5\n"
+        );
+    }
+
+    #[test]
+    fn test_dense_tsv() {
+        let mut output = Vec::new();
+        CsvExporter::tsv().with_layout(MatrixLayout::Dense).export(&matrix(), &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "document\trust\t\"say \"\"hi\"\"\"\ndoc,2\t0\t0.25\ndoc1\t0.5\t0\n"
+        );
+    }
+}
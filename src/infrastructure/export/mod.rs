@@ -0,0 +1,27 @@
+// src/infrastructure/export/mod.rs
+
+//! Exporters writing TF-IDF matrices and vocabularies in formats read by external analysis tools.
+
+mod csv;
+mod npz;
+mod vocabulary;
+#[cfg(feature = "arrow")]
+mod columnar;
+
+pub use csv::{CsvExporter, MatrixLayout};
+pub use npz::NpzExporter;
+pub use vocabulary::{export_vocabulary, import_vocabulary, VocabularyFormat};
+#[cfg(feature = "arrow")]
+pub use columnar::{export_tables, ArrowIpcSink, Column, ColumnData, ColumnarTable, ParquetSink, RecordBatchSink};
+
+use std::io::Write;
+
+use crate::domain::DocumentTermMatrix;
+
+use super::InfrastructureResult;
+
+/// Trait for writing a document-term matrix in some file format
+pub trait MatrixExporter {
+    /// Write the matrix
+    fn export(&self, matrix: &DocumentTermMatrix, writer: &mut dyn Write) -> InfrastructureResult<()>;
+}
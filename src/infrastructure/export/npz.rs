@@ -0,0 +1,187 @@
+// src/infrastructure/export/npz.rs
+
+use std::io::Write;
+
+use crate::domain::DocumentTermMatrix;
+use crate::infrastructure::InfrastructureResult;
+
+use super::MatrixExporter;
+
+/// Exporter writing a matrix as a NumPy `.npz` archive in the layout of `scipy.sparse.save_npz`
+///
+/// `scipy.sparse.load_npz` reads the archive back as a CSR matrix (`data`,
+/// `indices`, `indptr`, `format`, `shape`). The row and column labels are stored
+/// alongside as the fixed-width unicode arrays `document_ids` and `terms`, so
+/// `numpy.load` reads them without pickling.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NpzExporter;
+
+impl NpzExporter {
+    /// Create an exporter
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl MatrixExporter for NpzExporter {
+    fn export(&self, matrix: &DocumentTermMatrix, writer: &mut dyn Write) -> InfrastructureResult<()> {
+        let to_i64 = |values: &[usize]| values.iter().map(|&value| value as i64).collect::<Vec<_>>();
+        let document_ids: Vec<&str> = matrix.document_ids().iter().map(|id| id.value()).collect();
+        let terms: Vec<&str> = matrix.terms().iter().map(String::as_str).collect();
+
+        let entries = [
+            ("data.npy", npy_f64(matrix.values())),
+            ("indices.npy", npy_i64(&to_i64(matrix.column_indices()))),
+            ("indptr.npy", npy_i64(&to_i64(matrix.row_offsets()))),
+            ("format.npy", npy_unicode(&["csr"], "()")),
+            ("shape.npy", npy_i64(&[matrix.rows() as i64, matrix.columns() as i64])),
+            ("document_ids.npy", npy_unicode(&document_ids, &format!("({},)", document_ids.len()))),
+            ("terms.npy", npy_unicode(&terms, &format!("({},)", terms.len()))),
+        ];
+
+        write_stored_zip(&entries, writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Serialize an array in the `.npy` 1.0 format
+fn npy(descr: &str, shape: &str, data: &[u8]) -> Vec<u8> {
+    let mut header = format!("{{'descr': '{}', 'fortran_order': False, 'shape': {}, }}", descr, shape);
+    // The magic, version and length prefix take 10 bytes; data must start 64-byte aligned
+    let padding = (64 - (10 + header.len() + 1) % 64) % 64;
+    header.push_str(&" ".repeat(padding));
+    header.push('\n');
+
+    let mut npy = b"\x93NUMPY\x01\x00".to_vec();
+    npy.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    npy.extend_from_slice(header.as_bytes());
+    npy.extend_from_slice(data);
+    npy
+}
+
+fn npy_f64(values: &[f64]) -> Vec<u8> {
+    let data: Vec<u8> = values.iter().flat_map(|value| value.to_le_bytes()).collect();
+    npy("<f8", &format!("({},)", values.len()), &data)
+}
+
+fn npy_i64(values: &[i64]) -> Vec<u8> {
+    let data: Vec<u8> = values.iter().flat_map(|value| value.to_le_bytes()).collect();
+    npy("<i8", &format!("({},)", values.len()), &data)
+}
+
+/// Serialize strings as a fixed-width UTF-32 (`<U`) array of the given shape
+fn npy_unicode(values: &[&str], shape: &str) -> Vec<u8> {
+    let width = values.iter().map(|value| value.chars().count()).max().unwrap_or(0).max(1);
+
+    let mut data = Vec::with_capacity(values.len() * width * 4);
+    for value in values {
+        let mut count = 0;
+        for c in value.chars() {
+            data.extend_from_slice(&(c as u32).to_le_bytes());
+            count += 1;
+        }
+        data.resize(data.len() + (width - count) * 4, 0);
+    }
+
+    npy(&format!("<U{}", width), shape, &data)
+}
+
+/// Write a zip archive with uncompressed entries
+fn write_stored_zip(entries: &[(&str, Vec<u8>)], writer: &mut dyn Write) -> InfrastructureResult<()> {
+    let mut directory = Vec::new();
+    let mut offset = 0u32;
+
+    for (name, data) in entries {
+        // Version needed, flags, method (stored), DOS time and date
+        let mut fields = vec![20, 0, 0, 0, 0, 0, 0, 0, 0x21, 0];
+        fields.extend_from_slice(&crc32(data).to_le_bytes());
+        fields.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        fields.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        fields.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        fields.extend_from_slice(&[0, 0]);
+
+        writer.write_all(b"PK\x03\x04")?;
+        writer.write_all(&fields)?;
+        writer.write_all(name.as_bytes())?;
+        writer.write_all(data)?;
+
+        directory.extend_from_slice(b"PK\x01\x02");
+        directory.extend_from_slice(&[20, 0]);
+        directory.extend_from_slice(&fields);
+        // Comment length, disk number, internal and external attributes
+        directory.extend_from_slice(&[0; 10]);
+        directory.extend_from_slice(&offset.to_le_bytes());
+        directory.extend_from_slice(name.as_bytes());
+
+        offset += (30 + name.len() + data.len()) as u32;
+    }
+
+    writer.write_all(&directory)?;
+    writer.write_all(b"PK\x05\x06\x00\x00\x00\x00")?;
+    writer.write_all(&(entries.len() as u16).to_le_bytes())?;
+    writer.write_all(&(entries.len() as u16).to_le_bytes())?;
+    writer.write_all(&(directory.len() as u32).to_le_bytes())?;
+    writer.write_all(&offset.to_le_bytes())?;
+    writer.write_all(&[0, 0])?;
+    Ok(())
+}
+
+/// CRC-32 (IEEE) checksum, as zip requires for every entry
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, &byte| {
+        (0..8).fold(crc ^ u32::from(byte), |crc, _| {
+            if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 }
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use crate::domain::DocumentId;
+
+    /// Find an entry of a stored zip by walking its local headers
+    fn entry<'a>(zip: &'a [u8], name: &str) -> Option<&'a [u8]> {
+        let mut offset = 0;
+        while zip.get(offset..offset + 4) == Some(b"PK\x03\x04") {
+            let size = u32::from_le_bytes(zip[offset + 18..offset + 22].try_into().unwrap()) as usize;
+            let name_length = u16::from_le_bytes(zip[offset + 26..offset + 28].try_into().unwrap()) as usize;
+            let start = offset + 30 + name_length;
+            if &zip[offset + 30..start] == name.as_bytes() {
+                return Some(&zip[start..start + size]);
+            }
+            offset = start + size;
+        }
+        None
+    }
+
+    #[test]
+    fn test_export_csr_arrays() {
+        let id = DocumentId::new("doc1");
+        let vector = HashMap::from([("rust".to_string(), 0.5), ("go".to_string(), 0.25)]);
+        let matrix = DocumentTermMatrix::from_vectors([(&id, &vector)]);
+
+        let mut output = Vec::new();
+        NpzExporter::new().export(&matrix, &mut output).unwrap();
+        assert!(output.windows(4).any(|window| window == b"PK\x05\x06"));
+
+        let data = entry(&output, "data.npy").unwrap();
+        assert!(data.starts_with(b"\x93NUMPY\x01\x00"));
+        let header_length = u16::from_le_bytes([data[8], data[9]]) as usize;
+        assert_eq!((10 + header_length) % 64, 0);
+        assert!(std::str::from_utf8(&data[10..10 + header_length]).unwrap().contains("'shape': (2,)"));
+        assert_eq!(&data[10 + header_length..10 + header_length + 8], &0.25f64.to_le_bytes());
+
+        let terms = entry(&output, "terms.npy").unwrap();
+        let header_length = u16::from_le_bytes([terms[8], terms[9]]) as usize;
+        assert!(std::str::from_utf8(&terms[10..10 + header_length]).unwrap().contains("'<U4'"));
+        assert_eq!(&terms[terms.len() - 16..], b"r\0\0\0u\0\0\0s\0\0\0t\0\0\0");
+    }
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+}
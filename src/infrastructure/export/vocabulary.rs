@@ -0,0 +1,121 @@
+// src/infrastructure/export/vocabulary.rs
+
+use std::collections::BTreeSet;
+use std::io::{BufRead, Write};
+
+use serde::Deserialize;
+
+use crate::domain::VocabularyEntry;
+use crate::infrastructure::{InfrastructureError, InfrastructureResult};
+
+use super::CsvExporter;
+
+/// File format of an exported vocabulary
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VocabularyFormat {
+    /// A JSON array of entries with `term`, `document_frequency`, `collection_frequency` and `idf`
+    #[default]
+    Json,
+
+    /// Comma-separated `term,df,cf,idf` lines under a header
+    Csv,
+}
+
+/// Write vocabulary entries in the given format
+pub fn export_vocabulary(
+    entries: &[VocabularyEntry],
+    format: VocabularyFormat,
+    writer: &mut dyn Write,
+) -> InfrastructureResult<()> {
+    match format {
+        VocabularyFormat::Json => serde_json::to_writer(&mut *writer, entries)?,
+        VocabularyFormat::Csv => {
+            let csv = CsvExporter::new();
+            csv.write_line(writer, ["term", "df", "cf", "idf"])?;
+            for entry in entries {
+                let (df, cf, idf) = (
+                    entry.document_frequency().to_string(),
+                    entry.collection_frequency().to_string(),
+                    entry.idf().to_string(),
+                );
+                csv.write_line(writer, [entry.term(), &df, &cf, &idf])?;
+            }
+        },
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// A JSON vocabulary item: a full entry or just the term
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredTerm {
+    Term(String),
+    Entry { term: String },
+}
+
+/// Read the set of terms of a fixed vocabulary
+///
+/// Besides files written by `export_vocabulary`, this accepts a JSON array of
+/// plain strings or a CSV file with just a `term` column, so hand-curated term
+/// lists work too. Statistics in the file are ignored.
+pub fn import_vocabulary(reader: &mut dyn BufRead, format: VocabularyFormat) -> InfrastructureResult<BTreeSet<String>> {
+    match format {
+        VocabularyFormat::Json => {
+            let terms: Vec<StoredTerm> = serde_json::from_reader(reader)?;
+            Ok(terms.into_iter()
+                .map(|term| match term {
+                    StoredTerm::Term(term) | StoredTerm::Entry { term } => term,
+                })
+                .collect())
+        },
+        VocabularyFormat::Csv => {
+            let csv = CsvExporter::new();
+            let mut lines = reader.lines();
+            let header = lines.next()
+                .transpose()?
+                .ok_or_else(|| InfrastructureError::Other("Vocabulary CSV is empty".to_string()))?;
+            let column = csv.split_line(&header)
+                .iter()
+                .position(|name| name == "term")
+                .ok_or_else(|| InfrastructureError::Other("Vocabulary CSV has no 'term' column".to_string()))?;
+
+            let mut terms = BTreeSet::new();
+            for line in lines {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                if let Some(term) = csv.split_line(&line).into_iter().nth(column) {
+                    terms.insert(term);
+                }
+            }
+            Ok(terms)
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vocabulary_round_trip() {
+        let entries = vec![VocabularyEntry::new("rust", 2, 5, 0.4), VocabularyEntry::new("a,b", 1, 1, 1.1)];
+
+        for format in [VocabularyFormat::Json, VocabularyFormat::Csv] {
+            let mut output = Vec::new();
+            export_vocabulary(&entries, format, &mut output).unwrap();
+            let terms = import_vocabulary(&mut output.as_slice(), format).unwrap();
+            assert_eq!(terms, BTreeSet::from(["a,b".to_string(), "rust".to_string()]));
+        }
+
+        // Hand-written term lists need no statistics
+        let terms = import_vocabulary(&mut "[\"rust\", \"cargo\"]".as_bytes(), VocabularyFormat::Json).unwrap();
+        assert_eq!(terms.len(), 2);
+        let terms = import_vocabulary(&mut "term\nrust\n\"say \"\"hi\"\"\"\n".as_bytes(), VocabularyFormat::Csv).unwrap();
+        assert!(terms.contains("say \"hi\""));
+        assert!(import_vocabulary(&mut "word\nrust\n".as_bytes(), VocabularyFormat::Csv).is_err());
+    }
+}
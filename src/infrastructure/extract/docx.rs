@@ -0,0 +1,113 @@
+// src/infrastructure/extract/docx.rs
+
+use std::io::{Cursor, Read};
+
+use zip::ZipArchive;
+use zip::result::ZipError;
+
+use crate::infrastructure::tokenizer::decode_entities;
+use crate::infrastructure::{InfrastructureError, InfrastructureResult};
+
+use super::{TextExtractor, MAX_EXTRACTED_BYTES};
+
+/// Zip entry holding the body of a Word document
+const DOCUMENT_ENTRY: &str = "word/document.xml";
+
+/// Extractor for Word (`.docx`) documents
+///
+/// Reads the main document body, one line per paragraph. Headers, footers,
+/// footnotes and comments live in other parts of the package and are skipped.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DocxExtractor;
+
+impl TextExtractor for DocxExtractor {
+    fn extract(&self, bytes: &[u8]) -> InfrastructureResult<String> {
+        let xml = read_zip_entry(bytes, DOCUMENT_ENTRY)?;
+        let xml = String::from_utf8(xml).map_err(|e| {
+            InfrastructureError::ExtractionError(format!("Invalid UTF-8 in {}: {}", DOCUMENT_ENTRY, e))
+        })?;
+
+        Ok(document_text(&xml))
+    }
+}
+
+fn invalid(reason: impl std::fmt::Display) -> InfrastructureError {
+    InfrastructureError::ExtractionError(format!("Invalid docx archive: {}", reason))
+}
+
+/// Decompress a zip entry, rejecting entries larger than `MAX_EXTRACTED_BYTES`
+fn read_zip_entry(zip: &[u8], name: &str) -> InfrastructureResult<Vec<u8>> {
+    let mut archive = ZipArchive::new(Cursor::new(zip)).map_err(invalid)?;
+    let entry = archive.by_name(name).map_err(|e| match e {
+        ZipError::FileNotFound => invalid(format!("missing {}", name)),
+        e => invalid(e),
+    })?;
+
+    let mut data = Vec::new();
+    entry.take(MAX_EXTRACTED_BYTES as u64 + 1).read_to_end(&mut data).map_err(invalid)?;
+    if data.len() > MAX_EXTRACTED_BYTES {
+        return Err(invalid(format!("{} exceeds {} bytes", name, MAX_EXTRACTED_BYTES)));
+    }
+
+    Ok(data)
+}
+
+/// Collect the text runs of WordprocessingML, breaking lines at paragraphs and breaks
+fn document_text(xml: &str) -> String {
+    let mut text = String::new();
+    let mut in_run_text = false;
+    let mut rest = xml;
+
+    while let Some(start) = rest.find('<') {
+        if in_run_text {
+            text.push_str(&decode_entities(&rest[..start]));
+        }
+        let Some(end) = rest[start..].find('>') else { break };
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        let name = tag.trim_end_matches('/').split_whitespace().next().unwrap_or("");
+        match name {
+            "w:t" => in_run_text = !tag.ends_with('/'),
+            "/w:t" => in_run_text = false,
+            "w:tab" => text.push('\t'),
+            "w:br" | "w:cr" | "/w:p" => text.push('\n'),
+            _ => {},
+        }
+    }
+
+    text.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::ZipWriter;
+    use zip::write::SimpleFileOptions;
+
+    /// Build a zip archive with deflated entries
+    fn zip(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, data) in entries {
+            writer.start_file(*name, SimpleFileOptions::default()).unwrap();
+            writer.write_all(data).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_extract_paragraphs() {
+        let xml = concat!(
+            r#"<?xml version="1.0"?><w:document><w:body>"#,
+            r#"<w:p><w:r><w:t>Rust &amp; </w:t></w:r><w:r><w:t xml:space="preserve">TF-IDF</w:t></w:r></w:p>"#,
+            r#"<w:p><w:r><w:t>Second</w:t><w:tab/><w:t>line</w:t></w:r></w:p>"#,
+            r#"</w:body></w:document>"#,
+        );
+        let docx = zip(&[("[Content_Types].xml", b"<Types/>"), (DOCUMENT_ENTRY, xml.as_bytes())]);
+
+        assert_eq!(DocxExtractor.extract(&docx).unwrap(), "Rust & TF-IDF\nSecond\tline");
+        assert!(DocxExtractor.extract(&zip(&[("other.xml", b"")])).is_err());
+        assert!(DocxExtractor.extract(b"not a zip").is_err());
+    }
+}
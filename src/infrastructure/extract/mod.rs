@@ -0,0 +1,56 @@
+// src/infrastructure/extract/mod.rs
+
+//! Extractors pulling plain text out of document file formats during ingestion.
+
+#[cfg(feature = "pdf")]
+mod pdf;
+#[cfg(feature = "docx")]
+mod docx;
+
+#[cfg(feature = "pdf")]
+pub use pdf::PdfExtractor;
+#[cfg(feature = "docx")]
+pub use docx::DocxExtractor;
+
+use std::path::Path;
+
+use super::{InfrastructureError, InfrastructureResult};
+
+/// Largest amount of decompressed content an extractor reads from one file, guarding against zip bombs
+#[cfg(any(feature = "pdf", feature = "docx"))]
+const MAX_EXTRACTED_BYTES: usize = 64 * 1024 * 1024;
+
+/// Trait for turning the bytes of a document file into plain text
+pub trait TextExtractor: Send + Sync {
+    /// Extract the text of a document
+    fn extract(&self, bytes: &[u8]) -> InfrastructureResult<String>;
+}
+
+/// Extractor for files that already are UTF-8 text
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PlainTextExtractor;
+
+impl TextExtractor for PlainTextExtractor {
+    fn extract(&self, bytes: &[u8]) -> InfrastructureResult<String> {
+        String::from_utf8(bytes.to_vec()).map_err(|e| {
+            InfrastructureError::ExtractionError(format!("Invalid UTF-8 text: {}", e))
+        })
+    }
+}
+
+/// Pick an extractor for a file from its extension, falling back to plain text
+///
+/// PDF and DOCX files are only recognized when their features are enabled.
+pub fn extractor_for_path(path: &Path) -> Box<dyn TextExtractor> {
+    let extension = path.extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase);
+
+    match extension.as_deref() {
+        #[cfg(feature = "pdf")]
+        Some("pdf") => Box::new(PdfExtractor),
+        #[cfg(feature = "docx")]
+        Some("docx") => Box::new(DocxExtractor),
+        _ => Box::new(PlainTextExtractor),
+    }
+}
@@ -0,0 +1,109 @@
+// src/infrastructure/extract/pdf.rs
+
+use lopdf::Document;
+
+use crate::infrastructure::{InfrastructureError, InfrastructureResult};
+
+use super::{TextExtractor, MAX_EXTRACTED_BYTES};
+
+/// Extractor for PDF documents
+///
+/// Reads the text of every page with `lopdf`, one page after another. Pages
+/// whose text cannot be decoded are skipped rather than failing the whole
+/// document; encrypted files fail, and scanned PDFs have no text to extract at all.
+/// Each page may decompress to at most `MAX_EXTRACTED_BYTES` of content.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PdfExtractor;
+
+impl TextExtractor for PdfExtractor {
+    fn extract(&self, bytes: &[u8]) -> InfrastructureResult<String> {
+        if !bytes.starts_with(b"%PDF-") {
+            return Err(InfrastructureError::ExtractionError("Missing PDF header".to_string()));
+        }
+
+        let document = Document::load_mem(bytes).map_err(|e| {
+            InfrastructureError::ExtractionError(format!("Invalid PDF: {}", e))
+        })?;
+        if document.is_encrypted() {
+            return Err(InfrastructureError::ExtractionError("Encrypted PDFs are not supported".to_string()));
+        }
+
+        let mut text = String::new();
+        for page in document.get_pages().into_keys() {
+            let Ok(page_text) = document.extract_text_with_limit(&[page], MAX_EXTRACTED_BYTES) else { continue };
+            if text.len() + page_text.len() > MAX_EXTRACTED_BYTES {
+                return Err(InfrastructureError::ExtractionError(
+                    format!("PDF text exceeds {} bytes", MAX_EXTRACTED_BYTES)
+                ));
+            }
+            text.push_str(&page_text);
+            text.push('\n');
+        }
+
+        let lines: Vec<&str> = text.lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+        Ok(lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lopdf::content::{Content, Operation};
+    use lopdf::{dictionary, Object, Stream};
+
+    /// Build a PDF with one page per text
+    fn pdf(pages: &[&str]) -> Vec<u8> {
+        let mut document = Document::with_version("1.5");
+        let pages_id = document.new_object_id();
+        let font_id = document.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Courier",
+        });
+        let resources_id = document.add_object(dictionary! { "Font" => dictionary! { "F1" => font_id } });
+
+        let kids: Vec<Object> = pages.iter()
+            .map(|text| {
+                let content = Content {
+                    operations: vec![
+                        Operation::new("BT", vec![]),
+                        Operation::new("Tf", vec!["F1".into(), 12.into()]),
+                        Operation::new("Td", vec![72.into(), 712.into()]),
+                        Operation::new("Tj", vec![Object::string_literal(*text)]),
+                        Operation::new("ET", vec![]),
+                    ],
+                };
+                let content_id = document.add_object(Stream::new(dictionary! {}, content.encode().unwrap()));
+                document.add_object(dictionary! {
+                    "Type" => "Page",
+                    "Parent" => pages_id,
+                    "Contents" => content_id,
+                }).into()
+            })
+            .collect();
+        let count = kids.len() as i64;
+        document.objects.insert(pages_id, Object::Dictionary(dictionary! {
+            "Type" => "Pages",
+            "Kids" => kids,
+            "Count" => count,
+            "Resources" => resources_id,
+            "MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()],
+        }));
+        let catalog_id = document.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages_id });
+        document.trailer.set("Root", catalog_id);
+        document.compress();
+
+        let mut bytes = Vec::new();
+        document.save_to(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_extract_pages() {
+        let text = PdfExtractor.extract(&pdf(&["Hello (PDF)", "Compressed page"])).unwrap();
+
+        assert_eq!(text, "Hello (PDF)\nCompressed page");
+        assert!(PdfExtractor.extract(b"plain text").is_err());
+        assert!(PdfExtractor.extract(b"%PDF-1.4\n) > ) >> ]\n%%EOF").is_err());
+    }
+}
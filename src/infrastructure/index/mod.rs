@@ -0,0 +1,9 @@
+// src/infrastructure/index/mod.rs
+
+//! Compressed on-disk inverted index segments.
+
+mod postings;
+mod segment;
+
+pub use postings::{Posting, Postings, BLOCK_SIZE};
+pub use segment::IndexSegment;
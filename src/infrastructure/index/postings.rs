@@ -0,0 +1,327 @@
+// src/infrastructure/index/postings.rs
+
+use crate::infrastructure::{InfrastructureError, InfrastructureResult};
+
+/// Number of postings per block; each block gets one skip entry
+pub const BLOCK_SIZE: usize = 128;
+
+/// A document containing a term, with the term's frequency in it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Posting {
+    /// Number of the document within its segment
+    document: u32,
+
+    /// Occurrences of the term in the document
+    frequency: u32,
+}
+
+impl Posting {
+    /// Create a posting
+    pub fn new(document: u32, frequency: u32) -> Self {
+        Self { document, frequency }
+    }
+
+    /// Get the number of the document within its segment
+    pub fn document(&self) -> u32 {
+        self.document
+    }
+
+    /// Get the occurrences of the term in the document
+    pub fn frequency(&self) -> u32 {
+        self.frequency
+    }
+}
+
+/// Append a LEB128 varint
+pub(super) fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Read a LEB128 varint, advancing `position` past it
+pub(super) fn read_varint(bytes: &[u8], position: &mut usize) -> InfrastructureResult<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = *bytes.get(*position).ok_or_else(|| {
+            InfrastructureError::PersistenceError("Truncated varint".to_string())
+        })?;
+        *position += 1;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(InfrastructureError::PersistenceError("Varint longer than 64 bits".to_string()))
+}
+
+/// Append a postings list sorted by document number
+///
+/// Documents are delta-encoded and split into blocks of `BLOCK_SIZE`. A skip
+/// table up front records the last document and byte length of each block, so
+/// a reader can jump over whole blocks without decoding them.
+pub(super) fn encode_postings(postings: &[Posting], out: &mut Vec<u8>) {
+    let mut blocks = Vec::new();
+    let mut skips = Vec::new();
+    let mut previous = 0;
+    for block in postings.chunks(BLOCK_SIZE) {
+        let start = blocks.len();
+        for posting in block {
+            write_varint(&mut blocks, u64::from(posting.document - previous));
+            write_varint(&mut blocks, u64::from(posting.frequency));
+            previous = posting.document;
+        }
+        skips.push((previous, blocks.len() - start));
+    }
+
+    write_varint(out, skips.len() as u64);
+    let mut last = 0;
+    for (document, length) in skips {
+        write_varint(out, u64::from(document - last));
+        write_varint(out, length as u64);
+        last = document;
+    }
+    out.extend_from_slice(&blocks);
+}
+
+/// One block of an encoded postings list
+#[derive(Debug, Clone, Copy)]
+struct Skip {
+    /// Last document of the previous block, which the first delta is relative to
+    base: u32,
+
+    /// Last document of this block
+    last_document: u32,
+
+    /// Byte range of the block
+    start: usize,
+    end: usize,
+}
+
+/// A cursor over an encoded postings list, in ascending document order
+#[derive(Debug, Clone)]
+pub struct Postings<'a> {
+    bytes: &'a [u8],
+    skips: Vec<Skip>,
+    block: usize,
+    position: usize,
+    previous: u32,
+
+    /// Posting found by `advance_to`, returned again until consumed by `next`
+    peeked: Option<Posting>,
+}
+
+impl<'a> Postings<'a> {
+    /// Read the skip table of an encoded postings list, validating every posting
+    ///
+    /// Fails unless documents are strictly ascending and below `document_count`,
+    /// and every block ends exactly on the document and byte its skip entry records.
+    pub(super) fn decode(bytes: &'a [u8], document_count: usize) -> InfrastructureResult<Self> {
+        Self::parse(bytes, Some(document_count))
+    }
+
+    /// Read the skip table of a postings list `decode` already accepted, without checking its blocks again
+    pub(super) fn read(bytes: &'a [u8]) -> InfrastructureResult<Self> {
+        Self::parse(bytes, None)
+    }
+
+    /// Read the skip table, checking every block against `document_count` if given
+    fn parse(bytes: &'a [u8], document_count: Option<usize>) -> InfrastructureResult<Self> {
+        let mut position = 0;
+        let count = read_varint(bytes, &mut position)? as usize;
+
+        let mut raw = Vec::with_capacity(count.min(bytes.len()));
+        let mut last_document = 0u64;
+        for _ in 0..count {
+            last_document = last_document.checked_add(read_varint(bytes, &mut position)?)
+                .ok_or_else(|| corrupt("document number out of range"))?;
+            raw.push((last_document, read_varint(bytes, &mut position)? as usize));
+        }
+
+        let bytes = &bytes[position..];
+        let mut skips = Vec::with_capacity(raw.len());
+        let (mut start, mut base): (usize, u32) = (0, 0);
+        for (last_document, length) in raw {
+            let last_document = u32::try_from(last_document).map_err(|_| corrupt("document number out of range"))?;
+            let end = start.checked_add(length)
+                .filter(|end| *end <= bytes.len())
+                .ok_or_else(|| corrupt("block past the end of the list"))?;
+            let skip = Skip { base, last_document, start, end };
+            if let Some(document_count) = document_count {
+                validate_block(bytes, &skip, skips.is_empty(), document_count)?;
+            }
+            skips.push(skip);
+            start = end;
+            base = last_document;
+        }
+        if start != bytes.len() {
+            return Err(corrupt("trailing bytes after the last block"));
+        }
+
+        let position = skips.first().map_or(0, |skip| skip.start);
+        Ok(Self { bytes, skips, block: 0, position, previous: 0, peeked: None })
+    }
+
+    /// Create a cursor over an empty list
+    pub fn empty() -> Self {
+        Self { bytes: &[], skips: Vec::new(), block: 0, position: 0, previous: 0, peeked: None }
+    }
+
+    /// Get the number of blocks in the list
+    pub fn block_count(&self) -> usize {
+        self.skips.len()
+    }
+
+    /// Move to the first posting at or after a document, skipping whole blocks where possible
+    ///
+    /// The posting is not consumed: the next `advance_to` or `next` returns it again.
+    pub fn advance_to(&mut self, target: u32) -> Option<Posting> {
+        if let Some(posting) = self.peeked
+            && posting.document >= target
+        {
+            return Some(posting);
+        }
+        self.peeked = None;
+
+        let skipped = self.skips[self.block.min(self.skips.len())..].iter()
+            .take_while(|skip| skip.last_document < target)
+            .count();
+        if skipped > 0 {
+            self.enter_block(self.block + skipped);
+        }
+
+        while let Some(posting) = self.decode_next() {
+            if posting.document >= target {
+                self.peeked = Some(posting);
+                return Some(posting);
+            }
+        }
+        None
+    }
+
+    fn enter_block(&mut self, block: usize) {
+        self.block = block;
+        if let Some(skip) = self.skips.get(block) {
+            self.position = skip.start;
+            self.previous = skip.base;
+        }
+    }
+
+    fn decode_next(&mut self) -> Option<Posting> {
+        let mut skip = *self.skips.get(self.block)?;
+        if self.position >= skip.end {
+            self.enter_block(self.block + 1);
+            skip = *self.skips.get(self.block)?;
+        }
+
+        // Blocks were validated by `decode`, so neither read can fail nor the sum overflow
+        let delta = read_varint(&self.bytes[..skip.end], &mut self.position).ok()?;
+        let frequency = read_varint(&self.bytes[..skip.end], &mut self.position).ok()?;
+        self.previous += delta as u32;
+        Some(Posting::new(self.previous, frequency as u32))
+    }
+}
+
+impl Iterator for Postings<'_> {
+    type Item = Posting;
+
+    fn next(&mut self) -> Option<Posting> {
+        self.peeked.take().or_else(|| self.decode_next())
+    }
+}
+
+/// Check that a block decodes to ascending documents below `document_count`, ending where its skip entry says
+fn validate_block(bytes: &[u8], skip: &Skip, first: bool, document_count: usize) -> InfrastructureResult<()> {
+    if skip.start == skip.end {
+        return Err(corrupt("empty block"));
+    }
+
+    let block = &bytes[..skip.end];
+    let mut position = skip.start;
+    let mut previous = skip.base;
+    let mut first_posting = first;
+    while position < skip.end {
+        let delta = u32::try_from(read_varint(block, &mut position)?).map_err(|_| corrupt("document number out of range"))?;
+        u32::try_from(read_varint(block, &mut position)?).map_err(|_| corrupt("frequency out of range"))?;
+        if delta == 0 && !first_posting {
+            return Err(corrupt("documents are not ascending"));
+        }
+        previous = previous.checked_add(delta).ok_or_else(|| corrupt("document number out of range"))?;
+        if previous as usize >= document_count {
+            return Err(corrupt("document number past the end of the segment"));
+        }
+        first_posting = false;
+    }
+
+    if previous != skip.last_document {
+        return Err(corrupt("block does not end on its skip entry"));
+    }
+    Ok(())
+}
+
+fn corrupt(reason: &str) -> InfrastructureError {
+    InfrastructureError::PersistenceError(format!("Corrupt postings list: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_varint_round_trip() {
+        let mut bytes = Vec::new();
+        for value in [0, 1, 127, 128, 300, u64::from(u32::MAX), u64::MAX] {
+            write_varint(&mut bytes, value);
+        }
+        assert_eq!(bytes[..3], [0, 1, 127]);
+
+        let mut position = 0;
+        for value in [0, 1, 127, 128, 300, u64::from(u32::MAX), u64::MAX] {
+            assert_eq!(read_varint(&bytes, &mut position).unwrap(), value);
+        }
+        assert!(read_varint(&bytes, &mut position).is_err());
+    }
+
+    #[test]
+    fn test_postings_skip_blocks() {
+        let postings: Vec<Posting> = (0..1000).map(|n| Posting::new(n * 3, n % 5 + 1)).collect();
+        let mut bytes = Vec::new();
+        encode_postings(&postings, &mut bytes);
+
+        let decoded = Postings::decode(&bytes, 3000).unwrap();
+        assert_eq!(decoded.block_count(), 8);
+        assert_eq!(decoded.clone().collect::<Vec<_>>(), postings);
+
+        let mut cursor = decoded;
+        assert_eq!(cursor.advance_to(1500), Some(Posting::new(1500, 1)));
+        assert_eq!(cursor.advance_to(1501), Some(Posting::new(1503, 2)));
+        assert_eq!(cursor.advance_to(1502), Some(Posting::new(1503, 2)));
+        assert_eq!(cursor.next(), Some(Posting::new(1503, 2)));
+        assert_eq!(cursor.next(), Some(Posting::new(1506, 3)));
+        assert_eq!(cursor.advance_to(3000), None);
+    }
+
+    #[test]
+    fn test_reject_invalid_postings() {
+        let mut bytes = Vec::new();
+        encode_postings(&[Posting::new(2, 1), Posting::new(5, 1)], &mut bytes);
+        assert!(Postings::decode(&bytes, 6).is_ok());
+        assert!(Postings::decode(&bytes, 5).is_err());
+
+        // A delta overflowing u32
+        let mut overflow = Vec::new();
+        for value in [1, u64::from(u32::MAX), 7, u64::from(u32::MAX), 1, 5, 1] {
+            write_varint(&mut overflow, value);
+        }
+        assert!(Postings::decode(&overflow, usize::MAX).is_err());
+
+        // A block length running past the end
+        let mut truncated = Vec::new();
+        for value in [1, 0, u64::MAX] {
+            write_varint(&mut truncated, value);
+        }
+        assert!(Postings::decode(&truncated, 1).is_err());
+    }
+}
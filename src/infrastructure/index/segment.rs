@@ -0,0 +1,591 @@
+// src/infrastructure/index/segment.rs
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::ops::Range;
+use std::sync::OnceLock;
+
+use crate::domain::{Bm25, Corpus, Document, DocumentId, DomainResult, Term, TermId};
+use crate::infrastructure::persistence::Storage;
+use crate::infrastructure::{InfrastructureError, InfrastructureResult};
+
+use super::postings::{encode_postings, read_varint, write_varint, Posting, Postings};
+
+/// Marks a segment blob
+const MAGIC: &[u8; 4] = b"TFIX";
+
+/// Version of the segment layout
+const VERSION: u8 = 2;
+
+/// Header size: magic, version byte, then the dictionary's length and checksum as little-endian u64s
+const HEADER_LEN: usize = 21;
+
+/// Postings lists being built, keyed by term ID, with the term they belong to
+type PostingsByTerm = HashMap<TermId, (Term, Vec<Posting>)>;
+
+/// A document of a segment
+#[derive(Debug, Clone)]
+struct SegmentDocument {
+    id: DocumentId,
+
+    /// Number of content terms
+    length: usize,
+}
+
+/// A term of the segment dictionary
+#[derive(Debug, Clone)]
+struct TermEntry {
+    text: String,
+    document_frequency: usize,
+
+    /// Byte range of the postings list within the postings section
+    postings: Range<usize>,
+
+    /// Checksum of the encoded postings list
+    checksum: u64,
+
+    /// Set once the postings list passed its checksum and validation, holding
+    /// its bytes if they were read from storage (empty for an in-memory segment)
+    verified: OnceLock<Vec<u8>>,
+}
+
+/// Where the postings lists of a segment are read from
+#[derive(Clone)]
+enum SegmentSource<'s> {
+    /// The whole encoded segment, in memory
+    Bytes(Vec<u8>),
+
+    /// A segment left in storage, whose postings lists are read one at a time on first use
+    Stored { storage: &'s dyn Storage, key: String },
+}
+
+impl fmt::Debug for SegmentSource<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SegmentSource::Bytes(data) => f.debug_tuple("Bytes").field(&data.len()).finish(),
+            SegmentSource::Stored { key, .. } => f.debug_struct("Stored").field("key", key).finish(),
+        }
+    }
+}
+
+/// An immutable, compressed inverted index over a set of documents
+///
+/// Documents are numbered in ID order and each term maps to a postings list of
+/// (document, frequency) pairs, delta- and varint-encoded in blocks with skip
+/// pointers. The document table and term dictionary come first, under their
+/// own checksum, and each postings list carries its own checksum in the
+/// dictionary. Opening a segment reads and verifies only the header and
+/// dictionary; a postings list is verified when a query first reads it, and a
+/// segment opened from storage loads just that list. Segments are written once
+/// and combined with `merge` rather than updated.
+#[derive(Debug, Clone)]
+pub struct IndexSegment<'s> {
+    /// The encoded segment, or the storage it is read from
+    source: SegmentSource<'s>,
+
+    /// Documents, indexed by their number
+    documents: Vec<SegmentDocument>,
+
+    /// Terms sorted by text
+    terms: Vec<TermEntry>,
+
+    /// Offset of the postings section within the encoded segment
+    postings_start: usize,
+
+    /// Number of content terms across all documents
+    token_count: usize,
+}
+
+impl<'s> IndexSegment<'s> {
+    /// Index the content terms of every document in a corpus
+    ///
+    /// Fails if a document held by ID cannot be looked up, rather than leaving
+    /// it out; documents that no longer exist are left out.
+    pub fn from_corpus(corpus: &Corpus) -> InfrastructureResult<IndexSegment<'static>> {
+        let mut documents: Vec<&Document> = corpus.documents()
+            .collect::<DomainResult<_>>()
+            .map_err(|e| InfrastructureError::Other(e.to_string()))?;
+        documents.sort_by(|a, b| a.id().value().cmp(b.id().value()));
+
+        let mut postings = PostingsByTerm::new();
+        for (number, document) in documents.iter().enumerate() {
+            for term in document.content_terms() {
+                let frequency = document.term_frequency(term).value() as u32;
+                postings.entry(term.id())
+                    .or_insert_with(|| (term.clone(), Vec::new()))
+                    .1.push(Posting::new(number as u32, frequency));
+            }
+        }
+
+        let documents = documents.iter()
+            .map(|document| SegmentDocument { id: document.id().clone(), length: document.term_count() })
+            .collect();
+        Ok(IndexSegment::encode(documents, postings))
+    }
+
+    /// Merge segments into one, dropping documents superseded by a later segment
+    ///
+    /// When several segments hold a document with the same ID, the copy in the
+    /// last of them is kept, so newer segments can be passed after older ones.
+    pub fn merge(segments: &[&IndexSegment<'_>]) -> InfrastructureResult<IndexSegment<'static>> {
+        let mut owners: BTreeMap<&str, (usize, u32)> = BTreeMap::new();
+        for (index, segment) in segments.iter().enumerate() {
+            for (number, document) in segment.documents.iter().enumerate() {
+                owners.insert(document.id.value(), (index, number as u32));
+            }
+        }
+
+        // Renumber the surviving documents in ID order
+        let mut renumbered: Vec<HashMap<u32, u32>> = vec![HashMap::new(); segments.len()];
+        let mut documents = Vec::with_capacity(owners.len());
+        for (number, (index, local)) in owners.values().enumerate() {
+            renumbered[*index].insert(*local, number as u32);
+            documents.push(segments[*index].documents[*local as usize].clone());
+        }
+
+        let mut postings = PostingsByTerm::new();
+        for (segment, renumbered) in segments.iter().zip(&renumbered) {
+            for (index, entry) in segment.terms.iter().enumerate() {
+                let term = Term::try_new(&entry.text).map_err(|e| InfrastructureError::Other(e.to_string()))?;
+                let (_, merged) = postings.entry(term.id()).or_insert_with(|| (term, Vec::new()));
+                for posting in segment.decode_postings(index)? {
+                    if let Some(number) = renumbered.get(&posting.document()) {
+                        merged.push(Posting::new(*number, posting.frequency()));
+                    }
+                }
+            }
+        }
+        postings.retain(|_, (_, list)| !list.is_empty());
+        for (_, list) in postings.values_mut() {
+            list.sort_by_key(Posting::document);
+        }
+
+        Ok(IndexSegment::encode(documents, postings))
+    }
+
+    fn encode(documents: Vec<SegmentDocument>, postings: PostingsByTerm) -> IndexSegment<'static> {
+        let mut postings: Vec<(Term, Vec<Posting>)> = postings.into_values().collect();
+        postings.sort_by(|a, b| a.0.text().cmp(b.0.text()));
+        IndexSegment::from_bytes(encode_bytes(&documents, &postings)).expect("freshly encoded segment is valid")
+    }
+
+    /// Open an encoded segment, verifying its header and dictionary
+    ///
+    /// Postings lists are verified as queries first read them.
+    pub fn from_bytes(data: Vec<u8>) -> InfrastructureResult<IndexSegment<'static>> {
+        let dictionary_len = read_header(&data)?;
+        let dictionary = data.get(HEADER_LEN..HEADER_LEN + dictionary_len).ok_or_else(|| corrupt("truncated dictionary"))?;
+        let (documents, terms, token_count) = read_dictionary(&data[..HEADER_LEN], dictionary)?;
+
+        let postings_len = terms.last().map_or(0, |entry| entry.postings.end);
+        if data.len() - HEADER_LEN - dictionary_len != postings_len {
+            return Err(corrupt("postings section does not match the dictionary"));
+        }
+        Ok(IndexSegment { source: SegmentSource::Bytes(data), documents, terms, postings_start: HEADER_LEN + dictionary_len, token_count })
+    }
+
+    /// Open a segment stored under a key, reading only its header and dictionary
+    ///
+    /// The segment keeps the storage to read each postings list from when a
+    /// query first needs it.
+    pub fn open(storage: &'s dyn Storage, key: &str) -> InfrastructureResult<Option<Self>> {
+        let Some(header) = storage.load_range(key, 0..HEADER_LEN)? else {
+            return Ok(None);
+        };
+        let dictionary_len = read_header(&header)?;
+        let dictionary = storage.load_range(key, HEADER_LEN..HEADER_LEN + dictionary_len)?
+            .filter(|dictionary| dictionary.len() == dictionary_len)
+            .ok_or_else(|| corrupt("truncated dictionary"))?;
+        let (documents, terms, token_count) = read_dictionary(&header, &dictionary)?;
+
+        let source = SegmentSource::Stored { storage, key: key.to_string() };
+        Ok(Some(Self { source, documents, terms, postings_start: HEADER_LEN + dictionary_len, token_count }))
+    }
+
+    /// Get the encoded segment, or None for a segment opened from storage
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match &self.source {
+            SegmentSource::Bytes(data) => Some(data),
+            SegmentSource::Stored { .. } => None,
+        }
+    }
+
+    /// Store the segment under a key, copying it from its storage if it was opened from one
+    pub fn save(&self, storage: &(impl Storage + ?Sized), key: &str) -> InfrastructureResult<()> {
+        match &self.source {
+            SegmentSource::Bytes(data) => storage.save(key, data),
+            SegmentSource::Stored { storage: source, key: source_key } => {
+                let data = source.load(source_key)?.ok_or_else(|| corrupt("segment is no longer in storage"))?;
+                storage.save(key, &data)
+            },
+        }
+    }
+
+    /// Get the number of documents
+    pub fn document_count(&self) -> usize {
+        self.documents.len()
+    }
+
+    /// Get the number of distinct terms
+    pub fn term_count(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// Get the ID of a document by its number
+    pub fn document_id(&self, number: u32) -> Option<&DocumentId> {
+        self.documents.get(number as usize).map(|document| &document.id)
+    }
+
+    /// Get the number of content terms of a document by its number
+    pub fn document_length(&self, number: u32) -> Option<usize> {
+        self.documents.get(number as usize).map(|document| document.length)
+    }
+
+    /// Get the average number of content terms per document
+    pub fn average_document_length(&self) -> f64 {
+        if self.documents.is_empty() {
+            return 0.0;
+        }
+
+        self.token_count as f64 / self.documents.len() as f64
+    }
+
+    /// Get the number of documents containing a term
+    pub fn document_frequency(&self, term: &Term) -> usize {
+        self.entry(term.text()).map_or(0, |index| self.terms[index].document_frequency)
+    }
+
+    /// Get a cursor over the documents containing a term, empty if no document does
+    pub fn postings(&self, term: &Term) -> InfrastructureResult<Postings<'_>> {
+        match self.entry(term.text()) {
+            Some(index) => self.decode_postings(index),
+            None => Ok(Postings::empty()),
+        }
+    }
+
+    /// Get the documents containing every term, in ID order
+    ///
+    /// Lists are intersected rarest first, skipping blocks of the longer lists.
+    pub fn matching_documents(&self, terms: &[Term]) -> InfrastructureResult<Vec<DocumentId>> {
+        let mut entries = Vec::with_capacity(terms.len());
+        for term in terms {
+            match self.entry(term.text()) {
+                Some(index) => entries.push(index),
+                None => return Ok(Vec::new()),
+            }
+        }
+        entries.sort_by_key(|index| self.terms[*index].document_frequency);
+
+        let mut cursors = entries.into_iter().map(|index| self.decode_postings(index)).collect::<InfrastructureResult<Vec<_>>>()?;
+        let Some((lead, rest)) = cursors.split_first_mut() else {
+            return Ok(Vec::new());
+        };
+
+        let mut matches = Vec::new();
+        let mut candidate = lead.next();
+        while let Some(posting) = candidate {
+            let mut target = posting.document();
+            let mut agreed = true;
+            for cursor in rest.iter_mut() {
+                match cursor.advance_to(target) {
+                    Some(found) if found.document() == target => {},
+                    Some(found) => {
+                        target = found.document();
+                        agreed = false;
+                        break;
+                    },
+                    None => return Ok(matches),
+                }
+            }
+
+            candidate = if agreed {
+                matches.push(self.documents[target as usize].id.clone());
+                lead.next()
+            } else {
+                lead.advance_to(target).and_then(|_| lead.next())
+            };
+        }
+        Ok(matches)
+    }
+
+    /// Score the documents containing any of the terms with BM25, best first
+    pub fn search(&self, terms: &[Term], bm25: &Bm25) -> InfrastructureResult<Vec<(DocumentId, f64)>> {
+        let average_length = self.average_document_length();
+        let mut scores: HashMap<u32, f64> = HashMap::new();
+        let mut seen = Vec::new();
+        for term in terms {
+            let Some(index) = self.entry(term.text()) else {
+                continue;
+            };
+            if seen.contains(&index) {
+                continue;
+            }
+            seen.push(index);
+
+            let idf = bm25.idf_from(self.terms[index].document_frequency, self.documents.len());
+            for posting in self.decode_postings(index)? {
+                let length = self.documents[posting.document() as usize].length;
+                *scores.entry(posting.document()).or_insert(0.0) += idf * bm25.tf(posting.frequency() as usize, length, average_length);
+            }
+        }
+
+        let mut results: Vec<(DocumentId, f64)> = scores.into_iter()
+            .map(|(number, score)| (self.documents[number as usize].id.clone(), score))
+            .collect();
+        results.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.value().cmp(b.0.value())));
+        Ok(results)
+    }
+
+    /// Find a term's position in the dictionary
+    fn entry(&self, text: &str) -> Option<usize> {
+        self.terms.binary_search_by(|entry| entry.text.as_str().cmp(text)).ok()
+    }
+
+    /// Get a cursor over a term's postings, reading and verifying the list on first use
+    fn decode_postings(&self, index: usize) -> InfrastructureResult<Postings<'_>> {
+        Postings::read(self.list_bytes(index)?)
+    }
+
+    /// Get the encoded postings list of a term, checking it against its checksum and the document table once
+    fn list_bytes(&self, index: usize) -> InfrastructureResult<&[u8]> {
+        let entry = &self.terms[index];
+        let range = self.postings_start + entry.postings.start..self.postings_start + entry.postings.end;
+        match &self.source {
+            SegmentSource::Bytes(data) => {
+                let bytes = &data[range];
+                if entry.verified.get().is_none() {
+                    self.verify_list(entry, bytes)?;
+                    let _ = entry.verified.set(Vec::new());
+                }
+                Ok(bytes)
+            },
+            SegmentSource::Stored { storage, key } => {
+                if let Some(bytes) = entry.verified.get() {
+                    return Ok(bytes);
+                }
+                let length = range.len();
+                let bytes = storage.load_range(key, range)?
+                    .filter(|bytes| bytes.len() == length)
+                    .ok_or_else(|| corrupt("truncated postings section"))?;
+                self.verify_list(entry, &bytes)?;
+                Ok(entry.verified.get_or_init(|| bytes))
+            },
+        }
+    }
+
+    fn verify_list(&self, entry: &TermEntry, bytes: &[u8]) -> InfrastructureResult<()> {
+        if checksum(bytes) != entry.checksum {
+            return Err(corrupt(&format!("checksum mismatch in the postings of '{}'", entry.text)));
+        }
+        if Postings::decode(bytes, self.documents.len())?.count() != entry.document_frequency {
+            return Err(corrupt(&format!("document frequency of '{}' does not match its postings", entry.text)));
+        }
+        Ok(())
+    }
+}
+
+/// Check a segment's magic and version, returning the length of its dictionary
+fn read_header(header: &[u8]) -> InfrastructureResult<usize> {
+    if header.len() < HEADER_LEN || &header[..4] != MAGIC {
+        return Err(corrupt("not an index segment"));
+    }
+    if header[4] != VERSION {
+        return Err(corrupt(&format!("unsupported version {}", header[4])));
+    }
+    usize::try_from(u64::from_le_bytes(header[5..13].try_into().expect("eight length bytes")))
+        .ok()
+        .filter(|length| *length <= usize::MAX - HEADER_LEN)
+        .ok_or_else(|| corrupt("dictionary length out of range"))
+}
+
+/// Verify a dictionary against the checksum in the header and read its documents and terms
+fn read_dictionary(header: &[u8], data: &[u8]) -> InfrastructureResult<(Vec<SegmentDocument>, Vec<TermEntry>, usize)> {
+    let sum = u64::from_le_bytes(header[13..HEADER_LEN].try_into().expect("eight checksum bytes"));
+    if checksum(data) != sum {
+        return Err(corrupt("dictionary checksum mismatch"));
+    }
+
+    let mut position = 0;
+    let document_count = read_varint(data, &mut position)? as usize;
+    let mut documents = Vec::with_capacity(document_count.min(data.len()));
+    let mut token_count: usize = 0;
+    for _ in 0..document_count {
+        let id = read_text(data, &mut position)?;
+        let length = read_varint(data, &mut position)? as usize;
+        token_count = token_count.checked_add(length).ok_or_else(|| corrupt("token count out of range"))?;
+        documents.push(SegmentDocument { id: DocumentId::new(id), length });
+    }
+    if documents.len() > u32::MAX as usize {
+        return Err(corrupt("too many documents"));
+    }
+
+    let term_count = read_varint(data, &mut position)? as usize;
+    let mut terms = Vec::with_capacity(term_count.min(data.len()));
+    let mut offset: usize = 0;
+    for _ in 0..term_count {
+        let text = read_text(data, &mut position)?;
+        let document_frequency = read_varint(data, &mut position)? as usize;
+        let length = read_varint(data, &mut position)? as usize;
+        let checksum = data.get(position..position + 8).ok_or_else(|| corrupt("truncated postings checksum"))?;
+        let checksum = u64::from_le_bytes(checksum.try_into().expect("eight checksum bytes"));
+        position += 8;
+        let end = offset.checked_add(length).ok_or_else(|| corrupt("postings length out of range"))?;
+        terms.push(TermEntry { text, document_frequency, postings: offset..end, checksum, verified: OnceLock::new() });
+        offset = end;
+    }
+
+    if position != data.len() {
+        return Err(corrupt("trailing bytes after the dictionary"));
+    }
+    if terms.windows(2).any(|pair| pair[0].text >= pair[1].text) {
+        return Err(corrupt("dictionary is not sorted"));
+    }
+    Ok((documents, terms, token_count))
+}
+
+/// Encode a document table and postings lists, sorted by term text and then document number, as a segment blob
+fn encode_bytes(documents: &[SegmentDocument], postings: &[(Term, Vec<Posting>)]) -> Vec<u8> {
+    let mut dictionary = Vec::new();
+    write_varint(&mut dictionary, documents.len() as u64);
+    for document in documents {
+        write_varint(&mut dictionary, document.id.value().len() as u64);
+        dictionary.extend_from_slice(document.id.value().as_bytes());
+        write_varint(&mut dictionary, document.length as u64);
+    }
+
+    let mut lists = Vec::new();
+    write_varint(&mut dictionary, postings.len() as u64);
+    for (term, list) in postings {
+        let start = lists.len();
+        encode_postings(list, &mut lists);
+        write_varint(&mut dictionary, term.text().len() as u64);
+        dictionary.extend_from_slice(term.text().as_bytes());
+        write_varint(&mut dictionary, list.len() as u64);
+        write_varint(&mut dictionary, (lists.len() - start) as u64);
+        dictionary.extend_from_slice(&checksum(&lists[start..]).to_le_bytes());
+    }
+
+    let mut data = Vec::with_capacity(HEADER_LEN + dictionary.len() + lists.len());
+    data.extend_from_slice(MAGIC);
+    data.push(VERSION);
+    data.extend_from_slice(&(dictionary.len() as u64).to_le_bytes());
+    data.extend_from_slice(&checksum(&dictionary).to_le_bytes());
+    data.extend_from_slice(&dictionary);
+    data.extend_from_slice(&lists);
+    data
+}
+
+/// Read a varint length followed by that many bytes of UTF-8
+fn read_text(data: &[u8], position: &mut usize) -> InfrastructureResult<String> {
+    let length = read_varint(data, position)? as usize;
+    let bytes = data.get(*position..position.saturating_add(length)).ok_or_else(|| corrupt("truncated text"))?;
+    *position += length;
+    String::from_utf8(bytes.to_vec()).map_err(|_| corrupt("text is not UTF-8"))
+}
+
+fn checksum(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn corrupt(reason: &str) -> InfrastructureError {
+    InfrastructureError::PersistenceError(format!("Corrupt index segment: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Document;
+    use crate::infrastructure::persistence::InMemoryStorage;
+
+    fn corpus(id: &str, texts: &[(&str, &str)]) -> Corpus {
+        let mut corpus = Corpus::new(id, id);
+        for (doc_id, text) in texts {
+            let mut document = Document::new(*doc_id, *text);
+            document.add_terms(text.split_whitespace().map(Term::new));
+            corpus.add_document(document).unwrap();
+        }
+        corpus
+    }
+
+    #[test]
+    fn test_query_stored_segment() {
+        let corpus = corpus("corpus1", &[
+            ("doc1", "rust is fast and rust is safe"),
+            ("doc2", "go is simple"),
+            ("doc3", "rust and go"),
+        ]);
+        let storage = InMemoryStorage::new();
+        IndexSegment::from_corpus(&corpus).unwrap().save(&storage, "segments/0").unwrap();
+
+        let segment = IndexSegment::open(&storage, "segments/0").unwrap().unwrap();
+        assert_eq!(segment.document_count(), 3);
+        assert_eq!(segment.document_frequency(&Term::new("rust")), 2);
+        assert_eq!(segment.document_frequency(&Term::new("java")), 0);
+        assert_eq!(
+            segment.matching_documents(&[Term::new("rust"), Term::new("go")]).unwrap(),
+            vec![DocumentId::new("doc3")]
+        );
+
+        let results = segment.search(&[Term::new("rust")], &Bm25::default()).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, DocumentId::new("doc1"));
+
+        // A corrupt dictionary fails to open; a corrupt postings list only fails the queries reading it
+        let bytes = IndexSegment::from_corpus(&corpus).unwrap().as_bytes().unwrap().to_vec();
+        let mut corrupted = bytes.clone();
+        corrupted[HEADER_LEN] ^= 1;
+        assert!(IndexSegment::from_bytes(corrupted).is_err());
+        let mut corrupted = bytes;
+        *corrupted.last_mut().unwrap() ^= 1;
+        storage.save("segments/2", &corrupted).unwrap();
+        let segment = IndexSegment::open(&storage, "segments/2").unwrap().unwrap();
+        assert_eq!(segment.document_frequency(&Term::new("rust")), 2);
+        let last = segment.terms.last().unwrap().text.clone();
+        assert!(segment.postings(&Term::new(&last)).is_err());
+        assert_eq!(segment.matching_documents(&[Term::new("go")]).unwrap().len(), 2);
+
+        // A posting pointing past the document table fails to read, even with a valid checksum
+        let documents = &segment.documents[..2];
+        let postings = [(Term::new("rust"), vec![Posting::new(0, 2), Posting::new(2, 1)])];
+        let forged = IndexSegment::from_bytes(encode_bytes(documents, &postings)).unwrap();
+        assert!(forged.matching_documents(&[Term::new("rust")]).is_err());
+        assert!(IndexSegment::open(&storage, "segments/1").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_merge_segments() {
+        let old = IndexSegment::from_corpus(&corpus("old", &[("doc1", "rust code"), ("doc2", "go code")])).unwrap();
+        let new = IndexSegment::from_corpus(&corpus("new", &[("doc2", "java code"), ("doc3", "rust tools")])).unwrap();
+
+        let merged = IndexSegment::merge(&[&old, &new]).unwrap();
+
+        assert_eq!(merged.document_count(), 3);
+        assert_eq!(merged.document_frequency(&Term::new("code")), 2);
+        assert_eq!(merged.document_frequency(&Term::new("go")), 0);
+        assert_eq!(
+            merged.matching_documents(&[Term::new("java")]).unwrap(),
+            vec![DocumentId::new("doc2")]
+        );
+        assert_eq!(
+            merged.matching_documents(&[Term::new("rust")]).unwrap(),
+            vec![DocumentId::new("doc1"), DocumentId::new("doc3")]
+        );
+    }
+
+    #[test]
+    fn test_intersect_across_blocks() {
+        let texts: Vec<(String, String)> = (0..1000)
+            .map(|n| (format!("doc{:04}", n), if n % 300 == 0 { "common rare".to_string() } else { "common".to_string() }))
+            .collect();
+        let texts: Vec<(&str, &str)> = texts.iter().map(|(id, text)| (id.as_str(), text.as_str())).collect();
+        let segment = IndexSegment::from_corpus(&corpus("large", &texts)).unwrap();
+
+        assert!(segment.postings(&Term::new("common")).unwrap().block_count() > 1);
+        assert_eq!(
+            segment.matching_documents(&[Term::new("common"), Term::new("rare")]).unwrap(),
+            ["doc0000", "doc0300", "doc0600", "doc0900"].map(DocumentId::new)
+        );
+    }
+}
@@ -0,0 +1,185 @@
+// src/infrastructure/metrics.rs
+
+//! Counters and histograms describing the engine's workload.
+//!
+//! Services record into a `Metrics` implementation set with `set_metrics`;
+//! the default `NoopMetrics` discards everything. `InMemoryMetrics` keeps
+//! running aggregates and hands them out as a serializable snapshot for
+//! exporters to translate.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use serde::{Serialize, Deserialize};
+
+/// Counter of corpus index builds
+pub const INDEX_BUILDS: &str = "index.builds";
+
+/// Histogram of corpus index build durations, in seconds
+pub const INDEX_BUILD_SECONDS: &str = "index.build.seconds";
+
+/// Counter of search requests
+pub const SEARCHES: &str = "search.requests";
+
+/// Histogram of search durations, in seconds
+pub const SEARCH_SECONDS: &str = "search.seconds";
+
+/// Histogram of the number of hits per search
+pub const SEARCH_HITS: &str = "search.hits";
+
+/// Counter of analyzed documents
+pub const DOCUMENTS_ANALYZED: &str = "documents.analyzed";
+
+/// Histogram of document tokenization and analysis durations, in seconds
+pub const TOKENIZE_SECONDS: &str = "tokenize.seconds";
+
+/// Prefix of repository operation latency histograms, e.g. `repository.corpus.find.seconds`
+pub const REPOSITORY_PREFIX: &str = "repository";
+
+/// Receives metric measurements
+pub trait Metrics: Send + Sync {
+    /// Add to a counter
+    fn increment_counter(&self, name: &str, value: u64);
+
+    /// Record one observation of a histogram
+    fn record_histogram(&self, name: &str, value: f64);
+
+    /// Record the seconds elapsed since `started` in a histogram
+    fn record_duration(&self, name: &str, started: Instant) {
+        self.record_histogram(name, started.elapsed().as_secs_f64());
+    }
+}
+
+/// Discards all measurements
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopMetrics;
+
+impl Metrics for NoopMetrics {
+    fn increment_counter(&self, _name: &str, _value: u64) {}
+
+    fn record_histogram(&self, _name: &str, _value: f64) {}
+}
+
+/// Aggregated observations of one histogram
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HistogramSnapshot {
+    pub count: u64,
+    pub sum: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl HistogramSnapshot {
+    fn new(value: f64) -> Self {
+        Self { count: 1, sum: value, min: value, max: value }
+    }
+
+    fn observe(&mut self, value: f64) {
+        self.count += 1;
+        self.sum += value;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+    }
+
+    /// Get the mean observation
+    pub fn mean(&self) -> f64 {
+        if self.count == 0 { 0.0 } else { self.sum / self.count as f64 }
+    }
+}
+
+/// Point-in-time copy of all metrics, ordered by name
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MetricsSnapshot {
+    pub counters: BTreeMap<String, u64>,
+    pub histograms: BTreeMap<String, HistogramSnapshot>,
+}
+
+impl MetricsSnapshot {
+    /// Get a counter's value, zero if never incremented
+    pub fn counter(&self, name: &str) -> u64 {
+        self.counters.get(name).copied().unwrap_or(0)
+    }
+
+    /// Get a histogram's aggregates, if anything was recorded
+    pub fn histogram(&self, name: &str) -> Option<&HistogramSnapshot> {
+        self.histograms.get(name)
+    }
+}
+
+/// Keeps counters and histogram aggregates in memory
+#[derive(Debug, Default)]
+pub struct InMemoryMetrics {
+    counters: Mutex<HashMap<String, u64>>,
+    histograms: Mutex<HashMap<String, HistogramSnapshot>>,
+}
+
+impl InMemoryMetrics {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copy the current values
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        let counters = self.counters.lock().map(|counters| counters.clone()).unwrap_or_default();
+        let histograms = self.histograms.lock().map(|histograms| histograms.clone()).unwrap_or_default();
+
+        MetricsSnapshot {
+            counters: counters.into_iter().collect(),
+            histograms: histograms.into_iter().collect(),
+        }
+    }
+
+    /// Clear all values, e.g. after exporting them as deltas
+    pub fn reset(&self) {
+        if let Ok(mut counters) = self.counters.lock() {
+            counters.clear();
+        }
+        if let Ok(mut histograms) = self.histograms.lock() {
+            histograms.clear();
+        }
+    }
+}
+
+impl Metrics for InMemoryMetrics {
+    fn increment_counter(&self, name: &str, value: u64) {
+        if let Ok(mut counters) = self.counters.lock() {
+            *counters.entry(name.to_string()).or_insert(0) += value;
+        }
+    }
+
+    fn record_histogram(&self, name: &str, value: f64) {
+        if let Ok(mut histograms) = self.histograms.lock() {
+            histograms.entry(name.to_string())
+                .and_modify(|histogram| histogram.observe(value))
+                .or_insert_with(|| HistogramSnapshot::new(value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot() {
+        let metrics = InMemoryMetrics::new();
+        metrics.increment_counter(SEARCHES, 1);
+        metrics.increment_counter(SEARCHES, 2);
+        metrics.record_histogram(SEARCH_HITS, 4.0);
+        metrics.record_histogram(SEARCH_HITS, 2.0);
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.counter(SEARCHES), 3);
+        assert_eq!(snapshot.counter(INDEX_BUILDS), 0);
+        let hits = snapshot.histogram(SEARCH_HITS).unwrap();
+        assert_eq!((hits.count, hits.min, hits.max, hits.mean()), (2, 2.0, 4.0, 3.0));
+
+        let json = serde_json::to_value(&snapshot).unwrap();
+        assert_eq!(json["counters"][SEARCHES], 3);
+
+        metrics.reset();
+        assert_eq!(metrics.snapshot(), MetricsSnapshot::default());
+    }
+}
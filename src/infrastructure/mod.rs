@@ -7,7 +7,12 @@
 
 pub mod repository;
 pub mod persistence;
+pub mod index;
 pub mod tokenizer;
+pub mod extract;
+pub mod export;
+pub mod telemetry;
+pub mod metrics;
 
 /// Common error type for infrastructure operations
 #[derive(Debug, thiserror::Error)]
@@ -27,6 +32,9 @@ pub enum InfrastructureError {
     #[error("Tokenization error: {0}")]
     TokenizationError(String),
     
+    #[error("Text extraction error: {0}")]
+    ExtractionError(String),
+    
     #[error("Other infrastructure error: {0}")]
     Other(String),
 }
@@ -0,0 +1,147 @@
+// src/infrastructure/persistence/compressed.rs
+
+use std::io::{Read, Write};
+use std::time::Duration;
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+use crate::infrastructure::{InfrastructureError, InfrastructureResult};
+
+use super::Storage;
+
+/// Leading bytes of a gzip stream; blobs without them are loaded as stored
+const GZIP_MAGIC: &[u8; 2] = &[0x1f, 0x8b];
+
+/// Storage decorator that compresses blobs on `save` and decompresses them on `load`
+///
+/// Blobs are written as gzip streams, which do well on the repeated terms and
+/// field names of serialized corpora. Blobs written without compression are
+/// still loaded as-is, so compression can be enabled on existing storage.
+pub struct CompressedStorage<S: Storage> {
+    inner: S,
+
+    /// 0 stores blobs without compressing them; 1-9 trade speed for compression ratio
+    level: u32
+}
+
+impl<S: Storage> CompressedStorage<S> {
+    /// Default compression level
+    pub const DEFAULT_LEVEL: u32 = 6;
+
+    /// Wrap a storage with the default compression level
+    pub fn new(inner: S) -> Self {
+        Self::with_level(inner, Self::DEFAULT_LEVEL)
+    }
+
+    /// Wrap a storage with a compression level from 0 (none) to 9 (smallest)
+    pub fn with_level(inner: S, level: u32) -> Self {
+        Self {
+            inner,
+            level: level.min(9)
+        }
+    }
+
+    /// Get the compression level
+    pub fn level(&self) -> u32 {
+        self.level
+    }
+
+    /// Get the wrapped storage
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+}
+
+impl<S: Storage> Storage for CompressedStorage<S> {
+    fn save(&self, key: &str, data: &[u8]) -> InfrastructureResult<()> {
+        self.inner.save(key, &compress(data, self.level)?)
+    }
+
+    fn save_with_ttl(&self, key: &str, data: &[u8], ttl: Duration) -> InfrastructureResult<()> {
+        self.inner.save_with_ttl(key, &compress(data, self.level)?, ttl)
+    }
+
+    fn load(&self, key: &str) -> InfrastructureResult<Option<Vec<u8>>> {
+        self.inner.load(key)?
+            .map(|blob| decompress(&blob).map_err(|e| {
+                InfrastructureError::PersistenceError(format!("Error decompressing '{}': {}", key, e))
+            }))
+            .transpose()
+    }
+
+    fn exists(&self, key: &str) -> InfrastructureResult<bool> {
+        self.inner.exists(key)
+    }
+
+    fn delete(&self, key: &str) -> InfrastructureResult<()> {
+        self.inner.delete(key)
+    }
+
+    fn list_keys(&self) -> InfrastructureResult<Vec<String>> {
+        self.inner.list_keys()
+    }
+}
+
+/// Compress data into a gzip stream
+fn compress(data: &[u8], level: u32) -> InfrastructureResult<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::with_capacity(data.len() / 2), Compression::new(level));
+    encoder.write_all(data)?;
+    Ok(encoder.finish()?)
+}
+
+/// Restore the original data of a gzip blob, passing other blobs through
+fn decompress(blob: &[u8]) -> std::io::Result<Vec<u8>> {
+    if !blob.starts_with(GZIP_MAGIC) {
+        return Ok(blob.to_vec());
+    }
+
+    let mut out = Vec::new();
+    GzDecoder::new(blob).read_to_end(&mut out)?;
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::persistence::InMemoryStorage;
+
+    #[test]
+    fn test_round_trip() {
+        let redundant = r#"{"term":"rust","frequency":1},"#.repeat(500).into_bytes();
+        let varied: Vec<u8> = (0..5000u32).map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8).collect();
+
+        for data in [Vec::new(), b"abc".to_vec(), b"aaaaaaaaaaaaaaaaaaaaaaaaa".to_vec(), redundant.clone(), varied] {
+            for level in [0, 1, 6, 9] {
+                assert_eq!(decompress(&compress(&data, level).unwrap()).unwrap(), data, "level {}", level);
+            }
+        }
+
+        assert!(compress(&redundant, 6).unwrap().len() < redundant.len() / 10);
+    }
+
+    #[test]
+    fn test_compressed_storage() {
+        let storage = CompressedStorage::new(InMemoryStorage::new());
+        let data = "the quick brown fox jumps over the lazy dog ".repeat(100);
+
+        storage.save("doc", data.as_bytes()).unwrap();
+        assert_eq!(storage.load("doc").unwrap(), Some(data.clone().into_bytes()));
+        assert!(storage.inner().load("doc").unwrap().unwrap().len() < data.len() / 4);
+        assert_eq!(storage.load("missing").unwrap(), None);
+
+        // Ranges are read from the decompressed blob
+        assert_eq!(storage.load_range("doc", 4..9).unwrap(), Some(b"quick".to_vec()));
+
+        // Blobs saved before compression was enabled still load
+        storage.inner().save("legacy", b"{\"plain\":true}").unwrap();
+        assert_eq!(storage.load("legacy").unwrap(), Some(b"{\"plain\":true}".to_vec()));
+
+        // Corrupt blobs are reported instead of returning garbage
+        let mut corrupt = compress(data.as_bytes(), 6).unwrap();
+        corrupt.truncate(corrupt.len() / 2);
+        storage.inner().save("corrupt", &corrupt).unwrap();
+        assert!(storage.load("corrupt").is_err());
+    }
+}
@@ -0,0 +1,418 @@
+// src/infrastructure/persistence/file.rs
+
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::infrastructure::{InfrastructureError, InfrastructureResult};
+
+use super::Storage;
+
+/// Marks the start of a journal record
+const JOURNAL_MAGIC: &[u8; 4] = b"TFWL";
+
+/// Longest file name of a key, leaving room for the journal suffix within the usual 255-byte limit
+const MAX_NAME_LEN: usize = 240;
+
+/// What `FileStorage::open` found in the journal left by the previous run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RecoveryReport {
+    /// Complete writes that were re-applied
+    pub replayed: usize,
+
+    /// Incomplete or corrupt writes that were thrown away
+    pub discarded: usize,
+}
+
+/// Storage keeping one file per key in a directory, with a write-ahead journal
+///
+/// Every save is first written to the journal with a checksum and synced, then
+/// copied into place with an atomic rename, and only then removed from the
+/// journal; the directories are synced after each step, so the journal entry
+/// and the rename are on disk before `save` returns. A crash therefore leaves
+/// either the old or the new file, never a torn one: on the next `open`,
+/// complete journal records are replayed and incomplete ones discarded.
+///
+/// Keys are percent-encoded into file names. Keys too long for that are named
+/// by their hash instead, and their files start with the full key.
+pub struct FileStorage {
+    root: PathBuf,
+
+    /// Journal cleanup on the previous run's leftovers
+    recovery: RecoveryReport,
+
+    /// Serializes writes so two saves of one key cannot interleave their renames
+    write_lock: Mutex<()>,
+}
+
+impl FileStorage {
+    /// Open (creating if needed) a storage directory, recovering any interrupted writes
+    pub fn open(root: impl Into<PathBuf>) -> InfrastructureResult<Self> {
+        let root = root.into();
+        for dir in ["data", "journal", "tmp"] {
+            fs::create_dir_all(root.join(dir))?;
+        }
+
+        let mut storage = Self {
+            root,
+            recovery: RecoveryReport::default(),
+            write_lock: Mutex::new(()),
+        };
+        storage.recovery = storage.recover()?;
+        Ok(storage)
+    }
+
+    /// Get the storage directory
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Get what was recovered from the journal when the storage was opened
+    pub fn recovery(&self) -> RecoveryReport {
+        self.recovery
+    }
+
+    fn data_path(&self, key: &str) -> PathBuf {
+        self.root.join("data").join(file_name(key).0)
+    }
+
+    fn journal_path(&self, key: &str) -> PathBuf {
+        self.root.join("journal").join(format!("{}.wal", file_name(key).0))
+    }
+
+    fn tmp_path(&self, key: &str) -> PathBuf {
+        self.root.join("tmp").join(file_name(key).0)
+    }
+
+    /// Open the file of a key, positioned at its data (None = the key is not stored)
+    fn open_data(&self, key: &str) -> InfrastructureResult<Option<File>> {
+        let (name, hashed) = file_name(key);
+        let mut file = match File::open(self.root.join("data").join(name)) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        // A hashed name may belong to another key with the same hash
+        if hashed && read_key(&mut file)? != key {
+            return Ok(None);
+        }
+        Ok(Some(file))
+    }
+
+    /// Durably record an intended write
+    fn write_journal(&self, key: &str, data: &[u8]) -> InfrastructureResult<()> {
+        let mut record = Vec::with_capacity(JOURNAL_MAGIC.len() + 24 + key.len() + data.len());
+        record.extend_from_slice(JOURNAL_MAGIC);
+        record.extend_from_slice(&(key.len() as u64).to_le_bytes());
+        record.extend_from_slice(key.as_bytes());
+        record.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        record.extend_from_slice(data);
+        record.extend_from_slice(&checksum(&record).to_le_bytes());
+
+        write_synced(&self.journal_path(key), &record)?;
+        sync_dir(&self.root.join("journal"))
+    }
+
+    /// Write data into place atomically: a reader sees the old or the new file, never a partial one
+    fn apply(&self, key: &str, data: &[u8]) -> InfrastructureResult<()> {
+        let tmp = self.tmp_path(key);
+        if file_name(key).1 {
+            let mut contents = key_header(key);
+            contents.extend_from_slice(data);
+            write_synced(&tmp, &contents)?;
+        } else {
+            write_synced(&tmp, data)?;
+        }
+        fs::rename(&tmp, self.data_path(key))?;
+        sync_dir(&self.root.join("data"))
+    }
+
+    /// Replay complete journal records and discard incomplete ones
+    fn recover(&self) -> InfrastructureResult<RecoveryReport> {
+        let mut report = RecoveryReport::default();
+
+        for entry in fs::read_dir(self.root.join("tmp"))? {
+            fs::remove_file(entry?.path())?;
+        }
+
+        for entry in fs::read_dir(self.root.join("journal"))? {
+            let path = entry?.path();
+            match parse_journal(&fs::read(&path)?) {
+                Some((key, data)) => {
+                    self.apply(&key, &data)?;
+                    report.replayed += 1;
+                },
+                None => report.discarded += 1,
+            }
+            fs::remove_file(&path)?;
+        }
+        sync_dir(&self.root.join("journal"))?;
+
+        Ok(report)
+    }
+}
+
+impl Storage for FileStorage {
+    fn save(&self, key: &str, data: &[u8]) -> InfrastructureResult<()> {
+        let _guard = self.write_lock.lock().map_err(|e| {
+            InfrastructureError::PersistenceError(format!("Lock error: {}", e))
+        })?;
+
+        if file_name(key).1 && self.data_path(key).is_file() && self.open_data(key)?.is_none() {
+            return Err(InfrastructureError::PersistenceError(format!(
+                "Key '{}' has the same file name as another stored key", key
+            )));
+        }
+
+        self.write_journal(key, data)?;
+        self.apply(key, data)?;
+        fs::remove_file(self.journal_path(key))?;
+        sync_dir(&self.root.join("journal"))
+    }
+
+    fn load(&self, key: &str) -> InfrastructureResult<Option<Vec<u8>>> {
+        let Some(mut file) = self.open_data(key)? else {
+            return Ok(None);
+        };
+
+        let mut data = Vec::new();
+        file.read_to_end(&mut data)?;
+        Ok(Some(data))
+    }
+
+    fn load_range(&self, key: &str, range: Range<usize>) -> InfrastructureResult<Option<Vec<u8>>> {
+        let Some(mut file) = self.open_data(key)? else {
+            return Ok(None);
+        };
+
+        let mut data = Vec::with_capacity(range.len().min(file.metadata()?.len() as usize));
+        file.seek(SeekFrom::Current(range.start as i64))?;
+        file.take(range.len() as u64).read_to_end(&mut data)?;
+        Ok(Some(data))
+    }
+
+    fn exists(&self, key: &str) -> InfrastructureResult<bool> {
+        Ok(self.open_data(key)?.is_some())
+    }
+
+    fn delete(&self, key: &str) -> InfrastructureResult<()> {
+        let _guard = self.write_lock.lock().map_err(|e| {
+            InfrastructureError::PersistenceError(format!("Lock error: {}", e))
+        })?;
+
+        if self.open_data(key)?.is_none() {
+            return Ok(());
+        }
+        fs::remove_file(self.data_path(key))?;
+        sync_dir(&self.root.join("data"))
+    }
+
+    fn list_keys(&self) -> InfrastructureResult<Vec<String>> {
+        let mut keys = Vec::new();
+        for entry in fs::read_dir(self.root.join("data"))? {
+            let entry = entry?;
+            let name = entry.file_name();
+            match name.to_str() {
+                Some(name) if name.starts_with('~') => keys.push(read_key(&mut File::open(entry.path())?)?),
+                Some(name) => keys.extend(decode_key(name)),
+                None => {},
+            }
+        }
+        Ok(keys)
+    }
+}
+
+/// Write a file and sync it to disk before returning
+fn write_synced(path: &Path, data: &[u8]) -> InfrastructureResult<()> {
+    let mut file = File::create(path)?;
+    file.write_all(data)?;
+    file.sync_all()?;
+    Ok(())
+}
+
+/// Sync a directory, so the entries just created, renamed or removed in it survive a crash
+#[cfg(unix)]
+fn sync_dir(dir: &Path) -> InfrastructureResult<()> {
+    File::open(dir)?.sync_all()?;
+    Ok(())
+}
+
+/// Directories cannot be opened for syncing here; their entries are made durable by the file system
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) -> InfrastructureResult<()> {
+    Ok(())
+}
+
+/// Get the file name of a key, and whether it is a hash of the key
+///
+/// `~` is always percent-encoded, so hashed names never clash with encoded ones.
+fn file_name(key: &str) -> (String, bool) {
+    let encoded = encode_key(key);
+    if encoded.len() <= MAX_NAME_LEN {
+        (encoded, false)
+    } else {
+        (format!("~{:016x}", checksum(key.as_bytes())), true)
+    }
+}
+
+/// Encode the full key that starts the file of a hashed name
+fn key_header(key: &str) -> Vec<u8> {
+    let mut header = Vec::with_capacity(8 + key.len());
+    header.extend_from_slice(&(key.len() as u64).to_le_bytes());
+    header.extend_from_slice(key.as_bytes());
+    header
+}
+
+/// Read the full key from the start of a file with a hashed name, leaving the file at its data
+fn read_key(file: &mut File) -> InfrastructureResult<String> {
+    let mut len = [0u8; 8];
+    file.read_exact(&mut len)?;
+    let mut key = Vec::new();
+    file.take(u64::from_le_bytes(len)).read_to_end(&mut key)?;
+    String::from_utf8(key).map_err(|_| {
+        InfrastructureError::PersistenceError("Stored key is not valid UTF-8".to_string())
+    })
+}
+
+/// Parse a journal record, returning `None` if it is truncated or fails its checksum
+fn parse_journal(record: &[u8]) -> Option<(String, Vec<u8>)> {
+    let (body, sum) = record.split_at_checked(record.len().checked_sub(8)?)?;
+    if checksum(body) != u64::from_le_bytes(sum.try_into().ok()?) {
+        return None;
+    }
+
+    let rest = body.strip_prefix(JOURNAL_MAGIC)?;
+    let (key_len, rest) = rest.split_at_checked(8)?;
+    let (key, rest) = rest.split_at_checked(u64::from_le_bytes(key_len.try_into().ok()?) as usize)?;
+    let (data_len, data) = rest.split_at_checked(8)?;
+    if data.len() != u64::from_le_bytes(data_len.try_into().ok()?) as usize {
+        return None;
+    }
+
+    Some((String::from_utf8(key.to_vec()).ok()?, data.to_vec()))
+}
+
+/// 64-bit FNV-1a hash, enough to detect torn journal writes
+fn checksum(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Turn a key into a safe file name by percent-encoding everything but ASCII letters, digits, `-` and `_`
+fn encode_key(key: &str) -> String {
+    key.bytes()
+        .map(|byte| match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' => (byte as char).to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+fn decode_key(name: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(name.len());
+    let mut chars = name.bytes();
+    while let Some(byte) = chars.next() {
+        if byte == b'%' {
+            let hex = [chars.next()?, chars.next()?];
+            bytes.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn temp_root() -> PathBuf {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let root = std::env::temp_dir().join(format!(
+            "tf-idf-rs-file-storage-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::SeqCst)
+        ));
+        let _ = fs::remove_dir_all(&root);
+        root
+    }
+
+    #[test]
+    fn test_save_load_delete() {
+        let root = temp_root();
+        let storage = FileStorage::open(&root).unwrap();
+
+        storage.save("corpora/news.json", b"{}").unwrap();
+        assert_eq!(storage.load("corpora/news.json").unwrap(), Some(b"{}".to_vec()));
+        assert_eq!(storage.list_keys().unwrap(), vec!["corpora/news.json"]);
+        assert_eq!(storage.load_range("corpora/news.json", 1..5).unwrap(), Some(b"}".to_vec()));
+        assert_eq!(storage.load_range("corpora/missing.json", 0..1).unwrap(), None);
+        assert!(fs::read_dir(root.join("journal")).unwrap().next().is_none());
+
+        storage.delete("corpora/news.json").unwrap();
+        assert!(!storage.exists("corpora/news.json").unwrap());
+        assert_eq!(storage.load("corpora/news.json").unwrap(), None);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_recovery() {
+        let root = temp_root();
+        let storage = FileStorage::open(&root).unwrap();
+        storage.save("complete", b"old").unwrap();
+        storage.save("torn", b"old").unwrap();
+
+        // Simulate crashes: one after journaling, one halfway through journaling
+        storage.write_journal("complete", b"new").unwrap();
+        storage.write_journal("torn", b"new").unwrap();
+        let torn = storage.journal_path("torn");
+        let record = fs::read(&torn).unwrap();
+        fs::write(&torn, &record[..record.len() / 2]).unwrap();
+        fs::write(root.join("tmp").join("complete"), b"ne").unwrap();
+        drop(storage);
+
+        let storage = FileStorage::open(&root).unwrap();
+        assert_eq!(storage.recovery(), RecoveryReport { replayed: 1, discarded: 1 });
+        assert_eq!(storage.load("complete").unwrap(), Some(b"new".to_vec()));
+        assert_eq!(storage.load("torn").unwrap(), Some(b"old".to_vec()));
+        assert!(fs::read_dir(root.join("tmp")).unwrap().next().is_none());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_long_keys() {
+        let root = temp_root();
+        let storage = FileStorage::open(&root).unwrap();
+
+        // Encoded, this key is three times the length of a file name the file system allows
+        let key = "/".repeat(200);
+        storage.save(&key, b"long").unwrap();
+        storage.save("short", b"short").unwrap();
+        assert_eq!(storage.load(&key).unwrap(), Some(b"long".to_vec()));
+        assert_eq!(storage.load_range(&key, 1..3).unwrap(), Some(b"on".to_vec()));
+        assert!(storage.exists(&key).unwrap());
+        assert!(!storage.exists(&"/".repeat(201)).unwrap());
+
+        let mut keys = storage.list_keys().unwrap();
+        keys.sort();
+        assert_eq!(keys, vec![key.clone(), "short".to_string()]);
+
+        storage.delete(&key).unwrap();
+        assert_eq!(storage.load(&key).unwrap(), None);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_key_encoding() {
+        for key in ["plain", "with/slash", "dots.and spaces", "ünïcode", "%25"] {
+            assert_eq!(decode_key(&encode_key(key)).as_deref(), Some(key));
+        }
+    }
+}
@@ -1,14 +1,27 @@
-
 // src/infrastructure/persistence/in_memory.rs
 
 use std::collections::HashMap;
+use std::ops::Range;
 use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 use crate::infrastructure::{InfrastructureError, InfrastructureResult};
 
-use super::Storage;
+use super::{slice_range, Storage};
+
+/// A stored blob with its optional expiry time
+struct Entry {
+    data: Vec<u8>,
+    expires_at: Option<Instant>
+}
+
+impl Entry {
+    fn is_live(&self, now: Instant) -> bool {
+        self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+}
 
 pub struct InMemoryStorage {
-    data: Arc<RwLock<HashMap<String, Vec<u8>>>>
+    data: Arc<RwLock<HashMap<String, Entry>>>
 }
 
 impl InMemoryStorage {
@@ -17,6 +30,29 @@ impl InMemoryStorage {
             data: Arc::new(RwLock::new(HashMap::new()))
         }
     }
+
+    /// Drop every expired entry, returning how many were removed
+    ///
+    /// Expired entries are invisible to reads either way; this only frees their memory.
+    pub fn purge_expired(&self) -> InfrastructureResult<usize> {
+        let mut storage = self.data.write().map_err(|e| {
+            InfrastructureError::PersistenceError(format!("Lock error: {}", e))
+        })?;
+
+        let now = Instant::now();
+        let before = storage.len();
+        storage.retain(|_, entry| entry.is_live(now));
+        Ok(before - storage.len())
+    }
+
+    fn insert(&self, key: &str, data: &[u8], expires_at: Option<Instant>) -> InfrastructureResult<()> {
+        let mut storage = self.data.write().map_err(|e| {
+            InfrastructureError::PersistenceError(format!("Lock error: {}", e))
+        })?;
+        
+        storage.insert(key.to_string(), Entry { data: data.to_vec(), expires_at });
+        Ok(())
+    }
 }
 impl Default for InMemoryStorage {
     fn default() -> Self {
@@ -26,20 +62,30 @@ impl Default for InMemoryStorage {
 
 impl Storage for InMemoryStorage {
     fn save(&self, key: &str, data: &[u8]) -> InfrastructureResult<()> {
-        let mut storage = self.data.write().map_err(|e| {
+        self.insert(key, data, None)
+    }
+
+    fn save_with_ttl(&self, key: &str, data: &[u8], ttl: Duration) -> InfrastructureResult<()> {
+        // A TTL too large to represent never expires
+        self.insert(key, data, Instant::now().checked_add(ttl))
+    }
+    
+    fn load(&self, key: &str) -> InfrastructureResult<Option<Vec<u8>>> {
+        let storage = self.data.read().map_err(|e| {
             InfrastructureError::PersistenceError(format!("Lock error: {}", e))
         })?;
         
-        storage.insert(key.to_string(), data.to_vec());
-        Ok(())
+        let now = Instant::now();
+        Ok(storage.get(key).filter(|entry| entry.is_live(now)).map(|entry| entry.data.clone()))
     }
     
-    fn load(&self, key: &str) -> InfrastructureResult<Option<Vec<u8>>> {
+    fn load_range(&self, key: &str, range: Range<usize>) -> InfrastructureResult<Option<Vec<u8>>> {
         let storage = self.data.read().map_err(|e| {
             InfrastructureError::PersistenceError(format!("Lock error: {}", e))
         })?;
         
-        Ok(storage.get(key).cloned())
+        let now = Instant::now();
+        Ok(storage.get(key).filter(|entry| entry.is_live(now)).map(|entry| slice_range(&entry.data, range).to_vec()))
     }
     
     fn exists(&self, key: &str) -> InfrastructureResult<bool> {
@@ -47,7 +93,8 @@ impl Storage for InMemoryStorage {
             InfrastructureError::PersistenceError(format!("Lock error: {}", e))
         })?;
         
-        Ok(storage.contains_key(key))
+        let now = Instant::now();
+        Ok(storage.get(key).is_some_and(|entry| entry.is_live(now)))
     }
     
     fn delete(&self, key: &str) -> InfrastructureResult<()> {
@@ -64,7 +111,11 @@ impl Storage for InMemoryStorage {
             InfrastructureError::PersistenceError(format!("Lock error: {}", e))
         })?;
         
-        let keys: Vec<String> = storage.keys().cloned().collect();
+        let now = Instant::now();
+        let keys: Vec<String> = storage.iter()
+            .filter(|(_, entry)| entry.is_live(now))
+            .map(|(key, _)| key.clone())
+            .collect();
         Ok(keys)
     }
 }
@@ -131,4 +182,28 @@ mod tests {
         assert!(keys.contains(&"key1".to_string()));
         assert!(keys.contains(&"key2".to_string()));
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_expiry() {
+        let storage = InMemoryStorage::new();
+        
+        storage.save_with_ttl("expired", b"data", Duration::ZERO).unwrap();
+        storage.save_with_ttl("fresh", b"data", Duration::from_secs(3600)).unwrap();
+        storage.save("permanent", b"data").unwrap();
+        storage.save_with_ttl("forever", b"data", Duration::MAX).unwrap();
+        
+        // Expired entries are invisible before they are purged
+        assert!(!storage.exists("expired").unwrap());
+        assert!(storage.load("expired").unwrap().is_none());
+        assert_eq!(storage.list_keys().unwrap().len(), 3);
+        assert!(storage.exists("fresh").unwrap());
+        assert!(storage.exists("forever").unwrap());
+        
+        assert_eq!(storage.purge_expired().unwrap(), 1);
+        
+        // Saving without a TTL makes an entry permanent again
+        storage.save_with_ttl("permanent", b"data", Duration::ZERO).unwrap();
+        storage.save("permanent", b"data").unwrap();
+        assert!(storage.exists("permanent").unwrap());
+    }
+}
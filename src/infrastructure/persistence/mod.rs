@@ -3,19 +3,47 @@
 //! Persistence implementations for storing TF-IDF data.
 
 mod in_memory;
+mod compressed;
+mod file;
+#[cfg(feature = "object-store")]
+mod object_store;
 
-//pub use in_memory::InMemoryStorage;
+pub use in_memory::InMemoryStorage;
+pub use compressed::CompressedStorage;
+pub use file::{FileStorage, RecoveryReport};
+#[cfg(feature = "object-store")]
+pub use object_store::{ObjectStoreBucket, ObjectStoreClient, ObjectStoreStorage};
 
-use crate::infrastructure::InfrastructureResult;
+use std::ops::Range;
+use std::time::Duration;
+
+use crate::infrastructure::{InfrastructureError, InfrastructureResult};
 
 /// Generic persistence interface
 pub trait Storage: Send + Sync {
     /// Save data under a key
     fn save(&self, key: &str, data: &[u8]) -> InfrastructureResult<()>;
     
+    /// Save data under a key that expires after `ttl`, for transient artifacts such as cached vectors
+    ///
+    /// An expired key behaves as if it was deleted. Backends without expiry support return an error.
+    fn save_with_ttl(&self, key: &str, data: &[u8], ttl: Duration) -> InfrastructureResult<()> {
+        let _ = (data, ttl);
+        Err(InfrastructureError::PersistenceError(format!("Storage does not support expiry for key '{}'", key)))
+    }
+    
     /// Load data by key
     fn load(&self, key: &str) -> InfrastructureResult<Option<Vec<u8>>>;
     
+    /// Load the bytes in a range of the data stored under a key
+    ///
+    /// The range is cut off at the end of the data, so fewer bytes come back
+    /// past it. The default loads the whole blob; backends that can read part
+    /// of one override it.
+    fn load_range(&self, key: &str, range: Range<usize>) -> InfrastructureResult<Option<Vec<u8>>> {
+        Ok(self.load(key)?.map(|data| slice_range(&data, range).to_vec()))
+    }
+    
     /// Check if data exists for a key
     fn exists(&self, key: &str) -> InfrastructureResult<bool>;
     
@@ -24,4 +52,10 @@ pub trait Storage: Send + Sync {
     
     /// List all keys
     fn list_keys(&self) -> InfrastructureResult<Vec<String>>;
-}
\ No newline at end of file
+}
+
+/// Get the part of a blob in a range, cut off at the end of the blob
+fn slice_range(data: &[u8], range: Range<usize>) -> &[u8] {
+    let end = range.end.min(data.len());
+    &data[range.start.min(end)..end]
+}
@@ -0,0 +1,269 @@
+// src/infrastructure/persistence/object_store.rs
+
+use std::sync::Arc;
+
+use futures::TryStreamExt;
+use object_store::path::Path;
+use object_store::{ObjectStore, ObjectStoreExt, PutPayload};
+use tokio::runtime::Runtime;
+
+use crate::infrastructure::{InfrastructureError, InfrastructureResult};
+
+use super::Storage;
+
+/// Minimal blocking interface to a bucket in an object store such as S3, GCS or Azure Blob
+///
+/// `ObjectStoreBucket` implements it over any backend of the `object_store`
+/// crate; implement it over another SDK to plug that into `ObjectStoreStorage`.
+pub trait ObjectStoreClient: Send + Sync {
+    /// Upload an object, replacing any existing one
+    fn put(&self, path: &str, data: &[u8]) -> InfrastructureResult<()>;
+
+    /// Download an object, or `None` if it does not exist
+    fn get(&self, path: &str) -> InfrastructureResult<Option<Vec<u8>>>;
+
+    /// Check whether an object exists
+    fn head(&self, path: &str) -> InfrastructureResult<bool>;
+
+    /// Delete an object; deleting a missing object is not an error
+    fn delete(&self, path: &str) -> InfrastructureResult<()>;
+
+    /// List the paths of all objects starting with a prefix
+    fn list(&self, prefix: &str) -> InfrastructureResult<Vec<String>>;
+}
+
+/// An `ObjectStoreClient` over a backend of the `object_store` crate
+///
+/// The async backend is driven by a runtime owned by the bucket, so its
+/// methods block and must not be called from inside another async runtime.
+/// Cloud backends are available when the matching `object_store` features
+/// (`aws`, `gcp`, `azure`, `http`) are enabled.
+pub struct ObjectStoreBucket {
+    store: Arc<dyn ObjectStore>,
+    runtime: Runtime,
+}
+
+impl ObjectStoreBucket {
+    /// Wrap an `object_store` backend
+    pub fn new(store: Arc<dyn ObjectStore>) -> InfrastructureResult<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+        Ok(Self { store, runtime })
+    }
+
+    /// Open the backend named by a URL such as `s3://bucket`, `gs://bucket` or `file:///data`
+    ///
+    /// The path of the URL is not applied; pass it to `ObjectStoreStorage::with_prefix` instead.
+    pub fn from_url(url: &str) -> InfrastructureResult<Self> {
+        let url = url.parse().map_err(|e| {
+            InfrastructureError::PersistenceError(format!("Invalid object store URL '{}': {}", url, e))
+        })?;
+        let (store, _) = object_store::parse_url(&url).map_err(store_error)?;
+        Self::new(Arc::from(store))
+    }
+
+    /// Get the wrapped backend
+    pub fn store(&self) -> &Arc<dyn ObjectStore> {
+        &self.store
+    }
+}
+
+impl ObjectStoreClient for ObjectStoreBucket {
+    fn put(&self, path: &str, data: &[u8]) -> InfrastructureResult<()> {
+        let path = parse_path(path)?;
+        self.runtime.block_on(self.store.put(&path, PutPayload::from(data.to_vec()))).map_err(store_error)?;
+        Ok(())
+    }
+
+    fn get(&self, path: &str) -> InfrastructureResult<Option<Vec<u8>>> {
+        let path = parse_path(path)?;
+        self.runtime.block_on(async {
+            match self.store.get(&path).await {
+                Ok(result) => Ok(Some(result.bytes().await.map_err(store_error)?.to_vec())),
+                Err(object_store::Error::NotFound { .. }) => Ok(None),
+                Err(e) => Err(store_error(e)),
+            }
+        })
+    }
+
+    fn head(&self, path: &str) -> InfrastructureResult<bool> {
+        let path = parse_path(path)?;
+        match self.runtime.block_on(self.store.head(&path)) {
+            Ok(_) => Ok(true),
+            Err(object_store::Error::NotFound { .. }) => Ok(false),
+            Err(e) => Err(store_error(e)),
+        }
+    }
+
+    fn delete(&self, path: &str) -> InfrastructureResult<()> {
+        let path = parse_path(path)?;
+        match self.runtime.block_on(self.store.delete(&path)) {
+            Ok(()) | Err(object_store::Error::NotFound { .. }) => Ok(()),
+            Err(e) => Err(store_error(e)),
+        }
+    }
+
+    fn list(&self, prefix: &str) -> InfrastructureResult<Vec<String>> {
+        // Listing matches whole path segments, so list the enclosing directory and filter
+        let directory = prefix.rsplit_once('/').map(|(directory, _)| parse_path(directory)).transpose()?;
+        let objects: Vec<_> = self.runtime.block_on(self.store.list(directory.as_ref()).try_collect()).map_err(store_error)?;
+
+        Ok(objects.into_iter()
+            .map(|object| object.location.to_string())
+            .filter(|path| path.starts_with(prefix))
+            .collect())
+    }
+}
+
+fn parse_path(path: &str) -> InfrastructureResult<Path> {
+    Path::parse(path).map_err(|e| InfrastructureError::PersistenceError(format!("Invalid object path '{}': {}", path, e)))
+}
+
+fn store_error(e: object_store::Error) -> InfrastructureError {
+    InfrastructureError::PersistenceError(format!("Object store error: {}", e))
+}
+
+/// Storage that keeps every key as an object in a bucket, optionally under a path prefix
+pub struct ObjectStoreStorage<C: ObjectStoreClient> {
+    client: C,
+
+    /// Path prefix shared by all objects, without a trailing slash (empty = bucket root)
+    prefix: String
+}
+
+impl<C: ObjectStoreClient> ObjectStoreStorage<C> {
+    /// Store objects at the root of the bucket
+    pub fn new(client: C) -> Self {
+        Self::with_prefix(client, "")
+    }
+
+    /// Store objects under a path prefix, e.g. `indexes/production`
+    pub fn with_prefix(client: C, prefix: impl Into<String>) -> Self {
+        Self {
+            client,
+            prefix: prefix.into().trim_matches('/').to_string()
+        }
+    }
+
+    /// Get the path prefix
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Get the underlying client
+    pub fn client(&self) -> &C {
+        &self.client
+    }
+
+    /// Map a storage key to an object path
+    fn path(&self, key: &str) -> InfrastructureResult<String> {
+        if key.is_empty() || key.starts_with('/') || key.split('/').any(|segment| segment == "..") {
+            return Err(InfrastructureError::PersistenceError(format!("Invalid object key '{}'", key)));
+        }
+
+        if self.prefix.is_empty() {
+            Ok(key.to_string())
+        } else {
+            Ok(format!("{}/{}", self.prefix, key))
+        }
+    }
+}
+
+impl<C: ObjectStoreClient> Storage for ObjectStoreStorage<C> {
+    fn save(&self, key: &str, data: &[u8]) -> InfrastructureResult<()> {
+        self.client.put(&self.path(key)?, data)
+    }
+
+    fn load(&self, key: &str) -> InfrastructureResult<Option<Vec<u8>>> {
+        self.client.get(&self.path(key)?)
+    }
+
+    fn exists(&self, key: &str) -> InfrastructureResult<bool> {
+        self.client.head(&self.path(key)?)
+    }
+
+    fn delete(&self, key: &str) -> InfrastructureResult<()> {
+        self.client.delete(&self.path(key)?)
+    }
+
+    fn list_keys(&self) -> InfrastructureResult<Vec<String>> {
+        let prefix = if self.prefix.is_empty() {
+            String::new()
+        } else {
+            format!("{}/", self.prefix)
+        };
+
+        Ok(self.client.list(&prefix)?
+            .into_iter()
+            .filter_map(|path| path.strip_prefix(&prefix).map(str::to_string))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use std::sync::RwLock;
+
+    /// A bucket kept in memory
+    #[derive(Default)]
+    struct FakeBucket {
+        objects: RwLock<BTreeMap<String, Vec<u8>>>
+    }
+
+    impl ObjectStoreClient for FakeBucket {
+        fn put(&self, path: &str, data: &[u8]) -> InfrastructureResult<()> {
+            self.objects.write().unwrap().insert(path.to_string(), data.to_vec());
+            Ok(())
+        }
+
+        fn get(&self, path: &str) -> InfrastructureResult<Option<Vec<u8>>> {
+            Ok(self.objects.read().unwrap().get(path).cloned())
+        }
+
+        fn head(&self, path: &str) -> InfrastructureResult<bool> {
+            Ok(self.objects.read().unwrap().contains_key(path))
+        }
+
+        fn delete(&self, path: &str) -> InfrastructureResult<()> {
+            self.objects.write().unwrap().remove(path);
+            Ok(())
+        }
+
+        fn list(&self, prefix: &str) -> InfrastructureResult<Vec<String>> {
+            Ok(self.objects.read().unwrap().keys().filter(|path| path.starts_with(prefix)).cloned().collect())
+        }
+    }
+
+    #[test]
+    fn test_prefixed_storage() {
+        let storage = ObjectStoreStorage::with_prefix(FakeBucket::default(), "/indexes/prod/");
+        storage.client().put("other/key", b"unrelated").unwrap();
+
+        storage.save("corpus1", b"data").unwrap();
+        assert_eq!(storage.load("corpus1").unwrap(), Some(b"data".to_vec()));
+        assert!(storage.client().head("indexes/prod/corpus1").unwrap());
+        assert_eq!(storage.list_keys().unwrap(), vec!["corpus1"]);
+
+        storage.delete("corpus1").unwrap();
+        assert!(!storage.exists("corpus1").unwrap());
+        assert!(storage.save("../escape", b"data").is_err());
+    }
+
+    #[test]
+    fn test_object_store_bucket() {
+        let bucket = ObjectStoreBucket::new(Arc::new(object_store::memory::InMemory::new())).unwrap();
+        let storage = ObjectStoreStorage::with_prefix(bucket, "indexes/prod");
+        storage.client().put("indexes/production/corpus1", b"unrelated").unwrap();
+
+        storage.save("corpora/corpus1", b"data").unwrap();
+        assert_eq!(storage.load("corpora/corpus1").unwrap(), Some(b"data".to_vec()));
+        assert_eq!(storage.load("corpora/missing").unwrap(), None);
+        assert!(storage.exists("corpora/corpus1").unwrap());
+        assert_eq!(storage.list_keys().unwrap(), vec!["corpora/corpus1"]);
+
+        storage.delete("corpora/corpus1").unwrap();
+        storage.delete("corpora/corpus1").unwrap();
+        assert!(!storage.exists("corpora/corpus1").unwrap());
+    }
+}
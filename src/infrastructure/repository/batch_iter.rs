@@ -0,0 +1,116 @@
+// src/infrastructure/repository/batch_iter.rs
+
+use std::collections::VecDeque;
+
+use super::{RepositoryError, RepositoryResult};
+
+/// Number of entities fetched per batch when streaming a repository
+pub(crate) const BATCH_SIZE: usize = 256;
+
+/// Iterator that streams entities in ID order, loading one batch of IDs at a time
+///
+/// The IDs are listed and sorted once up front, then walked with a cursor, so
+/// each batch costs only the entities it loads. Only the current batch of
+/// entities is held in memory. Entities deleted after the IDs were listed are
+/// skipped. The first error is yielded and ends iteration.
+pub(crate) struct BatchIter<T, K, F>
+where
+    F: FnMut(&K) -> RepositoryResult<Option<T>>,
+{
+    /// Load the entity with an ID, if it still exists
+    load: F,
+
+    /// IDs of all entities, in order
+    ids: Vec<K>,
+
+    /// Position of the next ID to load
+    cursor: usize,
+
+    buffer: VecDeque<T>,
+    batch_size: usize,
+
+    /// Error to yield next, such as a failure to list the IDs
+    error: Option<RepositoryError>,
+    failed: bool,
+}
+
+impl<T, K, F> BatchIter<T, K, F>
+where
+    F: FnMut(&K) -> RepositoryResult<Option<T>>,
+{
+    pub(crate) fn new(batch_size: usize, ids: RepositoryResult<Vec<K>>, load: F) -> Self {
+        let (ids, error) = match ids {
+            Ok(ids) => (ids, None),
+            Err(e) => (Vec::new(), Some(e)),
+        };
+        Self {
+            load,
+            ids,
+            cursor: 0,
+            buffer: VecDeque::new(),
+            batch_size: batch_size.max(1),
+            error,
+            failed: false,
+        }
+    }
+
+    /// Load the next batch of entities into the buffer
+    fn fill(&mut self) -> RepositoryResult<()> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+        while self.buffer.is_empty() && self.cursor < self.ids.len() {
+            let end = (self.cursor + self.batch_size).min(self.ids.len());
+            for id in &self.ids[self.cursor..end] {
+                if let Some(entity) = (self.load)(id)? {
+                    self.buffer.push_back(entity);
+                }
+            }
+            self.cursor = end;
+        }
+        Ok(())
+    }
+}
+
+impl<T, K, F> Iterator for BatchIter<T, K, F>
+where
+    F: FnMut(&K) -> RepositoryResult<Option<T>>,
+{
+    type Item = RepositoryResult<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        if let Err(e) = self.fill() {
+            self.failed = true;
+            return Some(Err(e));
+        }
+
+        self.buffer.pop_front().map(Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batches() {
+        let mut loads = 0;
+        let items: Vec<usize> = BatchIter::new(2, Ok((0..6).collect()), |id: &usize| {
+            loads += 1;
+            Ok((*id != 3).then_some(*id))
+        })
+            .map(Result::unwrap)
+            .collect();
+
+        // Missing entities are skipped, and every ID is loaded exactly once
+        assert_eq!(items, vec![0, 1, 2, 4, 5]);
+        assert_eq!(loads, 6);
+
+        let mut failing = BatchIter::new(2, Err(RepositoryError::Other("unavailable".to_string())), |id: &usize| Ok(Some(*id)));
+        assert!(failing.next().unwrap().is_err());
+        assert!(failing.next().is_none());
+    }
+}
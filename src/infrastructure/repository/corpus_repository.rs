@@ -3,14 +3,21 @@
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
-use crate::domain::{Corpus, CorpusId};
-use super::{RepositoryError, RepositoryResult};
+use crate::domain::{Corpus, CorpusId, CorpusSnapshot, Page, PageRequest};
+use super::{DocumentRepository, RepositoryError, RepositoryResult};
+use super::document_repository::resolver;
+use super::batch_iter::{BatchIter, BATCH_SIZE};
 
 /// Repository interface for Corpus entities
 pub trait CorpusRepository: Send + Sync {
     /// Find a corpus by ID
     fn find(&self, id: &CorpusId) -> RepositoryResult<Option<Corpus>>;
     
+    /// Find a corpus by ID as a snapshot that searches can share without copying it
+    fn find_snapshot(&self, id: &CorpusId) -> RepositoryResult<Option<CorpusSnapshot>> {
+        Ok(self.find(id)?.map(CorpusSnapshot::new))
+    }
+    
     /// Check if a corpus exists
     fn exists(&self, id: &CorpusId) -> RepositoryResult<bool>;
     
@@ -23,6 +30,22 @@ pub trait CorpusRepository: Send + Sync {
     /// Find all corpora
     fn find_all(&self) -> RepositoryResult<Vec<Corpus>>;
     
+    /// Find one page of corpora, ordered by ID
+    fn find_page(&self, request: PageRequest) -> RepositoryResult<Page<Corpus>>;
+    
+    /// Find up to `limit` corpora whose IDs sort after `after` (None = from the start), ordered by ID
+    fn find_after(&self, after: Option<&CorpusId>, limit: usize) -> RepositoryResult<Vec<Corpus>>;
+    
+    /// List the IDs of all corpora, ordered by ID
+    fn find_ids(&self) -> RepositoryResult<Vec<CorpusId>>;
+    
+    /// Stream all corpora in ID order, fetching them in batches instead of all at once
+    ///
+    /// The IDs are listed once when iteration starts; corpora deleted later are skipped.
+    fn iter_corpora(&self) -> Box<dyn Iterator<Item = RepositoryResult<Corpus>> + '_> {
+        Box::new(BatchIter::new(BATCH_SIZE, self.find_ids(), |id: &CorpusId| self.find(id)))
+    }
+    
     /// Count all corpora
     fn count(&self) -> RepositoryResult<usize>;
     
@@ -31,8 +54,15 @@ pub trait CorpusRepository: Send + Sync {
 }
 
 /// In-memory implementation of CorpusRepository
+///
+/// With a document repository set, stored corpora hold their documents by ID
+/// and every corpus returned loads them from that repository on first use,
+/// so it always reads the repository's current copies.
 pub struct InMemoryCorpusRepository {
-    corpora: Arc<RwLock<HashMap<String, Corpus>>>,
+    corpora: Arc<RwLock<HashMap<String, CorpusSnapshot>>>,
+
+    /// Repository corpus documents are resolved from (None = corpora hold their documents)
+    documents: Option<Arc<dyn DocumentRepository>>,
 }
 
 impl InMemoryCorpusRepository {
@@ -40,6 +70,26 @@ impl InMemoryCorpusRepository {
     pub fn new() -> Self {
         Self {
             corpora: Arc::new(RwLock::new(HashMap::new())),
+            documents: None,
+        }
+    }
+
+    /// Hold corpus documents by reference to a document repository (None = corpora hold their documents)
+    ///
+    /// Saving a corpus with an added document missing from the repository fails.
+    pub fn set_document_repository(&mut self, documents: Option<Arc<dyn DocumentRepository>>) {
+        self.documents = documents;
+    }
+
+    /// Copy a stored corpus to return, attaching its documents afresh so the copy loads their current versions
+    fn found(&self, stored: &CorpusSnapshot) -> Corpus {
+        match &self.documents {
+            Some(documents) => {
+                let (mut corpus, members) = stored.detach_documents();
+                corpus.attach_documents(&members, resolver(documents.clone()));
+                corpus
+            },
+            None => stored.corpus().clone(),
         }
     }
 }
@@ -56,6 +106,18 @@ impl CorpusRepository for InMemoryCorpusRepository {
             RepositoryError::Other(format!("Lock error: {}", e))
         })?;
         
+        Ok(corpora.get(id.value()).map(|snapshot| self.found(snapshot)))
+    }
+    
+    fn find_snapshot(&self, id: &CorpusId) -> RepositoryResult<Option<CorpusSnapshot>> {
+        if self.documents.is_some() {
+            // A shared snapshot would keep serving the documents its first search loaded
+            return Ok(self.find(id)?.map(CorpusSnapshot::new));
+        }
+        let corpora = self.corpora.read().map_err(|e| {
+            RepositoryError::Other(format!("Lock error: {}", e))
+        })?;
+        
         Ok(corpora.get(id.value()).cloned())
     }
     
@@ -68,11 +130,27 @@ impl CorpusRepository for InMemoryCorpusRepository {
     }
     
     fn save(&self, corpus: &Corpus) -> RepositoryResult<()> {
+        let stored = match &self.documents {
+            Some(documents) => {
+                for document_id in corpus.document_ids().filter(|id| corpus.is_document_loaded(id)) {
+                    if !documents.exists(document_id)? {
+                        return Err(RepositoryError::PersistenceError(format!(
+                            "Document '{}' of corpus '{}' is not in the document repository", document_id.value(), corpus.id().value()
+                        )));
+                    }
+                }
+                let (mut stored, members) = corpus.detach_documents();
+                stored.attach_documents(&members, resolver(documents.clone()));
+                stored
+            },
+            None => corpus.clone(),
+        };
+
         let mut corpora = self.corpora.write().map_err(|e| {
             RepositoryError::Other(format!("Lock error: {}", e))
         })?;
         
-        corpora.insert(corpus.id().value().to_string(), corpus.clone());
+        corpora.insert(corpus.id().value().to_string(), CorpusSnapshot::new(stored));
         Ok(())
     }
     
@@ -90,10 +168,53 @@ impl CorpusRepository for InMemoryCorpusRepository {
             RepositoryError::Other(format!("Lock error: {}", e))
         })?;
         
-        let results: Vec<Corpus> = corpora.values().cloned().collect();
+        let results: Vec<Corpus> = corpora.values().map(|snapshot| self.found(snapshot)).collect();
         Ok(results)
     }
     
+    fn find_page(&self, request: PageRequest) -> RepositoryResult<Page<Corpus>> {
+        let corpora = self.corpora.read().map_err(|e| {
+            RepositoryError::Other(format!("Lock error: {}", e))
+        })?;
+        
+        let mut ids: Vec<&String> = corpora.keys().collect();
+        ids.sort();
+        
+        let items = ids.iter()
+            .skip(request.offset())
+            .take(request.limit())
+            .filter_map(|id| corpora.get(*id).map(|snapshot| self.found(snapshot)))
+            .collect();
+        
+        Ok(Page::new(items, request, corpora.len()))
+    }
+    
+    fn find_after(&self, after: Option<&CorpusId>, limit: usize) -> RepositoryResult<Vec<Corpus>> {
+        let corpora = self.corpora.read().map_err(|e| {
+            RepositoryError::Other(format!("Lock error: {}", e))
+        })?;
+        
+        let mut ids: Vec<&String> = corpora.keys()
+            .filter(|id| after.is_none_or(|after| id.as_str() > after.value()))
+            .collect();
+        ids.sort();
+        
+        Ok(ids.into_iter()
+            .take(limit)
+            .filter_map(|id| corpora.get(id).map(|snapshot| self.found(snapshot)))
+            .collect())
+    }
+    
+    fn find_ids(&self) -> RepositoryResult<Vec<CorpusId>> {
+        let corpora = self.corpora.read().map_err(|e| {
+            RepositoryError::Other(format!("Lock error: {}", e))
+        })?;
+        
+        let mut ids: Vec<CorpusId> = corpora.keys().map(CorpusId::new).collect();
+        ids.sort_by(|a, b| a.value().cmp(b.value()));
+        Ok(ids)
+    }
+    
     fn count(&self) -> RepositoryResult<usize> {
         let corpora = self.corpora.read().map_err(|e| {
             RepositoryError::Other(format!("Lock error: {}", e))
@@ -109,7 +230,7 @@ impl CorpusRepository for InMemoryCorpusRepository {
 
         let match_corpora = corpora.values()
             .filter(|c| c.name().to_lowercase().contains(&name.to_lowercase()))
-            .cloned()
+            .map(|c| self.found(c))
             .collect();
         
         Ok(match_corpora)
@@ -119,6 +240,8 @@ impl CorpusRepository for InMemoryCorpusRepository {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::{Document, DocumentId};
+    use crate::infrastructure::repository::InMemoryDocumentRepository;
     
     #[test]
     fn test_save_and_find_corpus() {
@@ -179,6 +302,26 @@ mod tests {
         assert_eq!(repo.count().unwrap(), 2);
     }
     
+    #[test]
+    fn test_pagination() {
+        let repo = InMemoryCorpusRepository::new();
+        
+        for id in ["corpus3", "corpus1", "corpus2"] {
+            repo.save(&Corpus::new(id, "Corpus")).unwrap();
+        }
+        
+        let page = repo.find_page(PageRequest::new(1, 1)).unwrap();
+        assert_eq!(page.items()[0].id().value(), "corpus2");
+        assert_eq!(page.total(), 3);
+        
+        let after = repo.find_after(Some(&CorpusId::new("corpus1")), 5).unwrap();
+        let ids: Vec<&str> = after.iter().map(|corpus| corpus.id().value()).collect();
+        assert_eq!(ids, vec!["corpus2", "corpus3"]);
+        
+        let streamed: Vec<String> = repo.iter_corpora().map(|corpus| corpus.unwrap().id().value().to_string()).collect();
+        assert_eq!(streamed, vec!["corpus1", "corpus2", "corpus3"]);
+    }
+    
     #[test]
     fn test_find_by_name() {
         let repo = InMemoryCorpusRepository::new();
@@ -200,4 +343,26 @@ mod tests {
         let nonexistent = repo.find_by_name("nonexistent").unwrap();
         assert_eq!(nonexistent.len(), 0);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_find_loads_current_documents() {
+        let documents = Arc::new(InMemoryDocumentRepository::new());
+        let mut repo = InMemoryCorpusRepository::new();
+        repo.set_document_repository(Some(documents.clone()));
+
+        let mut corpus = Corpus::new("corpus1", "Test Corpus");
+        let doc = Document::new("doc1", "rust code");
+        documents.save(&doc).unwrap();
+        corpus.add_document(doc).unwrap();
+        repo.save(&corpus).unwrap();
+
+        // Loading the documents of one copy leaves the stored corpus unloaded
+        let found = repo.find_snapshot(&CorpusId::new("corpus1")).unwrap().unwrap();
+        assert_eq!(found.get_document(&DocumentId::new("doc1")).unwrap().unwrap().content(), "rust code");
+
+        let edited = Document::new("doc1", "go code");
+        documents.save(&edited).unwrap();
+        let found = repo.find(&CorpusId::new("corpus1")).unwrap().unwrap();
+        assert_eq!(found.get_document(&DocumentId::new("doc1")).unwrap().unwrap().content(), "go code");
+    }
+}
@@ -3,13 +3,16 @@
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
-use crate::domain::{Document, DocumentId, Term};
+use crate::domain::{Document, DocumentId, DocumentResolver, DomainError, Page, PageRequest, Term};
 use super::{RepositoryError, RepositoryResult};
+use super::batch_iter::{BatchIter, BATCH_SIZE};
 
 /// Repository interface for Document entities
+///
+/// Documents are returned as shared handles, so reads do not copy their content and terms.
 pub trait DocumentRepository: Send + Sync {
     /// Find a document by ID
-    fn find(&self, id: &DocumentId) -> RepositoryResult<Option<Document>>;
+    fn find(&self, id: &DocumentId) -> RepositoryResult<Option<Arc<Document>>>;
     
     /// Check if a document exists
     fn exists(&self, id: &DocumentId) -> RepositoryResult<bool>;
@@ -21,18 +24,43 @@ pub trait DocumentRepository: Send + Sync {
     fn delete(&self, id: &DocumentId) -> RepositoryResult<()>;
     
     /// Find all documents
-    fn find_all(&self) -> RepositoryResult<Vec<Document>>;
+    fn find_all(&self) -> RepositoryResult<Vec<Arc<Document>>>;
+    
+    /// Find one page of documents, ordered by ID
+    fn find_page(&self, request: PageRequest) -> RepositoryResult<Page<Arc<Document>>>;
+    
+    /// Find up to `limit` documents whose IDs sort after `after` (None = from the start), ordered by ID
+    ///
+    /// Unlike offset paging, this stays cheap and stable deep into a large store.
+    fn find_after(&self, after: Option<&DocumentId>, limit: usize) -> RepositoryResult<Vec<Arc<Document>>>;
+    
+    /// List the IDs of all documents, ordered by ID
+    fn find_ids(&self) -> RepositoryResult<Vec<DocumentId>>;
+    
+    /// Stream all documents in ID order, fetching them in batches instead of all at once
+    ///
+    /// The IDs are listed once when iteration starts; documents deleted later are skipped.
+    fn iter_documents(&self) -> Box<dyn Iterator<Item = RepositoryResult<Arc<Document>>> + '_> {
+        Box::new(BatchIter::new(BATCH_SIZE, self.find_ids(), |id: &DocumentId| self.find(id)))
+    }
     
     /// Count all documents
     fn count(&self) -> RepositoryResult<usize>;
     
     /// Find documents containing a specific term
-    fn find_by_term(&self, term: &Term) -> RepositoryResult<Vec<Document>>;
+    fn find_by_term(&self, term: &Term) -> RepositoryResult<Vec<Arc<Document>>>;
+}
+
+/// Resolve the documents of corpora held by ID through a document repository
+pub(super) fn resolver(documents: Arc<dyn DocumentRepository>) -> Arc<dyn DocumentResolver> {
+    Arc::new(move |document_id: &DocumentId| documents.find(document_id).map_err(|e| {
+        DomainError::Other(format!("Error retrieving document '{}': {}", document_id.value(), e))
+    }))
 }
 
 /// In-memory implementation of DocumentRepository
 pub struct InMemoryDocumentRepository {
-    documents: Arc<RwLock<HashMap<String, Document>>>,
+    documents: Arc<RwLock<HashMap<String, Arc<Document>>>>,
 }
 
 impl InMemoryDocumentRepository {
@@ -51,7 +79,7 @@ impl Default for InMemoryDocumentRepository {
 }
 
 impl DocumentRepository for InMemoryDocumentRepository {
-    fn find(&self, id: &DocumentId) -> RepositoryResult<Option<Document>> {
+    fn find(&self, id: &DocumentId) -> RepositoryResult<Option<Arc<Document>>> {
         let documents = self.documents.read().map_err(
             |e|  RepositoryError::Other(format!("Lock error {}", e))
         )?;
@@ -72,7 +100,7 @@ impl DocumentRepository for InMemoryDocumentRepository {
             |e|  RepositoryError::Other(format!("Lock error {}", e))
         )?;
         
-        documents.insert(document.id().value().to_string(), document.clone());
+        documents.insert(document.id().value().to_string(), Arc::new(document.clone()));
         Ok(())
     }
 
@@ -86,7 +114,7 @@ impl DocumentRepository for InMemoryDocumentRepository {
         Ok(())
     }
 
-    fn find_all(&self) -> RepositoryResult<Vec<Document>> {
+    fn find_all(&self) -> RepositoryResult<Vec<Arc<Document>>> {
         let documents = self.documents.read().map_err(
             |e|  RepositoryError::Other(format!("Lock error {}", e))
         )?;
@@ -94,6 +122,50 @@ impl DocumentRepository for InMemoryDocumentRepository {
         Ok(documents.values().cloned().collect())
     }
 
+    fn find_page(&self, request: PageRequest) -> RepositoryResult<Page<Arc<Document>>> {
+        let documents = self.documents.read().map_err(
+            |e|  RepositoryError::Other(format!("Lock error {}", e))
+        )?;
+
+        // Sort the IDs so pages are stable, and share only the documents on this page
+        let mut ids: Vec<&String> = documents.keys().collect();
+        ids.sort();
+
+        let items = ids.iter()
+            .skip(request.offset())
+            .take(request.limit())
+            .filter_map(|id| documents.get(*id).cloned())
+            .collect();
+
+        Ok(Page::new(items, request, documents.len()))
+    }
+
+    fn find_after(&self, after: Option<&DocumentId>, limit: usize) -> RepositoryResult<Vec<Arc<Document>>> {
+        let documents = self.documents.read().map_err(
+            |e|  RepositoryError::Other(format!("Lock error {}", e))
+        )?;
+
+        let mut ids: Vec<&String> = documents.keys()
+            .filter(|id| after.is_none_or(|after| id.as_str() > after.value()))
+            .collect();
+        ids.sort();
+
+        Ok(ids.into_iter()
+            .take(limit)
+            .filter_map(|id| documents.get(id).cloned())
+            .collect())
+    }
+
+    fn find_ids(&self) -> RepositoryResult<Vec<DocumentId>> {
+        let documents = self.documents.read().map_err(
+            |e|  RepositoryError::Other(format!("Lock error {}", e))
+        )?;
+
+        let mut ids: Vec<DocumentId> = documents.keys().map(DocumentId::new).collect();
+        ids.sort_by(|a, b| a.value().cmp(b.value()));
+        Ok(ids)
+    }
+
     fn count(&self) -> RepositoryResult<usize> {
         let documents = self.documents.read().map_err(
             |e|  RepositoryError::Other(format!("Lock error {}", e))
@@ -103,7 +175,7 @@ impl DocumentRepository for InMemoryDocumentRepository {
 
     }
 
-    fn find_by_term(&self, term: &Term) -> RepositoryResult<Vec<Document>> {
+    fn find_by_term(&self, term: &Term) -> RepositoryResult<Vec<Arc<Document>>> {
         let documents = self.documents.read().map_err(
             |e|  RepositoryError::Other(format!("Lock error {}", e))
         )?;
@@ -181,6 +253,54 @@ mod tests {
         assert_eq!(repo.count().unwrap(), 2);
     }
     
+    #[test]
+    fn test_find_after() {
+        let repo = InMemoryDocumentRepository::new();
+        
+        for id in ["doc3", "doc1", "doc2"] {
+            repo.save(&Document::new(id, "Document")).unwrap();
+        }
+        
+        let first = repo.find_after(None, 2).unwrap();
+        let ids: Vec<&str> = first.iter().map(|doc| doc.id().value()).collect();
+        assert_eq!(ids, vec!["doc1", "doc2"]);
+        
+        let rest = repo.find_after(Some(first[1].id()), 2).unwrap();
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest[0].id().value(), "doc3");
+        assert!(repo.find_after(Some(&DocumentId::new("doc3")), 2).unwrap().is_empty());
+    }
+    
+    #[test]
+    fn test_iter_documents() {
+        let repo = InMemoryDocumentRepository::new();
+        
+        for i in 0..300 {
+            repo.save(&Document::new(format!("doc{:03}", i), "Document")).unwrap();
+        }
+        
+        let ids: Vec<String> = repo.iter_documents()
+            .map(|doc| doc.unwrap().id().value().to_string())
+            .collect();
+        assert_eq!(ids.len(), 300);
+        assert_eq!(ids[299], "doc299");
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+    
+    #[test]
+    fn test_find_page() {
+        let repo = InMemoryDocumentRepository::new();
+        
+        for id in ["doc3", "doc1", "doc2"] {
+            repo.save(&Document::new(id, "Document")).unwrap();
+        }
+        
+        let page = repo.find_page(PageRequest::new(1, 5)).unwrap();
+        assert_eq!(page.total(), 3);
+        let ids: Vec<_> = page.items().iter().map(|d| d.id().value()).collect();
+        assert_eq!(ids, vec!["doc2", "doc3"]);
+    }
+    
     #[test]
     fn test_find_by_term() {
         let repo = InMemoryDocumentRepository::new();
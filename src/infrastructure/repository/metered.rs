@@ -0,0 +1,162 @@
+// src/infrastructure/repository/metered.rs
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::domain::{Corpus, CorpusId, CorpusSnapshot, Document, DocumentId, Page, PageRequest, Term};
+use crate::infrastructure::metrics::{Metrics, REPOSITORY_PREFIX};
+
+use super::{CorpusRepository, DocumentRepository, RepositoryResult};
+
+/// Run a repository operation, recording its latency as `repository.<kind>.<operation>.seconds`
+fn timed<T>(metrics: &dyn Metrics, kind: &str, operation: &str, f: impl FnOnce() -> T) -> T {
+    let started = Instant::now();
+    let result = f();
+    metrics.record_duration(&format!("{}.{}.{}.seconds", REPOSITORY_PREFIX, kind, operation), started);
+    result
+}
+
+/// Corpus repository decorator recording the latency of every operation
+pub struct MeteredCorpusRepository<R: CorpusRepository> {
+    inner: R,
+    metrics: Arc<dyn Metrics>,
+}
+
+impl<R: CorpusRepository> MeteredCorpusRepository<R> {
+    /// Wrap a repository
+    pub fn new(inner: R, metrics: Arc<dyn Metrics>) -> Self {
+        Self { inner, metrics }
+    }
+
+    /// Get the wrapped repository
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+}
+
+impl<R: CorpusRepository> CorpusRepository for MeteredCorpusRepository<R> {
+    fn find(&self, id: &CorpusId) -> RepositoryResult<Option<Corpus>> {
+        timed(&*self.metrics, "corpus", "find", || self.inner.find(id))
+    }
+
+    fn find_snapshot(&self, id: &CorpusId) -> RepositoryResult<Option<CorpusSnapshot>> {
+        timed(&*self.metrics, "corpus", "find_snapshot", || self.inner.find_snapshot(id))
+    }
+
+    fn exists(&self, id: &CorpusId) -> RepositoryResult<bool> {
+        timed(&*self.metrics, "corpus", "exists", || self.inner.exists(id))
+    }
+
+    fn save(&self, corpus: &Corpus) -> RepositoryResult<()> {
+        timed(&*self.metrics, "corpus", "save", || self.inner.save(corpus))
+    }
+
+    fn delete(&self, id: &CorpusId) -> RepositoryResult<()> {
+        timed(&*self.metrics, "corpus", "delete", || self.inner.delete(id))
+    }
+
+    fn find_all(&self) -> RepositoryResult<Vec<Corpus>> {
+        timed(&*self.metrics, "corpus", "find_all", || self.inner.find_all())
+    }
+
+    fn find_page(&self, request: PageRequest) -> RepositoryResult<Page<Corpus>> {
+        timed(&*self.metrics, "corpus", "find_page", || self.inner.find_page(request))
+    }
+
+    fn find_after(&self, after: Option<&CorpusId>, limit: usize) -> RepositoryResult<Vec<Corpus>> {
+        timed(&*self.metrics, "corpus", "find_after", || self.inner.find_after(after, limit))
+    }
+
+    fn find_ids(&self) -> RepositoryResult<Vec<CorpusId>> {
+        timed(&*self.metrics, "corpus", "find_ids", || self.inner.find_ids())
+    }
+
+    fn count(&self) -> RepositoryResult<usize> {
+        timed(&*self.metrics, "corpus", "count", || self.inner.count())
+    }
+
+    fn find_by_name(&self, name: &str) -> RepositoryResult<Vec<Corpus>> {
+        timed(&*self.metrics, "corpus", "find_by_name", || self.inner.find_by_name(name))
+    }
+}
+
+/// Document repository decorator recording the latency of every operation
+pub struct MeteredDocumentRepository<R: DocumentRepository> {
+    inner: R,
+    metrics: Arc<dyn Metrics>,
+}
+
+impl<R: DocumentRepository> MeteredDocumentRepository<R> {
+    /// Wrap a repository
+    pub fn new(inner: R, metrics: Arc<dyn Metrics>) -> Self {
+        Self { inner, metrics }
+    }
+
+    /// Get the wrapped repository
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+}
+
+impl<R: DocumentRepository> DocumentRepository for MeteredDocumentRepository<R> {
+    fn find(&self, id: &DocumentId) -> RepositoryResult<Option<Arc<Document>>> {
+        timed(&*self.metrics, "document", "find", || self.inner.find(id))
+    }
+
+    fn exists(&self, id: &DocumentId) -> RepositoryResult<bool> {
+        timed(&*self.metrics, "document", "exists", || self.inner.exists(id))
+    }
+
+    fn save(&self, document: &Document) -> RepositoryResult<()> {
+        timed(&*self.metrics, "document", "save", || self.inner.save(document))
+    }
+
+    fn delete(&self, id: &DocumentId) -> RepositoryResult<()> {
+        timed(&*self.metrics, "document", "delete", || self.inner.delete(id))
+    }
+
+    fn find_all(&self) -> RepositoryResult<Vec<Arc<Document>>> {
+        timed(&*self.metrics, "document", "find_all", || self.inner.find_all())
+    }
+
+    fn find_page(&self, request: PageRequest) -> RepositoryResult<Page<Arc<Document>>> {
+        timed(&*self.metrics, "document", "find_page", || self.inner.find_page(request))
+    }
+
+    fn find_after(&self, after: Option<&DocumentId>, limit: usize) -> RepositoryResult<Vec<Arc<Document>>> {
+        timed(&*self.metrics, "document", "find_after", || self.inner.find_after(after, limit))
+    }
+
+    fn find_ids(&self) -> RepositoryResult<Vec<DocumentId>> {
+        timed(&*self.metrics, "document", "find_ids", || self.inner.find_ids())
+    }
+
+    fn count(&self) -> RepositoryResult<usize> {
+        timed(&*self.metrics, "document", "count", || self.inner.count())
+    }
+
+    fn find_by_term(&self, term: &Term) -> RepositoryResult<Vec<Arc<Document>>> {
+        timed(&*self.metrics, "document", "find_by_term", || self.inner.find_by_term(term))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::metrics::InMemoryMetrics;
+    use crate::infrastructure::repository::InMemoryCorpusRepository;
+
+    #[test]
+    fn test_records_latencies() {
+        let metrics = Arc::new(InMemoryMetrics::new());
+        let repository = MeteredCorpusRepository::new(InMemoryCorpusRepository::new(), metrics.clone());
+
+        repository.save(&Corpus::new("corpus1", "Test")).unwrap();
+        assert!(repository.find(&CorpusId::new("corpus1")).unwrap().is_some());
+        assert!(repository.find(&CorpusId::new("missing")).unwrap().is_none());
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.histogram("repository.corpus.find.seconds").unwrap().count, 2);
+        assert_eq!(snapshot.histogram("repository.corpus.save.seconds").unwrap().count, 1);
+    }
+}
@@ -4,9 +4,18 @@
 
 mod document_repository;
 mod corpus_repository;
+mod storage_document_repository;
+mod storage_corpus_repository;
+mod batch_iter;
+mod versioning;
+mod metered;
 
 pub use document_repository::{DocumentRepository, InMemoryDocumentRepository};
 pub use corpus_repository::{CorpusRepository, InMemoryCorpusRepository};
+pub use storage_document_repository::StorageDocumentRepository;
+pub use storage_corpus_repository::{CorpusSummary, StorageCorpusRepository};
+pub use metered::{MeteredCorpusRepository, MeteredDocumentRepository};
+pub use versioning::{CORPUS_SCHEMA_VERSION, DOCUMENT_SCHEMA_VERSION, Migration, MigrationRegistry};
 
 /// Common error type for repository operations
 #[derive(Debug, thiserror::Error)]
@@ -23,6 +32,9 @@ pub enum RepositoryError {
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
     
+    #[error("Schema migration error: {0}")]
+    MigrationError(String),
+    
     #[error("Other repository error: {0}")]
     Other(String),
 }
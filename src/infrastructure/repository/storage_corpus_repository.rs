@@ -0,0 +1,525 @@
+// src/infrastructure/repository/storage_corpus_repository.rs
+
+use std::collections::{BTreeSet, HashMap};
+use std::ops::Bound;
+use std::sync::{Arc, Mutex};
+
+use serde::{Serialize, Deserialize};
+
+use crate::domain::{Corpus, CorpusId, DocumentId, Page, PageRequest};
+use crate::infrastructure::index::IndexSegment;
+use crate::infrastructure::persistence::Storage;
+use super::{CorpusRepository, DocumentRepository, MigrationRegistry, RepositoryError, RepositoryResult};
+use super::document_repository::resolver;
+
+/// The lightweight fields of a stored corpus, readable without loading its documents and index
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CorpusSummary {
+    id: CorpusId,
+    name: String,
+    description: Option<String>,
+    document_count: usize,
+    indexed: bool,
+    revision: u64,
+    metadata: HashMap<String, String>,
+}
+
+impl CorpusSummary {
+    /// Summarize a corpus
+    pub fn of(corpus: &Corpus) -> Self {
+        Self {
+            id: corpus.id().clone(),
+            name: corpus.name().to_string(),
+            description: corpus.description().map(str::to_string),
+            document_count: corpus.document_count(),
+            indexed: corpus.is_indexed(),
+            revision: corpus.revision(),
+            metadata: corpus.metadata().clone(),
+        }
+    }
+
+    /// Get the corpus ID
+    pub fn id(&self) -> &CorpusId {
+        &self.id
+    }
+
+    /// Get the corpus name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the corpus description, if available
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Get the number of documents in the corpus
+    pub fn document_count(&self) -> usize {
+        self.document_count
+    }
+
+    /// Check if the corpus was indexed when saved
+    pub fn is_indexed(&self) -> bool {
+        self.indexed
+    }
+
+    /// Get the corpus revision when saved
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Get the corpus metadata
+    pub fn metadata(&self) -> &HashMap<String, String> {
+        &self.metadata
+    }
+}
+
+/// CorpusRepository that serializes corpora as JSON through any `Storage` backend
+///
+/// Every corpus is stored twice: in full (documents and index data) under
+/// `<prefix>/data/<id>`, and as a small `CorpusSummary` under `<prefix>/summaries/<id>`.
+/// Listing, counting and name lookups read only the summaries, so the heavy
+/// documents and index are loaded only for the corpora actually returned. Full
+/// corpora carry a schema version and are migrated on load by the `MigrationRegistry`.
+///
+/// With a document repository set, corpora store only the IDs and revisions
+/// of their documents, in the same record as the index data, so storage keeps
+/// one copy of each document. A loaded corpus holds its documents by ID and
+/// loads each from that repository on first use, sharing the repository's
+/// copy; a document deleted from the repository stays in the corpus until it
+/// is removed. Documents added to a corpus must already be in the repository
+/// when the corpus is saved.
+///
+/// With index segments enabled, every indexed corpus is also written as a
+/// compressed `IndexSegment` under `<prefix>/segments/<id>`, which
+/// `find_segment` opens for queries without loading the corpus.
+pub struct StorageCorpusRepository<S: Storage> {
+    storage: S,
+
+    /// Versions and migrates full corpus payloads
+    migrations: MigrationRegistry,
+
+    /// Repository corpus documents are stored in and resolved from (None = store them with the corpus)
+    documents: Option<Arc<dyn DocumentRepository>>,
+
+    /// Key prefix of the corpus entries
+    prefix: String,
+
+    /// Whether indexed corpora are also stored as index segments
+    index_segments: bool,
+
+    /// Serializes read-modify-write updates of the ID index
+    index_lock: Mutex<()>,
+}
+
+impl<S: Storage> StorageCorpusRepository<S> {
+    /// Default key prefix of the corpus entries
+    pub const DEFAULT_PREFIX: &'static str = "corpora";
+
+    /// Create a repository storing corpora under the default prefix
+    pub fn new(storage: S) -> Self {
+        Self::with_prefix(storage, Self::DEFAULT_PREFIX)
+    }
+
+    /// Create a repository storing corpora under a custom prefix
+    pub fn with_prefix(storage: S, prefix: impl Into<String>) -> Self {
+        Self {
+            storage,
+            migrations: MigrationRegistry::for_corpora(),
+            documents: None,
+            prefix: prefix.into(),
+            index_segments: false,
+            index_lock: Mutex::new(()),
+        }
+    }
+
+    /// Get the underlying storage
+    pub fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    /// Replace the registry used to version and migrate corpus payloads
+    pub fn set_migrations(&mut self, migrations: MigrationRegistry) {
+        self.migrations = migrations;
+    }
+
+    /// Store corpus documents by reference to a document repository (None = store them with the corpus)
+    ///
+    /// Saving a corpus with an added document missing from the repository fails.
+    /// Corpora saved before the repository was set load as they were.
+    pub fn set_document_repository(&mut self, documents: Option<Arc<dyn DocumentRepository>>) {
+        self.documents = documents;
+    }
+
+    /// Store indexed corpora as compressed index segments too, readable with `find_segment`
+    ///
+    /// Corpora saved afterwards get a segment when indexed and lose it when not.
+    pub fn set_index_segments(&mut self, index_segments: bool) {
+        self.index_segments = index_segments;
+    }
+
+    /// Open the index segment stored with a corpus, if it has one
+    ///
+    /// Only the segment's dictionary is read here; its postings lists are read
+    /// from storage as queries need them.
+    pub fn find_segment(&self, id: &CorpusId) -> RepositoryResult<Option<IndexSegment<'_>>> {
+        let key = self.segment_key(id.value());
+        IndexSegment::open(&self.storage, &key).map_err(|e| {
+            RepositoryError::PersistenceError(format!("Error loading '{}': {}", key, e))
+        })
+    }
+
+    fn data_key(&self, id: &str) -> String {
+        format!("{}/data/{}", self.prefix, id)
+    }
+
+    fn segment_key(&self, id: &str) -> String {
+        format!("{}/segments/{}", self.prefix, id)
+    }
+
+    fn summary_key(&self, id: &str) -> String {
+        format!("{}/summaries/{}", self.prefix, id)
+    }
+
+    fn index_key(&self) -> String {
+        format!("{}.index", self.prefix)
+    }
+
+    fn load_bytes(&self, key: &str) -> RepositoryResult<Option<Vec<u8>>> {
+        self.storage.load(key).map_err(|e| {
+            RepositoryError::PersistenceError(format!("Error loading '{}': {}", key, e))
+        })
+    }
+
+    fn save_bytes(&self, key: &str, data: &[u8]) -> RepositoryResult<()> {
+        self.storage.save(key, data).map_err(|e| {
+            RepositoryError::PersistenceError(format!("Error saving '{}': {}", key, e))
+        })
+    }
+
+    fn load_json<T: for<'de> Deserialize<'de>>(&self, key: &str) -> RepositoryResult<Option<T>> {
+        self.load_bytes(key)?
+            .map(|data| serde_json::from_slice(&data).map_err(RepositoryError::from))
+            .transpose()
+    }
+
+    fn save_json<T: Serialize>(&self, key: &str, value: &T) -> RepositoryResult<()> {
+        self.save_bytes(key, &serde_json::to_vec(value)?)
+    }
+
+    fn delete_key(&self, key: &str) -> RepositoryResult<()> {
+        self.storage.delete(key).map_err(|e| {
+            RepositoryError::PersistenceError(format!("Error deleting '{}': {}", key, e))
+        })
+    }
+
+    fn exists_key(&self, key: &str) -> RepositoryResult<bool> {
+        self.storage.exists(key).map_err(|e| {
+            RepositoryError::PersistenceError(format!("Error checking '{}': {}", key, e))
+        })
+    }
+
+    /// Load the sorted corpus IDs
+    fn load_index(&self) -> RepositoryResult<BTreeSet<String>> {
+        Ok(self.load_json(&self.index_key())?.unwrap_or_default())
+    }
+
+    /// Apply a change to the corpus IDs and store them
+    fn update_index(&self, update: impl FnOnce(&mut BTreeSet<String>) -> bool) -> RepositoryResult<()> {
+        let _guard = self.index_lock.lock().map_err(|e| RepositoryError::Other(format!("Lock error: {}", e)))?;
+
+        let mut index = self.load_index()?;
+        if update(&mut index) {
+            self.save_json(&self.index_key(), &index)?;
+        }
+        Ok(())
+    }
+
+    /// Load the summary of a corpus without its documents and index
+    pub fn find_summary(&self, id: &CorpusId) -> RepositoryResult<Option<CorpusSummary>> {
+        self.load_json(&self.summary_key(id.value()))
+    }
+
+    /// Load the summaries of all corpora, ordered by ID
+    pub fn find_all_summaries(&self) -> RepositoryResult<Vec<CorpusSummary>> {
+        let mut summaries = Vec::new();
+        for id in self.load_index()? {
+            if let Some(summary) = self.find_summary(&CorpusId::new(id))? {
+                summaries.push(summary);
+            }
+        }
+        Ok(summaries)
+    }
+
+    /// Load the full corpora for a set of IDs, skipping any that vanished since the index was read
+    fn load_corpora<'a>(&self, ids: impl IntoIterator<Item = &'a CorpusId>) -> RepositoryResult<Vec<Corpus>> {
+        let mut corpora = Vec::new();
+        for id in ids {
+            if let Some(corpus) = self.find(id)? {
+                corpora.push(corpus);
+            }
+        }
+        Ok(corpora)
+    }
+}
+
+/// The stored form of a corpus: its data, plus the IDs and revisions of its documents when they are stored by reference
+///
+/// Keeping both in one record lets a single write replace them together.
+#[derive(Serialize, Deserialize)]
+struct CorpusRecord<C> {
+    #[serde(flatten)]
+    corpus: C,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    members: Option<Vec<(DocumentId, u64)>>,
+}
+
+impl<S: Storage> CorpusRepository for StorageCorpusRepository<S> {
+    fn find(&self, id: &CorpusId) -> RepositoryResult<Option<Corpus>> {
+        let Some(CorpusRecord { mut corpus, members }) = self.load_bytes(&self.data_key(id.value()))?
+            .map(|data| self.migrations.decode::<CorpusRecord<Corpus>>(&data))
+            .transpose()? else {
+            return Ok(None);
+        };
+
+        if let Some(members) = members {
+            let documents = self.documents.as_ref().ok_or_else(|| RepositoryError::PersistenceError(
+                format!("Corpus '{}' stores its documents by reference, but no document repository is set", id.value())
+            ))?;
+            corpus.attach_documents(&members, resolver(documents.clone()));
+        }
+        Ok(Some(corpus))
+    }
+
+    fn exists(&self, id: &CorpusId) -> RepositoryResult<bool> {
+        self.storage.exists(&self.summary_key(id.value())).map_err(|e| {
+            RepositoryError::PersistenceError(format!("Error checking corpus '{}': {}", id.value(), e))
+        })
+    }
+
+    fn save(&self, corpus: &Corpus) -> RepositoryResult<()> {
+        let id = corpus.id().value();
+        let record = match &self.documents {
+            Some(documents) => {
+                // Documents loaded from the repository are there already; only those added since need checking
+                for document_id in corpus.document_ids().filter(|id| corpus.is_document_loaded(id)) {
+                    if !documents.exists(document_id)? {
+                        return Err(RepositoryError::PersistenceError(format!(
+                            "Document '{}' of corpus '{}' is not in the document repository", document_id.value(), id
+                        )));
+                    }
+                }
+                let (detached, members) = corpus.detach_documents();
+                self.migrations.encode(&CorpusRecord { corpus: &detached, members: Some(members) })?
+            },
+            None => self.migrations.encode(&CorpusRecord { corpus, members: None })?,
+        };
+        self.save_bytes(&self.data_key(id), &record)?;
+        if self.index_segments && corpus.is_indexed() {
+            let key = self.segment_key(id);
+            IndexSegment::from_corpus(corpus).and_then(|segment| segment.save(&self.storage, &key)).map_err(|e| {
+                RepositoryError::PersistenceError(format!("Error saving '{}': {}", key, e))
+            })?;
+        } else if self.exists_key(&self.segment_key(id))? {
+            self.delete_key(&self.segment_key(id))?;
+        }
+        self.save_json(&self.summary_key(id), &CorpusSummary::of(corpus))?;
+
+        self.update_index(|index| index.insert(id.to_string()))
+    }
+
+    fn delete(&self, id: &CorpusId) -> RepositoryResult<()> {
+        self.delete_key(&self.summary_key(id.value()))?;
+        self.delete_key(&self.data_key(id.value()))?;
+        if self.exists_key(&self.segment_key(id.value()))? {
+            self.delete_key(&self.segment_key(id.value()))?;
+        }
+
+        self.update_index(|index| index.remove(id.value()))
+    }
+
+    fn find_all(&self) -> RepositoryResult<Vec<Corpus>> {
+        let ids: Vec<CorpusId> = self.load_index()?.into_iter().map(CorpusId::new).collect();
+        self.load_corpora(&ids)
+    }
+
+    fn find_page(&self, request: PageRequest) -> RepositoryResult<Page<Corpus>> {
+        let index = self.load_index()?;
+        let ids: Vec<CorpusId> = index.iter()
+            .skip(request.offset())
+            .take(request.limit())
+            .map(CorpusId::new)
+            .collect();
+
+        Ok(Page::new(self.load_corpora(&ids)?, request, index.len()))
+    }
+
+    fn find_after(&self, after: Option<&CorpusId>, limit: usize) -> RepositoryResult<Vec<Corpus>> {
+        let index = self.load_index()?;
+        let ids = match after {
+            Some(after) => index.range::<str, _>((Bound::Excluded(after.value()), Bound::Unbounded)),
+            None => index.range::<str, _>(..),
+        };
+        let ids: Vec<CorpusId> = ids.take(limit).map(CorpusId::new).collect();
+
+        self.load_corpora(&ids)
+    }
+
+    fn find_ids(&self) -> RepositoryResult<Vec<CorpusId>> {
+        Ok(self.load_index()?.into_iter().map(CorpusId::new).collect())
+    }
+
+    fn count(&self) -> RepositoryResult<usize> {
+        Ok(self.load_index()?.len())
+    }
+
+    fn find_by_name(&self, name: &str) -> RepositoryResult<Vec<Corpus>> {
+        let name = name.to_lowercase();
+        let ids: Vec<CorpusId> = self.find_all_summaries()?
+            .into_iter()
+            .filter(|summary| summary.name().to_lowercase().contains(&name))
+            .map(|summary| summary.id)
+            .collect();
+
+        self.load_corpora(&ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Document, Term};
+    use crate::infrastructure::persistence::InMemoryStorage;
+    use crate::infrastructure::repository::InMemoryDocumentRepository;
+
+    #[test]
+    fn test_storage_round_trip() {
+        let repo = StorageCorpusRepository::new(InMemoryStorage::new());
+
+        let mut corpus = Corpus::new("corpus1", "Rust Articles");
+        let mut doc = Document::new("doc1", "rust rust");
+        doc.add_terms(["rust", "rust"].map(Term::new));
+        corpus.add_document(doc).unwrap();
+        corpus.add_stopword("the");
+        corpus.build_index().unwrap();
+        repo.save(&corpus).unwrap();
+        repo.save(&Corpus::new("corpus2", "Go Articles")).unwrap();
+
+        let found = repo.find(&CorpusId::new("corpus1")).unwrap().unwrap();
+        assert!(found.contains_document(&DocumentId::new("doc1")));
+        assert_eq!(found.document_frequency(&Term::new("rust")), 1);
+        assert_eq!(found.collection_frequency(&Term::new("rust")), 2);
+        assert!(found.is_stopword("the"));
+        assert!(!found.has_stale_index().unwrap());
+
+        let summary = repo.find_summary(&CorpusId::new("corpus1")).unwrap().unwrap();
+        assert_eq!(summary.document_count(), 1);
+        assert!(summary.is_indexed());
+
+        assert_eq!(repo.find_by_name("rust").unwrap().len(), 1);
+        assert_eq!(repo.count().unwrap(), 2);
+        assert_eq!(repo.find_page(PageRequest::new(1, 5)).unwrap().items()[0].id().value(), "corpus2");
+        assert_eq!(repo.find_after(Some(&CorpusId::new("corpus1")), 5).unwrap().len(), 1);
+
+        repo.delete(&CorpusId::new("corpus2")).unwrap();
+        assert!(!repo.exists(&CorpusId::new("corpus2")).unwrap());
+        assert_eq!(repo.find_all_summaries().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_index_segments() {
+        let mut repo = StorageCorpusRepository::new(InMemoryStorage::new());
+        repo.set_index_segments(true);
+
+        let mut corpus = Corpus::new("corpus1", "Rust Articles");
+        for (id, text) in [("doc1", "rust is fast"), ("doc2", "go is simple")] {
+            let mut doc = Document::new(id, text);
+            doc.add_terms(text.split_whitespace().map(Term::new));
+            corpus.add_document(doc).unwrap();
+        }
+        repo.save(&corpus).unwrap();
+        assert!(repo.find_segment(corpus.id()).unwrap().is_none());
+
+        corpus.build_index().unwrap();
+        repo.save(&corpus).unwrap();
+        let segment = repo.find_segment(corpus.id()).unwrap().unwrap();
+        assert_eq!(segment.document_frequency(&Term::new("is")), 2);
+        assert_eq!(segment.matching_documents(&[Term::new("rust")]).unwrap(), vec![DocumentId::new("doc1")]);
+
+        repo.delete(corpus.id()).unwrap();
+        assert!(repo.find_segment(corpus.id()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_documents_by_reference() {
+        let documents = Arc::new(InMemoryDocumentRepository::new());
+        let mut repo = StorageCorpusRepository::new(InMemoryStorage::new());
+        repo.set_document_repository(Some(documents.clone()));
+
+        let mut corpus = Corpus::new("corpus1", "Rust Articles");
+        let mut doc = Document::new("doc1", "rust code");
+        doc.add_terms(["rust", "code"].map(Term::new));
+        corpus.add_document(doc.clone()).unwrap();
+        corpus.build_index().unwrap();
+
+        // Saving never writes documents, so they must be stored first
+        assert!(repo.save(&corpus).is_err());
+        assert!(!documents.exists(&DocumentId::new("doc1")).unwrap());
+        documents.save(&doc).unwrap();
+        repo.save(&corpus).unwrap();
+
+        // The document lives in the document repository only, and the loaded corpus shares its copy
+        let stored = String::from_utf8(repo.storage().load("corpora/data/corpus1").unwrap().unwrap()).unwrap();
+        assert!(!stored.contains("rust code"));
+        assert_eq!(repo.storage().list_keys().unwrap().iter().filter(|key| key.contains("corpus1")).count(), 2);
+        let found = repo.find(&CorpusId::new("corpus1")).unwrap().unwrap();
+        let shared = documents.find(&DocumentId::new("doc1")).unwrap().unwrap();
+        assert!(Arc::ptr_eq(found.get_shared_document(&DocumentId::new("doc1")).unwrap().unwrap(), &shared));
+
+        // Edits made through the document repository show up in the loaded corpus, but
+        let mut edited = Arc::unwrap_or_clone(documents.find(&DocumentId::new("doc1")).unwrap().unwrap());
+        edited.set_content("go code");
+        edited.clear_terms();
+        edited.add_terms(["go", "code"].map(Term::new));
+        documents.save(&edited).unwrap();
+
+        // until the corpus copy is replaced, the index describes the revision it was added at
+        let mut found = repo.find(&CorpusId::new("corpus1")).unwrap().unwrap();
+        assert_eq!(found.get_document(&DocumentId::new("doc1")).unwrap().unwrap().content(), "go code");
+        assert!(found.has_outdated_copy(&edited));
+        assert_eq!(found.document_frequency(&Term::new("rust")), 1);
+        found.replace_document(edited).unwrap();
+        repo.save(&found).unwrap();
+        let found = repo.find(&CorpusId::new("corpus1")).unwrap().unwrap();
+        assert_eq!(found.document_frequency(&Term::new("go")), 1);
+        assert_eq!(found.document_frequency(&Term::new("rust")), 0);
+
+        // A document deleted from the repository stays in the corpus, reported as missing
+        documents.delete(&DocumentId::new("doc1")).unwrap();
+        let found = repo.find(&CorpusId::new("corpus1")).unwrap().unwrap();
+        assert!(found.contains_document(&DocumentId::new("doc1")));
+        assert_eq!(found.load_documents().unwrap(), vec![DocumentId::new("doc1")]);
+        repo.save(&found).unwrap();
+        assert_eq!(repo.find(&CorpusId::new("corpus1")).unwrap().unwrap().document_count(), 1);
+
+        repo.set_document_repository(None);
+        assert!(repo.find(&CorpusId::new("corpus1")).is_err());
+    }
+
+    #[test]
+    fn test_migrates_older_payloads() {
+        let mut repo = StorageCorpusRepository::new(InMemoryStorage::new());
+        repo.save(&Corpus::new("corpus1", "Rust Articles")).unwrap();
+
+        let mut migrations = MigrationRegistry::new(2);
+        migrations.register(1, |mut data| {
+            data["description"] = serde_json::Value::from("migrated");
+            Ok(data)
+        });
+        repo.set_migrations(migrations);
+
+        let found = repo.find(&CorpusId::new("corpus1")).unwrap().unwrap();
+        assert_eq!(found.description(), Some("migrated"));
+    }
+}
@@ -0,0 +1,210 @@
+// src/infrastructure/repository/storage_document_repository.rs
+
+use std::collections::BTreeSet;
+use std::ops::Bound;
+use std::sync::{Arc, Mutex};
+
+use crate::domain::{Document, DocumentId, Page, PageRequest, Term};
+use crate::infrastructure::persistence::Storage;
+use super::{DocumentRepository, MigrationRegistry, RepositoryError, RepositoryResult};
+
+/// DocumentRepository that serializes documents as JSON through any `Storage` backend
+///
+/// Each document is stored under `<prefix>/<id>`, and the sorted list of IDs under
+/// `<prefix>.index`, so listing and counting never scan the whole store. Documents
+/// carry a schema version and are migrated on load by the `MigrationRegistry`.
+pub struct StorageDocumentRepository<S: Storage> {
+    storage: S,
+
+    /// Versions and migrates document payloads
+    migrations: MigrationRegistry,
+
+    /// Key prefix of the document entries
+    prefix: String,
+
+    /// Serializes read-modify-write updates of the ID index
+    index_lock: Mutex<()>,
+}
+
+impl<S: Storage> StorageDocumentRepository<S> {
+    /// Default key prefix of the document entries
+    pub const DEFAULT_PREFIX: &'static str = "documents";
+
+    /// Create a repository storing documents under the default prefix
+    pub fn new(storage: S) -> Self {
+        Self::with_prefix(storage, Self::DEFAULT_PREFIX)
+    }
+
+    /// Create a repository storing documents under a custom prefix
+    pub fn with_prefix(storage: S, prefix: impl Into<String>) -> Self {
+        Self {
+            storage,
+            migrations: MigrationRegistry::for_documents(),
+            prefix: prefix.into(),
+            index_lock: Mutex::new(()),
+        }
+    }
+
+    /// Get the underlying storage
+    pub fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    /// Replace the registry used to version and migrate document payloads
+    pub fn set_migrations(&mut self, migrations: MigrationRegistry) {
+        self.migrations = migrations;
+    }
+
+    fn document_key(&self, id: &str) -> String {
+        format!("{}/{}", self.prefix, id)
+    }
+
+    fn index_key(&self) -> String {
+        format!("{}.index", self.prefix)
+    }
+
+    /// Load the sorted document IDs
+    fn load_index(&self) -> RepositoryResult<BTreeSet<String>> {
+        let data = self.storage.load(&self.index_key()).map_err(|e| {
+            RepositoryError::PersistenceError(format!("Error loading document index: {}", e))
+        })?;
+
+        match data {
+            Some(data) => Ok(serde_json::from_slice(&data)?),
+            None => Ok(BTreeSet::new()),
+        }
+    }
+
+    /// Apply a change to the document IDs and store them
+    fn update_index(&self, update: impl FnOnce(&mut BTreeSet<String>) -> bool) -> RepositoryResult<()> {
+        let _guard = self.index_lock.lock().map_err(|e| RepositoryError::Other(format!("Lock error {}", e)))?;
+
+        let mut index = self.load_index()?;
+        if update(&mut index) {
+            self.storage.save(&self.index_key(), &serde_json::to_vec(&index)?).map_err(|e| {
+                RepositoryError::PersistenceError(format!("Error saving document index: {}", e))
+            })?;
+        }
+        Ok(())
+    }
+
+    fn load_document(&self, id: &str) -> RepositoryResult<Option<Arc<Document>>> {
+        let data = self.storage.load(&self.document_key(id)).map_err(|e| {
+            RepositoryError::PersistenceError(format!("Error loading document '{}': {}", id, e))
+        })?;
+
+        data.map(|data| self.migrations.decode(&data).map(Arc::new)).transpose()
+    }
+
+    /// Load the documents with the given IDs, skipping any that vanished since the index was read
+    fn load_documents<'a>(&self, ids: impl IntoIterator<Item = &'a String>) -> RepositoryResult<Vec<Arc<Document>>> {
+        let mut documents = Vec::new();
+        for id in ids {
+            if let Some(document) = self.load_document(id)? {
+                documents.push(document);
+            }
+        }
+        Ok(documents)
+    }
+}
+
+impl<S: Storage> DocumentRepository for StorageDocumentRepository<S> {
+    fn find(&self, id: &DocumentId) -> RepositoryResult<Option<Arc<Document>>> {
+        self.load_document(id.value())
+    }
+
+    fn exists(&self, id: &DocumentId) -> RepositoryResult<bool> {
+        self.storage.exists(&self.document_key(id.value())).map_err(|e| {
+            RepositoryError::PersistenceError(format!("Error checking document '{}': {}", id.value(), e))
+        })
+    }
+
+    fn save(&self, document: &Document) -> RepositoryResult<()> {
+        let id = document.id().value();
+        self.storage.save(&self.document_key(id), &self.migrations.encode(document)?).map_err(|e| {
+            RepositoryError::PersistenceError(format!("Error saving document '{}': {}", id, e))
+        })?;
+
+        self.update_index(|index| index.insert(id.to_string()))
+    }
+
+    fn delete(&self, id: &DocumentId) -> RepositoryResult<()> {
+        self.storage.delete(&self.document_key(id.value())).map_err(|e| {
+            RepositoryError::PersistenceError(format!("Error deleting document '{}': {}", id.value(), e))
+        })?;
+
+        self.update_index(|index| index.remove(id.value()))
+    }
+
+    fn find_all(&self) -> RepositoryResult<Vec<Arc<Document>>> {
+        self.load_documents(&self.load_index()?)
+    }
+
+    fn find_page(&self, request: PageRequest) -> RepositoryResult<Page<Arc<Document>>> {
+        let index = self.load_index()?;
+        let items = self.load_documents(index.iter().skip(request.offset()).take(request.limit()))?;
+
+        Ok(Page::new(items, request, index.len()))
+    }
+
+    fn find_after(&self, after: Option<&DocumentId>, limit: usize) -> RepositoryResult<Vec<Arc<Document>>> {
+        let index = self.load_index()?;
+        let ids = match after {
+            Some(after) => index.range::<str, _>((Bound::Excluded(after.value()), Bound::Unbounded)),
+            None => index.range::<str, _>(..),
+        };
+
+        self.load_documents(ids.take(limit))
+    }
+
+    fn find_ids(&self) -> RepositoryResult<Vec<DocumentId>> {
+        Ok(self.load_index()?.into_iter().map(DocumentId::new).collect())
+    }
+
+    fn count(&self) -> RepositoryResult<usize> {
+        Ok(self.load_index()?.len())
+    }
+
+    fn find_by_term(&self, term: &Term) -> RepositoryResult<Vec<Arc<Document>>> {
+        Ok(self.find_all()?
+            .into_iter()
+            .filter(|doc| doc.term_frequencies().contains_key(term))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::persistence::InMemoryStorage;
+
+    #[test]
+    fn test_storage_round_trip() {
+        let repo = StorageDocumentRepository::new(InMemoryStorage::new());
+
+        let mut doc = Document::with_title("doc2", "Guide", "rust rust go");
+        doc.add_terms(["rust", "rust", "go"].map(Term::new));
+        doc.add_field_term(Document::TITLE_FIELD, Term::new("guide"));
+        repo.save(&doc).unwrap();
+        repo.save(&Document::new("doc1", "First document")).unwrap();
+
+        let found = repo.find(&DocumentId::new("doc2")).unwrap().unwrap();
+        assert_eq!(found.term_frequency(&Term::new("rust")).value(), 2);
+        assert_eq!(found.term_positions(&Term::new("go")), &[2]);
+        assert_eq!(found.field_term_frequency(Document::TITLE_FIELD, &Term::new("guide")).value(), 1);
+        assert_eq!(repo.find_by_term(&Term::new("go")).unwrap().len(), 1);
+
+        // Pages follow the sorted ID index
+        let page = repo.find_page(PageRequest::first(1)).unwrap();
+        assert_eq!(page.items()[0].id().value(), "doc1");
+        assert_eq!(page.total(), 2);
+        let after = repo.find_after(Some(page.items()[0].id()), 10).unwrap();
+        assert_eq!(after.len(), 1);
+        assert_eq!(after[0].id().value(), "doc2");
+
+        repo.delete(&DocumentId::new("doc1")).unwrap();
+        assert!(!repo.exists(&DocumentId::new("doc1")).unwrap());
+        assert_eq!(repo.count().unwrap(), 1);
+        assert_eq!(repo.storage().list_keys().unwrap().len(), 2);
+    }
+}
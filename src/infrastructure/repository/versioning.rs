@@ -0,0 +1,160 @@
+// src/infrastructure/repository/versioning.rs
+
+use std::collections::BTreeMap;
+
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::{Map, Value};
+
+use super::{RepositoryError, RepositoryResult};
+
+/// Schema version of `Document` payloads written by this crate version
+pub const DOCUMENT_SCHEMA_VERSION: u32 = 1;
+
+/// Schema version of `Corpus` payloads written by this crate version
+pub const CORPUS_SCHEMA_VERSION: u32 = 1;
+
+/// Rewrites a payload from one schema version to the next
+pub type Migration = Box<dyn Fn(Value) -> Result<Value, String> + Send + Sync>;
+
+/// Upgrades stored payloads written by older crate versions
+///
+/// Payloads are stored as `{"schema_version": N, "data": ...}`. Bare payloads
+/// written before versioning existed are treated as version 0. On load, the
+/// migrations registered for each version between the stored one and the current
+/// one are applied in order before deserializing.
+pub struct MigrationRegistry {
+    current_version: u32,
+
+    /// Migration from each version to the next, keyed by the source version
+    migrations: BTreeMap<u32, Migration>,
+}
+
+impl MigrationRegistry {
+    /// Create a registry writing the given version, with no migrations
+    pub fn new(current_version: u32) -> Self {
+        Self {
+            current_version,
+            migrations: BTreeMap::new(),
+        }
+    }
+
+    /// Create the registry for `Document` payloads, with the built-in migrations
+    pub fn for_documents() -> Self {
+        let mut registry = Self::new(DOCUMENT_SCHEMA_VERSION);
+        // Unversioned payloads have the same shape as version 1
+        registry.register(0, Ok);
+        registry
+    }
+
+    /// Create the registry for `Corpus` payloads, with the built-in migrations
+    pub fn for_corpora() -> Self {
+        let mut registry = Self::new(CORPUS_SCHEMA_VERSION);
+        registry.register(0, Ok);
+        registry
+    }
+
+    /// Get the version new payloads are written with
+    pub fn current_version(&self) -> u32 {
+        self.current_version
+    }
+
+    /// Register the migration from `from_version` to `from_version + 1`, replacing any existing one
+    pub fn register<F>(&mut self, from_version: u32, migration: F)
+    where
+        F: Fn(Value) -> Result<Value, String> + Send + Sync + 'static,
+    {
+        self.migrations.insert(from_version, Box::new(migration));
+    }
+
+    /// Serialize a value wrapped with the current schema version
+    pub fn encode<T: Serialize>(&self, value: &T) -> RepositoryResult<Vec<u8>> {
+        let mut envelope = Map::new();
+        envelope.insert("schema_version".to_string(), Value::from(self.current_version));
+        envelope.insert("data".to_string(), serde_json::to_value(value)?);
+
+        Ok(serde_json::to_vec(&envelope)?)
+    }
+
+    /// Deserialize a payload of any supported version, migrating it first if needed
+    pub fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> RepositoryResult<T> {
+        let (version, data) = split_envelope(serde_json::from_slice(bytes)?)?;
+        Ok(serde_json::from_value(self.migrate(data, version)?)?)
+    }
+
+    /// Upgrade a payload from the given version to the current one
+    pub fn migrate(&self, mut data: Value, version: u32) -> RepositoryResult<Value> {
+        if version > self.current_version {
+            return Err(RepositoryError::MigrationError(format!(
+                "Schema version {} is newer than the supported version {}",
+                version, self.current_version
+            )));
+        }
+
+        for from in version..self.current_version {
+            let migration = self.migrations.get(&from).ok_or_else(|| {
+                RepositoryError::MigrationError(format!("No migration from schema version {}", from))
+            })?;
+            data = migration(data).map_err(|e| {
+                RepositoryError::MigrationError(format!("Migration from schema version {} failed: {}", from, e))
+            })?;
+        }
+        Ok(data)
+    }
+}
+
+/// Split a stored payload into its schema version and data
+fn split_envelope(value: Value) -> RepositoryResult<(u32, Value)> {
+    match value {
+        Value::Object(mut map) if map.contains_key("schema_version") && map.contains_key("data") => {
+            let version = map["schema_version"]
+                .as_u64()
+                .and_then(|version| u32::try_from(version).ok())
+                .ok_or_else(|| RepositoryError::MigrationError("Invalid schema version".to_string()))?;
+            Ok((version, map.remove("data").unwrap_or_default()))
+        },
+        value => Ok((0, value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Document;
+
+    #[test]
+    fn test_round_trip_and_legacy_payload() {
+        let registry = MigrationRegistry::for_documents();
+        let document = Document::with_title("doc1", "Title", "content");
+
+        let encoded = registry.encode(&document).unwrap();
+        let value: Value = serde_json::from_slice(&encoded).unwrap();
+        assert_eq!(value["schema_version"], DOCUMENT_SCHEMA_VERSION);
+        let decoded: Document = registry.decode(&encoded).unwrap();
+        assert_eq!(decoded.id(), document.id());
+
+        let legacy = serde_json::to_vec(&document).unwrap();
+        let decoded: Document = registry.decode(&legacy).unwrap();
+        assert_eq!(decoded.content(), "content");
+    }
+
+    #[test]
+    fn test_migration_chain() {
+        let mut registry = MigrationRegistry::new(3);
+        registry.register(1, |mut data| {
+            data["label"] = data["name"].take();
+            Ok(data)
+        });
+        registry.register(2, |mut data| {
+            data["label"] = Value::from(data["label"].as_str().ok_or("missing label")?.to_uppercase());
+            Ok(data)
+        });
+
+        let old = br#"{"schema_version":1,"data":{"name":"news"}}"#;
+        let migrated: Value = registry.decode(old).unwrap();
+        assert_eq!(migrated["label"], "NEWS");
+
+        let newer = br#"{"schema_version":4,"data":{}}"#;
+        assert!(matches!(registry.decode::<Value>(newer), Err(RepositoryError::MigrationError(_))));
+        assert!(matches!(registry.decode::<Value>(b"{}"), Err(RepositoryError::MigrationError(_))));
+    }
+}
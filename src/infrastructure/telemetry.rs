@@ -0,0 +1,115 @@
+// src/infrastructure/telemetry.rs
+
+//! Targets and subscribers for the `tracing` spans and events of the engine.
+//!
+//! Searches, index builds, document analysis and the vector cache are traced
+//! with `tracing` under the targets below, so any `tracing` subscriber can
+//! record them. Nothing is recorded until one is installed, globally with
+//! `tracing::subscriber::set_global_default` or for a scope with
+//! `tracing::subscriber::set_default`, whose guard restores the previous
+//! subscriber when dropped. With the `subscriber` feature, `subscriber` and
+//! `stderr_subscriber` build a ready-made one.
+
+#[cfg(feature = "subscriber")]
+use tracing::Subscriber;
+#[cfg(feature = "subscriber")]
+use tracing_subscriber::fmt::format::FmtSpan;
+#[cfg(feature = "subscriber")]
+use tracing_subscriber::fmt::MakeWriter;
+#[cfg(feature = "subscriber")]
+use tracing_subscriber::EnvFilter;
+
+/// Target of search spans
+pub const SEARCH: &str = "tf_idf::search";
+
+/// Target of corpus indexing spans
+pub const INDEX: &str = "tf_idf::index";
+
+/// Target of document analysis spans
+pub const DOCUMENT: &str = "tf_idf::document";
+
+/// Target of vector cache events
+pub const CACHE: &str = "tf_idf::cache";
+
+/// Build a subscriber writing one line per event and closed span, with its duration, to a writer
+///
+/// `filter` takes `EnvFilter` directives such as `debug` or `tf_idf::search=trace`.
+#[cfg(feature = "subscriber")]
+pub fn subscriber<W>(filter: &str, writer: W) -> Result<impl Subscriber + Send + Sync, String>
+where
+    W: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
+    let filter = EnvFilter::try_new(filter).map_err(|e| format!("Invalid log filter '{}': {}", filter, e))?;
+
+    Ok(tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_writer(writer)
+        .with_ansi(false)
+        .finish())
+}
+
+/// Build a subscriber writing to standard error, as `subscriber` does
+#[cfg(feature = "subscriber")]
+pub fn stderr_subscriber(filter: &str) -> Result<impl Subscriber + Send + Sync, String> {
+    subscriber(filter, std::io::stderr)
+}
+
+#[cfg(all(test, feature = "subscriber"))]
+pub(crate) mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    /// Keeps formatted output for assertions
+    #[derive(Clone, Default)]
+    pub(crate) struct CollectingWriter(Arc<Mutex<Vec<u8>>>);
+
+    impl CollectingWriter {
+        /// Get the lines written so far
+        pub(crate) fn lines(&self) -> Vec<String> {
+            String::from_utf8_lossy(&self.0.lock().unwrap()).lines().map(str::to_string).collect()
+        }
+    }
+
+    impl Write for CollectingWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for CollectingWriter {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[test]
+    fn test_events_and_spans() {
+        let writer = CollectingWriter::default();
+        let subscriber = subscriber("debug", writer.clone()).unwrap();
+
+        {
+            let _guard = tracing::subscriber::set_default(subscriber);
+            tracing::info!(target: SEARCH, hits = 3, "searched");
+            tracing::trace!(target: SEARCH, "ignored");
+
+            let span = tracing::debug_span!(target: INDEX, "build_index", documents = tracing::field::Empty).entered();
+            span.record("documents", 10);
+        }
+        tracing::info!(target: SEARCH, "outside the scope");
+
+        let lines = writer.lines();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("tf_idf::search: searched hits=3"));
+        assert!(lines[1].contains("build_index{documents=10}"));
+        assert!(lines[1].contains("time.busy="));
+        assert!(super::subscriber("not a [filter", writer).is_err());
+    }
+}
@@ -0,0 +1,119 @@
+use crate::domain::Token;
+
+use super::{CaseFolding, Tokenizer};
+
+/// A step in an analysis pipeline that rewrites, drops or adds tokens
+pub trait TokenFilter: Send + Sync {
+    fn filter(&self, tokens: Vec<Token>) -> Vec<Token>;
+
+    /// How this filter changes token case (by default it leaves case alone)
+    fn case_folding(&self) -> CaseFolding {
+        CaseFolding::Preserve
+    }
+}
+
+/// A tokenizer followed by an ordered chain of token filters
+///
+/// An analyzer is itself a `Tokenizer`, so it can be handed to the document and
+/// search services wherever a tokenizer is expected.
+pub struct Analyzer {
+    tokenizer: Box<dyn Tokenizer>,
+    filters: Vec<Box<dyn TokenFilter>>
+}
+
+impl Analyzer {
+    /// Create an analyzer with no filters
+    pub fn new(tokenizer: impl Tokenizer + 'static) -> Self {
+        Self {
+            tokenizer: Box::new(tokenizer),
+            filters: Vec::new()
+        }
+    }
+
+    /// Append a filter to the end of the chain
+    pub fn with_filter(mut self, filter: impl TokenFilter + 'static) -> Self {
+        self.filters.push(Box::new(filter));
+        self
+    }
+
+    /// Get the number of filters in the chain
+    pub fn filter_count(&self) -> usize {
+        self.filters.len()
+    }
+
+    /// Run text through the tokenizer and every filter in order
+    pub fn analyze(&self, text: &str) -> Vec<Token> {
+        self.filters
+            .iter()
+            .fold(self.tokenizer.tokenize_with_offsets(text), |tokens, filter| filter.filter(tokens))
+    }
+
+    /// Run text through the pipeline, keeping only the token texts
+    pub fn analyze_text(&self, text: &str) -> Vec<String> {
+        self.analyze(text)
+            .into_iter()
+            .map(Token::into_text)
+            .collect()
+    }
+}
+
+impl Tokenizer for Analyzer {
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        self.analyze_text(text)
+    }
+
+    fn tokenize_with_offsets(&self, text: &str) -> Vec<Token> {
+        self.analyze(text)
+    }
+
+    fn case_folding(&self) -> CaseFolding {
+        self.filters.iter().fold(self.tokenizer.case_folding(), |folding, filter| folding.then(filter.case_folding()))
+    }
+
+    fn joins_words(&self) -> bool {
+        self.tokenizer.joins_words()
+    }
+
+    fn is_stopword(&self, word: &str) -> bool {
+        self.tokenizer.is_stopword(word)
+    }
+
+    fn stopwords(&self) -> Vec<String> {
+        self.tokenizer.stopwords()
+    }
+
+    fn add_stopword(&self, word: &str) {
+        self.tokenizer.add_stopword(word);
+    }
+
+    fn remove_stopword(&self, word: &str) -> bool {
+        self.tokenizer.remove_stopword(word)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::tokenizer::{LengthFilter, SimpleTokenizer, StopwordFilter, SynonymFilter};
+
+    #[test]
+    fn test_filter_chain() {
+        let mut synonyms = SynonymFilter::new();
+        synonyms.add("car", ["automobile"]);
+
+        let analyzer = Analyzer::new(SimpleTokenizer::new())
+            .with_filter(StopwordFilter::new(["the"]))
+            .with_filter(LengthFilter::new(3, 10))
+            .with_filter(synonyms);
+        assert_eq!(analyzer.filter_count(), 3);
+
+        let text = "The red car is extraordinarily fast";
+        let tokens = analyzer.analyze(text);
+        let texts: Vec<&str> = tokens.iter().map(Token::text).collect();
+        assert_eq!(texts, vec!["red", "car", "automobile", "fast"]);
+
+        // Filters keep the offsets into the original text
+        assert_eq!(&text[tokens[2].start()..tokens[2].end()], "car");
+        assert_eq!(analyzer.tokenize(text), texts);
+    }
+}
@@ -0,0 +1,79 @@
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+
+/// How token case is normalized
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CaseFolding {
+    /// Keep tokens exactly as written
+    Preserve,
+
+    /// Lowercase every token
+    #[default]
+    Lowercase,
+
+    /// Lowercase every token except all-caps acronyms, so "NASA" and "nasa" stay distinct
+    PreserveAcronyms,
+}
+
+impl CaseFolding {
+    /// Normalize the case of a single token
+    pub fn fold(&self, word: &str) -> String {
+        self.fold_cow(word).into_owned()
+    }
+
+    /// Normalize the case of a single token, borrowing it when it is already normalized
+    pub fn fold_cow<'a>(&self, word: &'a str) -> Cow<'a, str> {
+        let lowercase = match self {
+            CaseFolding::Preserve => false,
+            CaseFolding::Lowercase => true,
+            CaseFolding::PreserveAcronyms => !is_acronym(word),
+        };
+
+        // Titlecase letters change too without counting as uppercase, so compare each mapping
+        if lowercase && word.chars().any(|c| !c.to_lowercase().eq(std::iter::once(c))) {
+            Cow::Owned(word.to_lowercase())
+        } else {
+            Cow::Borrowed(word)
+        }
+    }
+
+    /// Combine with a folding applied afterwards, e.g. by a filter after the tokenizer
+    pub fn then(self, next: CaseFolding) -> CaseFolding {
+        match (self, next) {
+            (folding, CaseFolding::Preserve) | (CaseFolding::Preserve, folding) => folding,
+            (CaseFolding::PreserveAcronyms, CaseFolding::PreserveAcronyms) => CaseFolding::PreserveAcronyms,
+            _ => CaseFolding::Lowercase,
+        }
+    }
+}
+
+/// Check whether a word is an acronym: at least two letters, all of them uppercase
+pub fn is_acronym(word: &str) -> bool {
+    let letters: Vec<char> = word.chars().filter(|c| c.is_alphabetic()).collect();
+    letters.len() >= 2 && letters.iter().all(|c| c.is_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fold() {
+        assert_eq!(CaseFolding::Lowercase.fold("NASA"), "nasa");
+        assert_eq!(CaseFolding::Preserve.fold("Rust"), "Rust");
+        assert_eq!(CaseFolding::PreserveAcronyms.fold("NASA"), "NASA");
+        assert_eq!(CaseFolding::PreserveAcronyms.fold("GPT4"), "GPT4");
+        assert_eq!(CaseFolding::PreserveAcronyms.fold("Rust"), "rust");
+        assert_eq!(CaseFolding::PreserveAcronyms.fold("I"), "i");
+        assert!(matches!(CaseFolding::Lowercase.fold_cow("rust"), Cow::Borrowed("rust")));
+    }
+
+    #[test]
+    fn test_then() {
+        assert_eq!(CaseFolding::Preserve.then(CaseFolding::PreserveAcronyms), CaseFolding::PreserveAcronyms);
+        assert_eq!(CaseFolding::PreserveAcronyms.then(CaseFolding::Preserve), CaseFolding::PreserveAcronyms);
+        assert_eq!(CaseFolding::Lowercase.then(CaseFolding::PreserveAcronyms), CaseFolding::Lowercase);
+        assert_eq!(CaseFolding::PreserveAcronyms.then(CaseFolding::Lowercase), CaseFolding::Lowercase);
+    }
+}
@@ -0,0 +1,139 @@
+use crate::domain::Token;
+
+use super::{CaseFolding, Tokenizer};
+
+/// Wraps another tokenizer and splits each word into character n-grams
+///
+/// Useful for typo-tolerant matching and agglutinative languages: "search" and the
+/// misspelled "serch" still share the 3-grams "rch". Words shorter than the minimum
+/// gram size, and stopwords, are emitted whole.
+pub struct CharNGramTokenizer<T: Tokenizer> {
+    inner: T,
+
+    /// Smallest gram size in characters
+    min_gram: usize,
+
+    /// Largest gram size in characters
+    max_gram: usize
+}
+
+impl<T: Tokenizer> CharNGramTokenizer<T> {
+    /// Create a tokenizer emitting every gram of `min_gram` to `max_gram` characters
+    pub fn new(inner: T, min_gram: usize, max_gram: usize) -> Self {
+        let min_gram = min_gram.max(1);
+        Self {
+            inner,
+            min_gram,
+            max_gram: max_gram.max(min_gram)
+        }
+    }
+
+    /// Create a tokenizer emitting grams of exactly `n` characters
+    pub fn with_size(inner: T, n: usize) -> Self {
+        Self::new(inner, n, n)
+    }
+
+    /// Get the smallest gram size
+    pub fn min_gram(&self) -> usize {
+        self.min_gram
+    }
+
+    /// Get the largest gram size
+    pub fn max_gram(&self) -> usize {
+        self.max_gram
+    }
+
+    /// Get the wrapped tokenizer
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T: Tokenizer> Tokenizer for CharNGramTokenizer<T> {
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        self.tokenize_with_offsets(text)
+            .into_iter()
+            .map(|token| token.text().to_string())
+            .collect()
+    }
+
+    fn tokenize_with_offsets(&self, text: &str) -> Vec<Token> {
+        let mut tokens = Vec::new();
+
+        for word in self.inner.tokenize_with_offsets(text) {
+            let chars: Vec<(usize, char)> = word.text().char_indices().collect();
+            if chars.len() < self.min_gram || self.inner.is_stopword(word.text()) {
+                tokens.push(Token::new(word.text(), tokens.len(), word.start(), word.end()));
+                continue;
+            }
+
+            // Offsets inside the word are only exact when normalization kept its byte length
+            let exact = word.text().len() == word.end() - word.start();
+
+            for size in self.min_gram..=self.max_gram.min(chars.len()) {
+                for i in 0..=chars.len() - size {
+                    let from = chars[i].0;
+                    let to = chars.get(i + size).map_or(word.text().len(), |(offset, _)| *offset);
+                    let (start, end) = if exact {
+                        (word.start() + from, word.start() + to)
+                    } else {
+                        (word.start(), word.end())
+                    };
+                    tokens.push(Token::new(&word.text()[from..to], tokens.len(), start, end));
+                }
+            }
+        }
+
+        tokens
+    }
+
+    fn case_folding(&self) -> CaseFolding {
+        self.inner.case_folding()
+    }
+
+    fn joins_words(&self) -> bool {
+        self.inner.joins_words()
+    }
+
+    fn is_stopword(&self, word: &str) -> bool {
+        self.inner.is_stopword(word)
+    }
+
+    fn stopwords(&self) -> Vec<String> {
+        self.inner.stopwords()
+    }
+
+    fn add_stopword(&self, word: &str) {
+        self.inner.add_stopword(word);
+    }
+
+    fn remove_stopword(&self, word: &str) -> bool {
+        self.inner.remove_stopword(word)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::tokenizer::SimpleTokenizer;
+
+    #[test]
+    fn test_trigrams() {
+        let tokenizer = CharNGramTokenizer::with_size(SimpleTokenizer::new(), 3);
+
+        assert_eq!(tokenizer.tokenize("Search"), vec!["sea", "ear", "arc", "rch"]);
+        assert_eq!(tokenizer.tokenize("go the"), vec!["go", "the"]);
+
+        let text = "Fuzzy Search";
+        let tokens = tokenizer.tokenize_with_offsets(text);
+        let gram = tokens.iter().find(|token| token.text() == "sea").unwrap();
+        assert_eq!(&text[gram.start()..gram.end()], "Sea");
+    }
+
+    #[test]
+    fn test_gram_range() {
+        let tokenizer = CharNGramTokenizer::new(SimpleTokenizer::new(), 2, 3);
+
+        assert_eq!(tokenizer.tokenize("über"), vec!["üb", "be", "er", "übe", "ber"]);
+    }
+}
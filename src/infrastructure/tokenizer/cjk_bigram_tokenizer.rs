@@ -0,0 +1,145 @@
+use crate::domain::Token;
+
+use super::{CaseFolding, Tokenizer};
+
+/// Wraps another tokenizer and splits runs of CJK characters into overlapping bigrams
+///
+/// Chinese and Japanese text has no spaces between words, so a split-based tokenizer
+/// turns whole sentences into a single token. Indexing character bigrams instead
+/// ("東京都" -> "東京", "京都") lets any two-character word match without a dictionary.
+/// A lone CJK character is emitted as a unigram, and non-CJK text inside the same
+/// token (e.g. "rust入門") is emitted as its own token.
+pub struct CjkBigramTokenizer<T: Tokenizer> {
+    inner: T
+}
+
+impl<T: Tokenizer> CjkBigramTokenizer<T> {
+    pub fn new(inner: T) -> Self {
+        Self { inner }
+    }
+
+    /// Get the wrapped tokenizer
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+}
+
+/// Check whether a character belongs to a CJK script written without word separators
+pub fn is_cjk(c: char) -> bool {
+    matches!(c as u32,
+        0x3040..=0x30FF      // Hiragana, Katakana
+        | 0x31F0..=0x31FF    // Katakana phonetic extensions
+        | 0x3400..=0x4DBF    // CJK extension A
+        | 0x4E00..=0x9FFF    // CJK unified ideographs
+        | 0xAC00..=0xD7AF    // Hangul syllables
+        | 0xF900..=0xFAFF    // CJK compatibility ideographs
+        | 0xFF66..=0xFF9F    // Halfwidth Katakana
+        | 0x20000..=0x2FA1F  // CJK extensions B-F, compatibility supplement
+    )
+}
+
+impl<T: Tokenizer> Tokenizer for CjkBigramTokenizer<T> {
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        self.tokenize_with_offsets(text)
+            .into_iter()
+            .map(|token| token.text().to_string())
+            .collect()
+    }
+
+    fn tokenize_with_offsets(&self, text: &str) -> Vec<Token> {
+        let mut tokens = Vec::new();
+
+        for word in self.inner.tokenize_with_offsets(text) {
+            if !word.text().chars().any(is_cjk) {
+                tokens.push(Token::new(word.text(), tokens.len(), word.start(), word.end()));
+                continue;
+            }
+
+            // Offsets inside the word are only exact when normalization kept its byte length
+            let exact = word.text().len() == word.end() - word.start();
+            let locate = |from: usize, to: usize| if exact {
+                (word.start() + from, word.start() + to)
+            } else {
+                (word.start(), word.end())
+            };
+
+            // Split the word into alternating CJK and non-CJK runs of (byte offset, char)
+            let chars: Vec<(usize, char)> = word.text().char_indices().collect();
+            let end_of = |i: usize| chars.get(i).map_or(word.text().len(), |(offset, _)| *offset);
+
+            let mut run_start = 0;
+            while run_start < chars.len() {
+                let cjk = is_cjk(chars[run_start].1);
+                let run_end = (run_start..chars.len())
+                    .find(|&i| is_cjk(chars[i].1) != cjk)
+                    .unwrap_or(chars.len());
+
+                let spans: Vec<(usize, usize)> = if !cjk || run_end - run_start == 1 {
+                    vec![(run_start, run_end)]
+                } else {
+                    (run_start..run_end - 1).map(|i| (i, i + 2)).collect()
+                };
+
+                for (first, last) in spans {
+                    let (from, to) = (chars[first].0, end_of(last));
+                    let (start, end) = locate(from, to);
+                    tokens.push(Token::new(&word.text()[from..to], tokens.len(), start, end));
+                }
+
+                run_start = run_end;
+            }
+        }
+
+        tokens
+    }
+
+    fn case_folding(&self) -> CaseFolding {
+        self.inner.case_folding()
+    }
+
+    fn joins_words(&self) -> bool {
+        self.inner.joins_words()
+    }
+
+    fn is_stopword(&self, word: &str) -> bool {
+        self.inner.is_stopword(word)
+    }
+
+    fn stopwords(&self) -> Vec<String> {
+        self.inner.stopwords()
+    }
+
+    fn add_stopword(&self, word: &str) {
+        self.inner.add_stopword(word);
+    }
+
+    fn remove_stopword(&self, word: &str) -> bool {
+        self.inner.remove_stopword(word)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::tokenizer::SimpleTokenizer;
+
+    #[test]
+    fn test_cjk_bigrams() {
+        let tokenizer = CjkBigramTokenizer::new(SimpleTokenizer::new());
+
+        assert_eq!(tokenizer.tokenize("東京都に行く"), vec!["東京", "京都", "都に", "に行", "行く"]);
+        assert_eq!(tokenizer.tokenize("Hello 世界"), vec!["hello", "世界"]);
+        assert_eq!(tokenizer.tokenize("Rust入門 猫"), vec!["rust", "入門", "猫"]);
+    }
+
+    #[test]
+    fn test_cjk_offsets() {
+        let tokenizer = CjkBigramTokenizer::new(SimpleTokenizer::new());
+        let text = "学习Rust语言";
+
+        let tokens = tokenizer.tokenize_with_offsets(text);
+        let slices: Vec<&str> = tokens.iter().map(|token| &text[token.start()..token.end()]).collect();
+        assert_eq!(slices, vec!["学习", "Rust", "语言"]);
+        assert_eq!(tokens[2].position(), 2);
+    }
+}
@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use super::Lemmatizer;
+
+/// Maps inflected forms to their lemma with a user-supplied dictionary
+///
+/// Words missing from the dictionary are returned unchanged.
+#[derive(Debug, Clone, Default)]
+pub struct DictionaryLemmatizer {
+    lemmas: HashMap<String, String>
+}
+
+impl DictionaryLemmatizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a lemmatizer from (form, lemma) pairs
+    pub fn with_entries(entries: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>) -> Self {
+        let mut lemmatizer = Self::new();
+        for (form, lemma) in entries {
+            lemmatizer.add(form, lemma);
+        }
+        lemmatizer
+    }
+
+    /// Map a form to its lemma, matching case-insensitively
+    pub fn add(&mut self, form: impl Into<String>, lemma: impl Into<String>) {
+        self.lemmas.insert(form.into().to_lowercase(), lemma.into().to_lowercase());
+    }
+
+    /// Get the number of forms in the dictionary
+    pub fn len(&self) -> usize {
+        self.lemmas.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lemmas.is_empty()
+    }
+}
+
+impl Lemmatizer for DictionaryLemmatizer {
+    fn lemmatize(&self, word: &str) -> String {
+        self.lemmas.get(word).cloned().unwrap_or_else(|| word.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lemmatize() {
+        let lemmatizer = DictionaryLemmatizer::with_entries([("ran", "run"), ("Running", "run"), ("runs", "run")]);
+
+        assert_eq!(lemmatizer.len(), 3);
+        assert_eq!(lemmatizer.lemmatize("ran"), "run");
+        assert_eq!(lemmatizer.lemmatize("running"), "run");
+        assert_eq!(lemmatizer.lemmatize("walked"), "walked");
+    }
+}
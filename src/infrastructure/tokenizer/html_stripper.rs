@@ -0,0 +1,177 @@
+use super::Preprocessor;
+
+/// Turns an HTML page into plain text before tokenization
+///
+/// Tags and comments are removed, `<script>` and `<style>` contents are dropped,
+/// and character entities are decoded. Page titles and image `alt` text are
+/// dropped too unless `keep_title_and_alt` is set.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HtmlStripper {
+    keep_title_and_alt: bool
+}
+
+impl HtmlStripper {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep the text of `<title>` elements and `alt` attributes
+    pub fn keeping_title_and_alt() -> Self {
+        Self { keep_title_and_alt: true }
+    }
+
+    /// Check whether `<title>` and `alt` text is kept
+    pub fn keeps_title_and_alt(&self) -> bool {
+        self.keep_title_and_alt
+    }
+
+    /// Check whether the whole content of an element should be dropped
+    fn skips_element(&self, name: &str) -> bool {
+        matches!(name, "script" | "style") || (name == "title" && !self.keep_title_and_alt)
+    }
+}
+
+impl Preprocessor for HtmlStripper {
+    fn preprocess(&self, text: &str) -> String {
+        let mut output = String::with_capacity(text.len());
+        let mut rest = text;
+
+        while let Some(open) = rest.find('<') {
+            output.push_str(&decode_entities(&rest[..open]));
+            rest = &rest[open..];
+
+            if let Some(comment) = rest.strip_prefix("<!--") {
+                rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+                continue;
+            }
+
+            let Some(close) = rest.find('>') else {
+                // An unterminated tag is treated as text
+                output.push_str(&decode_entities(rest));
+                return output;
+            };
+            let tag = &rest[1..close];
+            rest = &rest[close + 1..];
+            // Tags separate words, e.g. "<td>a</td><td>b</td>"
+            output.push(' ');
+
+            if self.keep_title_and_alt && let Some(alt) = attribute(tag, "alt") {
+                output.push_str(&decode_entities(alt));
+                output.push(' ');
+            }
+
+            let name = tag_name(tag);
+            let self_closing = tag.starts_with('/') || tag.ends_with('/');
+            if !self_closing && self.skips_element(&name) {
+                let closing = format!("</{}", name);
+                rest = rest.to_ascii_lowercase().find(&closing)
+                    .and_then(|end| rest[end..].find('>').map(|close| &rest[end + close + 1..]))
+                    .unwrap_or("");
+            }
+        }
+
+        output.push_str(&decode_entities(rest));
+        output
+    }
+}
+
+/// Get the lowercased element name of a tag body such as `img src="a.png"`
+fn tag_name(tag: &str) -> String {
+    tag.trim_start_matches('/')
+        .split(|c: char| c.is_whitespace() || c == '/')
+        .next()
+        .unwrap_or("")
+        .to_ascii_lowercase()
+}
+
+/// Get the value of a quoted attribute in a tag body
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let lower = tag.to_ascii_lowercase();
+    let mut search = 0;
+
+    while let Some(found) = lower[search..].find(name) {
+        let at = search + found;
+        search = at + name.len();
+
+        let preceded = lower[..at].ends_with(|c: char| c.is_whitespace());
+        let value = lower[search..].trim_start().strip_prefix('=').map(str::trim_start);
+        if let (true, Some(value)) = (preceded, value) {
+            let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+            let start = tag.len() - value.len() + 1;
+            let end = tag[start..].find(quote)?;
+            return Some(&tag[start..start + end]);
+        }
+    }
+
+    None
+}
+
+/// Decode named and numeric character entities, leaving unknown ones as written
+pub fn decode_entities(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(amp) = rest.find('&') {
+        output.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+
+        let decoded = rest.find(';').filter(|end| *end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => entity.strip_prefix("#x").or_else(|| entity.strip_prefix("#X"))
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| entity.strip_prefix('#').map(str::parse::<u32>))
+                    .and_then(Result::ok)
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                output.push(c);
+                rest = &rest[end + 1..];
+            },
+            None => {
+                output.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    output.push_str(rest);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(text: &str) -> Vec<&str> {
+        text.split_whitespace().collect()
+    }
+
+    #[test]
+    fn test_strip_tags() {
+        let html = r#"<html><head><title>Page</title><style>p { color: red }</style></head>
+            <body><!-- nav --><p>Fish &amp; chips&nbsp;&#8364;5</p><img src="a.png" alt="A fish"/>
+            <script>var x = "<b>";</script></body></html>"#;
+
+        assert_eq!(words(&HtmlStripper::new().preprocess(html)), vec!["Fish", "&", "chips", "€5"]);
+        assert_eq!(
+            words(&HtmlStripper::keeping_title_and_alt().preprocess(html)),
+            vec!["Page", "Fish", "&", "chips", "€5", "A", "fish"]
+        );
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(decode_entities("a &lt;b&gt; &#x41;&#66; &unknown; & c"), "a <b> AB &unknown; & c");
+    }
+}
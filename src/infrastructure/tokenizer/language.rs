@@ -0,0 +1,170 @@
+// src/infrastructure/tokenizer/language.rs
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use super::{Stemmer, Tokenizer};
+
+/// Guesses the language of a text, as an ISO 639-1 code such as "en"
+pub trait LanguageDetector: Send + Sync {
+    /// Detect the language of a text, or None if it cannot tell
+    fn detect(&self, text: &str) -> Option<String>;
+}
+
+/// Common function words of the languages `StopwordLanguageDetector` recognizes
+const FUNCTION_WORDS: &[(&str, &[&str])] = &[
+    ("en", &["the", "and", "of", "to", "is", "in", "that", "it", "with", "for", "are", "this", "was", "not", "be"]),
+    ("de", &["der", "die", "das", "und", "ist", "nicht", "mit", "ein", "eine", "zu", "den", "von", "sich", "auf", "auch"]),
+    ("fr", &["le", "la", "les", "et", "est", "des", "une", "un", "dans", "que", "pour", "pas", "sur", "avec", "du"]),
+    ("es", &["el", "la", "los", "las", "y", "es", "que", "en", "un", "una", "por", "con", "para", "del", "se"]),
+    ("it", &["il", "lo", "la", "gli", "e", "che", "di", "un", "una", "per", "con", "non", "sono", "del", "della"]),
+    ("nl", &["de", "het", "een", "en", "van", "is", "dat", "niet", "met", "op", "zijn", "voor", "ook", "naar", "te"]),
+    ("pt", &["o", "os", "as", "e", "que", "do", "da", "em", "um", "uma", "para", "com", "não", "por", "dos"]),
+];
+
+/// A lightweight detector counting common function words, plus script checks for Chinese, Japanese and Korean
+///
+/// Texts in Han, kana or Hangul script are identified by script alone. Other
+/// texts are assigned the language whose function words occur most often,
+/// provided at least `min_matches` occur; short texts are often left undetected.
+#[derive(Debug, Clone)]
+pub struct StopwordLanguageDetector {
+    min_matches: usize,
+}
+
+impl Default for StopwordLanguageDetector {
+    fn default() -> Self {
+        Self { min_matches: 2 }
+    }
+}
+
+impl StopwordLanguageDetector {
+    /// Create a detector requiring two function word matches
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a detector requiring the given number of function word matches
+    pub fn with_min_matches(min_matches: usize) -> Self {
+        Self { min_matches: min_matches.max(1) }
+    }
+
+    /// Get the languages recognized by function words
+    pub fn languages() -> impl Iterator<Item = &'static str> {
+        FUNCTION_WORDS.iter().map(|(language, _)| *language)
+    }
+}
+
+impl LanguageDetector for StopwordLanguageDetector {
+    fn detect(&self, text: &str) -> Option<String> {
+        let (mut han, mut kana, mut hangul, mut letters) = (0, 0, 0, 0);
+        for c in text.chars().filter(|c| c.is_alphabetic()) {
+            letters += 1;
+            match c {
+                '\u{3040}'..='\u{30FF}' => kana += 1,
+                '\u{AC00}'..='\u{D7AF}' | '\u{1100}'..='\u{11FF}' => hangul += 1,
+                '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}' => han += 1,
+                _ => {},
+            }
+        }
+        // Japanese mixes kana into Han text, so any kana decides it
+        if kana > 0 && (kana + han) * 2 >= letters {
+            return Some("ja".to_string());
+        }
+        if hangul * 2 >= letters && hangul > 0 {
+            return Some("ko".to_string());
+        }
+        if han * 2 >= letters && han > 0 {
+            return Some("zh".to_string());
+        }
+
+        let words: Vec<String> = text.split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect();
+        FUNCTION_WORDS.iter()
+            .map(|(language, function_words)| {
+                (*language, words.iter().filter(|word| function_words.contains(&word.as_str())).count())
+            })
+            .filter(|(_, matches)| *matches >= self.min_matches)
+            // Earlier languages win ties
+            .fold(None, |best: Option<(&str, usize)>, (language, matches)| match best {
+                Some((_, best_matches)) if best_matches >= matches => best,
+                _ => Some((language, matches)),
+            })
+            .map(|(language, _)| language.to_string())
+    }
+}
+
+/// Language-specific analysis applied to documents tagged or detected as that language
+#[derive(Clone, Default)]
+pub struct LanguageProfile {
+    /// Tokenizer replacing the default one, along with its stopwords (None = keep the default)
+    tokenizer: Option<Arc<dyn Tokenizer>>,
+
+    /// Stemmer replacing the default one (None = keep the default)
+    stemmer: Option<Arc<dyn Stemmer>>,
+
+    /// Content tokens dropped in addition to the tokenizer's stopwords, lowercase
+    stopwords: HashSet<String>,
+}
+
+impl LanguageProfile {
+    /// Create a profile that changes nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tokenize text of this language with the given tokenizer or analyzer
+    ///
+    /// Its stopwords replace the default tokenizer's, so e.g. English stopwords
+    /// are not dropped from French text.
+    pub fn with_tokenizer(mut self, tokenizer: Arc<dyn Tokenizer>) -> Self {
+        self.tokenizer = Some(tokenizer);
+        self
+    }
+
+    /// Stem terms of this language with the given stemmer
+    pub fn with_stemmer(mut self, stemmer: Arc<dyn Stemmer>) -> Self {
+        self.stemmer = Some(stemmer);
+        self
+    }
+
+    /// Drop the given words from content of this language
+    pub fn with_stopwords(mut self, stopwords: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+        self.stopwords.extend(stopwords.into_iter().map(|word| word.as_ref().to_lowercase()));
+        self
+    }
+
+    /// Get the tokenizer of this language, if it has one
+    pub fn tokenizer(&self) -> Option<&Arc<dyn Tokenizer>> {
+        self.tokenizer.as_ref()
+    }
+
+    /// Get the stemmer of this language, if it has one
+    pub fn stemmer(&self) -> Option<&Arc<dyn Stemmer>> {
+        self.stemmer.as_ref()
+    }
+
+    /// Check if a word is a stopword of this language
+    pub fn is_stopword(&self, word: &str) -> bool {
+        self.stopwords.contains(&word.to_lowercase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        let detector = StopwordLanguageDetector::new();
+
+        assert_eq!(detector.detect("The borrow checker is part of the compiler").as_deref(), Some("en"));
+        assert_eq!(detector.detect("Der Compiler ist schnell und nicht kompliziert").as_deref(), Some("de"));
+        assert_eq!(detector.detect("Le compilateur est rapide et les erreurs sont claires").as_deref(), Some("fr"));
+        assert_eq!(detector.detect("東京は日本の首都です").as_deref(), Some("ja"));
+        assert_eq!(detector.detect("编译器很快").as_deref(), Some("zh"));
+        assert_eq!(detector.detect("Rust compiler").as_deref(), None);
+    }
+}
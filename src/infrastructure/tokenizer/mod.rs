@@ -1,10 +1,71 @@
 mod simple_tokenizer;
+mod porter_stemmer;
+mod dictionary_lemmatizer;
+mod ngram_tokenizer;
+mod char_ngram_tokenizer;
+mod cjk_bigram_tokenizer;
+mod analyzer;
+mod token_filters;
+mod html_stripper;
+mod case_folding;
+mod language;
 pub use simple_tokenizer::SimpleTokenizer;
+pub use porter_stemmer::PorterStemmer;
+pub use dictionary_lemmatizer::DictionaryLemmatizer;
+pub use ngram_tokenizer::NGramTokenizer;
+pub use char_ngram_tokenizer::CharNGramTokenizer;
+pub use cjk_bigram_tokenizer::{is_cjk, CjkBigramTokenizer};
+pub use analyzer::{Analyzer, TokenFilter};
+pub use case_folding::{is_acronym, CaseFolding};
+pub use html_stripper::{decode_entities, HtmlStripper};
+pub use language::{LanguageDetector, LanguageProfile, StopwordLanguageDetector};
+pub use token_filters::{LengthFilter, LowercaseFilter, StemmerFilter, StopwordFilter, SynonymFilter};
+
+use crate::domain::{Token, TokenSpan};
 
 pub trait Tokenizer: Send + Sync {
     fn tokenize(&self, text: &str) -> Vec<String>;
+    fn tokenize_with_offsets(&self, text: &str) -> Vec<Token>;
+
+    /// Tokenize text into byte-range spans, borrowing token text that normalization left unchanged
+    ///
+    /// The default allocates every token through `tokenize_with_offsets`.
+    fn tokenize_spans<'a>(&self, text: &'a str) -> Vec<TokenSpan<'a>> {
+        self.tokenize_with_offsets(text).into_iter().map(TokenSpan::from).collect()
+    }
+
+    /// How token case is normalized, so raw query patterns can be folded to match
+    ///
+    /// The default assumes lowercased tokens.
+    fn case_folding(&self) -> CaseFolding {
+        CaseFolding::Lowercase
+    }
+
+    /// Whether a token may span several words, as word n-grams do
+    ///
+    /// Text appended to such a tokenizer's input can change tokens ending
+    /// words before it, not just the last one.
+    fn joins_words(&self) -> bool {
+        false
+    }
+
     fn is_stopword(&self, word: &str) -> bool;
     fn stopwords(&self) -> Vec<String>;
-    fn add_stopword(&mut self, word: &str);
-    fn remove_stopword(&mut self, word: &str) -> bool;
-}
\ No newline at end of file
+    fn add_stopword(&self, word: &str);
+    fn remove_stopword(&self, word: &str) -> bool;
+}
+
+/// Reduces a token to a stem shared by its inflected forms
+pub trait Stemmer: Send + Sync {
+    fn stem(&self, word: &str) -> String;
+}
+
+/// Maps a token to its dictionary form, e.g. "ran" to "run"
+pub trait Lemmatizer: Send + Sync {
+    fn lemmatize(&self, word: &str) -> String;
+}
+
+/// Rewrites raw content before it is tokenized, e.g. to strip markup
+pub trait Preprocessor: Send + Sync {
+    fn preprocess(&self, text: &str) -> String;
+}
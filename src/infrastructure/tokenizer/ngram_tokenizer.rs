@@ -0,0 +1,137 @@
+use crate::domain::Token;
+
+use super::{CaseFolding, Tokenizer};
+
+/// Wraps another tokenizer and adds word n-grams, so "new york" can be indexed as one term
+///
+/// N-grams are joined with a single space and never start or end with a stopword,
+/// so "bank of america" is kept while "of america" is not. Unigram stopwords are
+/// passed through unchanged and left for stopword filtering to handle.
+pub struct NGramTokenizer<T: Tokenizer> {
+    inner: T,
+
+    /// Largest n-gram size emitted
+    n: usize,
+
+    /// Whether single words are emitted alongside the n-grams
+    keep_unigrams: bool
+}
+
+impl<T: Tokenizer> NGramTokenizer<T> {
+    /// Create a tokenizer emitting every n-gram from 2 up to `n` words, plus unigrams
+    pub fn new(inner: T, n: usize) -> Self {
+        Self {
+            inner,
+            n: n.max(1),
+            keep_unigrams: true
+        }
+    }
+
+    /// Set whether single words are emitted alongside the n-grams
+    pub fn set_keep_unigrams(&mut self, keep_unigrams: bool) {
+        self.keep_unigrams = keep_unigrams;
+    }
+
+    /// Get the largest n-gram size emitted
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// Get the wrapped tokenizer
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T: Tokenizer> Tokenizer for NGramTokenizer<T> {
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        self.tokenize_with_offsets(text)
+            .into_iter()
+            .map(|token| token.text().to_string())
+            .collect()
+    }
+
+    fn tokenize_with_offsets(&self, text: &str) -> Vec<Token> {
+        let words = self.inner.tokenize_with_offsets(text);
+        let mut tokens = Vec::new();
+
+        for (i, first) in words.iter().enumerate() {
+            if self.keep_unigrams {
+                tokens.push(Token::new(first.text(), tokens.len(), first.start(), first.end()));
+            }
+            if self.inner.is_stopword(first.text()) {
+                continue;
+            }
+
+            for j in (i + 1..words.len()).take(self.n - 1) {
+                let last = &words[j];
+                if self.inner.is_stopword(last.text()) {
+                    continue;
+                }
+                let gram = words[i..=j]
+                    .iter()
+                    .map(Token::text)
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                tokens.push(Token::new(gram, tokens.len(), first.start(), last.end()));
+            }
+        }
+
+        tokens
+    }
+
+    fn case_folding(&self) -> CaseFolding {
+        self.inner.case_folding()
+    }
+
+    fn joins_words(&self) -> bool {
+        self.n > 1 || self.inner.joins_words()
+    }
+
+    fn is_stopword(&self, word: &str) -> bool {
+        self.inner.is_stopword(word)
+    }
+
+    fn stopwords(&self) -> Vec<String> {
+        self.inner.stopwords()
+    }
+
+    fn add_stopword(&self, word: &str) {
+        self.inner.add_stopword(word);
+    }
+
+    fn remove_stopword(&self, word: &str) -> bool {
+        self.inner.remove_stopword(word)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::tokenizer::SimpleTokenizer;
+
+    #[test]
+    fn test_bigrams() {
+        let tokenizer = NGramTokenizer::new(SimpleTokenizer::new(), 2);
+
+        assert_eq!(
+            tokenizer.tokenize("New York City"),
+            vec!["new", "new york", "york", "york city", "city"]
+        );
+
+        let text = "Visit New York";
+        let tokens = tokenizer.tokenize_with_offsets(text);
+        let gram = tokens.iter().find(|token| token.text() == "new york").unwrap();
+        assert_eq!(&text[gram.start()..gram.end()], "New York");
+    }
+
+    #[test]
+    fn test_stopwords_bound_ngrams() {
+        let mut tokenizer = NGramTokenizer::new(SimpleTokenizer::new(), 3);
+        tokenizer.set_keep_unigrams(false);
+
+        // No n-gram starts or ends with "of", but it may sit inside one
+        assert_eq!(tokenizer.tokenize("bank of america"), vec!["bank of america"]);
+        assert!(tokenizer.is_stopword("of"));
+    }
+}
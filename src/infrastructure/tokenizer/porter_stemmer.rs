@@ -0,0 +1,216 @@
+use super::Stemmer;
+
+/// The Porter (1980) suffix-stripping stemmer for English
+///
+/// Follows Martin Porter's reference implementation. Words that are shorter
+/// than three letters or contain anything but ASCII lowercase letters are
+/// returned unchanged, so tokens should be lowercased first.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PorterStemmer;
+
+impl PorterStemmer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Stemmer for PorterStemmer {
+    fn stem(&self, word: &str) -> String {
+        if word.len() <= 2 || !word.bytes().all(|b| b.is_ascii_lowercase()) {
+            return word.to_string();
+        }
+
+        let mut w = word.as_bytes().to_vec();
+        step1a(&mut w);
+        step1b(&mut w);
+        step1c(&mut w);
+        step2(&mut w);
+        step3(&mut w);
+        step4(&mut w);
+        step5(&mut w);
+
+        String::from_utf8(w).expect("stemming only removes or appends ASCII letters")
+    }
+}
+
+fn is_consonant(w: &[u8], i: usize) -> bool {
+    match w[i] {
+        b'a' | b'e' | b'i' | b'o' | b'u' => false,
+        b'y' => i == 0 || !is_consonant(w, i - 1),
+        _ => true,
+    }
+}
+
+/// The number of vowel-consonant sequences, m in [C](VC){m}[V]
+fn measure(w: &[u8]) -> usize {
+    let mut m = 0;
+    let mut previous_vowel = false;
+
+    for i in 0..w.len() {
+        let vowel = !is_consonant(w, i);
+        if previous_vowel && !vowel {
+            m += 1;
+        }
+        previous_vowel = vowel;
+    }
+
+    m
+}
+
+fn contains_vowel(w: &[u8]) -> bool {
+    (0..w.len()).any(|i| !is_consonant(w, i))
+}
+
+fn ends_double_consonant(w: &[u8]) -> bool {
+    let n = w.len();
+    n >= 2 && w[n - 1] == w[n - 2] && is_consonant(w, n - 1)
+}
+
+/// Consonant-vowel-consonant ending, where the last consonant is not w, x or y
+fn ends_cvc(w: &[u8]) -> bool {
+    let n = w.len();
+    n >= 3
+        && is_consonant(w, n - 3)
+        && !is_consonant(w, n - 2)
+        && is_consonant(w, n - 1)
+        && !matches!(w[n - 1], b'w' | b'x' | b'y')
+}
+
+fn replace_suffix(w: &mut Vec<u8>, suffix: &str, replacement: &str) {
+    w.truncate(w.len() - suffix.len());
+    w.extend_from_slice(replacement.as_bytes());
+}
+
+/// Apply the first rule whose suffix matches, if the remaining stem has a measure above `min_measure`
+///
+/// Rules are ordered so that a longer suffix is tried before any suffix it ends with.
+fn apply_rules(w: &mut Vec<u8>, rules: &[(&str, &str)], min_measure: usize) {
+    if let Some((suffix, replacement)) = rules.iter().find(|(suffix, _)| w.ends_with(suffix.as_bytes()))
+        && measure(&w[..w.len() - suffix.len()]) > min_measure
+    {
+        replace_suffix(w, suffix, replacement);
+    }
+}
+
+fn step1a(w: &mut Vec<u8>) {
+    if w.ends_with(b"sses") || w.ends_with(b"ies") {
+        w.truncate(w.len() - 2);
+    } else if w.ends_with(b"s") && !w.ends_with(b"ss") {
+        w.pop();
+    }
+}
+
+fn step1b(w: &mut Vec<u8>) {
+    if w.ends_with(b"eed") {
+        if measure(&w[..w.len() - 3]) > 0 {
+            w.pop();
+        }
+        return;
+    }
+
+    let suffix_len = if w.ends_with(b"ed") {
+        2
+    } else if w.ends_with(b"ing") {
+        3
+    } else {
+        return;
+    };
+
+    if !contains_vowel(&w[..w.len() - suffix_len]) {
+        return;
+    }
+    w.truncate(w.len() - suffix_len);
+
+    if w.ends_with(b"at") || w.ends_with(b"bl") || w.ends_with(b"iz") {
+        w.push(b'e');
+    } else if ends_double_consonant(w) && !matches!(w[w.len() - 1], b'l' | b's' | b'z') {
+        w.pop();
+    } else if measure(w) == 1 && ends_cvc(w) {
+        w.push(b'e');
+    }
+}
+
+fn step1c(w: &mut [u8]) {
+    let n = w.len();
+    if w.ends_with(b"y") && contains_vowel(&w[..n - 1]) {
+        w[n - 1] = b'i';
+    }
+}
+
+fn step2(w: &mut Vec<u8>) {
+    apply_rules(w, &[
+        ("ational", "ate"), ("tional", "tion"), ("enci", "ence"), ("anci", "ance"),
+        ("izer", "ize"), ("bli", "ble"), ("alli", "al"), ("entli", "ent"),
+        ("eli", "e"), ("ousli", "ous"), ("ization", "ize"), ("ation", "ate"),
+        ("ator", "ate"), ("alism", "al"), ("iveness", "ive"), ("fulness", "ful"),
+        ("ousness", "ous"), ("aliti", "al"), ("iviti", "ive"), ("biliti", "ble"),
+        ("logi", "log"),
+    ], 0);
+}
+
+fn step3(w: &mut Vec<u8>) {
+    apply_rules(w, &[
+        ("icate", "ic"), ("ative", ""), ("alize", "al"), ("iciti", "ic"),
+        ("ical", "ic"), ("ful", ""), ("ness", ""),
+    ], 0);
+}
+
+fn step4(w: &mut Vec<u8>) {
+    // "ion" only counts as a suffix after s or t
+    if w.ends_with(b"sion") || w.ends_with(b"tion") {
+        apply_rules(w, &[("ion", "")], 1);
+        return;
+    }
+
+    apply_rules(w, &[
+        ("ement", ""), ("ance", ""), ("ence", ""), ("able", ""), ("ible", ""),
+        ("ment", ""), ("ant", ""), ("ent", ""), ("ism", ""), ("ate", ""),
+        ("iti", ""), ("ous", ""), ("ive", ""), ("ize", ""), ("al", ""),
+        ("er", ""), ("ic", ""), ("ou", ""),
+    ], 1);
+}
+
+fn step5(w: &mut Vec<u8>) {
+    if w.ends_with(b"e") {
+        let stem = &w[..w.len() - 1];
+        let m = measure(stem);
+        if m > 1 || (m == 1 && !ends_cvc(stem)) {
+            w.pop();
+        }
+    }
+
+    if w.ends_with(b"ll") && measure(w) > 1 {
+        w.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference_vocabulary() {
+        let stemmer = PorterStemmer::new();
+
+        for (word, stem) in [
+            ("caresses", "caress"), ("ponies", "poni"), ("cats", "cat"), ("feed", "feed"),
+            ("agreed", "agre"), ("plastered", "plaster"), ("motoring", "motor"), ("sing", "sing"),
+            ("conflated", "conflat"), ("troubled", "troubl"), ("sized", "size"), ("hopping", "hop"),
+            ("falling", "fall"), ("hissing", "hiss"), ("filing", "file"), ("happy", "happi"),
+            ("relational", "relat"), ("conditional", "condit"), ("rational", "ration"),
+            ("generalization", "gener"), ("adjustment", "adjust"), ("adoption", "adopt"),
+            ("controlling", "control"), ("roll", "roll"), ("running", "run"), ("runs", "run"),
+        ] {
+            assert_eq!(stemmer.stem(word), stem, "stem of '{}'", word);
+        }
+    }
+
+    #[test]
+    fn test_untouched_words() {
+        let stemmer = PorterStemmer::new();
+
+        assert_eq!(stemmer.stem("is"), "is");
+        assert_eq!(stemmer.stem("naïve"), "naïve");
+        assert_eq!(stemmer.stem("2024"), "2024");
+    }
+}
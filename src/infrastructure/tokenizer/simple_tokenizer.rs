@@ -1,9 +1,14 @@
 use std::{collections::HashSet, sync::RwLock};
 
-use super::Tokenizer;
+use crate::domain::{Token, TokenSpan};
+
+use super::{CaseFolding, Tokenizer};
 
 pub struct SimpleTokenizer {
-    stopwords: RwLock<HashSet<String>>   
+    stopwords: RwLock<HashSet<String>>,
+
+    /// How token case is normalized (lowercase by default)
+    case_folding: CaseFolding
 }
 
 impl SimpleTokenizer {
@@ -15,7 +20,8 @@ impl SimpleTokenizer {
         }
 
         Self {
-            stopwords: RwLock::new(stopwords)
+            stopwords: RwLock::new(stopwords),
+            case_folding: CaseFolding::default()
         }
     }
 
@@ -25,9 +31,20 @@ impl SimpleTokenizer {
             .map(|s| s.into().to_lowercase())
             .collect();
         Self {
-            stopwords: RwLock::new(stopwords_set)
+            stopwords: RwLock::new(stopwords_set),
+            case_folding: CaseFolding::default()
         }
     }
+
+    /// Get how token case is normalized
+    pub fn case_folding(&self) -> CaseFolding {
+        self.case_folding
+    }
+
+    /// Set how token case is normalized, e.g. `Preserve` to leave it to a `LowercaseFilter`
+    pub fn set_case_folding(&mut self, case_folding: CaseFolding) {
+        self.case_folding = case_folding;
+    }
 }
 
 impl Default for SimpleTokenizer {
@@ -38,15 +55,40 @@ impl Default for SimpleTokenizer {
 
 impl Tokenizer for SimpleTokenizer {
     fn tokenize(&self, text: &str) -> Vec<String> {
-        let text = text.to_lowercase();
+        self.tokenize_spans(text).into_iter().map(TokenSpan::into_text).collect()
+    }
 
-        let tokens: Vec<String> = text
-            .split(|c: char| !c.is_alphanumeric())
-            .filter(|s| !s.is_empty())
-            .map(|t|t.to_string())
-            .collect();
+    fn tokenize_with_offsets(&self, text: &str) -> Vec<Token> {
+        self.tokenize_spans(text)
+            .into_iter()
+            .enumerate()
+            .map(|(position, span)| {
+                let (start, end) = (span.start(), span.end());
+                Token::new(span.into_text(), position, start, end)
+            })
+            .collect()
+    }
+
+    fn tokenize_spans<'a>(&self, text: &'a str) -> Vec<TokenSpan<'a>> {
+        let mut spans = Vec::new();
+        let mut token_start = None;
+
+        for (index, c) in text.char_indices().chain(std::iter::once((text.len(), ' '))) {
+            match (c.is_alphanumeric(), token_start) {
+                (true, None) => token_start = Some(index),
+                (false, Some(start)) => {
+                    spans.push(TokenSpan::new(self.case_folding.fold_cow(&text[start..index]), start, index));
+                    token_start = None;
+                },
+                _ => {}
+            }
+        }
 
-        tokens
+        spans
+    }
+
+    fn case_folding(&self) -> CaseFolding {
+        self.case_folding
     }
     
     fn is_stopword(&self, word: &str) -> bool {
@@ -59,12 +101,12 @@ impl Tokenizer for SimpleTokenizer {
         stopwords.iter().cloned().collect()
     }
     
-    fn add_stopword(&mut self, word: &str) {
+    fn add_stopword(&self, word: &str) {
         let mut stopwords = self.stopwords.write().expect("FAILED to acquire write lock");
         stopwords.insert(word.to_lowercase());
     }
     
-    fn remove_stopword(&mut self, word: &str) -> bool {
+    fn remove_stopword(&self, word: &str) -> bool {
         let mut stopwords = self.stopwords.write().expect("FAILED to acquire write lock");
         stopwords.remove(&word.to_lowercase())
     }
@@ -92,6 +134,7 @@ static DEFAULT_STOPWORDS: &[&str] = &[
 ];
 
 #[cfg(test)]
+#[allow(clippy::len_zero)]
 mod tests {
    use super::*;
     
@@ -116,8 +159,45 @@ mod tests {
     }
     
     #[test]
-    fn test_stopwords() {
+    fn test_tokenize_with_offsets() {
+        let tokenizer = SimpleTokenizer::new();
+        let text = "Hello, World!";
+
+        let tokens = tokenizer.tokenize_with_offsets(text);
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[0].text(), "hello");
+        assert_eq!(tokens[1].text(), "world");
+        assert_eq!(tokens[1].position(), 1);
+        assert_eq!(&text[tokens[1].start()..tokens[1].end()], "World");
+    }
+
+    #[test]
+    fn test_tokenize_spans_borrow() {
+        let tokenizer = SimpleTokenizer::new();
+        let text = "rust Compiler";
+
+        let spans = tokenizer.tokenize_spans(text);
+        assert_eq!(spans.len(), 2);
+        assert!(spans[0].is_borrowed());
+        assert!(!spans[1].is_borrowed());
+        assert_eq!(spans[1].text(), "compiler");
+        assert_eq!(&text[spans[1].start()..spans[1].end()], "Compiler");
+    }
+
+    #[test]
+    fn test_case_folding() {
         let mut tokenizer = SimpleTokenizer::new();
+        tokenizer.set_case_folding(CaseFolding::PreserveAcronyms);
+        assert_eq!(tokenizer.tokenize("NASA launches Rockets"), vec!["NASA", "launches", "rockets"]);
+        
+        tokenizer.set_case_folding(CaseFolding::Preserve);
+        assert_eq!(tokenizer.tokenize_with_offsets("The NASA")[0].text(), "The");
+        assert!(tokenizer.is_stopword("The"));
+    }
+    
+    #[test]
+    fn test_stopwords() {
+        let tokenizer = SimpleTokenizer::new();
         
         // Test default stopwords
         assert!(tokenizer.is_stopword("the"));
@@ -0,0 +1,172 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::domain::Token;
+
+use super::{CaseFolding, Stemmer, TokenFilter};
+
+/// Replace a token's text, keeping its position and offsets
+fn retext(token: &Token, text: impl Into<String>) -> Token {
+    Token::new(text, token.position(), token.start(), token.end())
+}
+
+/// Lowercases every token, optionally keeping all-caps acronyms such as "NASA"
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LowercaseFilter {
+    preserve_acronyms: bool
+}
+
+impl LowercaseFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a filter that leaves acronyms uppercase, so "NASA" and "nasa" stay distinct
+    pub fn preserving_acronyms() -> Self {
+        Self { preserve_acronyms: true }
+    }
+}
+
+impl TokenFilter for LowercaseFilter {
+    fn filter(&self, tokens: Vec<Token>) -> Vec<Token> {
+        let case_folding = self.case_folding();
+        tokens.iter().map(|token| retext(token, case_folding.fold(token.text()))).collect()
+    }
+
+    fn case_folding(&self) -> CaseFolding {
+        if self.preserve_acronyms {
+            CaseFolding::PreserveAcronyms
+        } else {
+            CaseFolding::Lowercase
+        }
+    }
+}
+
+/// Drops tokens found in a stopword list
+///
+/// Positions are left untouched, so a removed stopword leaves a gap.
+#[derive(Debug, Clone, Default)]
+pub struct StopwordFilter {
+    stopwords: HashSet<String>
+}
+
+impl StopwordFilter {
+    pub fn new(stopwords: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            stopwords: stopwords.into_iter().map(Into::into).collect()
+        }
+    }
+}
+
+impl TokenFilter for StopwordFilter {
+    fn filter(&self, tokens: Vec<Token>) -> Vec<Token> {
+        tokens.into_iter().filter(|token| !self.stopwords.contains(token.text())).collect()
+    }
+}
+
+/// Replaces every token with its stem
+pub struct StemmerFilter {
+    stemmer: Arc<dyn Stemmer>
+}
+
+impl StemmerFilter {
+    pub fn new(stemmer: impl Stemmer + 'static) -> Self {
+        Self {
+            stemmer: Arc::new(stemmer)
+        }
+    }
+
+    /// Create a filter sharing an existing stemmer
+    pub fn from_shared(stemmer: Arc<dyn Stemmer>) -> Self {
+        Self { stemmer }
+    }
+}
+
+impl TokenFilter for StemmerFilter {
+    fn filter(&self, tokens: Vec<Token>) -> Vec<Token> {
+        tokens.iter().map(|token| retext(token, self.stemmer.stem(token.text()))).collect()
+    }
+}
+
+/// Drops tokens shorter than `min` or longer than `max` characters
+#[derive(Debug, Clone, Copy)]
+pub struct LengthFilter {
+    min: usize,
+    max: usize
+}
+
+impl LengthFilter {
+    pub fn new(min: usize, max: usize) -> Self {
+        Self { min, max }
+    }
+}
+
+impl TokenFilter for LengthFilter {
+    fn filter(&self, tokens: Vec<Token>) -> Vec<Token> {
+        tokens
+            .into_iter()
+            .filter(|token| (self.min..=self.max).contains(&token.text().chars().count()))
+            .collect()
+    }
+}
+
+/// Adds the synonyms of a token right after it, at the same position and offsets
+#[derive(Debug, Clone, Default)]
+pub struct SynonymFilter {
+    synonyms: HashMap<String, Vec<String>>
+}
+
+impl SynonymFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register synonyms for a word (one direction only)
+    pub fn add(&mut self, word: impl Into<String>, synonyms: impl IntoIterator<Item = impl Into<String>>) {
+        self.synonyms
+            .entry(word.into())
+            .or_default()
+            .extend(synonyms.into_iter().map(Into::into));
+    }
+}
+
+impl TokenFilter for SynonymFilter {
+    fn filter(&self, tokens: Vec<Token>) -> Vec<Token> {
+        let mut filtered = Vec::with_capacity(tokens.len());
+
+        for token in tokens {
+            let synonyms = self.synonyms.get(token.text()).map(Vec::as_slice).unwrap_or_default();
+            let expanded: Vec<Token> = synonyms.iter().map(|synonym| retext(&token, synonym.as_str())).collect();
+            filtered.push(token);
+            filtered.extend(expanded);
+        }
+
+        filtered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::tokenizer::PorterStemmer;
+
+    fn tokens(words: &[&str]) -> Vec<Token> {
+        words.iter().enumerate().map(|(i, word)| Token::new(*word, i, 0, word.len())).collect()
+    }
+
+    fn texts(tokens: &[Token]) -> Vec<&str> {
+        tokens.iter().map(Token::text).collect()
+    }
+
+    #[test]
+    fn test_filters() {
+        assert_eq!(texts(&LowercaseFilter::new().filter(tokens(&["NASA", "Rust"]))), vec!["nasa", "rust"]);
+        assert_eq!(texts(&LowercaseFilter::preserving_acronyms().filter(tokens(&["NASA", "Rust"]))), vec!["NASA", "rust"]);
+        assert_eq!(texts(&StemmerFilter::new(PorterStemmer::new()).filter(tokens(&["runs", "walked"]))), vec!["run", "walk"]);
+        assert_eq!(texts(&LengthFilter::new(2, 4).filter(tokens(&["a", "bc", "defgh"]))), vec!["bc"]);
+
+        let filtered = StopwordFilter::new(["of"]).filter(tokens(&["bank", "of", "america"]));
+        assert_eq!(texts(&filtered), vec!["bank", "america"]);
+        assert_eq!(filtered[1].position(), 2);
+    }
+}
@@ -0,0 +1,337 @@
+// src/interfaces/cli.rs
+
+//! Command-line interface for the `tfidf` binary.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::application::{ApplicationError, ApplicationResult, CorpusHealthReport, CorpusService, CorpusServiceImpl, DocumentService, DocumentServiceImpl, HealthIssue};
+use crate::infrastructure::persistence::FileStorage;
+use crate::infrastructure::repository::{StorageCorpusRepository, StorageDocumentRepository};
+use crate::infrastructure::tokenizer::SimpleTokenizer;
+
+/// Usage text printed by `tfidf help`
+pub const USAGE: &str = "\
+Usage: tfidf <command> [options]
+
+Commands:
+    doctor [corpus_id] [--repair] [--path <dir>]
+                                     Check corpora for problems, optionally repairing them
+    help                             Show this message
+
+Options:
+    --path <dir>    Storage directory holding the corpora (default: current directory)
+
+The analyzer a corpus was indexed with is not stored, so doctor does not check
+term statistics and --repair is refused; repair from code that configures the
+analyzer instead.";
+
+/// A parsed command-line command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Check one corpus, or all corpora when no ID is given
+    Doctor {
+        corpus_id: Option<String>,
+        repair: bool,
+        path: PathBuf,
+    },
+    
+    /// Print usage
+    Help,
+}
+
+impl Command {
+    /// Parse a command from arguments (excluding the program name)
+    pub fn parse(args: impl IntoIterator<Item = impl Into<String>>) -> ApplicationResult<Self> {
+        let mut args = args.into_iter().map(Into::into);
+        
+        match args.next().as_deref() {
+            Some("doctor") => {
+                let mut corpus_id = None;
+                let mut repair = false;
+                let mut path = PathBuf::from(".");
+                
+                while let Some(arg) = args.next() {
+                    match arg.as_str() {
+                        "--repair" => repair = true,
+                        "--path" => {
+                            path = args.next()
+                                .ok_or_else(|| ApplicationError::InvalidInput("Option '--path' needs a directory".to_string()))?
+                                .into();
+                        },
+                        flag if flag.starts_with("--") => {
+                            return Err(ApplicationError::InvalidInput(format!("Unknown option '{}'", flag)));
+                        },
+                        _ if corpus_id.is_none() => corpus_id = Some(arg),
+                        _ => {
+                            return Err(ApplicationError::InvalidInput(format!("Unexpected argument '{}'", arg)));
+                        }
+                    }
+                }
+                
+                Ok(Command::Doctor { corpus_id, repair, path })
+            },
+            Some("help") | None => Ok(Command::Help),
+            Some(other) => Err(ApplicationError::InvalidInput(format!("Unknown command '{}'", other))),
+        }
+    }
+}
+
+/// Documents in the storage under a directory
+pub type StoredDocuments = StorageDocumentRepository<FileStorage>;
+
+/// Open the corpus service over the file storage in a directory, without the corpora's analyzer
+///
+/// Corpora are stored under `<path>/corpora` and documents under
+/// `<path>/documents`, with corpora referencing their documents by ID. Health
+/// checks skip re-analyzing documents and repairs are refused, since any
+/// analyzer chosen here could differ from the one the corpora were indexed with.
+pub fn open_corpus_service(path: &Path) -> ApplicationResult<impl CorpusService> {
+    let mut corpus_service = open_storage(path, |documents| {
+        DocumentServiceImpl::new(documents, Arc::new(SimpleTokenizer::new()))
+    })?;
+    corpus_service.set_reanalyze_documents(false);
+    Ok(corpus_service)
+}
+
+/// Open the corpus service over the file storage in a directory, analyzing documents with the given service
+///
+/// `document_service` builds the document service over the document
+/// repository, configured the way the corpora were indexed, so health checks
+/// can find stale term statistics and repairs re-analyze documents correctly.
+pub fn open_corpus_service_with<DS, F>(path: &Path, document_service: F) -> ApplicationResult<impl CorpusService>
+where
+    DS: DocumentService,
+    F: FnOnce(Arc<StoredDocuments>) -> DS,
+{
+    open_storage(path, document_service)
+}
+
+fn open_storage<DS, F>(
+    path: &Path,
+    document_service: F
+) -> ApplicationResult<CorpusServiceImpl<StorageCorpusRepository<FileStorage>, StoredDocuments, DS>>
+where
+    DS: DocumentService,
+    F: FnOnce(Arc<StoredDocuments>) -> DS,
+{
+    if !path.is_dir() {
+        return Err(ApplicationError::InvalidInput(format!("Storage directory '{}' does not exist", path.display())));
+    }
+    let open = |name: &str| FileStorage::open(path.join(name)).map_err(|e| {
+        ApplicationError::RepositoryError(format!("Error opening storage '{}': {}", path.join(name).display(), e))
+    });
+
+    let document_repository = Arc::new(StorageDocumentRepository::new(open("documents")?));
+    let mut corpus_repository = StorageCorpusRepository::new(open("corpora")?);
+    corpus_repository.set_document_repository(Some(document_repository.clone()));
+
+    let document_service = Arc::new(document_service(document_repository.clone()));
+    Ok(CorpusServiceImpl::new(Arc::new(corpus_repository), document_repository, document_service))
+}
+
+/// Run a command against the storage it names, writing human-readable output, and return the process exit code
+pub fn execute<W: Write>(command: &Command, out: &mut W) -> ApplicationResult<i32> {
+    match command {
+        Command::Doctor { path, .. } => run(command, &open_corpus_service(path)?, out),
+        Command::Help => {
+            writeln!(out, "{}", USAGE).map_err(io_error)?;
+            Ok(0)
+        },
+    }
+}
+
+/// Run a command, writing human-readable output, and return the process exit code
+pub fn run<CS, W>(command: &Command, corpus_service: &CS, out: &mut W) -> ApplicationResult<i32>
+where
+    CS: CorpusService,
+    W: Write,
+{
+    match command {
+        Command::Help => {
+            writeln!(out, "{}", USAGE).map_err(io_error)?;
+            Ok(0)
+        },
+        Command::Doctor { corpus_id, repair, .. } => {
+            let corpus_ids = match corpus_id {
+                Some(id) => vec![id.clone()],
+                None => corpus_service.list_corpora()?
+                    .iter()
+                    .map(|corpus| corpus.id().value().to_string())
+                    .collect(),
+            };
+            
+            if corpus_ids.is_empty() {
+                writeln!(out, "No corpora found").map_err(io_error)?;
+                return Ok(0);
+            }
+            
+            let mut exit_code = 0;
+            for id in corpus_ids {
+                let report = if *repair {
+                    corpus_service.repair_corpus(&id)?
+                } else {
+                    corpus_service.health_check(&id)?
+                };
+                
+                write_report(&report, out)?;
+                
+                if !report.is_healthy() && !report.is_repaired() {
+                    exit_code = 1;
+                }
+            }
+            
+            Ok(exit_code)
+        },
+    }
+}
+
+fn write_report<W: Write>(report: &CorpusHealthReport, out: &mut W) -> ApplicationResult<()> {
+    let status = if report.is_healthy() {
+        "ok"
+    } else if report.is_repaired() {
+        "repaired"
+    } else {
+        "unhealthy"
+    };
+    
+    writeln!(out, "corpus '{}': {}", report.corpus_id().value(), status).map_err(io_error)?;
+    
+    for issue in report.issues() {
+        let description = match issue {
+            HealthIssue::NotIndexed => "corpus is not indexed".to_string(),
+            HealthIssue::MissingDocument(id) => format!("document '{}' is missing from the repository", id.value()),
+            HealthIssue::StaleDocument(id) => format!("document '{}' has stale term statistics", id.value()),
+            HealthIssue::StaleIndex => "document frequency index is stale".to_string(),
+        };
+        writeln!(out, "  - {}", description).map_err(io_error)?;
+    }
+    
+    Ok(())
+}
+
+fn io_error(e: std::io::Error) -> ApplicationError {
+    ApplicationError::Other(format!("Error writing output: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::application::{CorpusServiceImpl, DocumentService, DocumentServiceImpl};
+    use crate::domain::{Corpus, CorpusId, Document, DocumentId};
+    use crate::infrastructure::repository::{CorpusRepository, DocumentRepository, InMemoryCorpusRepository, InMemoryDocumentRepository};
+    use crate::infrastructure::tokenizer::PorterStemmer;
+    
+    #[test]
+    fn test_parse() {
+        assert_eq!(Command::parse(Vec::<String>::new()).unwrap(), Command::Help);
+        assert_eq!(
+            Command::parse(["doctor", "corpus1", "--repair"]).unwrap(),
+            Command::Doctor { corpus_id: Some("corpus1".to_string()), repair: true, path: PathBuf::from(".") }
+        );
+        assert_eq!(
+            Command::parse(["doctor", "--path", "/data"]).unwrap(),
+            Command::Doctor { corpus_id: None, repair: false, path: PathBuf::from("/data") }
+        );
+        assert!(Command::parse(["doctor", "--force"]).is_err());
+        assert!(Command::parse(["doctor", "--path"]).is_err());
+        assert!(Command::parse(["unknown"]).is_err());
+    }
+    
+    #[test]
+    fn test_doctor() {
+        let document_repository = Arc::new(InMemoryDocumentRepository::new());
+        let document_service = Arc::new(DocumentServiceImpl::new(
+            document_repository.clone(),
+            Arc::new(SimpleTokenizer::new()),
+        ));
+        let corpus_service = CorpusServiceImpl::new(
+            Arc::new(InMemoryCorpusRepository::new()),
+            document_repository,
+            document_service.clone(),
+        );
+        
+        document_service.create_document("doc1", "Document one").unwrap();
+        corpus_service.create_corpus("corpus1", "Test Corpus").unwrap();
+        corpus_service.add_document("corpus1", "doc1").unwrap();
+        
+        let mut out = Vec::new();
+        let command = Command::Doctor { corpus_id: None, repair: false, path: PathBuf::from(".") };
+        assert_eq!(run(&command, &corpus_service, &mut out).unwrap(), 1);
+        assert!(String::from_utf8(out).unwrap().contains("corpus is not indexed"));
+        
+        let mut out = Vec::new();
+        let command = Command::Doctor { corpus_id: Some("corpus1".to_string()), repair: true, path: PathBuf::from(".") };
+        assert_eq!(run(&command, &corpus_service, &mut out).unwrap(), 0);
+        assert!(String::from_utf8(out).unwrap().contains("repaired"));
+    }
+    
+    #[test]
+    fn test_doctor_on_disk() {
+        let root = std::env::temp_dir().join(format!("tf-idf-rs-doctor-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        
+        // Write a corpus the way an earlier run would have left it
+        let documents = Arc::new(StorageDocumentRepository::new(FileStorage::open(root.join("documents")).unwrap()));
+        let mut corpora = StorageCorpusRepository::new(FileStorage::open(root.join("corpora")).unwrap());
+        corpora.set_document_repository(Some(documents.clone()));
+        let mut corpus = Corpus::new("corpus1", "Test Corpus");
+        for document in [Document::new("doc1", "Document one"), Document::new("doc2", "Document two")] {
+            documents.save(&document).unwrap();
+            corpus.add_document(document).unwrap();
+        }
+        corpora.save(&corpus).unwrap();
+        documents.delete(&DocumentId::new("doc2")).unwrap();
+        
+        let mut out = Vec::new();
+        let command = Command::parse(["doctor", "--path", root.to_str().unwrap()]).unwrap();
+        assert_eq!(execute(&command, &mut out).unwrap(), 1);
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("corpus 'corpus1': unhealthy"));
+        assert!(out.contains("corpus is not indexed"));
+        assert!(out.contains("document 'doc2' is missing from the repository"));
+        
+        // Without the corpus's analyzer, repairing is refused
+        let command = Command::parse(["doctor", "corpus1", "--repair", "--path", root.to_str().unwrap()]).unwrap();
+        assert!(execute(&command, &mut Vec::new()).is_err());
+        assert_eq!(corpora.find(&CorpusId::new("corpus1")).unwrap().unwrap().document_count(), 2);
+        
+        let corpus_service = open_corpus_service_with(&root, |documents| {
+            DocumentServiceImpl::new(documents, Arc::new(SimpleTokenizer::new()))
+        }).unwrap();
+        assert_eq!(run(&command, &corpus_service, &mut Vec::new()).unwrap(), 0);
+        assert_eq!(corpora.find(&CorpusId::new("corpus1")).unwrap().unwrap().document_count(), 1);
+        
+        let command = Command::parse(["doctor", "--path", root.join("missing").to_str().unwrap()]).unwrap();
+        assert!(execute(&command, &mut Vec::new()).is_err());
+        
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+    
+    #[test]
+    fn test_doctor_with_stemmed_corpus() {
+        let root = std::env::temp_dir().join(format!("tf-idf-rs-doctor-stemmed-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        let stemmed = |documents| {
+            DocumentServiceImpl::with_stemmer(documents, Arc::new(SimpleTokenizer::new()), Arc::new(PorterStemmer::new()))
+        };
+        
+        let corpus_service = open_corpus_service_with(&root, stemmed).unwrap();
+        let documents = Arc::new(StorageDocumentRepository::new(FileStorage::open(root.join("documents")).unwrap()));
+        stemmed(documents).create_document("doc1", "Running runners run").unwrap();
+        corpus_service.create_corpus("corpus1", "Test Corpus").unwrap();
+        corpus_service.add_document("corpus1", "doc1").unwrap();
+        corpus_service.build_index("corpus1").unwrap();
+        
+        // Neither the analyzer-less binary nor the stemming service reports the stemmed terms as stale
+        let command = Command::parse(["doctor", "--path", root.to_str().unwrap()]).unwrap();
+        let mut out = Vec::new();
+        assert_eq!(execute(&command, &mut out).unwrap(), 0);
+        assert!(String::from_utf8(out).unwrap().contains("corpus 'corpus1': ok"));
+        assert_eq!(run(&command, &corpus_service, &mut Vec::new()).unwrap(), 0);
+        
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
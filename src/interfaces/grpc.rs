@@ -0,0 +1,460 @@
+// src/interfaces/grpc.rs
+
+//! gRPC server for the document, corpus and search services.
+//!
+//! The messages and service traits are generated from `proto/tfidf.proto`.
+//! `GrpcApi` implements each RPC against the application services and can be
+//! tested without a socket; `serve` runs it as a tonic server, calling the
+//! blocking services off the async runtime. `SearchStream` ranks the hits
+//! on a blocking thread, which then converts and sends them one message at
+//! a time through a bounded channel, so a slow client holds back the sender
+//! instead of the whole reply being buffered.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use tokio::net::TcpListener;
+use tokio::sync::{mpsc, oneshot};
+use tokio_stream::wrappers::{ReceiverStream, TcpListenerStream};
+use tonic::{Code, Request, Response, Status};
+
+use crate::application::{ApplicationError, CorpusService, DocumentService, SearchService};
+use crate::domain::{Corpus, Document, SearchHit};
+
+/// Messages, service traits and clients generated from `proto/tfidf.proto`
+pub mod proto {
+    tonic::include_proto!("tfidf.v1");
+}
+
+pub use proto::{
+    CorpusDocumentRequest, CorpusReply, CreateCorpusRequest, CreateDocumentRequest, DocumentReply, SearchRequest,
+    SearchHit as SearchHitReply, UpdateDocumentRequest,
+};
+
+use proto::corpus_api_server::{CorpusApi, CorpusApiServer};
+use proto::document_api_server::{DocumentApi, DocumentApiServer};
+use proto::search_api_server::{SearchApi, SearchApiServer};
+
+/// Number of hits `SearchStream` buffers ahead of the client
+const STREAM_BUFFER: usize = 32;
+
+impl From<ApplicationError> for Status {
+    fn from(e: ApplicationError) -> Self {
+        let code = match &e {
+            ApplicationError::NotFound(_) => Code::NotFound,
+            ApplicationError::InvalidInput(_) => Code::InvalidArgument,
+            ApplicationError::NotPermitted(_) | ApplicationError::DomainError(_) => Code::FailedPrecondition,
+            ApplicationError::RepositoryError(_) | ApplicationError::Other(_) => Code::Internal,
+        };
+        Status::new(code, e.to_string())
+    }
+}
+
+/// Result of an RPC
+pub type GrpcResult<T> = Result<T, Status>;
+
+impl From<&Document> for DocumentReply {
+    fn from(document: &Document) -> Self {
+        Self {
+            id: document.id().value().to_string(),
+            title: document.title().map(str::to_string),
+            content: document.content().to_string(),
+            metadata: document.metadata().clone(),
+            term_count: document.term_count() as u64,
+        }
+    }
+}
+
+impl From<&Corpus> for CorpusReply {
+    fn from(corpus: &Corpus) -> Self {
+        Self {
+            id: corpus.id().value().to_string(),
+            name: corpus.name().to_string(),
+            description: corpus.description().map(str::to_string),
+            document_count: corpus.document_count() as u64,
+            indexed: corpus.is_indexed(),
+        }
+    }
+}
+
+impl From<&SearchHit> for SearchHitReply {
+    fn from(hit: &SearchHit) -> Self {
+        Self {
+            document_id: hit.document_id().value().to_string(),
+            score: hit.score(),
+        }
+    }
+}
+
+/// Handlers for the `DocumentApi`, `CorpusApi` and `SearchApi` services
+pub struct GrpcApi<DS, CS, SS>
+where
+    DS: DocumentService,
+    CS: CorpusService,
+    SS: SearchService,
+{
+    document_service: Arc<DS>,
+    corpus_service: Arc<CS>,
+    search_service: Arc<SS>,
+}
+
+impl<DS, CS, SS> GrpcApi<DS, CS, SS>
+where
+    DS: DocumentService,
+    CS: CorpusService,
+    SS: SearchService,
+{
+    /// Create a new GrpcApi
+    pub fn new(document_service: Arc<DS>, corpus_service: Arc<CS>, search_service: Arc<SS>) -> Self {
+        Self {
+            document_service,
+            corpus_service,
+            search_service,
+        }
+    }
+
+    /// `DocumentApi.CreateDocument`
+    pub fn create_document(&self, request: CreateDocumentRequest) -> GrpcResult<DocumentReply> {
+        let document = match &request.title {
+            Some(title) => self.document_service.create_document_with_title(&request.id, title, &request.content)?,
+            None => self.document_service.create_document(&request.id, &request.content)?,
+        };
+        Ok(DocumentReply::from(&document))
+    }
+
+    /// `DocumentApi.GetDocument`
+    pub fn get_document(&self, id: &str) -> GrpcResult<DocumentReply> {
+        Ok(DocumentReply::from(&*self.document_service.get_document(id)?))
+    }
+
+    /// `DocumentApi.UpdateDocument`
+    pub fn update_document(&self, request: UpdateDocumentRequest) -> GrpcResult<DocumentReply> {
+        let mut document = self.document_service.get_document(&request.id)?;
+        if let Some(title) = &request.title {
+            document = self.document_service.update_title(&request.id, title)?.into();
+        }
+        if let Some(content) = &request.content {
+            document = self.document_service.update_content(&request.id, content)?.into();
+        }
+        Ok(DocumentReply::from(&*document))
+    }
+
+    /// `DocumentApi.DeleteDocument`, which also removes the document from every corpus
+    pub fn delete_document(&self, id: &str) -> GrpcResult<()> {
+        self.corpus_service.delete_document(id)?;
+        Ok(())
+    }
+
+    /// `CorpusApi.CreateCorpus`
+    pub fn create_corpus(&self, request: CreateCorpusRequest) -> GrpcResult<CorpusReply> {
+        let corpus = match &request.description {
+            Some(description) => self.corpus_service.create_corpus_with_description(&request.id, &request.name, description)?,
+            None => self.corpus_service.create_corpus(&request.id, &request.name)?,
+        };
+        Ok(CorpusReply::from(&corpus))
+    }
+
+    /// `CorpusApi.GetCorpus`
+    pub fn get_corpus(&self, id: &str) -> GrpcResult<CorpusReply> {
+        Ok(CorpusReply::from(&self.corpus_service.get_corpus(id)?))
+    }
+
+    /// `CorpusApi.DeleteCorpus`
+    pub fn delete_corpus(&self, id: &str) -> GrpcResult<()> {
+        Ok(self.corpus_service.delete_corpus(id)?)
+    }
+
+    /// `CorpusApi.AddDocument`
+    pub fn add_document(&self, request: CorpusDocumentRequest) -> GrpcResult<CorpusReply> {
+        Ok(CorpusReply::from(&self.corpus_service.add_document(&request.corpus_id, &request.document_id)?))
+    }
+
+    /// `CorpusApi.RemoveDocument`
+    pub fn remove_document(&self, request: CorpusDocumentRequest) -> GrpcResult<CorpusReply> {
+        Ok(CorpusReply::from(&self.corpus_service.remove_document(&request.corpus_id, &request.document_id)?))
+    }
+
+    /// `CorpusApi.BuildIndex`
+    pub fn build_index(&self, id: &str) -> GrpcResult<CorpusReply> {
+        Ok(CorpusReply::from(&self.corpus_service.build_index(id)?))
+    }
+
+    /// `SearchApi.Search`
+    pub fn search(&self, request: SearchRequest) -> GrpcResult<Vec<SearchHitReply>> {
+        Ok(self.search_stream(request)?.collect())
+    }
+
+    /// `SearchApi.SearchStream`, yielding hits best first
+    ///
+    /// Ranking needs every score, so the hits are found up front; they are
+    /// converted to messages as the iterator is consumed.
+    pub fn search_stream(&self, request: SearchRequest) -> GrpcResult<impl Iterator<Item = SearchHitReply> + use<DS, CS, SS>> {
+        let hits = self.search_service.search_hits(&request.corpus_id, &request.query)?;
+        let limit = if request.limit == 0 { usize::MAX } else { request.limit as usize };
+        Ok(hits.into_iter().take(limit).map(|hit| SearchHitReply::from(&hit)))
+    }
+}
+
+/// Serve the API over gRPC on a listener until the server fails
+pub async fn serve<DS, CS, SS>(api: Arc<GrpcApi<DS, CS, SS>>, listener: TcpListener) -> Result<(), tonic::transport::Error>
+where
+    DS: DocumentService + Send + Sync + 'static,
+    CS: CorpusService + Send + Sync + 'static,
+    SS: SearchService + Send + Sync + 'static,
+{
+    let server = GrpcServer { api };
+    tonic::transport::Server::builder()
+        .add_service(DocumentApiServer::new(server.clone()))
+        .add_service(CorpusApiServer::new(server.clone()))
+        .add_service(SearchApiServer::new(server))
+        .serve_with_incoming(TcpListenerStream::new(listener))
+        .await
+}
+
+/// Bind an address and serve the API over gRPC on it
+pub async fn serve_on<DS, CS, SS>(api: Arc<GrpcApi<DS, CS, SS>>, address: SocketAddr) -> std::io::Result<()>
+where
+    DS: DocumentService + Send + Sync + 'static,
+    CS: CorpusService + Send + Sync + 'static,
+    SS: SearchService + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind(address).await?;
+    serve(api, listener).await.map_err(std::io::Error::other)
+}
+
+/// The generated service traits, implemented by forwarding to a shared `GrpcApi`
+struct GrpcServer<DS, CS, SS>
+where
+    DS: DocumentService,
+    CS: CorpusService,
+    SS: SearchService,
+{
+    api: Arc<GrpcApi<DS, CS, SS>>,
+}
+
+impl<DS, CS, SS> Clone for GrpcServer<DS, CS, SS>
+where
+    DS: DocumentService,
+    CS: CorpusService,
+    SS: SearchService,
+{
+    fn clone(&self) -> Self {
+        Self { api: Arc::clone(&self.api) }
+    }
+}
+
+impl<DS, CS, SS> GrpcServer<DS, CS, SS>
+where
+    DS: DocumentService + Send + Sync + 'static,
+    CS: CorpusService + Send + Sync + 'static,
+    SS: SearchService + Send + Sync + 'static,
+{
+    /// Run a handler on the blocking thread pool, since the services block on their repositories
+    async fn call<T: Send + 'static>(
+        &self,
+        handler: impl FnOnce(&GrpcApi<DS, CS, SS>) -> GrpcResult<T> + Send + 'static,
+    ) -> Result<Response<T>, Status> {
+        let api = Arc::clone(&self.api);
+        match tokio::task::spawn_blocking(move || handler(&api)).await {
+            Ok(result) => result.map(Response::new),
+            Err(e) => Err(Status::internal(format!("Handler failed: {}", e))),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl<DS, CS, SS> DocumentApi for GrpcServer<DS, CS, SS>
+where
+    DS: DocumentService + Send + Sync + 'static,
+    CS: CorpusService + Send + Sync + 'static,
+    SS: SearchService + Send + Sync + 'static,
+{
+    async fn create_document(&self, request: Request<CreateDocumentRequest>) -> Result<Response<DocumentReply>, Status> {
+        let request = request.into_inner();
+        self.call(move |api| api.create_document(request)).await
+    }
+
+    async fn get_document(&self, request: Request<proto::DocumentIdRequest>) -> Result<Response<DocumentReply>, Status> {
+        let id = request.into_inner().id;
+        self.call(move |api| api.get_document(&id)).await
+    }
+
+    async fn update_document(&self, request: Request<UpdateDocumentRequest>) -> Result<Response<DocumentReply>, Status> {
+        let request = request.into_inner();
+        self.call(move |api| api.update_document(request)).await
+    }
+
+    async fn delete_document(&self, request: Request<proto::DocumentIdRequest>) -> Result<Response<proto::Empty>, Status> {
+        let id = request.into_inner().id;
+        self.call(move |api| api.delete_document(&id).map(|_| proto::Empty {})).await
+    }
+}
+
+#[tonic::async_trait]
+impl<DS, CS, SS> CorpusApi for GrpcServer<DS, CS, SS>
+where
+    DS: DocumentService + Send + Sync + 'static,
+    CS: CorpusService + Send + Sync + 'static,
+    SS: SearchService + Send + Sync + 'static,
+{
+    async fn create_corpus(&self, request: Request<CreateCorpusRequest>) -> Result<Response<CorpusReply>, Status> {
+        let request = request.into_inner();
+        self.call(move |api| api.create_corpus(request)).await
+    }
+
+    async fn get_corpus(&self, request: Request<proto::CorpusIdRequest>) -> Result<Response<CorpusReply>, Status> {
+        let id = request.into_inner().id;
+        self.call(move |api| api.get_corpus(&id)).await
+    }
+
+    async fn delete_corpus(&self, request: Request<proto::CorpusIdRequest>) -> Result<Response<proto::Empty>, Status> {
+        let id = request.into_inner().id;
+        self.call(move |api| api.delete_corpus(&id).map(|_| proto::Empty {})).await
+    }
+
+    async fn add_document(&self, request: Request<CorpusDocumentRequest>) -> Result<Response<CorpusReply>, Status> {
+        let request = request.into_inner();
+        self.call(move |api| api.add_document(request)).await
+    }
+
+    async fn remove_document(&self, request: Request<CorpusDocumentRequest>) -> Result<Response<CorpusReply>, Status> {
+        let request = request.into_inner();
+        self.call(move |api| api.remove_document(request)).await
+    }
+
+    async fn build_index(&self, request: Request<proto::CorpusIdRequest>) -> Result<Response<CorpusReply>, Status> {
+        let id = request.into_inner().id;
+        self.call(move |api| api.build_index(&id)).await
+    }
+}
+
+#[tonic::async_trait]
+impl<DS, CS, SS> SearchApi for GrpcServer<DS, CS, SS>
+where
+    DS: DocumentService + Send + Sync + 'static,
+    CS: CorpusService + Send + Sync + 'static,
+    SS: SearchService + Send + Sync + 'static,
+{
+    type SearchStreamStream = ReceiverStream<Result<SearchHitReply, Status>>;
+
+    async fn search(&self, request: Request<SearchRequest>) -> Result<Response<proto::SearchReply>, Status> {
+        let request = request.into_inner();
+        self.call(move |api| api.search(request).map(|hits| proto::SearchReply { hits })).await
+    }
+
+    async fn search_stream(&self, request: Request<SearchRequest>) -> Result<Response<Self::SearchStreamStream>, Status> {
+        let request = request.into_inner();
+        let api = Arc::clone(&self.api);
+        let (sender, receiver) = mpsc::channel(STREAM_BUFFER);
+        let (started, outcome) = oneshot::channel();
+
+        // The search blocks, so it runs and feeds the channel from the blocking pool;
+        // a failed search is reported as the RPC status rather than as a stream item
+        tokio::task::spawn_blocking(move || {
+            let hits = match api.search_stream(request) {
+                Ok(hits) => hits,
+                Err(status) => {
+                    let _ = started.send(Err(status));
+                    return;
+                },
+            };
+            if started.send(Ok(())).is_err() {
+                return;
+            }
+            for hit in hits {
+                // Stop once the client hangs up
+                if sender.blocking_send(Ok(hit)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        match outcome.await {
+            Ok(Ok(())) => Ok(Response::new(ReceiverStream::new(receiver))),
+            Ok(Err(status)) => Err(status),
+            Err(e) => Err(Status::internal(format!("Handler failed: {}", e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::{CorpusServiceImpl, DocumentServiceImpl, SearchServiceImpl};
+    use crate::domain::TfIdf;
+    use crate::infrastructure::repository::{InMemoryCorpusRepository, InMemoryDocumentRepository};
+    use crate::infrastructure::tokenizer::SimpleTokenizer;
+
+    type Api = GrpcApi<
+        DocumentServiceImpl<InMemoryDocumentRepository, SimpleTokenizer>,
+        CorpusServiceImpl<InMemoryCorpusRepository, InMemoryDocumentRepository, DocumentServiceImpl<InMemoryDocumentRepository, SimpleTokenizer>>,
+        SearchServiceImpl<InMemoryCorpusRepository, SimpleTokenizer>,
+    >;
+
+    fn create_api() -> Api {
+        let document_repository = Arc::new(InMemoryDocumentRepository::new());
+        let corpus_repository = Arc::new(InMemoryCorpusRepository::new());
+        let tokenizer = Arc::new(SimpleTokenizer::new());
+        let document_service = Arc::new(DocumentServiceImpl::new(document_repository.clone(), tokenizer.clone()));
+        let corpus_service = Arc::new(CorpusServiceImpl::new(corpus_repository.clone(), document_repository, document_service.clone()));
+        let search_service = Arc::new(SearchServiceImpl::new(corpus_repository, tokenizer, TfIdf::default()));
+        GrpcApi::new(document_service, corpus_service, search_service)
+    }
+
+    #[test]
+    fn test_rpcs() {
+        let api = create_api();
+
+        api.create_corpus(CreateCorpusRequest { id: "c1".into(), name: "Languages".into(), description: None }).unwrap();
+        for (id, content) in [("doc1", "rust is safe"), ("doc2", "rust is fast and safe"), ("doc3", "go is simple"), ("doc4", "python is popular")] {
+            api.create_document(CreateDocumentRequest { id: id.into(), content: content.into(), title: None }).unwrap();
+            api.add_document(CorpusDocumentRequest { corpus_id: "c1".into(), document_id: id.into() }).unwrap();
+        }
+        assert!(api.build_index("c1").unwrap().indexed);
+
+        let request = SearchRequest { corpus_id: "c1".into(), query: "safe".into(), limit: 0 };
+        let mut stream = api.search_stream(request.clone()).unwrap();
+        assert!(stream.next().is_some());
+        assert_eq!(api.search(request.clone()).unwrap().len(), 2);
+        assert_eq!(api.search(SearchRequest { limit: 1, ..request }).unwrap().len(), 1);
+
+        assert_eq!(api.get_document("missing").unwrap_err().code(), Code::NotFound);
+        let status = api.create_corpus(CreateCorpusRequest { id: "c1".into(), name: "Again".into(), description: None }).unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_serve_streaming_search() {
+        use proto::corpus_api_client::CorpusApiClient;
+        use proto::document_api_client::DocumentApiClient;
+        use proto::search_api_client::SearchApiClient;
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(serve(Arc::new(create_api()), listener));
+
+        let mut documents = DocumentApiClient::connect(address.clone()).await.unwrap();
+        let mut corpora = CorpusApiClient::connect(address.clone()).await.unwrap();
+        corpora.create_corpus(CreateCorpusRequest { id: "c1".into(), name: "Numbers".into(), description: None }).await.unwrap();
+        for n in 0..100 {
+            let id = format!("doc{:03}", n);
+            documents.create_document(CreateDocumentRequest { id: id.clone(), content: format!("{} term{}", if n % 2 == 0 { "common" } else { "rare" }, n), title: None }).await.unwrap();
+            corpora.add_document(CorpusDocumentRequest { corpus_id: "c1".into(), document_id: id }).await.unwrap();
+        }
+        corpora.build_index(proto::CorpusIdRequest { id: "c1".into() }).await.unwrap();
+
+        let mut search = SearchApiClient::connect(address).await.unwrap();
+        let request = SearchRequest { corpus_id: "c1".into(), query: "common".into(), limit: 0 };
+        let mut stream = search.search_stream(request.clone()).await.unwrap().into_inner();
+        let mut received = 0;
+        while let Some(hit) = stream.message().await.unwrap() {
+            assert!(hit.document_id.starts_with("doc"));
+            received += 1;
+        }
+        assert_eq!(received, 50);
+        assert_eq!(search.search(request).await.unwrap().into_inner().hits.len(), 50);
+
+        let status = search.search_stream(SearchRequest { corpus_id: "missing".into(), query: "common".into(), limit: 0 }).await.unwrap_err();
+        assert_eq!(status.code(), Code::NotFound);
+    }
+}
@@ -0,0 +1,502 @@
+// src/interfaces/http/api.rs
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::{Serialize, Deserialize};
+
+use crate::application::{ApplicationError, ApplicationResult, CorpusService, DocumentService, SearchService};
+use crate::domain::{Corpus, Document, DomainError, Page, PageRequest, SearchHit};
+
+use super::HttpResponse;
+
+/// Body of `POST /documents`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateDocumentRequest {
+    pub id: String,
+    pub content: String,
+    #[serde(default)]
+    pub title: Option<String>,
+}
+
+/// Body of `PUT /documents/{id}`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateDocumentRequest {
+    #[serde(default)]
+    pub content: Option<String>,
+    #[serde(default)]
+    pub title: Option<String>,
+}
+
+/// Body of `POST /corpora`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateCorpusRequest {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Body of `POST /corpora/{id}/documents`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddDocumentRequest {
+    pub document_id: String,
+}
+
+/// A document as returned by the API, without its term statistics
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DocumentResponse {
+    pub id: String,
+    pub title: Option<String>,
+    pub content: String,
+    pub metadata: HashMap<String, String>,
+    pub term_count: usize,
+}
+
+impl From<&Document> for DocumentResponse {
+    fn from(document: &Document) -> Self {
+        Self {
+            id: document.id().value().to_string(),
+            title: document.title().map(str::to_string),
+            content: document.content().to_string(),
+            metadata: document.metadata().clone(),
+            term_count: document.term_count(),
+        }
+    }
+}
+
+/// A corpus as returned by the API, without its documents
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CorpusResponse {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub document_count: usize,
+    pub indexed: bool,
+}
+
+impl From<&Corpus> for CorpusResponse {
+    fn from(corpus: &Corpus) -> Self {
+        Self {
+            id: corpus.id().value().to_string(),
+            name: corpus.name().to_string(),
+            description: corpus.description().map(str::to_string),
+            document_count: corpus.document_count(),
+            indexed: corpus.is_indexed(),
+        }
+    }
+}
+
+/// A search result as returned by the API
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchHitResponse {
+    pub document_id: String,
+    pub score: f64,
+}
+
+impl From<&SearchHit> for SearchHitResponse {
+    fn from(hit: &SearchHit) -> Self {
+        Self {
+            document_id: hit.document_id().value().to_string(),
+            score: hit.score(),
+        }
+    }
+}
+
+/// Body of every error response
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+/// Query string of the list endpoints: `?offset=20&limit=10` skips 20 results and returns at most 10
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageQuery {
+    #[serde(default)]
+    pub offset: Option<usize>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+impl PageQuery {
+    /// Get the requested window, from the first result and without a limit by default
+    pub fn page_request(&self) -> PageRequest {
+        PageRequest::new(self.offset.unwrap_or(0), self.limit.unwrap_or(usize::MAX))
+    }
+}
+
+/// Query string of `GET /corpora/{id}/search`, paged like the list endpoints
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchQuery {
+    #[serde(default)]
+    pub q: Option<String>,
+    #[serde(default)]
+    pub offset: Option<usize>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// Answers HTTP requests with the document, corpus and search services
+///
+/// `router` routes each endpoint to one method here:
+///
+/// | Method | Path | Action |
+/// |---|---|---|
+/// | GET, POST | `/documents` | List or create documents |
+/// | GET, PUT, DELETE | `/documents/{id}` | Get, update or delete a document |
+/// | GET, POST | `/corpora` | List or create corpora |
+/// | GET, DELETE | `/corpora/{id}` | Get or delete a corpus |
+/// | GET, POST | `/corpora/{id}/documents` | List a corpus's documents or add one |
+/// | DELETE | `/corpora/{id}/documents/{document_id}` | Remove a document from a corpus |
+/// | POST | `/corpora/{id}/index` | Rebuild a corpus's index |
+/// | GET | `/corpora/{id}/search?q=...` | Search a corpus |
+///
+/// The GET endpoints returning lists take `offset` and `limit` query parameters.
+pub struct HttpApi<DS, CS, SS>
+where
+    DS: DocumentService,
+    CS: CorpusService,
+    SS: SearchService,
+{
+    document_service: Arc<DS>,
+    corpus_service: Arc<CS>,
+    search_service: Arc<SS>,
+}
+
+impl<DS, CS, SS> HttpApi<DS, CS, SS>
+where
+    DS: DocumentService,
+    CS: CorpusService,
+    SS: SearchService,
+{
+    /// Create a new HttpApi
+    pub fn new(document_service: Arc<DS>, corpus_service: Arc<CS>, search_service: Arc<SS>) -> Self {
+        Self {
+            document_service,
+            corpus_service,
+            search_service,
+        }
+    }
+
+    /// Turn the result of an endpoint into a response, mapping application errors to status codes
+    pub fn respond(result: ApplicationResult<HttpResponse>) -> HttpResponse {
+        result.unwrap_or_else(|e| {
+            let status = match &e {
+                ApplicationError::NotFound(_) => 404,
+                ApplicationError::InvalidInput(_) => 400,
+                ApplicationError::NotPermitted(_) => 409,
+                ApplicationError::DomainError(DomainError::Duplicate(_)) => 409,
+                ApplicationError::DomainError(_) => 422,
+                ApplicationError::RepositoryError(_) | ApplicationError::Other(_) => 500,
+            };
+            error_response(status, e.to_string())
+        })
+    }
+
+    /// `GET /documents`
+    pub fn list_documents(&self, query: PageQuery) -> ApplicationResult<HttpResponse> {
+        let page = self.document_service.list_documents_page(query.page_request())?;
+        ok(200, page.items().iter().map(|document| DocumentResponse::from(&**document)).collect::<Vec<_>>())
+    }
+
+    /// `POST /documents`
+    pub fn create_document(&self, body: CreateDocumentRequest) -> ApplicationResult<HttpResponse> {
+        let document = match &body.title {
+            Some(title) => self.document_service.create_document_with_title(&body.id, title, &body.content)?,
+            None => self.document_service.create_document(&body.id, &body.content)?,
+        };
+        ok(201, DocumentResponse::from(&document))
+    }
+
+    /// `GET /documents/{id}`
+    pub fn get_document(&self, id: &str) -> ApplicationResult<HttpResponse> {
+        ok(200, DocumentResponse::from(&*self.document_service.get_document(id)?))
+    }
+
+    /// `PUT /documents/{id}`
+    pub fn update_document(&self, id: &str, body: UpdateDocumentRequest) -> ApplicationResult<HttpResponse> {
+        let mut document = self.document_service.get_document(id)?;
+        if let Some(title) = &body.title {
+            document = self.document_service.update_title(id, title)?.into();
+        }
+        if let Some(content) = &body.content {
+            document = self.document_service.update_content(id, content)?.into();
+        }
+        ok(200, DocumentResponse::from(&*document))
+    }
+
+    /// `DELETE /documents/{id}`, removing the document from every corpus too
+    pub fn delete_document(&self, id: &str) -> ApplicationResult<HttpResponse> {
+        self.corpus_service.delete_document(id)?;
+        Ok(HttpResponse::empty(204))
+    }
+
+    /// `GET /corpora`
+    pub fn list_corpora(&self, query: PageQuery) -> ApplicationResult<HttpResponse> {
+        let page = self.corpus_service.list_corpora_page(query.page_request())?;
+        ok(200, page.items().iter().map(CorpusResponse::from).collect::<Vec<_>>())
+    }
+
+    /// `POST /corpora`
+    pub fn create_corpus(&self, body: CreateCorpusRequest) -> ApplicationResult<HttpResponse> {
+        let corpus = match &body.description {
+            Some(description) => self.corpus_service.create_corpus_with_description(&body.id, &body.name, description)?,
+            None => self.corpus_service.create_corpus(&body.id, &body.name)?,
+        };
+        ok(201, CorpusResponse::from(&corpus))
+    }
+
+    /// `GET /corpora/{id}`
+    pub fn get_corpus(&self, id: &str) -> ApplicationResult<HttpResponse> {
+        ok(200, CorpusResponse::from(&self.corpus_service.get_corpus(id)?))
+    }
+
+    /// `DELETE /corpora/{id}`
+    pub fn delete_corpus(&self, id: &str) -> ApplicationResult<HttpResponse> {
+        self.corpus_service.delete_corpus(id)?;
+        Ok(HttpResponse::empty(204))
+    }
+
+    /// `GET /corpora/{id}/documents`
+    pub fn list_corpus_documents(&self, id: &str, query: PageQuery) -> ApplicationResult<HttpResponse> {
+        let page = Page::from_vec(self.corpus_service.get_corpus_documents(id)?, query.page_request());
+        ok(200, page.items().iter().map(|document| DocumentResponse::from(&**document)).collect::<Vec<_>>())
+    }
+
+    /// `POST /corpora/{id}/documents`
+    pub fn add_document(&self, id: &str, body: AddDocumentRequest) -> ApplicationResult<HttpResponse> {
+        ok(200, CorpusResponse::from(&self.corpus_service.add_document(id, &body.document_id)?))
+    }
+
+    /// `DELETE /corpora/{id}/documents/{document_id}`
+    pub fn remove_document(&self, id: &str, document_id: &str) -> ApplicationResult<HttpResponse> {
+        ok(200, CorpusResponse::from(&self.corpus_service.remove_document(id, document_id)?))
+    }
+
+    /// `POST /corpora/{id}/index`
+    pub fn build_index(&self, id: &str) -> ApplicationResult<HttpResponse> {
+        ok(200, CorpusResponse::from(&self.corpus_service.build_index(id)?))
+    }
+
+    /// `GET /corpora/{id}/search?q=...`
+    pub fn search(&self, id: &str, query: SearchQuery) -> ApplicationResult<HttpResponse> {
+        let text = query.q.as_deref().ok_or_else(|| {
+            ApplicationError::InvalidInput("Missing query parameter 'q'".to_string())
+        })?;
+        let request = PageQuery { offset: query.offset, limit: query.limit }.page_request();
+        let page = Page::from_vec(self.search_service.search_hits(id, text)?, request);
+        ok(200, page.items().iter().map(SearchHitResponse::from).collect::<Vec<_>>())
+    }
+}
+
+fn ok(status: u16, body: impl Serialize) -> ApplicationResult<HttpResponse> {
+    let body = serde_json::to_value(body).map_err(|e| {
+        ApplicationError::Other(format!("Error serializing response: {}", e))
+    })?;
+    Ok(HttpResponse::json(status, body))
+}
+
+pub(super) fn error_response(status: u16, error: String) -> HttpResponse {
+    HttpResponse::json(status, serde_json::json!(ErrorResponse { error }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::{CorpusServiceImpl, DocumentServiceImpl, SearchServiceImpl};
+    use crate::domain::TfIdf;
+    use crate::infrastructure::repository::{InMemoryCorpusRepository, InMemoryDocumentRepository};
+    use crate::infrastructure::tokenizer::SimpleTokenizer;
+
+    type Api = HttpApi<
+        DocumentServiceImpl<InMemoryDocumentRepository, SimpleTokenizer>,
+        CorpusServiceImpl<InMemoryCorpusRepository, InMemoryDocumentRepository, DocumentServiceImpl<InMemoryDocumentRepository, SimpleTokenizer>>,
+        SearchServiceImpl<InMemoryCorpusRepository, SimpleTokenizer>,
+    >;
+
+    fn create_api() -> Api {
+        let document_repository = Arc::new(InMemoryDocumentRepository::new());
+        let corpus_repository = Arc::new(InMemoryCorpusRepository::new());
+        let tokenizer = Arc::new(SimpleTokenizer::new());
+        let document_service = Arc::new(DocumentServiceImpl::new(document_repository.clone(), tokenizer.clone()));
+        let corpus_service = Arc::new(CorpusServiceImpl::new(corpus_repository.clone(), document_repository, document_service.clone()));
+        let search_service = Arc::new(SearchServiceImpl::new(corpus_repository, tokenizer, TfIdf::default()));
+        HttpApi::new(document_service, corpus_service, search_service)
+    }
+
+    #[test]
+    fn test_document_and_corpus_flow() {
+        let api = create_api();
+
+        let body = CreateDocumentRequest { id: "doc1".into(), content: "rust is safe".into(), title: Some("Rust".into()) };
+        let response = api.create_document(body).unwrap();
+        assert_eq!(response.status(), 201);
+        assert_eq!(response.body().unwrap()["title"], "Rust");
+        for (id, content) in [("doc2", "python is popular"), ("doc3", "go is simple")] {
+            let body = CreateDocumentRequest { id: id.into(), content: content.into(), title: None };
+            assert_eq!(api.create_document(body).unwrap().status(), 201);
+        }
+
+        let body = CreateCorpusRequest { id: "c1".into(), name: "Languages".into(), description: None };
+        assert_eq!(api.create_corpus(body).unwrap().status(), 201);
+        for id in ["doc1", "doc2", "doc3"] {
+            assert_eq!(api.add_document("c1", AddDocumentRequest { document_id: id.into() }).unwrap().status(), 200);
+        }
+        assert_eq!(api.build_index("c1").unwrap().body().unwrap()["indexed"], true);
+
+        let response = api.search("c1", SearchQuery { q: Some("safe".into()), ..SearchQuery::default() }).unwrap();
+        assert_eq!(response.body().unwrap()[0]["document_id"], "doc1");
+
+        let body = UpdateDocumentRequest { content: Some("python is dynamic".into()), title: None };
+        assert_eq!(api.update_document("doc2", body).unwrap().body().unwrap()["content"], "python is dynamic");
+        assert_eq!(api.delete_document("doc3").unwrap().status(), 204);
+        let response = api.list_documents(PageQuery::default()).unwrap();
+        assert_eq!(response.body().unwrap().as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_pagination() {
+        let api = create_api();
+        for (id, content) in [("doc1", "rust is fast"), ("doc2", "rust is safe"), ("doc3", "go is simple")] {
+            api.create_document(CreateDocumentRequest { id: id.into(), content: content.into(), title: None }).unwrap();
+        }
+        api.create_corpus(CreateCorpusRequest { id: "c1".into(), name: "Rust".into(), description: None }).unwrap();
+        for id in ["doc1", "doc2", "doc3"] {
+            api.add_document("c1", AddDocumentRequest { document_id: id.into() }).unwrap();
+        }
+        api.build_index("c1").unwrap();
+
+        let ids = |response: HttpResponse, key: &str| -> Vec<String> {
+            response.body().unwrap().as_array().unwrap().iter().map(|item| item[key].as_str().unwrap().to_string()).collect()
+        };
+        let page = PageQuery { offset: Some(1), limit: Some(1) };
+        assert_eq!(ids(api.list_documents(page).unwrap(), "id"), ["doc2"]);
+        assert_eq!(ids(api.list_documents(PageQuery { offset: Some(2), limit: None }).unwrap(), "id"), ["doc3"]);
+        assert_eq!(ids(api.list_corpus_documents("c1", PageQuery { offset: None, limit: Some(2) }).unwrap(), "id").len(), 2);
+        assert!(ids(api.list_corpora(PageQuery { offset: Some(1), limit: None }).unwrap(), "id").is_empty());
+
+        let search = SearchQuery { q: Some("fast safe".into()), offset: None, limit: None };
+        assert_eq!(ids(api.search("c1", search.clone()).unwrap(), "document_id").len(), 2);
+        assert_eq!(ids(api.search("c1", SearchQuery { limit: Some(1), ..search }).unwrap(), "document_id").len(), 1);
+    }
+
+    #[test]
+    fn test_errors() {
+        let api = create_api();
+
+        assert_eq!(Api::respond(api.get_document("missing")).status(), 404);
+
+        api.create_corpus(CreateCorpusRequest { id: "c1".into(), name: "Empty".into(), description: None }).unwrap();
+        assert_eq!(Api::respond(api.search("c1", SearchQuery::default())).status(), 400);
+        let response = Api::respond(api.create_corpus(CreateCorpusRequest { id: "c1".into(), name: "Again".into(), description: None }));
+        assert_eq!(response.status(), 400);
+        assert!(response.body().unwrap()["error"].as_str().unwrap().contains("already exists"));
+    }
+
+    /// Send one request over a fresh connection and return the status code and body
+    fn send(address: std::net::SocketAddr, request: &str) -> (u16, String) {
+        use std::io::{Read, Write};
+
+        let mut stream = std::net::TcpStream::connect(address).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let status = response[9..12].parse().unwrap();
+        let body = response.split_once("\r\n\r\n").map_or("", |(_, body)| body).to_string();
+        (status, body)
+    }
+
+    fn json_request(method: &str, target: &str, body: &str) -> String {
+        format!(
+            "{} {} HTTP/1.1\r\nHost: x\r\nConnection: close\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            method, target, body.len(), body
+        )
+    }
+
+    #[tokio::test]
+    async fn test_routes() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(crate::interfaces::http::serve(Arc::new(create_api()), listener));
+
+        tokio::task::spawn_blocking(move || {
+            let (status, _) = send(address, &json_request("POST", "/documents", r#"{"id": "a/b c", "content": "rust is fast"}"#));
+            assert_eq!(status, 201);
+            let (status, body) = send(address, &json_request("GET", "/documents/a%2Fb%20c", ""));
+            assert_eq!(status, 200);
+            assert!(body.contains("a/b c"));
+
+            for (id, content) in [("go", "go is simple"), ("py", "python is popular")] {
+                let body = serde_json::json!({ "id": id, "content": content }).to_string();
+                assert_eq!(send(address, &json_request("POST", "/documents", &body)).0, 201);
+            }
+            assert_eq!(send(address, &json_request("POST", "/corpora", r#"{"id": "c1", "name": "Languages"}"#)).0, 201);
+            for id in ["a/b c", "go", "py"] {
+                let body = serde_json::json!({ "document_id": id }).to_string();
+                assert_eq!(send(address, &json_request("POST", "/corpora/c1/documents", &body)).0, 200);
+            }
+            assert_eq!(send(address, &json_request("POST", "/corpora/c1/index", "")).0, 200);
+            let (status, body) = send(address, &json_request("GET", "/corpora/c1/search?q=fast&limit=5", ""));
+            assert_eq!(status, 200);
+            assert!(body.contains("a/b c"));
+            assert_eq!(send(address, &json_request("GET", "/documents?offset=3", "")).1, "[]");
+            assert_eq!(send(address, &json_request("DELETE", "/corpora/c1/documents/a%2Fb%20c", "")).0, 200);
+
+            assert_eq!(send(address, &json_request("GET", "/documents?limit=many", "")).0, 400);
+            assert_eq!(send(address, &json_request("GET", "/corpora/c1/search", "")).0, 400);
+            assert_eq!(send(address, &json_request("POST", "/documents", "not json")).0, 400);
+            assert_eq!(send(address, &json_request("PATCH", "/documents", "")).0, 405);
+            assert_eq!(send(address, &json_request("GET", "/nowhere", "")).0, 404);
+
+            // Only a body over the size limit is answered with 413
+            let oversized = format!(
+                "POST /documents HTTP/1.1\r\nHost: x\r\nConnection: close\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n",
+                crate::interfaces::http::MAX_BODY_SIZE + 1
+            );
+            let (status, body) = send(address, &oversized);
+            assert_eq!(status, 413);
+            assert!(body.contains("exceeds"));
+        }).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_serve() {
+        use std::io::{BufRead, BufReader, Read, Write};
+        use std::net::TcpStream;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(crate::interfaces::http::serve(Arc::new(create_api()), listener));
+
+        tokio::task::spawn_blocking(move || {
+            // Both requests share one kept-alive connection, and the second sends a chunked body
+            let mut stream = BufReader::new(TcpStream::connect(address).unwrap());
+            let body = r#"{"id": "doc1", "content": "rust"}"#;
+            let requests = [
+                "GET /documents/missing%20doc HTTP/1.1\r\nHost: x\r\n\r\n".to_string(),
+                format!("POST /documents HTTP/1.1\r\nHost: x\r\nContent-Type: application/json\r\nTransfer-Encoding: chunked\r\n\r\n{:x}\r\n{}\r\n0\r\n\r\n", body.len(), body),
+            ];
+            for (request, status, expected) in [(&requests[0], "404 Not Found", "missing doc"), (&requests[1], "201 Created", "doc1")] {
+                stream.get_mut().write_all(request.as_bytes()).unwrap();
+                let mut line = String::new();
+                stream.read_line(&mut line).unwrap();
+                assert!(line.starts_with(&format!("HTTP/1.1 {}", status)), "{}", line);
+
+                let mut length = 0;
+                loop {
+                    line.clear();
+                    stream.read_line(&mut line).unwrap();
+                    if line == "\r\n" {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') && name.eq_ignore_ascii_case("content-length") {
+                        length = value.trim().parse().unwrap();
+                    }
+                }
+                let mut response = vec![0; length];
+                stream.read_exact(&mut response).unwrap();
+                assert!(String::from_utf8(response).unwrap().contains(expected));
+            }
+        }).await.unwrap();
+    }
+}
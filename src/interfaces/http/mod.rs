@@ -0,0 +1,285 @@
+// src/interfaces/http/mod.rs
+
+//! JSON-over-HTTP interface to the application services.
+//!
+//! `HttpApi` answers each endpoint through the services and can be tested
+//! without a socket; `router` routes requests to it with axum's `Path`,
+//! `Query` and JSON body extractors, and `serve` runs that on a tokio
+//! listener. Hyper handles the HTTP/1.1 framing, including chunked bodies and
+//! keep-alive, while the blocking services are called off the async runtime.
+//! Bodies are bounded in size and in how long they take to arrive.
+
+mod api;
+
+pub use api::{
+    AddDocumentRequest, CorpusResponse, CreateCorpusRequest, CreateDocumentRequest, DocumentResponse,
+    ErrorResponse, HttpApi, PageQuery, SearchHitResponse, SearchQuery, UpdateDocumentRequest,
+};
+
+use std::marker::PhantomData;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::rejection::{JsonRejection, QueryRejection};
+use axum::extract::{DefaultBodyLimit, FromRequest, Path, Query, Request, State};
+use axum::http::{header, StatusCode, Uri};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{delete, get, post};
+use axum::{Json, Router};
+use serde::de::DeserializeOwned;
+use tokio::net::TcpListener;
+
+use crate::application::{ApplicationResult, CorpusService, DocumentService, SearchService};
+
+/// Largest request body accepted, in bytes
+pub const MAX_BODY_SIZE: usize = 16 * 1024 * 1024;
+
+/// How long a client may take to send a request body
+pub const BODY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// An HTTP response with a JSON body
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpResponse {
+    status: u16,
+    body: Option<serde_json::Value>,
+}
+
+impl HttpResponse {
+    /// Create a response with a JSON body
+    pub fn json(status: u16, body: serde_json::Value) -> Self {
+        Self { status, body: Some(body) }
+    }
+
+    /// Create a response without a body
+    pub fn empty(status: u16) -> Self {
+        Self { status, body: None }
+    }
+
+    /// Get the status code
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// Get the JSON body, if any
+    pub fn body(&self) -> Option<&serde_json::Value> {
+        self.body.as_ref()
+    }
+}
+
+impl IntoResponse for HttpResponse {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        match self.body.as_ref().map(serde_json::to_vec) {
+            None => status.into_response(),
+            Some(Ok(body)) => (status, [(header::CONTENT_TYPE, "application/json")], body).into_response(),
+            Some(Err(e)) => (StatusCode::INTERNAL_SERVER_ERROR, format!("Error serializing response: {}", e)).into_response(),
+        }
+    }
+}
+
+/// Build an axum router that sends each endpoint of the API to its `HttpApi` method
+pub fn router<DS, CS, SS>(api: Arc<HttpApi<DS, CS, SS>>) -> Router
+where
+    DS: DocumentService + Send + Sync + 'static,
+    CS: CorpusService + Send + Sync + 'static,
+    SS: SearchService + Send + Sync + 'static,
+{
+    Handlers::<DS, CS, SS>::router().with_state(api)
+}
+
+/// Serve the API over HTTP on a listener until the server fails
+pub async fn serve<DS, CS, SS>(api: Arc<HttpApi<DS, CS, SS>>, listener: TcpListener) -> std::io::Result<()>
+where
+    DS: DocumentService + Send + Sync + 'static,
+    CS: CorpusService + Send + Sync + 'static,
+    SS: SearchService + Send + Sync + 'static,
+{
+    axum::serve(listener, router(api)).await
+}
+
+/// Bind an address and serve the API over HTTP on it
+pub async fn serve_on<DS, CS, SS>(api: Arc<HttpApi<DS, CS, SS>>, address: SocketAddr) -> std::io::Result<()>
+where
+    DS: DocumentService + Send + Sync + 'static,
+    CS: CorpusService + Send + Sync + 'static,
+    SS: SearchService + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind(address).await?;
+    serve(api, listener).await
+}
+
+/// A JSON request body, read within `BODY_TIMEOUT`
+///
+/// Rejections are answered like API errors: 413 for a body over
+/// `MAX_BODY_SIZE`, 408 when it arrives too slowly, and axum's status, such as
+/// 400 or 415, when it cannot be read or is not the expected JSON.
+struct JsonBody<T>(T);
+
+impl<S, T> FromRequest<S> for JsonBody<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned,
+{
+    type Rejection = HttpResponse;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let too_large = || api::error_response(413, format!("Request body exceeds {} bytes", MAX_BODY_SIZE));
+
+        // A declared length over the limit is refused before waiting for the body
+        let declared = request.headers().get(header::CONTENT_LENGTH)
+            .and_then(|length| length.to_str().ok()?.parse::<usize>().ok());
+        if declared.is_some_and(|length| length > MAX_BODY_SIZE) {
+            return Err(too_large());
+        }
+
+        match tokio::time::timeout(BODY_TIMEOUT, Json::<T>::from_request(request, state)).await {
+            Ok(Ok(Json(body))) => Ok(JsonBody(body)),
+            Ok(Err(JsonRejection::BytesRejection(rejection))) if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE => {
+                Err(too_large())
+            },
+            Ok(Err(rejection)) => Err(api::error_response(rejection.status().as_u16(), rejection.body_text())),
+            Err(_) => Err(api::error_response(408, "Timed out reading the request body".to_string())),
+        }
+    }
+}
+
+/// Take a parsed query string, or answer its rejection like an API error
+fn query<T>(query: Result<Query<T>, QueryRejection>) -> Result<T, HttpResponse> {
+    query
+        .map(|Query(query)| query)
+        .map_err(|rejection| api::error_response(rejection.status().as_u16(), rejection.body_text()))
+}
+
+/// The route handlers, each calling one `HttpApi` method on a blocking thread
+struct Handlers<DS, CS, SS>(PhantomData<(DS, CS, SS)>);
+
+impl<DS, CS, SS> Handlers<DS, CS, SS>
+where
+    DS: DocumentService + Send + Sync + 'static,
+    CS: CorpusService + Send + Sync + 'static,
+    SS: SearchService + Send + Sync + 'static,
+{
+    fn router() -> Router<Arc<HttpApi<DS, CS, SS>>> {
+        Router::new()
+            .route("/documents", get(Self::list_documents).post(Self::create_document))
+            .route("/documents/{id}", get(Self::get_document).put(Self::update_document).delete(Self::delete_document))
+            .route("/corpora", get(Self::list_corpora).post(Self::create_corpus))
+            .route("/corpora/{id}", get(Self::get_corpus).delete(Self::delete_corpus))
+            .route("/corpora/{id}/documents", get(Self::list_corpus_documents).post(Self::add_document))
+            .route("/corpora/{id}/documents/{document_id}", delete(Self::remove_document))
+            .route("/corpora/{id}/index", post(Self::build_index))
+            .route("/corpora/{id}/search", get(Self::search))
+            .method_not_allowed_fallback(method_not_allowed)
+            .fallback(not_found)
+            .layer(DefaultBodyLimit::max(MAX_BODY_SIZE))
+    }
+
+    /// Run an endpoint on a blocking thread, since the services block
+    async fn call<F>(api: Arc<HttpApi<DS, CS, SS>>, endpoint: F) -> HttpResponse
+    where
+        F: FnOnce(&HttpApi<DS, CS, SS>) -> ApplicationResult<HttpResponse> + Send + 'static,
+    {
+        tokio::task::spawn_blocking(move || HttpApi::<DS, CS, SS>::respond(endpoint(&api))).await
+            .unwrap_or_else(|e| api::error_response(500, format!("Request handler failed: {}", e)))
+    }
+
+    async fn list_documents(
+        State(api): State<Arc<HttpApi<DS, CS, SS>>>,
+        page: Result<Query<PageQuery>, QueryRejection>,
+    ) -> Result<HttpResponse, HttpResponse> {
+        let page = query(page)?;
+        Ok(Self::call(api, move |api| api.list_documents(page)).await)
+    }
+
+    async fn create_document(
+        State(api): State<Arc<HttpApi<DS, CS, SS>>>,
+        JsonBody(body): JsonBody<CreateDocumentRequest>,
+    ) -> HttpResponse {
+        Self::call(api, move |api| api.create_document(body)).await
+    }
+
+    async fn get_document(State(api): State<Arc<HttpApi<DS, CS, SS>>>, Path(id): Path<String>) -> HttpResponse {
+        Self::call(api, move |api| api.get_document(&id)).await
+    }
+
+    async fn update_document(
+        State(api): State<Arc<HttpApi<DS, CS, SS>>>,
+        Path(id): Path<String>,
+        JsonBody(body): JsonBody<UpdateDocumentRequest>,
+    ) -> HttpResponse {
+        Self::call(api, move |api| api.update_document(&id, body)).await
+    }
+
+    async fn delete_document(State(api): State<Arc<HttpApi<DS, CS, SS>>>, Path(id): Path<String>) -> HttpResponse {
+        Self::call(api, move |api| api.delete_document(&id)).await
+    }
+
+    async fn list_corpora(
+        State(api): State<Arc<HttpApi<DS, CS, SS>>>,
+        page: Result<Query<PageQuery>, QueryRejection>,
+    ) -> Result<HttpResponse, HttpResponse> {
+        let page = query(page)?;
+        Ok(Self::call(api, move |api| api.list_corpora(page)).await)
+    }
+
+    async fn create_corpus(
+        State(api): State<Arc<HttpApi<DS, CS, SS>>>,
+        JsonBody(body): JsonBody<CreateCorpusRequest>,
+    ) -> HttpResponse {
+        Self::call(api, move |api| api.create_corpus(body)).await
+    }
+
+    async fn get_corpus(State(api): State<Arc<HttpApi<DS, CS, SS>>>, Path(id): Path<String>) -> HttpResponse {
+        Self::call(api, move |api| api.get_corpus(&id)).await
+    }
+
+    async fn delete_corpus(State(api): State<Arc<HttpApi<DS, CS, SS>>>, Path(id): Path<String>) -> HttpResponse {
+        Self::call(api, move |api| api.delete_corpus(&id)).await
+    }
+
+    async fn list_corpus_documents(
+        State(api): State<Arc<HttpApi<DS, CS, SS>>>,
+        Path(id): Path<String>,
+        page: Result<Query<PageQuery>, QueryRejection>,
+    ) -> Result<HttpResponse, HttpResponse> {
+        let page = query(page)?;
+        Ok(Self::call(api, move |api| api.list_corpus_documents(&id, page)).await)
+    }
+
+    async fn add_document(
+        State(api): State<Arc<HttpApi<DS, CS, SS>>>,
+        Path(id): Path<String>,
+        JsonBody(body): JsonBody<AddDocumentRequest>,
+    ) -> HttpResponse {
+        Self::call(api, move |api| api.add_document(&id, body)).await
+    }
+
+    async fn remove_document(
+        State(api): State<Arc<HttpApi<DS, CS, SS>>>,
+        Path((id, document_id)): Path<(String, String)>,
+    ) -> HttpResponse {
+        Self::call(api, move |api| api.remove_document(&id, &document_id)).await
+    }
+
+    async fn build_index(State(api): State<Arc<HttpApi<DS, CS, SS>>>, Path(id): Path<String>) -> HttpResponse {
+        Self::call(api, move |api| api.build_index(&id)).await
+    }
+
+    async fn search(
+        State(api): State<Arc<HttpApi<DS, CS, SS>>>,
+        Path(id): Path<String>,
+        search: Result<Query<SearchQuery>, QueryRejection>,
+    ) -> Result<HttpResponse, HttpResponse> {
+        let search = query(search)?;
+        Ok(Self::call(api, move |api| api.search(&id, search)).await)
+    }
+}
+
+async fn method_not_allowed(method: axum::http::Method, uri: Uri) -> HttpResponse {
+    api::error_response(405, format!("Method {} not allowed on {}", method, uri.path()))
+}
+
+async fn not_found(uri: Uri) -> HttpResponse {
+    api::error_response(404, format!("No route for {}", uri.path()))
+}
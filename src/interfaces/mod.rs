@@ -0,0 +1,9 @@
+// src/interfaces/mod.rs
+
+//! Interfaces layer module exposing the application services to the outside world.
+
+pub mod cli;
+#[cfg(feature = "http")]
+pub mod http;
+#[cfg(feature = "grpc")]
+pub mod grpc;
@@ -4,8 +4,8 @@ pub mod infrastructure;
 pub mod interfaces;
 
 /// Re-export commonly used types for convenience
-//pub use domain::{Document, Corpus, Term, TfIdf};
-//pub use application::{DocumentService, CorpusService, TfIdfService};
+pub use domain::{Document, Corpus, Term, TfIdf};
+pub use application::{DocumentService, CorpusService, TfIdfService, TfIdfEngine};
 
 /// Library version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
\ No newline at end of file
@@ -1,3 +1,36 @@
+#[cfg(feature = "subscriber")]
+use tf_idf_rs::infrastructure::telemetry;
+use tf_idf_rs::interfaces::cli::{self, Command};
+
 fn main() {
-    println!("Hello, world!");
+    let command = match Command::parse(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    // TFIDF_LOG=debug (or any tracing filter, e.g. tf_idf::search=trace) writes engine events to stderr
+    #[cfg(feature = "subscriber")]
+    if let Ok(filter) = std::env::var("TFIDF_LOG") {
+        match telemetry::stderr_subscriber(&filter) {
+            Ok(subscriber) => {
+                let _ = tracing::subscriber::set_global_default(subscriber);
+            },
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+    #[cfg(not(feature = "subscriber"))]
+    if std::env::var_os("TFIDF_LOG").is_some() {
+        eprintln!("TFIDF_LOG is ignored: tfidf was built without the `subscriber` feature");
+    }
+
+    match cli::execute(&command, &mut std::io::stdout()) {
+        Ok(code) => std::process::exit(code),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
 }